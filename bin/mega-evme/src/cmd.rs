@@ -26,6 +26,10 @@ pub enum Commands {
     Tx(crate::tx::Cmd),
     /// Replay a transaction from RPC
     Replay(crate::replay::Cmd),
+    /// Generate a devnet genesis file
+    Genesis(crate::genesis::Cmd),
+    /// Decode the storage variables of a contract
+    Storage(crate::storage::Cmd),
 }
 
 /// Error types for the main command system
@@ -34,7 +38,7 @@ pub enum Error {
     /// Custom error with static message
     #[error("Custom error: {0}")]
     Custom(&'static str),
-    /// Evme error (used by run, tx, and replay commands)
+    /// Evme error (used by run, tx, replay, genesis, and storage commands)
     #[error("{0}")]
     Evme(#[from] crate::common::EvmeError),
 }
@@ -58,6 +62,14 @@ impl MainCmd {
                 cmd.run().await?;
                 Ok(())
             }
+            Commands::Genesis(cmd) => {
+                cmd.run()?;
+                Ok(())
+            }
+            Commands::Storage(cmd) => {
+                cmd.run().await?;
+                Ok(())
+            }
         }
         .inspect_err(|e| {
             error!(err = ?e, "Error executing command");
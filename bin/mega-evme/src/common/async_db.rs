@@ -0,0 +1,421 @@
+//! Bridging of asynchronous remote state backends to the synchronous [`Database`] interface.
+//!
+//! The EVM reads state through the synchronous [`Database`] trait, while remote backends (RPC
+//! providers) are asynchronous. [`BufferedAsyncDb`] bridges the two without stalling the async
+//! runtime: the state a transaction is known to touch is fetched concurrently ahead of execution
+//! with [`BufferedAsyncDb::prefetch`], and the lookups that miss the prefetch buffer block the
+//! executing thread, never a runtime worker, for at most a configured timeout.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use alloy_primitives::{Address, B256, U256};
+use mega_evm::revm::{
+    database::{async_db::DatabaseAsyncRef, DBErrorMarker},
+    state::{AccountInfo, Bytecode},
+    Database, DatabaseRef,
+};
+use tokio::{
+    runtime::{Handle, RuntimeFlavor},
+    task::JoinSet,
+};
+use tracing::debug;
+
+/// An asynchronous state backend.
+///
+/// Implemented for every revm [`DatabaseAsyncRef`], such as the RPC-backed
+/// [`AlloyDB`](mega_evm::revm::database::AlloyDB).
+pub trait AsyncDatabase: Send + Sync {
+    /// The error of the backend.
+    type Error: std::error::Error + Send + 'static;
+
+    /// Gets the basic info of an account.
+    fn basic_async(
+        &self,
+        address: Address,
+    ) -> impl Future<Output = Result<Option<AccountInfo>, Self::Error>> + Send;
+
+    /// Gets a bytecode by its hash.
+    fn code_by_hash_async(
+        &self,
+        code_hash: B256,
+    ) -> impl Future<Output = Result<Bytecode, Self::Error>> + Send;
+
+    /// Gets a storage slot of an account.
+    fn storage_async(
+        &self,
+        address: Address,
+        index: U256,
+    ) -> impl Future<Output = Result<U256, Self::Error>> + Send;
+
+    /// Gets the hash of a block.
+    fn block_hash_async(
+        &self,
+        number: u64,
+    ) -> impl Future<Output = Result<B256, Self::Error>> + Send;
+}
+
+impl<T> AsyncDatabase for T
+where
+    T: DatabaseAsyncRef + Send + Sync,
+    T::Error: Send + 'static,
+{
+    type Error = T::Error;
+
+    fn basic_async(
+        &self,
+        address: Address,
+    ) -> impl Future<Output = Result<Option<AccountInfo>, Self::Error>> + Send {
+        self.basic_async_ref(address)
+    }
+
+    fn code_by_hash_async(
+        &self,
+        code_hash: B256,
+    ) -> impl Future<Output = Result<Bytecode, Self::Error>> + Send {
+        self.code_by_hash_async_ref(code_hash)
+    }
+
+    fn storage_async(
+        &self,
+        address: Address,
+        index: U256,
+    ) -> impl Future<Output = Result<U256, Self::Error>> + Send {
+        self.storage_async_ref(address, index)
+    }
+
+    fn block_hash_async(
+        &self,
+        number: u64,
+    ) -> impl Future<Output = Result<B256, Self::Error>> + Send {
+        self.block_hash_async_ref(number)
+    }
+}
+
+/// The error of a [`BufferedAsyncDb`] lookup.
+#[derive(Debug, thiserror::Error)]
+pub enum AsyncDbError<E> {
+    /// The backend failed.
+    #[error(transparent)]
+    Backend(E),
+    /// The backend did not answer in time.
+    #[error("remote state lookup timed out after {0:?}")]
+    Timeout(Duration),
+}
+
+impl<E> DBErrorMarker for AsyncDbError<E> {}
+
+/// The prefetched state not yet read by the EVM.
+#[derive(Debug, Default)]
+struct PrefetchBuffer {
+    accounts: HashMap<Address, Option<AccountInfo>>,
+    storage: HashMap<(Address, U256), U256>,
+}
+
+/// A prefetched value.
+enum Prefetched {
+    Account(Address, Option<AccountInfo>),
+    Storage(Address, U256, U256),
+}
+
+/// A synchronous [`Database`] over an [`AsyncDatabase`], with a prefetch buffer and bounded
+/// blocking lookups.
+///
+/// Every prefetched value is handed out once and then dropped from the buffer: the adapter is
+/// meant to sit under a caching layer such as [`CacheDB`](mega_evm::revm::database::CacheDB) or
+/// [`State`](mega_evm::revm::database::State), which keeps it from then on.
+#[derive(Debug)]
+pub struct BufferedAsyncDb<DB> {
+    db: Arc<DB>,
+    handle: Handle,
+    timeout: Duration,
+    max_in_flight: usize,
+    buffer: Mutex<PrefetchBuffer>,
+}
+
+impl<DB> BufferedAsyncDb<DB> {
+    /// The default bound on the time a lookup may block.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// The default number of concurrent requests issued by a prefetch.
+    pub const DEFAULT_MAX_IN_FLIGHT: usize = 16;
+
+    /// Wraps `db`, running its requests on the current Tokio runtime. Returns `None` outside a
+    /// runtime.
+    pub fn new(db: DB) -> Option<Self> {
+        let handle = Handle::try_current().ok()?;
+        Some(Self {
+            db: Arc::new(db),
+            handle,
+            timeout: Self::DEFAULT_TIMEOUT,
+            max_in_flight: Self::DEFAULT_MAX_IN_FLIGHT,
+            buffer: Mutex::default(),
+        })
+    }
+
+    /// Sets the bound on the time a lookup, or a single prefetched request, may take.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the number of concurrent requests issued by a prefetch. Zero is treated as one.
+    pub fn with_max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = max_in_flight.max(1);
+        self
+    }
+
+    /// Returns the number of prefetched values not yet read.
+    pub fn buffered_len(&self) -> usize {
+        let buffer = self.buffer.lock().expect("prefetch buffer lock poisoned");
+        buffer.accounts.len() + buffer.storage.len()
+    }
+}
+
+impl<DB: AsyncDatabase + 'static> BufferedAsyncDb<DB> {
+    /// Fetches the given accounts and storage slots concurrently into the prefetch buffer, and
+    /// returns the number of values buffered.
+    ///
+    /// Prefetching is best effort: a request that fails or times out is skipped, and the value is
+    /// fetched again, blocking, if the EVM reads it.
+    pub async fn prefetch(
+        &self,
+        accounts: impl IntoIterator<Item = Address>,
+        slots: impl IntoIterator<Item = (Address, U256)>,
+    ) -> usize {
+        let requests: Vec<_> = {
+            let buffer = self.buffer.lock().expect("prefetch buffer lock poisoned");
+            accounts
+                .into_iter()
+                .filter(|address| !buffer.accounts.contains_key(address))
+                .map(|address| (address, None))
+                .chain(
+                    slots
+                        .into_iter()
+                        .filter(|slot| !buffer.storage.contains_key(slot))
+                        .map(|(address, index)| (address, Some(index))),
+                )
+                .collect()
+        };
+
+        let mut tasks = JoinSet::new();
+        let mut buffered = 0;
+        for (address, index) in requests {
+            if tasks.len() >= self.max_in_flight {
+                buffered += self.buffer_prefetched(tasks.join_next().await);
+            }
+            let (db, timeout) = (Arc::clone(&self.db), self.timeout);
+            tasks.spawn(async move {
+                let fetched = async {
+                    Ok::<_, DB::Error>(match index {
+                        None => Prefetched::Account(address, db.basic_async(address).await?),
+                        Some(index) => Prefetched::Storage(
+                            address,
+                            index,
+                            db.storage_async(address, index).await?,
+                        ),
+                    })
+                };
+                tokio::time::timeout(timeout, fetched)
+                    .await
+                    .map_err(|_| AsyncDbError::Timeout(timeout))?
+                    .map_err(AsyncDbError::Backend)
+            });
+        }
+        while let Some(joined) = tasks.join_next().await {
+            buffered += self.buffer_prefetched(Some(joined));
+        }
+        buffered
+    }
+
+    /// Stores a completed prefetch request, and returns the number of values buffered.
+    fn buffer_prefetched(
+        &self,
+        joined: Option<Result<Result<Prefetched, AsyncDbError<DB::Error>>, tokio::task::JoinError>>,
+    ) -> usize {
+        let prefetched = match joined {
+            Some(Ok(Ok(prefetched))) => prefetched,
+            Some(Ok(Err(err))) => {
+                debug!(%err, "Prefetch request failed, the value will be fetched on demand");
+                return 0;
+            }
+            Some(Err(err)) => {
+                debug!(%err, "Prefetch task failed, the value will be fetched on demand");
+                return 0;
+            }
+            None => return 0,
+        };
+        let mut buffer = self.buffer.lock().expect("prefetch buffer lock poisoned");
+        match prefetched {
+            Prefetched::Account(address, info) => {
+                buffer.accounts.insert(address, info);
+            }
+            Prefetched::Storage(address, index, value) => {
+                buffer.storage.insert((address, index), value);
+            }
+        }
+        1
+    }
+}
+
+impl<DB: AsyncDatabase> BufferedAsyncDb<DB> {
+    /// Runs `fut` to completion on the runtime, blocking the current thread for at most the
+    /// timeout.
+    ///
+    /// On a multi-threaded runtime the current worker hands its other tasks over before blocking;
+    /// on a current-thread runtime the future is driven from a scoped thread, since the only
+    /// worker cannot both block and drive it.
+    fn block_on<T: Send>(
+        &self,
+        fut: impl Future<Output = Result<T, DB::Error>> + Send,
+    ) -> Result<T, AsyncDbError<DB::Error>> {
+        let timeout = self.timeout;
+        let fut = async { tokio::time::timeout(timeout, fut).await };
+        let result = match self.handle.runtime_flavor() {
+            RuntimeFlavor::CurrentThread => std::thread::scope(|scope| {
+                scope
+                    .spawn(|| self.handle.block_on(fut))
+                    .join()
+                    .expect("remote state lookup panicked")
+            }),
+            _ => tokio::task::block_in_place(|| self.handle.block_on(fut)),
+        };
+        result.map_err(|_| AsyncDbError::Timeout(timeout))?.map_err(AsyncDbError::Backend)
+    }
+}
+
+impl<DB: AsyncDatabase> DatabaseRef for BufferedAsyncDb<DB> {
+    type Error = AsyncDbError<DB::Error>;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let prefetched =
+            self.buffer.lock().expect("prefetch buffer lock poisoned").accounts.remove(&address);
+        match prefetched {
+            Some(info) => Ok(info),
+            None => self.block_on(self.db.basic_async(address)),
+        }
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.block_on(self.db.code_by_hash_async(code_hash))
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let prefetched = self
+            .buffer
+            .lock()
+            .expect("prefetch buffer lock poisoned")
+            .storage
+            .remove(&(address, index));
+        match prefetched {
+            Some(value) => Ok(value),
+            None => self.block_on(self.db.storage_async(address, index)),
+        }
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.block_on(self.db.block_hash_async(number))
+    }
+}
+
+impl<DB: AsyncDatabase> Database for BufferedAsyncDb<DB> {
+    type Error = AsyncDbError<DB::Error>;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.basic_ref(address)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.code_by_hash_ref(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.storage_ref(address, index)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.block_hash_ref(number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const ALICE: Address = Address::with_last_byte(1);
+
+    /// A backend answering every lookup after `delay`, counting the requests it served.
+    #[derive(Debug, Default)]
+    struct SlowBackend {
+        delay: Duration,
+        requests: AtomicUsize,
+    }
+
+    impl SlowBackend {
+        async fn serve<T>(&self, value: T) -> Result<T, std::io::Error> {
+            tokio::time::sleep(self.delay).await;
+            self.requests.fetch_add(1, Ordering::Relaxed);
+            Ok(value)
+        }
+    }
+
+    impl AsyncDatabase for SlowBackend {
+        type Error = std::io::Error;
+
+        async fn basic_async(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            let nonce = address.0[19] as u64;
+            self.serve(Some(AccountInfo { nonce, ..Default::default() })).await
+        }
+
+        async fn code_by_hash_async(&self, _code_hash: B256) -> Result<Bytecode, Self::Error> {
+            self.serve(Bytecode::default()).await
+        }
+
+        async fn storage_async(&self, _address: Address, index: U256) -> Result<U256, Self::Error> {
+            self.serve(index + U256::from(1)).await
+        }
+
+        async fn block_hash_async(&self, number: u64) -> Result<B256, Self::Error> {
+            self.serve(B256::with_last_byte(number as u8)).await
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_prefetched_values_are_served_without_requests() {
+        let db = BufferedAsyncDb::new(SlowBackend::default()).unwrap();
+        let slots = (0..4).map(|index| (ALICE, U256::from(index)));
+        assert_eq!(db.prefetch([ALICE], slots).await, 5);
+        assert_eq!(db.db.requests.load(Ordering::Relaxed), 5);
+
+        assert_eq!(db.basic_ref(ALICE).unwrap().unwrap().nonce, 1);
+        assert_eq!(db.storage_ref(ALICE, U256::from(3)).unwrap(), U256::from(4));
+        assert_eq!(db.buffered_len(), 3);
+        assert_eq!(db.db.requests.load(Ordering::Relaxed), 5);
+
+        // A value is handed out once; reading it again goes to the backend.
+        assert_eq!(db.basic_ref(ALICE).unwrap().unwrap().nonce, 1);
+        assert_eq!(db.db.requests.load(Ordering::Relaxed), 6);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_blocking_lookups_work_on_a_current_thread_runtime() {
+        let mut db = BufferedAsyncDb::new(SlowBackend::default()).unwrap();
+        assert_eq!(db.block_hash(7).unwrap(), B256::with_last_byte(7));
+        assert_eq!(db.storage(ALICE, U256::ZERO).unwrap(), U256::from(1));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_lookups_are_bounded_by_the_timeout() {
+        let backend = SlowBackend { delay: Duration::from_secs(60), ..Default::default() };
+        let db = BufferedAsyncDb::new(backend).unwrap().with_timeout(Duration::from_millis(10));
+        assert!(matches!(db.basic_ref(ALICE), Err(AsyncDbError::Timeout(_))));
+        // A timed-out prefetch is skipped.
+        assert_eq!(db.prefetch([ALICE], []).await, 0);
+        assert_eq!(db.buffered_len(), 0);
+    }
+}
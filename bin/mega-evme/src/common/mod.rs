@@ -1,3 +1,4 @@
+mod async_db;
 mod env;
 mod error;
 mod hardfork;
@@ -10,6 +11,7 @@ mod trace;
 mod tx;
 mod tx_override;
 
+pub use async_db::*;
 pub use env::*;
 pub use error::*;
 pub use hardfork::*;
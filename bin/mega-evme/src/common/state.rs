@@ -9,14 +9,14 @@ use clap::Parser;
 use op_alloy_network::Optimism;
 
 use mega_evm::revm::{
-    database::{AlloyDB, CacheDB, EmptyDB, WrapDatabaseAsync},
+    database::{AlloyDB, CacheDB, EmptyDB},
     primitives::HashMap,
     state::{Account, AccountInfo, Bytecode, EvmState, EvmStorageSlot},
     Database, DatabaseRef,
 };
 use tracing::{debug, info, trace};
 
-use super::{EvmeError, Result, RpcCacheStore};
+use super::{BufferedAsyncDb, EvmeError, Result, RpcCacheStore};
 
 /// Pre-execution state configuration arguments
 #[derive(Parser, Debug, Clone)]
@@ -301,6 +301,7 @@ impl PreStateArgs {
                 rpc_args.build_provider().await?;
             let state =
                 EvmeState::new_forked(provider, self.fork_block, prestate, block_hashes).await?;
+            state.prefetch([*sender], []).await;
             Ok((state, cache_store))
         } else {
             debug!("Creating local state");
@@ -453,7 +454,7 @@ where
     /// Local state with no RPC backend
     Empty(EmptyDB),
     /// Forked state from RPC
-    Forked(Box<CacheDB<WrapDatabaseAsync<AlloyDB<N, P>>>>),
+    Forked(Box<CacheDB<BufferedAsyncDb<AlloyDB<N, P>>>>),
 }
 
 /// State database that can be backed by either [`EmptyDB`] or [`AlloyDB`] (forked from RPC)
@@ -607,8 +608,8 @@ where
         let alloy_db = AlloyDB::new(provider, block_num.into());
 
         // Wrap the AlloyDB for synchronous access with the runtime
-        let wrapped_db =
-            WrapDatabaseAsync::new(alloy_db).expect("Failed to create wrapped database");
+        let wrapped_db = BufferedAsyncDb::new(alloy_db)
+            .ok_or_else(|| EvmeError::Other("forked state requires a Tokio runtime".to_string()))?;
 
         // Wrap with CacheDB to enable mutable Database trait
         let db = CacheDB::new(wrapped_db);
@@ -625,6 +626,40 @@ where
     }
 }
 
+impl<N, P> EvmeState<N, P>
+where
+    N: Network + 'static,
+    P: Provider<N> + 'static,
+{
+    /// Fetches the given accounts and storage slots from the forked state ahead of execution,
+    /// concurrently, so the execution does not block on them one by one. Overridden and already
+    /// cached values are skipped. A no-op for a local state.
+    pub async fn prefetch(
+        &self,
+        accounts: impl IntoIterator<Item = Address>,
+        slots: impl IntoIterator<Item = (Address, U256)>,
+    ) {
+        let EvmeBackend::Forked(db) = &self.backend else { return };
+        let accounts = accounts.into_iter().filter(|address| {
+            !self.prestate.contains_key(address) && !db.cache.accounts.contains_key(address)
+        });
+        let slots = slots.into_iter().filter(|(address, index)| {
+            let overridden = self
+                .prestate
+                .get(address)
+                .is_some_and(|account| account.storage.contains_key(index));
+            let cached = db
+                .cache
+                .accounts
+                .get(address)
+                .is_some_and(|account| account.storage.contains_key(index));
+            !overridden && !cached
+        });
+        let buffered = db.db.prefetch(accounts, slots).await;
+        debug!(buffered, "Prefetched forked state");
+    }
+}
+
 impl<N, P> Database for EvmeState<N, P>
 where
     N: Network,
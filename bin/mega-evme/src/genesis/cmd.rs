@@ -0,0 +1,269 @@
+use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
+
+use alloy_primitives::{address, Address, Bytes, B256, U256};
+use clap::Parser;
+use mega_evm::{
+    alloy_hardforks::ForkCondition,
+    flat_system_contract_specs,
+    revm::state::{Account, AccountInfo, Bytecode, EvmStorageSlot},
+    MegaHardfork, MegaHardforks,
+};
+use serde_json::{Map, Value};
+use tracing::{debug, info};
+
+use super::{parse_ether_value, AccountState, ChainArgs, FixedHardfork, GenesisError, Result};
+
+/// The accounts of the well-known development mnemonic
+/// (`test test test test test test test test test test test junk`), in derivation order.
+pub const DEV_ACCOUNTS: [Address; 10] = [
+    address!("f39fd6e51aad88f6f4ce6ab8827279cfffb92266"),
+    address!("70997970c51812dc3a010c7d01b50e0d17dc79c8"),
+    address!("3c44cdddb6a900fa2b585dd299e03d12fa4293bc"),
+    address!("90f79bf6eb2c4f870365e785982e1f101e93b906"),
+    address!("15d34aaf54267db7d7c367839aaf71a00a2c6a65"),
+    address!("9965507d1a55bcc2695c58ba16fb37d819b0a4dc"),
+    address!("976ea74026e726554db657fa54763abd0c3a0aa9"),
+    address!("14dc79964da2c08b23698b3d3cc7ca32193d9955"),
+    address!("23618e81e3f5cdf7f54c3d65f7fbc0abf5b21e8f"),
+    address!("a0ee7a142d267c1f36714e4a8f75612f20a79720"),
+];
+
+/// The Ethereum and Optimism forks of the chain config, all active at genesis like in
+/// [`FixedHardfork`].
+const BLOCK_FORKS: [&str; 12] = [
+    "homesteadBlock",
+    "eip150Block",
+    "eip155Block",
+    "eip158Block",
+    "byzantiumBlock",
+    "constantinopleBlock",
+    "petersburgBlock",
+    "istanbulBlock",
+    "berlinBlock",
+    "londonBlock",
+    "mergeNetsplitBlock",
+    "bedrockBlock",
+];
+const TIME_FORKS: [&str; 10] = [
+    "shanghaiTime",
+    "cancunTime",
+    "pragueTime",
+    "regolithTime",
+    "canyonTime",
+    "ecotoneTime",
+    "fjordTime",
+    "graniteTime",
+    "holoceneTime",
+    "isthmusTime",
+];
+
+/// The `MegaETH` forks of the chain config, active at genesis up to `--spec`.
+const MEGA_FORKS: [MegaHardfork; 10] = [
+    MegaHardfork::MiniRex,
+    MegaHardfork::MiniRex1,
+    MegaHardfork::MiniRex2,
+    MegaHardfork::Rex,
+    MegaHardfork::Rex1,
+    MegaHardfork::Rex2,
+    MegaHardfork::Rex3,
+    MegaHardfork::Rex4,
+    MegaHardfork::Rex5,
+    MegaHardfork::Rex6,
+];
+
+/// Generate a genesis file for a devnet
+#[derive(Parser, Debug)]
+pub struct Cmd {
+    /// Chain configuration. Every fork up to `--spec` is active at genesis.
+    #[command(flatten)]
+    pub chain: ChainArgs,
+
+    /// Number of development accounts to fund, taken in order from the well-known
+    /// development mnemonic (at most 10)
+    #[arg(long = "dev-accounts", default_value = "10")]
+    pub dev_accounts: usize,
+
+    /// Balance of each development account.
+    /// VALUE can be: plain number (wei), or number with suffix (ether, gwei, wei).
+    #[arg(long = "dev-balance", default_value = "10000ether")]
+    pub dev_balance: String,
+
+    /// Fund additional accounts. Each entry format: `ADDRESS=VALUE`
+    /// VALUE can be: plain number (wei), or number with suffix (ether, gwei, wei).
+    /// Examples: `--account 0x1234=100ether`
+    /// Can be repeated for multiple addresses.
+    #[arg(long = "account")]
+    pub accounts: Vec<String>,
+
+    /// Genesis block timestamp
+    #[arg(long = "timestamp", default_value = "0")]
+    pub timestamp: u64,
+
+    /// Genesis block gas limit
+    #[arg(long = "gaslimit", visible_aliases = ["gas-limit"], default_value = "10000000000")]
+    pub gas_limit: u64,
+
+    /// Genesis block base fee per gas (EIP-1559)
+    #[arg(long = "basefee", visible_aliases = ["base-fee"], default_value = "0")]
+    pub base_fee: u64,
+
+    /// Output file for the genesis (if not specified, prints to console)
+    #[arg(long = "output", short = 'o')]
+    pub output: Option<PathBuf>,
+}
+
+/// A devnet genesis, in the format of go-ethereum's `genesis.json`.
+///
+/// The `alloc` uses the same account format as the `--prestate` file, so it can also be passed
+/// to `run` and `tx`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Genesis {
+    /// Chain configuration: chain ID and fork activations
+    pub config: Map<String, Value>,
+    /// Block nonce
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// Block timestamp
+    #[serde(with = "alloy_serde::quantity")]
+    pub timestamp: u64,
+    /// Block extra data
+    pub extra_data: Bytes,
+    /// Block gas limit
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_limit: u64,
+    /// Block difficulty
+    pub difficulty: U256,
+    /// Block mix hash
+    pub mix_hash: B256,
+    /// Block coinbase
+    pub coinbase: Address,
+    /// Block base fee per gas
+    #[serde(with = "alloy_serde::quantity")]
+    pub base_fee_per_gas: u64,
+    /// Genesis accounts (sorted by address for deterministic output)
+    pub alloc: BTreeMap<Address, AccountState>,
+}
+
+impl Cmd {
+    /// Execute the genesis command
+    pub fn run(&self) -> Result<()> {
+        let genesis = self.build_genesis()?;
+        let genesis_json = serde_json::to_string_pretty(&genesis)
+            .map_err(|e| GenesisError::Other(format!("Failed to serialize genesis: {}", e)))?;
+
+        if let Some(ref output_file) = self.output {
+            debug!(output_file = ?output_file, "Writing genesis to file");
+            std::fs::write(output_file, genesis_json)?;
+            println!("Genesis written to: {}", output_file.display());
+        } else {
+            println!("{}", genesis_json);
+        }
+        Ok(())
+    }
+
+    /// Builds the genesis: the system contracts active under `--spec`, the development accounts
+    /// and the additional accounts.
+    ///
+    /// The `SequencerRegistry` is not predeployed: the block executor deploys it in the
+    /// pre-execution changes of the first `Rex5` block, seeding its storage from the hardfork
+    /// config.
+    pub fn build_genesis(&self) -> Result<Genesis> {
+        let spec = self.chain.spec_id()?;
+        if self.dev_accounts > DEV_ACCOUNTS.len() {
+            return Err(GenesisError::InvalidInput(format!(
+                "At most {} development accounts are available, got {}",
+                DEV_ACCOUNTS.len(),
+                self.dev_accounts
+            )));
+        }
+
+        let mut alloc = BTreeMap::new();
+
+        // System contracts come from the canonical registry shared with the block executor,
+        // resolved at the genesis timestamp. Seeded storage is written to the alloc so the
+        // genesis state matches a fresh deploy.
+        let hardforks = FixedHardfork::new(spec);
+        for contract in flat_system_contract_specs(hardforks, self.timestamp) {
+            debug!(address = %contract.address, "Predeploying system contract");
+            let mut info = AccountInfo::default();
+            info.set_code(Bytecode::new_raw(contract.code));
+            let storage = contract
+                .seed
+                .into_iter()
+                .map(|(slot, value)| (slot, EvmStorageSlot::new(value, 0)));
+            let account = Account::from(info).with_storage(storage);
+            alloc.insert(contract.address, AccountState::from_account(account));
+        }
+
+        // Development accounts, then the additional accounts (which override them).
+        let dev_balance = parse_ether_value(&self.dev_balance)?;
+        let funded = DEV_ACCOUNTS[..self.dev_accounts]
+            .iter()
+            .map(|address| (*address, dev_balance))
+            .chain(self.parse_accounts()?);
+        for (address, balance) in funded {
+            info!(address = %address, balance = %balance, "Funding account");
+            let info = AccountInfo { balance, ..Default::default() };
+            alloc.insert(address, AccountState::from_account(Account::from(info)));
+        }
+
+        Ok(Genesis {
+            config: self.chain_config(&hardforks),
+            nonce: 0,
+            timestamp: self.timestamp,
+            extra_data: Bytes::new(),
+            gas_limit: self.gas_limit,
+            difficulty: U256::ZERO,
+            mix_hash: B256::ZERO,
+            coinbase: Address::ZERO,
+            base_fee_per_gas: self.base_fee,
+            alloc,
+        })
+    }
+
+    /// Builds the chain config, with every `MegaETH` fork up to `--spec` active at genesis.
+    fn chain_config(&self, hardforks: &FixedHardfork) -> Map<String, Value> {
+        let mut config = Map::new();
+        config.insert("chainId".to_string(), self.chain.chain_id.into());
+        for fork in BLOCK_FORKS.into_iter().chain(TIME_FORKS) {
+            config.insert(fork.to_string(), 0.into());
+        }
+        config.insert("terminalTotalDifficulty".to_string(), 0.into());
+        config.insert("terminalTotalDifficultyPassed".to_string(), true.into());
+        for fork in MEGA_FORKS {
+            if let ForkCondition::Timestamp(timestamp) = hardforks.mega_fork_activation(fork) {
+                // `MiniRex` → `miniRexTime`
+                let name = format!("{fork:?}");
+                let key = format!("{}{}Time", name[..1].to_lowercase(), &name[1..]);
+                config.insert(key, timestamp.into());
+            }
+        }
+        config
+    }
+
+    /// Parse additional account entries from CLI arguments.
+    ///
+    /// Each entry should be in the format `ADDRESS=VALUE`.
+    /// VALUE can be: plain number (wei), or number with suffix (ether, gwei, wei).
+    pub fn parse_accounts(&self) -> Result<Vec<(Address, U256)>> {
+        let mut entries = Vec::new();
+        for entry in &self.accounts {
+            let (addr_str, value_str) = entry.split_once('=').ok_or_else(|| {
+                GenesisError::InvalidInput(format!(
+                    "Invalid account entry '{}': expected format 'ADDRESS=VALUE'",
+                    entry
+                ))
+            })?;
+            let address = Address::from_str(addr_str.trim()).map_err(|e| {
+                GenesisError::InvalidInput(format!(
+                    "Invalid address '{}' in account entry '{}': {}",
+                    addr_str, entry, e
+                ))
+            })?;
+            let wei = parse_ether_value(value_str)?;
+            entries.push((address, wei));
+        }
+        Ok(entries)
+    }
+}
@@ -0,0 +1,13 @@
+//! Genesis module for generating devnet genesis files
+//!
+//! This module provides functionality to produce a genesis file with the `MegaETH` system
+//! contracts predeployed and funded test accounts, ready to start a local devnet.
+
+mod cmd;
+
+pub use cmd::*;
+
+// Re-export from common module
+pub use crate::common::{
+    parse_ether_value, AccountState, ChainArgs, EvmeError as GenesisError, FixedHardfork, Result,
+};
@@ -11,10 +11,14 @@ pub mod cmd;
 /// Shared building blocks: RPC provider/session, state, env, error, output
 /// formatting, tracing, transaction utilities.
 pub mod common;
+/// Devnet genesis generation command.
+pub mod genesis;
 /// Historical transaction replay command.
 pub mod replay;
 /// Arbitrary EVM bytecode execution command.
 pub mod run;
+/// Layout-aware contract storage inspection command.
+pub mod storage;
 /// Single-transaction execution command.
 pub mod tx;
 
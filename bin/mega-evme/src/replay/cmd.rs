@@ -180,7 +180,7 @@ impl Cmd {
         external_envs: EvmeExternalEnvs,
     ) -> Result<()>
     where
-        P: Provider<op_alloy_network::Optimism> + Clone + std::fmt::Debug,
+        P: Provider<op_alloy_network::Optimism> + Clone + std::fmt::Debug + 'static,
     {
         let result = self.execute(provider, rctx, external_envs).await?;
         self.output_results(&result)?;
@@ -348,7 +348,7 @@ impl Cmd {
         external_envs: EvmeExternalEnvs,
     ) -> Result<ReplayOutcome>
     where
-        P: Provider<op_alloy_network::Optimism> + Clone + std::fmt::Debug,
+        P: Provider<op_alloy_network::Optimism> + Clone + std::fmt::Debug + 'static,
     {
         let hardforks = get_hardfork_config(ctx.chain_id);
         let spec = hardforks.spec_id(ctx.block.header.timestamp());
@@ -363,6 +363,17 @@ impl Cmd {
             Default::default(),
         )
         .await?;
+        // Fetch the accounts every replay touches concurrently, instead of one blocking request
+        // at a time once execution starts.
+        let target = &ctx.target_tx.inner.inner;
+        database
+            .prefetch(
+                [Some(target.signer()), target.to(), Some(ctx.block.header.beneficiary())]
+                    .into_iter()
+                    .flatten(),
+                [],
+            )
+            .await;
 
         let block_env = retrieve_block_env(&ctx.block)?;
         trace!(?block_env, "Block environment built");
@@ -28,7 +28,7 @@ use mega_evm::{
         state::EvmState,
         DatabaseRef,
     },
-    MegaHaltReason, MegaSpecId,
+    DivergenceReport, MegaHaltReason, MegaSpecId,
 };
 use op_alloy_consensus::OpTxEnvelope;
 use op_alloy_rpc_types::Transaction;
@@ -162,28 +162,14 @@ where
     // bakes that shifted value into `post` (gas, status, output, and logs all
     // still match). The fixture still self-validates and reproduces gas exactly.
     let anchor = &inputs.anchor;
-    if actual_gas != anchor.gas_used {
+    let divergence = DivergenceReport::new("on-chain receipt", "replay")
+        .compare("gas_used", anchor.gas_used, actual_gas)
+        .compare("success", anchor.success, inputs.result.is_success())
+        .compare("logs_root", anchor.logs_root, actual_logs_root);
+    if !divergence.is_empty() {
         return Err(ReplayError::Other(format!(
-            "replay gas {actual_gas} != on-chain receipt gas {}: the local replay does \
-             not reproduce on-chain execution (likely a wrong spec or hardfork config \
-             for chain {chain_id} at this block)",
-            anchor.gas_used
-        )));
-    }
-    if inputs.result.is_success() != anchor.success {
-        return Err(ReplayError::Other(format!(
-            "replay status (success={}) != on-chain receipt status (success={}): the \
-             local replay does not reproduce on-chain execution for chain {chain_id}",
-            inputs.result.is_success(),
-            anchor.success
-        )));
-    }
-    if actual_logs_root != anchor.logs_root {
-        return Err(ReplayError::Other(format!(
-            "replay logs root {actual_logs_root} != on-chain receipt logs root {}: the \
-             local replay emits different logs than the chain for chain {chain_id} \
-             (same gas/status, different log contents)",
-            anchor.logs_root
+            "the local replay does not reproduce on-chain execution (likely a wrong spec or \
+             hardfork config for chain {chain_id} at this block); {divergence}"
         )));
     }
 
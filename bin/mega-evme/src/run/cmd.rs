@@ -4,7 +4,7 @@ use clap::Parser;
 use mega_evm::revm::{context::result::ExecutionResult, state::Bytecode, DatabaseRef};
 use tracing::{debug, info, trace, warn};
 
-use super::{load_hex, Result, RunError};
+use super::{load_hex, print_gas_sweep, run_gas_sweep, GasSweep, Result, RunError};
 use crate::common::{
     print_execution_summary, print_execution_trace, EvmeOutcome, ExecutionSummary,
 };
@@ -23,6 +23,13 @@ pub struct Cmd {
     #[arg(long = "codefile")]
     pub codefile: Option<String>,
 
+    /// Execute the code once per gas limit in `START:END:STEP` (both ends included) and report
+    /// the minimum gas limit, gas used and compute gas used at which it succeeds.
+    /// Tracing and state dump are not available in this mode.
+    /// Example: `--gas-sweep 21000:100000:1000`
+    #[arg(long = "gas-sweep", value_name = "START:END:STEP")]
+    pub gas_sweep: Option<GasSweep>,
+
     // Shared argument groups
     /// Transaction configuration
     #[command(flatten)]
@@ -104,6 +111,21 @@ impl Cmd {
             tx.base.data = [code.as_ref(), tx.base.data.as_ref()].concat().into();
         }
 
+        // Gas sweep mode: execute once per gas limit and report instead of a single outcome
+        if let Some(sweep) = self.gas_sweep {
+            let report = run_gas_sweep(sweep, &self.env_args, &mut state, &tx)?;
+            if self.output_args.json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&report).expect("failed to serialize output")
+                );
+            } else {
+                print_gas_sweep(&report);
+            }
+            cache_store.persist()?;
+            return Ok(());
+        }
+
         // Create EVM context and execute transaction
         let evm_context = self.env_args.create_evm_context(&mut state)?;
         let start = Instant::now();
@@ -4,8 +4,10 @@
 //! similar to go-ethereum's `evm run` command.
 
 mod cmd;
+mod sweep;
 
 pub use cmd::*;
+pub use sweep::*;
 
 // Re-export from common module
 pub use crate::common::{
@@ -0,0 +1,183 @@
+//! Gas-limit sweep for the run command
+
+use std::str::FromStr;
+
+use mega_evm::{
+    alloy_evm::Database, revm::context::result::ExecutionResult, MegaEvm, MegaTransaction,
+};
+use serde::Serialize;
+use tracing::{debug, info};
+
+use super::{EnvArgs, Result};
+
+/// A range of gas limits, parsed from `START:END:STEP` (both ends included).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasSweep {
+    /// First gas limit
+    pub start: u64,
+    /// Last gas limit, included if reached by a whole number of steps
+    pub end: u64,
+    /// Increment between gas limits
+    pub step: u64,
+}
+
+impl FromStr for GasSweep {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(':').collect();
+        let [start, end, step] = parts[..] else {
+            return Err(format!("Invalid gas sweep '{}': expected format 'START:END:STEP'", s));
+        };
+        let parse = |part: &str| {
+            part.trim()
+                .parse::<u64>()
+                .map_err(|e| format!("Invalid gas limit '{}' in gas sweep '{}': {}", part, s, e))
+        };
+        let sweep = Self { start: parse(start)?, end: parse(end)?, step: parse(step)? };
+        if sweep.step == 0 {
+            return Err(format!("Invalid gas sweep '{}': STEP must be positive", s));
+        }
+        if sweep.start > sweep.end {
+            return Err(format!("Invalid gas sweep '{}': START must not exceed END", s));
+        }
+        Ok(sweep)
+    }
+}
+
+impl GasSweep {
+    /// Returns the gas limits of the sweep, in increasing order.
+    pub fn gas_limits(&self) -> impl Iterator<Item = u64> {
+        let Self { start, end, step } = *self;
+        (0..=(end - start) / step).map(move |index| start + index * step)
+    }
+}
+
+/// The outcome of the transaction at one gas limit of a [`GasSweep`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasSweepPoint {
+    /// Gas limit of the transaction
+    pub gas_limit: u64,
+    /// `success`, `revert`, `halt`, or `invalid` if the transaction failed validation
+    pub status: &'static str,
+    /// Gas used, 0 if invalid
+    pub gas_used: u64,
+    /// Compute gas used, 0 if invalid
+    pub compute_gas_used: u64,
+    /// Halt reason or validation error, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl GasSweepPoint {
+    /// Returns whether the transaction succeeded.
+    pub fn is_success(&self) -> bool {
+        self.status == "success"
+    }
+}
+
+/// The outcomes of a [`GasSweep`] and the cheapest successful run.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasSweepReport {
+    /// Outcome at each gas limit, in increasing order of gas limit
+    pub points: Vec<GasSweepPoint>,
+    /// The lowest gas limit at which the transaction succeeded, if any
+    pub min_success: Option<GasSweepPoint>,
+}
+
+/// Executes `tx` at every gas limit of `sweep`, each time on the same initial state.
+pub fn run_gas_sweep<DB: Database>(
+    sweep: GasSweep,
+    env_args: &EnvArgs,
+    db: &mut DB,
+    tx: &MegaTransaction,
+) -> Result<GasSweepReport> {
+    info!(start = sweep.start, end = sweep.end, step = sweep.step, "Sweeping gas limits");
+    let mut points = Vec::new();
+    for gas_limit in sweep.gas_limits() {
+        let mut tx = tx.clone();
+        tx.base.gas_limit = gas_limit;
+        // Nothing is committed, so every run starts from the same state.
+        let mut evm = MegaEvm::new(env_args.create_evm_context(&mut *db)?);
+        let point = match evm.execute_transaction_owned(tx) {
+            Ok((outcome, _)) => {
+                let (status, reason) = match &outcome.result {
+                    ExecutionResult::Success { .. } => ("success", None),
+                    ExecutionResult::Revert { .. } => ("revert", None),
+                    ExecutionResult::Halt { reason, .. } => ("halt", Some(format!("{reason:?}"))),
+                };
+                GasSweepPoint {
+                    gas_limit,
+                    status,
+                    gas_used: outcome.result.gas_used(),
+                    compute_gas_used: outcome.compute_gas_used,
+                    reason,
+                }
+            }
+            Err(e) => GasSweepPoint {
+                gas_limit,
+                status: "invalid",
+                gas_used: 0,
+                compute_gas_used: 0,
+                reason: Some(format!("{e:?}")),
+            },
+        };
+        debug!(point = ?point, "Gas sweep point executed");
+        points.push(point);
+    }
+
+    let min_success = points.iter().find(|point| point.is_success()).cloned();
+    Ok(GasSweepReport { points, min_success })
+}
+
+/// Print a human-readable gas sweep report.
+pub fn print_gas_sweep(report: &GasSweepReport) {
+    println!();
+    println!("=== Gas Sweep ===");
+    println!(
+        "{:>16} {:>8} {:>16} {:>16}  Reason",
+        "Gas Limit", "Status", "Gas Used", "Compute Gas"
+    );
+    for point in &report.points {
+        println!(
+            "{:>16} {:>8} {:>16} {:>16}  {}",
+            point.gas_limit,
+            point.status,
+            point.gas_used,
+            point.compute_gas_used,
+            point.reason.as_deref().unwrap_or_default()
+        );
+    }
+
+    println!();
+    match &report.min_success {
+        Some(point) => {
+            println!("Min Gas Limit:    {}", point.gas_limit);
+            println!("Gas Used:         {}", point.gas_used);
+            println!("Compute Gas Used: {}", point.compute_gas_used);
+        }
+        None => println!("No gas limit in the sweep succeeded"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gas_sweep() {
+        let sweep: GasSweep = "21000:25000:1500".parse().unwrap();
+        assert_eq!(sweep, GasSweep { start: 21000, end: 25000, step: 1500 });
+        assert_eq!(sweep.gas_limits().collect::<Vec<_>>(), vec![21000, 22500, 24000]);
+
+        let single: GasSweep = "21000:21000:1".parse().unwrap();
+        assert_eq!(single.gas_limits().collect::<Vec<_>>(), vec![21000]);
+
+        assert!("21000:25000".parse::<GasSweep>().is_err());
+        assert!("21000:25000:0".parse::<GasSweep>().is_err());
+        assert!("25000:21000:1000".parse::<GasSweep>().is_err());
+        assert!("21000:abc:1000".parse::<GasSweep>().is_err());
+    }
+}
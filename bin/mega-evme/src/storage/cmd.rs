@@ -0,0 +1,126 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use alloy_primitives::Address;
+use clap::Parser;
+use mega_evm::revm::DatabaseRef;
+use tracing::{debug, info};
+
+use super::{DecodeOptions, DecodedValue, DecodedVariable, Result, StorageError, StorageLayout};
+
+/// Decode the storage variables of a contract
+#[derive(Parser, Debug)]
+pub struct Cmd {
+    /// Address of the contract
+    #[arg(value_name = "ADDRESS")]
+    pub address: Address,
+
+    /// JSON file with the storage layout of the contract, as output by solc
+    /// (`storageLayout`), either bare or inside the contract output
+    #[arg(long = "layout")]
+    pub layout: PathBuf,
+
+    /// Mapping entries to decode. Each entry format: `PATH=KEY[,KEY...]`, with one key per
+    /// nesting level. PATH is the variable name, or `STRUCT.MEMBER` for a struct member.
+    /// Examples: `--key balances=0x1234`, `--key allowances=0x1234,0x5678`
+    /// Can be repeated for multiple entries.
+    #[arg(long = "key")]
+    pub keys: Vec<String>,
+
+    /// Maximum number of elements decoded per array
+    #[arg(long = "max-elements", default_value = "16")]
+    pub max_elements: usize,
+
+    // Shared argument groups
+    /// State configuration
+    #[command(flatten)]
+    pub prestate_args: super::PreStateArgs,
+
+    /// RPC configuration (used when --fork is enabled)
+    #[command(flatten)]
+    pub rpc_args: super::RpcArgs,
+
+    /// Output format configuration
+    #[command(flatten)]
+    pub output_args: super::OutputArgs,
+}
+
+impl Cmd {
+    /// Execute the storage command
+    pub async fn run(&self) -> Result<()> {
+        // Step 1: Load the storage layout
+        info!(layout = ?self.layout, "Loading storage layout");
+        let layout = StorageLayout::from_json(&std::fs::read_to_string(&self.layout)?)?;
+        let options =
+            DecodeOptions { mapping_keys: self.parse_keys()?, max_elements: self.max_elements };
+        debug!(variables = layout.storage.len(), "Storage layout loaded");
+
+        // Step 2: Setup the state
+        info!("Setting up state");
+        let (state, cache_store) =
+            self.prestate_args.create_initial_state(&self.address, &self.rpc_args).await?;
+
+        // Step 3: Decode the storage
+        info!(address = %self.address, "Decoding storage");
+        let decoded = layout.decode(&options, &mut |slot| state.storage_ref(self.address, slot))?;
+
+        // Step 4: Output results
+        if self.output_args.json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&decoded).expect("failed to serialize output")
+            );
+        } else {
+            print_storage(&decoded, 0);
+        }
+
+        // Step 5: Persist the RPC cache (clean-exit only).
+        cache_store.persist()?;
+
+        Ok(())
+    }
+
+    /// Parse mapping key entries from CLI arguments.
+    ///
+    /// Each entry should be in the format `PATH=KEY[,KEY...]`.
+    pub fn parse_keys(&self) -> Result<HashMap<String, Vec<Vec<String>>>> {
+        let mut keys: HashMap<String, Vec<Vec<String>>> = HashMap::new();
+        for entry in &self.keys {
+            let (path, key_str) = entry.split_once('=').ok_or_else(|| {
+                StorageError::InvalidInput(format!(
+                    "Invalid key entry '{}': expected format 'PATH=KEY[,KEY...]'",
+                    entry
+                ))
+            })?;
+            let key = key_str.split(',').map(|key| key.trim().to_string()).collect();
+            keys.entry(path.trim().to_string()).or_default().push(key);
+        }
+        Ok(keys)
+    }
+}
+
+/// Prints decoded variables as an indented tree.
+fn print_storage(variables: &[DecodedVariable], depth: usize) {
+    let indent = "  ".repeat(depth);
+    for variable in variables {
+        let location = if variable.offset == 0 {
+            format!("slot {:#x}", variable.slot)
+        } else {
+            format!("slot {:#x}, offset {}", variable.slot, variable.offset)
+        };
+        match &variable.value {
+            DecodedValue::Value(value) if variable.type_label.is_empty() => {
+                println!("{indent}{}: {value}", variable.label);
+            }
+            DecodedValue::Value(value) => {
+                println!(
+                    "{indent}{} ({}, {location}) = {value}",
+                    variable.label, variable.type_label
+                );
+            }
+            DecodedValue::Members(members) => {
+                println!("{indent}{} ({}, {location})", variable.label, variable.type_label);
+                print_storage(members, depth + 1);
+            }
+        }
+    }
+}
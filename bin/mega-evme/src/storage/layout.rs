@@ -0,0 +1,552 @@
+//! Solidity storage layout parsing and decoding.
+
+use std::{collections::HashMap, str::FromStr};
+
+use alloy_primitives::{hex, keccak256, Address, I256, U256};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::{Result, StorageError};
+
+/// Longest `bytes` or `string` value read, in bytes. Longer values are truncated.
+const MAX_BYTES_LEN: usize = 4096;
+
+/// A contract storage layout, as output by solc (`storageLayout`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct StorageLayout {
+    /// The state variables, in declaration order
+    pub storage: Vec<StorageEntry>,
+    /// The types of the state variables, keyed by type identifier
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub types: HashMap<String, StorageType>,
+}
+
+/// A state variable, or a member of a struct.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StorageEntry {
+    /// Name of the variable
+    pub label: String,
+    /// Slot of the variable, relative to the struct for a member
+    #[serde(deserialize_with = "u256_from_str")]
+    pub slot: U256,
+    /// Offset of the variable in its slot, in bytes
+    pub offset: usize,
+    /// Type identifier of the variable
+    #[serde(rename = "type")]
+    pub type_id: String,
+}
+
+/// How a type is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageEncoding {
+    /// Stored in place: value types, structs and static arrays
+    Inplace,
+    /// Mapping, with values at `keccak256(key . slot)`
+    Mapping,
+    /// Dynamic array, with the length in the slot and elements from `keccak256(slot)`
+    DynamicArray,
+    /// `bytes` or `string`, in the slot if short, from `keccak256(slot)` otherwise
+    Bytes,
+}
+
+/// A type of the storage layout.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageType {
+    /// How the type is stored
+    pub encoding: StorageEncoding,
+    /// Name of the type, e.g. `mapping(address => uint256)`
+    pub label: String,
+    /// Size of the type, in bytes
+    #[serde(deserialize_with = "u256_from_str")]
+    pub number_of_bytes: U256,
+    /// Key type identifier of a mapping
+    pub key: Option<String>,
+    /// Value type identifier of a mapping
+    pub value: Option<String>,
+    /// Element type identifier of an array
+    pub base: Option<String>,
+    /// Members of a struct
+    pub members: Option<Vec<StorageEntry>>,
+}
+
+/// A decoded variable, struct member, array element or mapping entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedVariable {
+    /// Name of the variable, `[index]` for an array element or `[key]` for a mapping entry
+    pub label: String,
+    /// Name of the type
+    #[serde(rename = "type")]
+    pub type_label: String,
+    /// Slot the variable starts at
+    pub slot: U256,
+    /// Offset of the variable in its slot, in bytes
+    pub offset: usize,
+    /// The decoded value
+    pub value: DecodedValue,
+}
+
+/// The value of a [`DecodedVariable`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum DecodedValue {
+    /// A value type, `bytes` or `string`, formatted
+    Value(String),
+    /// The members of a struct, the elements of an array or the requested entries of a mapping
+    Members(Vec<DecodedVariable>),
+}
+
+/// Options of [`StorageLayout::decode`].
+#[derive(Debug, Clone, Default)]
+pub struct DecodeOptions {
+    /// Keys to decode, keyed by mapping path (`a.b` for member `b` of struct `a`). Each entry
+    /// holds one key per nesting level, so `[k1, k2]` decodes `m[k1][k2]`.
+    pub mapping_keys: HashMap<String, Vec<Vec<String>>>,
+    /// Maximum number of elements decoded per array
+    pub max_elements: usize,
+}
+
+impl StorageLayout {
+    /// Parses a storage layout, either bare (`{"storage": ..., "types": ...}`) or inside a solc
+    /// contract output (`{"storageLayout": ...}`).
+    pub fn from_json(json: &str) -> Result<Self> {
+        let mut value: serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| StorageError::InvalidInput(format!("Invalid storage layout: {}", e)))?;
+        if let Some(layout) = value.get_mut("storageLayout") {
+            value = layout.take();
+        }
+        serde_json::from_value(value)
+            .map_err(|e| StorageError::InvalidInput(format!("Invalid storage layout: {}", e)))
+    }
+
+    /// Decodes every state variable, reading storage slots with `read`.
+    pub fn decode(
+        &self,
+        options: &DecodeOptions,
+        read: &mut dyn FnMut(U256) -> Result<U256>,
+    ) -> Result<Vec<DecodedVariable>> {
+        let mut decoder = Decoder { layout: self, options, read };
+        self.storage
+            .iter()
+            .map(|entry| decoder.decode_entry(entry, U256::ZERO, &entry.label))
+            .collect()
+    }
+
+    fn type_of(&self, type_id: &str) -> Result<&StorageType> {
+        self.types.get(type_id).ok_or_else(|| {
+            StorageError::InvalidInput(format!("Unknown type '{}' in storage layout", type_id))
+        })
+    }
+}
+
+struct Decoder<'a, 'r> {
+    layout: &'a StorageLayout,
+    options: &'a DecodeOptions,
+    read: &'r mut dyn FnMut(U256) -> Result<U256>,
+}
+
+impl Decoder<'_, '_> {
+    fn decode_entry(
+        &mut self,
+        entry: &StorageEntry,
+        base: U256,
+        path: &str,
+    ) -> Result<DecodedVariable> {
+        let keys = self.keys_at(path);
+        self.decode(
+            entry.label.clone(),
+            &entry.type_id,
+            base + entry.slot,
+            entry.offset,
+            path,
+            keys,
+        )
+    }
+
+    /// Returns the mapping keys requested for the variable at `path`.
+    fn keys_at(&self, path: &str) -> Vec<Vec<String>> {
+        self.options.mapping_keys.get(path).cloned().unwrap_or_default()
+    }
+
+    fn decode(
+        &mut self,
+        label: String,
+        type_id: &str,
+        slot: U256,
+        offset: usize,
+        path: &str,
+        keys: Vec<Vec<String>>,
+    ) -> Result<DecodedVariable> {
+        let layout = self.layout;
+        let ty = layout.type_of(type_id)?;
+        let value = match ty.encoding {
+            StorageEncoding::Inplace => {
+                if let Some(members) = &ty.members {
+                    let members = members
+                        .iter()
+                        .map(|member| {
+                            self.decode_entry(member, slot, &format!("{path}.{}", member.label))
+                        })
+                        .collect::<Result<_>>()?;
+                    DecodedValue::Members(members)
+                } else if let Some(base) = &ty.base {
+                    let len = static_array_len(&ty.label)?;
+                    DecodedValue::Members(self.decode_elements(base, slot, len, path)?)
+                } else {
+                    let size = ty.number_of_bytes.saturating_to::<usize>();
+                    let word = (self.read)(slot)?;
+                    DecodedValue::Value(format_value(&ty.label, extract(word, offset, size), size))
+                }
+            }
+            StorageEncoding::Mapping => {
+                let (Some(key), Some(value)) = (&ty.key, &ty.value) else {
+                    return Err(StorageError::InvalidInput(format!(
+                        "Mapping type '{}' has no key or value type",
+                        type_id
+                    )));
+                };
+                let key_label = &layout.type_of(key)?.label;
+                // Decode each requested first-level key once, with the deeper keys of every
+                // request starting with it.
+                let mut entries: Vec<(String, Vec<Vec<String>>)> = Vec::new();
+                for request in keys {
+                    let Some((first, rest)) = request.split_first() else { continue };
+                    let nested = (!rest.is_empty()).then(|| rest.to_vec());
+                    match entries.iter_mut().find(|(key, _)| key == first) {
+                        Some((_, deeper)) => deeper.extend(nested),
+                        None => entries.push((first.clone(), nested.into_iter().collect())),
+                    }
+                }
+                let members = entries
+                    .into_iter()
+                    .map(|(key, deeper)| {
+                        let entry_slot = mapping_slot(key_label, &key, slot)?;
+                        self.decode(format!("[{key}]"), value, entry_slot, 0, path, deeper)
+                    })
+                    .collect::<Result<_>>()?;
+                DecodedValue::Members(members)
+            }
+            StorageEncoding::DynamicArray => {
+                let base = ty.base.as_deref().ok_or_else(|| {
+                    StorageError::InvalidInput(format!("Array type '{}' has no base type", type_id))
+                })?;
+                let len = (self.read)(slot)?.saturating_to::<usize>();
+                let start = U256::from_be_bytes(keccak256(slot.to_be_bytes::<32>()).0);
+                let mut elements = self.decode_elements(base, start, len, path)?;
+                if len > elements.len() {
+                    elements.push(DecodedVariable {
+                        label: "..".to_string(),
+                        type_label: String::new(),
+                        slot: start,
+                        offset: 0,
+                        value: DecodedValue::Value(format!("{} more", len - elements.len())),
+                    });
+                }
+                DecodedValue::Members(elements)
+            }
+            StorageEncoding::Bytes => DecodedValue::Value(self.decode_bytes(&ty.label, slot)?),
+        };
+        Ok(DecodedVariable { label, type_label: ty.label.clone(), slot, offset, value })
+    }
+
+    /// Decodes the first `len` elements of an array starting at `start`, up to
+    /// [`DecodeOptions::max_elements`].
+    fn decode_elements(
+        &mut self,
+        base: &str,
+        start: U256,
+        len: usize,
+        path: &str,
+    ) -> Result<Vec<DecodedVariable>> {
+        let size = self.layout.type_of(base)?.number_of_bytes.saturating_to::<usize>();
+        (0..len.min(self.options.max_elements))
+            .map(|index| {
+                // Elements of at most 16 bytes are packed; larger ones start a new slot.
+                let (slot, offset) = if size <= 16 && size > 0 {
+                    let per_slot = 32 / size;
+                    (start + U256::from(index / per_slot), (index % per_slot) * size)
+                } else {
+                    (start + U256::from(index * size.div_ceil(32)), 0)
+                };
+                let keys = self.keys_at(path);
+                self.decode(format!("[{index}]"), base, slot, offset, path, keys)
+            })
+            .collect()
+    }
+
+    /// Decodes a `bytes` or `string` value stored at `slot`.
+    fn decode_bytes(&mut self, type_label: &str, slot: U256) -> Result<String> {
+        let word = (self.read)(slot)?;
+        let data = if !word.bit(0) {
+            // Short: the data in the high-order bytes, twice the length in the lowest byte.
+            let len = (word.byte(0) / 2) as usize;
+            word.to_be_bytes::<32>()[..len].to_vec()
+        } else {
+            // Long: twice the length plus one in the slot, the data from `keccak256(slot)`.
+            let len = ((word - U256::from(1)) / U256::from(2)).saturating_to::<usize>();
+            let start = U256::from_be_bytes(keccak256(slot.to_be_bytes::<32>()).0);
+            let mut data = Vec::with_capacity(len.min(MAX_BYTES_LEN));
+            for index in 0..len.min(MAX_BYTES_LEN).div_ceil(32) {
+                data.extend_from_slice(
+                    &(self.read)(start + U256::from(index))?.to_be_bytes::<32>(),
+                );
+            }
+            data.truncate(len.min(MAX_BYTES_LEN));
+            if len > MAX_BYTES_LEN {
+                let shown = format_bytes(type_label, &data);
+                return Ok(format!("{shown}.. ({len} bytes)"));
+            }
+            data
+        };
+        Ok(format_bytes(type_label, &data))
+    }
+}
+
+/// Returns the length of a static array type, e.g. 3 for `uint256[3]`.
+fn static_array_len(type_label: &str) -> Result<usize> {
+    type_label
+        .strip_suffix(']')
+        .and_then(|label| label.rsplit_once('['))
+        .and_then(|(_, len)| len.parse().ok())
+        .ok_or_else(|| {
+            StorageError::InvalidInput(format!("Invalid static array type '{}'", type_label))
+        })
+}
+
+/// Extracts the `size` bytes at `offset` (from the low-order end) of a storage word.
+fn extract(word: U256, offset: usize, size: usize) -> U256 {
+    let value = word >> (offset * 8);
+    if size >= 32 {
+        value
+    } else {
+        value & ((U256::from(1) << (size * 8)) - U256::from(1))
+    }
+}
+
+/// Formats a value type of `size` bytes according to its type name.
+fn format_value(type_label: &str, value: U256, size: usize) -> String {
+    if type_label == "bool" {
+        (!value.is_zero()).to_string()
+    } else if type_label.starts_with("address") || type_label.starts_with("contract ") {
+        Address::from_word(value.into()).to_string()
+    } else if type_label.starts_with("uint") || type_label.starts_with("enum ") {
+        value.to_string()
+    } else if type_label.starts_with("int") {
+        // Sign-extend from the type's width.
+        let bits = size * 8;
+        let value =
+            if bits < 256 && value.bit(bits - 1) { value | (U256::MAX << bits) } else { value };
+        I256::from_raw(value).to_string()
+    } else {
+        hex::encode_prefixed(&value.to_be_bytes::<32>()[32 - size.min(32)..])
+    }
+}
+
+/// Formats `bytes` as hex and `string` as a quoted UTF-8 string.
+fn format_bytes(type_label: &str, data: &[u8]) -> String {
+    if type_label == "string" {
+        format!("{:?}", String::from_utf8_lossy(data))
+    } else {
+        hex::encode_prefixed(data)
+    }
+}
+
+/// Returns the slot of the entry with key `key`, of type `key_type`, in a mapping at `slot`.
+fn mapping_slot(key_type: &str, key: &str, slot: U256) -> Result<U256> {
+    let invalid = |e: &dyn std::fmt::Display| {
+        StorageError::InvalidInput(format!("Invalid {} mapping key '{}': {}", key_type, key, e))
+    };
+    // `string` and `bytes` keys are hashed unpadded; value type keys are padded to a word.
+    let mut preimage = match key_type {
+        "string" => key.as_bytes().to_vec(),
+        "bytes" => hex::decode(key).map_err(|e| invalid(&e))?,
+        _ => {
+            let word = if key_type == "bool" {
+                U256::from(bool::from_str(key).map_err(|e| invalid(&e))?).to_be_bytes::<32>()
+            } else if key_type.starts_with("address") || key_type.starts_with("contract ") {
+                Address::from_str(key).map_err(|e| invalid(&e))?.into_word().0
+            } else if key_type.starts_with("uint") || key_type.starts_with("enum ") {
+                U256::from_str(key).map_err(|e| invalid(&e))?.to_be_bytes::<32>()
+            } else if key_type.starts_with("int") {
+                I256::from_dec_str(key).map_err(|e| invalid(&e))?.into_raw().to_be_bytes::<32>()
+            } else if key_type.starts_with("bytes") {
+                // `bytesN` keys are left-aligned.
+                let bytes = hex::decode(key).map_err(|e| invalid(&e))?;
+                if bytes.len() > 32 {
+                    return Err(invalid(&"longer than 32 bytes"));
+                }
+                let mut word = [0u8; 32];
+                word[..bytes.len()].copy_from_slice(&bytes);
+                word
+            } else {
+                return Err(invalid(&"unsupported key type"));
+            };
+            word.to_vec()
+        }
+    };
+    preimage.extend_from_slice(&slot.to_be_bytes::<32>());
+    Ok(U256::from_be_bytes(keccak256(preimage).0))
+}
+
+/// Deserializes a number given as a decimal string, as in solc storage layouts.
+fn u256_from_str<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<U256, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    U256::from_str(&s).map_err(serde::de::Error::custom)
+}
+
+/// Deserializes `null` as the default value: solc outputs `"types": null` without storage.
+fn null_as_default<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The layout of:
+    ///
+    /// ```solidity
+    /// contract Token {
+    ///     struct Info { uint8 decimals; bool paused; string name; }
+    ///     address owner;
+    ///     uint8 flags;
+    ///     int16 delta;
+    ///     mapping(address => uint256) balances;
+    ///     mapping(address => mapping(address => uint256)) allowances;
+    ///     Info info;
+    ///     uint64[] history;
+    /// }
+    /// ```
+    const LAYOUT: &str = r#"{
+        "storageLayout": {
+            "storage": [
+                {"astId": 1, "contract": "Token", "label": "owner", "offset": 0, "slot": "0", "type": "t_address"},
+                {"astId": 2, "contract": "Token", "label": "flags", "offset": 20, "slot": "0", "type": "t_uint8"},
+                {"astId": 3, "contract": "Token", "label": "delta", "offset": 21, "slot": "0", "type": "t_int16"},
+                {"astId": 4, "contract": "Token", "label": "balances", "offset": 0, "slot": "1", "type": "t_mapping(t_address,t_uint256)"},
+                {"astId": 5, "contract": "Token", "label": "allowances", "offset": 0, "slot": "2", "type": "t_mapping(t_address,t_mapping(t_address,t_uint256))"},
+                {"astId": 6, "contract": "Token", "label": "info", "offset": 0, "slot": "3", "type": "t_struct(Info)1_storage"},
+                {"astId": 7, "contract": "Token", "label": "history", "offset": 0, "slot": "5", "type": "t_array(t_uint64)dyn_storage"}
+            ],
+            "types": {
+                "t_address": {"encoding": "inplace", "label": "address", "numberOfBytes": "20"},
+                "t_bool": {"encoding": "inplace", "label": "bool", "numberOfBytes": "1"},
+                "t_int16": {"encoding": "inplace", "label": "int16", "numberOfBytes": "2"},
+                "t_string_storage": {"encoding": "bytes", "label": "string", "numberOfBytes": "32"},
+                "t_uint8": {"encoding": "inplace", "label": "uint8", "numberOfBytes": "1"},
+                "t_uint64": {"encoding": "inplace", "label": "uint64", "numberOfBytes": "8"},
+                "t_uint256": {"encoding": "inplace", "label": "uint256", "numberOfBytes": "32"},
+                "t_array(t_uint64)dyn_storage": {"encoding": "dynamic_array", "label": "uint64[]", "numberOfBytes": "32", "base": "t_uint64"},
+                "t_mapping(t_address,t_uint256)": {"encoding": "mapping", "label": "mapping(address => uint256)", "numberOfBytes": "32", "key": "t_address", "value": "t_uint256"},
+                "t_mapping(t_address,t_mapping(t_address,t_uint256))": {"encoding": "mapping", "label": "mapping(address => mapping(address => uint256))", "numberOfBytes": "32", "key": "t_address", "value": "t_mapping(t_address,t_uint256)"},
+                "t_struct(Info)1_storage": {"encoding": "inplace", "label": "struct Token.Info", "numberOfBytes": "64", "members": [
+                    {"astId": 8, "contract": "Token", "label": "decimals", "offset": 0, "slot": "0", "type": "t_uint8"},
+                    {"astId": 9, "contract": "Token", "label": "paused", "offset": 1, "slot": "0", "type": "t_bool"},
+                    {"astId": 10, "contract": "Token", "label": "name", "offset": 0, "slot": "1", "type": "t_string_storage"}
+                ]}
+            }
+        }
+    }"#;
+
+    const OWNER: &str = "0x00000000000000000000000000000000000000AA";
+    const SPENDER: &str = "0x00000000000000000000000000000000000000BB";
+
+    fn value(variable: &DecodedVariable) -> &str {
+        match &variable.value {
+            DecodedValue::Value(value) => value,
+            DecodedValue::Members(_) => panic!("{} is not a value", variable.label),
+        }
+    }
+
+    fn members(variable: &DecodedVariable) -> &[DecodedVariable] {
+        match &variable.value {
+            DecodedValue::Members(members) => members,
+            DecodedValue::Value(_) => panic!("{} has no members", variable.label),
+        }
+    }
+
+    #[test]
+    fn test_decodes_solidity_storage_layout() {
+        let layout = StorageLayout::from_json(LAYOUT).unwrap();
+        let owner = Address::from_str(OWNER).unwrap();
+        let balance_slot = mapping_slot("address", OWNER, U256::from(1)).unwrap();
+        let allowance_slot = mapping_slot(
+            "address",
+            SPENDER,
+            mapping_slot("address", OWNER, U256::from(2)).unwrap(),
+        )
+        .unwrap();
+        let history = U256::from_be_bytes(keccak256(U256::from(5).to_be_bytes::<32>()).0);
+
+        let mut storage = HashMap::new();
+        // owner | flags = 7 | delta = -2
+        storage.insert(
+            U256::ZERO,
+            U256::from_be_slice(owner.as_slice()) |
+                (U256::from(7) << 160) |
+                (U256::from(0xfffe) << 168),
+        );
+        storage.insert(balance_slot, U256::from(100));
+        storage.insert(allowance_slot, U256::from(5));
+        // decimals = 18 | paused = true
+        storage.insert(U256::from(3), U256::from(18) | (U256::from(1) << 8));
+        // name = "MEGA", short string
+        let mut name = [0u8; 32];
+        name[..4].copy_from_slice(b"MEGA");
+        name[31] = 8;
+        storage.insert(U256::from(4), U256::from_be_bytes(name));
+        // history = [1, 2, 3], four uint64 per slot
+        storage.insert(U256::from(5), U256::from(3));
+        storage.insert(history, U256::from(1) | (U256::from(2) << 64) | (U256::from(3) << 128));
+
+        let options = DecodeOptions {
+            mapping_keys: HashMap::from([
+                ("balances".to_string(), vec![vec![OWNER.to_string()]]),
+                ("allowances".to_string(), vec![vec![OWNER.to_string(), SPENDER.to_string()]]),
+            ]),
+            max_elements: 2,
+        };
+        let decoded = layout
+            .decode(&options, &mut |slot| Ok(storage.get(&slot).copied().unwrap_or_default()))
+            .unwrap();
+
+        assert_eq!(value(&decoded[0]), owner.to_string());
+        assert_eq!(value(&decoded[1]), "7");
+        assert_eq!(value(&decoded[2]), "-2");
+        assert_eq!(value(&members(&decoded[3])[0]), "100");
+        assert_eq!(members(&decoded[3])[0].label, format!("[{OWNER}]"));
+        assert_eq!(value(&members(&members(&decoded[4])[0])[0]), "5");
+        let info = members(&decoded[5]);
+        assert_eq!(info.iter().map(value).collect::<Vec<_>>(), ["18", "true", "\"MEGA\""]);
+        let history = members(&decoded[6]);
+        assert_eq!(history.iter().map(value).collect::<Vec<_>>(), ["1", "2", "1 more"]);
+    }
+
+    #[test]
+    fn test_decodes_long_bytes() {
+        let layout = StorageLayout::from_json(
+            r#"{"storage": [{"label": "data", "offset": 0, "slot": "0", "type": "t_bytes_storage"}],
+                "types": {"t_bytes_storage": {"encoding": "bytes", "label": "bytes", "numberOfBytes": "32"}}}"#,
+        )
+        .unwrap();
+        let start = U256::from_be_bytes(keccak256([0u8; 32]).0);
+        let mut storage = HashMap::new();
+        storage.insert(U256::ZERO, U256::from(40 * 2 + 1));
+        storage.insert(start, U256::from_be_bytes([0x11; 32]));
+        storage.insert(start + U256::from(1), U256::from_be_bytes([0x22; 32]));
+
+        let decoded = layout
+            .decode(&DecodeOptions::default(), &mut |slot| {
+                Ok(storage.get(&slot).copied().unwrap_or_default())
+            })
+            .unwrap();
+        assert_eq!(value(&decoded[0]), format!("0x{}{}", "11".repeat(32), "22".repeat(8)));
+    }
+}
@@ -0,0 +1,13 @@
+//! Storage module for inspecting contract storage
+//!
+//! This module provides functionality to decode the storage variables of a contract from its
+//! solc storage layout, reading the state from a prestate file or a forked remote state.
+
+mod cmd;
+mod layout;
+
+pub use cmd::*;
+pub use layout::*;
+
+// Re-export from common module
+pub use crate::common::{EvmeError as StorageError, OutputArgs, PreStateArgs, Result, RpcArgs};
@@ -0,0 +1,86 @@
+//! Integration tests for the `genesis` command: the system contracts, funded accounts and chain
+//! config of the generated genesis, and its alloc loading back as a prestate.
+
+use alloy_primitives::{address, U256};
+use clap::Parser;
+use mega_evm::{
+    revm::DatabaseRef, KEYLESS_DEPLOY_ADDRESS, KEYLESS_DEPLOY_CODE, ORACLE_CONTRACT_ADDRESS,
+    ORACLE_CONTRACT_CODE_REX5, SEQUENCER_REGISTRY_ADDRESS,
+};
+use mega_evme::{
+    common::{EvmeState, PreStateArgs},
+    genesis::{Cmd, DEV_ACCOUNTS},
+};
+use tempfile::tempdir;
+
+fn genesis(args: &[&str]) -> mega_evme::genesis::Genesis {
+    let cmd = Cmd::parse_from(["genesis"].into_iter().chain(args.iter().copied()));
+    cmd.build_genesis().expect("build_genesis")
+}
+
+#[test]
+fn test_genesis_predeploys_system_contracts() {
+    let genesis = genesis(&["--spec", "Rex6"]);
+
+    let oracle = &genesis.alloc[&ORACLE_CONTRACT_ADDRESS];
+    assert_eq!(oracle.code.as_ref(), Some(&ORACLE_CONTRACT_CODE_REX5));
+    let keyless_deploy = &genesis.alloc[&KEYLESS_DEPLOY_ADDRESS];
+    assert_eq!(keyless_deploy.code.as_ref(), Some(&KEYLESS_DEPLOY_CODE));
+    assert!(
+        !genesis.alloc.contains_key(&SEQUENCER_REGISTRY_ADDRESS),
+        "the SequencerRegistry is deployed by the block executor"
+    );
+
+    // Before MiniRex there is no system contract.
+    let genesis = genesis(&["--spec", "Equivalence"]);
+    assert!(!genesis.alloc.contains_key(&ORACLE_CONTRACT_ADDRESS));
+    assert!(!genesis.alloc.contains_key(&KEYLESS_DEPLOY_ADDRESS));
+}
+
+#[test]
+fn test_genesis_funds_accounts_and_activates_forks() {
+    let extra = address!("0000000000000000000000000000000000001234");
+    let genesis = genesis(&[
+        "--spec",
+        "Rex4",
+        "--chain-id",
+        "1337",
+        "--dev-accounts",
+        "2",
+        "--dev-balance",
+        "5ether",
+        "--account",
+        "0x0000000000000000000000000000000000001234=1gwei",
+    ]);
+
+    let ether = U256::from(1_000_000_000_000_000_000u128);
+    assert_eq!(genesis.alloc[&DEV_ACCOUNTS[0]].balance, Some(U256::from(5) * ether));
+    assert_eq!(genesis.alloc[&DEV_ACCOUNTS[1]].balance, Some(U256::from(5) * ether));
+    assert!(!genesis.alloc.contains_key(&DEV_ACCOUNTS[2]));
+    assert_eq!(genesis.alloc[&extra].balance, Some(U256::from(1_000_000_000u64)));
+
+    assert_eq!(genesis.config["chainId"], 1337);
+    assert_eq!(genesis.config["isthmusTime"], 0);
+    assert_eq!(genesis.config["rex4Time"], 0);
+    assert!(!genesis.config.contains_key("rex5Time"), "Rex5 is not active under Rex4");
+
+    assert!(Cmd::parse_from(["genesis", "--dev-accounts", "11"]).build_genesis().is_err());
+}
+
+#[test]
+fn test_genesis_alloc_loads_as_prestate() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("alloc.json");
+    let genesis = genesis(&["--dev-accounts", "1"]);
+    std::fs::write(&path, serde_json::to_string(&genesis.alloc).unwrap()).unwrap();
+
+    let prestate_args =
+        PreStateArgs::parse_from(["mega-evme", "--prestate", path.to_str().unwrap()]);
+    let prestate = prestate_args.load_prestate(&DEV_ACCOUNTS[0]).expect("load_prestate");
+    let state = EvmeState::new_empty(prestate, Default::default());
+
+    let dev = state.basic_ref(DEV_ACCOUNTS[0]).unwrap().expect("the account is funded");
+    assert_eq!(Some(dev.balance), genesis.alloc[&DEV_ACCOUNTS[0]].balance);
+    let oracle = state.basic_ref(ORACLE_CONTRACT_ADDRESS).unwrap().expect("the oracle exists");
+    assert_eq!(Some(oracle.code_hash), genesis.alloc[&ORACLE_CONTRACT_ADDRESS].code_hash);
+}
@@ -0,0 +1,22 @@
+//! Regenerates the golden execution traces checked by the `golden` test target.
+//!
+//! Run with `cargo run -p mega-fixtures --bin bless-golden-traces`, then review and commit the
+//! changed traces.
+
+use std::{path::Path, process::ExitCode};
+
+use mega_evm::test_utils::{bless_golden_traces, GOLDEN_TRACES_DIR};
+
+fn main() -> ExitCode {
+    let dir = Path::new(GOLDEN_TRACES_DIR);
+    match bless_golden_traces(dir) {
+        Ok(written) => {
+            println!("wrote {written} golden traces to {}", dir.display());
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("failed to write the golden traces to {}: {err}", dir.display());
+            ExitCode::FAILURE
+        }
+    }
+}
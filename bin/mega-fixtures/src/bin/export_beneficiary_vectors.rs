@@ -0,0 +1,25 @@
+//! Exports the beneficiary access conformance vectors checked by the `golden` test target.
+//!
+//! Run with `cargo run -p mega-fixtures --bin export-beneficiary-vectors`, then review and commit
+//! the changed vectors.
+
+use std::{path::Path, process::ExitCode};
+
+use mega_evm::test_utils::{export_beneficiary_access_vectors, BENEFICIARY_VECTORS_FILE};
+
+fn main() -> ExitCode {
+    let path = Path::new(BENEFICIARY_VECTORS_FILE);
+    match export_beneficiary_access_vectors(path) {
+        Ok(written) => {
+            println!("wrote {written} beneficiary access vectors to {}", path.display());
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!(
+                "failed to write the beneficiary access vectors to {}: {err}",
+                path.display()
+            );
+            ExitCode::FAILURE
+        }
+    }
+}
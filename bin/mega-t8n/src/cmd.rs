@@ -4,7 +4,12 @@ use clap::Parser;
 use mega_evm::{
     revm::{
         context::{
-            block::BlockEnv, cfg::CfgEnv, either::Either, result::ExecutionResult, tx::TxEnv,
+            block::BlockEnv,
+            cfg::CfgEnv,
+            either::Either,
+            result::{ExecutionResult, InvalidTransaction},
+            transaction::TransactionType,
+            tx::TxEnv,
         },
         database::{CacheState, EmptyDB, State},
         primitives::{eip4844, hardfork::SpecId, Bytes, TxKind, B256},
@@ -16,11 +21,11 @@ use mega_evm::{
 use state_test::types::Env;
 
 use crate::{
-    calculate_logs_bloom, calculate_logs_root, calculate_state_root,
-    extract_post_state_alloc_from_state, load_alloc, load_env, load_from_stdin, load_transactions,
-    recover_address_from_secret_key, write_alloc_to_file, write_body_output, write_result_to_file,
-    RejectedTx, Result, StateAlloc, T8nError, T8nOutput, Transaction, TransactionLog,
-    TransactionReceipt, TransitionInputs, TransitionResults,
+    calculate_logs_bloom, calculate_logs_root, calculate_receipts_root, calculate_state_root,
+    calculate_tx_root, extract_post_state_alloc_from_state, load_alloc, load_env, load_from_stdin,
+    load_transactions, recover_address_from_secret_key, write_alloc_to_file, write_body_output,
+    write_result_to_file, RejectedTx, Result, StateAlloc, T8nError, T8nOutput, Transaction,
+    TransactionLog, TransactionReceipt, TransitionInputs, TransitionResults,
 };
 
 /// Executes a full state transition
@@ -160,12 +165,24 @@ impl Cmd {
         let mut all_logs = Vec::new();
         let mut receipts = Vec::new();
         let mut rejected = Vec::new();
+        let mut transactions = Vec::new();
 
         for (tx_index, tx_data) in inputs.txs.iter().enumerate() {
+            // Blob transactions have no `MegaETH` envelope and are never included in a block:
+            // reject them the way the EVM does instead of failing the whole transition.
+            if tx_data.tx_type == Some(TransactionType::Eip4844 as u8) {
+                rejected.push(RejectedTx {
+                    index: tx_index as u64,
+                    error: format!("{:?}", InvalidTransaction::Eip4844NotSupported),
+                });
+                continue;
+            }
+
             // Calculate transaction hash by converting to envelope
             let tx =
                 tx_data.to_envelope().map_err(|e| T8nError::InvalidTransaction(e.to_string()))?;
             let tx_hash = tx.tx_hash();
+            transactions.push(tx);
 
             // Convert transaction to TxEnv
             let tx_env = match self.convert_transaction_to_env(tx_data) {
@@ -296,8 +313,8 @@ impl Cmd {
 
         // Calculate roots
         let state_root = calculate_state_root(&state);
-        let tx_root = B256::default(); // TODO: Calculate transaction trie root
-        let receipts_root = B256::default(); // TODO: Calculate receipts root
+        let tx_root = calculate_tx_root(&transactions);
+        let receipts_root = calculate_receipts_root(&transactions, &receipts);
         let logs_hash = calculate_logs_root(&all_logs);
 
         // Extract post-state allocation
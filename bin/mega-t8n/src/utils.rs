@@ -1,16 +1,61 @@
 use std::collections::HashMap;
 
-use mega_evm::revm::{
-    database::{EmptyDB, State},
-    primitives::{alloy_primitives::Bloom, Address, Log, B256},
+use mega_evm::{
+    alloy_consensus::{Eip658Value, Receipt, ReceiptEnvelope, ReceiptWithBloom},
+    alloy_eips::Typed2718,
+    revm::{
+        database::{EmptyDB, State},
+        primitives::{alloy_primitives::Bloom, Address, Log, B256},
+    },
+    MegaTxEnvelope,
 };
 use state_test::types::AccountInfo;
 
-use crate::{Result, StateAlloc, T8nError};
+use crate::{Result, StateAlloc, T8nError, TransactionReceipt};
 
 /// Calculate state root from the final state
 pub(crate) fn calculate_state_root(state: &State<EmptyDB>) -> B256 {
-    state_test::utils::state_merkle_trie_root(state.cache.trie_account())
+    state_test::roots::state_root(state.cache.trie_account())
+}
+
+/// Calculate transactions root from the block's transactions
+pub(crate) fn calculate_tx_root(txs: &[MegaTxEnvelope]) -> B256 {
+    state_test::roots::tx_root(txs)
+}
+
+/// Calculate receipts root from the receipts of the block's transactions
+pub(crate) fn calculate_receipts_root(
+    txs: &[MegaTxEnvelope],
+    receipts: &[TransactionReceipt],
+) -> B256 {
+    let receipts: Vec<ReceiptEnvelope> = txs
+        .iter()
+        .zip(receipts)
+        .map(|(tx, receipt)| {
+            let logs: Vec<Log> = receipt
+                .logs
+                .iter()
+                .map(|log| Log::new_unchecked(log.address, log.topics.clone(), log.data.clone()))
+                .collect();
+            let logs_bloom = calculate_logs_bloom(&logs);
+            let receipt = ReceiptWithBloom {
+                receipt: Receipt {
+                    status: Eip658Value::Eip658(receipt.status == 1),
+                    cumulative_gas_used: receipt.cumulative_gas_used,
+                    logs,
+                },
+                logs_bloom,
+            };
+            match tx.ty() {
+                0 => ReceiptEnvelope::Legacy(receipt),
+                1 => ReceiptEnvelope::Eip2930(receipt),
+                2 => ReceiptEnvelope::Eip1559(receipt),
+                4 => ReceiptEnvelope::Eip7702(receipt),
+                ty => unreachable!("unsupported transaction type {ty}"),
+            }
+        })
+        .collect();
+    state_test::roots::receipts_root(&receipts)
 }
 
 /// Calculate logs root from all transaction logs
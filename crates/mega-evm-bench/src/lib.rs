@@ -0,0 +1,186 @@
+//! Programmatic opcode-cost benchmarks for mega-evm.
+//!
+//! [`bench_bytecode`] executes a contract under a given [`MegaSpecId`] and reports its gas usage
+//! and wall-clock timing. Results are collected in a [`BenchReport`], which exports to JSON and
+//! can be compared against a baseline report with percent [`Thresholds`], so CI and external
+//! tools can track opcode cost drift between releases.
+//!
+//! The [`bytecode`] module holds the workload generators shared with the criterion
+//! `comp_cost` benchmark of the `mega-evm` crate.
+
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+pub mod bytecode;
+
+pub mod report;
+
+pub use report::{BenchReport, Metric, Regression, Thresholds};
+
+use alloy_primitives::{address, Address, Bytes, U256};
+use core::hint::black_box;
+use mega_evm::{
+    revm::{
+        context::{
+            result::{ExecResultAndState, ExecutionResult},
+            tx::TxEnvBuilder,
+        },
+        ExecuteEvm,
+    },
+    test_utils::MemoryDatabase,
+    MegaChainConfig, MegaContext, MegaEvm, MegaHaltReason, MegaSpecId, MegaTransaction,
+};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Sender of benchmark transactions.
+pub const CALLER: Address = address!("0000000000000000000000000000000000100000");
+
+/// Address the benchmarked bytecode is deployed at.
+pub const CONTRACT: Address = address!("0000000000000000000000000000000000100002");
+
+/// Gas limit of benchmark transactions. High enough that no workload runs out of gas.
+pub const BENCH_TX_GAS_LIMIT: u64 = 10_000_000_000;
+
+/// Execute `bytecode` under `spec` and return the transaction execution result.
+///
+/// # Panics
+///
+/// Panics if the transaction fails or does not spend more than the intrinsic 21000 gas, which
+/// means the workload did not run.
+pub fn execute_bytecode(
+    bytecode: &Bytes,
+    spec: MegaSpecId,
+) -> ExecResultAndState<ExecutionResult<MegaHaltReason>> {
+    let db = MemoryDatabase::default()
+        .account_code(CONTRACT, bytecode.clone())
+        .account_balance(CALLER, U256::from(10).pow(U256::from(18)));
+
+    let context = MegaContext::new(db, spec)
+        .with_chain_config(MegaChainConfig::default().without_operator_fee());
+    let mut evm = MegaEvm::new(context);
+
+    let tx = TxEnvBuilder::new()
+        .caller(CALLER)
+        .call(CONTRACT)
+        .gas_limit(BENCH_TX_GAS_LIMIT)
+        .build_fill();
+    let mut mega_tx = MegaTransaction::new(tx);
+    mega_tx.enveloped_tx = Some(Bytes::new());
+
+    let r = evm.transact(mega_tx).expect("transaction should succeed");
+    assert!(r.result.is_success(), "transaction should succeed: {:?}", r.result);
+    assert!(r.result.gas_used() > 21000, "transaction should spend more than 21000 gas");
+    r
+}
+
+/// Execute `bytecode` under `spec` and return the gas used.
+///
+/// # Panics
+///
+/// See [`execute_bytecode`].
+pub fn execute_and_get_gas(bytecode: &Bytes, spec: MegaSpecId) -> u64 {
+    execute_bytecode(bytecode, spec).result.gas_used()
+}
+
+/// Sampling parameters of [`bench_bytecode_with`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Untimed executions run before sampling.
+    pub warmup: u32,
+    /// Timed executions. Must be non-zero.
+    pub iterations: u32,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self { warmup: 3, iterations: 20 }
+    }
+}
+
+/// Gas usage and timing of one workload under one spec.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchResult {
+    /// Name of the spec the workload ran under.
+    pub spec: String,
+    /// Gas used by the benchmark transaction.
+    pub gas_used: u64,
+    /// Number of timed executions.
+    pub iterations: u32,
+    /// Mean execution time in nanoseconds.
+    pub mean_ns: u64,
+    /// Median execution time in nanoseconds.
+    pub median_ns: u64,
+    /// Fastest execution time in nanoseconds.
+    pub min_ns: u64,
+}
+
+impl BenchResult {
+    /// Median nanoseconds spent per unit of gas, the cost-to-price ratio tracked across
+    /// releases.
+    pub fn ns_per_gas(&self) -> f64 {
+        self.median_ns as f64 / self.gas_used as f64
+    }
+}
+
+/// Benchmark `bytecode` under `spec` with the default [`BenchConfig`].
+///
+/// # Panics
+///
+/// See [`execute_bytecode`].
+pub fn bench_bytecode(spec: MegaSpecId, bytecode: &Bytes) -> BenchResult {
+    bench_bytecode_with(spec, bytecode, BenchConfig::default())
+}
+
+/// Benchmark `bytecode` under `spec`.
+///
+/// Every execution builds a fresh EVM over a fresh database, so the timing includes the same
+/// setup as the `comp_cost` criterion benchmark.
+///
+/// # Panics
+///
+/// Panics if `config.iterations` is zero, and in the cases listed in [`execute_bytecode`].
+pub fn bench_bytecode_with(spec: MegaSpecId, bytecode: &Bytes, config: BenchConfig) -> BenchResult {
+    assert!(config.iterations > 0, "at least one timed iteration is required");
+    let gas_used = execute_and_get_gas(bytecode, spec);
+    for _ in 0..config.warmup {
+        black_box(execute_bytecode(bytecode, black_box(spec)));
+    }
+
+    let mut samples: Vec<Duration> = (0..config.iterations)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(execute_bytecode(bytecode, black_box(spec)));
+            start.elapsed()
+        })
+        .collect();
+    samples.sort_unstable();
+    let total: Duration = samples.iter().sum();
+
+    BenchResult {
+        spec: spec.to_string(),
+        gas_used,
+        iterations: config.iterations,
+        mean_ns: (total / config.iterations).as_nanos() as u64,
+        median_ns: samples[samples.len() / 2].as_nanos() as u64,
+        min_ns: samples[0].as_nanos() as u64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bytecode::generate_add_bytecode;
+
+    #[test]
+    fn test_bench_bytecode_reports_gas_and_timing() {
+        let bytecode = generate_add_bytecode(10);
+        let config = BenchConfig { warmup: 0, iterations: 3 };
+        let result = bench_bytecode_with(MegaSpecId::REX5, &bytecode, config);
+        assert_eq!(result.gas_used, execute_and_get_gas(&bytecode, MegaSpecId::REX5));
+        assert_eq!(result.iterations, 3);
+        assert_eq!(result.spec, MegaSpecId::REX5.to_string());
+        assert!(result.min_ns <= result.median_ns);
+        assert!(result.ns_per_gas() >= 0.0);
+    }
+}
@@ -0,0 +1,183 @@
+//! Benchmark reports: JSON export and regression checks against a baseline.
+
+use crate::BenchResult;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt};
+
+/// Named benchmark results of one run.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BenchReport {
+    /// Results keyed by benchmark name, e.g. `keccak256_1KB/rex5`.
+    pub results: BTreeMap<String, BenchResult>,
+}
+
+impl BenchReport {
+    /// Record `result` under `name`, replacing any previous result with that name.
+    pub fn insert(&mut self, name: impl Into<String>, result: BenchResult) {
+        self.results.insert(name.into(), result);
+    }
+
+    /// Render the report as JSON.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("bench report is serializable")
+    }
+
+    /// Parse a report previously exported with [`to_json`](Self::to_json).
+    ///
+    /// # Errors
+    ///
+    /// Returns the JSON error if `s` is not a valid report.
+    pub fn from_json(s: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(s)
+    }
+
+    /// Returns the benchmarks that regressed relative to `baseline`.
+    ///
+    /// Only benchmarks present in both reports are compared. Timing regresses when the median
+    /// grows by more than [`Thresholds::time_pct`]; gas regresses when it drifts in either
+    /// direction by more than [`Thresholds::gas_pct`], since any gas change alters the price of
+    /// the opcode.
+    pub fn compare(&self, baseline: &Self, thresholds: &Thresholds) -> Vec<Regression> {
+        let mut regressions = Vec::new();
+        for (name, current) in &self.results {
+            let Some(base) = baseline.results.get(name) else { continue };
+
+            let change = percent_change(base.median_ns as f64, current.median_ns as f64);
+            if change > thresholds.time_pct {
+                regressions.push(Regression {
+                    name: name.clone(),
+                    metric: Metric::Time,
+                    baseline: base.median_ns,
+                    current: current.median_ns,
+                    change_pct: change,
+                });
+            }
+
+            let change = percent_change(base.gas_used as f64, current.gas_used as f64);
+            if change.abs() > thresholds.gas_pct {
+                regressions.push(Regression {
+                    name: name.clone(),
+                    metric: Metric::Gas,
+                    baseline: base.gas_used,
+                    current: current.gas_used,
+                    change_pct: change,
+                });
+            }
+        }
+        regressions
+    }
+}
+
+/// Returns the change from `base` to `current` in percent of `base`.
+fn percent_change(base: f64, current: f64) -> f64 {
+    if base == 0.0 {
+        return if current == 0.0 { 0.0 } else { f64::INFINITY };
+    }
+    (current - base) / base * 100.0
+}
+
+/// Percent tolerances used by [`BenchReport::compare`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Thresholds {
+    /// Largest tolerated increase of the median execution time, in percent.
+    pub time_pct: f64,
+    /// Largest tolerated change of gas used, in percent.
+    pub gas_pct: f64,
+}
+
+impl Default for Thresholds {
+    /// Tolerate 10% timing noise and no gas drift.
+    fn default() -> Self {
+        Self { time_pct: 10.0, gas_pct: 0.0 }
+    }
+}
+
+/// The measurement a [`Regression`] refers to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    /// Median execution time in nanoseconds.
+    Time,
+    /// Gas used.
+    Gas,
+}
+
+/// A benchmark whose measurement moved beyond its threshold.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Regression {
+    /// Name of the benchmark.
+    pub name: String,
+    /// The regressed measurement.
+    pub metric: Metric,
+    /// Baseline value.
+    pub baseline: u64,
+    /// Current value.
+    pub current: u64,
+    /// Change from the baseline, in percent.
+    pub change_pct: f64,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = match self.metric {
+            Metric::Time => "ns",
+            Metric::Gas => "gas",
+        };
+        write!(
+            f,
+            "{}: {} {unit} -> {} {unit} ({:+.2}%)",
+            self.name, self.baseline, self.current, self.change_pct
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(gas_used: u64, median_ns: u64) -> BenchResult {
+        BenchResult {
+            spec: "Rex5".to_string(),
+            gas_used,
+            iterations: 10,
+            mean_ns: median_ns,
+            median_ns,
+            min_ns: median_ns,
+        }
+    }
+
+    fn report(entries: &[(&str, u64, u64)]) -> BenchReport {
+        let mut report = BenchReport::default();
+        for &(name, gas, ns) in entries {
+            report.insert(name, result(gas, ns));
+        }
+        report
+    }
+
+    #[test]
+    fn test_report_json_roundtrip() {
+        let report = report(&[("add/rex5", 30_000, 1_000), ("exp_256bit/rex5", 40_000, 2_000)]);
+        let json = report.to_json();
+        assert_eq!(json["results"]["add/rex5"]["gasUsed"], 30_000);
+        assert_eq!(json["results"]["add/rex5"]["medianNs"], 1_000);
+        assert_eq!(BenchReport::from_json(&json.to_string()).unwrap(), report);
+    }
+
+    #[test]
+    fn test_compare_flags_time_and_gas_regressions() {
+        let baseline = report(&[("a", 100, 1_000), ("b", 100, 1_000), ("c", 100, 1_000)]);
+        let current = report(&[
+            ("a", 100, 1_050), // within the 10% timing tolerance
+            ("b", 100, 1_200), // 20% slower
+            ("c", 90, 500),    // faster but cheaper: gas drift
+            ("new", 1, 1),     // not in the baseline
+        ]);
+        let regressions = current.compare(&baseline, &Thresholds::default());
+        assert_eq!(regressions.len(), 2);
+        assert_eq!((regressions[0].name.as_str(), regressions[0].metric), ("b", Metric::Time));
+        assert_eq!((regressions[1].name.as_str(), regressions[1].metric), ("c", Metric::Gas));
+        assert_eq!(regressions[1].to_string(), "c: 100 gas -> 90 gas (-10.00%)");
+
+        let lenient = Thresholds { time_pct: 25.0, gas_pct: 15.0 };
+        assert!(current.compare(&baseline, &lenient).is_empty());
+    }
+}
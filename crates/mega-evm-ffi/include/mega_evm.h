@@ -0,0 +1,102 @@
+/*
+ * C ABI of mega-evm, for executing MegaETH transactions from non-Rust tooling.
+ *
+ * Link against the `mega_evm_ffi` cdylib or staticlib built from `crates/mega-evm-ffi`. The Rust
+ * docs of that crate are the reference for the JSON schemas of the environment and the outcome.
+ */
+
+#ifndef MEGA_EVM_H
+#define MEGA_EVM_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Version of this ABI, bumped on every incompatible change. */
+#define MEGA_EVM_ABI_VERSION 1
+
+/* Return codes of the state callbacks. Negative codes are errors and abort the execution. */
+#define MEGA_EVM_STATE_FOUND 0
+#define MEGA_EVM_STATE_NOT_FOUND 1
+
+typedef enum MegaEvmStatus {
+    /* Executed; the output holds the JSON outcome. Reverted and halted transactions count. */
+    MEGA_EVM_STATUS_OK = 0,
+    /* env, tx, callbacks or out is null. */
+    MEGA_EVM_STATUS_NULL_ARGUMENT = 1,
+    /* The environment is not valid JSON of an execution environment. */
+    MEGA_EVM_STATUS_INVALID_ENV = 2,
+    /* The transaction could not be decoded or its sender recovered. */
+    MEGA_EVM_STATUS_INVALID_TRANSACTION = 3,
+    /* The transaction failed validation or a state callback returned an error. */
+    MEGA_EVM_STATUS_EXECUTION_FAILED = 4,
+    /* Execution panicked. This is a bug in mega-evm. */
+    MEGA_EVM_STATUS_PANIC = 5,
+} MegaEvmStatus;
+
+/* A byte buffer allocated by the library, freed with mega_evm_buffer_free. */
+typedef struct MegaEvmBuffer {
+    uint8_t *data;
+    size_t len;
+} MegaEvmBuffer;
+
+/* An account returned by the basic callback. */
+typedef struct MegaEvmAccount {
+    /* 32-byte big-endian integer. */
+    uint8_t balance[32];
+    uint64_t nonce;
+    /* The code is requested by this hash through the code_by_hash callback. */
+    uint8_t code_hash[32];
+} MegaEvmAccount;
+
+/* Loads the account at the 20-byte address into out. */
+typedef int32_t (*MegaEvmBasicFn)(void *ctx, const uint8_t *address, MegaEvmAccount *out);
+
+/* Points code and code_len at the code of the 32-byte code_hash. The code must stay valid until
+ * the callback returns. */
+typedef int32_t (*MegaEvmCodeByHashFn)(void *ctx, const uint8_t *code_hash, const uint8_t **code,
+                                       size_t *code_len);
+
+/* Writes the 32-byte big-endian value of the 32-byte slot of the 20-byte address to value. */
+typedef int32_t (*MegaEvmStorageFn)(void *ctx, const uint8_t *address, const uint8_t *slot,
+                                    uint8_t *value);
+
+/* Writes the 32-byte hash of block number to hash. */
+typedef int32_t (*MegaEvmBlockHashFn)(void *ctx, uint64_t number, uint8_t *hash);
+
+/* The callbacks through which the state is read. ctx is passed to every callback. A null
+ * callback behaves as if no entry exists. */
+typedef struct MegaEvmStateCallbacks {
+    void *ctx;
+    MegaEvmBasicFn basic;
+    MegaEvmCodeByHashFn code_by_hash;
+    MegaEvmStorageFn storage;
+    MegaEvmBlockHashFn block_hash;
+} MegaEvmStateCallbacks;
+
+/* Returns MEGA_EVM_ABI_VERSION of the linked library. */
+uint32_t mega_evm_abi_version(void);
+
+/*
+ * Executes the EIP-2718 encoded transaction tx in the JSON environment env, e.g.
+ * {"spec": "REX4", "chain": {"chainId": 6342, ...}, "block": {"number": "0xa", ...}}.
+ *
+ * Nothing is committed. On MEGA_EVM_STATUS_OK, out receives the JSON outcome: the sender, the
+ * execution result, the touched accounts and the resource usage. On any other status, out
+ * receives a UTF-8 error message. Either way, out must be freed with mega_evm_buffer_free.
+ */
+MegaEvmStatus mega_evm_execute(const uint8_t *env, size_t env_len, const uint8_t *tx,
+                               size_t tx_len, const MegaEvmStateCallbacks *callbacks,
+                               MegaEvmBuffer *out);
+
+/* Frees a buffer returned by the library. Freeing an empty buffer is a no-op. */
+void mega_evm_buffer_free(MegaEvmBuffer buffer);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* MEGA_EVM_H */
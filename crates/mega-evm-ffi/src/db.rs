@@ -0,0 +1,172 @@
+//! [`Database`] reading the state through the callbacks of the caller.
+
+use core::ffi::c_void;
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+use mega_evm::revm::{
+    database::DBErrorMarker,
+    primitives::{StorageKey, StorageValue},
+    state::{AccountInfo, Bytecode},
+    Database,
+};
+
+/// Return code of a state callback: the requested entry was written to the output.
+pub const MEGA_EVM_STATE_FOUND: i32 = 0;
+/// Return code of a state callback: the requested entry does not exist. The output is ignored.
+pub const MEGA_EVM_STATE_NOT_FOUND: i32 = 1;
+
+/// An account as returned by [`MegaEvmStateCallbacks::basic`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MegaEvmAccount {
+    /// The balance, as a 32-byte big-endian integer.
+    pub balance: [u8; 32],
+    /// The nonce.
+    pub nonce: u64,
+    /// The code hash. The code is then requested by hash through
+    /// [`MegaEvmStateCallbacks::code_by_hash`].
+    pub code_hash: [u8; 32],
+}
+
+/// Loads the account at the 20-byte `address` into `out`.
+pub type MegaEvmBasicFn =
+    unsafe extern "C" fn(ctx: *mut c_void, address: *const u8, out: *mut MegaEvmAccount) -> i32;
+
+/// Points `code` and `code_len` at the code of the 32-byte `code_hash`. The code must stay valid
+/// until the callback returns, and is copied before it does.
+pub type MegaEvmCodeByHashFn = unsafe extern "C" fn(
+    ctx: *mut c_void,
+    code_hash: *const u8,
+    code: *mut *const u8,
+    code_len: *mut usize,
+) -> i32;
+
+/// Writes the 32-byte big-endian value of the 32-byte `slot` of the 20-byte `address` to `value`.
+pub type MegaEvmStorageFn = unsafe extern "C" fn(
+    ctx: *mut c_void,
+    address: *const u8,
+    slot: *const u8,
+    value: *mut u8,
+) -> i32;
+
+/// Writes the 32-byte hash of the block `number` to `hash`.
+pub type MegaEvmBlockHashFn =
+    unsafe extern "C" fn(ctx: *mut c_void, number: u64, hash: *mut u8) -> i32;
+
+/// The callbacks through which the state is read during execution.
+///
+/// Every callback receives `ctx` as its first argument and returns [`MEGA_EVM_STATE_FOUND`],
+/// [`MEGA_EVM_STATE_NOT_FOUND`] or a negative error code, which aborts the execution. A null
+/// callback behaves as if no entry exists: accounts and storage are empty, code is empty and block
+/// hashes are zero.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct MegaEvmStateCallbacks {
+    /// Opaque pointer passed to every callback.
+    pub ctx: *mut c_void,
+    /// Loads an account.
+    pub basic: Option<MegaEvmBasicFn>,
+    /// Loads the code of a code hash.
+    pub code_by_hash: Option<MegaEvmCodeByHashFn>,
+    /// Loads a storage slot.
+    pub storage: Option<MegaEvmStorageFn>,
+    /// Loads a block hash.
+    pub block_hash: Option<MegaEvmBlockHashFn>,
+}
+
+/// Error of a state callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("state callback `{callback}` failed with code {code}")]
+pub struct CallbackError {
+    /// The name of the callback.
+    pub callback: &'static str,
+    /// The code it returned.
+    pub code: i32,
+}
+
+impl DBErrorMarker for CallbackError {}
+
+/// [`Database`] over [`MegaEvmStateCallbacks`].
+#[derive(Debug)]
+pub struct CallbackDatabase {
+    callbacks: MegaEvmStateCallbacks,
+}
+
+impl CallbackDatabase {
+    /// Creates a database reading through `callbacks`.
+    ///
+    /// # Safety
+    ///
+    /// Every non-null callback must be safe to call with `callbacks.ctx` and valid output
+    /// pointers for as long as the database is used, and must honor the contracts of
+    /// [`MegaEvmStateCallbacks`].
+    pub unsafe fn new(callbacks: MegaEvmStateCallbacks) -> Self {
+        Self { callbacks }
+    }
+}
+
+/// Maps the return code of `callback` to whether the entry was found.
+fn check(callback: &'static str, code: i32) -> Result<bool, CallbackError> {
+    match code {
+        MEGA_EVM_STATE_FOUND => Ok(true),
+        MEGA_EVM_STATE_NOT_FOUND => Ok(false),
+        code => Err(CallbackError { callback, code }),
+    }
+}
+
+impl Database for CallbackDatabase {
+    type Error = CallbackError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let Some(basic) = self.callbacks.basic else { return Ok(None) };
+        let mut account = MegaEvmAccount::default();
+        // SAFETY: guaranteed by the caller of `CallbackDatabase::new`.
+        let code = unsafe { basic(self.callbacks.ctx, address.as_ptr(), &mut account) };
+        Ok(check("basic", code)?.then(|| AccountInfo {
+            balance: U256::from_be_bytes(account.balance),
+            nonce: account.nonce,
+            code_hash: B256::from(account.code_hash),
+            code: None,
+        }))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let Some(code_by_hash) = self.callbacks.code_by_hash else {
+            return Ok(Bytecode::default());
+        };
+        let (mut code, mut code_len) = (core::ptr::null(), 0);
+        // SAFETY: guaranteed by the caller of `CallbackDatabase::new`.
+        let ret = unsafe {
+            code_by_hash(self.callbacks.ctx, code_hash.as_ptr(), &mut code, &mut code_len)
+        };
+        if !check("code_by_hash", ret)? || code.is_null() {
+            return Ok(Bytecode::default());
+        }
+        // SAFETY: the callback points `code` at `code_len` readable bytes.
+        let bytes = unsafe { core::slice::from_raw_parts(code, code_len) };
+        Ok(Bytecode::new_raw(Bytes::copy_from_slice(bytes)))
+    }
+
+    fn storage(
+        &mut self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        let Some(storage) = self.callbacks.storage else { return Ok(StorageValue::ZERO) };
+        let slot = index.to_be_bytes::<32>();
+        let mut value = [0u8; 32];
+        // SAFETY: guaranteed by the caller of `CallbackDatabase::new`.
+        let code = unsafe {
+            storage(self.callbacks.ctx, address.as_ptr(), slot.as_ptr(), value.as_mut_ptr())
+        };
+        Ok(if check("storage", code)? { U256::from_be_bytes(value) } else { U256::ZERO })
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        let Some(block_hash) = self.callbacks.block_hash else { return Ok(B256::ZERO) };
+        let mut hash = B256::ZERO;
+        // SAFETY: guaranteed by the caller of `CallbackDatabase::new`.
+        let code = unsafe { block_hash(self.callbacks.ctx, number, hash.as_mut_ptr()) };
+        Ok(if check("block_hash", code)? { hash } else { B256::ZERO })
+    }
+}
@@ -0,0 +1,142 @@
+//! Safe core of the C ABI: decoding the inputs, executing and encoding the outcome.
+
+use std::collections::BTreeMap;
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+use mega_evm::{
+    alloy_consensus::transaction::SignerRecoverable,
+    alloy_eips::Decodable2718,
+    alloy_evm::FromTxWithEncoded,
+    revm::{
+        context::{result::ExecutionResult, BlockEnv},
+        Database,
+    },
+    MegaChainConfig, MegaContext, MegaEvm, MegaHaltReason, MegaSpecId, MegaTransaction,
+    MegaTxEnvelope,
+};
+use serde::{Deserialize, Serialize};
+
+/// The environment a transaction is executed in, decoded from the JSON passed to
+/// `mega_evm_execute`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionEnv {
+    /// The spec to execute under, e.g. `"REX4"`.
+    pub spec: MegaSpecId,
+    /// The chain id and chain-level parameters.
+    pub chain: MegaChainConfig,
+    /// The block the transaction is executed in.
+    pub block: BlockEnv,
+}
+
+/// The outcome of a transaction, encoded as the JSON returned by `mega_evm_execute`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionOutcome {
+    /// The recovered sender of the transaction.
+    pub sender: Address,
+    /// The execution result: success with logs and output, revert, or halt.
+    pub result: ExecutionResult<MegaHaltReason>,
+    /// The accounts touched by the transaction, with their post-execution state.
+    pub state: BTreeMap<Address, AccountChange>,
+    /// The data size usage in bytes.
+    pub data_size: u64,
+    /// The number of KV updates.
+    pub kv_updates: u64,
+    /// The compute gas used.
+    pub compute_gas_used: u64,
+    /// The state growth used.
+    pub state_growth_used: u64,
+}
+
+/// The post-execution state of an account touched by a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountChange {
+    /// The balance.
+    pub balance: U256,
+    /// The nonce.
+    pub nonce: u64,
+    /// The code hash.
+    pub code_hash: B256,
+    /// The code, if the account was created by the transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// The storage slots changed by the transaction, with their new values.
+    pub storage: BTreeMap<U256, U256>,
+    /// Whether the account was selfdestructed.
+    pub selfdestructed: bool,
+}
+
+/// Errors of [`execute`].
+#[derive(Debug, thiserror::Error)]
+pub enum ExecuteError {
+    /// The environment is not valid JSON of an [`ExecutionEnv`].
+    #[error("invalid environment: {0}")]
+    InvalidEnv(#[from] serde_json::Error),
+    /// The transaction is not a valid EIP-2718 encoded transaction.
+    #[error("invalid transaction: {0}")]
+    InvalidTransaction(String),
+    /// The transaction could not be executed, e.g. because it failed validation or the state
+    /// callbacks reported an error.
+    #[error("execution failed: {0}")]
+    Execution(String),
+}
+
+/// Executes the EIP-2718 encoded transaction `tx_bytes` in `env` against the state of `db`.
+///
+/// The sender is recovered from the signature, or taken from the `from` field of a deposit
+/// transaction. Nothing is committed: the changes are only reported in the outcome.
+pub fn execute<DB: Database>(
+    env: &ExecutionEnv,
+    tx_bytes: &[u8],
+    db: DB,
+) -> Result<ExecutionOutcome, ExecuteError> {
+    let envelope = MegaTxEnvelope::decode_2718(&mut &tx_bytes[..])
+        .map_err(|err| ExecuteError::InvalidTransaction(err.to_string()))?;
+    let sender = envelope
+        .recover_signer()
+        .map_err(|err| ExecuteError::InvalidTransaction(err.to_string()))?;
+    let tx = MegaTransaction::from_encoded_tx(&envelope, sender, Bytes::copy_from_slice(tx_bytes));
+
+    let context =
+        MegaContext::new(db, env.spec).with_chain_config(env.chain).with_block(env.block.clone());
+    let outcome = MegaEvm::new(context)
+        .execute_transaction(tx)
+        .map_err(|err| ExecuteError::Execution(err.to_string()))?;
+
+    let state = outcome
+        .state
+        .into_iter()
+        .filter(|(_, account)| account.is_touched())
+        .map(|(address, account)| {
+            let change = AccountChange {
+                balance: account.info.balance,
+                nonce: account.info.nonce,
+                code_hash: account.info.code_hash,
+                code: account
+                    .is_created()
+                    .then(|| account.info.code.as_ref().map(|code| code.original_bytes()))
+                    .flatten(),
+                storage: account
+                    .storage
+                    .iter()
+                    .filter(|(_, slot)| slot.is_changed())
+                    .map(|(key, slot)| (*key, slot.present_value))
+                    .collect(),
+                selfdestructed: account.is_selfdestructed(),
+            };
+            (address, change)
+        })
+        .collect();
+
+    Ok(ExecutionOutcome {
+        sender,
+        result: outcome.result,
+        state,
+        data_size: outcome.data_size,
+        kv_updates: outcome.kv_updates,
+        compute_gas_used: outcome.compute_gas_used,
+        state_growth_used: outcome.state_growth_used,
+    })
+}
@@ -0,0 +1,282 @@
+//! C ABI for executing `MegaETH` transactions with mega-evm.
+//!
+//! Non-Rust tooling links against this crate (as a `cdylib` or `staticlib`) and calls
+//! [`mega_evm_execute`] with an EIP-2718 encoded transaction, a JSON [`ExecutionEnv`] and a set of
+//! [`MegaEvmStateCallbacks`] reading its own state, instead of shelling out to `mega-evme`. The
+//! outcome is returned as a JSON [`ExecutionOutcome`] in a [`MegaEvmBuffer`] that must be freed
+//! with [`mega_evm_buffer_free`].
+//!
+//! The declarations are mirrored in `include/mega_evm.h`. The functions and types prefixed with
+//! `mega_evm_`/`MegaEvm` are the stable ABI; the Rust API of [`execute`] is not.
+
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+mod db;
+mod execute;
+
+pub use db::*;
+pub use execute::*;
+
+use core::ptr;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Version of the ABI, bumped on every incompatible change of the declarations in
+/// `include/mega_evm.h` or of the JSON schemas.
+pub const MEGA_EVM_ABI_VERSION: u32 = 1;
+
+/// Status returned by [`mega_evm_execute`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MegaEvmStatus {
+    /// The transaction was executed; the output holds the JSON [`ExecutionOutcome`]. Reverted and
+    /// halted transactions are executed too.
+    Ok = 0,
+    /// A required pointer argument is null.
+    NullArgument = 1,
+    /// The environment is not valid JSON of an [`ExecutionEnv`].
+    InvalidEnv = 2,
+    /// The transaction could not be decoded or its sender recovered.
+    InvalidTransaction = 3,
+    /// The transaction failed validation or a state callback returned an error.
+    ExecutionFailed = 4,
+    /// Execution panicked. This is a bug in mega-evm.
+    Panic = 5,
+}
+
+/// A byte buffer allocated by this library, freed with [`mega_evm_buffer_free`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct MegaEvmBuffer {
+    /// The bytes, or null for an empty buffer.
+    pub data: *mut u8,
+    /// The number of bytes.
+    pub len: usize,
+}
+
+impl MegaEvmBuffer {
+    const fn empty() -> Self {
+        Self { data: ptr::null_mut(), len: 0 }
+    }
+
+    fn from_vec(bytes: Vec<u8>) -> Self {
+        let len = bytes.len();
+        let data = Box::into_raw(bytes.into_boxed_slice()).cast::<u8>();
+        Self { data, len }
+    }
+}
+
+/// Returns [`MEGA_EVM_ABI_VERSION`].
+#[no_mangle]
+pub extern "C" fn mega_evm_abi_version() -> u32 {
+    MEGA_EVM_ABI_VERSION
+}
+
+/// Executes a transaction and writes its outcome to `out`.
+///
+/// `env` points at `env_len` bytes of JSON [`ExecutionEnv`], `tx` at `tx_len` bytes of EIP-2718
+/// encoded transaction. The state is read through `callbacks` during the call. On
+/// [`MegaEvmStatus::Ok`], `out` receives the JSON [`ExecutionOutcome`]; on any other status it
+/// receives a UTF-8 error message. Either way, `out` must be freed with [`mega_evm_buffer_free`].
+///
+/// # Safety
+///
+/// `env` and `tx` must be valid for reads of `env_len` and `tx_len` bytes, `callbacks` must point
+/// at callbacks honoring the contracts of [`MegaEvmStateCallbacks`], and `out` must be valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn mega_evm_execute(
+    env: *const u8,
+    env_len: usize,
+    tx: *const u8,
+    tx_len: usize,
+    callbacks: *const MegaEvmStateCallbacks,
+    out: *mut MegaEvmBuffer,
+) -> MegaEvmStatus {
+    if out.is_null() {
+        return MegaEvmStatus::NullArgument;
+    }
+    // SAFETY: `out` is non-null and valid for writes.
+    unsafe { out.write(MegaEvmBuffer::empty()) };
+    if env.is_null() || tx.is_null() || callbacks.is_null() {
+        let message = "env, tx and callbacks must not be null";
+        // SAFETY: as above.
+        unsafe { out.write(MegaEvmBuffer::from_vec(message.into())) };
+        return MegaEvmStatus::NullArgument;
+    }
+
+    // SAFETY: the pointers are non-null and valid for reads, as required by the caller.
+    let (env, tx, callbacks) = unsafe {
+        (
+            core::slice::from_raw_parts(env, env_len),
+            core::slice::from_raw_parts(tx, tx_len),
+            *callbacks,
+        )
+    };
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let env: ExecutionEnv = serde_json::from_slice(env)?;
+        // SAFETY: the callbacks honor their contracts, as required by the caller.
+        let db = unsafe { CallbackDatabase::new(callbacks) };
+        execute(&env, tx, db)
+    }));
+
+    let (status, bytes) = match result {
+        Ok(Ok(outcome)) => (
+            MegaEvmStatus::Ok,
+            serde_json::to_vec(&outcome).expect("execution outcome serializes to JSON"),
+        ),
+        Ok(Err(err)) => {
+            let status = match err {
+                ExecuteError::InvalidEnv(_) => MegaEvmStatus::InvalidEnv,
+                ExecuteError::InvalidTransaction(_) => MegaEvmStatus::InvalidTransaction,
+                ExecuteError::Execution(_) => MegaEvmStatus::ExecutionFailed,
+            };
+            (status, err.to_string().into_bytes())
+        }
+        Err(_) => (MegaEvmStatus::Panic, b"execution panicked".to_vec()),
+    };
+    // SAFETY: `out` is non-null and valid for writes.
+    unsafe { out.write(MegaEvmBuffer::from_vec(bytes)) };
+    status
+}
+
+/// Frees a buffer returned by this library. Freeing an empty buffer is a no-op.
+///
+/// # Safety
+///
+/// `buffer` must have been returned by this library and not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn mega_evm_buffer_free(buffer: MegaEvmBuffer) {
+    if buffer.data.is_null() {
+        return;
+    }
+    // SAFETY: the buffer was allocated by `MegaEvmBuffer::from_vec` as a boxed slice.
+    drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(buffer.data, buffer.len)) });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, keccak256, Address, Bytes, B256, U256};
+    use core::ffi::c_void;
+    use mega_evm::{
+        alloy_consensus::Sealed,
+        alloy_eips::Encodable2718,
+        op_alloy_consensus::TxDeposit,
+        revm::{context::BlockEnv, primitives::TxKind},
+        test_utils::BytecodeBuilder,
+        MegaChainConfig, MegaSpecId, MegaTxEnvelope,
+    };
+
+    const SENDER: Address = address!("2000000000000000000000000000000000000001");
+    const CONTRACT: Address = address!("1000000000000000000000000000000000000001");
+
+    struct TestState {
+        code: Bytes,
+    }
+
+    unsafe extern "C" fn basic(
+        ctx: *mut c_void,
+        address: *const u8,
+        out: *mut MegaEvmAccount,
+    ) -> i32 {
+        let state = unsafe { &*ctx.cast::<TestState>() };
+        let address = Address::from_slice(unsafe { core::slice::from_raw_parts(address, 20) });
+        if address != CONTRACT {
+            return MEGA_EVM_STATE_NOT_FOUND;
+        }
+        let account = MegaEvmAccount { code_hash: keccak256(&state.code).0, ..Default::default() };
+        unsafe { out.write(account) };
+        MEGA_EVM_STATE_FOUND
+    }
+
+    unsafe extern "C" fn code_by_hash(
+        ctx: *mut c_void,
+        _code_hash: *const u8,
+        code: *mut *const u8,
+        code_len: *mut usize,
+    ) -> i32 {
+        let state = unsafe { &*ctx.cast::<TestState>() };
+        unsafe {
+            code.write(state.code.as_ptr());
+            code_len.write(state.code.len());
+        }
+        MEGA_EVM_STATE_FOUND
+    }
+
+    unsafe extern "C" fn failing_storage(
+        _ctx: *mut c_void,
+        _address: *const u8,
+        _slot: *const u8,
+        _value: *mut u8,
+    ) -> i32 {
+        -7
+    }
+
+    fn deposit_tx() -> Vec<u8> {
+        let deposit = TxDeposit {
+            source_hash: B256::repeat_byte(1),
+            from: SENDER,
+            to: TxKind::Call(CONTRACT),
+            mint: 0,
+            value: U256::ZERO,
+            gas_limit: 1_000_000,
+            is_system_transaction: false,
+            input: Bytes::new(),
+        };
+        MegaTxEnvelope::Deposit(Sealed::new(deposit)).encoded_2718()
+    }
+
+    fn env_json() -> Vec<u8> {
+        let env = ExecutionEnv {
+            spec: MegaSpecId::REX4,
+            chain: MegaChainConfig::new(6342).without_operator_fee(),
+            block: BlockEnv { number: U256::from(10), gas_limit: 30_000_000, ..Default::default() },
+        };
+        serde_json::to_vec(&env).unwrap()
+    }
+
+    fn run(state: &mut TestState, storage: Option<MegaEvmStorageFn>) -> (MegaEvmStatus, Vec<u8>) {
+        let callbacks = MegaEvmStateCallbacks {
+            ctx: ptr::from_mut(state).cast(),
+            basic: Some(basic),
+            code_by_hash: Some(code_by_hash),
+            storage,
+            block_hash: None,
+        };
+        let (env, tx) = (env_json(), deposit_tx());
+        let mut out = MegaEvmBuffer::empty();
+        let status = unsafe {
+            mega_evm_execute(env.as_ptr(), env.len(), tx.as_ptr(), tx.len(), &callbacks, &mut out)
+        };
+        let bytes = unsafe { core::slice::from_raw_parts(out.data, out.len) }.to_vec();
+        unsafe { mega_evm_buffer_free(out) };
+        (status, bytes)
+    }
+
+    #[test]
+    fn test_execute_reads_state_through_callbacks() {
+        let code = BytecodeBuilder::default().sstore(U256::from(1), U256::from(42)).stop().build();
+        let (status, bytes) = run(&mut TestState { code }, None);
+        assert_eq!(status, MegaEvmStatus::Ok, "{}", String::from_utf8_lossy(&bytes));
+
+        let outcome: ExecutionOutcome = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(outcome.sender, SENDER);
+        assert!(outcome.result.is_success());
+        assert_eq!(outcome.state[&CONTRACT].storage[&U256::from(1)], U256::from(42));
+    }
+
+    #[test]
+    fn test_execute_reports_errors() {
+        let code = BytecodeBuilder::default().sstore(U256::from(1), U256::from(42)).stop().build();
+        let (status, message) = run(&mut TestState { code }, Some(failing_storage));
+        assert_eq!(status, MegaEvmStatus::ExecutionFailed);
+        assert!(String::from_utf8(message).unwrap().contains("`storage` failed with code -7"));
+
+        let mut out = MegaEvmBuffer::empty();
+        let status =
+            unsafe { mega_evm_execute(ptr::null(), 0, ptr::null(), 0, ptr::null(), &mut out) };
+        assert_eq!(status, MegaEvmStatus::NullArgument);
+        unsafe { mega_evm_buffer_free(out) };
+        assert_eq!(mega_evm_abi_version(), MEGA_EVM_ABI_VERSION);
+    }
+}
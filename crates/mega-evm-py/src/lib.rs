@@ -0,0 +1,141 @@
+//! Python bindings for mega-evm.
+//!
+//! The `mega_evm_py` extension module lets research and test-generation scripts target `MegaETH`
+//! directly. Inputs and outputs are JSON strings, to be handled with Python's `json` module:
+//!
+//! - `execute_transaction(pre_state, tx)` simulates a transaction and returns its outcome (see
+//!   [`mega_evm_wasm::simulate_transaction`] for the formats).
+//! - `estimate_limits(pre_state, tx)` returns the resource usage of a transaction against the
+//!   limits of its spec, see [`limit_report`].
+//! - `run_state_test(path, keep_going=True)` runs the state tests of a file or directory and
+//!   returns the JSON run report, see [`state_test_report`].
+//!
+//! Build the module with `maturin develop -m crates/mega-evm-py/pyproject.toml`.
+
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+use std::path::Path;
+
+use mega_evm::EvmTxRuntimeLimits;
+use mega_evm_wasm::{simulate, PreState, SimulationError, SimulationTx};
+use pyo3::{exceptions::PyValueError, prelude::*};
+use serde_json::{json, Value};
+use state_test::{
+    report::RunReport,
+    runner::{find_all_json_tests, run_with_report, RunOptions},
+};
+
+/// Simulates the transaction `tx` on top of `pre_state` and returns the resource usage against
+/// the limits of the spec of `tx`.
+///
+/// Every resource reports its `used` amount, its `limit` and the `headroom` left. The detained
+/// compute gas limit the transaction was subjected to, if it accessed volatile data, and its
+/// `MegaETH`-specific failure, if any, are reported too.
+pub fn limit_report(pre_state: &str, tx: &str) -> Result<Value, SimulationError> {
+    let pre_state: PreState =
+        serde_json::from_str(pre_state).map_err(SimulationError::InvalidPreState)?;
+    let tx: SimulationTx = serde_json::from_str(tx).map_err(SimulationError::InvalidTransaction)?;
+    let limits = EvmTxRuntimeLimits::from_spec(tx.spec);
+    let outcome = simulate(&pre_state, tx)?;
+
+    let usage = |used: u64, limit: u64| {
+        let headroom = limit.saturating_sub(used);
+        json!({ "used": used, "limit": limit, "headroom": headroom })
+    };
+    let accessed = outcome.volatile_data_accessed;
+    let detained_compute_gas_limit = [
+        (accessed.has_block_env_access() || accessed.has_beneficiary_balance_access())
+            .then_some(limits.block_env_access_compute_gas_limit),
+        accessed.has_oracle_access().then_some(limits.oracle_access_compute_gas_limit),
+    ]
+    .into_iter()
+    .flatten()
+    .min();
+    Ok(json!({
+        "dataSize": usage(outcome.data_size, limits.tx_data_size_limit),
+        "kvUpdates": usage(outcome.kv_updates, limits.tx_kv_updates_limit),
+        "computeGas": usage(outcome.compute_gas_used, limits.tx_compute_gas_limit),
+        "stateGrowth": usage(outcome.state_growth_used, limits.tx_state_growth_limit),
+        "detainedComputeGasLimit": detained_compute_gas_limit,
+        "megaFailure": outcome.mega_failure,
+    }))
+}
+
+/// Runs the state tests of the file or directory `path` and returns the JSON run report.
+///
+/// Failing tests are recorded in the report rather than returned as an error. With `keep_going`
+/// unset, the run stops at the first failing file.
+pub fn state_test_report(path: &Path, keep_going: bool) -> Value {
+    let options = RunOptions { keep_going, ..Default::default() };
+    let mut report = RunReport::default();
+    // The error of a failing run is the failure already recorded in the report.
+    let _ = run_with_report(find_all_json_tests(path), options, &mut report);
+    report.to_json()
+}
+
+fn value_error(err: SimulationError) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
+
+/// Simulates a transaction and returns its JSON outcome.
+#[pyfunction]
+fn execute_transaction(pre_state: &str, tx: &str) -> PyResult<String> {
+    mega_evm_wasm::simulate_transaction(pre_state, tx).map_err(value_error)
+}
+
+/// Returns the JSON resource usage of a transaction against the limits of its spec.
+#[pyfunction]
+fn estimate_limits(pre_state: &str, tx: &str) -> PyResult<String> {
+    limit_report(pre_state, tx).map(|report| report.to_string()).map_err(value_error)
+}
+
+/// Runs the state tests of a file or directory and returns the JSON run report.
+#[pyfunction]
+#[pyo3(signature = (path, keep_going = true))]
+fn run_state_test(py: Python<'_>, path: &str, keep_going: bool) -> String {
+    py.allow_threads(|| state_test_report(Path::new(path), keep_going)).to_string()
+}
+
+/// The `mega_evm_py` Python module.
+#[pymodule]
+fn mega_evm_py(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(execute_transaction, module)?)?;
+    module.add_function(wrap_pyfunction!(estimate_limits, module)?)?;
+    module.add_function(wrap_pyfunction!(run_state_test, module)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_report_reports_usage_against_spec_limits() {
+        let pre_state = json!({
+            "0x2000000000000000000000000000000000000001": { "balance": "0xde0b6b3a7640000" },
+            // TIMESTAMP, POP, PUSH1 1, PUSH1 0, SSTORE, STOP
+            "0x1000000000000000000000000000000000000001": { "code": "0x4250600160005500" },
+        });
+        let tx = json!({
+            "spec": "REX4",
+            "from": "0x2000000000000000000000000000000000000001",
+            "to": "0x1000000000000000000000000000000000000001",
+            "gas": "0xf4240",
+        });
+        let report = limit_report(&pre_state.to_string(), &tx.to_string()).unwrap();
+
+        let limits = EvmTxRuntimeLimits::from_spec(mega_evm::MegaSpecId::REX4);
+        assert_eq!(report["kvUpdates"]["limit"], limits.tx_kv_updates_limit);
+        let used = report["kvUpdates"]["used"].as_u64().unwrap();
+        assert!(used > 0, "the SSTORE is a KV update");
+        assert_eq!(report["kvUpdates"]["headroom"], limits.tx_kv_updates_limit - used);
+        assert_eq!(report["detainedComputeGasLimit"], limits.block_env_access_compute_gas_limit);
+        assert_eq!(report["megaFailure"], Value::Null);
+    }
+
+    #[test]
+    fn test_state_test_report_of_missing_path_is_empty() {
+        let report = state_test_report(Path::new("/nonexistent/state-tests"), true);
+        assert_eq!(report["total"], 0);
+    }
+}
@@ -0,0 +1,150 @@
+//! WASM bindings for simulating `MegaETH` transactions in the browser.
+//!
+//! [`simulate_transaction`] executes a transaction on top of a JSON pre-state with the `no_std`
+//! core of mega-evm, so dapp frontends can preview `MegaETH`-specific failures, such as an
+//! exceeded data limit or detention after reading volatile data, before submitting the
+//! transaction. With the `wasm-bindgen` feature, it is exported to JavaScript as
+//! `simulateTransaction(preState, txJson)`:
+//!
+//! ```sh
+//! wasm-pack build crates/mega-evm-wasm --target web -- --features wasm-bindgen
+//! ```
+
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+mod simulate;
+
+pub use simulate::*;
+
+/// JavaScript entry point of [`simulate_transaction`]: takes and returns JSON strings and throws
+/// on error.
+#[cfg(feature = "wasm-bindgen")]
+#[wasm_bindgen::prelude::wasm_bindgen(js_name = simulateTransaction)]
+pub fn simulate_transaction_js(
+    pre_state: &str,
+    tx_json: &str,
+) -> Result<String, wasm_bindgen::JsError> {
+    simulate_transaction(pre_state, tx_json)
+        .map_err(|err| wasm_bindgen::JsError::new(&err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, Address, U256};
+    use mega_evm::{
+        revm::bytecode::opcode::{POP, TIMESTAMP},
+        test_utils::BytecodeBuilder,
+        MegaHaltReason, MegaSpecId, VolatileDataAccess,
+    };
+    use serde_json::json;
+
+    const SENDER: Address = address!("2000000000000000000000000000000000000001");
+    const CONTRACT: Address = address!("1000000000000000000000000000000000000001");
+
+    fn simulate_call(code: alloy_primitives::Bytes) -> SimulationOutcome {
+        let pre_state = json!({
+            SENDER.to_string(): { "balance": "0xde0b6b3a7640000" },
+            CONTRACT.to_string(): { "code": code },
+        });
+        let tx = json!({
+            "spec": MegaSpecId::REX4,
+            "from": SENDER,
+            "to": CONTRACT,
+            "gas": "0xf4240",
+        });
+        let outcome = simulate_transaction(&pre_state.to_string(), &tx.to_string()).unwrap();
+        serde_json::from_str(&outcome).unwrap()
+    }
+
+    #[test]
+    fn test_simulation_reports_volatile_access() {
+        let outcome =
+            simulate_call(BytecodeBuilder::default().append(TIMESTAMP).append(POP).stop().build());
+        assert!(outcome.result.is_success());
+        assert_eq!(outcome.mega_failure, None);
+        assert_eq!(outcome.volatile_data_accessed, VolatileDataAccess::TIMESTAMP);
+    }
+
+    #[test]
+    fn test_simulation_decodes_mega_failure_from_revert() {
+        let failure = MegaHaltReason::DataLimitExceeded { limit: 10, actual: 11 };
+        let code = BytecodeBuilder::default()
+            .revert_with_data(failure.to_revert_output().unwrap())
+            .build();
+        let outcome = simulate_call(code);
+        assert!(!outcome.result.is_success());
+        assert_eq!(outcome.mega_failure, Some(failure));
+    }
+
+    #[test]
+    fn test_simulation_nonce_gap() {
+        let pre_state = json!({
+            SENDER.to_string(): { "balance": "0xde0b6b3a7640000", "nonce": "0x1" },
+        });
+        let tx = |allow_nonce_gap: bool| {
+            json!({
+                "spec": "REX4",
+                "from": SENDER,
+                "to": CONTRACT,
+                "gas": "0xf4240",
+                "nonce": "0x5",
+                "allowNonceGap": allow_nonce_gap,
+            })
+            .to_string()
+        };
+        assert!(matches!(
+            simulate_transaction(&pre_state.to_string(), &tx(false)),
+            Err(SimulationError::Execution(_))
+        ));
+        let outcome: SimulationOutcome =
+            serde_json::from_str(&simulate_transaction(&pre_state.to_string(), &tx(true)).unwrap())
+                .unwrap();
+        assert!(outcome.result.is_success());
+    }
+
+    #[test]
+    fn test_simulation_fee_payer() {
+        const SPONSOR: Address = address!("3000000000000000000000000000000000000001");
+        let pre_state = json!({
+            SPONSOR.to_string(): { "balance": "0xde0b6b3a7640000" },
+        });
+        let tx = |fee_payer: Option<Address>| {
+            json!({
+                "spec": "REX4",
+                "from": SENDER,
+                "to": CONTRACT,
+                "gas": "0xf4240",
+                "gasPrice": "0x3e8",
+                "feePayer": fee_payer,
+            })
+            .to_string()
+        };
+        assert!(matches!(
+            simulate_transaction(&pre_state.to_string(), &tx(None)),
+            Err(SimulationError::Execution(_))
+        ));
+        let outcome: SimulationOutcome = serde_json::from_str(
+            &simulate_transaction(&pre_state.to_string(), &tx(Some(SPONSOR))).unwrap(),
+        )
+        .unwrap();
+        assert!(outcome.result.is_success());
+    }
+
+    #[test]
+    fn test_simulation_rejects_invalid_input() {
+        assert!(matches!(
+            simulate_transaction("[]", "{}"),
+            Err(SimulationError::InvalidPreState(_))
+        ));
+        assert!(matches!(
+            simulate_transaction("{}", "{}"),
+            Err(SimulationError::InvalidTransaction(_))
+        ));
+        let tx = json!({ "spec": "REX4", "from": SENDER, "gas": "0x5208", "value": U256::from(1) });
+        assert!(matches!(
+            simulate_transaction("{}", &tx.to_string()),
+            Err(SimulationError::Execution(_))
+        ));
+    }
+}
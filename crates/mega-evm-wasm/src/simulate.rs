@@ -0,0 +1,228 @@
+//! Transaction simulation over a JSON pre-state.
+
+use std::collections::BTreeMap;
+
+use alloy_primitives::{Address, Bytes, U256};
+use mega_evm::{
+    revm::{
+        context::{result::ExecutionResult, BlockEnv, TxEnv},
+        database::{CacheDB, EmptyDB},
+        primitives::TxKind,
+        state::{AccountInfo, Bytecode},
+    },
+    DetainedGasRefund, MegaChainConfig, MegaContext, MegaEvm, MegaHaltReason, MegaSpecId,
+    MegaTransaction, SimulationOptions, VolatileDataAccess,
+};
+use serde::{Deserialize, Serialize};
+
+/// The state an account starts the simulation with, in the same format as the prestate files of
+/// `mega-evme`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreStateAccount {
+    /// The balance.
+    #[serde(default)]
+    pub balance: U256,
+    /// The nonce.
+    #[serde(default, with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// The code.
+    #[serde(default)]
+    pub code: Bytes,
+    /// The storage.
+    #[serde(default)]
+    pub storage: BTreeMap<U256, U256>,
+}
+
+/// The accounts the simulation starts with. Missing accounts are empty.
+pub type PreState = BTreeMap<Address, PreStateAccount>;
+
+/// A transaction to simulate, with the field names of an `eth_call` request and the spec and
+/// block to simulate it in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationTx {
+    /// The spec to execute under, e.g. `"REX4"`.
+    pub spec: MegaSpecId,
+    /// The block to execute in. Defaults to [`BlockEnv::default`].
+    #[serde(default)]
+    pub block: BlockEnv,
+    /// The chain id. When set, it is both the chain id of the chain and of the transaction.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub chain_id: Option<u64>,
+    /// The sender. No signature is needed.
+    pub from: Address,
+    /// The callee, or `None` for a contract creation.
+    #[serde(default)]
+    pub to: Option<Address>,
+    /// The gas limit.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas: u64,
+    /// The gas price of a legacy transaction.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub gas_price: Option<u128>,
+    /// The max fee per gas of an EIP-1559 transaction.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub max_fee_per_gas: Option<u128>,
+    /// The max priority fee per gas of an EIP-1559 transaction.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub max_priority_fee_per_gas: Option<u128>,
+    /// The value transferred.
+    #[serde(default)]
+    pub value: U256,
+    /// The calldata, or the initcode of a contract creation.
+    #[serde(default, alias = "data")]
+    pub input: Bytes,
+    /// The nonce. Defaults to the nonce of the sender in the pre-state.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub nonce: Option<u64>,
+    /// Whether a nonce above the nonce of the sender in the pre-state is accepted, as if the
+    /// transactions in between had run. See [`MegaContext::with_nonce_gap_tolerance`].
+    #[serde(default)]
+    pub allow_nonce_gap: bool,
+    /// The account paying the fees instead of the sender, as for a sponsored transaction. The
+    /// sender still pays the value. See [`SimulationOptions::fee_payer_override`].
+    #[serde(default)]
+    pub fee_payer: Option<Address>,
+    /// Whether the sender, or the fee payer, may lack the funds for the transaction. See
+    /// [`SimulationOptions::disable_balance_check`].
+    #[serde(default)]
+    pub disable_balance_check: bool,
+    /// The EIP-2718 encoding of the signed transaction, from which the L1 data fee and the data
+    /// availability size are computed. When unset they are computed for an empty transaction.
+    #[serde(default)]
+    pub raw: Option<Bytes>,
+}
+
+/// The outcome of a simulated transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationOutcome {
+    /// The execution result: success with logs and output, revert, or halt.
+    pub result: ExecutionResult<MegaHaltReason>,
+    /// The `MegaETH`-specific failure of the transaction, if any: an exceeded resource limit
+    /// (e.g. the data limit) or detention after accessing volatile data. Such a failure is a halt
+    /// in some specs and a revert with an ABI-encoded reason in others; it is decoded from either.
+    pub mega_failure: Option<MegaHaltReason>,
+    /// The data size usage in bytes.
+    pub data_size: u64,
+    /// The number of KV updates.
+    pub kv_updates: u64,
+    /// The compute gas used.
+    pub compute_gas_used: u64,
+    /// The state growth used.
+    pub state_growth_used: u64,
+    /// The volatile data accessed, which subjects the transaction to detention.
+    pub volatile_data_accessed: VolatileDataAccess,
+    /// The refund of the gas left when detention halted the transaction, if it did.
+    pub detained_gas_refund: Option<DetainedGasRefund>,
+}
+
+/// Errors of [`simulate_transaction`].
+#[derive(Debug, thiserror::Error)]
+pub enum SimulationError {
+    /// The pre-state is not valid JSON of a [`PreState`].
+    #[error("invalid pre-state: {0}")]
+    InvalidPreState(serde_json::Error),
+    /// The code of an account of the pre-state is not valid bytecode.
+    #[error("invalid code of {address}: {reason}")]
+    InvalidCode {
+        /// The account.
+        address: Address,
+        /// Why the code is invalid.
+        reason: String,
+    },
+    /// The transaction is not valid JSON of a [`SimulationTx`].
+    #[error("invalid transaction: {0}")]
+    InvalidTransaction(serde_json::Error),
+    /// The transaction could not be executed, e.g. because its nonce or balance is invalid.
+    #[error("execution failed: {0}")]
+    Execution(String),
+}
+
+/// Simulates the JSON [`SimulationTx`] `tx_json` on top of the JSON [`PreState`] `pre_state` and
+/// returns the JSON [`SimulationOutcome`].
+pub fn simulate_transaction(pre_state: &str, tx_json: &str) -> Result<String, SimulationError> {
+    let pre_state: PreState =
+        serde_json::from_str(pre_state).map_err(SimulationError::InvalidPreState)?;
+    let tx: SimulationTx =
+        serde_json::from_str(tx_json).map_err(SimulationError::InvalidTransaction)?;
+    let outcome = simulate(&pre_state, tx)?;
+    Ok(serde_json::to_string(&outcome).expect("simulation outcome serializes to JSON"))
+}
+
+/// Simulates `tx` on top of `pre_state`.
+pub fn simulate(
+    pre_state: &PreState,
+    tx: SimulationTx,
+) -> Result<SimulationOutcome, SimulationError> {
+    let mut db = CacheDB::<EmptyDB>::default();
+    for (address, account) in pre_state {
+        let code = if account.code.is_empty() {
+            Bytecode::default()
+        } else {
+            Bytecode::new_raw_checked(account.code.clone()).map_err(|err| {
+                SimulationError::InvalidCode { address: *address, reason: err.to_string() }
+            })?
+        };
+        db.insert_account_info(
+            *address,
+            AccountInfo::new(account.balance, account.nonce, code.hash_slow(), code),
+        );
+        for (slot, value) in &account.storage {
+            db.insert_account_storage(*address, *slot, *value).unwrap_or_else(|err| match err {});
+        }
+    }
+
+    let nonce = tx.nonce.unwrap_or_else(|| {
+        pre_state.get(&tx.from).map(|account| account.nonce).unwrap_or_default()
+    });
+    let mut mega_tx = MegaTransaction::new(TxEnv {
+        tx_type: if tx.max_fee_per_gas.is_some() { 2 } else { 0 },
+        caller: tx.from,
+        gas_limit: tx.gas,
+        gas_price: tx.max_fee_per_gas.or(tx.gas_price).unwrap_or_default(),
+        gas_priority_fee: tx.max_priority_fee_per_gas,
+        kind: tx.to.map_or(TxKind::Create, TxKind::Call),
+        value: tx.value,
+        data: tx.input,
+        nonce,
+        chain_id: tx.chain_id,
+        ..Default::default()
+    });
+    mega_tx.enveloped_tx = Some(tx.raw.unwrap_or_default());
+
+    let chain = tx
+        .chain_id
+        .map_or_else(MegaChainConfig::default, MegaChainConfig::new)
+        .without_operator_fee();
+    let context = MegaContext::new(db, tx.spec)
+        .with_chain_config(chain)
+        .with_block(tx.block)
+        .with_nonce_gap_tolerance(tx.allow_nonce_gap)
+        .with_simulation_options(
+            SimulationOptions::default()
+                .with_fee_payer_override(tx.fee_payer)
+                .with_disable_balance_check(tx.disable_balance_check),
+        );
+    let outcome = MegaEvm::new(context)
+        .execute_transaction(mega_tx)
+        .map_err(|err| SimulationError::Execution(err.to_string()))?;
+
+    let mega_failure = match &outcome.result {
+        ExecutionResult::Success { .. } |
+        ExecutionResult::Halt { reason: MegaHaltReason::Base(_), .. } => None,
+        ExecutionResult::Halt { reason, .. } => Some(reason.clone()),
+        ExecutionResult::Revert { output, .. } => MegaHaltReason::from_revert_output(output),
+    };
+    Ok(SimulationOutcome {
+        result: outcome.result,
+        mega_failure,
+        data_size: outcome.data_size,
+        kv_updates: outcome.kv_updates,
+        compute_gas_used: outcome.compute_gas_used,
+        state_growth_used: outcome.state_growth_used,
+        volatile_data_accessed: outcome.volatile_data_accessed,
+        detained_gas_refund: outcome.detained_gas_refund,
+    })
+}
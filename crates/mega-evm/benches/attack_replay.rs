@@ -52,7 +52,8 @@ use std::{
 use alloy_primitives::{hex, Address, Bytes, B256, U256};
 use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
 use mega_evm::{
-    test_utils::MemoryDatabase, EmptyExternalEnv, MegaContext, MegaEvm, MegaSpecId, MegaTransaction,
+    test_utils::MemoryDatabase, EmptyExternalEnv, MegaChainConfig, MegaContext, MegaEvm,
+    MegaSpecId, MegaTransaction,
 };
 use revm::{
     context::{
@@ -195,12 +196,9 @@ fn build_mega_context(
 ) -> MegaContext<MemoryDatabase, EmptyExternalEnv> {
     let db = build_db(prestate);
     let mut ctx = MegaContext::new(db, spec);
-    ctx.modify_chain(|chain| {
-        chain.operator_fee_scalar = Some(U256::from(0));
-        chain.operator_fee_constant = Some(U256::from(0));
-    });
-    // MegaContext derefs to OpContext — cfg lives on the inner context.
-    ctx.cfg.chain_id = tx_fixture.chain_id;
+    ctx.modify_cfg(|cfg| cfg.chain_id = tx_fixture.chain_id);
+    let mut ctx =
+        ctx.with_chain_config(MegaChainConfig::new(tx_fixture.chain_id).without_operator_fee());
     ctx.modify_block(|b| {
         b.number = U256::from(block.number);
         b.timestamp = U256::from(block.timestamp);
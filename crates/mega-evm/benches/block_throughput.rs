@@ -0,0 +1,428 @@
+//! Block-building throughput benchmarks.
+//!
+//! Executes realistic blocks through `MegaBlockExecutor` and reports throughput in transactions
+//! per second (`<spec>/txs` rows) and gas per second (`<spec>/gas` rows). Three workloads are
+//! modeled, plus a mix of them:
+//!
+//! - `erc20`: token transfers between many holders (two balance SSTOREs + a `Transfer` log).
+//! - `swap`: uniswap-style swaps against one pool (reserve math, two reserve SSTOREs, a nested
+//!   token transfer CALL, and a `Swap` log).
+//! - `nft_mint`: mints that grow state (counter SSTORE, new owner slot, and a `Transfer` log).
+//! - `mixed`: 60% transfers, 20% swaps, 20% mints.
+//!
+//! The contracts are hand-assembled to the storage and log footprint of their Solidity
+//! counterparts; calldata is raw 32-byte words without selectors.
+//!
+//! Each group also has an `op_revm_pinned` row that runs the same transactions on vanilla
+//! op-revm. It has no block executor, so the row measures per-transaction execution only; the
+//! gap to the `equivalence` row is the cost of the block pipeline, and the gap between
+//! `equivalence` and the later specs is the cost of the limit and tracking subsystem.
+
+#![allow(missing_docs)]
+
+use std::convert::Infallible;
+
+use alloy_consensus::{transaction::Recovered, Signed, TxLegacy};
+use alloy_evm::{block::BlockExecutor, EvmEnv, EvmFactory};
+use alloy_hardforks::ForkCondition;
+use alloy_op_evm::block::receipt_builder::OpAlloyReceiptBuilder;
+use alloy_primitives::{address, b256, Address, Bytes, Signature, TxKind, B256, U256};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use mega_evm::{
+    test_utils::{BytecodeBuilder, MemoryDatabase},
+    BlockLimits, MegaBlockExecutionCtx, MegaBlockExecutor, MegaEvmFactory, MegaHardfork,
+    MegaHardforkConfig, MegaSpecId, MegaTxEnvelope, SequencerRegistryConfig, TestExternalEnvs,
+    MEGA_SYSTEM_ADDRESS,
+};
+use revm::{
+    bytecode::opcode::{
+        ADD, CALL, CALLDATALOAD, CALLER, DIV, DUP1, DUP2, DUP3, GAS, LOG2, LOG3, LOG4, MSTORE, MUL,
+        POP, PUSH0, SLOAD, SSTORE, SUB, SWAP1,
+    },
+    context::BlockEnv,
+    database::State,
+};
+
+mod common;
+use common::{
+    subject::{OpRevmPinned, Subject},
+    Account, TxSpec, Workload,
+};
+
+const TOKEN_A: Address = address!("1000000000000000000000000000000000000001");
+const TOKEN_B: Address = address!("1000000000000000000000000000000000000002");
+const POOL: Address = address!("1000000000000000000000000000000000000003");
+const NFT: Address = address!("1000000000000000000000000000000000000004");
+
+/// `Transfer(address,address,uint256)`, shared by the ERC-20 and ERC-721 events.
+const TRANSFER_TOPIC: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+/// Uniswap V2 `Swap(address,uint256,uint256,uint256,uint256,address)`.
+const SWAP_TOPIC: B256 = b256!("d78ad95fa46c994b6551d0da85fc275fe613ce37657fb8d5e3d130840159d822");
+
+/// Number of distinct transaction senders.
+const SENDERS: u64 = 16;
+/// Gas limit of every transaction. Covers MINI_REX storage gas for new slots.
+const TX_GAS_LIMIT: u64 = 10_000_000;
+/// Transactions per benchmarked block.
+const BLOCK_TXS: usize = 200;
+
+const SPECS: &[(&str, MegaSpecId)] = &[
+    ("equivalence", MegaSpecId::EQUIVALENCE),
+    ("mini_rex", MegaSpecId::MINI_REX),
+    ("rex4", MegaSpecId::REX4),
+    ("rex5", MegaSpecId::REX5),
+];
+
+//
+// ============================================================================
+// Contracts
+// ============================================================================
+//
+
+/// ERC-20 `transfer(to, amount)` with balances keyed by holder address.
+fn erc20_code() -> Bytes {
+    BytecodeBuilder::default()
+        // balance[caller] -= amount
+        .push_number(0x20_u64)
+        .append(CALLDATALOAD)
+        .append(CALLER)
+        .append(SLOAD)
+        .append(DUP2)
+        .append(SWAP1)
+        .append(SUB)
+        .append(CALLER)
+        .append(SSTORE)
+        // balance[to] += amount
+        .append(PUSH0)
+        .append(CALLDATALOAD)
+        .append(DUP1)
+        .append(SLOAD)
+        .append(DUP3)
+        .append(ADD)
+        .append(SWAP1)
+        .append(SSTORE)
+        // emit Transfer(caller, to, amount)
+        .append(PUSH0)
+        .append(MSTORE)
+        .append(PUSH0)
+        .append(CALLDATALOAD)
+        .append(CALLER)
+        .push_u256(TRANSFER_TOPIC.into())
+        .push_number(0x20_u64)
+        .append(PUSH0)
+        .append(LOG3)
+        .stop()
+        .build()
+}
+
+/// Constant-product pool: `swap(amount_in)` pays `amount_in * r1 / (r0 + amount_in)` of `token`
+/// to the caller and updates the reserves in slots 0 and 1.
+fn pool_code(token: Address) -> Bytes {
+    BytecodeBuilder::default()
+        // out = amount_in * r1 / (r0 + amount_in)
+        .append(PUSH0)
+        .append(CALLDATALOAD)
+        .append(PUSH0)
+        .append(SLOAD)
+        .push_number(1_u64)
+        .append(SLOAD)
+        .append(DUP3)
+        .append(MUL)
+        .append(SWAP1)
+        .append(DUP3)
+        .append(ADD)
+        .append(SWAP1)
+        .append(DIV)
+        // r0 += amount_in
+        .append(PUSH0)
+        .append(SLOAD)
+        .append(DUP3)
+        .append(ADD)
+        .append(PUSH0)
+        .append(SSTORE)
+        // r1 -= out
+        .append(DUP1)
+        .push_number(1_u64)
+        .append(SLOAD)
+        .append(SUB)
+        .push_number(1_u64)
+        .append(SSTORE)
+        // token.transfer(caller, out)
+        .append(CALLER)
+        .append(PUSH0)
+        .append(MSTORE)
+        .append(DUP1)
+        .push_number(0x20_u64)
+        .append(MSTORE)
+        .append(PUSH0)
+        .append(PUSH0)
+        .push_number(0x40_u64)
+        .append(PUSH0)
+        .append(PUSH0)
+        .push_address(token)
+        .append(GAS)
+        .append(CALL)
+        .append(POP)
+        // emit Swap(caller) with (amount_in, out)
+        .push_number(0x20_u64)
+        .append(MSTORE)
+        .append(PUSH0)
+        .append(MSTORE)
+        .append(CALLER)
+        .push_u256(SWAP_TOPIC.into())
+        .push_number(0x40_u64)
+        .append(PUSH0)
+        .append(LOG2)
+        .stop()
+        .build()
+}
+
+/// ERC-721 `mint()`: assigns the next token id to the caller.
+fn nft_code() -> Bytes {
+    BytecodeBuilder::default()
+        // id = counter++
+        .append(PUSH0)
+        .append(SLOAD)
+        .append(DUP1)
+        .push_number(1_u64)
+        .append(ADD)
+        .append(PUSH0)
+        .append(SSTORE)
+        // owner[id] = caller, stored at 2^128 + id
+        .append(CALLER)
+        .append(DUP2)
+        .push_u256(U256::from(1) << 128)
+        .append(ADD)
+        .append(SSTORE)
+        // emit Transfer(0, caller, id)
+        .append(CALLER)
+        .append(PUSH0)
+        .push_u256(TRANSFER_TOPIC.into())
+        .append(PUSH0)
+        .append(PUSH0)
+        .append(LOG4)
+        .stop()
+        .build()
+}
+
+//
+// ============================================================================
+// Workloads
+// ============================================================================
+//
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Erc20,
+    Swap,
+    NftMint,
+}
+
+fn sender(i: u64) -> Address {
+    Address::from_word(U256::from(0x3000_0000_u64 + i % SENDERS).into())
+}
+
+fn holder_slot(holder: Address) -> U256 {
+    holder.into_word().into()
+}
+
+fn tx_spec(kind: Kind, i: u64) -> TxSpec {
+    let caller = sender(i);
+    match kind {
+        Kind::Erc20 => {
+            let to = Address::from_word(U256::from(0x4000_0000_u64 + i).into());
+            let mut data = to.into_word().to_vec();
+            data.extend_from_slice(&U256::from(1_000 + i).to_be_bytes::<32>());
+            TxSpec::call(caller, TOKEN_A).data(data.into())
+        }
+        Kind::Swap => {
+            let amount_in = U256::from(1_000_000_u64 + i);
+            TxSpec::call(caller, POOL).data(amount_in.to_be_bytes_vec().into())
+        }
+        Kind::NftMint => TxSpec::call(caller, NFT),
+    }
+    .gas_limit(TX_GAS_LIMIT)
+}
+
+/// `n` transactions following the repeating `pattern`.
+fn workload(pattern: &[Kind], n: usize) -> Workload {
+    let txs = (0..n as u64).map(|i| tx_spec(pattern[i as usize % pattern.len()], i)).collect();
+    let supply = U256::from(10).pow(U256::from(30));
+    let mut token_a = Account::new(TOKEN_A).code(erc20_code());
+    for i in 0..SENDERS {
+        token_a = token_a.storage(holder_slot(sender(i)), supply);
+    }
+    let mut accounts = vec![
+        token_a,
+        Account::new(TOKEN_B).code(erc20_code()).storage(holder_slot(POOL), supply),
+        Account::new(POOL)
+            .code(pool_code(TOKEN_B))
+            .storage(U256::ZERO, U256::from(10).pow(U256::from(24)))
+            .storage(U256::from(1), U256::from(10).pow(U256::from(24))),
+        Account::new(NFT).code(nft_code()),
+    ];
+    accounts.extend((0..SENDERS).map(|i| Account::new(sender(i)).balance(supply)));
+    Workload::new(accounts, txs)
+}
+
+//
+// ============================================================================
+// Block execution
+// ============================================================================
+//
+
+fn seed_db(workload: &Workload) -> MemoryDatabase {
+    let mut db = MemoryDatabase::default();
+    for account in &workload.accounts {
+        db.set_account_balance(account.address, account.balance);
+        if let Some(code) = &account.code {
+            db.set_account_code(account.address, code.clone());
+        }
+        for (slot, value) in &account.storage {
+            db.set_account_storage(account.address, *slot, *value);
+        }
+    }
+    db
+}
+
+/// Signed legacy transactions for `workload`, with per-sender nonces.
+fn recovered_txs(workload: &Workload) -> Vec<Recovered<MegaTxEnvelope>> {
+    let mut nonces = std::collections::HashMap::<Address, u64>::new();
+    workload
+        .txs
+        .iter()
+        .map(|spec| {
+            let nonce = nonces.entry(spec.caller).or_default();
+            let tx = TxLegacy {
+                chain_id: Some(8453),
+                nonce: *nonce,
+                gas_price: 0,
+                gas_limit: spec.gas_limit,
+                to: TxKind::Call(spec.target),
+                value: spec.value,
+                input: spec.data.clone(),
+            };
+            *nonce += 1;
+            let signed = Signed::new_unchecked(tx, Signature::test_signature(), Default::default());
+            Recovered::new_unchecked(MegaTxEnvelope::Legacy(signed), spec.caller)
+        })
+        .collect()
+}
+
+fn all_hardforks_config() -> MegaHardforkConfig {
+    MegaHardforkConfig::default()
+        .with(MegaHardfork::MiniRex, ForkCondition::Timestamp(0))
+        .with(MegaHardfork::Rex, ForkCondition::Timestamp(0))
+        .with(MegaHardfork::Rex1, ForkCondition::Timestamp(0))
+        .with(MegaHardfork::Rex2, ForkCondition::Timestamp(0))
+        .with(MegaHardfork::Rex3, ForkCondition::Timestamp(0))
+        .with(MegaHardfork::Rex4, ForkCondition::Timestamp(0))
+        .with(MegaHardfork::Rex5, ForkCondition::Timestamp(0))
+        .with_params(SequencerRegistryConfig {
+            rex5_initial_sequencer: MEGA_SYSTEM_ADDRESS,
+            rex5_initial_admin: MEGA_SYSTEM_ADDRESS,
+        })
+}
+
+fn block_evm_env(spec: MegaSpecId) -> EvmEnv<MegaSpecId> {
+    let mut cfg_env = revm::context::CfgEnv::default();
+    cfg_env.spec = spec;
+    let block_env = BlockEnv {
+        number: U256::from(1000),
+        timestamp: U256::from(1_800_000_000),
+        gas_limit: u64::MAX,
+        ..Default::default()
+    };
+    EvmEnv::new(cfg_env, block_env)
+}
+
+/// Execute `txs` as one block on a copy of `db` and return the block's gas used.
+fn execute_block(db: &MemoryDatabase, spec: MegaSpecId, txs: &[Recovered<MegaTxEnvelope>]) -> u64 {
+    let mut db = db.clone();
+    let mut state = State::builder().with_database(&mut db).build();
+    let evm_factory =
+        MegaEvmFactory::new().with_external_env_factory(TestExternalEnvs::<Infallible>::new());
+    let evm = evm_factory.create_evm(&mut state, block_evm_env(spec));
+    let block_ctx = MegaBlockExecutionCtx::new(
+        B256::ZERO,
+        Some(B256::ZERO),
+        Bytes::new(),
+        BlockLimits::no_limits(),
+    );
+    let mut executor = MegaBlockExecutor::new(
+        evm,
+        block_ctx,
+        all_hardforks_config(),
+        OpAlloyReceiptBuilder::default(),
+    );
+    executor.apply_pre_execution_changes().expect("pre-execution changes should succeed");
+    for tx in txs {
+        let gas = executor.execute_transaction(tx).expect("transaction should succeed");
+        black_box(gas);
+    }
+    let (_evm, block_result) = executor.finish().expect("finish should succeed");
+    let gas_used = block_result.gas_used;
+    black_box(block_result);
+    gas_used
+}
+
+//
+// ============================================================================
+// Benchmarks
+// ============================================================================
+//
+
+fn bench_block(c: &mut Criterion, name: &str, pattern: &[Kind]) {
+    let mut group = c.benchmark_group(format!("block_throughput_{name}"));
+    group.sample_size(10);
+
+    let workload = workload(pattern, BLOCK_TXS);
+    let db = seed_db(&workload);
+    let txs = recovered_txs(&workload);
+
+    group.throughput(Throughput::Elements(BLOCK_TXS as u64));
+    group.bench_function("op_revm_pinned/txs", |b| b.iter(|| OpRevmPinned.run(&workload)));
+
+    println!("\n=== Gas per block for {name} ({BLOCK_TXS} txs) ===");
+    for &(spec_name, spec) in SPECS {
+        let gas_used = execute_block(&db, spec, &txs);
+        println!("  {spec_name} spec: {gas_used} gas");
+
+        group.throughput(Throughput::Elements(BLOCK_TXS as u64));
+        group.bench_function(format!("{spec_name}/txs"), |b| {
+            b.iter(|| execute_block(&db, black_box(spec), &txs))
+        });
+        group.throughput(Throughput::Elements(gas_used));
+        group.bench_function(format!("{spec_name}/gas"), |b| {
+            b.iter(|| execute_block(&db, black_box(spec), &txs))
+        });
+    }
+    println!();
+    group.finish();
+}
+
+fn bench_erc20_block(c: &mut Criterion) {
+    bench_block(c, "erc20", &[Kind::Erc20]);
+}
+
+fn bench_swap_block(c: &mut Criterion) {
+    bench_block(c, "swap", &[Kind::Swap]);
+}
+
+fn bench_nft_mint_block(c: &mut Criterion) {
+    bench_block(c, "nft_mint", &[Kind::NftMint]);
+}
+
+fn bench_mixed_block(c: &mut Criterion) {
+    bench_block(c, "mixed", &[Kind::Erc20, Kind::Swap, Kind::Erc20, Kind::NftMint, Kind::Erc20]);
+}
+
+criterion_group!(
+    benches,
+    bench_erc20_block,
+    bench_swap_block,
+    bench_nft_mint_block,
+    bench_mixed_block
+);
+criterion_main!(benches);
@@ -126,7 +126,12 @@ pub fn register_env_isolation(
 ) {
     run_subjects(group, variant, w, &[Box::new(RevmPinned)]);
     run_subjects(group, variant, w, &[Box::new(Mega { name: baseline_name, spec })]);
-    run_subjects(group, variant, w, &[Box::new(MegaWithEnv { name: active_name, spec, env })]);
+    run_subjects(
+        group,
+        variant,
+        w,
+        &[Box::new(MegaWithEnv { name: active_name, spec, env, oracle_fast_path: false })],
+    );
 }
 
 /// Like [`register_env_isolation`] but omits the `revm_pinned` floor row.
@@ -147,5 +152,31 @@ pub fn register_env_isolation_mega_only(
     w: &Workload,
 ) {
     run_subjects(group, variant, w, &[Box::new(Mega { name: baseline_name, spec })]);
-    run_subjects(group, variant, w, &[Box::new(MegaWithEnv { name: active_name, spec, env })]);
+    run_subjects(
+        group,
+        variant,
+        w,
+        &[Box::new(MegaWithEnv { name: active_name, spec, env, oracle_fast_path: false })],
+    );
+}
+
+/// Register two `MegaWithEnv` rows for one spec + workload under a `/variant`
+/// suffix: `bytecode_name` runs oracle `getSlot` calls through the oracle
+/// bytecode, `fast_path_name` serves them through the oracle fast path.
+///
+/// Both rows share the spec, env, and workload, so the gap is the cost of the
+/// oracle bytecode run that the fast path skips.
+pub fn register_oracle_fast_path(
+    group: &mut Group<'_>,
+    spec: MegaSpecId,
+    bytecode_name: &'static str,
+    fast_path_name: &'static str,
+    env: TestExternalEnvs<Infallible>,
+    variant: &str,
+    w: &Workload,
+) {
+    let bytecode =
+        MegaWithEnv { name: bytecode_name, spec, env: env.clone(), oracle_fast_path: false };
+    let fast_path = MegaWithEnv { name: fast_path_name, spec, env, oracle_fast_path: true };
+    run_subjects(group, variant, w, &[Box::new(bytecode), Box::new(fast_path)]);
 }
@@ -17,8 +17,8 @@ use alloy_primitives::{Address, Bytes, U256};
 use core::convert::Infallible;
 use criterion::black_box;
 use mega_evm::{
-    revm::inspector::NoOpInspector, test_utils::MemoryDatabase, EmptyExternalEnv, MegaContext,
-    MegaEvm, MegaSpecId, MegaTransaction, TestExternalEnvs,
+    revm::inspector::NoOpInspector, test_utils::MemoryDatabase, EmptyExternalEnv, MegaChainConfig,
+    MegaContext, MegaEvm, MegaSpecId, MegaTransaction, TestExternalEnvs,
 };
 use op_revm::{
     DefaultOp as _, OpBuilder as _, OpContext as OpContextPinned, OpSpecId as OpSpecIdPinned,
@@ -68,10 +68,11 @@ const REVM_FORK_LATEST: SpecIdLatest = SpecIdLatest::CANCUN;
 const OP_FORK: OpSpecIdPinned = OpSpecIdPinned::HOLOCENE;
 const OP_FORK_LATEST: OpSpecIdLatest = OpSpecIdLatest::HOLOCENE;
 
-/// Zero the operator fee so the op and mega rows are comparable to the revm
-/// rows, which carry no such fee. A macro rather than a fn: the three `chain`
-/// types come from distinct crates (op-revm pinned/latest, mega-evm) and share
-/// no common trait — only the field names line up.
+/// Zero the operator fee so the op rows are comparable to the revm rows, which
+/// carry no such fee (the mega rows use `MegaChainConfig::without_operator_fee`).
+/// A macro rather than a fn: the two `chain` types come from distinct crates
+/// (op-revm pinned/latest) and share no common trait — only the field names
+/// line up.
 macro_rules! zero_operator_fee {
     ($chain:expr) => {{
         $chain.operator_fee_scalar = Some(U256::ZERO);
@@ -206,8 +207,8 @@ impl Subject for Mega {
             self.name,
             workload,
             || {
-                let mut context = MegaContext::new(build_pinned_db(&workload.accounts), spec);
-                context.modify_chain(|chain| zero_operator_fee!(chain));
+                let context = MegaContext::new(build_pinned_db(&workload.accounts), spec)
+                    .with_chain_config(MegaChainConfig::default().without_operator_fee());
                 MegaEvm::<_, NoOpInspector, EmptyExternalEnv>::new(context)
             },
             |evm, tx| {
@@ -233,10 +234,12 @@ impl Subject for Mega {
 /// `MegaEvm` at a single spec over a configurable `TestExternalEnvs` (crowded
 /// SALT buckets and/or oracle storage). Used by the SALT and oracle benches.
 /// The `env` is cloned per build so each iteration starts from the same config.
+/// `oracle_fast_path` toggles `MegaContext::with_oracle_fast_path`.
 pub struct MegaWithEnv {
     pub name: &'static str,
     pub spec: MegaSpecId,
     pub env: TestExternalEnvs<Infallible>,
+    pub oracle_fast_path: bool,
 }
 
 impl Subject for MegaWithEnv {
@@ -247,6 +250,7 @@ impl Subject for MegaWithEnv {
     fn run(&self, workload: &Workload) {
         let spec = self.spec;
         let env = self.env.clone();
+        let oracle_fast_path = self.oracle_fast_path;
         run_workload(
             self.name,
             workload,
@@ -254,8 +258,9 @@ impl Subject for MegaWithEnv {
                 let db = build_pinned_db(&workload.accounts);
                 let salt = Rc::new(env.clone());
                 let oracle = Rc::new(RefCell::new(env.clone()));
-                let mut context = MegaContext::new_with_ext_envs(db, spec, salt, oracle);
-                context.modify_chain(|chain| zero_operator_fee!(chain));
+                let context = MegaContext::new_with_ext_envs(db, spec, salt, oracle)
+                    .with_chain_config(MegaChainConfig::default().without_operator_fee())
+                    .with_oracle_fast_path(oracle_fast_path);
                 MegaEvm::<_, NoOpInspector, TestExternalEnvs<Infallible>>::new(context)
             },
             |evm, tx| {
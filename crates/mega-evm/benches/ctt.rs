@@ -8,7 +8,9 @@
 
 use alloy_primitives::{address, bytes, Address, Bytes, U256};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use mega_evm::{test_utils::MemoryDatabase, MegaContext, MegaEvm, MegaSpecId, MegaTransaction};
+use mega_evm::{
+    test_utils::MemoryDatabase, MegaChainConfig, MegaContext, MegaEvm, MegaSpecId, MegaTransaction,
+};
 use revm::{context::tx::TxEnvBuilder, primitives::KECCAK_EMPTY, ExecuteCommitEvm, ExecuteEvm};
 
 const DEPLOYER: Address = address!("0000000000000000000000000000000000100000");
@@ -41,11 +43,8 @@ fn deploy_ctt(spec: MegaSpecId) -> (Address, MemoryDatabase) {
         .account_balance(DEPLOYER, U256::from(10).pow(U256::from(18)))
         .account_balance(CALLER, U256::from(10).pow(U256::from(18)));
 
-    let mut context = MegaContext::new(&mut db, spec);
-    context.modify_chain(|chain| {
-        chain.operator_fee_scalar = Some(U256::from(0));
-        chain.operator_fee_constant = Some(U256::from(0));
-    });
+    let context = MegaContext::new(&mut db, spec)
+        .with_chain_config(MegaChainConfig::default().without_operator_fee());
     let mut evm = MegaEvm::new(context);
 
     // Deploy contract (CREATE transaction with no 'to' address)
@@ -102,11 +101,8 @@ fn execute_batch_transfer(
     contract_addr: Address,
     calldata: &Bytes,
 ) {
-    let mut context = MegaContext::new(black_box(db), black_box(spec));
-    context.modify_chain(|chain| {
-        chain.operator_fee_scalar = Some(U256::from(0));
-        chain.operator_fee_constant = Some(U256::from(0));
-    });
+    let context = MegaContext::new(black_box(db), black_box(spec))
+        .with_chain_config(MegaChainConfig::default().without_operator_fee());
     let mut evm = MegaEvm::new(context);
 
     let tx =
@@ -12,14 +12,21 @@
 //!   stored fields for an `EnrichedMegaTx`.
 //! - `cached_fields` reads the wrapper's precomputed fields directly, the floor
 //!   `via_trait_dispatch` should match.
+//!
+//! The `large_calldata` group prepares a 1 MB calldata transaction for execution (sizes plus
+//! conversion into the EVM transaction) from a plain `Recovered` transaction, which encodes it
+//! twice, and from an `EncodedMegaTx` over the raw bytes, which never copies the calldata.
 
 #![allow(missing_docs)]
 
 use alloy_consensus::{transaction::Recovered, Signed, TxLegacy};
-use alloy_evm::RecoveredTx;
+use alloy_eips::Encodable2718;
+use alloy_evm::{IntoTxEnv, RecoveredTx};
 use alloy_primitives::{address, Address, Bytes, Signature, TxKind, U256};
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
-use mega_evm::{EnrichedMegaTx, MegaTransactionExt, MegaTxEnvelope};
+use mega_evm::{
+    EncodedMegaTx, EnrichedMegaTx, MegaTransaction, MegaTransactionExt, MegaTxEnvelope,
+};
 
 const CALLER: Address = address!("2000000000000000000000000000000000000001");
 const CONTRACT: Address = address!("3000000000000000000000000000000000000001");
@@ -27,7 +34,10 @@ const CONTRACT: Address = address!("3000000000000000000000000000000000000001");
 /// Calldata sizes spanning a plain transfer up to a large multicall-style payload.
 const CALLDATA_SIZES: &[usize] = &[0, 68, 180, 1000];
 
-fn enriched_tx(calldata_len: usize) -> EnrichedMegaTx<Recovered<MegaTxEnvelope>> {
+/// Calldata size of the `large_calldata` group.
+const LARGE_CALLDATA_SIZE: usize = 1 << 20;
+
+fn envelope(calldata_len: usize) -> MegaTxEnvelope {
     let tx = TxLegacy {
         chain_id: Some(1),
         nonce: 7,
@@ -37,13 +47,15 @@ fn enriched_tx(calldata_len: usize) -> EnrichedMegaTx<Recovered<MegaTxEnvelope>>
         value: U256::from(11),
         input: Bytes::from(vec![0xabu8; calldata_len]),
     };
-    let envelope = MegaTxEnvelope::Legacy(Signed::new_unchecked(
+    MegaTxEnvelope::Legacy(Signed::new_unchecked(
         tx,
         Signature::test_signature(),
         Default::default(),
-    ));
-    let recovered = Recovered::new_unchecked(envelope, CALLER);
-    EnrichedMegaTx::new_slow(recovered)
+    ))
+}
+
+fn enriched_tx(calldata_len: usize) -> EnrichedMegaTx<Recovered<MegaTxEnvelope>> {
+    EnrichedMegaTx::new_slow(Recovered::new_unchecked(envelope(calldata_len), CALLER))
 }
 
 /// The `alloy_evm` block-execution path's pattern: `tx.tx().<method>()` unwraps `EnrichedMegaTx`
@@ -94,10 +106,38 @@ fn bench_cached_fields(c: &mut Criterion) {
     group.finish();
 }
 
+/// Sizes plus EVM transaction conversion for a 1 MB calldata transaction: the work
+/// `MegaBlockExecutor::run_transaction` does before execution starts.
+fn bench_large_calldata(c: &mut Criterion) {
+    let mut group = c.benchmark_group("large_calldata");
+    let tx = envelope(LARGE_CALLDATA_SIZE);
+    let encoded = Bytes::from(tx.encoded_2718());
+
+    group.bench_function("recovered", |b| {
+        b.iter(|| {
+            let recovered = Recovered::new_unchecked(black_box(&tx), CALLER);
+            black_box((recovered.tx_size(), recovered.estimated_da_size()));
+            let tx_env: MegaTransaction = recovered.into_tx_env();
+            black_box(tx_env)
+        })
+    });
+    group.bench_function("encoded", |b| {
+        b.iter(|| {
+            let recovered = Recovered::new_unchecked(black_box(&tx), CALLER);
+            let wrapped = EncodedMegaTx::new(recovered, &encoded);
+            black_box((wrapped.tx_size(), wrapped.estimated_da_size()));
+            let tx_env: MegaTransaction = wrapped.into_tx_env();
+            black_box(tx_env)
+        })
+    });
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_recompute_via_tx_unwrap,
     bench_via_trait_dispatch,
-    bench_cached_fields
+    bench_cached_fields,
+    bench_large_calldata
 );
 criterion_main!(benches);
@@ -0,0 +1,68 @@
+//! Benchmarks of the keccak256 implementation selected by the `asm-keccak` and `tiny-keccak`
+//! features.
+//!
+//! Run once per implementation and compare, e.g.:
+//!
+//! ```text
+//! cargo bench -p mega-evm --bench keccak
+//! cargo bench -p mega-evm --bench keccak --features asm-keccak
+//! cargo bench -p mega-evm --bench keccak --features tiny-keccak
+//! ```
+//!
+//! The benchmark names carry the implementation, so the three runs land side by side in the
+//! criterion report.
+
+#![allow(missing_docs)]
+
+use alloy_primitives::{keccak256, Address};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use mega_evm::{KeccakBackend, MegaSpecId};
+use mega_evm_bench::{
+    bytecode::generate_keccak256_bytecode, execute_and_get_gas, execute_bytecode,
+};
+
+/// Input sizes of the raw hashing benchmarks: a slot key preimage, a typical calldata and a
+/// contract initcode.
+const SIZES: &[(&str, usize)] = &[("64B", 64), ("1KB", 1024), ("24KB", 24 * 1024)];
+
+/// Benchmark hashing buffers of increasing size.
+fn bench_raw_keccak256(c: &mut Criterion) {
+    let backend = KeccakBackend::selected();
+    for &(size_name, size) in SIZES {
+        let mut group = c.benchmark_group(format!("raw_keccak256_{size_name}"));
+        let data = vec![0xa5u8; size];
+        group.bench_function(backend.name(), |b| b.iter(|| keccak256(black_box(&data))));
+        group.finish();
+    }
+}
+
+/// Benchmark `CREATE` address derivation, which RLP-encodes and hashes the sender and nonce.
+fn bench_create_address(c: &mut Criterion) {
+    let backend = KeccakBackend::selected();
+    let mut group = c.benchmark_group("create_address");
+    let sender = Address::repeat_byte(0x11);
+    group.bench_function(backend.name(), |b| b.iter(|| black_box(sender).create(black_box(7))));
+    group.finish();
+}
+
+/// Benchmark a KECCAK256-heavy contract: 1000 hashes of each size.
+fn bench_keccak256_workload(c: &mut Criterion) {
+    const ITERATIONS: usize = 1000;
+
+    let backend = KeccakBackend::selected();
+    for &(size_name, size) in SIZES {
+        let mut group = c.benchmark_group(format!("keccak256_workload_{size_name}"));
+        let bytecode = generate_keccak256_bytecode(size, ITERATIONS);
+        println!(
+            "\n=== keccak256_workload_{size_name} with {backend}: {} gas ===",
+            execute_and_get_gas(&bytecode, MegaSpecId::REX5)
+        );
+        group.bench_function(backend.name(), |b| {
+            b.iter(|| black_box(execute_bytecode(&bytecode, black_box(MegaSpecId::REX5))))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_raw_keccak256, bench_create_address, bench_keccak256_workload);
+criterion_main!(benches);
@@ -0,0 +1,184 @@
+//! Benchmarks for the overhead of the additional-limit machinery.
+//!
+//! Each workload executes identical bytecode in three configurations:
+//! - `limiter_on`: `MINI_REX` with its own limits, so every tracker (compute gas, data size, KV
+//!   updates, state growth) records usage and checks it against a finite limit.
+//! - `limiter_unenforced`: `MINI_REX` with [`EvmTxRuntimeLimits::no_limits`]. The trackers still
+//!   record everything, but no limit can be reached.
+//! - `limiter_off`: `EQUIVALENCE`, which runs revm's instruction table without the limit wrappers
+//!   and skips the whole additional-limit lifecycle. This is the no-op limiter baseline.
+//!
+//! The throughput of each group is the number of opcodes the workload executes, so criterion
+//! reports the time per opcode and the gap between the rows is the per-opcode cost of tracking.
+//! The compute-only workloads spend the same gas in every row; the storage-writing ones do not,
+//! because `EQUIVALENCE` charges no storage gas, which is printed before timing.
+
+#![allow(missing_docs)]
+
+use alloy_primitives::{address, Address, Bytes, U256};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use mega_evm::{
+    test_utils::{BytecodeBuilder, GasInspector, MegaTxBuilder, MemoryDatabase},
+    EmptyExternalEnv, EvmTxRuntimeLimits, MegaChainConfig, MegaContext, MegaEvm, MegaHaltReason,
+    MegaSpecId, MegaTransaction,
+};
+use revm::{
+    bytecode::opcode::{
+        ADD, DUP1, KECCAK256, LOG2, MLOAD, MSTORE, MUL, POP, PUSH0, SLOAD, SSTORE, SWAP1,
+    },
+    context::result::ExecutionResult,
+};
+
+const CALLER: Address = address!("0000000000000000000000000000000000100000");
+const CONTRACT: Address = address!("0000000000000000000000000000000000100002");
+const CALLEE: Address = address!("0000000000000000000000000000000000100003");
+
+/// High enough that no workload runs out of gas, including the storage gas of `MINI_REX`.
+const GAS_LIMIT: u64 = 10_000_000_000;
+
+/// One row of every group: a spec and the runtime limits it runs with.
+#[derive(Clone, Copy)]
+struct Limiter {
+    name: &'static str,
+    spec: MegaSpecId,
+    limits: fn() -> EvmTxRuntimeLimits,
+}
+
+const LIMITERS: [Limiter; 3] = [
+    Limiter {
+        name: "limiter_on",
+        spec: MegaSpecId::MINI_REX,
+        limits: EvmTxRuntimeLimits::mini_rex,
+    },
+    Limiter {
+        name: "limiter_unenforced",
+        spec: MegaSpecId::MINI_REX,
+        limits: EvmTxRuntimeLimits::no_limits,
+    },
+    Limiter {
+        name: "limiter_off",
+        spec: MegaSpecId::EQUIVALENCE,
+        limits: EvmTxRuntimeLimits::equivalence,
+    },
+];
+
+/// Builds a fresh EVM over `db` under `limiter` and calls [`CONTRACT`], asserting that the call
+/// succeeds.
+fn execute(db: &MemoryDatabase, limiter: Limiter) -> ExecutionResult<MegaHaltReason> {
+    let outcome = MegaEvm::new(context(db, limiter))
+        .execute_transaction(transaction())
+        .expect("transaction should be valid");
+    assert!(outcome.result.is_success(), "{}: {:?}", limiter.name, outcome.result);
+    outcome.result
+}
+
+/// Returns the number of opcodes the call of [`CONTRACT`] executes under `limiter`.
+fn count_opcodes(db: &MemoryDatabase, limiter: Limiter) -> u64 {
+    let mut inspector = GasInspector::new();
+    MegaEvm::new(context(db, limiter))
+        .with_inspector(&mut inspector)
+        .execute_transaction(transaction())
+        .expect("transaction should be valid");
+    inspector.records().len() as u64
+}
+
+/// Returns a context over a copy of `db` with the spec and limits of `limiter`.
+fn context(db: &MemoryDatabase, limiter: Limiter) -> MegaContext<MemoryDatabase, EmptyExternalEnv> {
+    MegaContext::new(db.clone(), limiter.spec)
+        .with_chain_config(MegaChainConfig::default().without_operator_fee())
+        .with_tx_runtime_limits((limiter.limits)())
+}
+
+/// Returns the transaction calling [`CONTRACT`].
+fn transaction() -> MegaTransaction {
+    MegaTxBuilder::new().caller(CALLER).call(CONTRACT).gas_limit(GAS_LIMIT).build()
+}
+
+/// Benchmarks `code` deployed at [`CONTRACT`] under every [`LIMITERS`] row.
+fn bench_workload(c: &mut Criterion, name: &str, code: Bytes) {
+    let db = MemoryDatabase::default()
+        .account_code(CONTRACT, code)
+        .account_code(CALLEE, BytecodeBuilder::default().stop().build())
+        .account_balance(CALLER, U256::from(10).pow(U256::from(18)));
+
+    let opcodes = count_opcodes(&db, LIMITERS[0]);
+    println!("\n=== {name}: {opcodes} opcodes ===");
+    for limiter in LIMITERS {
+        let gas_used = execute(&db, limiter).gas_used();
+        println!("  {}: {gas_used} gas", limiter.name);
+    }
+    println!();
+
+    let mut group = c.benchmark_group(format!("limit_overhead_{name}"));
+    group.throughput(Throughput::Elements(opcodes));
+    for limiter in LIMITERS {
+        group.bench_function(limiter.name, |b| {
+            b.iter(|| black_box(execute(&db, black_box(limiter))))
+        });
+    }
+    group.finish();
+}
+
+/// Benchmarks one workload per tracker: every opcode pays for compute gas tracking, while storage
+/// writes, logs and calls also exercise the data size, KV update and state growth trackers.
+fn bench_limit_overhead(c: &mut Criterion) {
+    const ITERATIONS: u64 = 1_000;
+
+    let arithmetic = BytecodeBuilder::default()
+        .append(PUSH0)
+        .repeat(ITERATIONS, |b| {
+            b.append(SWAP1)
+                .push_number(7_u8)
+                .append(MUL)
+                .push_number(3_u8)
+                .append_many([ADD, SWAP1])
+        })
+        .append(POP)
+        .stop()
+        .build();
+    bench_workload(c, "arithmetic", arithmetic);
+
+    let memory = BytecodeBuilder::default()
+        .repeat(ITERATIONS, |b| {
+            b.append_many([DUP1, DUP1, MSTORE])
+                .append(DUP1)
+                .append_many([MLOAD, POP])
+                .push_number(32_u8)
+                .append_many([PUSH0, KECCAK256, POP])
+        })
+        .stop()
+        .build();
+    bench_workload(c, "memory", memory);
+
+    let sload = BytecodeBuilder::default()
+        .repeat(ITERATIONS, |b| b.push_number(1_u8).append_many([SLOAD, POP]))
+        .stop()
+        .build();
+    bench_workload(c, "sload", sload);
+
+    // Each iteration stores the loop counter into the slot of the same number, so every write
+    // creates a slot and is recorded by the data size, KV update and state growth trackers.
+    let sstore = BytecodeBuilder::default()
+        .repeat(500, |b| b.append_many([DUP1, DUP1, SSTORE]))
+        .stop()
+        .build();
+    bench_workload(c, "sstore", sstore);
+
+    let log = BytecodeBuilder::default()
+        .mstore(0, [0xab; 64])
+        .repeat(ITERATIONS, |b| {
+            b.append_many([DUP1, PUSH0]).push_number(64_u8).append_many([PUSH0, LOG2])
+        })
+        .stop()
+        .build();
+    bench_workload(c, "log", log);
+
+    let call = BytecodeBuilder::default()
+        .repeat(ITERATIONS, |b| b.call_contract(CALLEE, []).append(POP))
+        .stop()
+        .build();
+    bench_workload(c, "call", call);
+}
+
+criterion_group!(benches, bench_limit_overhead);
+criterion_main!(benches);
@@ -9,6 +9,7 @@
 //!   overhead
 //! - **`delegatecall_system_contract`**: DELEGATECALL vs CALL to system contracts
 //! - **`oracle_sload`**: Oracle forced-cold SLOAD vs regular SLOAD
+//! - **`oracle_get_slot_fast_path`**: Oracle `getSlot` calls through the bytecode vs the fast path
 //! - **`create_deploy`**: CREATE/CREATE2 contract deployment with resource tracking
 //! - **`selfdestruct`**: SELFDESTRUCT behavior across specs
 //! - **`call_value_empty_account`**: CALL with value to empty accounts (dynamic gas)
@@ -20,11 +21,13 @@
 
 use alloy_eips::eip7702::{Authorization, RecoveredAuthority, RecoveredAuthorization};
 use alloy_primitives::{address, Address, Bytes, U256};
+use alloy_sol_types::SolCall;
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use mega_evm::{
     revm::inspector::NoOpInspector,
     test_utils::{BytecodeBuilder, MemoryDatabase},
-    EmptyExternalEnv, MegaContext, MegaEvm, MegaSpecId, MegaTransaction,
+    EmptyExternalEnv, IOracle, MegaChainConfig, MegaContext, MegaEvm, MegaSpecId, MegaTransaction,
+    ORACLE_CONTRACT_CODE_REX5,
 };
 use revm::{
     bytecode::opcode::{
@@ -239,6 +242,19 @@ fn generate_sstore_bytecode(iterations: usize) -> Bytes {
     builder.build()
 }
 
+/// Slots rewritten by [`generate_sstore_overwrite_bytecode`].
+const SSTORE_OVERWRITE_SLOTS: u64 = 10;
+
+/// Rewrites [`SSTORE_OVERWRITE_SLOTS`] slots round-robin, so after the first pass every SSTORE
+/// inspects a slot already resident in the journal.
+fn generate_sstore_overwrite_bytecode(iterations: usize) -> Bytes {
+    let mut builder = BytecodeBuilder::default();
+    for i in 0..iterations as u64 {
+        builder = builder.push_number(i + 1).push_number(i % SSTORE_OVERWRITE_SLOTS).append(SSTORE);
+    }
+    builder.build()
+}
+
 fn generate_sload_bytecode(iterations: usize) -> Bytes {
     let mut builder = BytecodeBuilder::default();
     for i in 0..iterations {
@@ -264,6 +280,7 @@ fn generate_sstore_sload_bytecode(iterations: usize) -> Bytes {
 fn bench_sstore(c: &mut Criterion) {
     let variants: &[(&str, Bytes)] = &[
         ("sstore_100", generate_sstore_bytecode(SSTORE_ITERATIONS)),
+        ("sstore_overwrite_1000", generate_sstore_overwrite_bytecode(10 * SSTORE_ITERATIONS)),
         ("sload_100", generate_sload_bytecode(SSTORE_ITERATIONS)),
         ("sstore_sload_100", generate_sstore_sload_bytecode(SSTORE_ITERATIONS)),
     ];
@@ -729,13 +746,10 @@ fn bench_eip7702_authlist(c: &mut Criterion) {
             MemoryDatabase::default().account_balance(CALLER, U256::from(10).pow(U256::from(24)));
         group.bench_function(format!("rex5/{n}"), |b| {
             b.iter(|| {
-                let mut context = MegaContext::new(db.clone(), MegaSpecId::REX5);
                 // Match the harness's Mega subject: the op-revm base panics on
-                // unset operator-fee fields, so zero them explicitly.
-                context.modify_chain(|chain| {
-                    chain.operator_fee_scalar = Some(U256::ZERO);
-                    chain.operator_fee_constant = Some(U256::ZERO);
-                });
+                // unset operator-fee fields, so disable the fee explicitly.
+                let context = MegaContext::new(db.clone(), MegaSpecId::REX5)
+                    .with_chain_config(MegaChainConfig::default().without_operator_fee());
                 let mut evm = MegaEvm::<_, NoOpInspector, EmptyExternalEnv>::new(context);
                 let tx = TxEnvBuilder::new()
                     .caller(CALLER)
@@ -950,6 +964,70 @@ fn bench_oracle_real_data(c: &mut Criterion) {
     group.finish();
 }
 
+//
+// ============================================================================
+// Oracle getSlot Fast Path Benchmark
+// ============================================================================
+//
+// A contract STATICCALLs the deployed (v2.0.0) oracle's `getSlot` for 50
+// distinct slots populated in the oracle env. Two rows, same spec, env, and
+// workload:
+//   - `rex5_bytecode`:  every call runs the oracle bytecode.
+//   - `rex5_fast_path`: every call is served by the oracle fast path
+//     (`MegaContext::with_oracle_fast_path`).
+//
+// Both rows spend the same gas; the gap is the frame and interpreter cost of
+// the oracle bytecode run.
+//
+
+const ORACLE_GET_SLOT_CALLS: u64 = 50;
+
+fn generate_oracle_get_slot_bytecode(calls: u64) -> Bytes {
+    let mut builder = BytecodeBuilder::default();
+    for slot in 0..calls {
+        let calldata = IOracle::getSlotCall { slot: U256::from(slot) }.abi_encode();
+        builder = builder
+            .mstore(0, &calldata)
+            .push_number(32u64) // retSize
+            .push_number(64u64) // retOffset
+            .push_number(calldata.len() as u64) // argsSize
+            .push_number(0u64) // argsOffset
+            .push_address(ORACLE_ADDRESS)
+            .append(GAS)
+            .append(STATICCALL)
+            .append(POP);
+    }
+    builder.build()
+}
+
+fn bench_oracle_get_slot_fast_path(c: &mut Criterion) {
+    let workload = Workload::single(
+        vec![
+            Account::new(CONTRACT).code(generate_oracle_get_slot_bytecode(ORACLE_GET_SLOT_CALLS)),
+            Account::new(ORACLE_ADDRESS).code(ORACLE_CONTRACT_CODE_REX5),
+            Account::new(CALLER).balance(U256::from(10).pow(U256::from(18))),
+        ],
+        TxSpec::call(CALLER, CONTRACT).gas_limit(FEATURE_GAS_LIMIT),
+    );
+
+    let mut env = mega_evm::TestExternalEnvs::<core::convert::Infallible>::new();
+    for slot in 0..ORACLE_GET_SLOT_CALLS {
+        env = env.with_oracle_storage(U256::from(slot), U256::from(slot + 1));
+    }
+
+    let mut group = c.benchmark_group("oracle_get_slot_fast_path");
+    common::register_oracle_fast_path(
+        &mut group,
+        MegaSpecId::REX5,
+        "rex5_bytecode",
+        "rex5_fast_path",
+        env,
+        "get_slot_50",
+        &workload,
+    );
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_volatile_data,
@@ -963,6 +1041,7 @@ criterion_group!(
     bench_delegatecall_system_contract,
     bench_oracle_sload,
     bench_oracle_real_data,
+    bench_oracle_get_slot_fast_path,
     bench_mixed_workload,
     bench_eip7702_authlist,
     bench_staticcall_selfdestruct,
@@ -0,0 +1,219 @@
+//! Benchmarks for pooling the additional-limit trackers across contexts.
+//!
+//! Each iteration executes a block through `MegaBlockExecutor` on a freshly created EVM, as a
+//! block builder does. The transactions recurse to a fixed call depth so the trackers' frame
+//! stacks have to grow. The `pooled` rows draw the trackers from a shared `TrackerPool`, the
+//! `fresh` rows allocate them per block.
+//!
+//! Before timing, the allocations per block are counted with a counting global allocator and
+//! printed, which shows the allocator pressure removed by the pool.
+
+#![allow(missing_docs)]
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    convert::Infallible,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use alloy_consensus::{transaction::Recovered, Signed, TxLegacy};
+use alloy_evm::{block::BlockExecutor, EvmEnv, EvmFactory};
+use alloy_hardforks::ForkCondition;
+use alloy_op_evm::block::receipt_builder::OpAlloyReceiptBuilder;
+use alloy_primitives::{address, Address, Bytes, Signature, TxKind, B256, U256};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use mega_evm::{
+    test_utils::{BytecodeBuilder, MemoryDatabase},
+    BlockLimits, MegaBlockExecutionCtx, MegaBlockExecutor, MegaEvmFactory, MegaHardfork,
+    MegaHardforkConfig, MegaSpecId, MegaTxEnvelope, TestExternalEnvs, TrackerPool,
+};
+use revm::{
+    bytecode::opcode::{
+        ADDRESS, CALL, CALLDATALOAD, DUP1, GAS, ISZERO, JUMPDEST, JUMPI, MSTORE, PUSH0, STOP, SUB,
+        SWAP1,
+    },
+    context::BlockEnv,
+    database::State,
+};
+
+/// Counts allocations and reallocations made through the global allocator.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const CALLER: Address = address!("2000000000000000000000000000000000000002");
+const CONTRACT: Address = address!("1000000000000000000000000000000000000001");
+
+/// Call depth reached by every transaction.
+const CALL_DEPTH: u8 = 64;
+/// Transactions per block.
+const BLOCK_TXS: u64 = 20;
+
+/// Contract that calls itself with `depth - 1` until the depth read from calldata is zero.
+fn recursive_contract() -> Bytes {
+    let body = |end: u8| {
+        BytecodeBuilder::default()
+            .append(PUSH0)
+            .append(CALLDATALOAD)
+            .append(DUP1)
+            .append(ISZERO)
+            .push_number(end)
+            .append(JUMPI)
+            // mem[0] = depth - 1
+            .push_number(1_u8)
+            .append(SWAP1)
+            .append(SUB)
+            .append(PUSH0)
+            .append(MSTORE)
+            // CALL(gas, address, 0, 0, 32, 0, 0)
+            .append(PUSH0)
+            .append(PUSH0)
+            .push_number(32_u8)
+            .append(PUSH0)
+            .append(PUSH0)
+            .append(ADDRESS)
+            .append(GAS)
+            .append(CALL)
+            .append(STOP)
+    };
+    let end = body(0).len() as u8;
+    body(end).append(JUMPDEST).stop().build()
+}
+
+fn create_call_tx(nonce: u64) -> Recovered<MegaTxEnvelope> {
+    let tx = TxLegacy {
+        chain_id: Some(8453),
+        nonce,
+        gas_price: 0,
+        gas_limit: 10_000_000,
+        to: TxKind::Call(CONTRACT),
+        value: U256::ZERO,
+        input: U256::from(CALL_DEPTH).to_be_bytes_vec().into(),
+    };
+    let signed = Signed::new_unchecked(tx, Signature::test_signature(), Default::default());
+    Recovered::new_unchecked(MegaTxEnvelope::Legacy(signed), CALLER)
+}
+
+fn hardforks_config() -> MegaHardforkConfig {
+    MegaHardforkConfig::default()
+        .with(MegaHardfork::MiniRex, ForkCondition::Timestamp(0))
+        .with(MegaHardfork::Rex, ForkCondition::Timestamp(0))
+        .with(MegaHardfork::Rex1, ForkCondition::Timestamp(0))
+        .with(MegaHardfork::Rex2, ForkCondition::Timestamp(0))
+        .with(MegaHardfork::Rex3, ForkCondition::Timestamp(0))
+        .with(MegaHardfork::Rex4, ForkCondition::Timestamp(0))
+}
+
+fn block_evm_env() -> EvmEnv<MegaSpecId> {
+    let mut cfg_env = revm::context::CfgEnv::default();
+    cfg_env.spec = MegaSpecId::REX4;
+    let block_env = BlockEnv {
+        number: U256::from(1000),
+        timestamp: U256::from(1_800_000_000),
+        gas_limit: 1_000_000_000,
+        ..Default::default()
+    };
+    EvmEnv::new(cfg_env, block_env)
+}
+
+/// Executes one block on `db` through a newly created EVM, drawing the trackers from `pool`.
+fn execute_block(
+    db: &mut MemoryDatabase,
+    txs: &[Recovered<MegaTxEnvelope>],
+    pool: Option<&TrackerPool>,
+) {
+    let mut state = State::builder().with_database(db).build();
+    let evm_factory =
+        MegaEvmFactory::new().with_external_env_factory(TestExternalEnvs::<Infallible>::new());
+    let mut evm = evm_factory.create_evm(&mut state, block_evm_env());
+    if let Some(pool) = pool {
+        evm.ctx_mut().set_tracker_pool(pool.clone());
+    }
+    let block_ctx = MegaBlockExecutionCtx::new(
+        B256::ZERO,
+        Some(B256::ZERO),
+        Bytes::new(),
+        BlockLimits::no_limits(),
+    );
+    let mut executor = MegaBlockExecutor::new(
+        evm,
+        block_ctx,
+        hardforks_config(),
+        OpAlloyReceiptBuilder::default(),
+    );
+    executor.apply_pre_execution_changes().expect("pre-execution changes should succeed");
+    for tx in txs {
+        black_box(executor.execute_transaction(tx).expect("transaction should succeed"));
+    }
+    black_box(executor.finish().expect("finish should succeed"));
+}
+
+/// Returns the number of allocations made while executing one block.
+fn allocations_per_block(
+    db: &MemoryDatabase,
+    txs: &[Recovered<MegaTxEnvelope>],
+    pool: Option<&TrackerPool>,
+) -> u64 {
+    let mut db = db.clone();
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    execute_block(&mut db, txs, pool);
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn bench_tracker_pool(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tracker_pool");
+    group.sample_size(10);
+
+    let db = MemoryDatabase::default()
+        .account_code(CONTRACT, recursive_contract())
+        .account_balance(CALLER, U256::from(1_000_000_000_000_000u64));
+    let txs: Vec<_> = (0..BLOCK_TXS).map(create_call_tx).collect();
+    let pool = TrackerPool::new();
+
+    // Warm the pool so the counted block reuses grown stacks.
+    allocations_per_block(&db, &txs, Some(&pool));
+    let fresh = allocations_per_block(&db, &txs, None);
+    let pooled = allocations_per_block(&db, &txs, Some(&pool));
+    println!("\n=== Allocations per block ({BLOCK_TXS} txs, call depth {CALL_DEPTH}) ===");
+    println!("  fresh:  {fresh}");
+    println!("  pooled: {pooled}");
+    println!();
+
+    group.bench_function("fresh", |b| {
+        b.iter_batched(
+            || db.clone(),
+            |mut db| execute_block(&mut db, &txs, None),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("pooled", |b| {
+        b.iter_batched(
+            || db.clone(),
+            |mut db| execute_block(&mut db, &txs, Some(&pool)),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_tracker_pool);
+criterion_main!(benches);
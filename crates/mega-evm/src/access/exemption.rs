@@ -0,0 +1,84 @@
+//! Contracts exempt from compute gas detention.
+
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::{collections::BTreeSet, vec::Vec};
+
+use alloy_primitives::{keccak256, Address, B256};
+use revm::primitives::KECCAK_EMPTY;
+
+/// The set of trusted contracts whose volatile data accesses do not lower the compute gas limit.
+///
+/// An exempt contract reading the block environment (`TIMESTAMP`, `NUMBER`, ...), touching the
+/// beneficiary, or (pre-Rex3) calling the oracle still has the access recorded in the
+/// [`VolatileDataAccess`](crate::VolatileDataAccess) bitmap, but the transaction is not detained.
+/// Only the contract's own code is exempt: a contract it calls is detained as usual, and so is a
+/// contract that calls it, since the access happens in the exempt contract's frame.
+///
+/// The set changes execution, so a block must be replayed with the same set. [`Self::hash`]
+/// commits to it; the block executor reports it in
+/// [`BlockEnvAccessSummary::detention_exemptions_hash`](crate::BlockEnvAccessSummary::detention_exemptions_hash)
+/// so a replay with another set is detected.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DetentionExemptions {
+    addresses: BTreeSet<Address>,
+}
+
+impl DetentionExemptions {
+    /// The [`hash`](Self::hash) of the empty set.
+    pub const EMPTY_HASH: B256 = KECCAK_EMPTY;
+
+    /// Creates an exemption set from the given addresses.
+    pub fn new(addresses: impl IntoIterator<Item = Address>) -> Self {
+        Self { addresses: addresses.into_iter().collect() }
+    }
+
+    /// Returns whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty()
+    }
+
+    /// Returns whether `address` is exempt from detention.
+    #[inline]
+    pub fn contains(&self, address: &Address) -> bool {
+        !self.addresses.is_empty() && self.addresses.contains(address)
+    }
+
+    /// Returns the exempt addresses in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = &Address> {
+        self.addresses.iter()
+    }
+
+    /// Returns the commitment to the set: the keccak256 hash of the exempt addresses,
+    /// concatenated in ascending order. The empty set hashes to `keccak256("")`.
+    pub fn hash(&self) -> B256 {
+        let bytes: Vec<u8> =
+            self.addresses.iter().flat_map(|address| address.as_slice()).copied().collect();
+        keccak256(bytes)
+    }
+}
+
+impl FromIterator<Address> for DetentionExemptions {
+    fn from_iter<T: IntoIterator<Item = Address>>(iter: T) -> Self {
+        Self::new(iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    const A: Address = address!("0000000000000000000000000000000000000001");
+    const B: Address = address!("0000000000000000000000000000000000000002");
+
+    #[test]
+    fn test_hash_is_order_independent() {
+        assert_eq!(
+            DetentionExemptions::new([A, B]).hash(),
+            DetentionExemptions::new([B, A]).hash()
+        );
+        assert_ne!(DetentionExemptions::new([A]).hash(), DetentionExemptions::new([A, B]).hash());
+        assert_eq!(DetentionExemptions::default().hash(), DetentionExemptions::EMPTY_HASH);
+    }
+}
@@ -5,8 +5,10 @@
 //! the remaining gas in all message calls to a small amount of gas, forcing the transaction to
 //! finish execution soon. These restrictions are necessary to prevent `DoS` attacks on EVM.
 
+mod exemption;
 mod tracker;
 mod volatile;
 
+pub use exemption::*;
 pub use tracker::*;
 pub use volatile::*;
@@ -110,6 +110,11 @@ impl VolatileDataAccessTracker {
         self.compute_gas_limit
     }
 
+    /// Returns the compute gas limit applied when the oracle contract is accessed.
+    pub fn oracle_access_limit(&self) -> u64 {
+        self.oracle_access_limit
+    }
+
     /// Returns the bitmap of block environment data accessed during transaction execution.
     pub fn get_block_env_accesses(&self) -> VolatileDataAccess {
         self.volatile_data_accessed.block_env_only()
@@ -216,6 +221,13 @@ impl VolatileDataAccessTracker {
         }
     }
 
+    /// Restores the compute gas limit saved before a volatile data access by a
+    /// [detention-exempt](crate::DetentionExemptions) contract, so that the access is recorded in
+    /// the bitmap without detaining the transaction.
+    pub fn restore_compute_gas_limit(&mut self, limit: Option<u64>) {
+        self.compute_gas_limit = limit;
+    }
+
     /// Resets all access tracking for a new transaction.
     /// Preserves the configured limits (only resets access state).
     pub fn reset(&mut self) {
@@ -0,0 +1,139 @@
+//! EIP-1559 base fee derivation for `MegaETH` blocks.
+//!
+//! `MegaETH` streams the transactions of an EVM block as mini-blocks, but the base fee only
+//! changes between EVM blocks: every mini-block of a block is priced at the base fee of its
+//! block, and the next base fee is derived from the gas used by the whole parent block, i.e. the
+//! sum over all its mini-blocks. The consensus layer must therefore call [`next_base_fee`] once
+//! per EVM block with the sealed parent header, never with the partial gas used of a mini-block.
+//!
+//! The adjustment follows EIP-1559 with the [`BaseFeeParams`] of the [`MegaChainConfig`]: the gas
+//! target is the gas limit divided by the elasticity multiplier, and the base fee moves towards
+//! it by at most `1 / max_change_denominator` per block.
+
+use alloy_consensus::BlockHeader;
+use alloy_eips::eip1559::{BaseFeeParams, INITIAL_BASE_FEE};
+
+use crate::MegaChainConfig;
+
+/// Returns the base fee of the block following `parent` under `chain_config`.
+///
+/// If `parent` has no base fee, i.e. the next block is the first one with EIP-1559 enabled, the
+/// base fee is [`INITIAL_BASE_FEE`].
+pub fn next_base_fee<H: BlockHeader>(parent: &H, chain_config: &MegaChainConfig) -> u64 {
+    let Some(parent_base_fee) = parent.base_fee_per_gas() else { return INITIAL_BASE_FEE };
+    calc_next_base_fee(
+        parent_base_fee,
+        parent.gas_used(),
+        parent.gas_limit(),
+        chain_config.base_fee_params,
+    )
+}
+
+/// Returns the gas target of a block with the given gas limit: the gas used at which the base
+/// fee of the next block stays unchanged.
+pub fn gas_target(gas_limit: u64, params: BaseFeeParams) -> u64 {
+    (gas_limit as u128 / params.elasticity_multiplier.max(1)) as u64
+}
+
+/// Returns the base fee of the block following a block with the given base fee, gas used and
+/// gas limit.
+///
+/// This is the EIP-1559 adjustment, except that it saturates instead of overflowing and treats a
+/// zero gas target (a gas limit below the elasticity multiplier) as always exceeded by any gas
+/// used, so no input can make it panic.
+pub fn calc_next_base_fee(
+    base_fee: u64,
+    gas_used: u64,
+    gas_limit: u64,
+    params: BaseFeeParams,
+) -> u64 {
+    let gas_target = gas_target(gas_limit, params) as u128;
+    let denominator = params.max_change_denominator.max(1);
+    let (base_fee, gas_used) = (base_fee as u128, gas_used as u128);
+    match gas_used.cmp(&gas_target) {
+        core::cmp::Ordering::Equal => base_fee as u64,
+        core::cmp::Ordering::Greater => {
+            // An increase is at least 1 wei, so a congested chain never gets stuck at a base fee
+            // too small for the relative change to round up.
+            let delta =
+                (base_fee * (gas_used - gas_target) / gas_target.max(1) / denominator).max(1);
+            base_fee.saturating_add(delta).min(u64::MAX as u128) as u64
+        }
+        core::cmp::Ordering::Less => {
+            let delta = base_fee * (gas_target - gas_used) / gas_target / denominator;
+            base_fee.saturating_sub(delta) as u64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+
+    /// The OP Stack parameters since Canyon: denominator 250, elasticity 6.
+    const PARAMS: BaseFeeParams = BaseFeeParams::optimism_canyon();
+    const GAS_LIMIT: u64 = 6_000_000;
+    const BASE_FEE: u64 = 1_000_000_000;
+
+    fn parent(base_fee: Option<u64>, gas_used: u64) -> Header {
+        Header { base_fee_per_gas: base_fee, gas_used, gas_limit: GAS_LIMIT, ..Default::default() }
+    }
+
+    #[test]
+    fn test_base_fee_is_unchanged_at_the_gas_target() {
+        assert_eq!(gas_target(GAS_LIMIT, PARAMS), 1_000_000);
+        assert_eq!(calc_next_base_fee(BASE_FEE, 1_000_000, GAS_LIMIT, PARAMS), BASE_FEE);
+    }
+
+    #[test]
+    fn test_base_fee_moves_at_most_one_denominator_share() {
+        // A full block exceeds the target by five targets: +5/250 of the base fee.
+        assert_eq!(
+            calc_next_base_fee(BASE_FEE, GAS_LIMIT, GAS_LIMIT, PARAMS),
+            BASE_FEE + BASE_FEE * 5 / 250
+        );
+        // An empty block is one target below: -1/250 of the base fee.
+        assert_eq!(calc_next_base_fee(BASE_FEE, 0, GAS_LIMIT, PARAMS), BASE_FEE - BASE_FEE / 250);
+        // One gas above and below the target.
+        assert_eq!(calc_next_base_fee(BASE_FEE, 1_000_001, GAS_LIMIT, PARAMS), BASE_FEE + 4);
+        assert_eq!(calc_next_base_fee(BASE_FEE, 999_999, GAS_LIMIT, PARAMS), BASE_FEE - 4);
+    }
+
+    #[test]
+    fn test_base_fee_increase_is_at_least_one_wei() {
+        assert_eq!(calc_next_base_fee(1, GAS_LIMIT, GAS_LIMIT, PARAMS), 2);
+        // A decrease may round down to nothing.
+        assert_eq!(calc_next_base_fee(1, 0, GAS_LIMIT, PARAMS), 1);
+        assert_eq!(calc_next_base_fee(0, 0, GAS_LIMIT, PARAMS), 0);
+    }
+
+    #[test]
+    fn test_base_fee_calculation_does_not_panic_on_extremes() {
+        assert_eq!(calc_next_base_fee(u64::MAX, GAS_LIMIT, GAS_LIMIT, PARAMS), u64::MAX);
+        // A gas limit below the elasticity multiplier has a zero target.
+        assert_eq!(calc_next_base_fee(BASE_FEE, 0, 5, PARAMS), BASE_FEE);
+        assert_eq!(calc_next_base_fee(BASE_FEE, 1, 5, PARAMS), BASE_FEE + BASE_FEE / 250);
+        // Zero parameters behave as one.
+        let degenerate = BaseFeeParams::new(0, 0);
+        assert_eq!(calc_next_base_fee(BASE_FEE, 1, 2, degenerate), BASE_FEE / 2);
+    }
+
+    #[test]
+    fn test_next_base_fee_of_parent_header() {
+        let config = MegaChainConfig::default();
+        assert_eq!(next_base_fee(&parent(None, 0), &config), INITIAL_BASE_FEE);
+        assert_eq!(next_base_fee(&parent(Some(BASE_FEE), 1_000_000), &config), BASE_FEE);
+        assert_eq!(
+            next_base_fee(&parent(Some(BASE_FEE), GAS_LIMIT), &config),
+            calc_next_base_fee(BASE_FEE, GAS_LIMIT, GAS_LIMIT, config.base_fee_params)
+        );
+
+        // The chain's parameters apply: with elasticity 2 the same block is below the target.
+        let config = config.with_base_fee_params(BaseFeeParams::new(8, 2));
+        assert_eq!(
+            next_base_fee(&parent(Some(BASE_FEE), 1_000_000), &config),
+            BASE_FEE - BASE_FEE * 2 / 3 / 8
+        );
+    }
+}
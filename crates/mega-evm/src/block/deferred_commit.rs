@@ -0,0 +1,159 @@
+//! Deferred state commitment.
+//!
+//! The state changes of a block are committed to the executor's [`State`](revm::database::State)
+//! as soon as they happen, since the next transaction reads them, but committing them to the
+//! state trie is the expensive part and `MegaETH` runs it asynchronously. With a state mutation
+//! log enabled (see
+//! [`MegaBlockExecutor::with_state_mutation_log`](crate::MegaBlockExecutor::with_state_mutation_log)),
+//! the executor also records every committed change, in commit order, and
+//! [`MegaBlockExecutor::commit_later`](crate::MegaBlockExecutor::commit_later) hands them over as a
+//! [`DeferredStateCommit`] for the state-root pipeline to apply whenever it is ready. Nothing is
+//! hashed when a mutation is recorded.
+
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::vec::Vec;
+
+use alloy_evm::block::StateChangeSource;
+use alloy_primitives::Address;
+use revm::{
+    state::{Account, EvmState},
+    DatabaseCommit,
+};
+
+/// The change of one account committed by the executor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateMutation {
+    /// What caused the change: a pre-block system call, a transaction or a post-block change.
+    pub source: StateChangeSource,
+    /// The changed account.
+    pub address: Address,
+    /// The account after the change. Its storage only holds the changed slots, with their
+    /// original and present values.
+    pub account: Account,
+}
+
+/// The state mutations recorded by an executor, in the order they were committed, to be applied
+/// later.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeferredStateCommit {
+    mutations: Vec<StateMutation>,
+}
+
+impl DeferredStateCommit {
+    /// Creates a handle over `mutations`, which must be in commit order.
+    pub(crate) fn new(mutations: Vec<StateMutation>) -> Self {
+        Self { mutations }
+    }
+
+    /// Returns the mutations in commit order.
+    pub fn mutations(&self) -> &[StateMutation] {
+        &self.mutations
+    }
+
+    /// Returns the mutations in commit order.
+    pub fn into_mutations(self) -> Vec<StateMutation> {
+        self.mutations
+    }
+
+    /// Returns the number of mutations.
+    pub fn len(&self) -> usize {
+        self.mutations.len()
+    }
+
+    /// Returns whether there is no mutation.
+    pub fn is_empty(&self) -> bool {
+        self.mutations.is_empty()
+    }
+
+    /// Commits the mutations to `db`, one by one in commit order.
+    pub fn commit<DB: DatabaseCommit>(self, db: &mut DB) {
+        for StateMutation { address, account, .. } in self.mutations {
+            db.commit(EvmState::from_iter([(address, account)]));
+        }
+    }
+}
+
+/// Appends the changes of `state`, committed by `source`, to `log`.
+///
+/// Accounts that were not touched are left out, as [`DatabaseCommit::commit`] ignores them, and so
+/// are unchanged storage slots. Accounts are ordered by address, so the log does not depend on the
+/// iteration order of `state`.
+pub(crate) fn record_state_mutations(
+    log: &mut Vec<StateMutation>,
+    source: StateChangeSource,
+    state: &EvmState,
+) {
+    let start = log.len();
+    log.extend(state.iter().filter(|(_, account)| account.is_touched()).map(
+        |(address, account)| {
+            let mut account = account.clone();
+            account.storage.retain(|_, slot| slot.is_changed());
+            StateMutation { source, address: *address, account }
+        },
+    ));
+    log[start..].sort_unstable_by_key(|mutation| mutation.address);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, U256};
+    use revm::{
+        database::{CacheDB, EmptyDB},
+        state::{AccountInfo, EvmStorageSlot},
+        DatabaseRef,
+    };
+
+    const ALICE: Address = address!("0000000000000000000000000000000000100001");
+    const BOB: Address = address!("0000000000000000000000000000000000100002");
+
+    fn touched(balance: u64) -> Account {
+        let mut account = Account::default()
+            .with_info(AccountInfo { balance: U256::from(balance), ..Default::default() });
+        account.mark_touch();
+        account
+    }
+
+    #[test]
+    fn test_records_only_touched_accounts_and_changed_slots() {
+        let mut bob = touched(2);
+        bob.storage
+            .insert(U256::from(1), EvmStorageSlot::new_changed(U256::ZERO, U256::from(7), 0));
+        bob.storage.insert(U256::from(2), EvmStorageSlot::new(U256::from(3), 0));
+        let state = EvmState::from_iter([
+            (BOB, bob),
+            (ALICE, touched(1)),
+            (Address::ZERO, Account::default()),
+        ]);
+
+        let mut log = Vec::new();
+        record_state_mutations(&mut log, StateChangeSource::Transaction(0), &state);
+
+        let addresses: Vec<_> = log.iter().map(|mutation| mutation.address).collect();
+        assert_eq!(addresses, [ALICE, BOB]);
+        let slots: Vec<_> = log[1].account.storage.keys().copied().collect();
+        assert_eq!(slots, [U256::from(1)]);
+    }
+
+    #[test]
+    fn test_deferred_commit_applies_mutations_in_order() {
+        let mut log = Vec::new();
+        record_state_mutations(
+            &mut log,
+            StateChangeSource::Transaction(0),
+            &EvmState::from_iter([(ALICE, touched(1))]),
+        );
+        record_state_mutations(
+            &mut log,
+            StateChangeSource::Transaction(1),
+            &EvmState::from_iter([(ALICE, touched(5))]),
+        );
+
+        let deferred = DeferredStateCommit::new(log);
+        assert_eq!(deferred.len(), 2);
+        let mut db = CacheDB::new(EmptyDB::default());
+        deferred.commit(&mut db);
+        assert_eq!(db.basic_ref(ALICE).unwrap().unwrap().balance, U256::from(5));
+    }
+}
@@ -1,6 +1,6 @@
 #[cfg(not(feature = "std"))]
 use alloc as std;
-use std::{boxed::Box, collections::BTreeMap, vec::Vec};
+use std::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
 
 use alloy_consensus::{Eip658Value, Header, Transaction, TxReceipt};
 use alloy_eips::{Encodable2718, Typed2718};
@@ -15,21 +15,26 @@ use alloy_evm::{
     Database, Evm as _, FromRecoveredTx, FromTxWithEncoded, IntoTxEnv, RecoveredTx,
 };
 use alloy_op_evm::block::receipt_builder::OpReceiptBuilder;
-use alloy_primitives::B256;
+use alloy_primitives::{Address, B256, U256};
 use op_alloy_consensus::OpDepositReceipt;
 use op_revm::transaction::deposit::DEPOSIT_TRANSACTION_TYPE;
 use revm::{
     context::result::{ExecResultAndState, ExecutionResult},
     database::State,
     handler::EvmTr,
+    state::{Account, EvmState},
     DatabaseCommit, Inspector,
 };
 
 use crate::{
-    block::eips, flat_system_contract_specs, is_apply_pending_changes_due, resolve_system_address,
+    block::{deferred_commit::record_state_mutations, eips},
+    flat_system_contract_specs, is_apply_pending_changes_due, resolve_system_address,
     transact_apply_pending_changes, transact_deploy, transact_deploy_sequencer_registry,
-    BlockLimiter, BlockMegaTransactionOutcome, BucketId, MegaBlockExecutionCtx, MegaHardforks,
-    MegaSystemCallOutcome, MegaTransaction, MegaTransactionExt, MegaTransactionOutcome,
+    BlockEnvAccessSummary, BlockLimiter, BlockLimits, BlockMegaTransactionOutcome, BucketId,
+    DeferredStateCommit, MegaBlockExecutionCtx, MegaHardforks, MegaSystemCallOutcome,
+    MegaTransaction, MegaTransactionExt, MegaTransactionOutcome, MiniBlock, MiniBlockContext,
+    PrefetchTx, Prefetcher, ReceiptHook, StateMutation, StatePrediction, StreamedReceipt,
+    TxLimitUsage, TxPolicy, TxPolicyInput, TxRejection,
 };
 
 /// Block executor for the `MegaETH` chain.
@@ -63,6 +68,23 @@ pub struct MegaBlockExecutor<H, E, R: OpReceiptBuilder> {
     pub block_limiter: BlockLimiter,
     /// The receipts for the transactions in the block.
     pub receipts: Vec<R::Receipt>,
+    /// The block hashes read by the transactions executed so far.
+    accessed_block_hashes: BTreeMap<u64, B256>,
+    /// The volatile data accessed by the transactions committed so far.
+    block_env_access: BlockEnvAccessSummary,
+    /// The policy transactions are checked against before execution, if any.
+    tx_policy: Option<Arc<dyn TxPolicy>>,
+    /// The transactions rejected by the policy so far.
+    pub rejected_txs: Vec<TxRejection>,
+    /// The mini-block state, if the block is executed as mini-blocks.
+    mini_blocks: Option<MiniBlockContext>,
+    /// The hook receipts are streamed to as transactions are committed, if any.
+    receipt_hook: Option<Box<dyn ReceiptHook<R::Receipt>>>,
+    /// The state mutations committed since the last [`MegaBlockExecutor::commit_later`], if they
+    /// are recorded.
+    state_mutations: Option<Vec<StateMutation>>,
+    /// The predictor of the state each transaction reads, loaded before it executes, if any.
+    prefetcher: Option<Arc<dyn Prefetcher>>,
 }
 
 impl<C, E, R: OpReceiptBuilder> core::fmt::Debug for MegaBlockExecutor<C, E, R> {
@@ -134,6 +156,14 @@ where
             hardforks: hardforks.clone(),
             receipt_builder,
             receipts: Vec::new(),
+            accessed_block_hashes: BTreeMap::new(),
+            block_env_access: BlockEnvAccessSummary::default(),
+            tx_policy: None,
+            rejected_txs: Vec::new(),
+            mini_blocks: None,
+            receipt_hook: None,
+            state_mutations: None,
+            prefetcher: None,
             block_limiter: ctx.block_limits.to_block_limiter(),
             ctx,
             evm,
@@ -141,6 +171,70 @@ where
         }
     }
 
+    /// Sets the policy transactions are checked against before execution. See [`TxPolicy`].
+    pub fn with_tx_policy(mut self, policy: Arc<dyn TxPolicy>) -> Self {
+        self.tx_policy = Some(policy);
+        self
+    }
+
+    /// Executes the block as mini-blocks, each limited by `limits` on top of the block limits.
+    /// See [`MiniBlockContext`].
+    pub fn with_mini_blocks(mut self, limits: BlockLimits) -> Self {
+        self.mini_blocks = Some(MiniBlockContext::new(limits));
+        self
+    }
+
+    /// Streams every receipt to `hook` as soon as its transaction is committed. See
+    /// [`ReceiptHook`].
+    pub fn with_receipt_hook(mut self, hook: impl ReceiptHook<R::Receipt>) -> Self {
+        self.receipt_hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Sets or clears the hook receipts are streamed to. See [`ReceiptHook`].
+    pub fn set_receipt_hook(&mut self, hook: Option<Box<dyn ReceiptHook<R::Receipt>>>) {
+        self.receipt_hook = hook;
+    }
+
+    /// Loads the state `prefetcher` predicts each transaction to read before executing it. See
+    /// [`Prefetcher`].
+    pub fn with_prefetcher(mut self, prefetcher: Arc<dyn Prefetcher>) -> Self {
+        self.prefetcher = Some(prefetcher);
+        self
+    }
+
+    /// Records every state change committed from now on, for later commitment to the state trie.
+    /// See [`MegaBlockExecutor::commit_later`].
+    pub fn with_state_mutation_log(mut self) -> Self {
+        self.state_mutations = Some(Vec::new());
+        self
+    }
+
+    /// Takes the state mutations committed since the last call, in commit order, as a handle to
+    /// apply them later. The post-block changes are committed by
+    /// [`BlockExecutor::finish`](alloy_evm::block::BlockExecutor::finish), so take the last
+    /// mutations with [`MegaBlockExecutor::finish_with_deferred_commit`].
+    ///
+    /// Returns `None` if the mutations are not recorded, see
+    /// [`MegaBlockExecutor::with_state_mutation_log`].
+    pub fn commit_later(&mut self) -> Option<DeferredStateCommit> {
+        self.state_mutations.as_mut().map(|log| DeferredStateCommit::new(core::mem::take(log)))
+    }
+
+    /// Returns the mini-block state, or `None` if the block is not executed as mini-blocks.
+    pub fn mini_blocks(&self) -> Option<&MiniBlockContext> {
+        self.mini_blocks.as_ref()
+    }
+
+    /// Seals the current mini-block after the transactions committed so far and starts the next
+    /// one, returning the sealed mini-block.
+    ///
+    /// Returns `None` if the block is not executed as mini-blocks.
+    pub fn seal_mini_block(&mut self) -> Option<&MiniBlock> {
+        let receipts_end = self.receipts.len();
+        Some(self.mini_blocks.as_mut()?.seal(receipts_end))
+    }
+
     /// Gets a mutable reference to the inspector in the `MegaEVM`.
     pub fn inspector_mut(&mut self) -> &mut INSP {
         self.evm.inspector_mut()
@@ -340,8 +434,14 @@ where
         let mut outcomes = Vec::new();
 
         // post block balance increments
-        let balance_increments =
+        let mut balance_increments =
             post_block_balance_increments::<Header>(&self.hardforks, self.evm.block(), &[], None);
+        // beneficiary rewards deferred by the transactions, credited once for the whole block
+        let deferred_reward = self.evm.ctx_mut().take_deferred_beneficiary_reward();
+        if !deferred_reward.is_zero() {
+            *balance_increments.entry(self.evm.block().beneficiary).or_default() +=
+                deferred_reward.saturating_to::<u128>();
+        }
         // self.evm
         //     .db_mut()
         //     .increment_balances(balance_increments.clone())
@@ -359,6 +459,26 @@ where
         Ok(outcomes)
     }
 
+    /// Credits the beneficiary rewards deferred so far (see
+    /// [`MegaContext::defer_beneficiary_reward`](crate::MegaContext::defer_beneficiary_reward))
+    /// ahead of the post-execution changes, so the next transaction sees them.
+    fn credit_deferred_beneficiary_reward(&mut self) -> Result<(), BlockExecutionError> {
+        let reward = self.evm.ctx_mut().take_deferred_beneficiary_reward();
+        let beneficiary = self.evm.block().beneficiary;
+        let state = eips::transact_balance_increments(
+            [(beneficiary, reward.saturating_to::<u128>())],
+            self.evm.db_mut(),
+        )
+        .map_err(BlockExecutionError::other)?;
+        if let Some(state) = state.filter(|state| !state.is_empty()) {
+            self.commit_system_call_outcomes(Vec::from([MegaSystemCallOutcome {
+                source: StateChangeSource::PostBlock(StateChangePostBlockSource::BalanceIncrements),
+                state,
+            }]))?;
+        }
+        Ok(())
+    }
+
     /// Commit the system call outcomes to the internal state of the block executor.
     pub fn commit_system_call_outcomes(
         &mut self,
@@ -366,12 +486,120 @@ where
     ) -> Result<(), BlockExecutionError> {
         for outcome in outcomes {
             self.system_caller.on_state(outcome.source, &outcome.state);
-            self.evm.db_mut().commit(outcome.state);
+            self.commit_state(outcome.source, outcome.state);
         }
 
         Ok(())
     }
 
+    /// Commits `state` to the database, recording it in the state mutation log, if any.
+    fn commit_state(&mut self, source: StateChangeSource, state: EvmState) {
+        if let Some(log) = &mut self.state_mutations {
+            record_state_mutations(log, source, &state);
+        }
+        self.evm.db_mut().commit(state);
+    }
+
+    /// Completes [`alloy_evm::block::BlockExecutor::finish`] once the post-block changes are
+    /// committed.
+    fn finish_committed(
+        mut self,
+    ) -> Result<
+        (crate::MegaEvm<&'db mut State<DB>, INSP, ExtEnvs>, BlockExecutionResult<R::Receipt>),
+        BlockExecutionError,
+    > {
+        // No more transactions run in this block; hand the trackers back to the pool, if any.
+        self.evm.ctx_mut().release_trackers();
+
+        let gas_used = self.receipts.last().map(|r| r.cumulative_gas_used()).unwrap_or_default();
+        Ok((
+            self.evm,
+            BlockExecutionResult {
+                receipts: self.receipts,
+                requests: Default::default(),
+                gas_used,
+            },
+        ))
+    }
+
+    /// Checks `tx` against the [`TxPolicy`], if any, recording it in
+    /// [`MegaBlockExecutor::rejected_txs`] when rejected.
+    ///
+    /// Returns whether `tx` may be executed. Deposit transactions are always admitted. The
+    /// `alloy_evm` block-execution path checks every transaction and skips the rejected ones;
+    /// callers of [`MegaBlockExecutor::run_transaction`] check them themselves.
+    pub fn admit_transaction(&mut self, tx: &impl RecoveredTx<R::Transaction>) -> bool {
+        let Some(policy) = &self.tx_policy else { return true };
+        let inner = tx.tx();
+        if inner.ty() == DEPOSIT_TRANSACTION_TYPE {
+            return true;
+        }
+        let input = TxPolicyInput {
+            hash: inner.tx_hash(),
+            sender: *tx.signer(),
+            to: inner.to(),
+            value: inner.value(),
+            input: inner.input().clone(),
+        };
+        match policy.check(&input) {
+            Ok(()) => true,
+            Err(violation) => {
+                self.rejected_txs.push(TxRejection {
+                    hash: input.hash,
+                    sender: input.sender,
+                    violation,
+                });
+                false
+            }
+        }
+    }
+
+    /// Finishes the block like [`alloy_evm::block::BlockExecutor::finish`], also returning the
+    /// transactions rejected by the [`TxPolicy`].
+    pub fn finish_with_rejections(
+        mut self,
+    ) -> Result<
+        (
+            crate::MegaEvm<&'db mut State<DB>, INSP, ExtEnvs>,
+            BlockExecutionResult<R::Receipt>,
+            Vec<TxRejection>,
+        ),
+        BlockExecutionError,
+    >
+    where
+        crate::MegaTransaction: FromRecoveredTx<R::Transaction> + FromTxWithEncoded<R::Transaction>,
+    {
+        let rejected_txs = core::mem::take(&mut self.rejected_txs);
+        let (evm, result) = alloy_evm::block::BlockExecutor::finish(self)?;
+        Ok((evm, result, rejected_txs))
+    }
+
+    /// Finishes the block like [`alloy_evm::block::BlockExecutor::finish`], also returning the
+    /// state mutations committed since the last [`MegaBlockExecutor::commit_later`], including
+    /// the post-block changes.
+    ///
+    /// The mutations are `None` if they are not recorded, see
+    /// [`MegaBlockExecutor::with_state_mutation_log`].
+    pub fn finish_with_deferred_commit(
+        mut self,
+    ) -> Result<
+        (
+            crate::MegaEvm<&'db mut State<DB>, INSP, ExtEnvs>,
+            BlockExecutionResult<R::Receipt>,
+            Option<DeferredStateCommit>,
+        ),
+        BlockExecutionError,
+    >
+    where
+        crate::MegaTransaction: FromRecoveredTx<R::Transaction> + FromTxWithEncoded<R::Transaction>,
+    {
+        let outcomes = self.post_execution_changes()?;
+        self.commit_system_call_outcomes(outcomes)?;
+        let deferred = self.commit_later();
+        let (evm, result) = self.finish_committed()?;
+        Ok((evm, result, deferred))
+    }
+
     /// Alias to [`MegaBlockExecutor::run_transaction`].
     pub fn execute_mega_transaction<Tx>(
         &mut self,
@@ -484,6 +712,17 @@ where
             da_size,
             is_deposit,
         )?;
+        if let Some(mini_blocks) = &self.mini_blocks {
+            mini_blocks.limiter().pre_execution_check(
+                tx.tx().tx_hash(),
+                tx.tx().gas_limit(),
+                tx_size,
+                da_size,
+                is_deposit,
+            )?;
+        }
+
+        self.prefetch_state(&tx);
 
         // Cache the depositor account prior to the state transition for the deposit nonce.
         //
@@ -501,16 +740,56 @@ where
             .map_err(BlockExecutionError::other)?;
 
         let hash = tx.tx().trie_hash();
+        let deferred_reward = self.evm.ctx_mut().deferred_beneficiary_reward();
 
         // Execute transaction.
-        let outcome = self
+        let mut outcome = self
             .evm
             .execute_transaction(tx.into_tx_env())
             .map_err(move |err| BlockExecutionError::evm(err, hash))?;
 
+        // With deferred beneficiary rewards, a transaction that accessed the beneficiary saw it
+        // without the rewards of the earlier transactions. Credit them and execute it again.
+        if deferred_reward.is_some_and(|reward| !reward.is_zero()) &&
+            self.evm.ctx_mut().volatile_data_tracker.borrow().has_accessed_beneficiary_balance()
+        {
+            self.evm.ctx_mut().deferred_beneficiary_reward = deferred_reward;
+            self.credit_deferred_beneficiary_reward()?;
+            outcome = self
+                .evm
+                .execute_transaction(tx.into_tx_env())
+                .map_err(move |err| BlockExecutionError::evm(err, hash))?;
+        }
+        self.accessed_block_hashes.extend(&outcome.accessed_block_hashes);
+
         Ok(BlockMegaTransactionOutcome { tx, tx_size, da_size, depositor, inner: outcome })
     }
 
+    /// Loads the state the [`Prefetcher`], if any, predicts `tx` to read into the state cache, as
+    /// one batch of reads.
+    ///
+    /// Failed reads are ignored: if the transaction does read that state, the read fails again
+    /// during execution, where the error is reported.
+    fn prefetch_state(&mut self, tx: &impl RecoveredTx<R::Transaction>) {
+        let Some(prefetcher) = &self.prefetcher else { return };
+        let inner = tx.tx();
+        let mut prediction = StatePrediction::default();
+        prefetcher.predict(
+            &PrefetchTx { sender: *tx.signer(), to: inner.to(), input: inner.input() },
+            &mut prediction,
+        );
+        let db = self.evm.db_mut();
+        for address in prediction.accounts {
+            let _ = db.load_cache_account(address);
+        }
+        for (address, index) in prediction.slots {
+            // The state cache only serves the storage of a loaded account.
+            if db.load_cache_account(address).is_ok() {
+                let _ = revm::Database::storage(&mut **db, address, index);
+            }
+        }
+    }
+
     /// Alias to [`MegaBlockExecutor::commit_transaction_outcome`].
     pub fn commit_execution_outcome<Tx>(
         &mut self,
@@ -544,24 +823,54 @@ where
         // Re-validate limits at commit time to handle parallel execution race conditions.
         // Between run_transaction() and commit_transaction_outcome(), other transactions
         // may have been committed, potentially exceeding block limits.
+        let is_deposit = outcome.tx.tx().ty() == DEPOSIT_TRANSACTION_TYPE;
         self.block_limiter.pre_execution_check(
             outcome.tx.tx().tx_hash(),
             outcome.tx.tx().gas_limit(),
             outcome.tx_size,
             outcome.da_size,
-            outcome.tx.tx().ty() == DEPOSIT_TRANSACTION_TYPE,
+            is_deposit,
         )?;
+        if let Some(mini_blocks) = &self.mini_blocks {
+            mini_blocks.limiter().pre_execution_check(
+                outcome.tx.tx().tx_hash(),
+                outcome.tx.tx().gas_limit(),
+                outcome.tx_size,
+                outcome.da_size,
+                is_deposit,
+            )?;
+        }
 
         // Accumulate post-execution resource usage into block-level counters.
         // This does not validate limits; over-limit enforcement happens in
         // `pre_execution_check` before the next transaction.
         self.block_limiter.post_execution_update(&outcome)?;
+        if let Some(mini_blocks) = &mut self.mini_blocks {
+            mini_blocks.limiter_mut().post_execution_update(&outcome)?;
+        }
+
+        self.block_env_access.record_outcome(&outcome);
 
+        let tx_hash = outcome.tx.tx().tx_hash();
+        let usage = TxLimitUsage {
+            gas_used: outcome.result.gas_used(),
+            tx_size: outcome.tx_size,
+            da_size: if is_deposit { 0 } else { outcome.da_size },
+            data_size: self
+                .block_limiter
+                .limits
+                .data_size_accounting
+                .select(outcome.data_size, outcome.da_data_size),
+            kv_updates: outcome.kv_updates,
+            compute_gas_used: outcome.compute_gas_used,
+            state_growth_used: outcome.state_growth_used,
+        };
         let BlockMegaTransactionOutcome { tx, depositor, inner, .. } = outcome;
         let MegaTransactionOutcome { result, state, .. } = inner;
         let gas_used = result.gas_used();
 
-        self.system_caller.on_state(StateChangeSource::Transaction(self.receipts.len()), &state);
+        let source = StateChangeSource::Transaction(self.receipts.len());
+        self.system_caller.on_state(source, &state);
 
         let block_gas_used = self.block_limiter.block_gas_used;
         self.receipts.push(
@@ -596,11 +905,79 @@ where
             },
         );
 
-        self.evm.db_mut().commit(state);
+        self.commit_state(source, state);
+
+        if let Some(hook) = &mut self.receipt_hook {
+            let index = self.receipts.len() - 1;
+            hook.on_receipt(StreamedReceipt {
+                index,
+                tx_hash,
+                receipt: &self.receipts[index],
+                usage,
+                block_usage: &self.block_limiter,
+                mini_block: self
+                    .mini_blocks
+                    .as_ref()
+                    .map(|mini_blocks| (mini_blocks.current_index(), mini_blocks.limiter())),
+            });
+        }
 
         Ok(gas_used)
     }
 
+    /// Commits a transaction outcome obtained by executing `tx` outside of this executor, against
+    /// the state at the start of the block (see [`crate::parallel`]).
+    ///
+    /// The fee recipients credited by the execution are left out of `outcome` and passed as
+    /// `fee_credits` instead; they are applied on top of their current balances. A deferred
+    /// beneficiary reward is added to this executor's ledger. The caller is responsible for the
+    /// outcome being the one the transaction produces on the current state.
+    pub(crate) fn commit_speculative_outcome<Tx>(
+        &mut self,
+        tx: Tx,
+        mut outcome: MegaTransactionOutcome,
+        fee_credits: &[(Address, U256)],
+        beneficiary_reward: U256,
+    ) -> Result<u64, BlockExecutionError>
+    where
+        Tx: IntoTxEnv<MegaTransaction> + RecoveredTx<R::Transaction> + MegaTransactionExt + Copy,
+    {
+        let depositor = (tx.tx().ty() == DEPOSIT_TRANSACTION_TYPE)
+            .then(|| {
+                self.evm
+                    .db_mut()
+                    .load_cache_account(*tx.signer())
+                    .map(|acc| acc.account_info().unwrap_or_default())
+            })
+            .transpose()
+            .map_err(BlockExecutionError::other)?;
+
+        for (address, credit) in fee_credits {
+            let cache_account = self
+                .evm
+                .db_mut()
+                .load_cache_account(*address)
+                .map_err(BlockExecutionError::other)?;
+            let mut account =
+                Account::default().with_info(cache_account.account_info().unwrap_or_default());
+            account.info.balance += *credit;
+            account.mark_touch();
+            outcome.state.insert(*address, account);
+        }
+        if let Some(ledger) = self.evm.ctx_mut().deferred_beneficiary_reward.as_mut() {
+            *ledger = ledger.saturating_add(beneficiary_reward);
+        }
+        self.accessed_block_hashes.extend(&outcome.accessed_block_hashes);
+
+        self.commit_transaction_outcome(BlockMegaTransactionOutcome {
+            tx,
+            tx_size: tx.tx_size(),
+            da_size: tx.estimated_da_size(),
+            depositor,
+            inner: outcome,
+        })
+    }
+
     /// Get the bucket IDs used during transaction execution.
     ///
     /// # Returns
@@ -610,25 +987,37 @@ where
         self.evm.ctx_ref().dynamic_storage_gas_cost.borrow().get_bucket_ids()
     }
 
-    /// Get the block hashes used during transaction execution.
+    /// Get the block hashes read by the transactions executed so far, whether or not their
+    /// outcomes were committed.
     ///
     /// # Returns
     ///
     /// Returns the block hashes used during transaction execution.
     pub fn get_accessed_block_hashes(&self) -> BTreeMap<u64, B256> {
-        self.evm.db().block_hashes.clone()
+        self.accessed_block_hashes.clone()
+    }
+
+    /// Get the summary of the volatile data accessed by the transactions committed so far, with
+    /// the hash of the detention exemption set they were executed with.
+    ///
+    /// Unlike [`MegaBlockExecutor::get_accessed_block_hashes`], transactions whose outcomes were
+    /// not committed are not counted.
+    pub fn get_block_env_access_summary(&self) -> BlockEnvAccessSummary {
+        BlockEnvAccessSummary {
+            detention_exemptions_hash: self.evm.ctx_ref().detention_exemptions_hash(),
+            ..self.block_env_access
+        }
     }
 
     /// Clears the recorded block hash accesses.
     ///
-    /// Block hash reads accumulate in the executor's database across every
-    /// transaction executed so far. Callers that need to attribute BLOCKHASH
-    /// reads to a single transaction (e.g. replay fixture dumping) clear the
-    /// record before executing it. The record is a cache: a cleared hash is
-    /// simply re-fetched from the underlying database on the next access, so
-    /// execution results are unaffected.
+    /// Block hash reads accumulate in the executor across every transaction
+    /// executed so far. Callers that need to attribute BLOCKHASH reads to a
+    /// single transaction (e.g. replay fixture dumping) clear the record before
+    /// executing it, or read [`MegaTransactionOutcome::accessed_block_hashes`]
+    /// instead. The record does not affect execution results.
     pub fn clear_accessed_block_hashes(&mut self) {
-        self.evm.db_mut().block_hashes.clear();
+        self.accessed_block_hashes.clear();
     }
 }
 
@@ -671,7 +1060,7 @@ where
             resolve_system_address(&self.hardforks, spec, self.evm.db_mut())?;
         if let Some(state) = read_state {
             self.system_caller.on_state(StateChangeSource::Transaction(0), &state);
-            self.evm.db_mut().commit(state);
+            self.commit_state(StateChangeSource::Transaction(0), state);
         }
         self.evm.ctx_mut().set_system_address(system_address);
 
@@ -686,6 +1075,9 @@ where
         tx: impl ExecutableTx<Self>,
         f: impl FnOnce(&ExecutionResult<<Self::Evm as alloy_evm::Evm>::HaltReason>) -> CommitChanges,
     ) -> Result<Option<u64>, BlockExecutionError> {
+        if !self.admit_transaction(&tx) {
+            return Ok(None);
+        }
         // `tx: impl ExecutableTx<Self>` cannot be required to implement `MegaTransactionExt`, so
         // this path recomputes the sizes from the raw inner transaction and bypasses
         // `run_transaction` (which reads them via the trait). See `run_transaction`'s docs.
@@ -708,16 +1100,7 @@ where
     ) -> Result<(Self::Evm, BlockExecutionResult<Self::Receipt>), BlockExecutionError> {
         let outcomes = self.post_execution_changes()?;
         self.commit_system_call_outcomes(outcomes)?;
-
-        let gas_used = self.receipts.last().map(|r| r.cumulative_gas_used()).unwrap_or_default();
-        Ok((
-            self.evm,
-            BlockExecutionResult {
-                receipts: self.receipts,
-                requests: Default::default(),
-                gas_used,
-            },
-        ))
+        self.finish_committed()
     }
 
     fn set_state_hook(&mut self, hook: Option<Box<dyn OnStateHook>>) {
@@ -1,3 +1,7 @@
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::sync::Arc;
+
 use alloy_consensus::{Transaction, TxReceipt};
 use alloy_eips::Encodable2718;
 use alloy_evm::{
@@ -7,7 +11,9 @@ use alloy_op_evm::block::receipt_builder::OpReceiptBuilder;
 use alloy_primitives::{Bytes, B256};
 use revm::{database::State, inspector::NoOpInspector, Inspector};
 
-use crate::{BlockLimits, MegaBlockExecutor, MegaEvm, MegaHardforks, MegaSpecId, MegaTxEnvelope};
+use crate::{
+    BlockLimits, MegaBlockExecutor, MegaEvm, MegaHardforks, MegaSpecId, MegaTxEnvelope, TxPolicy,
+};
 
 /// `MegaETH` block executor factory.
 ///
@@ -33,6 +39,7 @@ pub struct MegaBlockExecutorFactory<Hardforks, EvmF, ReceiptBuilder> {
     receipt_builder: ReceiptBuilder,
     hardforks: Hardforks,
     evm_factory: EvmF,
+    tx_policy: Option<Arc<dyn TxPolicy>>,
 }
 
 impl<Hardforks, EvmF, ReceiptBuilder> MegaBlockExecutorFactory<Hardforks, EvmF, ReceiptBuilder>
@@ -51,7 +58,19 @@ where
     ///
     /// A new `BlockExecutorFactory` instance configured with the provided parameters.
     pub fn new(hardforks: Hardforks, evm_factory: EvmF, receipt_builder: ReceiptBuilder) -> Self {
-        Self { receipt_builder, hardforks, evm_factory }
+        Self { receipt_builder, hardforks, evm_factory, tx_policy: None }
+    }
+
+    /// Sets the policy the created executors check transactions against before executing them.
+    /// See [`TxPolicy`].
+    pub fn with_tx_policy(mut self, policy: impl TxPolicy + 'static) -> Self {
+        self.tx_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// Returns the policy the created executors check transactions against, if any.
+    pub fn tx_policy(&self) -> Option<&Arc<dyn TxPolicy>> {
+        self.tx_policy.as_ref()
     }
 
     /// Returns a reference to the EVM factory.
@@ -71,7 +90,7 @@ where
     Hardforks: MegaHardforks + Clone,
     ReceiptBuilder: OpReceiptBuilder<Transaction: Transaction + Encodable2718> + Clone,
     crate::MegaTransaction: FromRecoveredTx<ReceiptBuilder::Transaction>,
-    ExtEnvFactory: crate::ExternalEnvFactory + Clone,
+    ExtEnvFactory: crate::ExternalEnvFactory,
 {
     /// Create a new block executor.
     ///
@@ -99,7 +118,16 @@ where
     {
         let runtime_limits = block_ctx.block_limits.to_evm_tx_runtime_limits();
         let evm = self.evm_factory.create_evm(db, evm_env).with_tx_runtime_limits(runtime_limits);
-        MegaBlockExecutor::new(evm, block_ctx, self.hardforks.clone(), self.receipt_builder.clone())
+        let executor = MegaBlockExecutor::new(
+            evm,
+            block_ctx,
+            self.hardforks.clone(),
+            self.receipt_builder.clone(),
+        );
+        match &self.tx_policy {
+            Some(policy) => executor.with_tx_policy(policy.clone()),
+            None => executor,
+        }
     }
 
     /// Create a new block executor with an inspector.
@@ -134,7 +162,16 @@ where
             .evm_factory
             .create_evm_with_inspector(db, evm_env, inspector)
             .with_tx_runtime_limits(runtime_limits);
-        MegaBlockExecutor::new(evm, block_ctx, self.hardforks.clone(), self.receipt_builder.clone())
+        let executor = MegaBlockExecutor::new(
+            evm,
+            block_ctx,
+            self.hardforks.clone(),
+            self.receipt_builder.clone(),
+        );
+        match &self.tx_policy {
+            Some(policy) => executor.with_tx_policy(policy.clone()),
+            None => executor,
+        }
     }
 }
 
@@ -143,7 +180,7 @@ impl<Hardforks, ExtEnvFactory, ReceiptBuilder> alloy_evm::block::BlockExecutorFa
 where
     ReceiptBuilder: OpReceiptBuilder<Transaction = MegaTxEnvelope, Receipt: TxReceipt>,
     Hardforks: MegaHardforks + Clone,
-    ExtEnvFactory: crate::ExternalEnvFactory + Clone,
+    ExtEnvFactory: crate::ExternalEnvFactory,
     crate::MegaTransaction: FromRecoveredTx<ReceiptBuilder::Transaction>
         + FromTxWithEncoded<ReceiptBuilder::Transaction>,
     Self: 'static,
@@ -174,7 +211,11 @@ where
         // between the inherent and trait construction routes.
         let runtime_limits = ctx.block_limits.to_evm_tx_runtime_limits();
         let evm = evm.with_tx_runtime_limits(runtime_limits);
-        MegaBlockExecutor::new(evm, ctx, &self.hardforks, &self.receipt_builder)
+        let executor = MegaBlockExecutor::new(evm, ctx, &self.hardforks, &self.receipt_builder);
+        match &self.tx_policy {
+            Some(policy) => executor.with_tx_policy(policy.clone()),
+            None => executor,
+        }
     }
 }
 
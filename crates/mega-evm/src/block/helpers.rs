@@ -1,11 +1,11 @@
 use alloy_consensus::{transaction::Recovered, Transaction};
 use alloy_eips::{eip2930::AccessList, eip7702::SignedAuthorization, Encodable2718, Typed2718};
-use alloy_evm::{IntoTxEnv, RecoveredTx};
+use alloy_evm::{FromRecoveredTx, FromTxWithEncoded, IntoTxEnv, RecoveredTx};
 use alloy_primitives::{Address, Bytes, ChainId, Selector, TxHash, TxKind, B256, U256};
 use auto_impl::auto_impl;
 use delegate::delegate;
 
-use crate::MegaTxEnvelope;
+use crate::{MegaTransaction, MegaTxEnvelope};
 
 /// Helper trait that allows attaching extra information to a transaction.
 #[auto_impl(&)]
@@ -187,12 +187,132 @@ impl<Tx, T: IntoTxEnv<Tx>> IntoTxEnv<Tx> for EnrichedMegaTx<T> {
 
 impl<T: Copy> Copy for EnrichedMegaTx<T> {}
 
+/// Lets block executors whose transaction type is an [`EnrichedMegaTx`] convert it like the
+/// wrapped transaction. [`MegaTransaction`] already converts from every [`MegaTxEnvelope`] variant
+/// (legacy, including the mega system transaction, EIP-2930, EIP-1559, EIP-7702 and OP deposit).
+impl<T> FromRecoveredTx<EnrichedMegaTx<T>> for MegaTransaction
+where
+    Self: FromRecoveredTx<T>,
+{
+    fn from_recovered_tx(tx: &EnrichedMegaTx<T>, sender: Address) -> Self {
+        Self::from_recovered_tx(&tx.inner, sender)
+    }
+}
+
+impl<T> FromTxWithEncoded<EnrichedMegaTx<T>> for MegaTransaction
+where
+    Self: FromTxWithEncoded<T>,
+{
+    fn from_encoded_tx(tx: &EnrichedMegaTx<T>, sender: Address, encoded: Bytes) -> Self {
+        Self::from_encoded_tx(&tx.inner, sender, encoded)
+    }
+}
+
+/// A recovered transaction paired with its EIP-2718 encoding, for callers that already hold the
+/// raw bytes (e.g. from the network or the mempool).
+///
+/// Without the encoding, executing a transaction encodes it twice: once to estimate the data
+/// availability size and once more for the `enveloped_tx` the L1 data fee is computed from. Both
+/// copy the whole calldata, which dominates for large transactions. This wrapper derives the sizes
+/// from the given bytes and hands them to the EVM as `enveloped_tx` by reference count, so the
+/// transaction is never re-encoded. The calldata itself is already shared as [`Bytes`] by the
+/// transaction environment and the data size tracker.
+#[derive(Debug, Clone, Copy)]
+pub struct EncodedMegaTx<'a, T> {
+    inner: T,
+    encoded: &'a Bytes,
+    da_size: u64,
+}
+
+impl<'a, T: RecoveredTx<MegaTxEnvelope>> EncodedMegaTx<'a, T> {
+    /// Creates a new wrapper over `inner` and its EIP-2718 `encoded` bytes.
+    ///
+    /// The encoding is trusted as-is, in the same way as the sizes passed to
+    /// [`EnrichedMegaTx::new`]: it is used for size-limit enforcement and the L1 data fee, so it
+    /// must be the encoding of `inner`. Debug builds check its length.
+    pub fn new(inner: T, encoded: &'a Bytes) -> Self {
+        debug_assert_eq!(
+            encoded.len(),
+            inner.tx().encode_2718_len(),
+            "EncodedMegaTx: encoded bytes do not match the transaction"
+        );
+        let da_size = op_alloy_flz::tx_estimated_size_fjord_bytes(encoded);
+        Self { inner, encoded, da_size }
+    }
+
+    /// Returns the EIP-2718 encoded bytes.
+    pub fn encoded(&self) -> &'a Bytes {
+        self.encoded
+    }
+}
+
+impl<T: RecoveredTx<MegaTxEnvelope>> MegaTransactionExt for EncodedMegaTx<'_, T> {
+    fn estimated_da_size(&self) -> u64 {
+        self.da_size
+    }
+
+    fn tx_size(&self) -> u64 {
+        self.encoded.len() as u64
+    }
+
+    fn tx_hash(&self) -> TxHash {
+        self.inner.tx().tx_hash()
+    }
+}
+
+impl<T: RecoveredTx<MegaTxEnvelope>> Encodable2718 for EncodedMegaTx<'_, T> {
+    fn type_flag(&self) -> Option<u8> {
+        self.inner.tx().type_flag()
+    }
+
+    fn encode_2718_len(&self) -> usize {
+        self.encoded.len()
+    }
+
+    fn encode_2718(&self, out: &mut dyn alloy_primitives::bytes::BufMut) {
+        out.put_slice(self.encoded);
+    }
+}
+
+impl<T: RecoveredTx<MegaTxEnvelope>> Typed2718 for EncodedMegaTx<'_, T> {
+    fn ty(&self) -> u8 {
+        self.inner.tx().ty()
+    }
+}
+
+impl<T: RecoveredTx<MegaTxEnvelope>> RecoveredTx<MegaTxEnvelope> for EncodedMegaTx<'_, T> {
+    delegate! {
+        to self.inner {
+            fn tx(&self) -> &MegaTxEnvelope;
+            fn signer(&self) -> &Address;
+        }
+    }
+}
+
+impl<T: RecoveredTx<MegaTxEnvelope>> IntoTxEnv<MegaTransaction> for EncodedMegaTx<'_, T> {
+    fn into_tx_env(self) -> MegaTransaction {
+        MegaTransaction::from_encoded_tx(
+            self.inner.tx(),
+            *self.inner.signer(),
+            self.encoded.clone(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_consensus::{transaction::Recovered, Signed, TxLegacy};
+    use alloy_consensus::{
+        transaction::Recovered, Sealed, Signed, TxEip1559, TxEip2930, TxEip7702, TxLegacy,
+    };
     use alloy_primitives::{address, bytes::BufMut, Signature};
-    use revm::context::TxEnv;
+    use op_alloy_consensus::TxDeposit;
+    use op_revm::transaction::deposit::DEPOSIT_TRANSACTION_TYPE;
+    use revm::context::{Transaction as _, TxEnv};
+
+    use crate::{
+        MEGA_SYSTEM_ADDRESS, MEGA_SYSTEM_TRANSACTION_SOURCE_HASH, ORACLE_CONTRACT_ADDRESS,
+    };
 
     const CALLER: Address = address!("2000000000000000000000000000000000000001");
     const CONTRACT: Address = address!("3000000000000000000000000000000000000001");
@@ -387,4 +507,90 @@ mod tests {
         let converted: TxEnv = enriched.into_tx_env();
         assert_eq!(converted, tx_env);
     }
+
+    #[test]
+    fn test_encoded_mega_tx_shares_encoding() {
+        let tx = legacy_envelope();
+        let encoded = Bytes::from(tx.encoded_2718());
+        let recovered = Recovered::new_unchecked(&tx, CALLER);
+        let wrapped = EncodedMegaTx::new(recovered, &encoded);
+
+        assert_eq!(MegaTransactionExt::tx_hash(&wrapped), tx.tx_hash());
+        assert_eq!(wrapped.tx_size(), MegaTransactionExt::tx_size(&tx));
+        assert_eq!(wrapped.estimated_da_size(), MegaTransactionExt::estimated_da_size(&tx));
+        assert_eq!(wrapped.encoded_2718(), encoded.to_vec());
+
+        let tx_env: MegaTransaction = wrapped.into_tx_env();
+        let enveloped = tx_env.enveloped_tx.expect("enveloped bytes should be set");
+        assert_eq!(enveloped.as_ptr(), encoded.as_ptr(), "encoding should not be copied");
+        assert_eq!(tx_env.base.data.as_ptr(), tx.input().as_ptr(), "calldata should be shared");
+    }
+
+    #[test]
+    fn test_enriched_mega_tx_converts_every_envelope_variant() {
+        let input = Bytes::from_static(&[0xaa, 0xbb]);
+        let envelopes = [
+            legacy_envelope(),
+            MegaTxEnvelope::Eip2930(Signed::new_unchecked(
+                TxEip2930 {
+                    to: TxKind::Call(CONTRACT),
+                    input: input.clone(),
+                    ..Default::default()
+                },
+                Signature::test_signature(),
+                B256::ZERO,
+            )),
+            MegaTxEnvelope::Eip1559(Signed::new_unchecked(
+                TxEip1559 {
+                    to: TxKind::Call(CONTRACT),
+                    input: input.clone(),
+                    ..Default::default()
+                },
+                Signature::test_signature(),
+                B256::ZERO,
+            )),
+            MegaTxEnvelope::Eip7702(Signed::new_unchecked(
+                TxEip7702 { to: CONTRACT, input: input.clone(), ..Default::default() },
+                Signature::test_signature(),
+                B256::ZERO,
+            )),
+            MegaTxEnvelope::Deposit(Sealed::new_unchecked(
+                TxDeposit {
+                    source_hash: MEGA_SYSTEM_TRANSACTION_SOURCE_HASH,
+                    from: CALLER,
+                    to: TxKind::Call(CONTRACT),
+                    input: input.clone(),
+                    ..Default::default()
+                },
+                B256::ZERO,
+            )),
+        ];
+
+        for envelope in envelopes {
+            let encoded = Bytes::from(envelope.encoded_2718());
+            let enriched = EnrichedMegaTx::new_slow(envelope.clone());
+
+            let recovered = MegaTransaction::from_recovered_tx(&enriched, CALLER);
+            let expected = MegaTransaction::from_recovered_tx(&envelope, CALLER);
+            assert_eq!(recovered, expected, "{:?}", envelope.tx_type());
+            assert_eq!(recovered.tx_type(), envelope.ty());
+            assert_eq!(recovered.caller(), CALLER);
+            assert_eq!(recovered.kind(), TxKind::Call(CONTRACT));
+
+            let with_encoded = MegaTransaction::from_encoded_tx(&enriched, CALLER, encoded.clone());
+            assert_eq!(with_encoded.enveloped_tx, Some(encoded));
+            if envelope.ty() == DEPOSIT_TRANSACTION_TYPE {
+                assert_eq!(with_encoded.deposit.source_hash, MEGA_SYSTEM_TRANSACTION_SOURCE_HASH);
+            }
+        }
+
+        // The mega system transaction is a legacy transaction sent by the system address.
+        let system_tx = EnrichedMegaTx::new_slow(MegaTxEnvelope::Legacy(Signed::new_unchecked(
+            TxLegacy { to: TxKind::Call(ORACLE_CONTRACT_ADDRESS), ..legacy_tx() },
+            Signature::test_signature(),
+            B256::ZERO,
+        )));
+        let converted = MegaTransaction::from_recovered_tx(&system_tx, MEGA_SYSTEM_ADDRESS);
+        assert!(crate::is_mega_system_transaction_with(&converted, MEGA_SYSTEM_ADDRESS));
+    }
 }
@@ -200,6 +200,33 @@ use crate::{
     MegaTransactionExt, MegaTxLimitExceededError,
 };
 
+/// How a transaction's execution data size is accounted against
+/// [`block_txs_data_limit`](BlockLimits::block_txs_data_limit).
+///
+/// The data size includes the transaction's own bytes (calldata, access list, authorizations).
+/// These can be counted raw, as the EVM does when enforcing
+/// [`tx_data_limit`](BlockLimits::tx_data_limit), or at their flz-estimated compressed size,
+/// which is what they cost in data availability.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DataSizeAccounting {
+    /// Counts the transaction's bytes raw, i.e. [`LimitUsage::data_size`](crate::LimitUsage).
+    #[default]
+    Raw,
+    /// Counts the transaction's bytes at their flz-estimated DA size, i.e.
+    /// [`LimitUsage::da_data_size`](crate::LimitUsage).
+    DaEstimated,
+}
+
+impl DataSizeAccounting {
+    /// Returns the accounted one of the `raw` and `da_estimated` data sizes.
+    pub const fn select(self, raw: u64, da_estimated: u64) -> u64 {
+        match self {
+            Self::Raw => raw,
+            Self::DaEstimated => da_estimated,
+        }
+    }
+}
+
 /// Configuration for block-level resource limits. The block-level resource limits are associated
 /// with a specific `MegaHardfork` instead of a `MegaSpecId`. In constrast, `EvmTxRuntimeLimits` is
 /// associated with a `MegaSpecId`.
@@ -302,6 +329,13 @@ pub struct BlockLimits {
     /// as failed.
     pub block_txs_data_limit: u64,
 
+    /// How each transaction's data size is accounted against
+    /// [`block_txs_data_limit`](Self::block_txs_data_limit). The transaction-level data limit is
+    /// always enforced on the raw data size.
+    ///
+    /// Default: [`DataSizeAccounting::Raw`]
+    pub data_size_accounting: DataSizeAccounting,
+
     /// Maximum key-value updates for a single transaction.
     pub tx_kv_update_limit: u64,
 
@@ -357,6 +391,19 @@ pub struct BlockLimits {
     /// When a transaction accesses the oracle contract, the compute gas is capped to this
     /// limit to prevent `DoS` attacks.
     pub oracle_access_compute_gas_limit: u64,
+
+    /// Maximum memory size (in bytes) of a single interpreter (call frame).
+    ///
+    /// Enforced from `REX6` before memory is expanded. Transactions exceeding this limit halt
+    /// with [`MegaHaltReason::MemoryLimitExceeded`](crate::MegaHaltReason::MemoryLimitExceeded),
+    /// regardless of the gas available.
+    pub interpreter_memory_limit: u64,
+
+    /// Maximum number of call frames a single transaction may create.
+    ///
+    /// Enforced from `REX6`, independent of call depth. Transactions exceeding this limit halt
+    /// with [`MegaHaltReason::FrameLimitExceeded`](crate::MegaHaltReason::FrameLimitExceeded).
+    pub tx_frame_limit: u64,
 }
 
 impl BlockLimits {
@@ -371,6 +418,7 @@ impl BlockLimits {
             block_da_size_limit: u64::MAX,
             tx_data_limit: u64::MAX,
             block_txs_data_limit: u64::MAX,
+            data_size_accounting: DataSizeAccounting::Raw,
             tx_kv_update_limit: u64::MAX,
             block_kv_update_limit: u64::MAX,
             tx_compute_gas_limit: u64::MAX,
@@ -379,6 +427,8 @@ impl BlockLimits {
             block_state_growth_limit: u64::MAX,
             block_env_access_compute_gas_limit: u64::MAX,
             oracle_access_compute_gas_limit: u64::MAX,
+            interpreter_memory_limit: u64::MAX,
+            tx_frame_limit: u64::MAX,
         }
     }
 
@@ -421,6 +471,8 @@ impl BlockLimits {
         self.tx_state_growth_limit = limits.tx_state_growth_limit;
         self.block_env_access_compute_gas_limit = limits.block_env_access_compute_gas_limit;
         self.oracle_access_compute_gas_limit = limits.oracle_access_compute_gas_limit;
+        self.interpreter_memory_limit = limits.interpreter_memory_limit;
+        self.tx_frame_limit = limits.tx_frame_limit;
         self
     }
 
@@ -513,6 +565,15 @@ impl BlockLimits {
         self
     }
 
+    /// Set how transaction data sizes are accounted against the block transactions data limit.
+    ///
+    /// This is a builder method that consumes self and returns a new instance
+    /// with the specified data size accounting.
+    pub fn with_data_size_accounting(mut self, accounting: DataSizeAccounting) -> Self {
+        self.data_size_accounting = accounting;
+        self
+    }
+
     /// Set a custom transaction KV update limit.
     ///
     /// This is a builder method that consumes self and returns a new instance
@@ -585,6 +646,24 @@ impl BlockLimits {
         self
     }
 
+    /// Set a custom interpreter memory limit.
+    ///
+    /// This is a builder method that consumes self and returns a new instance
+    /// with the specified interpreter memory limit.
+    pub fn with_interpreter_memory_limit(mut self, limit: u64) -> Self {
+        self.interpreter_memory_limit = limit;
+        self
+    }
+
+    /// Set a custom transaction frame limit.
+    ///
+    /// This is a builder method that consumes self and returns a new instance
+    /// with the specified transaction frame limit.
+    pub fn with_tx_frame_limit(mut self, limit: u64) -> Self {
+        self.tx_frame_limit = limit;
+        self
+    }
+
     /// Create a new block limiter from these limits.
     ///
     /// This converts the limit configuration into a stateful [`BlockLimiter`] that tracks
@@ -622,6 +701,8 @@ impl BlockLimits {
             tx_state_growth_limit: self.tx_state_growth_limit,
             block_env_access_compute_gas_limit: self.block_env_access_compute_gas_limit,
             oracle_access_compute_gas_limit: self.oracle_access_compute_gas_limit,
+            interpreter_memory_limit: self.interpreter_memory_limit,
+            tx_frame_limit: self.tx_frame_limit,
         }
     }
 }
@@ -922,7 +1003,8 @@ impl BlockLimiter {
     /// - `block_gas_used += gas_used`
     /// - `block_tx_size_used += tx_size_used`
     /// - `block_da_size_used += da_size_used` (only for non-deposit transactions)
-    /// - `block_data_used += data_size_used`
+    /// - `block_data_used += data_size_used` (raw or DA-estimated, per
+    ///   [`data_size_accounting`](BlockLimits::data_size_accounting))
     /// - `block_kv_updates_used += kv_updates_used`
     /// - `block_compute_gas_used += compute_gas_used`
     /// - `block_state_growth_used += state_growth_used`
@@ -951,7 +1033,7 @@ impl BlockLimiter {
             outcome.result.gas_used(),
             outcome.tx_size,
             outcome.da_size,
-            outcome.data_size,
+            self.limits.data_size_accounting.select(outcome.data_size, outcome.da_data_size),
             outcome.kv_updates,
             outcome.compute_gas_used,
             outcome.state_growth_used,
@@ -0,0 +1,146 @@
+//! Mini-block execution within a single EVM block.
+//!
+//! `MegaETH` streams the transactions of an EVM block in frequent mini-blocks. All the mini-blocks
+//! of a block share its block env (number, timestamp, base fee, ...) and its state, and their
+//! receipts form the receipts of the block. What a mini-block adds is its own resource budget: a
+//! transaction must fit in both the remaining capacity of the block and that of the current
+//! mini-block.
+//!
+//! [`MiniBlockContext`] tracks the current mini-block with a [`BlockLimiter`] of its own, reset
+//! whenever the mini-block is sealed, and records the receipts each sealed mini-block covers. It
+//! is enabled on a [`MegaBlockExecutor`](crate::MegaBlockExecutor) with
+//! [`with_mini_blocks`](crate::MegaBlockExecutor::with_mini_blocks).
+
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use core::ops::Range;
+use std::vec::Vec;
+
+use crate::{BlockLimiter, BlockLimits};
+
+/// A sealed mini-block.
+#[derive(Debug, Clone)]
+pub struct MiniBlock {
+    /// The position of the mini-block in its block, starting at zero.
+    pub index: u64,
+    /// The range of the block's receipts produced by the transactions of the mini-block.
+    pub receipts: Range<usize>,
+    /// The resources used by the transactions of the mini-block.
+    pub usage: BlockLimiter,
+}
+
+impl MiniBlock {
+    /// Returns the number of transactions in the mini-block.
+    pub fn tx_count(&self) -> usize {
+        self.receipts.len()
+    }
+
+    /// Returns whether the mini-block contains no transaction.
+    pub fn is_empty(&self) -> bool {
+        self.receipts.is_empty()
+    }
+}
+
+/// The mini-block state of a block being executed.
+///
+/// Every mini-block is admitted against the same [`BlockLimits`]. A transaction that does not fit
+/// in the current mini-block is rejected with the same errors as one that does not fit in the
+/// block; the caller seals the mini-block and offers the transaction again in the next one.
+#[derive(Debug, Clone)]
+pub struct MiniBlockContext {
+    /// The limiter of the current mini-block.
+    limiter: BlockLimiter,
+    /// The index of the block's first receipt produced in the current mini-block.
+    receipts_start: usize,
+    /// The mini-blocks sealed so far.
+    sealed: Vec<MiniBlock>,
+}
+
+impl MiniBlockContext {
+    /// Creates a context whose mini-blocks are each limited by `limits`.
+    pub fn new(limits: BlockLimits) -> Self {
+        Self { limiter: limits.to_block_limiter(), receipts_start: 0, sealed: Vec::new() }
+    }
+
+    /// Returns the limits every mini-block is admitted against.
+    pub fn limits(&self) -> &BlockLimits {
+        &self.limiter.limits
+    }
+
+    /// Returns the limiter of the current mini-block.
+    pub fn limiter(&self) -> &BlockLimiter {
+        &self.limiter
+    }
+
+    /// Returns a mutable reference to the limiter of the current mini-block.
+    pub fn limiter_mut(&mut self) -> &mut BlockLimiter {
+        &mut self.limiter
+    }
+
+    /// Returns the index of the current mini-block.
+    pub fn current_index(&self) -> u64 {
+        self.sealed.len() as u64
+    }
+
+    /// Returns the mini-blocks sealed so far, in order.
+    pub fn sealed(&self) -> &[MiniBlock] {
+        &self.sealed
+    }
+
+    /// Seals the current mini-block, which ends before the receipt at `receipts_end`, and starts
+    /// the next one with fresh resource counters.
+    ///
+    /// Sealing a mini-block without transactions is allowed and yields an empty mini-block.
+    pub fn seal(&mut self, receipts_end: usize) -> &MiniBlock {
+        debug_assert!(receipts_end >= self.receipts_start, "receipts cannot be removed");
+        let next = self.limiter.limits.to_block_limiter();
+        let mini_block = MiniBlock {
+            index: self.current_index(),
+            receipts: self.receipts_start..receipts_end,
+            usage: core::mem::replace(&mut self.limiter, next),
+        };
+        self.receipts_start = receipts_end;
+        self.sealed.push(mini_block);
+        self.sealed.last().expect("a mini-block was just sealed")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    #[test]
+    fn test_sealing_starts_a_fresh_limiter() {
+        let limits = BlockLimits::no_limits().with_block_gas_limit(100);
+        let mut ctx = MiniBlockContext::new(limits);
+        ctx.limiter_mut().post_execution_update_raw(60, 10, 5, 1, 2, 3, 4, false);
+        ctx.limiter_mut().post_execution_update_raw(30, 10, 5, 1, 2, 3, 4, false);
+        assert!(ctx.limiter().pre_execution_check(B256::ZERO, 20, 0, 0, false).is_err());
+
+        let sealed = ctx.seal(2).clone();
+        assert_eq!(sealed.index, 0);
+        assert_eq!(sealed.receipts, 0..2);
+        assert_eq!(sealed.usage.block_gas_used, 90);
+        assert_eq!(sealed.usage.block_kv_updates_used, 4);
+
+        // The next mini-block has the whole budget again.
+        assert_eq!(ctx.current_index(), 1);
+        assert_eq!(ctx.limiter().block_gas_used, 0);
+        assert!(ctx.limiter().pre_execution_check(B256::ZERO, 100, 0, 0, false).is_ok());
+    }
+
+    #[test]
+    fn test_sealed_mini_blocks_partition_the_receipts() {
+        let mut ctx = MiniBlockContext::new(BlockLimits::no_limits());
+        ctx.seal(3);
+        ctx.seal(3);
+        ctx.seal(5);
+
+        let ranges: Vec<_> = ctx.sealed().iter().map(|mini| mini.receipts.clone()).collect();
+        assert_eq!(ranges, [0..3, 3..3, 3..5]);
+        assert!(ctx.sealed()[1].is_empty());
+        assert_eq!(ctx.sealed()[2].tx_count(), 2);
+        assert_eq!(ctx.current_index(), 3);
+    }
+}
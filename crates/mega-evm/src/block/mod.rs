@@ -91,19 +91,31 @@
 //! - Support for parallel execution through access tracking
 //! - Optimized gas calculations for modified opcodes
 
+mod base_fee;
 mod chain;
+mod deferred_commit;
 mod eips;
 mod executor;
 mod factory;
 mod hardfork;
 mod helpers;
 mod limit;
+mod mini_block;
+mod policy;
+mod prefetch;
 mod result;
+mod stream;
 
+pub use base_fee::*;
 pub use chain::*;
+pub use deferred_commit::*;
 pub use executor::*;
 pub use factory::*;
 pub use hardfork::*;
 pub use helpers::*;
 pub use limit::*;
+pub use mini_block::*;
+pub use policy::*;
+pub use prefetch::*;
 pub use result::*;
+pub use stream::*;
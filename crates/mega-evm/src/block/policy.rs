@@ -0,0 +1,84 @@
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::{string::String, vec::Vec};
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+
+/// A transaction as seen by a [`TxPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxPolicyInput {
+    /// The transaction hash.
+    pub hash: B256,
+    /// The recovered sender.
+    pub sender: Address,
+    /// The callee, or `None` for a contract creation.
+    pub to: Option<Address>,
+    /// The value transferred.
+    pub value: U256,
+    /// The calldata, or the initcode of a contract creation.
+    pub input: Bytes,
+}
+
+/// The reason a [`TxPolicy`] rejects a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TxPolicyViolation {
+    /// The sender is not allowed to send transactions.
+    #[error("sender {0} is not allowed")]
+    SenderDenied(Address),
+    /// The callee is not allowed to be called.
+    #[error("callee {0} is not allowed")]
+    CalleeDenied(Address),
+    /// A policy-specific reason.
+    #[error("{0}")]
+    Other(String),
+}
+
+/// A filter consulted by the [`MegaBlockExecutor`](crate::MegaBlockExecutor) before executing
+/// each non-deposit transaction, e.g. to enforce sanctions lists or to only admit operator
+/// transactions during maintenance.
+///
+/// A rejected transaction is not executed and is recorded as a [`TxRejection`] instead of failing
+/// the block. Deposit transactions are derived from L1 and must be included, so they are never
+/// checked.
+pub trait TxPolicy: core::fmt::Debug + Send + Sync {
+    /// Checks whether `tx` may be executed.
+    fn check(&self, tx: &TxPolicyInput) -> Result<(), TxPolicyViolation>;
+}
+
+/// A transaction rejected by a [`TxPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxRejection {
+    /// The hash of the rejected transaction.
+    pub hash: B256,
+    /// The sender of the rejected transaction.
+    pub sender: Address,
+    /// Why the transaction was rejected.
+    pub violation: TxPolicyViolation,
+}
+
+/// A [`TxPolicy`] filtering transactions by sender and callee.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressPolicy {
+    /// Only the listed senders are allowed, to any callee.
+    Allow(Vec<Address>),
+    /// Transactions from or to a listed address are rejected.
+    Deny(Vec<Address>),
+}
+
+impl TxPolicy for AddressPolicy {
+    fn check(&self, tx: &TxPolicyInput) -> Result<(), TxPolicyViolation> {
+        match self {
+            Self::Allow(senders) if !senders.contains(&tx.sender) => {
+                Err(TxPolicyViolation::SenderDenied(tx.sender))
+            }
+            Self::Allow(_) => Ok(()),
+            Self::Deny(denied) if denied.contains(&tx.sender) => {
+                Err(TxPolicyViolation::SenderDenied(tx.sender))
+            }
+            Self::Deny(denied) => match tx.to {
+                Some(to) if denied.contains(&to) => Err(TxPolicyViolation::CalleeDenied(to)),
+                _ => Ok(()),
+            },
+        }
+    }
+}
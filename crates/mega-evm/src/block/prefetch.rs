@@ -0,0 +1,270 @@
+//! State prefetching ahead of transaction execution.
+//!
+//! Execution reads state one account or slot at a time, each read stalling the EVM on a cold
+//! backend. A [`Prefetcher`] set on the [`MegaBlockExecutor`](crate::MegaBlockExecutor) (see
+//! [`with_prefetcher`](crate::MegaBlockExecutor::with_prefetcher)) predicts from a transaction,
+//! before it runs, the accounts and storage slots it is likely to read, and the executor loads
+//! them as one batch into its state cache. A wrong prediction only costs the wasted reads:
+//! prefetching never changes the outcome of a transaction.
+//!
+//! [`CalldataPrefetcher`] predicts from the calldata of common calls: ERC-20 transfers and
+//! approvals and Uniswap V2 router swaps.
+
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::vec::Vec;
+
+use alloy_primitives::{keccak256, Address, U256};
+use alloy_sol_types::SolCall;
+
+alloy_sol_types::sol! {
+    interface IERC20 {
+        function transfer(address to, uint256 amount) external returns (bool);
+        function transferFrom(address from, address to, uint256 amount) external returns (bool);
+        function approve(address spender, uint256 amount) external returns (bool);
+    }
+
+    interface IUniswapV2Router {
+        function swapExactTokensForTokens(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline) external returns (uint256[] amounts);
+        function swapTokensForExactTokens(uint256 amountOut, uint256 amountInMax, address[] path, address to, uint256 deadline) external returns (uint256[] amounts);
+        function swapExactETHForTokens(uint256 amountOutMin, address[] path, address to, uint256 deadline) external payable returns (uint256[] amounts);
+        function swapExactTokensForETH(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline) external returns (uint256[] amounts);
+    }
+}
+
+/// A transaction as seen by a [`Prefetcher`].
+#[derive(Debug, Clone, Copy)]
+pub struct PrefetchTx<'a> {
+    /// The recovered sender.
+    pub sender: Address,
+    /// The callee, or `None` for a contract creation.
+    pub to: Option<Address>,
+    /// The calldata, or the initcode of a contract creation.
+    pub input: &'a [u8],
+}
+
+/// The state a transaction is predicted to read.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatePrediction {
+    /// The accounts.
+    pub accounts: Vec<Address>,
+    /// The storage slots, with the account they belong to.
+    pub slots: Vec<(Address, U256)>,
+}
+
+impl StatePrediction {
+    /// Returns whether nothing is predicted.
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty() && self.slots.is_empty()
+    }
+
+    /// Clears the prediction, keeping the allocations.
+    pub fn clear(&mut self) {
+        self.accounts.clear();
+        self.slots.clear();
+    }
+}
+
+/// A predictor of the state a transaction reads, consulted by the
+/// [`MegaBlockExecutor`](crate::MegaBlockExecutor) before executing each transaction.
+pub trait Prefetcher: core::fmt::Debug + Send + Sync {
+    /// Adds the state `tx` is predicted to read to `prediction`.
+    fn predict(&self, tx: &PrefetchTx<'_>, prediction: &mut StatePrediction);
+}
+
+/// A [`Prefetcher`] decoding the calldata of common calls.
+///
+/// Every transaction is predicted to read its sender and callee. On top of that:
+///
+/// - ERC-20 `transfer`, `transferFrom` and `approve` are predicted to read the balances and
+///   allowance they update on the callee;
+/// - Uniswap V2 router swaps are predicted to read the tokens of the swap path, the sender's
+///   balance of the input token and the recipient's balance of the output token.
+///
+/// Token balances and allowances are located assuming the layout of the `OpenZeppelin` ERC-20,
+/// i.e. the mappings declared in slots 0 and 1, unless configured otherwise with
+/// [`with_erc20_layout`](Self::with_erc20_layout).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalldataPrefetcher {
+    balances_slot: U256,
+    allowances_slot: U256,
+}
+
+impl Default for CalldataPrefetcher {
+    fn default() -> Self {
+        Self { balances_slot: U256::ZERO, allowances_slot: U256::from(1) }
+    }
+}
+
+impl CalldataPrefetcher {
+    /// Sets the slots of the `balances` (`address => uint256`) and `allowances`
+    /// (`address => address => uint256`) mappings of ERC-20 tokens.
+    pub fn with_erc20_layout(mut self, balances_slot: U256, allowances_slot: U256) -> Self {
+        self.balances_slot = balances_slot;
+        self.allowances_slot = allowances_slot;
+        self
+    }
+
+    /// Returns the slot holding the balance of `owner`.
+    fn balance_slot(&self, owner: Address) -> U256 {
+        mapping_slot(owner, self.balances_slot)
+    }
+
+    /// Returns the slot holding the allowance of `spender` over the tokens of `owner`.
+    fn allowance_slot(&self, owner: Address, spender: Address) -> U256 {
+        mapping_slot(spender, mapping_slot(owner, self.allowances_slot))
+    }
+
+    /// Adds the state read by a Uniswap V2 router swap of `sender` along `path` to `recipient`.
+    fn predict_swap(
+        &self,
+        sender: Address,
+        path: &[Address],
+        recipient: Address,
+        prediction: &mut StatePrediction,
+    ) {
+        let (Some(&token_in), Some(&token_out)) = (path.first(), path.last()) else { return };
+        prediction.accounts.extend_from_slice(path);
+        prediction.slots.push((token_in, self.balance_slot(sender)));
+        prediction.slots.push((token_out, self.balance_slot(recipient)));
+    }
+}
+
+impl Prefetcher for CalldataPrefetcher {
+    fn predict(&self, tx: &PrefetchTx<'_>, prediction: &mut StatePrediction) {
+        let sender = tx.sender;
+        prediction.accounts.push(sender);
+        let Some(to) = tx.to else { return };
+        prediction.accounts.push(to);
+        let Some(selector) = tx.input.get(..4) else { return };
+
+        match <[u8; 4]>::try_from(selector).expect("the selector has four bytes") {
+            IERC20::transferCall::SELECTOR => {
+                let Ok(call) = IERC20::transferCall::abi_decode(tx.input) else { return };
+                prediction.slots.push((to, self.balance_slot(sender)));
+                prediction.slots.push((to, self.balance_slot(call.to)));
+            }
+            IERC20::transferFromCall::SELECTOR => {
+                let Ok(call) = IERC20::transferFromCall::abi_decode(tx.input) else { return };
+                prediction.slots.push((to, self.balance_slot(call.from)));
+                prediction.slots.push((to, self.balance_slot(call.to)));
+                prediction.slots.push((to, self.allowance_slot(call.from, sender)));
+            }
+            IERC20::approveCall::SELECTOR => {
+                let Ok(call) = IERC20::approveCall::abi_decode(tx.input) else { return };
+                prediction.slots.push((to, self.allowance_slot(sender, call.spender)));
+            }
+            IUniswapV2Router::swapExactTokensForTokensCall::SELECTOR => {
+                let Ok(call) = IUniswapV2Router::swapExactTokensForTokensCall::abi_decode(tx.input)
+                else {
+                    return;
+                };
+                self.predict_swap(sender, &call.path, call.to, prediction);
+            }
+            IUniswapV2Router::swapTokensForExactTokensCall::SELECTOR => {
+                let Ok(call) = IUniswapV2Router::swapTokensForExactTokensCall::abi_decode(tx.input)
+                else {
+                    return;
+                };
+                self.predict_swap(sender, &call.path, call.to, prediction);
+            }
+            IUniswapV2Router::swapExactETHForTokensCall::SELECTOR => {
+                let Ok(call) = IUniswapV2Router::swapExactETHForTokensCall::abi_decode(tx.input)
+                else {
+                    return;
+                };
+                self.predict_swap(sender, &call.path, call.to, prediction);
+            }
+            IUniswapV2Router::swapExactTokensForETHCall::SELECTOR => {
+                let Ok(call) = IUniswapV2Router::swapExactTokensForETHCall::abi_decode(tx.input)
+                else {
+                    return;
+                };
+                self.predict_swap(sender, &call.path, call.to, prediction);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the slot of `key` in a Solidity mapping declared at `slot`.
+fn mapping_slot(key: Address, slot: U256) -> U256 {
+    let mut preimage = [0u8; 64];
+    preimage[12..32].copy_from_slice(key.as_slice());
+    preimage[32..].copy_from_slice(&slot.to_be_bytes::<32>());
+    U256::from_be_bytes(keccak256(preimage).0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    const ALICE: Address = address!("0000000000000000000000000000000000100001");
+    const BOB: Address = address!("0000000000000000000000000000000000100002");
+    const TOKEN: Address = address!("0000000000000000000000000000000000200001");
+    const ROUTER: Address = address!("0000000000000000000000000000000000200002");
+    const WETH: Address = address!("0000000000000000000000000000000000200003");
+
+    fn predict(to: Address, input: &[u8]) -> StatePrediction {
+        let mut prediction = StatePrediction::default();
+        let tx = PrefetchTx { sender: ALICE, to: Some(to), input };
+        CalldataPrefetcher::default().predict(&tx, &mut prediction);
+        prediction
+    }
+
+    #[test]
+    fn test_mapping_slot_matches_solidity_layout() {
+        // `keccak256(abi.encode(address(0), uint256(0)))`.
+        assert_eq!(
+            mapping_slot(Address::ZERO, U256::ZERO),
+            "0xad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5"
+                .parse::<U256>()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_predicts_erc20_balances_and_allowances() {
+        let prefetcher = CalldataPrefetcher::default();
+        let transfer = IERC20::transferCall { to: BOB, amount: U256::from(1) }.abi_encode();
+        let prediction = predict(TOKEN, &transfer);
+        assert_eq!(prediction.accounts, [ALICE, TOKEN]);
+        assert_eq!(
+            prediction.slots,
+            [(TOKEN, prefetcher.balance_slot(ALICE)), (TOKEN, prefetcher.balance_slot(BOB))]
+        );
+
+        let approve = IERC20::approveCall { spender: ROUTER, amount: U256::MAX }.abi_encode();
+        assert_eq!(
+            predict(TOKEN, &approve).slots,
+            [(TOKEN, prefetcher.allowance_slot(ALICE, ROUTER))]
+        );
+    }
+
+    #[test]
+    fn test_predicts_swap_path_tokens() {
+        let prefetcher = CalldataPrefetcher::default();
+        let swap = IUniswapV2Router::swapExactTokensForTokensCall {
+            amountIn: U256::from(1),
+            amountOutMin: U256::ZERO,
+            path: vec![TOKEN, WETH],
+            to: BOB,
+            deadline: U256::MAX,
+        }
+        .abi_encode();
+        let prediction = predict(ROUTER, &swap);
+        assert_eq!(prediction.accounts, [ALICE, ROUTER, TOKEN, WETH]);
+        assert_eq!(
+            prediction.slots,
+            [(TOKEN, prefetcher.balance_slot(ALICE)), (WETH, prefetcher.balance_slot(BOB))]
+        );
+    }
+
+    #[test]
+    fn test_unknown_or_malformed_calldata_predicts_only_the_parties() {
+        assert!(predict(TOKEN, &[0xde, 0xad, 0xbe, 0xef]).slots.is_empty());
+        assert!(predict(TOKEN, &IERC20::transferCall::SELECTOR).slots.is_empty());
+        assert_eq!(predict(TOKEN, &[]).accounts, [ALICE, TOKEN]);
+    }
+}
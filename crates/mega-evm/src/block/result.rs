@@ -1,7 +1,13 @@
-use alloy_evm::InvalidTxError;
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::vec::Vec;
+
+use alloy_consensus::TxReceipt;
+use alloy_evm::{block::BlockExecutionResult, InvalidTxError};
+use alloy_primitives::{Bloom, B256};
 use revm::state::AccountInfo;
 
-use crate::MegaTransactionOutcome;
+use crate::{DetainedGasRefund, DetentionExemptions, MegaTransactionOutcome, VolatileDataAccess};
 
 /// The execution outcome of a transaction in `MegaETH`.
 ///
@@ -23,6 +29,75 @@ pub struct BlockMegaTransactionOutcome<T> {
     pub inner: MegaTransactionOutcome,
 }
 
+/// Block-level aggregate of the volatile data accessed by the transactions committed to a block,
+/// for sequencer analytics.
+///
+/// Transactions that read the block environment are detained: their compute gas is capped
+/// shortly after the access. The summary tells how many transactions of a block touched the most
+/// commonly read fields and how much gas detention withheld at most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockEnvAccessSummary {
+    /// The number of transactions recorded.
+    pub txs: u64,
+    /// The number of transactions that accessed any volatile data.
+    pub volatile_txs: u64,
+    /// The number of transactions that read the block timestamp.
+    pub timestamp_txs: u64,
+    /// The number of transactions that read the block number.
+    pub number_txs: u64,
+    /// The number of transactions that read the block coinbase.
+    pub coinbase_txs: u64,
+    /// The union of the volatile data accessed by all transactions.
+    pub accessed: VolatileDataAccess,
+    /// The largest gas refunded to a transaction halted by the detained compute gas limit, i.e.
+    /// the most gas detention withheld from a single transaction.
+    pub max_detained_gas: u64,
+    /// The [`DetentionExemptions::hash`] of the set the block was executed with. Detention, and
+    /// so the block, depends on the set: a replay reporting another hash used another set.
+    pub detention_exemptions_hash: B256,
+}
+
+impl Default for BlockEnvAccessSummary {
+    fn default() -> Self {
+        Self {
+            txs: 0,
+            volatile_txs: 0,
+            timestamp_txs: 0,
+            number_txs: 0,
+            coinbase_txs: 0,
+            accessed: VolatileDataAccess::default(),
+            max_detained_gas: 0,
+            detention_exemptions_hash: DetentionExemptions::EMPTY_HASH,
+        }
+    }
+}
+
+impl BlockEnvAccessSummary {
+    /// Records a transaction that accessed `accessed` and, if it was halted by detention, had
+    /// `detained_gas_refund` refunded.
+    pub fn record(
+        &mut self,
+        accessed: VolatileDataAccess,
+        detained_gas_refund: Option<&DetainedGasRefund>,
+    ) {
+        let count = |flag| u64::from(accessed.contains(flag));
+        self.txs += 1;
+        self.volatile_txs += u64::from(!accessed.is_empty());
+        self.timestamp_txs += count(VolatileDataAccess::TIMESTAMP);
+        self.number_txs += count(VolatileDataAccess::BLOCK_NUMBER);
+        self.coinbase_txs += count(VolatileDataAccess::COINBASE);
+        self.accessed |= accessed;
+        if let Some(refund) = detained_gas_refund {
+            self.max_detained_gas = self.max_detained_gas.max(refund.refunded_gas);
+        }
+    }
+
+    /// Records the transaction of `outcome`.
+    pub fn record_outcome(&mut self, outcome: &MegaTransactionOutcome) {
+        self.record(outcome.volatile_data_accessed, outcome.detained_gas_refund.as_ref());
+    }
+}
+
 /// Error type for additional reasons of an invalid transaction. If one transaction is invalid, it
 /// will never be able to be included in a block and should be discarded.
 #[derive(Debug, Clone, thiserror::Error)]
@@ -177,9 +252,129 @@ impl InvalidTxError for MegaBlockLimitExceededError {
     }
 }
 
+/// Header and receipt fields derived from the receipts of an executed block, which the node
+/// would otherwise recompute from the raw receipts.
+///
+/// The cumulative gas used of each transaction is already carried by its receipt, and the gas used
+/// by the block by [`BlockExecutionResult::gas_used`].
+pub trait MegaBlockExecutionResultExt {
+    /// Returns the logs bloom of the block header: the union of the blooms of all receipts.
+    fn logs_bloom(&self) -> Bloom;
+
+    /// Returns, for each transaction, the block-wide index of its first log, i.e. the number of
+    /// logs emitted by the transactions before it.
+    fn log_index_offsets(&self) -> Vec<u64>;
+
+    /// Returns the gas used by each transaction, the difference between the cumulative gas used
+    /// of its receipt and of the previous receipt.
+    fn tx_gas_used(&self) -> Vec<u64>;
+}
+
+impl<R: TxReceipt> MegaBlockExecutionResultExt for BlockExecutionResult<R> {
+    fn logs_bloom(&self) -> Bloom {
+        let mut bloom = Bloom::ZERO;
+        for receipt in &self.receipts {
+            bloom.accrue_bloom(&receipt.bloom());
+        }
+        bloom
+    }
+
+    fn log_index_offsets(&self) -> Vec<u64> {
+        let mut offset = 0;
+        self.receipts
+            .iter()
+            .map(|receipt| {
+                let first = offset;
+                offset += receipt.logs().len() as u64;
+                first
+            })
+            .collect()
+    }
+
+    fn tx_gas_used(&self) -> Vec<u64> {
+        let mut previous = 0;
+        self.receipts
+            .iter()
+            .map(|receipt| {
+                let cumulative = receipt.cumulative_gas_used();
+                let gas_used = cumulative - previous;
+                previous = cumulative;
+                gas_used
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_consensus::{Eip658Value, Receipt};
+    use alloy_primitives::{Address, BloomInput, Log};
+    use op_alloy_consensus::OpReceipt;
+
+    #[test]
+    fn test_block_execution_result_ext_derives_header_fields() {
+        let log = |byte| Log::new_unchecked(Address::repeat_byte(byte), vec![], Default::default());
+        let receipt = |cumulative_gas_used, logs| {
+            OpReceipt::Legacy(Receipt {
+                status: Eip658Value::Eip658(true),
+                cumulative_gas_used,
+                logs,
+            })
+        };
+        let result = BlockExecutionResult {
+            receipts: vec![
+                receipt(21_000, vec![log(1), log(2)]),
+                receipt(42_000, vec![]),
+                receipt(100_000, vec![log(3)]),
+            ],
+            requests: Default::default(),
+            gas_used: 100_000,
+        };
+
+        assert_eq!(result.log_index_offsets(), [0, 2, 2]);
+        assert_eq!(result.tx_gas_used(), [21_000, 21_000, 58_000]);
+        let bloom = result.logs_bloom();
+        for byte in 1..=3 {
+            assert!(bloom.contains_input(BloomInput::Raw(Address::repeat_byte(byte).as_slice())));
+        }
+        assert!(!bloom.contains_input(BloomInput::Raw(Address::repeat_byte(4).as_slice())));
+    }
+
+    #[test]
+    fn test_block_env_access_summary_aggregates_transactions() {
+        let refund = |refunded_gas| DetainedGasRefund {
+            access_type: VolatileDataAccess::TIMESTAMP,
+            detained_limit: 20_000_000,
+            compute_gas_used: 20_000_000,
+            refunded_gas,
+        };
+        let mut summary = BlockEnvAccessSummary::default();
+        summary.record(VolatileDataAccess::empty(), None);
+        summary.record(
+            VolatileDataAccess::TIMESTAMP | VolatileDataAccess::BLOCK_NUMBER,
+            Some(&refund(500)),
+        );
+        summary.record(VolatileDataAccess::TIMESTAMP, Some(&refund(300)));
+        summary.record(VolatileDataAccess::COINBASE | VolatileDataAccess::ORACLE, None);
+
+        assert_eq!(
+            summary,
+            BlockEnvAccessSummary {
+                txs: 4,
+                volatile_txs: 3,
+                timestamp_txs: 2,
+                number_txs: 1,
+                coinbase_txs: 1,
+                accessed: VolatileDataAccess::TIMESTAMP |
+                    VolatileDataAccess::BLOCK_NUMBER |
+                    VolatileDataAccess::COINBASE |
+                    VolatileDataAccess::ORACLE,
+                max_detained_gas: 500,
+                detention_exemptions_hash: DetentionExemptions::EMPTY_HASH,
+            }
+        );
+    }
 
     #[test]
     fn test_transaction_limit_error_reports_usage_and_limit() {
@@ -0,0 +1,71 @@
+//! Streaming of receipts as transactions are committed.
+//!
+//! The receipts of a block are returned together by
+//! [`BlockExecutor::finish`](alloy_evm::block::BlockExecutor::finish), which is too late for
+//! preconfirmations: the real-time API pushes every receipt as soon as its transaction is in the
+//! block. A [`ReceiptHook`] set on the [`MegaBlockExecutor`](crate::MegaBlockExecutor) is called
+//! with each receipt right after its transaction is committed, together with the resources the
+//! transaction used and the usage of the block (and mini-block) so far.
+
+use alloy_primitives::B256;
+
+use crate::BlockLimiter;
+
+/// The resources used by a single committed transaction, as accounted by the [`BlockLimiter`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TxLimitUsage {
+    /// The gas used.
+    pub gas_used: u64,
+    /// The encoded size of the transaction.
+    pub tx_size: u64,
+    /// The data availability size of the transaction. Zero for deposit transactions, which are
+    /// exempt from the DA limits.
+    pub da_size: u64,
+    /// The execution data generated, as accounted per
+    /// [`BlockLimits::data_size_accounting`](crate::BlockLimits::data_size_accounting).
+    pub data_size: u64,
+    /// The key-value storage updates performed.
+    pub kv_updates: u64,
+    /// The compute gas used.
+    pub compute_gas_used: u64,
+    /// The state growth caused.
+    pub state_growth_used: u64,
+}
+
+/// A receipt streamed right after its transaction was committed.
+#[derive(Debug)]
+pub struct StreamedReceipt<'a, R> {
+    /// The index of the transaction in the block.
+    pub index: usize,
+    /// The hash of the transaction.
+    pub tx_hash: B256,
+    /// The receipt of the transaction.
+    pub receipt: &'a R,
+    /// The resources used by the transaction.
+    pub usage: TxLimitUsage,
+    /// The resources used by the block so far, including this transaction.
+    pub block_usage: &'a BlockLimiter,
+    /// The index of the mini-block the transaction was committed to and the resources used by
+    /// that mini-block so far, if the block is executed as mini-blocks.
+    pub mini_block: Option<(u64, &'a BlockLimiter)>,
+}
+
+/// A hook called with every receipt as soon as its transaction is committed.
+///
+/// The hook is called after the state changes of the transaction are committed, so it observes
+/// the transaction as final within the block. Transactions whose outcomes are discarded, e.g.
+/// because the commit condition declined them or a limit rejected them at commit time, are never
+/// streamed.
+pub trait ReceiptHook<R>: Send + 'static {
+    /// Called with the receipt of a committed transaction.
+    fn on_receipt(&mut self, receipt: StreamedReceipt<'_, R>);
+}
+
+impl<R, F> ReceiptHook<R> for F
+where
+    F: FnMut(StreamedReceipt<'_, R>) + Send + 'static,
+{
+    fn on_receipt(&mut self, receipt: StreamedReceipt<'_, R>) {
+        self(receipt)
+    }
+}
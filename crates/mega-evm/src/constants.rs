@@ -1,6 +1,9 @@
 //! Constants for the `MegaETH` EVM.
 //!
-//! It groups the constants for different EVM specs as sub-modules.
+//! It groups the constants for different EVM specs as sub-modules. [`SpecConstants`] resolves the
+//! constants in effect under a given spec.
+
+use crate::{EvmTxRuntimeLimits, MegaSpecId};
 
 /// Constants for the `EQUIVALENCE` spec.
 pub mod equivalence {
@@ -14,6 +17,10 @@ pub mod equivalence {
         WARM_STORAGE_READ_COST,
     };
     pub use revm::primitives::STACK_LIMIT;
+
+    /// The EIP-3529 maximum refund quotient: a transaction is refunded at most `1 /
+    /// MAX_REFUND_QUOTIENT` of the gas it spent.
+    pub const MAX_REFUND_QUOTIENT: u64 = 5;
 }
 
 /// Constants for the `MINI_REX` spec.
@@ -158,7 +165,15 @@ pub mod rex5 {
 }
 
 /// Constants for the `REX6` spec.
-pub mod rex6 {}
+pub mod rex6 {
+    /// The maximum memory size (in bytes) of a single interpreter (call frame) for the `REX6`
+    /// spec. An opcode that would expand memory beyond this size halts the transaction with
+    /// `MemoryLimitExceeded`, regardless of the gas available.
+    pub const INTERPRETER_MEMORY_LIMIT: u64 = 64 * 1024 * 1024;
+    /// The maximum number of call frames a transaction may create for the `REX6` spec, counting
+    /// the top-level frame. Creating one more halts the transaction with `FrameLimitExceeded`.
+    pub const TX_FRAME_LIMIT: u64 = 100_000;
+}
 
 /// Constants for the `REX` spec.
 pub mod rex {
@@ -205,3 +220,106 @@ pub mod rex {
     /// gas.
     pub const BLOCK_STATE_GROWTH_LIMIT: u64 = 1000;
 }
+
+/// The limits and costs in effect under a spec, resolved from the per-spec modules above.
+///
+/// Obtained with [`MegaSpecId::constants`](crate::MegaSpecId::constants). Costs that do not apply
+/// under a spec are zero, and limits that are not enforced are `u64::MAX` (as in
+/// [`EvmTxRuntimeLimits::no_limits`]). Storage gas for SSTORE, account creation and contract
+/// creation is scaled by SALT bucket capacity and is not included; neither are block-level limits,
+/// which depend on the hardfork (see [`BlockLimits`](crate::BlockLimits)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpecConstants {
+    /// The maximum size of deployed contract code.
+    pub max_contract_size: usize,
+    /// The maximum size of contract initcode.
+    pub max_initcode_size: usize,
+    /// The storage gas added to the intrinsic gas of every transaction.
+    pub tx_intrinsic_storage_gas: u64,
+    /// The storage gas per calldata token, charged on top of the standard calldata cost.
+    pub calldata_standard_token_storage_gas: u64,
+    /// The storage gas per calldata token, added to the EIP-7623 floor gas.
+    pub calldata_standard_token_storage_floor_gas: u64,
+    /// The storage gas per byte of deployed code.
+    pub code_deposit_storage_gas: u64,
+    /// The storage gas per byte of log data.
+    pub log_data_storage_gas: u64,
+    /// The storage gas per log topic.
+    pub log_topic_storage_gas: u64,
+    /// The extra stipend for storage gas granted to value-transferring `CALL`/`CALLCODE`.
+    pub storage_call_stipend: u64,
+    /// The fixed overhead gas of a keyless deploy.
+    pub keyless_deploy_overhead_gas: u64,
+    /// The runtime limits of a transaction.
+    pub tx_limits: EvmTxRuntimeLimits,
+}
+
+impl SpecConstants {
+    /// Returns the constants in effect under `spec`.
+    pub fn for_spec(spec: MegaSpecId) -> Self {
+        let enabled = |other| spec.is_enabled(other);
+        let (max_contract_size, max_initcode_size) = if enabled(MegaSpecId::MINI_REX) {
+            (mini_rex::MAX_CONTRACT_SIZE, mini_rex::MAX_INITCODE_SIZE)
+        } else {
+            (revm::primitives::eip170::MAX_CODE_SIZE, revm::primitives::eip3860::MAX_INITCODE_SIZE)
+        };
+        let from_mini_rex = |cost| if enabled(MegaSpecId::MINI_REX) { cost } else { 0 };
+        Self {
+            max_contract_size,
+            max_initcode_size,
+            tx_intrinsic_storage_gas: if enabled(MegaSpecId::REX) {
+                rex::TX_INTRINSIC_STORAGE_GAS
+            } else {
+                0
+            },
+            calldata_standard_token_storage_gas: from_mini_rex(
+                mini_rex::CALLDATA_STANDARD_TOKEN_STORAGE_GAS,
+            ),
+            calldata_standard_token_storage_floor_gas: from_mini_rex(
+                mini_rex::CALLDATA_STANDARD_TOKEN_STORAGE_FLOOR_GAS,
+            ),
+            code_deposit_storage_gas: from_mini_rex(mini_rex::CODEDEPOSIT_STORAGE_GAS),
+            log_data_storage_gas: from_mini_rex(mini_rex::LOG_DATA_STORAGE_GAS),
+            log_topic_storage_gas: from_mini_rex(mini_rex::LOG_TOPIC_STORAGE_GAS),
+            storage_call_stipend: if enabled(MegaSpecId::REX4) {
+                rex4::STORAGE_CALL_STIPEND
+            } else {
+                0
+            },
+            keyless_deploy_overhead_gas: if enabled(MegaSpecId::REX2) {
+                rex2::KEYLESS_DEPLOY_OVERHEAD_GAS
+            } else {
+                0
+            },
+            tx_limits: EvmTxRuntimeLimits::from_spec(spec),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_constants_follow_spec_order() {
+        let equivalence = MegaSpecId::EQUIVALENCE.constants();
+        assert_eq!(equivalence.max_contract_size, revm::primitives::eip170::MAX_CODE_SIZE);
+        assert_eq!(equivalence.log_data_storage_gas, 0);
+        assert_eq!(equivalence.tx_limits, EvmTxRuntimeLimits::no_limits());
+
+        let mini_rex = MegaSpecId::MINI_REX.constants();
+        assert_eq!(mini_rex.max_contract_size, mini_rex::MAX_CONTRACT_SIZE);
+        assert_eq!(mini_rex.tx_intrinsic_storage_gas, 0);
+        assert_eq!(mini_rex.tx_limits.tx_compute_gas_limit, mini_rex::TX_COMPUTE_GAS_LIMIT);
+
+        let rex3 = MegaSpecId::REX3.constants();
+        assert_eq!(rex3.tx_intrinsic_storage_gas, rex::TX_INTRINSIC_STORAGE_GAS);
+        assert_eq!(rex3.keyless_deploy_overhead_gas, rex2::KEYLESS_DEPLOY_OVERHEAD_GAS);
+        assert_eq!(rex3.storage_call_stipend, 0);
+        assert_eq!(rex3.tx_limits.oracle_access_compute_gas_limit, rex3::ORACLE_ACCESS_COMPUTE_GAS);
+
+        let rex6 = MegaSpecId::REX6.constants();
+        assert_eq!(rex6.storage_call_stipend, rex4::STORAGE_CALL_STIPEND);
+        assert_eq!(rex6.tx_limits.tx_frame_limit, rex6::TX_FRAME_LIMIT);
+    }
+}
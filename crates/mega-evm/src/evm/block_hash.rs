@@ -0,0 +1,97 @@
+//! Pluggable sources of the block hashes served to `BLOCKHASH`.
+
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::{collections::BTreeMap, vec::Vec};
+
+use alloy_primitives::B256;
+use core::cell::RefCell;
+use revm::primitives::BLOCK_HASH_HISTORY;
+
+/// A source of the block hashes served to `BLOCKHASH`.
+///
+/// Set on the context with
+/// [`MegaContext::with_block_hash_provider`](crate::MegaContext::with_block_hash_provider). Without
+/// a provider, or when the provider does not know a hash, `BLOCKHASH` reads it from the database.
+/// The window of blocks `BLOCKHASH` may read is enforced before the provider is asked.
+pub trait BlockHashProvider: core::fmt::Debug {
+    /// Returns the hash of block `number`, or `None` to read it from the database.
+    fn block_hash(&self, number: u64) -> Option<B256>;
+}
+
+impl<T: BlockHashProvider + ?Sized> BlockHashProvider for RefCell<T> {
+    fn block_hash(&self, number: u64) -> Option<B256> {
+        self.borrow().block_hash(number)
+    }
+}
+
+/// Serves block hashes from a witness, e.g. when proving a block.
+impl BlockHashProvider for BTreeMap<u64, B256> {
+    fn block_hash(&self, number: u64) -> Option<B256> {
+        self.get(&number).copied()
+    }
+}
+
+/// An in-memory ring buffer of the hashes of the last [`BLOCK_HASH_HISTORY`] blocks, for the
+/// sequencer to serve recent hashes without touching the database.
+///
+/// The sequencer pushes the hash of every block it seals. Sharing the buffer with the context
+/// through a `RefCell` lets it be updated between blocks.
+#[derive(Debug, Clone)]
+pub struct BlockHashRingBuffer {
+    slots: Vec<Option<(u64, B256)>>,
+}
+
+impl Default for BlockHashRingBuffer {
+    fn default() -> Self {
+        Self { slots: Vec::from([None; BLOCK_HASH_HISTORY as usize]) }
+    }
+}
+
+impl BlockHashRingBuffer {
+    /// Creates an empty ring buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the hash of block `number`, evicting the hash of block
+    /// `number - BLOCK_HASH_HISTORY`.
+    pub fn push(&mut self, number: u64, hash: B256) {
+        self.slots[(number % BLOCK_HASH_HISTORY) as usize] = Some((number, hash));
+    }
+}
+
+impl BlockHashProvider for BlockHashRingBuffer {
+    fn block_hash(&self, number: u64) -> Option<B256> {
+        match self.slots[(number % BLOCK_HASH_HISTORY) as usize] {
+            Some((stored, hash)) if stored == number => Some(hash),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_serves_recent_hashes() {
+        let mut buffer = BlockHashRingBuffer::new();
+        buffer.push(1, B256::with_last_byte(1));
+        buffer.push(2, B256::with_last_byte(2));
+
+        assert_eq!(buffer.block_hash(1), Some(B256::with_last_byte(1)));
+        assert_eq!(buffer.block_hash(2), Some(B256::with_last_byte(2)));
+        assert_eq!(buffer.block_hash(3), None);
+    }
+
+    #[test]
+    fn test_ring_buffer_evicts_old_hashes() {
+        let mut buffer = BlockHashRingBuffer::new();
+        buffer.push(1, B256::with_last_byte(1));
+        buffer.push(1 + BLOCK_HASH_HISTORY, B256::with_last_byte(2));
+
+        assert_eq!(buffer.block_hash(1), None, "the slot now holds a newer block");
+        assert_eq!(buffer.block_hash(1 + BLOCK_HASH_HISTORY), Some(B256::with_last_byte(2)));
+    }
+}
@@ -0,0 +1,163 @@
+//! Lightweight recording of the call graph of a transaction.
+
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::vec::Vec;
+
+use alloy_primitives::{Address, U256};
+use revm::{
+    context_interface::CreateScheme,
+    handler::FrameResult,
+    interpreter::{CallScheme, FrameInput, InstructionResult},
+};
+use serde::{Deserialize, Serialize};
+
+/// The kind of a call frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CallKind {
+    /// `CALL`, or the message call of a transaction.
+    Call,
+    /// `STATICCALL`.
+    StaticCall,
+    /// `CALLCODE`.
+    CallCode,
+    /// `DELEGATECALL`.
+    DelegateCall,
+    /// `CREATE`, or the contract creation of a transaction.
+    Create,
+    /// `CREATE2`.
+    Create2,
+}
+
+impl From<CallScheme> for CallKind {
+    fn from(scheme: CallScheme) -> Self {
+        match scheme {
+            CallScheme::Call => Self::Call,
+            CallScheme::StaticCall => Self::StaticCall,
+            CallScheme::CallCode => Self::CallCode,
+            CallScheme::DelegateCall => Self::DelegateCall,
+        }
+    }
+}
+
+/// A call frame of a transaction and the frames it opened, in order.
+///
+/// Every frame the EVM opens is recorded, including calls to precompiles and system contracts and
+/// calls that fail before running any code (e.g. on insufficient balance or call depth).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    /// The kind of the frame.
+    pub kind: CallKind,
+    /// The caller of the frame.
+    pub caller: Address,
+    /// The callee: the code address for calls, the created address for creations. Zero for a
+    /// creation that failed before deriving an address.
+    pub callee: Address,
+    /// The value transferred, or the apparent value of a `DELEGATECALL`.
+    pub value: U256,
+    /// The gas limit of the frame.
+    pub gas_limit: u64,
+    /// The gas spent by the frame, including the gas spent by its children.
+    pub gas_used: u64,
+    /// How the frame ended.
+    pub result: InstructionResult,
+    /// The frames opened by this frame.
+    pub calls: Vec<CallFrame>,
+}
+
+impl CallFrame {
+    fn open(input: &FrameInput) -> Self {
+        let frame = |kind, caller, callee, value, gas_limit| Self {
+            kind,
+            caller,
+            callee,
+            value,
+            gas_limit,
+            gas_used: 0,
+            result: InstructionResult::Stop,
+            calls: Vec::new(),
+        };
+        if let FrameInput::Call(inputs) = input {
+            let kind = inputs.scheme.into();
+            frame(
+                kind,
+                inputs.caller,
+                inputs.bytecode_address,
+                inputs.value.get(),
+                inputs.gas_limit,
+            )
+        } else if let FrameInput::Create(inputs) = input {
+            let kind = match inputs.scheme {
+                CreateScheme::Create2 { .. } => CallKind::Create2,
+                _ => CallKind::Create,
+            };
+            frame(kind, inputs.caller, Address::ZERO, inputs.value, inputs.gas_limit)
+        } else {
+            frame(CallKind::Call, Address::ZERO, Address::ZERO, U256::ZERO, 0)
+        }
+    }
+
+    fn close(&mut self, result: &FrameResult) {
+        if let FrameResult::Create(outcome) = result {
+            self.callee = outcome.address.unwrap_or_default();
+        }
+        self.gas_used = result.gas().spent();
+        self.result = result.interpreter_result().result;
+    }
+
+    /// Returns whether the frame completed without reverting or halting. A successful frame may
+    /// still have failed children.
+    pub fn is_success(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Records the call graph of a transaction from the frame lifecycle, cheaper than a tracing
+/// inspector since no per-opcode hook runs.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CallGraphRecorder {
+    /// The frames that have been opened but not closed yet, outermost first.
+    open: Vec<CallFrame>,
+    /// The closed outermost frame of the transaction.
+    root: Option<CallFrame>,
+}
+
+impl CallGraphRecorder {
+    /// Clears the recording for a new transaction.
+    pub(crate) fn clear(&mut self) {
+        self.open.clear();
+        self.root = None;
+    }
+
+    /// Records that a frame is opened.
+    pub(crate) fn enter(&mut self, input: &FrameInput) {
+        self.open.push(CallFrame::open(input));
+    }
+
+    /// Records that the innermost open frame returned `result`.
+    pub(crate) fn exit(&mut self, result: &FrameResult) {
+        let Some(mut frame) = self.open.pop() else { return };
+        frame.close(result);
+        match self.open.last_mut() {
+            Some(parent) => parent.calls.push(frame),
+            None => self.root = Some(frame),
+        }
+    }
+
+    /// Returns whether a frame is open.
+    pub(crate) fn has_open_frame(&self) -> bool {
+        !self.open.is_empty()
+    }
+
+    /// Returns the call graph of the transaction, if its outermost frame has returned.
+    pub(crate) fn root(&self) -> Option<&CallFrame> {
+        self.root.as_ref()
+    }
+
+    /// Takes the call graph of the transaction, if its outermost frame has returned.
+    pub(crate) fn take(&mut self) -> Option<CallFrame> {
+        self.root.take()
+    }
+}
@@ -0,0 +1,133 @@
+//! Chain-level configuration of a `MegaETH` network.
+
+use alloy_eips::eip1559::BaseFeeParams;
+use alloy_primitives::{Address, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::MEGA_SYSTEM_ADDRESS;
+
+/// Parameters of a `MegaETH` chain that stay fixed from block to block.
+///
+/// A [`MegaContext`](crate::MegaContext) applies it at construction through
+/// [`MegaContext::with_chain_config`](crate::MegaContext::with_chain_config), and a
+/// [`MegaEvmFactory`](crate::MegaEvmFactory) applies it to every EVM it creates. The chain id of
+/// an EVM is the one of its `EvmEnv`, never overwritten by the configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MegaChainConfig {
+    /// The chain id, checked against the chain id of transactions on mempool admission (see
+    /// [`AdmissionConfig`](crate::mempool::AdmissionConfig)). The EVM checks transactions and
+    /// EIP-7702 authorizations against the chain id of its `EvmEnv`, which must be the same.
+    pub chain_id: u64,
+    /// The operator fee scalar. When `None`, the value loaded from the `L1Block` contract is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator_fee_scalar: Option<U256>,
+    /// The operator fee constant. When `None`, the value loaded from the `L1Block` contract is
+    /// used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator_fee_constant: Option<U256>,
+    /// The EIP-1559 parameters used to derive the base fee of the next block by
+    /// [`next_base_fee`](crate::next_base_fee).
+    pub base_fee_params: BaseFeeParams,
+    /// The system address before it is resolved from the `SequencerRegistry` (REX5+).
+    pub system_address: Address,
+}
+
+impl Default for MegaChainConfig {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl MegaChainConfig {
+    /// Creates the configuration of the chain with the given id, with the default base fee
+    /// parameters of the OP Stack and the operator fee read from the `L1Block` contract.
+    pub fn new(chain_id: u64) -> Self {
+        Self {
+            chain_id,
+            operator_fee_scalar: None,
+            operator_fee_constant: None,
+            base_fee_params: BaseFeeParams::optimism_canyon(),
+            system_address: MEGA_SYSTEM_ADDRESS,
+        }
+    }
+
+    /// Sets the operator fee scalar and constant.
+    pub const fn with_operator_fee(mut self, scalar: U256, constant: U256) -> Self {
+        self.operator_fee_scalar = Some(scalar);
+        self.operator_fee_constant = Some(constant);
+        self
+    }
+
+    /// Charges no operator fee, e.g. to compare execution costs with plain Ethereum EVMs.
+    pub const fn without_operator_fee(self) -> Self {
+        self.with_operator_fee(U256::ZERO, U256::ZERO)
+    }
+
+    /// Sets the base fee parameters.
+    pub const fn with_base_fee_params(mut self, base_fee_params: BaseFeeParams) -> Self {
+        self.base_fee_params = base_fee_params;
+        self
+    }
+
+    /// Sets the system address.
+    pub const fn with_system_address(mut self, system_address: Address) -> Self {
+        self.system_address = system_address;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MegaContext, MegaSpecId};
+    use alloy_primitives::address;
+    use revm::{context::ContextTr, database::EmptyDB};
+
+    #[test]
+    fn test_context_applies_chain_config() {
+        let system_address = address!("0000000000000000000000000000000000abcdef");
+        let config = MegaChainConfig::new(6342)
+            .with_operator_fee(U256::from(7), U256::from(11))
+            .with_system_address(system_address);
+        let mut context = MegaContext::new(EmptyDB::default(), MegaSpecId::REX4);
+        context.modify_cfg(|cfg| cfg.chain_id = 6342);
+        let context = context.with_chain_config(config);
+        assert_eq!(context.cfg().chain_id, 6342);
+        assert_eq!(context.chain().operator_fee_scalar, Some(U256::from(7)));
+        assert_eq!(context.chain().operator_fee_constant, Some(U256::from(11)));
+        assert_eq!(context.system_address(), system_address);
+    }
+
+    #[test]
+    fn test_chain_config_keeps_cfg_chain_id() {
+        let context = MegaContext::new(EmptyDB::default(), MegaSpecId::REX4)
+            .with_chain_config(MegaChainConfig::new(6342).without_operator_fee());
+        assert_eq!(context.cfg().chain_id, 1, "the configuration must not retarget the context");
+        assert_eq!(context.chain().operator_fee_scalar, Some(U256::ZERO));
+    }
+
+    #[test]
+    fn test_unset_operator_fee_keeps_chain_values() {
+        let context = MegaContext::new(EmptyDB::default(), MegaSpecId::REX4)
+            .with_chain_config(MegaChainConfig::default().without_operator_fee())
+            .with_chain_config(MegaChainConfig::default());
+        assert_eq!(context.chain().operator_fee_scalar, Some(U256::ZERO));
+        assert_eq!(context.chain().operator_fee_constant, Some(U256::ZERO));
+    }
+
+    #[test]
+    fn test_chain_config_serde_roundtrip() {
+        let config = MegaChainConfig::new(6342).without_operator_fee();
+        let json = serde_json::to_value(config).unwrap();
+        assert_eq!(json["chainId"], 6342);
+        assert_eq!(serde_json::from_value::<MegaChainConfig>(json).unwrap(), config);
+
+        let json = serde_json::to_value(MegaChainConfig::default()).unwrap();
+        assert!(json.get("operatorFeeScalar").is_none());
+        assert_eq!(
+            serde_json::from_value::<MegaChainConfig>(json).unwrap(),
+            MegaChainConfig::default()
+        );
+    }
+}
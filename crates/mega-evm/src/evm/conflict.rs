@@ -0,0 +1,172 @@
+//! Read/write conflicts between the state accesses of two transactions.
+
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::{collections::BTreeSet, vec::Vec};
+
+use alloy_primitives::{Address, U256};
+use revm::state::EvmState;
+
+use crate::MegaTransactionOutcome;
+
+/// A piece of state accessed by a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StateLocation {
+    /// The info of an account: balance, nonce and code.
+    Account(Address),
+    /// A storage slot of an account.
+    Storage(Address, U256),
+}
+
+impl StateLocation {
+    /// Returns the address of the account the location belongs to.
+    pub const fn address(&self) -> Address {
+        match *self {
+            Self::Account(address) | Self::Storage(address, _) => address,
+        }
+    }
+
+    /// Returns the locations accessed in `state`, the state of an executed transaction: every
+    /// loaded account and storage slot, whether it was only read or also written.
+    pub fn accessed(state: &EvmState) -> impl Iterator<Item = Self> + '_ {
+        state.iter().flat_map(|(address, account)| {
+            core::iter::once(Self::Account(*address))
+                .chain(account.storage.keys().map(|slot| Self::Storage(*address, *slot)))
+        })
+    }
+
+    /// Returns the locations written in `state`, the state of an executed transaction: the
+    /// touched accounts and their changed storage slots.
+    pub fn written(state: &EvmState) -> impl Iterator<Item = Self> + '_ {
+        state.iter().filter(|(_, account)| account.is_touched()).flat_map(|(address, account)| {
+            core::iter::once(Self::Account(*address)).chain(
+                account
+                    .storage
+                    .iter()
+                    .filter(|(_, slot)| slot.is_changed())
+                    .map(|(slot, _)| Self::Storage(*address, *slot)),
+            )
+        })
+    }
+}
+
+/// The overlapping state accesses of two transactions `a` and `b`, computed by [`conflicts`].
+///
+/// Reads include every accessed location, so a location written by both transactions is reported
+/// in all three sets.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConflictReport {
+    /// Locations written by `a` and accessed by `b`: `b` depends on `a` if `a` runs first.
+    pub write_read: Vec<StateLocation>,
+    /// Locations accessed by `a` and written by `b`: `a` depends on `b` if `b` runs first.
+    pub read_write: Vec<StateLocation>,
+    /// Locations written by both transactions.
+    pub write_write: Vec<StateLocation>,
+}
+
+impl ConflictReport {
+    /// Returns whether the transactions do not conflict and can be executed in any order.
+    pub fn is_empty(&self) -> bool {
+        self.write_read.is_empty() && self.read_write.is_empty() && self.write_write.is_empty()
+    }
+
+    /// Returns the report without the locations of `accounts`.
+    ///
+    /// Every transaction credits the fee vaults, and the beneficiary unless its reward is
+    /// deferred. Schedulers that apply these credits as increments exclude them here.
+    pub fn without_accounts(mut self, accounts: &[Address]) -> Self {
+        for locations in [&mut self.write_read, &mut self.read_write, &mut self.write_write] {
+            locations.retain(|location| !accounts.contains(&location.address()));
+        }
+        self
+    }
+}
+
+/// Returns the state conflicts between the transactions that produced `a` and `b`, each executed
+/// on its own against the same state.
+pub fn conflicts(a: &MegaTransactionOutcome, b: &MegaTransactionOutcome) -> ConflictReport {
+    let accessed_a: BTreeSet<_> = StateLocation::accessed(&a.state).collect();
+    let accessed_b: BTreeSet<_> = StateLocation::accessed(&b.state).collect();
+    let written_a: BTreeSet<_> = StateLocation::written(&a.state).collect();
+    let written_b: BTreeSet<_> = StateLocation::written(&b.state).collect();
+    ConflictReport {
+        write_read: written_a.intersection(&accessed_b).copied().collect(),
+        read_write: accessed_a.intersection(&written_b).copied().collect(),
+        write_write: written_a.intersection(&written_b).copied().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{BytecodeBuilder, MemoryDatabase},
+        MegaChainConfig, MegaContext, MegaEvm, MegaSpecId, MegaTransaction,
+    };
+    use alloy_primitives::{address, Bytes, TxKind};
+    use op_revm::constants::{BASE_FEE_RECIPIENT, L1_FEE_RECIPIENT, OPERATOR_FEE_RECIPIENT};
+    use revm::{
+        bytecode::opcode::{PUSH0, SLOAD, SSTORE, STOP},
+        context::{BlockEnv, TxEnv},
+    };
+
+    const ALICE: Address = address!("4000000000000000000000000000000000000001");
+    const BOB: Address = address!("4000000000000000000000000000000000000002");
+    const WRITER: Address = address!("5000000000000000000000000000000000000001");
+    const READER: Address = address!("5000000000000000000000000000000000000002");
+    const OTHER_WRITER: Address = address!("5000000000000000000000000000000000000003");
+
+    /// Runs a call from `caller` to `callee` against a fresh copy of the same state.
+    fn run(caller: Address, callee: Address) -> MegaTransactionOutcome {
+        let write =
+            || BytecodeBuilder::default().push_number(1_u8).append(PUSH0).append(SSTORE).build();
+        let read = BytecodeBuilder::default().append(PUSH0).append(SLOAD).append(STOP).build();
+        let db = MemoryDatabase::default()
+            .account_balance(ALICE, U256::from(10).pow(U256::from(18)))
+            .account_balance(BOB, U256::from(10).pow(U256::from(18)))
+            .account_code(WRITER, write())
+            .account_code(OTHER_WRITER, write())
+            .account_code(READER, read);
+        let context = MegaContext::new(db, MegaSpecId::REX4)
+            .with_block(BlockEnv { basefee: 1, ..Default::default() })
+            .with_chain_config(MegaChainConfig::default().without_operator_fee());
+        let mut tx = MegaTransaction::new(TxEnv {
+            caller,
+            gas_limit: 1_000_000,
+            gas_price: 1,
+            kind: TxKind::Call(callee),
+            ..Default::default()
+        });
+        tx.enveloped_tx = Some(Bytes::new());
+        MegaEvm::new(context).execute_transaction(tx).unwrap()
+    }
+
+    #[test]
+    fn test_same_slot_writes_conflict() {
+        let report = conflicts(&run(ALICE, WRITER), &run(BOB, WRITER));
+        let slot = StateLocation::Storage(WRITER, U256::ZERO);
+        assert!(report.write_write.contains(&slot));
+        assert!(report.write_read.contains(&slot));
+        assert!(report.read_write.contains(&slot));
+    }
+
+    #[test]
+    fn test_disjoint_transactions_do_not_conflict() {
+        let report = conflicts(&run(ALICE, WRITER), &run(BOB, OTHER_WRITER));
+        assert!(!report.is_empty(), "both transactions credit the fee vaults");
+        assert!(report.write_write.contains(&StateLocation::Account(BASE_FEE_RECIPIENT)));
+
+        // The fee vaults and the default beneficiary.
+        let fee_accounts =
+            [BASE_FEE_RECIPIENT, L1_FEE_RECIPIENT, OPERATOR_FEE_RECIPIENT, Address::ZERO];
+        let report = report.without_accounts(&fee_accounts);
+        assert!(report.is_empty(), "unexpected conflicts: {report:?}");
+    }
+
+    #[test]
+    fn test_shared_reads_do_not_conflict() {
+        let report = conflicts(&run(ALICE, READER), &run(ALICE, READER));
+        assert!(report.write_read.contains(&StateLocation::Account(ALICE)));
+        assert!(!report.write_read.contains(&StateLocation::Storage(READER, U256::ZERO)));
+    }
+}
@@ -14,10 +14,10 @@
 
 #[cfg(not(feature = "std"))]
 use alloc as std;
-use std::{rc::Rc, vec::Vec};
+use std::{collections::BTreeMap, rc::Rc, vec::Vec};
 
 use alloy_evm::Database;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, B256, U256};
 use core::cell::RefCell;
 use delegate::delegate;
 use op_revm::{DefaultOp, L1BlockInfo, OpContext, OpSpecId};
@@ -25,13 +25,18 @@ use revm::{
     context::{BlockEnv, CfgEnv, ContextSetters, ContextTr, LocalContext},
     context_interface::context::ContextError,
     database::EmptyDB,
-    Journal,
+    Journal, JournalEntry,
 };
+use tracing::warn;
 
+use super::call_graph::CallGraphRecorder;
 use crate::{
-    constants, is_system_originated, AdditionalLimit, BucketId, DynamicGasCost, EmptyExternalEnv,
-    EvmTxRuntimeLimits, ExternalEnvTypes, ExternalEnvs, MegaSpecId, TxRuntimeLimit,
-    VolatileDataAccess, VolatileDataAccessTracker, VolatileDataAccessType,
+    constants, is_system_originated, AdditionalLimit, BlockHashProvider, BucketId, CallFrame,
+    DbIoMeter, DbIoStats, DetainedGasRefund, DetentionExemptions, DynamicGasCost, EmptyExternalEnv,
+    EvmTxRuntimeLimits, ExternalEnvRegistry, ExternalEnvTypes, ExternalEnvs, JournalStats,
+    LimitUsage, MegaChainConfig, MegaSpecId, PrecompileResourceReporter, SimulationOptions,
+    TrackerPool, TxRuntimeLimit, VolatileDataAccess, VolatileDataAccessTracker,
+    VolatileDataAccessType,
 };
 
 /// `MegaETH` EVM context type. This struct wraps [`OpContext`] and implements the [`ContextTr`]
@@ -50,9 +55,16 @@ pub struct MegaContext<DB: Database, ExtEnvs: ExternalEnvTypes> {
     /// Whether to disable the post-transaction reward to beneficiary.
     pub(crate) disable_beneficiary: bool,
 
+    /// Beneficiary rewards accumulated instead of being credited per transaction, if deferral is
+    /// enabled. See [`MegaContext::defer_beneficiary_reward`].
+    pub(crate) deferred_beneficiary_reward: Option<U256>,
+
     /// Additional limits for the EVM.
     pub additional_limit: Rc<RefCell<AdditionalLimit>>,
 
+    /// Pool the additional-limit trackers are drawn from and returned to, if any.
+    pub(crate) tracker_pool: Option<TrackerPool>,
+
     /// Shared SALT environment handle.
     pub(crate) salt_env: Rc<ExtEnvs::SaltEnv>,
 
@@ -62,11 +74,85 @@ pub struct MegaContext<DB: Database, ExtEnvs: ExternalEnvTypes> {
     /// The oracle environment.
     pub oracle_env: Rc<RefCell<ExtEnvs::OracleEnv>>,
 
+    /// The external environments beyond SALT and oracle. See [`MegaContext::extra_env`].
+    pub(crate) extra_envs: ExternalEnvRegistry,
+
     /* Internal state variables */
     /// Tracker for volatile data access (block environment, beneficiary, oracle)
     /// and volatile data access disable (`MegaAccessControl` system contract).
     pub volatile_data_tracker: Rc<RefCell<VolatileDataAccessTracker>>,
 
+    /// Block hashes read by `BLOCKHASH` during the current transaction.
+    pub(crate) accessed_block_hashes: BTreeMap<u64, B256>,
+
+    /// Oracle contract storage slots read during the current transaction, with the first value
+    /// observed.
+    pub(crate) accessed_oracle_slots: BTreeMap<U256, U256>,
+
+    /// The journal entries of the last executed transaction, if journal export is enabled. See
+    /// [`MegaContext::with_journal_export`].
+    pub(crate) exported_journal: Option<Vec<JournalEntry>>,
+
+    /// The recorder of the call graph of the current (or last executed) transaction, if call graph
+    /// recording is enabled. See [`MegaContext::with_call_graph_recording`].
+    pub(crate) call_graph: Option<CallGraphRecorder>,
+
+    /// The journal checkpoint statistics of the current (or last executed) transaction, if
+    /// journal stats collection is enabled. See [`MegaContext::with_journal_stats`].
+    pub(crate) journal_stats: Option<JournalStats>,
+
+    /// The meter of the database, reset when each transaction starts, if database I/O metering
+    /// is enabled. See [`MegaContext::with_db_io_meter`].
+    pub(crate) db_io_meter: Option<DbIoMeter>,
+
+    /// The per-opcode wall time of the current (or last executed) transaction, if opcode
+    /// profiling is enabled. See `MegaContext::with_opcode_profiling`.
+    pub(crate) opcode_profile: Option<crate::OpcodeProfile>,
+
+    /// The refund of the gas left when the detained compute gas limit halted the current (or last
+    /// executed) transaction, if it did.
+    pub(crate) detained_gas_refund: Option<DetainedGasRefund>,
+
+    /// The additional limit usage of the current (or last executed) transaction, snapshotted when
+    /// the transaction ends so that reading it takes no borrow of the limit trackers.
+    pub(crate) tx_limit_usage: LimitUsage,
+
+    /// The volatile data accessed by the current (or last executed) transaction, snapshotted when
+    /// the transaction ends.
+    pub(crate) tx_volatile_data_accessed: VolatileDataAccess,
+
+    /// Reporter of the resources consumed by stateful precompile calls, if any. See
+    /// [`MegaContext::with_precompile_resource_reporter`].
+    pub(crate) precompile_resource_reporter: Option<Rc<dyn PrecompileResourceReporter>>,
+
+    /// Source of the block hashes served to `BLOCKHASH` ahead of the database, if any. See
+    /// [`MegaContext::with_block_hash_provider`].
+    pub(crate) block_hash_provider: Option<Rc<dyn BlockHashProvider>>,
+
+    /// Contracts whose volatile data accesses do not lower the compute gas limit. See
+    /// [`MegaContext::with_detention_exemptions`].
+    pub(crate) detention_exemptions: DetentionExemptions,
+
+    /// The [`DetentionExemptions::hash`] of `detention_exemptions`, computed once when the set is
+    /// configured.
+    detention_exemptions_hash: B256,
+
+    /// Whether oracle `getSlot` calls are served without running the oracle bytecode. See
+    /// [`MegaContext::with_oracle_fast_path`].
+    pub(crate) oracle_fast_path: bool,
+
+    /// Whether a transaction nonce above the caller's nonce is accepted. See
+    /// [`MegaContext::with_nonce_gap_tolerance`].
+    pub(crate) nonce_gap_tolerance: bool,
+
+    /// The caller's nonce before the current transaction closed a nonce gap, restored if the
+    /// transaction is rejected.
+    pub(crate) nonce_gap_origin: Option<u64>,
+
+    /// Options relaxing the validation of transactions for simulation. See
+    /// [`MegaContext::with_simulation_options`].
+    pub(crate) simulation_options: SimulationOptions,
+
     /// Set to `true` when this context is itself a sandbox execution.
     ///
     /// Suppresses sandbox interception (preventing recursive sandboxing) and signals other
@@ -161,7 +247,9 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> MegaContext<DB, ExtEnvs> {
         Self {
             spec,
             disable_beneficiary: false,
+            deferred_beneficiary_reward: None,
             additional_limit: Rc::new(RefCell::new(AdditionalLimit::new(spec, tx_limits))),
+            tracker_pool: None,
             salt_env: Rc::clone(&salt_env),
             dynamic_storage_gas_cost: Rc::new(RefCell::new(DynamicGasCost::new(
                 spec,
@@ -173,6 +261,25 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> MegaContext<DB, ExtEnvs> {
                 tx_limits.block_env_access_compute_gas_limit,
                 tx_limits.oracle_access_compute_gas_limit,
             ))),
+            accessed_block_hashes: BTreeMap::new(),
+            accessed_oracle_slots: BTreeMap::new(),
+            detained_gas_refund: None,
+            tx_limit_usage: LimitUsage::default(),
+            tx_volatile_data_accessed: VolatileDataAccess::empty(),
+            exported_journal: None,
+            extra_envs: ExternalEnvRegistry::default(),
+            call_graph: None,
+            journal_stats: None,
+            db_io_meter: None,
+            opcode_profile: None,
+            precompile_resource_reporter: None,
+            block_hash_provider: None,
+            detention_exemptions: DetentionExemptions::default(),
+            detention_exemptions_hash: DetentionExemptions::EMPTY_HASH,
+            oracle_fast_path: false,
+            nonce_gap_tolerance: false,
+            nonce_gap_origin: None,
+            simulation_options: SimulationOptions::default(),
             inside_sandbox: Rc::new(RefCell::new(false)),
             system_address: crate::MEGA_SYSTEM_ADDRESS,
             inner,
@@ -224,7 +331,9 @@ impl<DB: Database, ExtEnvTypes: ExternalEnvTypes> MegaContext<DB, ExtEnvTypes> {
         Self {
             spec,
             disable_beneficiary: false,
+            deferred_beneficiary_reward: None,
             additional_limit: Rc::new(RefCell::new(AdditionalLimit::new(spec, tx_limits))),
+            tracker_pool: None,
             salt_env: Rc::clone(&salt_env),
             dynamic_storage_gas_cost: Rc::new(RefCell::new(DynamicGasCost::new(
                 spec,
@@ -236,6 +345,25 @@ impl<DB: Database, ExtEnvTypes: ExternalEnvTypes> MegaContext<DB, ExtEnvTypes> {
                 tx_limits.block_env_access_compute_gas_limit,
                 tx_limits.oracle_access_compute_gas_limit,
             ))),
+            accessed_block_hashes: BTreeMap::new(),
+            accessed_oracle_slots: BTreeMap::new(),
+            detained_gas_refund: None,
+            tx_limit_usage: LimitUsage::default(),
+            tx_volatile_data_accessed: VolatileDataAccess::empty(),
+            exported_journal: None,
+            extra_envs: ExternalEnvRegistry::default(),
+            call_graph: None,
+            journal_stats: None,
+            db_io_meter: None,
+            opcode_profile: None,
+            precompile_resource_reporter: None,
+            block_hash_provider: None,
+            detention_exemptions: DetentionExemptions::default(),
+            detention_exemptions_hash: DetentionExemptions::EMPTY_HASH,
+            oracle_fast_path: false,
+            nonce_gap_tolerance: false,
+            nonce_gap_origin: None,
+            simulation_options: SimulationOptions::default(),
             inside_sandbox: Rc::new(RefCell::new(false)),
             system_address: crate::MEGA_SYSTEM_ADDRESS,
             inner,
@@ -259,11 +387,32 @@ impl<DB: Database, ExtEnvTypes: ExternalEnvTypes> MegaContext<DB, ExtEnvTypes> {
             inner: self.inner.with_db(db),
             spec: self.spec,
             disable_beneficiary: self.disable_beneficiary,
+            deferred_beneficiary_reward: self.deferred_beneficiary_reward,
             additional_limit: self.additional_limit,
+            tracker_pool: self.tracker_pool,
             salt_env: self.salt_env,
             dynamic_storage_gas_cost: self.dynamic_storage_gas_cost,
             oracle_env: self.oracle_env,
             volatile_data_tracker: self.volatile_data_tracker,
+            accessed_block_hashes: self.accessed_block_hashes,
+            accessed_oracle_slots: self.accessed_oracle_slots,
+            detained_gas_refund: self.detained_gas_refund,
+            tx_limit_usage: self.tx_limit_usage,
+            tx_volatile_data_accessed: self.tx_volatile_data_accessed,
+            exported_journal: self.exported_journal,
+            extra_envs: self.extra_envs,
+            call_graph: self.call_graph,
+            journal_stats: self.journal_stats,
+            db_io_meter: self.db_io_meter,
+            opcode_profile: self.opcode_profile,
+            precompile_resource_reporter: self.precompile_resource_reporter,
+            block_hash_provider: self.block_hash_provider,
+            detention_exemptions: self.detention_exemptions,
+            detention_exemptions_hash: self.detention_exemptions_hash,
+            oracle_fast_path: self.oracle_fast_path,
+            nonce_gap_tolerance: self.nonce_gap_tolerance,
+            nonce_gap_origin: self.nonce_gap_origin,
+            simulation_options: self.simulation_options,
             inside_sandbox: self.inside_sandbox,
             system_address: self.system_address,
         }
@@ -359,7 +508,9 @@ impl<DB: Database, ExtEnvTypes: ExternalEnvTypes> MegaContext<DB, ExtEnvTypes> {
             inner: self.inner,
             spec,
             disable_beneficiary: self.disable_beneficiary,
+            deferred_beneficiary_reward: self.deferred_beneficiary_reward,
             additional_limit: self.additional_limit,
+            tracker_pool: self.tracker_pool,
             salt_env: Rc::clone(&salt_env),
             dynamic_storage_gas_cost: Rc::new(RefCell::new(DynamicGasCost::new(
                 spec,
@@ -368,6 +519,25 @@ impl<DB: Database, ExtEnvTypes: ExternalEnvTypes> MegaContext<DB, ExtEnvTypes> {
             ))),
             oracle_env: Rc::new(RefCell::new(external_envs.oracle_env)),
             volatile_data_tracker: self.volatile_data_tracker,
+            accessed_block_hashes: self.accessed_block_hashes,
+            accessed_oracle_slots: self.accessed_oracle_slots,
+            detained_gas_refund: self.detained_gas_refund,
+            tx_limit_usage: self.tx_limit_usage,
+            tx_volatile_data_accessed: self.tx_volatile_data_accessed,
+            exported_journal: self.exported_journal,
+            extra_envs: self.extra_envs,
+            call_graph: self.call_graph,
+            journal_stats: self.journal_stats,
+            db_io_meter: self.db_io_meter,
+            opcode_profile: self.opcode_profile,
+            precompile_resource_reporter: self.precompile_resource_reporter,
+            block_hash_provider: self.block_hash_provider,
+            detention_exemptions: self.detention_exemptions,
+            detention_exemptions_hash: self.detention_exemptions_hash,
+            oracle_fast_path: self.oracle_fast_path,
+            nonce_gap_tolerance: self.nonce_gap_tolerance,
+            nonce_gap_origin: self.nonce_gap_origin,
+            simulation_options: self.simulation_options,
             inside_sandbox: self.inside_sandbox,
             system_address: self.system_address,
         }
@@ -390,9 +560,41 @@ impl<DB: Database, ExtEnvTypes: ExternalEnvTypes> MegaContext<DB, ExtEnvTypes> {
         self
     }
 
+    /// Applies the chain-level parameters of `config`: the operator fee (if set) and the system
+    /// address.
+    ///
+    /// The chain id stays the one of the [`CfgEnv`], i.e. of the `EvmEnv` the context is built
+    /// from, so a configuration cannot retarget the context to another chain. A configuration for
+    /// another chain id is logged; set the chain id through the [`CfgEnv`] instead.
+    pub fn with_chain_config(mut self, config: MegaChainConfig) -> Self {
+        if config.chain_id != self.inner.cfg.chain_id {
+            warn!(
+                config_chain_id = config.chain_id,
+                chain_id = self.inner.cfg.chain_id,
+                "chain config is for another chain; keeping the chain id of the CfgEnv",
+            );
+        }
+        if let Some(scalar) = config.operator_fee_scalar {
+            self.inner.chain.operator_fee_scalar = Some(scalar);
+        }
+        if let Some(constant) = config.operator_fee_constant {
+            self.inner.chain.operator_fee_constant = Some(constant);
+        }
+        self.system_address = config.system_address;
+        self
+    }
+
     /// Sets the transaction limits for the EVM.
+    ///
+    /// With a [`TrackerPool`] set, the current trackers are re-initialized in place so their
+    /// allocations are kept.
     pub fn with_tx_runtime_limits(mut self, tx_limits: EvmTxRuntimeLimits) -> Self {
-        self.additional_limit = Rc::new(RefCell::new(AdditionalLimit::new(self.spec, tx_limits)));
+        if self.tracker_pool.is_some() {
+            self.additional_limit.borrow_mut().reinit(self.spec, tx_limits);
+        } else {
+            self.additional_limit =
+                Rc::new(RefCell::new(AdditionalLimit::new(self.spec, tx_limits)));
+        }
         self.volatile_data_tracker = Rc::new(RefCell::new(VolatileDataAccessTracker::new(
             tx_limits.block_env_access_compute_gas_limit,
             tx_limits.oracle_access_compute_gas_limit,
@@ -401,6 +603,39 @@ impl<DB: Database, ExtEnvTypes: ExternalEnvTypes> MegaContext<DB, ExtEnvTypes> {
     }
 }
 
+/* Tracker Pooling */
+impl<DB: Database, ExtEnvs: ExternalEnvTypes> MegaContext<DB, ExtEnvs> {
+    /// Sets the [`TrackerPool`] for the EVM. See [`set_tracker_pool`](Self::set_tracker_pool).
+    pub fn with_tracker_pool(mut self, pool: TrackerPool) -> Self {
+        self.set_tracker_pool(pool);
+        self
+    }
+
+    /// Swaps the additional-limit trackers for a recycled instance from `pool`, keeping the
+    /// current limits.
+    ///
+    /// The trackers go back to the pool on [`release_trackers`](Self::release_trackers), so
+    /// contexts created later can reuse their frame stack allocations.
+    pub fn set_tracker_pool(&mut self, pool: TrackerPool) {
+        self.release_trackers();
+        let limits = self.additional_limit.borrow().limits;
+        *self.additional_limit.borrow_mut() = pool.acquire(self.spec, limits);
+        self.tracker_pool = Some(pool);
+    }
+
+    /// Returns the additional-limit trackers to the [`TrackerPool`], leaving fresh trackers with
+    /// the same limits in their place. Does nothing if no pool is set.
+    ///
+    /// Call this once the context has finished executing, e.g. at the end of a block.
+    pub fn release_trackers(&mut self) {
+        if let Some(pool) = &self.tracker_pool {
+            let limits = self.additional_limit.borrow().limits;
+            let fresh = AdditionalLimit::new(self.spec, limits);
+            pool.release(core::mem::replace(&mut *self.additional_limit.borrow_mut(), fresh));
+        }
+    }
+}
+
 /* Getters */
 impl<DB: Database, ExtEnvs: ExternalEnvTypes> MegaContext<DB, ExtEnvs> {
     /// Gets the `MegaETH` specification ID.
@@ -414,6 +649,258 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> MegaContext<DB, ExtEnvs> {
         self.spec
     }
 
+    /// Gets the block hashes read by `BLOCKHASH` during the current (or last executed)
+    /// transaction, keyed by block number.
+    pub fn accessed_block_hashes(&self) -> &BTreeMap<u64, B256> {
+        &self.accessed_block_hashes
+    }
+
+    /// Gets the oracle contract storage slots read during the current (or last executed)
+    /// transaction, with the first value observed for each slot.
+    ///
+    /// If the oracle data changes before the block is sealed, the transaction only needs to be
+    /// re-executed if one of these slots changed.
+    pub fn accessed_oracle_slots(&self) -> &BTreeMap<U256, U256> {
+        &self.accessed_oracle_slots
+    }
+
+    /// Gets the refund of the gas left when the detained compute gas limit halted the current (or
+    /// last executed) transaction, if it did.
+    pub fn detained_gas_refund(&self) -> Option<DetainedGasRefund> {
+        self.detained_gas_refund
+    }
+
+    /// Gets the additional limit usage of the last executed transaction, as snapshotted when it
+    /// ended.
+    ///
+    /// Unlike [`AdditionalLimit::get_usage`], this takes no borrow of the limit trackers, so it
+    /// cannot conflict with a borrow held elsewhere, e.g. by an inspector.
+    pub fn tx_limit_usage(&self) -> LimitUsage {
+        self.tx_limit_usage
+    }
+
+    /// Enables or disables the export of the journal of each executed transaction.
+    ///
+    /// When enabled, the ordered [`JournalEntry`] stream a transaction leaves in the journal
+    /// (entries of reverted frames are already dropped) is kept after the transaction and reported
+    /// in [`MegaTransactionOutcome::journal`](crate::MegaTransactionOutcome::journal), so
+    /// indexers can attribute every state change.
+    pub fn with_journal_export(mut self, enabled: bool) -> Self {
+        self.exported_journal = enabled.then(Vec::new);
+        self
+    }
+
+    /// Enables or disables the recording of the call graph of each executed transaction.
+    ///
+    /// When enabled, every frame the transaction opens is recorded as a [`CallFrame`] and the
+    /// tree is reported in
+    /// [`MegaTransactionOutcome::call_graph`](crate::MegaTransactionOutcome::call_graph), for
+    /// indexers that need the internal transactions without running a tracing inspector.
+    pub fn with_call_graph_recording(mut self, enabled: bool) -> Self {
+        self.call_graph = enabled.then(CallGraphRecorder::default);
+        self
+    }
+
+    /// Enables or disables the collection of journal checkpoint statistics of each executed
+    /// transaction.
+    ///
+    /// When enabled, the checkpoints taken and reverted by the transaction are counted and reported
+    /// in [`MegaTransactionOutcome::journal_stats`](crate::MegaTransactionOutcome::journal_stats),
+    /// to diagnose pathological re-entrancy and costly deep reverts.
+    pub fn with_journal_stats(mut self, enabled: bool) -> Self {
+        self.journal_stats = enabled.then(JournalStats::default);
+        self
+    }
+
+    /// Sets the meter of a [`MeteredDb`](crate::MeteredDb) wrapping the database, or disables
+    /// database I/O metering with `None`.
+    ///
+    /// When set, the meter is reset when each transaction starts and the lookups the transaction
+    /// caused are reported in
+    /// [`MegaTransactionOutcome::db_io`](crate::MegaTransactionOutcome::db_io), so operators
+    /// can correlate slow transactions with their state access patterns.
+    pub fn with_db_io_meter(mut self, meter: Option<DbIoMeter>) -> Self {
+        self.db_io_meter = meter;
+        self
+    }
+
+    /// Enables or disables the profiling of the wall time spent per opcode by each executed
+    /// transaction.
+    ///
+    /// When enabled, every instruction is timed and the timings are reported in
+    /// [`MegaTransactionOutcome::opcode_profile`](crate::MegaTransactionOutcome::opcode_profile),
+    /// to check that compute gas pricing tracks the real CPU cost of each opcode. Timing every
+    /// instruction slows execution down, so this is meant for profiling runs, not production.
+    #[cfg(feature = "opcode-profiling")]
+    pub fn with_opcode_profiling(mut self, enabled: bool) -> Self {
+        self.opcode_profile = enabled.then(crate::OpcodeProfile::default);
+        self
+    }
+
+    /// Enables or disables the oracle fast path.
+    ///
+    /// When enabled, a `CALL` or `STATICCALL` of the oracle contract's `getSlot(uint256)` is served
+    /// from the [`OracleEnv`](crate::OracleEnv) without running the oracle bytecode, spending the
+    /// gas the bytecode would and returning the same encoding. Calls whose outcome the fast path
+    /// cannot reproduce exactly (a limit that would halt, disabled volatile data access, an
+    /// unknown oracle code) still run the bytecode. Inspectors do not see the steps of a served
+    /// call.
+    pub fn with_oracle_fast_path(mut self, enabled: bool) -> Self {
+        self.oracle_fast_path = enabled;
+        self
+    }
+
+    /// Enables or disables nonce gap tolerance.
+    ///
+    /// When enabled, a transaction whose nonce is above the caller's nonce is accepted as if the
+    /// transactions in between had already run: the caller's nonce is raised to the transaction
+    /// nonce once the transaction passes validation, so the nonce bump and the address of a
+    /// created contract follow the transaction nonce. A rejected transaction leaves it unchanged. A
+    /// nonce below the caller's nonce is still rejected. Deposit and system transactions are
+    /// unaffected.
+    ///
+    /// Meant for simulation only, e.g. simulating a bundle of future transactions without forging
+    /// the state in between. Never enable it when executing blocks.
+    pub fn with_nonce_gap_tolerance(mut self, enabled: bool) -> Self {
+        self.nonce_gap_tolerance = enabled;
+        self
+    }
+
+    /// Sets the [`SimulationOptions`] relaxing the pre-execution validation of transactions, e.g.
+    /// to simulate a sponsored transaction whose fees are paid by another account.
+    ///
+    /// Meant for simulation only. Never set them when executing blocks.
+    pub fn with_simulation_options(mut self, options: SimulationOptions) -> Self {
+        self.simulation_options = options;
+        self
+    }
+
+    /// Sets the external environments beyond SALT and oracle, replacing the current ones.
+    pub fn with_extra_envs(mut self, extra_envs: ExternalEnvRegistry) -> Self {
+        self.extra_envs = extra_envs;
+        self
+    }
+
+    /// Registers an external environment beyond SALT and oracle, replacing the one of the same
+    /// type, if any.
+    pub fn with_extra_env<E: core::any::Any>(mut self, env: E) -> Self {
+        self.extra_envs.insert(env);
+        self
+    }
+
+    /// Sets the reporter of the resources consumed by stateful precompile calls.
+    ///
+    /// The reported usage is charged to the data-size, KV-update and state-growth limits of the
+    /// transaction, as if the precompile had written state through `SSTORE`.
+    pub fn with_precompile_resource_reporter(
+        mut self,
+        reporter: impl PrecompileResourceReporter + 'static,
+    ) -> Self {
+        self.precompile_resource_reporter = Some(Rc::new(reporter));
+        self
+    }
+
+    /// Sets the source of the block hashes served to `BLOCKHASH`.
+    ///
+    /// Hashes the provider does not know are read from the database. Keep a clone of the `Rc` to
+    /// update the provider between blocks, e.g. a shared `RefCell<BlockHashRingBuffer>`.
+    pub fn with_block_hash_provider(mut self, provider: Rc<dyn BlockHashProvider>) -> Self {
+        self.block_hash_provider = Some(provider);
+        self
+    }
+
+    /// Sets the contracts whose volatile data accesses do not lower the compute gas limit.
+    ///
+    /// The set changes execution, so replays must use the same set; its hash, see
+    /// [`MegaContext::detention_exemptions_hash`], is reported with the block to check it.
+    pub fn with_detention_exemptions(mut self, exemptions: DetentionExemptions) -> Self {
+        self.detention_exemptions_hash = exemptions.hash();
+        self.detention_exemptions = exemptions;
+        self
+    }
+
+    /// Gets the contracts exempt from compute gas detention.
+    pub fn detention_exemptions(&self) -> &DetentionExemptions {
+        &self.detention_exemptions
+    }
+
+    /// Gets the [`DetentionExemptions::hash`] of the contracts exempt from compute gas detention.
+    pub fn detention_exemptions_hash(&self) -> B256 {
+        self.detention_exemptions_hash
+    }
+
+    /// Gets the journal entries of the last executed transaction, if journal export is enabled.
+    pub fn exported_journal(&self) -> Option<&[JournalEntry]> {
+        self.exported_journal.as_deref()
+    }
+
+    /// Takes the journal entries of the last executed transaction, keeping journal export enabled.
+    pub(crate) fn take_exported_journal(&mut self) -> Option<Vec<JournalEntry>> {
+        self.exported_journal.as_mut().map(core::mem::take)
+    }
+
+    /// Gets the external environment of type `E` beyond SALT and oracle, if registered, e.g. for a
+    /// precompile or a system contract interceptor that needs a price feed.
+    pub fn extra_env<E: core::any::Any>(&self) -> Option<&E> {
+        self.extra_envs.get()
+    }
+
+    /// Gets the registry of the external environments beyond SALT and oracle.
+    pub fn extra_envs(&self) -> &ExternalEnvRegistry {
+        &self.extra_envs
+    }
+
+    /// Gets the call graph of the last executed transaction, if call graph recording is enabled
+    /// and the transaction ran.
+    pub fn call_graph(&self) -> Option<&CallFrame> {
+        self.call_graph.as_ref().and_then(CallGraphRecorder::root)
+    }
+
+    /// Takes the call graph of the last executed transaction, keeping call graph recording
+    /// enabled.
+    pub(crate) fn take_call_graph(&mut self) -> Option<CallFrame> {
+        self.call_graph.as_mut().and_then(CallGraphRecorder::take)
+    }
+
+    /// Gets the journal checkpoint statistics of the current (or last executed) transaction, if
+    /// journal stats collection is enabled.
+    pub fn journal_stats(&self) -> Option<&JournalStats> {
+        self.journal_stats.as_ref()
+    }
+
+    /// Takes the journal checkpoint statistics of the last executed transaction, keeping the
+    /// collection enabled.
+    pub(crate) fn take_journal_stats(&mut self) -> Option<JournalStats> {
+        self.journal_stats.as_mut().map(core::mem::take)
+    }
+
+    /// Gets the database I/O of the current (or last executed) transaction, if database I/O
+    /// metering is enabled.
+    pub fn db_io_stats(&self) -> Option<DbIoStats> {
+        self.db_io_meter.as_ref().map(DbIoMeter::stats)
+    }
+
+    /// Returns whether `frame_run` collects journal stats or an opcode profile, which the op-revm
+    /// loop of the pre-`MINI_REX` specs does not.
+    pub(crate) fn has_frame_run_instrumentation(&self) -> bool {
+        if self.opcode_profile.is_some() {
+            return true;
+        }
+        self.journal_stats.is_some()
+    }
+
+    /// Gets the per-opcode wall time of the current (or last executed) transaction, if opcode
+    /// profiling is enabled. Always `None` without the `opcode-profiling` feature.
+    pub fn opcode_profile(&self) -> Option<&crate::OpcodeProfile> {
+        self.opcode_profile.as_ref()
+    }
+
+    /// Takes the per-opcode wall time of the last executed transaction, keeping the profiling
+    /// enabled.
+    pub(crate) fn take_opcode_profile(&mut self) -> Option<crate::OpcodeProfile> {
+        self.opcode_profile.as_mut().map(core::mem::take)
+    }
+
     /// Gets the system address for the current block.
     ///
     /// Pre-REX5: always `MEGA_SYSTEM_ADDRESS`.
@@ -537,6 +1024,34 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> MegaContext<DB, ExtEnvs> {
         self.disable_beneficiary = true;
     }
 
+    /// Defers the beneficiary reward to the end of the block.
+    ///
+    /// Crediting the block beneficiary after every transaction makes every transaction write the
+    /// same account, which serializes parallel execution. With deferral, each transaction's
+    /// beneficiary reward is added to a side ledger instead, and
+    /// [`MegaBlockExecutor`](crate::MegaBlockExecutor) credits the total once in its
+    /// post-execution changes. The fee vaults are still credited per transaction. A transaction
+    /// that accesses the beneficiary while rewards are pending would see a stale account, so the
+    /// block executor credits the pending rewards and executes it again. The final block state is
+    /// the same as without deferral.
+    ///
+    /// Has no effect if the beneficiary is one of the fee vaults, or if the beneficiary reward is
+    /// disabled.
+    pub fn defer_beneficiary_reward(&mut self) {
+        self.deferred_beneficiary_reward.get_or_insert(U256::ZERO);
+    }
+
+    /// Returns the beneficiary reward deferred so far, or `None` if deferral is not enabled.
+    pub fn deferred_beneficiary_reward(&self) -> Option<U256> {
+        self.deferred_beneficiary_reward
+    }
+
+    /// Takes the beneficiary reward deferred so far, leaving deferral enabled with an empty
+    /// ledger. Returns zero if deferral is not enabled.
+    pub fn take_deferred_beneficiary_reward(&mut self) -> U256 {
+        self.deferred_beneficiary_reward.as_mut().map(core::mem::take).unwrap_or_default()
+    }
+
     /// Check if address is beneficiary and mark access if so.
     /// Returns true if beneficiary was accessed.
     pub(crate) fn check_and_mark_beneficiary_balance_access(&self, address: &Address) -> bool {
@@ -593,6 +1108,24 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> MegaContext<DB, ExtEnvs> {
     /// DB-dependent pre-frame usage may still be recorded later during pre-execution.
     pub(crate) fn on_new_tx(&mut self) {
         self.reset_volatile_data_access();
+        self.accessed_block_hashes.clear();
+        self.accessed_oracle_slots.clear();
+        self.detained_gas_refund = None;
+        self.nonce_gap_origin = None;
+        self.tx_limit_usage = LimitUsage::default();
+        self.tx_volatile_data_accessed = VolatileDataAccess::empty();
+        if let Some(call_graph) = self.call_graph.as_mut() {
+            call_graph.clear();
+        }
+        if let Some(journal_stats) = self.journal_stats.as_mut() {
+            *journal_stats = JournalStats::default();
+        }
+        if let Some(meter) = self.db_io_meter.as_ref() {
+            meter.reset();
+        }
+        if let Some(opcode_profile) = self.opcode_profile.as_mut() {
+            *opcode_profile = crate::OpcodeProfile::default();
+        }
 
         // The additional-limit lifecycle (reset → intrinsic accounting) exists only for MINI_REX+.
         if self.spec.is_enabled(MegaSpecId::MINI_REX) {
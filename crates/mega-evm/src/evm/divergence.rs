@@ -0,0 +1,363 @@
+//! Field-by-field comparison of two executions, e.g. of a sequencer and a replica or of two
+//! clients, for the replay tool and differential tests.
+
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::{collections::BTreeSet, format, string::String, vec::Vec};
+
+use alloy_consensus::TxReceipt;
+use alloy_evm::block::BlockExecutionResult;
+use alloy_primitives::{Address, Log, U256};
+use revm::{context::result::ExecutionResult, state::EvmState};
+use serde::{Deserialize, Serialize};
+
+use crate::{MegaHaltReason, MegaTransactionOutcome};
+
+/// A field that differs between the two sides of a [`DivergenceReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Divergence {
+    /// The path of the field, e.g. `logs[0].data` or `state[0x..].storage[0x1]`.
+    pub field: String,
+    /// The value on the expected side.
+    pub expected: String,
+    /// The value on the actual side.
+    pub actual: String,
+}
+
+/// The fields that differ between an expected and an actual execution.
+///
+/// The report is built by chaining comparisons, which only record the fields that differ:
+///
+/// ```rust,ignore
+/// let report = DivergenceReport::new("sequencer", "replica")
+///     .compare_outcomes(&sequencer_outcome, &replica_outcome);
+/// if !report.is_empty() {
+///     eprintln!("{report}");
+/// }
+/// ```
+///
+/// Its [`Display`](core::fmt::Display) implementation renders one line per divergence, and it
+/// serializes to JSON with serde. Values are rendered with their `Debug` representation.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DivergenceReport {
+    /// The name of the expected side, e.g. `sequencer` or `on-chain`.
+    pub expected_label: String,
+    /// The name of the actual side, e.g. `replica` or `replay`.
+    pub actual_label: String,
+    /// The fields that differ, in comparison order.
+    pub divergences: Vec<Divergence>,
+}
+
+impl DivergenceReport {
+    /// Creates an empty report comparing `expected_label` against `actual_label`.
+    pub fn new(expected_label: impl Into<String>, actual_label: impl Into<String>) -> Self {
+        Self {
+            expected_label: expected_label.into(),
+            actual_label: actual_label.into(),
+            divergences: Vec::new(),
+        }
+    }
+
+    /// Returns whether no field differs.
+    pub fn is_empty(&self) -> bool {
+        self.divergences.is_empty()
+    }
+
+    /// Returns the number of fields that differ.
+    pub fn len(&self) -> usize {
+        self.divergences.len()
+    }
+
+    /// Records `field` if `expected` and `actual` differ.
+    pub fn compare<T: PartialEq + core::fmt::Debug>(
+        mut self,
+        field: impl Into<String>,
+        expected: T,
+        actual: T,
+    ) -> Self {
+        if expected != actual {
+            self.divergences.push(Divergence {
+                field: field.into(),
+                expected: format!("{expected:?}"),
+                actual: format!("{actual:?}"),
+            });
+        }
+        self
+    }
+
+    /// Compares two transaction outcomes: the status, halt reason and output, the gas used, the
+    /// logs, the state diff and the resource usage.
+    ///
+    /// The state diff covers the touched accounts and their changed storage slots, in address and
+    /// key order. Diagnostic data (accessed block hashes, journal, call graph, ...) is not
+    /// compared.
+    pub fn compare_outcomes(
+        self,
+        expected: &MegaTransactionOutcome,
+        actual: &MegaTransactionOutcome,
+    ) -> Self {
+        self.compare_results("", &expected.result, &actual.result)
+            .compare_state("state", &expected.state, &actual.state)
+            .compare("data_size", expected.data_size, actual.data_size)
+            .compare("kv_updates", expected.kv_updates, actual.kv_updates)
+            .compare("compute_gas_used", expected.compute_gas_used, actual.compute_gas_used)
+            .compare("state_growth_used", expected.state_growth_used, actual.state_growth_used)
+    }
+
+    /// Compares two block execution results: the gas used and, receipt by receipt, the status,
+    /// the cumulative gas used and the logs.
+    pub fn compare_block_results<R: TxReceipt<Log = Log>>(
+        mut self,
+        expected: &BlockExecutionResult<R>,
+        actual: &BlockExecutionResult<R>,
+    ) -> Self {
+        self = self.compare("gas_used", expected.gas_used, actual.gas_used).compare(
+            "receipts.len",
+            expected.receipts.len(),
+            actual.receipts.len(),
+        );
+        for (index, (expected, actual)) in
+            expected.receipts.iter().zip(actual.receipts.iter()).enumerate()
+        {
+            let prefix = format!("receipts[{index}]");
+            self = self
+                .compare(format!("{prefix}.status"), expected.status(), actual.status())
+                .compare(
+                    format!("{prefix}.cumulative_gas_used"),
+                    expected.cumulative_gas_used(),
+                    actual.cumulative_gas_used(),
+                )
+                .compare_logs(&format!("{prefix}.logs"), expected.logs(), actual.logs());
+        }
+        self
+    }
+
+    fn compare_results(
+        self,
+        prefix: &str,
+        expected: &ExecutionResult<MegaHaltReason>,
+        actual: &ExecutionResult<MegaHaltReason>,
+    ) -> Self {
+        let halt_reason = |result: &ExecutionResult<MegaHaltReason>| match result {
+            ExecutionResult::Halt { reason, .. } => Some(reason.clone()),
+            _ => None,
+        };
+        self.compare(format!("{prefix}status"), status(expected), status(actual))
+            .compare(format!("{prefix}halt_reason"), halt_reason(expected), halt_reason(actual))
+            .compare(format!("{prefix}output"), expected.output(), actual.output())
+            .compare(format!("{prefix}gas_used"), expected.gas_used(), actual.gas_used())
+            .compare_logs(&format!("{prefix}logs"), expected.logs(), actual.logs())
+    }
+
+    fn compare_logs(mut self, prefix: &str, expected: &[Log], actual: &[Log]) -> Self {
+        self = self.compare(format!("{prefix}.len"), expected.len(), actual.len());
+        for (index, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+            self = self
+                .compare(format!("{prefix}[{index}].address"), expected.address, actual.address)
+                .compare(
+                    format!("{prefix}[{index}].topics"),
+                    expected.data.topics(),
+                    actual.data.topics(),
+                )
+                .compare(format!("{prefix}[{index}].data"), &expected.data.data, &actual.data.data);
+        }
+        self
+    }
+
+    fn compare_state(mut self, prefix: &str, expected: &EvmState, actual: &EvmState) -> Self {
+        let touched = |state: &EvmState| -> BTreeSet<Address> {
+            state.iter().filter(|(_, account)| account.is_touched()).map(|(a, _)| *a).collect()
+        };
+        let addresses: BTreeSet<_> = touched(expected).union(&touched(actual)).copied().collect();
+        for address in addresses {
+            let prefix = format!("{prefix}[{address}]");
+            let (Some(expected), Some(actual)) = (
+                expected.get(&address).filter(|account| account.is_touched()),
+                actual.get(&address).filter(|account| account.is_touched()),
+            ) else {
+                let changed =
+                    |state: &EvmState| state.get(&address).is_some_and(|a| a.is_touched());
+                self = self.compare(prefix, changed(expected), changed(actual));
+                continue;
+            };
+            self = self
+                .compare(format!("{prefix}.balance"), expected.info.balance, actual.info.balance)
+                .compare(format!("{prefix}.nonce"), expected.info.nonce, actual.info.nonce)
+                .compare(
+                    format!("{prefix}.code_hash"),
+                    expected.info.code_hash,
+                    actual.info.code_hash,
+                )
+                .compare(
+                    format!("{prefix}.selfdestructed"),
+                    expected.is_selfdestructed(),
+                    actual.is_selfdestructed(),
+                );
+            let changed_slot = |account: &revm::state::Account, key: &U256| {
+                account
+                    .storage
+                    .get(key)
+                    .filter(|slot| slot.is_changed())
+                    .map(|slot| slot.present_value)
+            };
+            let keys: BTreeSet<_> = expected
+                .storage
+                .iter()
+                .chain(actual.storage.iter())
+                .filter(|(_, slot)| slot.is_changed())
+                .map(|(key, _)| *key)
+                .collect();
+            for key in keys {
+                self = self.compare(
+                    format!("{prefix}.storage[{key:#x}]"),
+                    changed_slot(expected, &key),
+                    changed_slot(actual, &key),
+                );
+            }
+        }
+        self
+    }
+}
+
+/// Returns the status of `result`, as reported by receipts and tracers.
+fn status(result: &ExecutionResult<MegaHaltReason>) -> &'static str {
+    match result {
+        ExecutionResult::Success { .. } => "success",
+        ExecutionResult::Revert { .. } => "revert",
+        ExecutionResult::Halt { .. } => "halt",
+    }
+}
+
+impl core::fmt::Display for DivergenceReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.is_empty() {
+            return write!(
+                f,
+                "no divergence between {} and {}",
+                self.expected_label, self.actual_label
+            );
+        }
+        write!(
+            f,
+            "{} divergence(s) between {} and {}:",
+            self.len(),
+            self.expected_label,
+            self.actual_label
+        )?;
+        for divergence in &self.divergences {
+            write!(
+                f,
+                "\n  {}: {} {}, {} {}",
+                divergence.field,
+                self.expected_label,
+                divergence.expected,
+                self.actual_label,
+                divergence.actual
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Eip658Value, Receipt};
+    use alloy_primitives::{address, Bytes, LogData, B256};
+    use revm::{
+        context::result::{Output, SuccessReason},
+        state::{Account, AccountInfo, EvmStorageSlot},
+    };
+
+    const CONTRACT: Address = address!("0000000000000000000000000000000000100001");
+
+    fn log(data: u8) -> Log {
+        Log {
+            address: CONTRACT,
+            data: LogData::new_unchecked(vec![B256::ZERO], Bytes::from(vec![data])),
+        }
+    }
+
+    fn outcome(gas_used: u64, logs: Vec<Log>, slot_value: u64) -> MegaTransactionOutcome {
+        let mut account = Account::default().with_info(AccountInfo::default());
+        account.mark_touch();
+        account.storage.insert(
+            U256::from(1),
+            EvmStorageSlot::new_changed(U256::ZERO, U256::from(slot_value), 0),
+        );
+        MegaTransactionOutcome {
+            result: ExecutionResult::Success {
+                reason: SuccessReason::Stop,
+                gas_used,
+                gas_refunded: 0,
+                logs,
+                output: Output::Call(Bytes::new()),
+            },
+            state: EvmState::from_iter([(CONTRACT, account)]),
+            data_size: 100,
+            da_data_size: 100,
+            kv_updates: 1,
+            compute_gas_used: gas_used,
+            state_growth_used: 1,
+            accessed_block_hashes: Default::default(),
+            accessed_oracle_slots: Default::default(),
+            journal: None,
+            volatile_data_accessed: Default::default(),
+            detained_gas_refund: None,
+            call_graph: None,
+            journal_stats: None,
+            db_io: None,
+            opcode_profile: None,
+        }
+    }
+
+    #[test]
+    fn test_identical_outcomes_do_not_diverge() {
+        let report = DivergenceReport::new("sequencer", "replica")
+            .compare_outcomes(&outcome(21_000, vec![log(1)], 7), &outcome(21_000, vec![log(1)], 7));
+        assert!(report.is_empty(), "{report}");
+        assert_eq!(report.to_string(), "no divergence between sequencer and replica");
+    }
+
+    #[test]
+    fn test_outcome_divergences_are_reported_per_field() {
+        let report = DivergenceReport::new("sequencer", "replica")
+            .compare_outcomes(&outcome(21_000, vec![log(1)], 7), &outcome(21_100, vec![log(2)], 8));
+        let fields: Vec<_> = report.divergences.iter().map(|d| d.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            [
+                "gas_used",
+                "logs[0].data",
+                "state[0x0000000000000000000000000000000000100001].storage[0x1]",
+                "compute_gas_used",
+            ]
+        );
+        assert!(report.to_string().starts_with("4 divergence(s) between sequencer and replica:"));
+        assert!(report.to_string().contains("\n  gas_used: sequencer 21000, replica 21100"));
+    }
+
+    #[test]
+    fn test_block_result_divergences_are_reported_per_receipt() {
+        let receipt = |status: bool, cumulative_gas_used: u64, logs: Vec<Log>| Receipt {
+            status: Eip658Value::Eip658(status),
+            cumulative_gas_used,
+            logs,
+        };
+        let expected = BlockExecutionResult {
+            receipts: vec![receipt(true, 21_000, vec![log(1)]), receipt(true, 42_000, vec![])],
+            requests: Default::default(),
+            gas_used: 42_000,
+        };
+        let actual = BlockExecutionResult {
+            receipts: vec![receipt(true, 21_000, vec![log(1)]), receipt(false, 43_000, vec![])],
+            requests: Default::default(),
+            gas_used: 43_000,
+        };
+
+        let report =
+            DivergenceReport::new("on-chain", "replay").compare_block_results(&expected, &actual);
+        let fields: Vec<_> = report.divergences.iter().map(|d| d.field.as_str()).collect();
+        assert_eq!(fields, ["gas_used", "receipts[1].status", "receipts[1].cumulative_gas_used"]);
+    }
+}
@@ -0,0 +1,246 @@
+use crate::{
+    sandbox::KeylessDeployError, DelegationTargetDenied, EVMError, ExternalEnvError,
+    InvalidTransaction, MegaBlockLimitExceededError, MegaTransactionError,
+    MegaTxLimitExceededError, OpTransactionError,
+};
+
+/// Classification of an error for mapping it to a response, e.g. a JSON-RPC error code.
+///
+/// The two hints are independent: a transaction with a nonce gap is the client's error, yet it
+/// may succeed unchanged once the gap is filled.
+pub trait MegaErrorKind {
+    /// Returns whether the same request may succeed later without change, e.g. once the sender is
+    /// funded, the base fee drops or the next block opens.
+    fn is_retryable(&self) -> bool;
+
+    /// Returns whether the error is caused by the request itself rather than by the node, e.g. an
+    /// invalid transaction as opposed to a database failure.
+    fn is_client_error(&self) -> bool;
+}
+
+impl MegaErrorKind for InvalidTransaction {
+    fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::NonceTooHigh { .. } |
+                Self::LackOfFundForMaxFee { .. } |
+                Self::GasPriceLessThanBasefee
+        )
+    }
+
+    fn is_client_error(&self) -> bool {
+        true
+    }
+}
+
+impl MegaErrorKind for OpTransactionError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Base(err) => err.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Deposit errors are not the client's: deposits are derived from L1.
+    fn is_client_error(&self) -> bool {
+        match self {
+            Self::Base(err) => err.is_client_error(),
+            _ => false,
+        }
+    }
+}
+
+impl MegaErrorKind for MegaTransactionError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Base(err) => err.is_retryable(),
+            Self::DelegationTargetDenied(err) => err.is_retryable(),
+        }
+    }
+
+    fn is_client_error(&self) -> bool {
+        match self {
+            Self::Base(err) => err.is_client_error(),
+            Self::DelegationTargetDenied(err) => err.is_client_error(),
+        }
+    }
+}
+
+/// Database errors are retryable node failures; header and custom errors are neither.
+impl<DBError, TxError: MegaErrorKind> MegaErrorKind for EVMError<DBError, TxError> {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Transaction(err) => err.is_retryable(),
+            Self::Database(_) => true,
+            _ => false,
+        }
+    }
+
+    fn is_client_error(&self) -> bool {
+        match self {
+            Self::Transaction(err) => err.is_client_error(),
+            _ => false,
+        }
+    }
+}
+
+/// A transaction over a transaction-level limit never fits in any block.
+impl MegaErrorKind for MegaTxLimitExceededError {
+    fn is_retryable(&self) -> bool {
+        false
+    }
+
+    fn is_client_error(&self) -> bool {
+        true
+    }
+}
+
+/// A block-level limit rejects the transaction only for the current block; it fits in the next.
+impl MegaErrorKind for MegaBlockLimitExceededError {
+    fn is_retryable(&self) -> bool {
+        true
+    }
+
+    fn is_client_error(&self) -> bool {
+        false
+    }
+}
+
+/// An external env outage is a node failure, like a database failure.
+impl MegaErrorKind for ExternalEnvError {
+    fn is_retryable(&self) -> bool {
+        true
+    }
+
+    fn is_client_error(&self) -> bool {
+        false
+    }
+}
+
+impl MegaErrorKind for KeylessDeployError {
+    fn is_retryable(&self) -> bool {
+        matches!(self, Self::InsufficientBalance | Self::InternalError)
+    }
+
+    fn is_client_error(&self) -> bool {
+        !matches!(
+            self,
+            Self::InternalError |
+                Self::NoContractCreated |
+                Self::AddressMismatch |
+                Self::NotIntercepted
+        )
+    }
+}
+
+/// The errors surfaced by `MegaETH` transaction execution, unified for classification with
+/// [`MegaErrorKind`].
+#[derive(Debug, thiserror::Error)]
+pub enum MegaError<DBError> {
+    /// The EVM rejected the transaction or failed to execute it.
+    #[error(transparent)]
+    Evm(#[from] EVMError<DBError, MegaTransactionError>),
+    /// The transaction exceeds a transaction-level limit in the pre-execution check.
+    #[error(transparent)]
+    TxLimitExceeded(#[from] MegaTxLimitExceededError),
+    /// The block has reached a block-level limit in the pre-execution check.
+    #[error(transparent)]
+    BlockLimitExceeded(#[from] MegaBlockLimitExceededError),
+    /// The keyless deploy sandbox rejected the deployment.
+    #[error("keyless deploy failed: {0:?}")]
+    KeylessDeploy(#[from] KeylessDeployError),
+}
+
+/// A denied delegation is the client's error and is denied again on retry.
+impl MegaErrorKind for DelegationTargetDenied {
+    fn is_retryable(&self) -> bool {
+        false
+    }
+
+    fn is_client_error(&self) -> bool {
+        true
+    }
+}
+
+impl<DBError> MegaErrorKind for MegaError<DBError> {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Evm(err) => err.is_retryable(),
+            Self::TxLimitExceeded(err) => err.is_retryable(),
+            Self::BlockLimitExceeded(err) => err.is_retryable(),
+            Self::KeylessDeploy(err) => err.is_retryable(),
+        }
+    }
+
+    fn is_client_error(&self) -> bool {
+        match self {
+            Self::Evm(err) => err.is_client_error(),
+            Self::TxLimitExceeded(err) => err.is_client_error(),
+            Self::BlockLimitExceeded(err) => err.is_client_error(),
+            Self::KeylessDeploy(err) => err.is_client_error(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+
+    #[test]
+    fn test_transaction_errors_are_client_errors() {
+        let nonce_gap: MegaError<Infallible> =
+            EVMError::Transaction(MegaTransactionError::from(InvalidTransaction::NonceTooHigh {
+                tx: 5,
+                state: 3,
+            }))
+            .into();
+        assert!(nonce_gap.is_client_error());
+        assert!(nonce_gap.is_retryable());
+
+        let wrong_chain: MegaError<Infallible> =
+            EVMError::Transaction(MegaTransactionError::from(InvalidTransaction::InvalidChainId))
+                .into();
+        assert!(wrong_chain.is_client_error());
+        assert!(!wrong_chain.is_retryable());
+
+        let target = crate::ORACLE_CONTRACT_ADDRESS;
+        let denied: MegaError<Infallible> =
+            EVMError::Transaction(MegaTransactionError::from(DelegationTargetDenied { target }))
+                .into();
+        assert!(denied.is_client_error());
+        assert!(!denied.is_retryable());
+    }
+
+    #[test]
+    fn test_limit_errors_split_by_level() {
+        let tx_limit: MegaError<Infallible> =
+            MegaTxLimitExceededError::TransactionGasLimit { tx_gas_limit: 2, limit: 1 }.into();
+        assert!(tx_limit.is_client_error());
+        assert!(!tx_limit.is_retryable());
+
+        let block_limit: MegaError<Infallible> =
+            MegaBlockLimitExceededError::KVUpdateLimit { block_used: 2, limit: 1 }.into();
+        assert!(!block_limit.is_client_error());
+        assert!(block_limit.is_retryable());
+    }
+
+    #[test]
+    fn test_node_failures_are_not_client_errors() {
+        let db: EVMError<&str, MegaTransactionError> = EVMError::Database("io");
+        assert!(db.is_retryable());
+        assert!(!db.is_client_error());
+
+        let internal: MegaError<Infallible> = KeylessDeployError::InternalError.into();
+        assert!(internal.is_retryable());
+        assert!(!internal.is_client_error());
+        assert!(MegaError::<Infallible>::from(KeylessDeployError::SignerHasCode).is_client_error());
+
+        let salt = ExternalEnvError::salt(
+            crate::ExternalEnvKey::Account { address: Default::default(), bucket_id: 0 },
+            "unavailable",
+        );
+        assert!(salt.is_retryable());
+        assert!(!salt.is_client_error());
+    }
+}
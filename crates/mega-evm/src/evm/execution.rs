@@ -1,10 +1,14 @@
 #[cfg(not(feature = "std"))]
 use alloc as std;
-use std::{collections::BTreeMap, string::ToString, vec::Vec};
+use std::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+    vec::Vec,
+};
 
 use alloy_evm::{precompiles::PrecompilesMap, Database};
 use alloy_primitives::{Address, Bytes, TxKind, U256};
-use delegate::delegate;
 use op_revm::{
     constants::{BASE_FEE_RECIPIENT, L1_FEE_RECIPIENT, OPERATOR_FEE_RECIPIENT},
     handler::{IsTxError, OpHandler},
@@ -13,7 +17,7 @@ use op_revm::{
 };
 use revm::{
     context::{
-        result::{ExecutionResult, FromStringError, InvalidTransaction},
+        result::{EVMError, ExecutionResult, FromStringError, InvalidTransaction},
         transaction::{AuthorizationTr, TransactionType},
         Block, Cfg, ContextError, ContextTr, FrameStack, JournalTr, LocalContextTr, Transaction,
     },
@@ -35,27 +39,52 @@ use revm::{
         InstructionResult, InterpreterAction, InterpreterResult,
     },
     primitives::CALL_STACK_LIMIT,
-    Inspector, Journal,
+    Inspector, Journal, JournalEntry,
 };
 
 use crate::{
     constants, dispatch_system_contract_interceptors, is_deposit_like_transaction,
-    is_mega_system_transaction_with, limit::ACCOUNT_INFO_WRITE_SIZE, sent_from_system_address,
-    ExternalEnvTypes, HostExt, JournalInspectTr, MegaContext, MegaEvm, MegaHaltReason,
-    MegaInstructions, MegaSpecId, MegaTransactionError, MEGA_SYSTEM_TRANSACTION_SOURCE_HASH,
+    is_mega_system_transaction_with, limit::ACCOUNT_INFO_WRITE_SIZE, oracle_get_slot_fast_path,
+    sent_from_system_address, DefaultMegaHandlerHooks, DetainedGasRefund, ExternalEnvTypes,
+    FeeDistribution, FeeRecipient, HostExt, JournalInspectTr, MegaContext, MegaEvm, MegaHaltReason,
+    MegaHandlerHooks, MegaInstructions, MegaSpecId, MegaTransactionError, PrewarmPolicy,
+    SimulationOptions, MEGA_SYSTEM_TRANSACTION_SOURCE_HASH,
 };
 
 /// Revm handler for `MegaETH`. It internally wraps the [`op_revm::handler::OpHandler`] and inherits
 /// most functionalities from Optimism.
+///
+/// The overridable `MegaETH` policies are delegated to `HOOKS` (see [`MegaHandlerHooks`]).
 #[allow(missing_debug_implementations)]
-pub struct MegaHandler<EVM, ERROR, FRAME> {
-    op: OpHandler<EVM, ERROR, FRAME>,
+pub struct MegaHandler<EVM, ERROR, FRAME, HOOKS = DefaultMegaHandlerHooks> {
+    hooks: HOOKS,
+    _phantom: core::marker::PhantomData<(EVM, ERROR, FRAME)>,
 }
 
+/// The error of the wrapped [`OpHandler`], converted by [`MegaHandler`] into its own.
+type OpError<DBError> = EVMError<DBError, OpTransactionError>;
+
 impl<EVM, ERROR, FRAME> MegaHandler<EVM, ERROR, FRAME> {
     /// Create a new `MegaethHandler`.
     pub fn new() -> Self {
-        Self { op: OpHandler::new() }
+        Self::with_hooks(DefaultMegaHandlerHooks)
+    }
+}
+
+impl<EVM, ERROR, FRAME, HOOKS> MegaHandler<EVM, ERROR, FRAME, HOOKS> {
+    /// Create a new `MegaethHandler` with custom hooks.
+    pub fn with_hooks(hooks: HOOKS) -> Self {
+        Self { hooks, _phantom: core::marker::PhantomData }
+    }
+
+    /// Returns the hooks of the handler.
+    pub const fn hooks(&self) -> &HOOKS {
+        &self.hooks
+    }
+
+    /// Consumes the handler and returns its hooks.
+    pub fn into_hooks(self) -> HOOKS {
+        self.hooks
     }
 }
 
@@ -65,7 +94,7 @@ impl<EVM, ERROR, FRAME> Default for MegaHandler<EVM, ERROR, FRAME> {
     }
 }
 
-impl<DB, EVM, ERROR, FRAME, ExtEnvs> MegaHandler<EVM, ERROR, FRAME>
+impl<DB, EVM, ERROR, FRAME, ExtEnvs, HOOKS> MegaHandler<EVM, ERROR, FRAME, HOOKS>
 where
     DB: Database,
     ExtEnvs: ExternalEnvTypes,
@@ -187,17 +216,80 @@ struct FeeRecipientSnapshot {
 struct AppliedAuthorization {
     /// The recovered authority account the authorization delegates.
     authority: Address,
+    /// The delegation target. The zero address clears the delegation.
+    target: Address,
     /// `true` if applying the authorization materializes an account that does not yet exist.
     creates_authority: bool,
+    /// `true` if the authority carries a delegation before this authorization applies.
+    delegated_before: bool,
+    /// `true` if the authority carries a delegation after this authorization applies, i.e. the
+    /// delegation target is not the zero address.
+    delegated_after: bool,
 }
 
-impl<DB, EVM, ERROR, FRAME, ExtEnvs> MegaHandler<EVM, ERROR, FRAME>
+impl<DB, EVM, ERROR, FRAME, ExtEnvs, HOOKS> MegaHandler<EVM, ERROR, FRAME, HOOKS>
 where
     DB: Database,
     ExtEnvs: ExternalEnvTypes,
     EVM: EvmTr<Context = MegaContext<DB, ExtEnvs>>,
     ERROR: From<DB::Error> + FromStringError,
 {
+    /// Returns the nonce [`Self::close_nonce_gap`] raises the caller's nonce to, if the context
+    /// tolerates nonce gaps (see [`MegaContext::with_nonce_gap_tolerance`]). Deposit-like
+    /// transactions carry no nonce check and have none.
+    fn nonce_gap_target(evm: &EVM) -> Option<u64> {
+        let ctx = evm.ctx_ref();
+        (ctx.nonce_gap_tolerance &&
+            !ctx.cfg().disable_nonce_check &&
+            !is_deposit_like_transaction(ctx.tx(), ctx.system_address))
+        .then(|| ctx.tx().nonce())
+    }
+
+    /// Raises the caller's nonce to the transaction nonce when the context tolerates nonce gaps
+    /// and the transaction nonce is ahead.
+    ///
+    /// Runs at the end of `validate`, after every validation rejection, so a rejected transaction
+    /// never changes the caller's nonce. The strict nonce check of
+    /// `validate_against_state_and_deduct_caller`, the nonce bump, and the created address of a
+    /// CREATE transaction all see the transaction nonce. A nonce behind the caller's is left
+    /// alone and rejected by the strict check as usual.
+    ///
+    /// The raise is not journaled: a `NonceChange` entry undoes a single increment, so journaling
+    /// it would take one entry per skipped nonce. The previous nonce is kept in the context
+    /// instead and [`Self::restore_nonce_gap`] puts it back when the transaction is rejected.
+    /// Reverting frames never undo the raise, as it precedes the first checkpoint.
+    fn close_nonce_gap(&self, evm: &mut EVM) -> Result<(), ERROR> {
+        let Some(tx_nonce) = Self::nonce_gap_target(evm) else {
+            return Ok(());
+        };
+        let ctx = evm.ctx_mut();
+        let caller = ctx.tx().caller();
+        let caller_account = ctx.journaled_state.inspect_account(caller, false).map_err(|e| {
+            ERROR::from_string(format!("Failed to inspect caller account for nonce gap: {e:?}"))
+        })?;
+        let old_nonce = caller_account.info.nonce;
+        if tx_nonce <= old_nonce {
+            return Ok(());
+        }
+        caller_account.info.nonce = tx_nonce;
+        caller_account.mark_touch();
+        ctx.nonce_gap_origin = Some(old_nonce);
+        Ok(())
+    }
+
+    /// Restores the caller's nonce raised by [`Self::close_nonce_gap`] once a rejected
+    /// transaction has been discarded.
+    fn restore_nonce_gap(evm: &mut EVM) {
+        let ctx = evm.ctx_mut();
+        let Some(old_nonce) = ctx.nonce_gap_origin.take() else {
+            return;
+        };
+        let caller = ctx.tx().caller();
+        if let Some(caller_account) = ctx.journaled_state.inner.state.get_mut(&caller) {
+            caller_account.info.nonce = old_nonce;
+        }
+    }
+
     /// Read-only scan of a transaction's EIP-7702 authorization list, mirroring revm's auth-list
     /// application order: the chain-id / `u64::MAX`-nonce / non-empty-non-7702-code gates, the
     /// per-authority account-nonce match, and the sequential simulated-nonce tracking for repeated
@@ -218,6 +310,9 @@ where
         evm: &mut EVM,
         caller_nonce_already_bumped: bool,
     ) -> Result<Vec<AppliedAuthorization>, ERROR> {
+        // The `validate` scan runs before `close_nonce_gap`, so it anticipates the raised nonce.
+        let gap_target =
+            if caller_nonce_already_bumped { None } else { Self::nonce_gap_target(evm) };
         let ctx = evm.ctx_mut();
         let chain_id = ctx.cfg().chain_id;
         let (tx, journal) = ctx.tx_journal_mut();
@@ -228,7 +323,7 @@ where
         // would need, bounding the whole pass at O(N log N) (an attacker could otherwise drive
         // O(N²) node CPU with many unique authorities in one tx). The map is only ever keyed,
         // never iterated for output, so the produced `applied` list is unchanged.
-        let mut simulated_authorities = BTreeMap::<Address, u64>::new();
+        let mut simulated_authorities = BTreeMap::<Address, (u64, bool)>::new();
         let mut applied = Vec::new();
         for authorization in tx.authorization_list() {
             let auth_chain_id = authorization.chain_id();
@@ -242,45 +337,58 @@ where
                 continue;
             };
 
-            let (authority_nonce, creates_authority) = if let Some(nonce) =
-                simulated_authorities.get(&authority).copied()
-            {
-                (nonce, false)
-            } else {
-                // No-warm read: this scan is a pre-flight count and must not change the
-                // access list. Authority warming is owned by revm's authorization application;
-                // `inspect_account` keeps that boundary clean.
-                let authority_acc = journal.inspect_account(authority, true)?;
-                if let Some(bytecode) = &authority_acc.info.code {
-                    if !bytecode.is_empty() && !bytecode.is_eip7702() {
-                        continue;
-                    }
-                }
-                // Mirror the call tx caller's own nonce bump for a self-authorization, unless this
-                // scan already runs after that bump. Type-4 txs are always calls (a missing `to`
-                // is rejected at validation as `Eip7702CannotBeCreate`), so no `is_call` guard is
-                // needed — the authorization list is non-empty only for calls.
-                let effective_nonce = if !caller_nonce_already_bumped && authority == caller {
-                    authority_acc.info.nonce.saturating_add(1)
+            let (authority_nonce, creates_authority, delegated_before) =
+                if let Some((nonce, delegated)) = simulated_authorities.get(&authority).copied() {
+                    (nonce, false, delegated)
                 } else {
-                    authority_acc.info.nonce
+                    // No-warm read: this scan is a pre-flight count and must not change the
+                    // access list. Authority warming is owned by revm's authorization application;
+                    // `inspect_account` keeps that boundary clean.
+                    let authority_acc = journal.inspect_account(authority, true)?;
+                    let mut delegated = false;
+                    if let Some(bytecode) = &authority_acc.info.code {
+                        if !bytecode.is_empty() && !bytecode.is_eip7702() {
+                            continue;
+                        }
+                        delegated = bytecode.is_eip7702();
+                    }
+                    // Mirror the call tx caller's own nonce bump for a self-authorization, unless
+                    // this scan already runs after that bump. Type-4 txs are always calls (a
+                    // missing `to` is rejected at validation as `Eip7702CannotBeCreate`), so no
+                    // `is_call` guard is needed — the authorization list is non-empty only for
+                    // calls.
+                    let effective_nonce = if !caller_nonce_already_bumped && authority == caller {
+                        let caller_nonce = authority_acc.info.nonce.max(gap_target.unwrap_or(0));
+                        caller_nonce.saturating_add(1)
+                    } else {
+                        authority_acc.info.nonce
+                    };
+                    (
+                        effective_nonce,
+                        authority_acc.is_empty() &&
+                            authority_acc.is_loaded_as_not_existing_not_touched(),
+                        delegated,
+                    )
                 };
-                (
-                    effective_nonce,
-                    authority_acc.is_empty() &&
-                        authority_acc.is_loaded_as_not_existing_not_touched(),
-                )
-            };
 
             if authorization.nonce() != authority_nonce {
                 continue;
             }
 
-            applied.push(AppliedAuthorization { authority, creates_authority });
+            // Delegating to the zero address clears the authority's code.
+            let target = authorization.address();
+            let delegated_after = !target.is_zero();
+            applied.push(AppliedAuthorization {
+                authority,
+                target,
+                creates_authority,
+                delegated_before,
+                delegated_after,
+            });
             let next_nonce = authority_nonce.saturating_add(1);
             // insert overwrites an existing entry and inserts a new one otherwise,
             // matching the prior find-or-push.
-            simulated_authorities.insert(authority, next_nonce);
+            simulated_authorities.insert(authority, (next_nonce, delegated_after));
         }
         Ok(applied)
     }
@@ -363,38 +471,55 @@ where
     ///   / nonce / code gates), not every recoverable one;
     /// - charges state growth +1 for each *net-new* authority and returns its address so the caller
     ///   can add the dynamic SALT account-creation gas to `initial_gas`;
+    /// - charges state growth +1 for each pre-existing authority that ends the authorization list
+    ///   with a delegation it did not have, and refunds 1 for each that ends it with its delegation
+    ///   cleared — so a set-then-clear of the same authority within one tx nets to zero;
     /// - marks beneficiary detention when an applied authority is the block beneficiary.
     ///
     /// Returns the net-new authority addresses; the caller uses them both to charge SALT gas and
     /// to avoid double-charging an auth-materialized value-transfer recipient. The caller gates
     /// this to REX6 type-4 transactions; pre-REX6 keeps the old split frozen.
+    ///
+    /// `applied` is the scan of the authorizations in `validate`, before the caller nonce bump.
     #[inline]
     fn record_rex6_eip7702_authority_accounting(
         &self,
         evm: &mut EVM,
-    ) -> Result<Vec<Address>, ERROR> {
-        // Runs in validate, before the caller nonce bump.
-        let applied = self.scan_applied_eip7702_authorizations(evm, false)?;
-
+        applied: Vec<AppliedAuthorization>,
+    ) -> BTreeSet<Address> {
         // Record per-applied-authority resources + beneficiary detention. Dynamic SALT gas is
         // charged by the caller from `materialized` (the net-new authorities).
         let ctx = evm.ctx_mut();
         let beneficiary = ctx.inner.block.beneficiary;
-        let mut materialized = Vec::new();
+        let mut materialized = BTreeSet::new();
         let mut beneficiary_applied = false;
+        // Delegation state of each pre-existing authority at the start and end of the list. A
+        // net-new authority is already counted as an account creation, which covers its code.
+        let mut delegations = BTreeMap::<Address, (bool, bool)>::new();
         for auth in applied {
             ctx.additional_limit
                 .borrow_mut()
                 .on_rex6_eip7702_authority_applied(auth.creates_authority);
+            if !auth.creates_authority && !materialized.contains(&auth.authority) {
+                delegations
+                    .entry(auth.authority)
+                    .and_modify(|(_, after)| *after = auth.delegated_after)
+                    .or_insert((auth.delegated_before, auth.delegated_after));
+            }
             if auth.creates_authority {
-                // The scanner yields each net-new authority once, so `materialized` stays a set.
-                debug_assert!(!materialized.contains(&auth.authority));
-                materialized.push(auth.authority);
+                // The scanner yields each net-new authority once.
+                let inserted = materialized.insert(auth.authority);
+                debug_assert!(inserted);
             }
             if auth.authority == beneficiary {
                 beneficiary_applied = true;
             }
         }
+        let installs = delegations.values().filter(|(before, after)| !before && *after).count();
+        let clears = delegations.values().filter(|(before, after)| *before && !after).count();
+        ctx.additional_limit
+            .borrow_mut()
+            .on_rex6_eip7702_delegation_changes(installs as u64, clears as u64);
 
         // An applied authority that is the block beneficiary mutates beneficiary state, so mark
         // it and re-derive the REX4 beneficiary detention cap — the cap set at `on_new_tx`
@@ -406,7 +531,20 @@ where
             }
         }
 
-        Ok(materialized)
+        materialized
+    }
+
+    /// Applies `policy` on top of the accounts warmed by `load_accounts`: un-warms the block
+    /// beneficiary if the policy leaves it cold, and loads the system contracts it warms.
+    fn apply_prewarm_policy(evm: &mut EVM, policy: PrewarmPolicy) -> Result<(), ERROR> {
+        let journal = &mut evm.ctx().journaled_state;
+        if !policy.coinbase {
+            journal.inner.warm_coinbase_address = None;
+        }
+        for address in policy.system_contract_addresses() {
+            journal.load_account(address)?;
+        }
+        Ok(())
     }
 }
 
@@ -521,6 +659,55 @@ impl<DB: Database, INSP, ExtEnvs: ExternalEnvTypes> MegaEvm<DB, INSP, ExtEnvs> {
 
         Ok(())
     }
+
+    /// Records the checkpoint of `frame` in the journal stats, if collected, once
+    /// `process_next_action` has returned its result and thus committed or reverted the
+    /// checkpoint. `journal_len` is the journal length before `process_next_action`.
+    #[inline]
+    fn record_journal_stats(
+        ctx: &mut MegaContext<DB, ExtEnvs>,
+        frame: &EthFrame<EthInterpreter>,
+        journal_len: usize,
+        frame_output: &ItemOrResult<FrameInit, FrameResult>,
+    ) {
+        let Some(journal_stats) = ctx.journal_stats.as_mut() else { return };
+        if let ItemOrResult::Result(frame_result) = frame_output {
+            let reverted_entries = (!frame_result.interpreter_result().is_ok())
+                .then(|| journal_len.saturating_sub(frame.checkpoint.journal_i));
+            journal_stats.record_frame_end(frame.depth + 1, reverted_entries);
+        }
+    }
+
+    /// Runs the instructions of `frame` until it yields an action, like `run_plain`, timing every
+    /// instruction in the opcode profile if opcode profiling is enabled.
+    #[inline]
+    fn run_frame_instructions(
+        ctx: &mut MegaContext<DB, ExtEnvs>,
+        frame: &mut EthFrame<EthInterpreter>,
+        instructions: &MegaInstructions<DB, ExtEnvs>,
+    ) -> InterpreterAction {
+        #[cfg(feature = "opcode-profiling")]
+        if let Some(mut profile) = ctx.opcode_profile.take() {
+            use revm::interpreter::interpreter_types::{Jumps, LoopControl};
+
+            let interpreter = &mut frame.interpreter;
+            while interpreter.bytecode.is_not_end() {
+                let opcode = interpreter.bytecode.opcode();
+                let gas_before = interpreter.gas.remaining();
+                let start = std::time::Instant::now();
+                interpreter.step(instructions.instruction_table(), ctx);
+                let elapsed = start.elapsed();
+                profile.record(
+                    opcode,
+                    elapsed,
+                    gas_before.saturating_sub(interpreter.gas.remaining()),
+                );
+            }
+            ctx.opcode_profile = Some(profile);
+            return interpreter.take_next_action();
+        }
+        frame.interpreter.run_plain(instructions.instruction_table(), ctx)
+    }
 }
 
 /// Mirrors `revm_handler::frame::return_create`'s pre-commit predicate.
@@ -558,12 +745,334 @@ fn will_return_create_charge_code_deposit(
     interpreter_result.gas.remaining() >= code_deposit_gas
 }
 
-impl<DB: Database, EVM, ERROR, FRAME, ExtEnvs: ExternalEnvTypes> Handler
-    for MegaHandler<EVM, ERROR, FRAME>
+/// Pending state of a deferred beneficiary reward, restored once op-revm has paid the fee
+/// recipients. See [`MegaContext::defer_beneficiary_reward`].
+struct BeneficiaryDeferral {
+    beneficiary: Address,
+    gas_price: u128,
+    gas_priority_fee: Option<u128>,
+    reward: U256,
+}
+
+/// Pending state of a fee redirection, applied once op-revm has paid the fee recipients. See
+/// [`FeeDistribution`].
+struct FeeRedirection {
+    beneficiary: Address,
+    gas_price: u128,
+    gas_priority_fee: Option<u128>,
+    /// The priority fee op-revm credits to the beneficiary in effect while it pays the rewards.
+    priority_reward: U256,
+    /// The redirected fee vaults, with their recipient and balance before the rewards.
+    vaults: Vec<(Address, FeeRecipient, U256)>,
+}
+
+impl<DB, EVM, ERROR, FRAME, ExtEnvs, HOOKS> MegaHandler<EVM, ERROR, FRAME, HOOKS>
 where
+    DB: Database,
+    ExtEnvs: ExternalEnvTypes,
+    EVM: EvmTr<Context = MegaContext<DB, ExtEnvs>, Frame = FRAME>,
+    ERROR: EvmTrError<EVM>
+        + From<MegaTransactionError>
+        + FromStringError
+        + IsTxError
+        + core::fmt::Debug,
+    FRAME: FrameTr<FrameResult = FrameResult, FrameInit = FrameInit>,
+{
+    /// Returns the op-revm handler the `MegaETH` handler builds on. It reports op-revm's own
+    /// error, which [`Self::from_op_error`] converts.
+    fn op() -> OpHandler<EVM, OpError<DB::Error>, FRAME> {
+        OpHandler::new()
+    }
+
+    /// Converts an error of the op-revm handler, wrapping its transaction errors in
+    /// [`MegaTransactionError::Base`].
+    fn from_op_error(error: OpError<DB::Error>) -> ERROR {
+        match error {
+            EVMError::Transaction(err) => MegaTransactionError::Base(err).into(),
+            EVMError::Header(err) => err.into(),
+            EVMError::Database(err) => err.into(),
+            EVMError::Custom(err) => ERROR::from_string(err),
+        }
+    }
+
+    /// Credits the post-execution rewards to the block beneficiary and the fee vaults through
+    /// op-revm, accounting the writes from REX6.
+    fn reward_fee_recipients(
+        &self,
+        evm: &mut EVM,
+        exec_result: &mut FrameResult,
+    ) -> Result<(), ERROR> {
+        // Pre-REX6: frozen. Delegate unchanged so stable-spec replay is byte-for-byte —
+        // the post-execution fee-reward materialisations remain unaccounted exactly as
+        // historical REX5-and-earlier blocks recorded them.
+        if !evm.ctx().spec.is_enabled(MegaSpecId::REX6) {
+            return Self::op().reward_beneficiary(evm, exec_result).map_err(Self::from_op_error);
+        }
+
+        // REX6: op-revm credits the beneficiary + fee vaults HERE, after `last_frame_result`
+        // finalised the trackers — so these writes escape DataSize / KV / StateGrowth unless
+        // accounted now. Deposit / keyless-sandbox txs credit nothing (op-revm early-returns),
+        // so the diff naturally records nothing for them.
+        let snapshots = Self::snapshot_fee_recipients(evm)?;
+
+        Self::op().reward_beneficiary(evm, exec_result).map_err(Self::from_op_error)?;
+
+        for snapshot in snapshots {
+            let (balance, now_empty) =
+                Self::fee_recipient_balance_and_emptiness(evm, snapshot.address)?;
+            if balance == snapshot.balance {
+                continue;
+            }
+            // One account-info write = 40 bytes DataSize + 1 KV update; a newly materialised
+            // account also counts as +1 StateGrowth. TX-persistent lane (frames are gone).
+            let mut limit = evm.ctx().additional_limit.borrow_mut();
+            limit.data_size.merge_persistent_usage(ACCOUNT_INFO_WRITE_SIZE);
+            limit.kv_update.merge_persistent_usage(1);
+            if snapshot.was_empty && !now_empty {
+                limit.state_growth.merge_persistent_usage(1);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Starts deferring the beneficiary reward of the current transaction, if enabled.
+    ///
+    /// Adds the reward op-revm would credit to the beneficiary (the priority fee per gas times
+    /// the gas used) to the context's ledger, then points op-revm's beneficiary credit at the base
+    /// fee vault with a zero priority fee. The base fee vault is credited by every non-deposit
+    /// transaction anyway, so the beneficiary account is never written and the vault credits are
+    /// unchanged. Deposit transactions pay no reward and are left alone, as is a beneficiary that
+    /// is itself a fee vault, since deferring its reward would not remove a write.
+    fn begin_beneficiary_deferral(evm: &mut EVM, gas: &Gas) -> Option<BeneficiaryDeferral> {
+        let ctx = evm.ctx();
+        let beneficiary = ctx.block().beneficiary();
+        if ctx.deferred_beneficiary_reward.is_none() ||
+            ctx.tx().tx_type() == DEPOSIT_TRANSACTION_TYPE ||
+            [L1_FEE_RECIPIENT, BASE_FEE_RECIPIENT, OPERATOR_FEE_RECIPIENT].contains(&beneficiary)
+        {
+            return None;
+        }
+
+        // Mirrors revm's `post_execution::reward_beneficiary` (London is always active).
+        let basefee = ctx.block().basefee() as u128;
+        let priority_fee = ctx.tx().effective_gas_price(basefee).saturating_sub(basefee);
+        let gas_used = gas.spent().saturating_sub(gas.refunded() as u64);
+        let reward = U256::from(priority_fee * gas_used as u128);
+        if let Some(ledger) = ctx.deferred_beneficiary_reward.as_mut() {
+            *ledger = ledger.saturating_add(reward);
+        }
+
+        let tx = &mut ctx.inner.tx.base;
+        let deferral = BeneficiaryDeferral {
+            beneficiary,
+            gas_price: tx.gas_price,
+            gas_priority_fee: tx.gas_priority_fee,
+            reward,
+        };
+        tx.gas_price = basefee;
+        tx.gas_priority_fee = Some(0);
+        ctx.inner.block.beneficiary = BASE_FEE_RECIPIENT;
+        Some(deferral)
+    }
+
+    /// Starts redirecting the fees of the current transaction according to `distribution`.
+    ///
+    /// The priority fee is redirected before op-revm pays it, by pointing the beneficiary at its
+    /// recipient, or, to burn it, at the base fee vault with a zero priority fee as a deferral
+    /// does. The base and operator fee vaults are snapshotted, and the amounts op-revm credits
+    /// them are moved by [`Self::end_fee_redirection`]. Deposit transactions pay no fee and a
+    /// deferred beneficiary reward is left to the ledger.
+    fn begin_fee_redirection(
+        evm: &mut EVM,
+        gas: &Gas,
+        distribution: &FeeDistribution,
+        deferred: bool,
+    ) -> Result<Option<FeeRedirection>, ERROR> {
+        let ctx = evm.ctx();
+        if ctx.tx().tx_type() == DEPOSIT_TRANSACTION_TYPE {
+            return Ok(None);
+        }
+        let basefee = ctx.block().basefee() as u128;
+        let tx = &mut ctx.inner.tx.base;
+        let mut redirection = FeeRedirection {
+            beneficiary: ctx.inner.block.beneficiary,
+            gas_price: tx.gas_price,
+            gas_priority_fee: tx.gas_priority_fee,
+            priority_reward: U256::ZERO,
+            vaults: Vec::new(),
+        };
+        match distribution.priority_fee {
+            _ if deferred => {}
+            FeeRecipient::Default => {}
+            FeeRecipient::Address(recipient) => ctx.inner.block.beneficiary = recipient,
+            FeeRecipient::Burn => {
+                tx.gas_price = basefee;
+                tx.gas_priority_fee = Some(0);
+                ctx.inner.block.beneficiary = BASE_FEE_RECIPIENT;
+            }
+        }
+
+        // Mirrors revm's `post_execution::reward_beneficiary` (London is always active).
+        let priority_fee = ctx.tx().effective_gas_price(basefee).saturating_sub(basefee);
+        let gas_used = gas.spent().saturating_sub(gas.refunded() as u64);
+        redirection.priority_reward = U256::from(priority_fee * gas_used as u128);
+
+        for (vault, recipient) in [
+            (BASE_FEE_RECIPIENT, distribution.base_fee),
+            (OPERATOR_FEE_RECIPIENT, distribution.operator_fee),
+        ] {
+            if recipient != FeeRecipient::Default {
+                let (balance, _) = Self::fee_recipient_balance_and_emptiness(evm, vault)?;
+                redirection.vaults.push((vault, recipient, balance));
+            }
+        }
+        Ok(Some(redirection))
+    }
+
+    /// Restores the transaction and block environment changed by
+    /// [`Self::begin_fee_redirection`], then moves the fees op-revm credited to the redirected
+    /// vaults to their recipient, or burns them.
+    fn end_fee_redirection(evm: &mut EVM, redirection: FeeRedirection) -> Result<(), ERROR> {
+        let ctx = evm.ctx();
+        let rewarded_beneficiary = ctx.inner.block.beneficiary;
+        ctx.inner.block.beneficiary = redirection.beneficiary;
+        ctx.inner.tx.base.gas_price = redirection.gas_price;
+        ctx.inner.tx.base.gas_priority_fee = redirection.gas_priority_fee;
+
+        for (vault, recipient, balance_before) in redirection.vaults {
+            let (balance, _) = Self::fee_recipient_balance_and_emptiness(evm, vault)?;
+            let mut fee = balance.saturating_sub(balance_before);
+            // The vault may also have been paid the priority fee as the beneficiary.
+            if vault == rewarded_beneficiary {
+                fee = fee.saturating_sub(redirection.priority_reward);
+            }
+            if fee.is_zero() {
+                continue;
+            }
+
+            let journal = &mut evm.ctx().journaled_state;
+            let account = journal.inspect_account(vault, false).map_err(|e| {
+                ERROR::from_string(format!("Failed to inspect fee vault {vault}: {e:?}"))
+            })?;
+            let old_balance = account.info.balance;
+            // The fee was measured on this balance, but never move more than the vault holds.
+            let fee = fee.min(old_balance);
+            account.info.balance = old_balance.saturating_sub(fee);
+            account.mark_touch();
+            journal.inner.journal.push(JournalEntry::BalanceChange { address: vault, old_balance });
+            if let FeeRecipient::Address(recipient) = recipient {
+                journal.balance_incr(recipient, fee).map_err(|e| {
+                    ERROR::from_string(format!("Failed to credit fee recipient {recipient}: {e:?}"))
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Restores the transaction and block environment changed by
+    /// [`Self::begin_beneficiary_deferral`].
+    ///
+    /// From REX6, also accounts the beneficiary write the deferred reward stands for, exactly as
+    /// [`Self::reward_fee_recipients`] would have: 40 bytes of data size and one KV update if the
+    /// reward is non-zero, plus one state growth if it materializes the beneficiary. The
+    /// beneficiary is empty only if neither the state nor the ledger credits it.
+    fn end_beneficiary_deferral(evm: &mut EVM, deferral: BeneficiaryDeferral) -> Result<(), ERROR> {
+        let ctx = evm.ctx();
+        ctx.inner.block.beneficiary = deferral.beneficiary;
+        ctx.inner.tx.base.gas_price = deferral.gas_price;
+        ctx.inner.tx.base.gas_priority_fee = deferral.gas_priority_fee;
+
+        if !ctx.spec.is_enabled(MegaSpecId::REX6) || deferral.reward.is_zero() {
+            return Ok(());
+        }
+        let ledger_before =
+            ctx.deferred_beneficiary_reward.unwrap_or_default().saturating_sub(deferral.reward);
+        let (_, empty) = Self::fee_recipient_balance_and_emptiness(evm, deferral.beneficiary)?;
+        let was_empty = empty && ledger_before.is_zero();
+        let mut limit = evm.ctx().additional_limit.borrow_mut();
+        limit.data_size.merge_persistent_usage(ACCOUNT_INFO_WRITE_SIZE);
+        limit.kv_update.merge_persistent_usage(1);
+        if was_empty {
+            limit.state_growth.merge_persistent_usage(1);
+        }
+        Ok(())
+    }
+
+    /// Returns the fee payer overriding the caller under the context's [`SimulationOptions`],
+    /// if any. Deposit-like transactions pay no fee and never have one.
+    fn simulated_fee_payer(evm: &mut EVM) -> Option<Address> {
+        let ctx = evm.ctx();
+        ctx.simulation_options.fee_payer_override.filter(|fee_payer| {
+            *fee_payer != ctx.tx().caller() &&
+                !is_deposit_like_transaction(ctx.tx(), ctx.system_address)
+        })
+    }
+
+    /// Returns the balance of `address` without warming it.
+    fn simulated_balance(evm: &mut EVM, address: Address) -> Result<U256, ERROR> {
+        let account = evm.ctx_mut().journal_mut().inspect_account(address, false).map_err(|e| {
+            ERROR::from_string(format!("Failed to inspect account {address}: {e:?}"))
+        })?;
+        Ok(account.info.balance)
+    }
+
+    /// Sets the balance of `address` without warming it, journaling the change so that a
+    /// discarded transaction restores the previous balance.
+    fn set_simulated_balance(evm: &mut EVM, address: Address, balance: U256) -> Result<(), ERROR> {
+        let journal = &mut evm.ctx().journaled_state;
+        let account = journal.inspect_account(address, false).map_err(|e| {
+            ERROR::from_string(format!("Failed to inspect account {address}: {e:?}"))
+        })?;
+        let old_balance = account.info.balance;
+        account.info.balance = balance;
+        account.mark_touch();
+        journal.inner.journal.push(JournalEntry::BalanceChange { address, old_balance });
+        Ok(())
+    }
+
+    /// Runs op-revm's `validate_against_state_and_deduct_caller` with `fee_payer` paying the
+    /// fees.
+    ///
+    /// op-revm checks and deducts the fees and the value against the caller's balance, so the
+    /// caller's balance is temporarily replaced with the fee payer's plus the value. Whatever
+    /// op-revm deducts beyond that is the fee, which is then charged to the fee payer, and the
+    /// caller's own balance is restored. The caller must still afford the value unless the
+    /// balance check is disabled.
+    fn deduct_fee_payer(&self, evm: &mut EVM, fee_payer: Address) -> Result<(), ERROR> {
+        let caller = evm.ctx().tx().caller();
+        let value = evm.ctx().tx().value();
+        let balance_check = !evm.ctx().cfg().disable_balance_check;
+        let caller_balance = Self::simulated_balance(evm, caller)?;
+        if balance_check && caller_balance < value {
+            return Err(InvalidTransaction::LackOfFundForMaxFee {
+                fee: Box::new(value),
+                balance: Box::new(caller_balance),
+            }
+            .into());
+        }
+
+        let fee_payer_balance = Self::simulated_balance(evm, fee_payer)?;
+        let funds = fee_payer_balance.saturating_add(value);
+        Self::set_simulated_balance(evm, caller, funds)?;
+        let deducted =
+            Self::op().validate_against_state_and_deduct_caller(evm).map_err(Self::from_op_error);
+        let remaining = Self::simulated_balance(evm, caller)?;
+        let restored = if balance_check { caller_balance } else { caller_balance.max(value) };
+        Self::set_simulated_balance(evm, caller, restored)?;
+        deducted?;
+
+        let fee = funds.saturating_sub(remaining);
+        Self::set_simulated_balance(evm, fee_payer, fee_payer_balance.saturating_sub(fee))
+    }
+}
+
+impl<DB: Database, EVM, ERROR, FRAME, ExtEnvs: ExternalEnvTypes, HOOKS> Handler
+    for MegaHandler<EVM, ERROR, FRAME, HOOKS>
+where
+    HOOKS: MegaHandlerHooks,
     EVM: EvmTr<Context = MegaContext<DB, ExtEnvs>, Frame = FRAME>,
     ERROR: EvmTrError<EVM>
-        + From<OpTransactionError>
         + From<MegaTransactionError>
         + FromStringError
         + IsTxError
@@ -576,21 +1085,86 @@ where
 
     type HaltReason = MegaHaltReason;
 
-    delegate! {
-        to self.op {
-            fn validate_env(&self, evm: &mut Self::Evm) -> Result<(), Self::Error>;
-            fn validate_against_state_and_deduct_caller(
-                &self,
-                evm: &mut Self::Evm,
-            ) -> Result<(), Self::Error>;
-            fn reimburse_caller(&self, evm: &mut Self::Evm, exec_result: &mut <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameResult) -> Result<(), Self::Error>;
-            fn refund(&self, evm: &mut Self::Evm, exec_result: &mut <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameResult, eip7702_refund: i64);
+    fn validate_env(&self, evm: &mut Self::Evm) -> Result<(), Self::Error> {
+        Self::op().validate_env(evm).map_err(Self::from_op_error)
+    }
+
+    /// Applies the context's [`SimulationOptions`] on top of op-revm's validation: the balance
+    /// check is skipped as with `CfgEnv::disable_balance_check`, and the fees are charged to the
+    /// fee payer override instead of the caller. Without options, op-revm runs unchanged.
+    fn validate_against_state_and_deduct_caller(
+        &self,
+        evm: &mut Self::Evm,
+    ) -> Result<(), Self::Error> {
+        let options = evm.ctx().simulation_options;
+        if options == SimulationOptions::default() {
+            return Self::op()
+                .validate_against_state_and_deduct_caller(evm)
+                .map_err(Self::from_op_error);
+        }
+
+        let ctx = evm.ctx_mut();
+        let relax_balance_check = options.disable_balance_check && !ctx.cfg().disable_balance_check;
+        if relax_balance_check {
+            ctx.inner.cfg.disable_balance_check = true;
+        }
+        let result = match Self::simulated_fee_payer(evm) {
+            Some(fee_payer) => self.deduct_fee_payer(evm, fee_payer),
+            None => Self::op()
+                .validate_against_state_and_deduct_caller(evm)
+                .map_err(Self::from_op_error),
+        };
+        if relax_balance_check {
+            evm.ctx_mut().inner.cfg.disable_balance_check = false;
+        }
+        result
+    }
+
+    /// Reimburses the unused gas to the fee payer override of the context's
+    /// [`SimulationOptions`] if set, and to the caller otherwise.
+    fn reimburse_caller(
+        &self,
+        evm: &mut Self::Evm,
+        exec_result: &mut <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameResult,
+    ) -> Result<(), Self::Error> {
+        let Some(fee_payer) = Self::simulated_fee_payer(evm) else {
+            return Self::op().reimburse_caller(evm, exec_result).map_err(Self::from_op_error);
+        };
+        let caller = evm.ctx().tx().caller();
+        let caller_balance = Self::simulated_balance(evm, caller)?;
+        Self::op().reimburse_caller(evm, exec_result).map_err(Self::from_op_error)?;
+        let reimbursed = Self::simulated_balance(evm, caller)?.saturating_sub(caller_balance);
+        if reimbursed.is_zero() {
+            return Ok(());
+        }
+        Self::set_simulated_balance(evm, caller, caller_balance)?;
+        let fee_payer_balance = Self::simulated_balance(evm, fee_payer)?;
+        Self::set_simulated_balance(evm, fee_payer, fee_payer_balance.saturating_add(reimbursed))
+    }
+
+    fn refund(
+        &self,
+        evm: &mut Self::Evm,
+        exec_result: &mut <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameResult,
+        eip7702_refund: i64,
+    ) {
+        let quotient = self.hooks.max_refund_quotient(evm.ctx().spec);
+        if quotient == Some(constants::equivalence::MAX_REFUND_QUOTIENT) {
+            return Self::op().refund(evm, exec_result, eip7702_refund);
         }
+        // Mirrors revm's `post_execution::refund` with the quotient of the hooks. Deposits are
+        // refunded as any other transaction since Regolith, which `MegaETH` always runs.
+        let gas = exec_result.gas_mut();
+        gas.record_refund(eip7702_refund);
+        let max_refund = quotient.map_or(0, |quotient| gas.spent() / quotient);
+        gas.set_refund((gas.refunded() as u64).min(max_refund) as i64);
     }
 
     fn pre_execution(&self, evm: &mut Self::Evm) -> Result<u64, Self::Error> {
         self.validate_against_state_and_deduct_caller(evm)?;
         self.load_accounts(evm)?;
+        let prewarm_policy = self.hooks.prewarm_policy(evm.ctx().spec);
+        Self::apply_prewarm_policy(evm, prewarm_policy)?;
         // EIP-7702 authority state-growth handling, split by spec era. Only type-4 txs reach
         // either branch, and no exempt (system-originated) tx is type-4 here — system txs are
         // legacy-typed pre-promotion / deposit-typed post-promotion, and a type-4 system caller is
@@ -671,24 +1245,63 @@ where
     /// REX5+ adds a final initial+floor gas validation after all Mega-side dynamic storage gas
     /// has been accounted for. Pre-REX5 specs keep the historical mid-sequence check exactly
     /// where it was so byte-for-byte replay is preserved.
+    ///
+    /// REX6+ rejects EIP-4844 blob transactions with
+    /// [`InvalidTransaction::Eip4844NotSupported`]. `MegaETH` has no blob data availability, and
+    /// pre-REX6 specs inherited whatever the op-revm validation accepted, which charged a blob fee
+    /// that no fee vault receives.
+    ///
+    /// When [`MegaHandlerHooks::restricts_delegation`], the delegation target of every EIP-7702
+    /// authorization that will be applied is checked against
+    /// [`MegaHandlerHooks::check_delegation_target`] right after the environment validation, so a
+    /// denied transaction is rejected before the sender is charged.
+    ///
+    /// The floor of [`MegaHandlerHooks::tx_gas_floors`] for the transaction type is applied last:
+    /// a transaction whose gas limit cannot cover it is rejected. Deposit and mega system
+    /// transactions have no floor.
     fn validate(&self, evm: &mut Self::Evm) -> Result<InitialAndFloorGas, Self::Error> {
+        let ctx = evm.ctx();
+        if ctx.spec.is_enabled(MegaSpecId::REX6) && ctx.tx().tx_type() == TransactionType::Eip4844 {
+            return Err(InvalidTransaction::Eip4844NotSupported.into());
+        }
         self.validate_env(evm)?;
-        let mut initial_and_floor_gas = self.validate_initial_tx_gas(evm)?;
 
         // REX6 only (gated on type-4 tx + REX6 spec): consolidated EIP-7702 authorization
-        // accounting. Records the per-applied-authority DataSize/KV/StateGrowth + beneficiary
-        // detention, and returns the net-new authority addresses so the caller folds the dynamic
-        // SALT account-creation gas into `initial_gas` before the gas-limit / fee-affordability
-        // check. Pre-REX6 / non-EIP-7702 produces `Vec::new()` and the SALT-gas loop is a no-op.
-        let record_rex6_accounting = {
+        // accounting, see below. It shares the scan of the applied authorizations with the
+        // delegation target check, which only runs when the hooks restrict the targets.
+        let (record_rex6_accounting, check_delegation_targets) = {
             let ctx = evm.ctx();
-            ctx.spec.is_enabled(MegaSpecId::REX6) && ctx.tx().tx_type() == TransactionType::Eip7702
+            let is_eip7702 = ctx.tx().tx_type() == TransactionType::Eip7702;
+            (
+                is_eip7702 && ctx.spec.is_enabled(MegaSpecId::REX6),
+                is_eip7702 && self.hooks.restricts_delegation(),
+            )
         };
-        let materialized_authorities = if record_rex6_accounting {
-            self.record_rex6_eip7702_authority_accounting(evm)?
+        let applied_authorizations = if record_rex6_accounting || check_delegation_targets {
+            self.scan_applied_eip7702_authorizations(evm, false)?
         } else {
             Vec::new()
         };
+        if check_delegation_targets {
+            // revm skips an authorization with a bad signature, chain id or nonce rather than
+            // rejecting the transaction, so only the applied ones are checked.
+            for applied in &applied_authorizations {
+                self.hooks
+                    .check_delegation_target(applied.target)
+                    .map_err(MegaTransactionError::from)?;
+            }
+        }
+        let mut initial_and_floor_gas = self.validate_initial_tx_gas(evm)?;
+
+        // Records the per-applied-authority DataSize/KV/StateGrowth + beneficiary detention, and
+        // returns the net-new authority addresses so the caller folds the dynamic SALT
+        // account-creation gas into `initial_gas` before the gas-limit / fee-affordability check.
+        // Pre-REX6 / non-EIP-7702 produces an empty set and the SALT-gas loop is a no-op.
+        let materialized_authorities = if record_rex6_accounting {
+            self.record_rex6_eip7702_authority_accounting(evm, applied_authorizations)
+        } else {
+            BTreeSet::new()
+        };
 
         let ctx = evm.ctx_mut();
         let is_mini_rex_enabled = ctx.spec.is_enabled(MegaSpecId::MINI_REX);
@@ -889,6 +1502,27 @@ where
             }
         }
 
+        // Hooks: the minimum gas charged to a transaction of this type. It raises the EIP-7623
+        // floor, so post-execution settles it like the calldata floor. Deposit and mega system
+        // transactions must never fail validation, so they have no floor.
+        let tx_gas_floor = if is_deposit_like_transaction(ctx.tx(), ctx.system_address) {
+            0
+        } else {
+            self.hooks.tx_gas_floors(ctx.spec).floor(ctx.tx().tx_type())
+        };
+        if tx_gas_floor > initial_and_floor_gas.floor_gas {
+            let gas_limit = ctx.tx().gas_limit();
+            if tx_gas_floor > gas_limit {
+                return Err(InvalidTransaction::GasFloorMoreThanGasLimit {
+                    gas_limit,
+                    gas_floor: tx_gas_floor,
+                }
+                .into());
+            }
+            initial_and_floor_gas.floor_gas = tx_gas_floor;
+        }
+
+        self.close_nonce_gap(evm)?;
         Ok(initial_and_floor_gas)
     }
 
@@ -925,38 +1559,21 @@ where
             return Ok(());
         }
 
-        // Pre-REX6: frozen. Delegate unchanged so stable-spec replay is byte-for-byte —
-        // the post-execution fee-reward materialisations remain unaccounted exactly as
-        // historical REX5-and-earlier blocks recorded them.
-        if !evm.ctx().spec.is_enabled(MegaSpecId::REX6) {
-            return self.op.reward_beneficiary(evm, exec_result);
+        let deferral = Self::begin_beneficiary_deferral(evm, exec_result.gas());
+        let distribution = self.hooks.fee_distribution(evm.ctx().spec);
+        let redirection = if distribution.is_default() {
+            None
+        } else {
+            Self::begin_fee_redirection(evm, exec_result.gas(), &distribution, deferral.is_some())?
+        };
+        let result = self.reward_fee_recipients(evm, exec_result);
+        if let Some(redirection) = redirection {
+            Self::end_fee_redirection(evm, redirection)?;
         }
-
-        // REX6: op-revm credits the beneficiary + fee vaults HERE, after `last_frame_result`
-        // finalised the trackers — so these writes escape DataSize / KV / StateGrowth unless
-        // accounted now. Deposit / keyless-sandbox txs credit nothing (op-revm early-returns),
-        // so the diff naturally records nothing for them.
-        let snapshots = Self::snapshot_fee_recipients(evm)?;
-
-        self.op.reward_beneficiary(evm, exec_result)?;
-
-        for snapshot in snapshots {
-            let (balance, now_empty) =
-                Self::fee_recipient_balance_and_emptiness(evm, snapshot.address)?;
-            if balance == snapshot.balance {
-                continue;
-            }
-            // One account-info write = 40 bytes DataSize + 1 KV update; a newly materialised
-            // account also counts as +1 StateGrowth. TX-persistent lane (frames are gone).
-            let mut limit = evm.ctx().additional_limit.borrow_mut();
-            limit.data_size.merge_persistent_usage(ACCOUNT_INFO_WRITE_SIZE);
-            limit.kv_update.merge_persistent_usage(1);
-            if snapshot.was_empty && !now_empty {
-                limit.state_growth.merge_persistent_usage(1);
-            }
+        if let Some(deferral) = deferral {
+            Self::end_beneficiary_deferral(evm, deferral)?;
         }
-
-        Ok(())
+        result
     }
 
     fn last_frame_result(
@@ -964,25 +1581,44 @@ where
         evm: &mut Self::Evm,
         frame_result: &mut <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameResult,
     ) -> Result<(), Self::Error> {
-        let is_mini_rex = evm.ctx().spec.is_enabled(MegaSpecId::MINI_REX);
-        if is_mini_rex {
-            // Update the additional limit before returning the frame result
-            evm.ctx().additional_limit.borrow_mut().before_frame_return_result::<true>(frame_result)
+        // Update the additional limit before returning the frame result
+        self.hooks.finalize_limits(evm.ctx(), frame_result);
+
+        // A first frame that returned from `frame_init` never reached `frame_return_result`.
+        if let Some(call_graph) = evm.ctx().call_graph.as_mut() {
+            if call_graph.has_open_frame() {
+                call_graph.exit(frame_result);
+            }
         }
 
         // Call the inner last_frame_result function first
         // This will finalize gas accounting according to REVM's rules:
         // - Spends all gas_limit
         // - Only refunds remaining gas if is_ok_or_revert()
-        self.op.last_frame_result(evm, frame_result)?;
+        Self::op().last_frame_result(evm, frame_result).map_err(Self::from_op_error)?;
 
         // After REVM's gas accounting, we need to return the rescued gas from additional limits.
-        if is_mini_rex {
-            let ctx = evm.ctx_mut();
+        let rescued_gas = self.hooks.rescued_gas(evm.ctx_ref());
+        if rescued_gas > 0 {
+            frame_result.gas_mut().erase_cost(rescued_gas);
 
-            let additional_limit = ctx.additional_limit.borrow();
-            let gas = frame_result.gas_mut();
-            gas.erase_cost(additional_limit.rescued_gas);
+            // Record the refund if the detained compute gas limit halted the transaction, since
+            // it is settled outside of any frame and thus invisible to inspectors.
+            let ctx = evm.ctx();
+            let access_type = ctx.volatile_data_tracker.borrow().get_volatile_data_info();
+            let halt = access_type.and_then(|access_type| {
+                ctx.additional_limit.borrow().detained_compute_gas_halt_reason(access_type)
+            });
+            if let Some(MegaHaltReason::VolatileDataAccessOutOfGas { access_type, limit, actual }) =
+                halt
+            {
+                ctx.detained_gas_refund = Some(DetainedGasRefund {
+                    access_type,
+                    detained_limit: limit,
+                    compute_gas_used: actual,
+                    refunded_gas: rescued_gas,
+                });
+            }
         }
 
         Ok(())
@@ -993,6 +1629,17 @@ where
         evm: &mut Self::Evm,
         result: <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameResult,
     ) -> Result<ExecutionResult<Self::HaltReason>, Self::Error> {
+        // Export the journal before `commit_tx` below clears it.
+        let ctx = evm.ctx();
+        if ctx.exported_journal.is_some() {
+            ctx.exported_journal = Some(ctx.journaled_state.inner.journal.clone());
+        }
+
+        // Snapshot the usage while no inspector can hold a borrow of the trackers.
+        ctx.tx_limit_usage = ctx.additional_limit.borrow().get_usage();
+        ctx.tx_volatile_data_accessed =
+            ctx.volatile_data_tracker.borrow().get_volatile_data_accessed();
+
         // Capture volatile data info for error reporting
         let volatile_info = evm
             .ctx()
@@ -1010,9 +1657,7 @@ where
         // the real halt reason to distinguish runtime halts (which must merge sandbox state and
         // charge `sandbox_gas_used` against the outer gas counter) from validation-rejects
         // (which still flow through `catch_error` and produce `FailedDeposit`).
-        let result = if evm.ctx().is_inside_sandbox() &&
-            evm.ctx().tx().tx_type() == DEPOSIT_TRANSACTION_TYPE
-        {
+        let result = if self.hooks.bypasses_deposit_halt_conversion(evm.ctx()) {
             match core::mem::replace(evm.ctx().error(), Ok(())) {
                 Err(ContextError::Db(e)) => return Err(e.into()),
                 Err(ContextError::Custom(e)) => return Err(Self::Error::from_string(e)),
@@ -1026,28 +1671,13 @@ where
             evm.frame_stack().clear();
             exec_result
         } else {
-            self.op.execution_result(evm, result)?
+            Self::op().execution_result(evm, result).map_err(Self::from_op_error)?
         };
         Ok(result.map_haltreason(|reason| {
-            let mut additional_limit = evm.ctx().additional_limit.borrow_mut();
-            if additional_limit.is_exceeding_limit_halt(&reason) {
-                if let Some(access_type) = volatile_info {
-                    if let Some(halt) =
-                        additional_limit.detained_compute_gas_halt_reason(access_type)
-                    {
-                        return halt;
-                    }
-                }
-                // normal additional limit exceeded (no volatile data access, or detention
-                // was not more restrictive than the per-tx compute gas limit)
-                additional_limit
-                    .check_limit()
-                    .maybe_halt_reason()
-                    .expect("should have a halt reason")
-            } else {
+            self.hooks
+                .limit_halt_reason(evm.ctx(), &reason, volatile_info)
                 // not due to additional limit exceeded
-                MegaHaltReason::Base(reason)
-            }
+                .unwrap_or(MegaHaltReason::Base(reason))
         }))
     }
 
@@ -1056,14 +1686,36 @@ where
         evm: &mut Self::Evm,
         error: Self::Error,
     ) -> Result<ExecutionResult<Self::HaltReason>, Self::Error> {
-        let result = self.op.catch_error(evm, error)?;
-        Ok(result.map_haltreason(MegaHaltReason::Base))
+        // A transaction rejected before `execution_result` leaves no journal to export.
+        if let Some(journal) = evm.ctx().exported_journal.as_mut() {
+            journal.clear();
+        }
+        // op-revm settles a deposit rejected with a transaction error as a failed deposit and
+        // returns any other error unchanged, so it is handed a stand-in of the same kind.
+        let fails_deposit =
+            error.is_tx_error() && evm.ctx().tx().tx_type() == DEPOSIT_TRANSACTION_TYPE;
+        let stand_in = if error.is_tx_error() {
+            EVMError::Transaction(OpTransactionError::Base(
+                InvalidTransaction::NonceOverflowInTransaction,
+            ))
+        } else {
+            EVMError::Custom(String::new())
+        };
+        let result = Self::op().catch_error(evm, stand_in);
+        Self::restore_nonce_gap(evm);
+        match result {
+            Ok(result) => Ok(result.map_haltreason(MegaHaltReason::Base)),
+            // The failed deposit could not be settled.
+            Err(err) if fails_deposit => Err(Self::from_op_error(err)),
+            Err(_) => Err(error),
+        }
     }
 }
 
-impl<DB, EVM, ERROR, ExtEnvs: ExternalEnvTypes> InspectorHandler
-    for MegaHandler<EVM, ERROR, EthFrame<EthInterpreter>>
+impl<DB, EVM, ERROR, ExtEnvs: ExternalEnvTypes, HOOKS> InspectorHandler
+    for MegaHandler<EVM, ERROR, EthFrame<EthInterpreter>, HOOKS>
 where
+    HOOKS: MegaHandlerHooks,
     DB: Database,
     MegaContext<DB, ExtEnvs>: ContextTr<Journal = Journal<DB>>,
     Journal<DB>: revm::inspector::JournalExt,
@@ -1076,7 +1728,6 @@ where
         >,
     >,
     ERROR: EvmTrError<EVM>
-        + From<OpTransactionError>
         + From<MegaTransactionError>
         + FromStringError
         + IsTxError
@@ -1162,7 +1813,15 @@ where
         &mut self,
         mut frame_init: <Self::Frame as revm::handler::FrameTr>::FrameInit,
     ) -> Result<FrameInitResult<'_, Self::Frame>, ContextDbError<Self::Context>> {
+        if let Some(call_graph) = self.ctx().call_graph.as_mut() {
+            call_graph.enter(&frame_init.frame_input);
+        }
         let is_mini_rex_enabled = self.ctx().spec.is_enabled(MegaSpecId::MINI_REX);
+        // EQUIVALENCE fast path: none of the hooks below apply before `MINI_REX`, so hand the
+        // frame straight to op-revm.
+        if !is_mini_rex_enabled {
+            return self.inner.frame_init(frame_init);
+        }
         let is_rex_enabled = self.ctx().spec.is_enabled(MegaSpecId::REX);
         let is_rex3_enabled = self.ctx().spec.is_enabled(MegaSpecId::REX3);
         let is_rex4_enabled = self.ctx().spec.is_enabled(MegaSpecId::REX4);
@@ -1182,7 +1841,7 @@ where
         // MiniRex: Only CALL triggers oracle access detection. STATICCALL, CALLCODE, and
         //   DELEGATECALL bypass it.
         // Rex: STATICCALL is added to oracle access detection (unifying CALL-like behavior).
-        if is_mini_rex_enabled && !is_rex3_enabled {
+        if !is_rex3_enabled {
             if let FrameInput::Call(call_inputs) = &frame_init.frame_input {
                 let detect_oracle = match call_inputs.scheme {
                     CallScheme::Call => true,
@@ -1195,10 +1854,15 @@ where
                 };
                 // Mega system address is exempted from volatile data access enforcement.
                 if detect_oracle && call_inputs.caller != self.ctx().system_address {
+                    let exempt = self.ctx().detention_exemptions.contains(&call_inputs.caller);
                     let volatile_data_tracker = self.ctx().volatile_data_tracker.clone();
                     let mut tracker = volatile_data_tracker.borrow_mut();
+                    let saved = tracker.get_compute_gas_limit();
                     if tracker.check_and_mark_oracle_access(&call_inputs.target_address) {
-                        if let Some(compute_gas_limit) = tracker.get_compute_gas_limit() {
+                        // A detention-exempt caller records the access without being detained.
+                        if exempt {
+                            tracker.restore_compute_gas_limit(saved);
+                        } else if let Some(compute_gas_limit) = tracker.get_compute_gas_limit() {
                             additional_limit.borrow_mut().set_compute_gas_limit(compute_gas_limit);
                         }
                     }
@@ -1262,30 +1926,38 @@ where
                     // Push an empty frame to keep the limit tracker stack balanced:
                     // `frame_return_result` / `last_frame_result` will pop a frame, but
                     // `after_frame_init` (which normally pushes) was skipped.
-                    if is_mini_rex_enabled {
-                        additional_limit.borrow_mut().push_empty_frame();
-                    }
+                    additional_limit.borrow_mut().push_empty_frame();
                     return Ok(FrameInitResult::Result(result));
                 }
             }
         }
 
-        if is_mini_rex_enabled {
-            if let Some(frame_result) = additional_limit
-                .borrow_mut()
-                .before_frame_init(&mut frame_init, self.ctx().journal_mut())?
-            {
-                return Ok(FrameInitResult::Result(frame_result));
+        if let Some(frame_result) = additional_limit
+            .borrow_mut()
+            .before_frame_init(&mut frame_init, self.ctx().journal_mut())?
+        {
+            return Ok(FrameInitResult::Result(frame_result));
+        }
+
+        // Oracle fast path: serve `getSlot` without running the oracle bytecode. Unlike the
+        // interceptors above, it runs after `before_frame_init`, so the result goes through the
+        // same frame accounting as a precompile call.
+        if self.ctx_ref().oracle_fast_path {
+            if let FrameInput::Call(call_inputs) = &frame_init.frame_input {
+                if let Some(result) =
+                    oracle_get_slot_fast_path(self.ctx(), call_inputs, frame_init.depth)?
+                {
+                    let init_result = FrameInitResult::Result(result);
+                    additional_limit.borrow_mut().after_frame_init(&init_result);
+                    return Ok(init_result);
+                }
             }
         }
 
         // call the inner frame_init function to initialize the frame
         let init_result = self.inner.frame_init(frame_init)?;
 
-        // Apply the additional limits only when the `MINI_REX` spec is enabled.
-        if is_mini_rex_enabled {
-            additional_limit.borrow_mut().after_frame_init(&init_result);
-        }
+        additional_limit.borrow_mut().after_frame_init(&init_result);
 
         Ok(init_result)
     }
@@ -1296,15 +1968,36 @@ where
     fn frame_run(
         &mut self,
     ) -> Result<FrameInitOrResult<Self::Frame>, ContextDbError<Self::Context>> {
+        // EQUIVALENCE fast path: run the frame with op-revm's loop, skipping the limiter hooks.
+        let is_mini_rex_enabled = self.ctx_ref().spec.is_enabled(MegaSpecId::MINI_REX);
+        if !is_mini_rex_enabled && !self.ctx_ref().has_frame_run_instrumentation() {
+            return self.inner.frame_run();
+        }
         let frame = self.inner.frame_stack.get();
         let context = &mut self.inner.ctx;
         let instructions = &mut self.inner.instruction;
 
+        // The same loop, with the journal length sampled before the checkpoint is settled and the
+        // instructions timed if profiled.
+        if !is_mini_rex_enabled {
+            let action = Self::run_frame_instructions(context, frame, instructions);
+            let journal_len = context.journaled_state.inner.journal.len();
+            let frame_output = frame
+                .process_next_action::<_, ContextDbError<Self::Context>>(context, action)
+                .inspect(|i| {
+                    if i.is_result() {
+                        frame.set_finished(true);
+                    }
+                })?;
+            Self::record_journal_stats(context, frame, journal_len, &frame_output);
+            return Ok(frame_output);
+        }
+
         // Before frame_run Hook
         let mut action = if let Some(action) = Self::before_frame_run(context, frame)? {
             action
         } else {
-            frame.interpreter.run_plain(instructions.instruction_table(), context)
+            Self::run_frame_instructions(context, frame, instructions)
         };
 
         // After frame_run instructions Hook
@@ -1320,6 +2013,7 @@ where
 
         // Process the frame action, it may need to create a new frame or return the current frame
         // result.
+        let journal_len = context.journaled_state.inner.journal.len();
         let mut frame_output = frame
             .process_next_action::<_, ContextDbError<Self::Context>>(context, action)
             .inspect(|i| {
@@ -1327,6 +2021,7 @@ where
                     frame.set_finished(true);
                 }
             })?;
+        Self::record_journal_stats(context, frame, journal_len, &frame_output);
 
         // After frame_run Hook
         Self::after_frame_run(context, &mut frame_output, gas_remaining_before)?;
@@ -1341,13 +2036,20 @@ where
         Option<<Self::Frame as revm::handler::FrameTr>::FrameResult>,
         ContextDbError<Self::Context>,
     > {
-        let ctx = self.ctx_ref();
-        let is_mini_rex = ctx.spec.is_enabled(MegaSpecId::MINI_REX);
-        // Apply the additional limits only when the `MINI_REX` spec is enabled.
-        if is_mini_rex {
-            // call the `on_frame_return` function to update the `AdditionalLimit` if the limit is
-            // exceeded, return the error frame result
-            ctx.additional_limit.borrow_mut().before_frame_return_result::<false>(&mut result);
+        let ctx = self.ctx();
+        // EQUIVALENCE fast path: the additional limits and volatile data access tracking only
+        // apply from `MINI_REX` on.
+        if !ctx.spec.is_enabled(MegaSpecId::MINI_REX) {
+            if let Some(call_graph) = ctx.call_graph.as_mut() {
+                call_graph.exit(&result);
+            }
+            return self.inner.frame_return_result(result);
+        }
+        // call the `on_frame_return` function to update the `AdditionalLimit` if the limit is
+        // exceeded, return the error frame result
+        ctx.additional_limit.borrow_mut().before_frame_return_result::<false>(&mut result);
+        if let Some(call_graph) = ctx.call_graph.as_mut() {
+            call_graph.exit(&result);
         }
 
         // Call the inner frame_return_result function to return the frame result.
@@ -1420,6 +2122,10 @@ where
 
         // Check if inspector wants to skip this call/create
         if let Some(mut output) = frame_start(ctx, inspector, &mut frame_init.frame_input) {
+            // `frame_init` is skipped, so the frame is opened in the call graph here.
+            if let Some(call_graph) = ctx.call_graph.as_mut() {
+                call_graph.enter(&frame_init.frame_input);
+            }
             // Inspector intercepted — `frame_init()` is skipped entirely, so neither
             // `frame_result_if_exceeding_limit` nor `before_frame_init` would run.
             //
@@ -1516,6 +2222,7 @@ where
 
         // Process the frame action, it may need to create a new frame or return the current frame
         // result.
+        let journal_len = ctx.journaled_state.inner.journal.len();
         let mut frame_output = frame
             .process_next_action::<_, ContextDbError<Self::Context>>(ctx, action)
             .inspect(|i| {
@@ -1523,6 +2230,7 @@ where
                     frame.set_finished(true);
                 }
             })?;
+        Self::record_journal_stats(ctx, frame, journal_len, &frame_output);
 
         // After frame_run Hook
         Self::after_frame_run(ctx, &mut frame_output, gas_remaining_before)?;
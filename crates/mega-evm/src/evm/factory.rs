@@ -3,8 +3,9 @@ use op_revm::L1BlockInfo;
 use revm::{context::result::EVMError, Inspector};
 
 use crate::{
-    DynPrecompilesBuilder, EmptyExternalEnv, EvmTxRuntimeLimits, ExternalEnvFactory, MegaContext,
-    MegaEvm, MegaHaltReason, MegaSpecId, MegaTransaction, MegaTransactionError,
+    DetentionExemptions, DynPrecompilesBuilder, EmptyExternalEnv, EvmTxRuntimeLimits,
+    ExternalEnvFactory, MegaChainConfig, MegaContext, MegaEvm, MegaHaltReason, MegaSpecId,
+    MegaTransaction, MegaTransactionError,
 };
 
 /// Factory for creating `MegaETH` EVM instances.
@@ -15,8 +16,10 @@ use crate::{
 ///
 /// # Type Parameters
 ///
-/// - `Oracle`: The `external_envs` service to provide deterministic external information during EVM
-///   execution. Must implement [`ExternalEnvs`] and [`Clone`] traits.
+/// - `ExtEnvFactory`: The [`ExternalEnvFactory`] providing the SALT and oracle environments of each
+///   block. The [`alloy_evm::EvmFactory`] implementation is generic over it, so callers that only
+///   know the factory through trait bounds (e.g. reth's `ConfigureEvm`) can instantiate [`MegaEvm`]
+///   with production providers.
 ///
 /// # Usage
 ///
@@ -48,6 +51,12 @@ pub struct MegaEvmFactory<ExtEnvFactory> {
     /// A builder function to build dynamic precompiles for the EVM.
     #[debug(ignore)]
     dyn_precompiles_builder: Option<DynPrecompilesBuilder>,
+
+    /// The chain configuration applied to the created EVMs, if any.
+    chain_config: Option<MegaChainConfig>,
+
+    /// The contracts exempt from compute gas detention in the created EVMs.
+    detention_exemptions: DetentionExemptions,
 }
 
 impl Default for MegaEvmFactory<EmptyExternalEnv> {
@@ -73,11 +82,27 @@ impl MegaEvmFactory<EmptyExternalEnv> {
     ///
     /// A new `EvmFactory` instance configured with the provided `external_envs`.
     pub fn new() -> Self {
-        Self { external_env_factory: EmptyExternalEnv, dyn_precompiles_builder: None }
+        Self {
+            external_env_factory: EmptyExternalEnv,
+            dyn_precompiles_builder: None,
+            chain_config: None,
+            detention_exemptions: DetentionExemptions::default(),
+        }
     }
 }
 
 impl<ExtEnvFactory> MegaEvmFactory<ExtEnvFactory> {
+    /// Creates a new factory drawing the external environments of each block from
+    /// `external_env_factory`.
+    pub fn with_external_envs(external_env_factory: ExtEnvFactory) -> Self {
+        Self {
+            external_env_factory,
+            dyn_precompiles_builder: None,
+            chain_config: None,
+            detention_exemptions: DetentionExemptions::default(),
+        }
+    }
+
     /// Sets the builder function to build dynamic precompiles for the EVM.
     pub fn with_dyn_precompiles_builder(
         mut self,
@@ -87,6 +112,30 @@ impl<ExtEnvFactory> MegaEvmFactory<ExtEnvFactory> {
         self
     }
 
+    /// Sets the chain configuration applied to the created EVMs. Their chain id stays the one of
+    /// the [`EvmEnv`] they are created with, see [`MegaContext::with_chain_config`].
+    pub fn with_chain_config(mut self, chain_config: MegaChainConfig) -> Self {
+        self.chain_config = Some(chain_config);
+        self
+    }
+
+    /// Returns the chain configuration applied to the created EVMs, if any.
+    pub fn chain_config(&self) -> Option<&MegaChainConfig> {
+        self.chain_config.as_ref()
+    }
+
+    /// Sets the contracts exempt from compute gas detention in the created EVMs. See
+    /// [`MegaContext::with_detention_exemptions`].
+    pub fn with_detention_exemptions(mut self, exemptions: DetentionExemptions) -> Self {
+        self.detention_exemptions = exemptions;
+        self
+    }
+
+    /// Returns the contracts exempt from compute gas detention in the created EVMs.
+    pub fn detention_exemptions(&self) -> &DetentionExemptions {
+        &self.detention_exemptions
+    }
+
     /// Returns a reference to the external environment factory.
     ///
     /// This is useful for inspecting or cloning the factory after construction,
@@ -111,13 +160,13 @@ impl<ExtEnvFactory> MegaEvmFactory<ExtEnvFactory> {
         MegaEvmFactory {
             external_env_factory,
             dyn_precompiles_builder: self.dyn_precompiles_builder,
+            chain_config: self.chain_config,
+            detention_exemptions: self.detention_exemptions,
         }
     }
 }
 
-impl<ExtEnvFactory: ExternalEnvFactory + Clone> alloy_evm::EvmFactory
-    for MegaEvmFactory<ExtEnvFactory>
-{
+impl<ExtEnvFactory: ExternalEnvFactory> alloy_evm::EvmFactory for MegaEvmFactory<ExtEnvFactory> {
     type Evm<DB: Database, I: Inspector<Self::Context<DB>>> =
         MegaEvm<DB, I, ExtEnvFactory::EnvTypes>;
     type Context<DB: Database> = MegaContext<DB, ExtEnvFactory::EnvTypes>;
@@ -151,13 +200,18 @@ impl<ExtEnvFactory: ExternalEnvFactory + Clone> alloy_evm::EvmFactory
         let spec_id = *evm_env.spec_id();
         let block_number = evm_env.block_env.number.to();
         let runtime_limits = EvmTxRuntimeLimits::from_spec(spec_id);
-        let ctx = MegaContext::new(db, spec_id)
+        let mut ctx = MegaContext::new(db, spec_id)
             .with_external_envs(self.external_env_factory.external_envs(block_number))
+            .with_extra_envs(self.external_env_factory.extra_envs(block_number))
             .with_tx(MegaTransaction::default())
             .with_block(evm_env.block_env)
             .with_cfg(evm_env.cfg_env)
             .with_chain(L1BlockInfo::default())
-            .with_tx_runtime_limits(runtime_limits);
+            .with_tx_runtime_limits(runtime_limits)
+            .with_detention_exemptions(self.detention_exemptions.clone());
+        if let Some(chain_config) = self.chain_config {
+            ctx = ctx.with_chain_config(chain_config);
+        }
         MegaEvm::new(ctx).with_dyn_precompiles(
             self.dyn_precompiles_builder
                 .as_ref()
@@ -177,7 +231,80 @@ impl<ExtEnvFactory: ExternalEnvFactory + Clone> alloy_evm::EvmFactory
 
 #[cfg(test)]
 mod tests {
+    use core::cell::RefCell;
+
+    use alloy_evm::EvmFactory;
+    use alloy_primitives::{BlockNumber, U256};
+    use revm::{context::BlockEnv, database::EmptyDB, handler::EvmTr};
+
     use super::*;
+    use crate::ExternalEnvs;
+
+    /// A provider that records the blocks it serves. It is deliberately not `Clone`.
+    #[derive(Debug, Default)]
+    struct RecordingEnvFactory {
+        blocks: RefCell<Vec<BlockNumber>>,
+    }
+
+    impl ExternalEnvFactory for RecordingEnvFactory {
+        type EnvTypes = EmptyExternalEnv;
+
+        fn external_envs(&self, block: BlockNumber) -> ExternalEnvs<Self::EnvTypes> {
+            self.blocks.borrow_mut().push(block);
+            EmptyExternalEnv.external_envs(block)
+        }
+    }
+
+    /// Creates an EVM knowing the factory only through the `EvmFactory` trait.
+    fn create_via_trait<F: EvmFactory<Spec = MegaSpecId>>(
+        factory: &F,
+        evm_env: EvmEnv<MegaSpecId>,
+    ) -> F::Evm<EmptyDB, revm::inspector::NoOpInspector> {
+        factory.create_evm(EmptyDB::default(), evm_env)
+    }
+
+    #[test]
+    fn test_evm_factory_trait_uses_external_env_factory() {
+        let factory = MegaEvmFactory::with_external_envs(RecordingEnvFactory::default());
+        let mut cfg_env = revm::context::CfgEnv::default();
+        cfg_env.spec = MegaSpecId::REX5;
+        let block_env = BlockEnv { number: U256::from(42), ..Default::default() };
+        let evm_env = EvmEnv::new(cfg_env, block_env);
+
+        let mut evm = create_via_trait(&factory, evm_env);
+
+        assert_eq!(*factory.external_env_factory().blocks.borrow(), [42]);
+        assert_eq!(evm.ctx().spec, MegaSpecId::REX5);
+    }
+
+    #[test]
+    fn test_evm_factory_registers_extra_envs() {
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        struct PriceFeed(u64);
+
+        let factory = MegaEvmFactory::with_external_envs((EmptyExternalEnv, PriceFeed(42)));
+        let mut evm = factory.create_evm(EmptyDB::default(), EvmEnv::default());
+
+        assert_eq!(evm.ctx().extra_env::<PriceFeed>(), Some(&PriceFeed(42)));
+        assert_eq!(evm.ctx().extra_envs().len(), 1);
+    }
+
+    #[test]
+    fn test_evm_factory_applies_chain_config() {
+        let factory = MegaEvmFactory::new()
+            .with_chain_config(MegaChainConfig::new(6342).without_operator_fee());
+        let mut evm_env = EvmEnv::default();
+        evm_env.cfg_env.chain_id = 6342;
+        let mut evm = factory.create_evm(EmptyDB::default(), evm_env);
+
+        assert_eq!(evm.ctx().cfg.chain_id, 6342);
+        assert_eq!(evm.ctx().chain.operator_fee_scalar, Some(U256::ZERO));
+        assert_eq!(evm.ctx().chain.operator_fee_constant, Some(U256::ZERO));
+
+        // A configuration for another chain does not override the chain id of the `EvmEnv`.
+        let mut evm = factory.create_evm(EmptyDB::default(), EvmEnv::default());
+        assert_eq!(evm.ctx().cfg.chain_id, 1);
+    }
 
     #[test]
     fn test_external_env_factory_getter() {
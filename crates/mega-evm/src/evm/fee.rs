@@ -0,0 +1,220 @@
+//! Fees paid by a transaction, broken down by recipient.
+
+use alloy_primitives::U256;
+use op_revm::L1BlockInfo;
+use revm::context::Transaction;
+use serde::{Deserialize, Serialize};
+
+use crate::{is_deposit_like_transaction, MegaChainConfig, MegaSpecId, MegaTransaction};
+
+/// The denominator of the operator fee scalar.
+const OPERATOR_FEE_SCALAR_DECIMALS: u64 = 1_000_000;
+
+/// The fees a transaction pays, as credited to each fee recipient.
+///
+/// Computed by [`fee_breakdown`]. Deposit and mega system transactions pay no fee.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeBreakdown {
+    /// The gas the fees are computed for.
+    pub gas_used: u64,
+    /// The base fee per gas times the gas used, credited to the base fee vault.
+    pub base_fee: U256,
+    /// The priority fee per gas times the gas used, credited to the block beneficiary.
+    pub priority_fee: U256,
+    /// The operator fee, credited to the operator fee vault.
+    pub operator_fee: U256,
+    /// The L1 data fee, credited to the L1 fee vault.
+    pub l1_data_fee: U256,
+}
+
+impl FeeBreakdown {
+    /// Returns the L2 execution fee: the effective gas price times the gas used.
+    pub fn l2_execution_fee(&self) -> U256 {
+        self.base_fee.saturating_add(self.priority_fee)
+    }
+
+    /// Returns the total fee credited to the fee recipients.
+    pub fn total(&self) -> U256 {
+        self.l2_execution_fee().saturating_add(self.operator_fee).saturating_add(self.l1_data_fee)
+    }
+}
+
+/// Computes the fees `tx` pays if it uses `gas_used` gas in a block with the given `base_fee`.
+///
+/// The operator fee parameters are taken from `chain_config` when set and from `l1_block_info`
+/// otherwise; the L1 data fee is computed from `l1_block_info` and the EIP-2718 encoding of `tx`
+/// (zero if it is not set). Passing the gas limit of `tx` as `gas_used` gives the maximum fees.
+pub fn fee_breakdown(
+    tx: &MegaTransaction,
+    gas_used: u64,
+    base_fee: u64,
+    spec: MegaSpecId,
+    chain_config: &MegaChainConfig,
+    l1_block_info: &L1BlockInfo,
+) -> FeeBreakdown {
+    if is_deposit_like_transaction(tx, chain_config.system_address) {
+        return FeeBreakdown { gas_used, ..Default::default() };
+    }
+
+    let gas = U256::from(gas_used);
+    let base_fee_per_gas = base_fee as u128;
+    let priority_fee_per_gas =
+        tx.effective_gas_price(base_fee_per_gas).saturating_sub(base_fee_per_gas);
+
+    let l1_data_fee = match tx.enveloped_tx.as_deref() {
+        Some(enveloped) if !enveloped.is_empty() => {
+            let mut l1_block_info = l1_block_info.clone();
+            l1_block_info.clear_tx_l1_cost();
+            l1_block_info.calculate_tx_l1_cost(enveloped, spec.into_op_spec())
+        }
+        _ => U256::ZERO,
+    };
+
+    FeeBreakdown {
+        gas_used,
+        base_fee: U256::from(base_fee_per_gas) * gas,
+        priority_fee: U256::from(priority_fee_per_gas) * gas,
+        operator_fee: operator_fee(gas_used, chain_config, l1_block_info),
+        l1_data_fee,
+    }
+}
+
+/// Computes the operator fee credited to the operator fee vault for `gas_used`.
+fn operator_fee(
+    gas_used: u64,
+    chain_config: &MegaChainConfig,
+    l1_block_info: &L1BlockInfo,
+) -> U256 {
+    let scalar =
+        chain_config.operator_fee_scalar.or(l1_block_info.operator_fee_scalar).unwrap_or_default();
+    let constant = chain_config
+        .operator_fee_constant
+        .or(l1_block_info.operator_fee_constant)
+        .unwrap_or_default();
+    (U256::from(gas_used).saturating_mul(scalar) / U256::from(OPERATOR_FEE_SCALAR_DECIMALS))
+        .saturating_add(constant)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{BytecodeBuilder, MemoryDatabase},
+        MegaContext, MegaEvm,
+    };
+    use alloy_primitives::{address, Address, Bytes, TxKind};
+    use op_revm::constants::{BASE_FEE_RECIPIENT, L1_FEE_RECIPIENT, OPERATOR_FEE_RECIPIENT};
+    use revm::{
+        bytecode::opcode::{PUSH0, SSTORE},
+        context::{BlockEnv, ContextTr, TxEnv},
+        handler::EvmTr,
+        ExecuteEvm,
+    };
+
+    const CALLER: Address = address!("4000000000000000000000000000000000000001");
+    const CALLEE: Address = address!("5000000000000000000000000000000000000001");
+    const BENEFICIARY: Address = address!("6000000000000000000000000000000000000001");
+    const BASE_FEE: u64 = 1_000;
+
+    fn l1_block_info() -> L1BlockInfo {
+        L1BlockInfo {
+            l1_base_fee: U256::from(30_000_000_000u64),
+            l1_base_fee_scalar: U256::from(1_368),
+            l1_blob_base_fee: Some(U256::from(1_000_000)),
+            l1_blob_base_fee_scalar: Some(U256::from(810_949)),
+            operator_fee_scalar: Some(U256::from(1_000_000_000)),
+            operator_fee_constant: Some(U256::from(7)),
+            ..Default::default()
+        }
+    }
+
+    fn mega_tx(gas_price: u128, enveloped_tx: Bytes) -> MegaTransaction {
+        let mut tx = MegaTransaction::new(TxEnv {
+            caller: CALLER,
+            gas_limit: 1_000_000,
+            gas_price,
+            kind: TxKind::Call(CALLEE),
+            ..Default::default()
+        });
+        tx.enveloped_tx = Some(enveloped_tx);
+        tx
+    }
+
+    #[test]
+    fn test_fee_breakdown_matches_vault_credits() {
+        let chain_config =
+            MegaChainConfig::default().with_operator_fee(U256::from(250), U256::ZERO);
+        let tx = mega_tx(BASE_FEE as u128 + 3, Bytes::from_static(&[0x01; 200]));
+
+        let code =
+            BytecodeBuilder::default().push_number(1_u8).append(PUSH0).append(SSTORE).build();
+        let db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(10).pow(U256::from(18)))
+            .account_code(CALLEE, code);
+        let context = MegaContext::new(db, MegaSpecId::REX4)
+            .with_block(BlockEnv {
+                basefee: BASE_FEE,
+                beneficiary: BENEFICIARY,
+                ..Default::default()
+            })
+            .with_chain(l1_block_info())
+            .with_chain_config(chain_config);
+        let mut evm = MegaEvm::new(context);
+        let outcome = evm.transact(tx.clone()).unwrap();
+        assert!(outcome.result.is_success());
+
+        let fees = fee_breakdown(
+            &tx,
+            outcome.result.gas_used(),
+            BASE_FEE,
+            MegaSpecId::REX4,
+            &chain_config,
+            evm.ctx_ref().chain(),
+        );
+        let credit = |address| outcome.state.get(&address).map(|a| a.info.balance);
+        assert_eq!(credit(BASE_FEE_RECIPIENT), Some(fees.base_fee));
+        assert_eq!(credit(BENEFICIARY), Some(fees.priority_fee));
+        assert_eq!(credit(OPERATOR_FEE_RECIPIENT), Some(fees.operator_fee));
+        assert_eq!(credit(L1_FEE_RECIPIENT), Some(fees.l1_data_fee));
+        assert!(!fees.l1_data_fee.is_zero());
+        assert_eq!(fees.priority_fee, U256::from(3 * fees.gas_used));
+    }
+
+    #[test]
+    fn test_chain_config_operator_fee_takes_precedence() {
+        let tx = mega_tx(BASE_FEE as u128, Bytes::new());
+        let from_l1_block = fee_breakdown(
+            &tx,
+            21_000,
+            BASE_FEE,
+            MegaSpecId::REX4,
+            &MegaChainConfig::default(),
+            &l1_block_info(),
+        );
+        assert_eq!(from_l1_block.operator_fee, U256::from(21_000 * 1_000 + 7));
+        assert_eq!(from_l1_block.l1_data_fee, U256::ZERO);
+        assert_eq!(from_l1_block.l2_execution_fee(), U256::from(21_000 * BASE_FEE));
+
+        let configured = fee_breakdown(
+            &tx,
+            21_000,
+            BASE_FEE,
+            MegaSpecId::REX4,
+            &MegaChainConfig::default().without_operator_fee(),
+            &l1_block_info(),
+        );
+        assert_eq!(configured.operator_fee, U256::ZERO);
+        assert_eq!(configured.total(), configured.l2_execution_fee());
+    }
+
+    #[test]
+    fn test_system_transactions_pay_no_fee() {
+        let chain_config = MegaChainConfig::default().with_system_address(CALLER);
+        let mut tx = mega_tx(BASE_FEE as u128, Bytes::new());
+        tx.base.kind = TxKind::Call(crate::ORACLE_CONTRACT_ADDRESS);
+        let fees =
+            fee_breakdown(&tx, 21_000, BASE_FEE, MegaSpecId::REX4, &chain_config, &l1_block_info());
+        assert_eq!(fees, FeeBreakdown { gas_used: 21_000, ..Default::default() });
+    }
+}
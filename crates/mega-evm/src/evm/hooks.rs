@@ -0,0 +1,878 @@
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::vec::Vec;
+
+use alloy_evm::Database;
+use alloy_primitives::Address;
+use op_revm::{transaction::deposit::DEPOSIT_TRANSACTION_TYPE, OpHaltReason};
+use revm::{
+    context::{ContextTr, Transaction},
+    handler::FrameResult,
+};
+
+use crate::{
+    constants, ExternalEnvTypes, MegaContext, MegaHaltReason, MegaSpecId, MegaTxType,
+    VolatileDataAccess, ACCESS_CONTROL_ADDRESS, HIGH_PRECISION_TIMESTAMP_ORACLE_ADDRESS,
+    KEYLESS_DEPLOY_ADDRESS, LIMIT_CONTROL_ADDRESS, ORACLE_CONTRACT_ADDRESS,
+    SEQUENCER_REGISTRY_ADDRESS,
+};
+
+/// Extension points of [`MegaHandler`](crate::MegaHandler).
+///
+/// Every hook has a default implementation with the `MegaETH` behavior, so an implementor only
+/// overrides the policies it wants to change. [`DefaultMegaHandlerHooks`] overrides nothing and is
+/// what [`MegaHandler::new`](crate::MegaHandler::new) uses.
+///
+/// Hooks change consensus behavior: a handler with custom hooks is meant for research and
+/// simulation, not for executing blocks that must match the network.
+pub trait MegaHandlerHooks {
+    /// Finalizes the additional limits when the top-level frame returns, before op-revm settles
+    /// the gas of the transaction.
+    ///
+    /// By default, from `MINI_REX`, pops the limit trackers' top-level frame and latches any
+    /// exceeded limit.
+    fn finalize_limits<DB: Database, ExtEnvs: ExternalEnvTypes>(
+        &self,
+        ctx: &mut MegaContext<DB, ExtEnvs>,
+        frame_result: &mut FrameResult,
+    ) {
+        if ctx.spec.is_enabled(MegaSpecId::MINI_REX) {
+            ctx.additional_limit.borrow_mut().before_frame_return_result::<true>(frame_result);
+        }
+    }
+
+    /// Returns the gas refunded to the sender on top of op-revm's gas settlement.
+    ///
+    /// By default, from `MINI_REX`, this is the gas rescued when a TX-level additional limit
+    /// halted the transaction (including a detained compute gas limit), which op-revm would
+    /// otherwise consume.
+    fn rescued_gas<DB: Database, ExtEnvs: ExternalEnvTypes>(
+        &self,
+        ctx: &MegaContext<DB, ExtEnvs>,
+    ) -> u64 {
+        if ctx.spec.is_enabled(MegaSpecId::MINI_REX) {
+            ctx.additional_limit.borrow().rescued_gas
+        } else {
+            0
+        }
+    }
+
+    /// Classifies a halt as an additional-limit halt, returning the `MegaETH` halt reason, or
+    /// `None` to report the halt reason unchanged.
+    ///
+    /// `volatile_access` is the volatile data accessed by the transaction, if any (`MINI_REX`+).
+    /// By default, an `OutOfGas` halt with an exceeded limit reports the detained compute gas
+    /// limit when detention was the binding constraint, and the exceeded limit otherwise.
+    fn limit_halt_reason<DB: Database, ExtEnvs: ExternalEnvTypes>(
+        &self,
+        ctx: &mut MegaContext<DB, ExtEnvs>,
+        reason: &OpHaltReason,
+        volatile_access: Option<VolatileDataAccess>,
+    ) -> Option<MegaHaltReason> {
+        let mut additional_limit = ctx.additional_limit.borrow_mut();
+        if !additional_limit.is_exceeding_limit_halt(reason) {
+            return None;
+        }
+        if let Some(access_type) = volatile_access {
+            if let Some(halt) = additional_limit.detained_compute_gas_halt_reason(access_type) {
+                return Some(halt);
+            }
+        }
+        // normal additional limit exceeded (no volatile data access, or detention was not more
+        // restrictive than the per-tx compute gas limit)
+        Some(additional_limit.check_limit().maybe_halt_reason().expect("should have a halt reason"))
+    }
+
+    /// Returns `true` if the transaction's halts bypass op-revm's deposit halt conversion.
+    ///
+    /// op-revm reports a halted deposit as `FailedDeposit` consuming its whole gas limit. By
+    /// default, deposit-style transactions executed inside a keyless-deploy sandbox bypass it, so
+    /// the sandbox sees the actual halt reason and gas used.
+    fn bypasses_deposit_halt_conversion<DB: Database, ExtEnvs: ExternalEnvTypes>(
+        &self,
+        ctx: &mut MegaContext<DB, ExtEnvs>,
+    ) -> bool {
+        ctx.is_inside_sandbox() && ctx.tx().tx_type() == DEPOSIT_TRANSACTION_TYPE
+    }
+
+    /// Checks the delegation target of each EIP-7702 authorization of the transaction that will
+    /// be applied, during validation and before the sender is charged. An error rejects the whole
+    /// transaction. Authorizations skipped for their signature, chain id or nonce are not checked.
+    ///
+    /// By default, every target is allowed. [`DelegationPolicy`] restricts the targets.
+    fn check_delegation_target(&self, target: Address) -> Result<(), DelegationTargetDenied> {
+        let _ = target;
+        Ok(())
+    }
+
+    /// Returns `true` if [`Self::check_delegation_target`] may deny a target. The handler only
+    /// scans the authorizations of a transaction for the check when it does.
+    ///
+    /// By default, `false`. An implementor overriding `check_delegation_target` must return `true`.
+    fn restricts_delegation(&self) -> bool {
+        false
+    }
+
+    /// Returns where the fees of a transaction executed under `spec` are credited.
+    ///
+    /// By default, every fee goes where op-revm credits it. [`FeeDistribution`] redirects them.
+    fn fee_distribution(&self, spec: MegaSpecId) -> FeeDistribution {
+        let _ = spec;
+        FeeDistribution::default()
+    }
+
+    /// Returns the maximum refund quotient of a transaction executed under `spec`: the gas
+    /// refunded is at most the gas spent divided by it. `None` disables refunds.
+    ///
+    /// By default, this is the EIP-3529 quotient of 5. [`RefundCap`] changes it.
+    fn max_refund_quotient(&self, spec: MegaSpecId) -> Option<u64> {
+        let _ = spec;
+        Some(constants::equivalence::MAX_REFUND_QUOTIENT)
+    }
+
+    /// Returns the accounts warmed before a transaction executed under `spec` runs, on top of the
+    /// precompiles and its access list.
+    ///
+    /// By default, only the block beneficiary (EIP-3651, active in every spec). [`PrewarmPolicy`]
+    /// changes it.
+    fn prewarm_policy(&self, spec: MegaSpecId) -> PrewarmPolicy {
+        let _ = spec;
+        PrewarmPolicy::default()
+    }
+
+    /// Returns the minimum gas charged to a transaction executed under `spec`, per transaction
+    /// type, enforced as a raised EIP-7623 floor.
+    ///
+    /// By default, there is none beyond the EIP-7623 floor. [`TxGasFloors`] sets them.
+    fn tx_gas_floors(&self, spec: MegaSpecId) -> TxGasFloors {
+        let _ = spec;
+        TxGasFloors::default()
+    }
+}
+
+/// The [`MegaHandlerHooks`] with the `MegaETH` behavior.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultMegaHandlerHooks;
+
+impl MegaHandlerHooks for DefaultMegaHandlerHooks {}
+
+/// The error returned when an EIP-7702 authorization delegates to a target the
+/// [`MegaHandlerHooks`] do not allow.
+///
+/// The handler rejects the transaction with
+/// [`MegaTransactionError::DelegationTargetDenied`](crate::MegaTransactionError::DelegationTargetDenied).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("EIP-7702 delegation to {target} is not allowed")]
+pub struct DelegationTargetDenied {
+    /// The denied delegation target.
+    pub target: Address,
+}
+
+/// [`MegaHandlerHooks`] restricting the targets EIP-7702 authorizations may delegate to, with the
+/// `MegaETH` behavior otherwise.
+///
+/// Clearing a delegation (delegating to the zero address) is always allowed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DelegationPolicy {
+    /// Only the listed targets are allowed.
+    Allow(Vec<Address>),
+    /// Every target but the listed ones is allowed.
+    Deny(Vec<Address>),
+}
+
+impl DelegationPolicy {
+    /// Denies delegating to the `MegaETH` system contracts, whose code relies on being called at
+    /// their own address.
+    pub fn deny_system_contracts() -> Self {
+        Self::Deny(Vec::from([
+            ORACLE_CONTRACT_ADDRESS,
+            HIGH_PRECISION_TIMESTAMP_ORACLE_ADDRESS,
+            KEYLESS_DEPLOY_ADDRESS,
+            ACCESS_CONTROL_ADDRESS,
+            LIMIT_CONTROL_ADDRESS,
+            SEQUENCER_REGISTRY_ADDRESS,
+        ]))
+    }
+
+    /// Returns whether an authorization may delegate to `target`.
+    pub fn allows(&self, target: Address) -> bool {
+        target.is_zero() ||
+            match self {
+                Self::Allow(targets) => targets.contains(&target),
+                Self::Deny(targets) => !targets.contains(&target),
+            }
+    }
+}
+
+impl MegaHandlerHooks for DelegationPolicy {
+    fn check_delegation_target(&self, target: Address) -> Result<(), DelegationTargetDenied> {
+        if self.allows(target) {
+            Ok(())
+        } else {
+            Err(DelegationTargetDenied { target })
+        }
+    }
+
+    fn restricts_delegation(&self) -> bool {
+        true
+    }
+}
+
+/// Where a fee is credited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FeeRecipient {
+    /// Where op-revm credits the fee: the block beneficiary for the priority fee, the fee vaults
+    /// for the others.
+    #[default]
+    Default,
+    /// The given account.
+    Address(Address),
+    /// No account: the fee is burnt.
+    Burn,
+}
+
+/// [`MegaHandlerHooks`] redirecting the fees paid by transactions, e.g. for devnets that credit
+/// fees to a single account or burn the base fee, with the `MegaETH` behavior otherwise.
+///
+/// op-revm first credits the fees as usual and the redirected amounts are then moved out of the
+/// default recipient, so the sender pays exactly the same. Deposit transactions pay no fee, and a
+/// deferred beneficiary reward (see
+/// [`MegaContext::defer_beneficiary_reward`](crate::MegaContext::defer_beneficiary_reward)) is
+/// left to the ledger. The L1 data fee always goes to the L1 fee vault.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeDistribution {
+    /// The recipient of the priority fee.
+    pub priority_fee: FeeRecipient,
+    /// The recipient of the base fee.
+    pub base_fee: FeeRecipient,
+    /// The recipient of the operator fee.
+    pub operator_fee: FeeRecipient,
+    /// The first spec the distribution applies to. Transactions of earlier specs keep the
+    /// default distribution.
+    pub activation: MegaSpecId,
+}
+
+impl Default for FeeDistribution {
+    fn default() -> Self {
+        Self {
+            priority_fee: FeeRecipient::Default,
+            base_fee: FeeRecipient::Default,
+            operator_fee: FeeRecipient::Default,
+            activation: MegaSpecId::EQUIVALENCE,
+        }
+    }
+}
+
+impl FeeDistribution {
+    /// Credits every fee to `recipient`, from `EQUIVALENCE` on.
+    pub fn all_to(recipient: Address) -> Self {
+        let recipient = FeeRecipient::Address(recipient);
+        Self {
+            priority_fee: recipient,
+            base_fee: recipient,
+            operator_fee: recipient,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the first spec the distribution applies to.
+    pub fn with_activation(mut self, activation: MegaSpecId) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    /// Returns whether every fee goes where op-revm credits it.
+    pub fn is_default(&self) -> bool {
+        self.priority_fee == FeeRecipient::Default &&
+            self.base_fee == FeeRecipient::Default &&
+            self.operator_fee == FeeRecipient::Default
+    }
+}
+
+impl MegaHandlerHooks for FeeDistribution {
+    fn fee_distribution(&self, spec: MegaSpecId) -> FeeDistribution {
+        if spec.is_enabled(self.activation) {
+            *self
+        } else {
+            Self::default()
+        }
+    }
+}
+
+/// [`MegaHandlerHooks`] changing the cap on the gas refunded to a transaction, e.g. to study a
+/// network without refunds, with the `MegaETH` behavior otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefundCap {
+    /// The maximum refund quotient, or `None` to refund nothing.
+    pub max_refund_quotient: Option<u64>,
+    /// The first spec the cap applies to. Transactions of earlier specs keep the EIP-3529 cap.
+    pub activation: MegaSpecId,
+}
+
+impl RefundCap {
+    /// Caps the refund at the gas spent divided by `quotient`, from `EQUIVALENCE` on.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `quotient` is zero; use [`RefundCap::disabled`] to refund nothing.
+    pub fn quotient(quotient: u64) -> Self {
+        assert!(quotient > 0, "the refund quotient must be positive");
+        Self { max_refund_quotient: Some(quotient), activation: MegaSpecId::EQUIVALENCE }
+    }
+
+    /// Refunds nothing, from `EQUIVALENCE` on.
+    pub fn disabled() -> Self {
+        Self { max_refund_quotient: None, activation: MegaSpecId::EQUIVALENCE }
+    }
+
+    /// Sets the first spec the cap applies to.
+    pub fn with_activation(mut self, activation: MegaSpecId) -> Self {
+        self.activation = activation;
+        self
+    }
+}
+
+impl MegaHandlerHooks for RefundCap {
+    fn max_refund_quotient(&self, spec: MegaSpecId) -> Option<u64> {
+        if spec.is_enabled(self.activation) {
+            self.max_refund_quotient
+        } else {
+            DefaultMegaHandlerHooks.max_refund_quotient(spec)
+        }
+    }
+}
+
+/// [`MegaHandlerHooks`] choosing the accounts warmed before a transaction runs, e.g. to study the
+/// cost of calling the system contracts warm, with the `MegaETH` behavior otherwise.
+///
+/// The precompiles and the accounts of the access list are always warm (EIP-2929). Warming an
+/// account loads it in pre-execution, so it is part of the state the transaction accessed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrewarmPolicy {
+    /// Whether the block beneficiary is warm (EIP-3651).
+    pub coinbase: bool,
+    /// Whether the oracle contract and the high-precision timestamp oracle are warm.
+    pub oracle: bool,
+    /// Whether the keyless deploy contract is warm.
+    pub keyless_deploy: bool,
+    /// Whether the access control, limit control and sequencer registry contracts are warm.
+    pub system_contracts: bool,
+    /// The first spec the policy applies to. Transactions of earlier specs keep the default
+    /// policy.
+    pub activation: MegaSpecId,
+}
+
+impl Default for PrewarmPolicy {
+    fn default() -> Self {
+        Self {
+            coinbase: true,
+            oracle: false,
+            keyless_deploy: false,
+            system_contracts: false,
+            activation: MegaSpecId::EQUIVALENCE,
+        }
+    }
+}
+
+impl PrewarmPolicy {
+    /// Warms the block beneficiary and every `MegaETH` system contract, from `EQUIVALENCE` on.
+    pub fn all_system_contracts() -> Self {
+        Self { oracle: true, keyless_deploy: true, system_contracts: true, ..Self::default() }
+    }
+
+    /// Sets the first spec the policy applies to.
+    pub fn with_activation(mut self, activation: MegaSpecId) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    /// Returns the system contracts the policy warms. The block beneficiary is not included.
+    pub fn system_contract_addresses(&self) -> impl Iterator<Item = Address> {
+        let oracle = [ORACLE_CONTRACT_ADDRESS, HIGH_PRECISION_TIMESTAMP_ORACLE_ADDRESS];
+        let system_contracts =
+            [ACCESS_CONTROL_ADDRESS, LIMIT_CONTROL_ADDRESS, SEQUENCER_REGISTRY_ADDRESS];
+        oracle
+            .into_iter()
+            .filter(|_| self.oracle)
+            .chain(self.keyless_deploy.then_some(KEYLESS_DEPLOY_ADDRESS))
+            .chain(system_contracts.into_iter().filter(|_| self.system_contracts))
+    }
+}
+
+impl MegaHandlerHooks for PrewarmPolicy {
+    fn prewarm_policy(&self, spec: MegaSpecId) -> PrewarmPolicy {
+        if spec.is_enabled(self.activation) {
+            *self
+        } else {
+            Self::default()
+        }
+    }
+}
+
+/// [`MegaHandlerHooks`] setting the minimum gas charged to a transaction of each type, e.g. to
+/// price EIP-7702 transactions higher, with the `MegaETH` behavior otherwise.
+///
+/// The floor is validated against the gas limit of the transaction, then charged like the
+/// EIP-7623 floor: a transaction spending less pays the floor and gets no refund. Deposit and mega
+/// system transactions must never fail validation, so they have no floor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxGasFloors {
+    /// The floor of legacy transactions.
+    pub legacy: u64,
+    /// The floor of EIP-2930 transactions.
+    pub eip2930: u64,
+    /// The floor of EIP-1559 transactions.
+    pub eip1559: u64,
+    /// The floor of EIP-7702 transactions.
+    pub eip7702: u64,
+    /// The first spec the floors apply to. Transactions of earlier specs have no floor.
+    pub activation: MegaSpecId,
+}
+
+impl Default for TxGasFloors {
+    fn default() -> Self {
+        Self { legacy: 0, eip2930: 0, eip1559: 0, eip7702: 0, activation: MegaSpecId::EQUIVALENCE }
+    }
+}
+
+impl TxGasFloors {
+    /// Sets the floor of transactions of type `tx_type`. Deposit transactions never have a floor,
+    /// so setting one for them has no effect.
+    pub fn with_floor(mut self, tx_type: MegaTxType, floor: u64) -> Self {
+        match tx_type {
+            MegaTxType::Legacy => self.legacy = floor,
+            MegaTxType::Eip2930 => self.eip2930 = floor,
+            MegaTxType::Eip1559 => self.eip1559 = floor,
+            MegaTxType::Eip7702 => self.eip7702 = floor,
+            MegaTxType::Deposit => {}
+        }
+        self
+    }
+
+    /// Sets the first spec the floors apply to.
+    pub fn with_activation(mut self, activation: MegaSpecId) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    /// Returns the floor of transactions of type `tx_type`, 0 for deposit transactions and for a
+    /// type `MegaETH` does not accept.
+    pub fn floor(&self, tx_type: u8) -> u64 {
+        match MegaTxType::try_from(tx_type) {
+            Ok(MegaTxType::Legacy) => self.legacy,
+            Ok(MegaTxType::Eip2930) => self.eip2930,
+            Ok(MegaTxType::Eip1559) => self.eip1559,
+            Ok(MegaTxType::Eip7702) => self.eip7702,
+            Ok(MegaTxType::Deposit) | Err(_) => 0,
+        }
+    }
+}
+
+impl MegaHandlerHooks for TxGasFloors {
+    fn tx_gas_floors(&self, spec: MegaSpecId) -> TxGasFloors {
+        if spec.is_enabled(self.activation) {
+            *self
+        } else {
+            Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{cell::Cell, convert::Infallible};
+
+    use alloy_eips::eip7702::{Authorization, RecoveredAuthority, RecoveredAuthorization};
+    use alloy_primitives::{address, Bytes, U256};
+    use op_revm::constants::BASE_FEE_RECIPIENT;
+    use revm::{
+        bytecode::opcode::{CALL, POP, PUSH0, SSTORE, STOP},
+        context::{
+            result::{ExecutionResult, InvalidTransaction},
+            tx::TxEnvBuilder,
+            BlockEnv, ContextSetters, ContextTr, JournalTr,
+        },
+        handler::{EthFrame, EvmTr, Handler},
+        interpreter::interpreter::EthInterpreter,
+    };
+
+    use super::*;
+    use crate::{
+        test_utils::{context_without_operator_fee, BytecodeBuilder, MemoryDatabase},
+        EVMError, EvmTxRuntimeLimits, MegaEvm, MegaHandler, MegaTransaction, MegaTransactionError,
+        OpTransactionError, MEGA_SYSTEM_ADDRESS,
+    };
+
+    const CALLER: Address = address!("0000000000000000000000000000000000100000");
+    const CONTRACT: Address = address!("0000000000000000000000000000000000100001");
+    const AUTHORITY: Address = address!("0000000000000000000000000000000000100002");
+    const COINBASE: Address = address!("0000000000000000000000000000000000100003");
+    const SINK: Address = address!("0000000000000000000000000000000000100004");
+    const GAS_LIMIT: u64 = 1_000_000;
+    /// The EIP-3529 refund for clearing a storage slot.
+    const SSTORE_CLEARS_SCHEDULE: u64 = 4_800;
+
+    /// Keeps the default policies, but counts limit finalizations and refunds no rescued gas.
+    #[derive(Default)]
+    struct NoRescueHooks {
+        finalized: Cell<u32>,
+    }
+
+    impl MegaHandlerHooks for NoRescueHooks {
+        fn finalize_limits<DB: Database, ExtEnvs: ExternalEnvTypes>(
+            &self,
+            ctx: &mut MegaContext<DB, ExtEnvs>,
+            frame_result: &mut FrameResult,
+        ) {
+            self.finalized.set(self.finalized.get() + 1);
+            DefaultMegaHandlerHooks.finalize_limits(ctx, frame_result);
+        }
+
+        fn rescued_gas<DB: Database, ExtEnvs: ExternalEnvTypes>(
+            &self,
+            _ctx: &MegaContext<DB, ExtEnvs>,
+        ) -> u64 {
+            0
+        }
+    }
+
+    /// Runs, with `hooks`, a call whose first opcode exceeds the compute gas limit.
+    fn run_with<H: MegaHandlerHooks>(hooks: H) -> (ExecutionResult<MegaHaltReason>, H) {
+        let code = BytecodeBuilder::default().push_number(1_u8).append(STOP).build();
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000_000_000_000_000u128))
+            .account_code(CONTRACT, code);
+        let context = context_without_operator_fee(&mut db, MegaSpecId::REX5)
+            .with_tx_runtime_limits(
+                EvmTxRuntimeLimits::from_spec(MegaSpecId::REX5).with_tx_compute_gas_limit(21_001),
+            );
+        let mut evm = MegaEvm::new(context);
+        let mut tx = MegaTransaction::new(
+            TxEnvBuilder::default().caller(CALLER).call(CONTRACT).gas_limit(GAS_LIMIT).build_fill(),
+        );
+        tx.enveloped_tx = Some(Bytes::new());
+        evm.ctx().set_tx(tx);
+
+        let mut handler = MegaHandler::<_, _, EthFrame<EthInterpreter>, H>::with_hooks(hooks);
+        let result: Result<_, EVMError<Infallible, MegaTransactionError>> = handler.run(&mut evm);
+        (result.expect("tx should execute"), handler.into_hooks())
+    }
+
+    /// Runs, with `hooks`, a REX5 EIP-7702 transaction delegating `AUTHORITY` to `target` with an
+    /// authorization signed for `nonce`. `AUTHORITY` does not exist, so only nonce 0 applies.
+    fn run_delegation<H: MegaHandlerHooks>(
+        hooks: H,
+        target: Address,
+        nonce: u64,
+    ) -> Result<ExecutionResult<MegaHaltReason>, EVMError<Infallible, MegaTransactionError>> {
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000_000_000_000_000u128));
+        let context = context_without_operator_fee(&mut db, MegaSpecId::REX5);
+        let chain_id = context.cfg().chain_id;
+        let mut evm = MegaEvm::new(context);
+        let authorization = RecoveredAuthorization::new_unchecked(
+            Authorization { chain_id: U256::from(chain_id), address: target, nonce },
+            RecoveredAuthority::Valid(AUTHORITY),
+        );
+        let mut tx = MegaTransaction::new(
+            TxEnvBuilder::default()
+                .caller(CALLER)
+                .call(CONTRACT)
+                .gas_limit(GAS_LIMIT)
+                .authorization_list_recovered(Vec::from([authorization]))
+                .build_fill(),
+        );
+        tx.enveloped_tx = Some(Bytes::new());
+        evm.ctx().set_tx(tx);
+
+        MegaHandler::<_, _, EthFrame<EthInterpreter>, H>::with_hooks(hooks).run(&mut evm)
+    }
+
+    #[test]
+    fn test_delegation_policy_rejects_denied_targets() {
+        let policy = DelegationPolicy::deny_system_contracts();
+        let err = run_delegation(policy.clone(), ORACLE_CONTRACT_ADDRESS, 0)
+            .expect_err("delegating to the oracle must be rejected");
+        assert!(
+            matches!(
+                err,
+                EVMError::Transaction(MegaTransactionError::DelegationTargetDenied(
+                    DelegationTargetDenied { target: ORACLE_CONTRACT_ADDRESS }
+                ))
+            ),
+            "got {err:?}"
+        );
+
+        assert!(run_delegation(DefaultMegaHandlerHooks, ORACLE_CONTRACT_ADDRESS, 0).is_ok());
+        assert!(run_delegation(policy, CONTRACT, 0).is_ok());
+    }
+
+    #[test]
+    fn test_delegation_policy_skips_authorizations_not_applied() {
+        // The nonce does not match, so revm skips the authorization and the target is not checked.
+        let policy = DelegationPolicy::deny_system_contracts();
+        assert!(run_delegation(policy, ORACLE_CONTRACT_ADDRESS, 1).is_ok());
+    }
+
+    #[test]
+    fn test_delegation_policy_always_allows_clearing() {
+        let policy = DelegationPolicy::Allow(Vec::from([CONTRACT]));
+        assert!(policy.allows(CONTRACT));
+        assert!(policy.allows(Address::ZERO));
+        assert!(!policy.allows(ORACLE_CONTRACT_ADDRESS));
+        assert!(DelegationPolicy::Deny(Vec::from([Address::ZERO])).allows(Address::ZERO));
+    }
+
+    /// Runs, with `hooks`, a REX5 call paying a priority fee of 1 over a base fee of 1 and returns
+    /// the gas used and the balance of `accounts` afterwards.
+    fn run_fee_distribution<H: MegaHandlerHooks, const N: usize>(
+        hooks: H,
+        accounts: [Address; N],
+    ) -> (u64, [U256; N]) {
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000_000_000_000_000u128));
+        let mut context = context_without_operator_fee(&mut db, MegaSpecId::REX5);
+        context.set_block(BlockEnv { basefee: 1, beneficiary: COINBASE, ..Default::default() });
+        let mut evm = MegaEvm::new(context);
+        let mut tx = MegaTransaction::new(
+            TxEnvBuilder::default()
+                .caller(CALLER)
+                .call(CONTRACT)
+                .gas_limit(GAS_LIMIT)
+                .gas_price(2)
+                .build_fill(),
+        );
+        tx.enveloped_tx = Some(Bytes::new());
+        evm.ctx().set_tx(tx);
+
+        let result: Result<_, EVMError<Infallible, MegaTransactionError>> =
+            MegaHandler::<_, _, EthFrame<EthInterpreter>, H>::with_hooks(hooks).run(&mut evm);
+        let gas_used = result.expect("tx should execute").gas_used();
+        let state = evm.ctx().journal_mut().finalize();
+        let balances = accounts.map(|address| {
+            state.get(&address).map(|account| account.info.balance).unwrap_or_default()
+        });
+        (gas_used, balances)
+    }
+
+    #[test]
+    fn test_fee_distribution_redirects_fees() {
+        let accounts = [SINK, COINBASE, BASE_FEE_RECIPIENT];
+        let (gas_used, [sink, coinbase, base_fee_vault]) =
+            run_fee_distribution(DefaultMegaHandlerHooks, accounts);
+        assert_eq!(sink, U256::ZERO);
+        assert_eq!(coinbase, U256::from(gas_used));
+        assert_eq!(base_fee_vault, U256::from(gas_used));
+
+        let (gas_used, [sink, coinbase, base_fee_vault]) =
+            run_fee_distribution(FeeDistribution::all_to(SINK), accounts);
+        assert_eq!(sink, U256::from(2 * gas_used), "the sink receives every fee");
+        assert_eq!(coinbase, U256::ZERO);
+        assert_eq!(base_fee_vault, U256::ZERO);
+
+        let burn_base_fee =
+            FeeDistribution { base_fee: FeeRecipient::Burn, ..FeeDistribution::default() };
+        let (gas_used, [sink, coinbase, base_fee_vault]) =
+            run_fee_distribution(burn_base_fee, accounts);
+        assert_eq!(sink, U256::ZERO);
+        assert_eq!(coinbase, U256::from(gas_used));
+        assert_eq!(base_fee_vault, U256::ZERO, "the base fee is burnt");
+
+        let inactive = FeeDistribution::all_to(SINK).with_activation(MegaSpecId::REX6);
+        let (gas_used, [sink, coinbase, _]) = run_fee_distribution(inactive, accounts);
+        assert_eq!(sink, U256::ZERO, "the distribution is not active before REX6");
+        assert_eq!(coinbase, U256::from(gas_used));
+    }
+
+    /// Runs, with `hooks`, a REX5 call clearing a storage slot, which earns a refund, and returns
+    /// the gas used.
+    fn run_refund<H: MegaHandlerHooks>(hooks: H) -> u64 {
+        let code = BytecodeBuilder::default().append_many([PUSH0, PUSH0, SSTORE, STOP]).build();
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000_000_000_000_000u128))
+            .account_code(CONTRACT, code)
+            .account_storage(CONTRACT, U256::ZERO, U256::from(1));
+        let context = context_without_operator_fee(&mut db, MegaSpecId::REX5);
+        let mut evm = MegaEvm::new(context);
+        let mut tx = MegaTransaction::new(
+            TxEnvBuilder::default().caller(CALLER).call(CONTRACT).gas_limit(GAS_LIMIT).build_fill(),
+        );
+        tx.enveloped_tx = Some(Bytes::new());
+        evm.ctx().set_tx(tx);
+
+        let result: Result<_, EVMError<Infallible, MegaTransactionError>> =
+            MegaHandler::<_, _, EthFrame<EthInterpreter>, H>::with_hooks(hooks).run(&mut evm);
+        let result = result.expect("tx should execute");
+        assert!(result.is_success());
+        result.gas_used()
+    }
+
+    #[test]
+    fn test_refund_cap_changes_the_refund_quotient() {
+        let spent = run_refund(RefundCap::disabled());
+        assert!(spent > 5 * SSTORE_CLEARS_SCHEDULE, "the EIP-3529 cap must not bind");
+
+        let default = run_refund(DefaultMegaHandlerHooks);
+        assert_eq!(default, spent - SSTORE_CLEARS_SCHEDULE, "the whole refund is granted");
+        assert_eq!(run_refund(RefundCap::quotient(5)), default);
+        assert_eq!(run_refund(RefundCap::quotient(100)), spent - spent / 100);
+
+        let inactive = RefundCap::disabled().with_activation(MegaSpecId::REX6);
+        assert_eq!(run_refund(inactive), default, "the cap is not active before REX6");
+    }
+
+    /// Runs, with `hooks`, a REX5 call to a contract calling `target` with no gas, and returns the
+    /// gas used.
+    fn run_prewarm<H: MegaHandlerHooks>(hooks: H, target: Address) -> u64 {
+        let code = BytecodeBuilder::default()
+            .append_many([PUSH0, PUSH0, PUSH0, PUSH0, PUSH0])
+            .push_address(target)
+            .append_many([PUSH0, CALL, POP, STOP])
+            .build();
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000_000_000_000_000u128))
+            .account_code(CONTRACT, code);
+        let mut context = context_without_operator_fee(&mut db, MegaSpecId::REX5);
+        context.set_block(BlockEnv { beneficiary: COINBASE, ..Default::default() });
+        let mut evm = MegaEvm::new(context);
+        let mut tx = MegaTransaction::new(
+            TxEnvBuilder::default().caller(CALLER).call(CONTRACT).gas_limit(GAS_LIMIT).build_fill(),
+        );
+        tx.enveloped_tx = Some(Bytes::new());
+        evm.ctx().set_tx(tx);
+
+        let result: Result<_, EVMError<Infallible, MegaTransactionError>> =
+            MegaHandler::<_, _, EthFrame<EthInterpreter>, H>::with_hooks(hooks).run(&mut evm);
+        let result = result.expect("tx should execute");
+        assert!(result.is_success());
+        result.gas_used()
+    }
+
+    #[test]
+    fn test_prewarm_policy_changes_cold_and_warm_call_costs() {
+        // EIP-2929: a cold account access costs 2600, a warm one 100.
+        const COLD_SURCHARGE: u64 = 2_500;
+
+        let cold_coinbase = PrewarmPolicy { coinbase: false, ..PrewarmPolicy::default() };
+        assert_eq!(
+            run_prewarm(cold_coinbase, COINBASE),
+            run_prewarm(DefaultMegaHandlerHooks, COINBASE) + COLD_SURCHARGE,
+            "the beneficiary is warm by default"
+        );
+
+        for target in [
+            ORACLE_CONTRACT_ADDRESS,
+            HIGH_PRECISION_TIMESTAMP_ORACLE_ADDRESS,
+            KEYLESS_DEPLOY_ADDRESS,
+            ACCESS_CONTROL_ADDRESS,
+            LIMIT_CONTROL_ADDRESS,
+            SEQUENCER_REGISTRY_ADDRESS,
+        ] {
+            let cold = run_prewarm(DefaultMegaHandlerHooks, target);
+            assert_eq!(
+                run_prewarm(PrewarmPolicy::all_system_contracts(), target),
+                cold - COLD_SURCHARGE,
+                "{target} is warm under the policy"
+            );
+            let inactive = PrewarmPolicy::all_system_contracts().with_activation(MegaSpecId::REX6);
+            assert_eq!(run_prewarm(inactive, target), cold, "the policy is not active before REX6");
+        }
+    }
+
+    /// Runs, with `hooks`, a REX5 legacy call to an empty contract.
+    fn run_gas_floor<H: MegaHandlerHooks>(
+        hooks: H,
+    ) -> Result<ExecutionResult<MegaHaltReason>, EVMError<Infallible, MegaTransactionError>> {
+        run_gas_floor_tx(hooks, CALLER, CONTRACT)
+    }
+
+    /// Runs, with `hooks`, a REX5 legacy call from `caller` to `target`.
+    fn run_gas_floor_tx<H: MegaHandlerHooks>(
+        hooks: H,
+        caller: Address,
+        target: Address,
+    ) -> Result<ExecutionResult<MegaHaltReason>, EVMError<Infallible, MegaTransactionError>> {
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000_000_000_000_000u128))
+            .account_code(CONTRACT, BytecodeBuilder::default().append(STOP).build());
+        let context = context_without_operator_fee(&mut db, MegaSpecId::REX5);
+        let mut evm = MegaEvm::new(context);
+        let mut tx = MegaTransaction::new(
+            TxEnvBuilder::default().caller(caller).call(target).gas_limit(GAS_LIMIT).build_fill(),
+        );
+        tx.enveloped_tx = Some(Bytes::new());
+        evm.ctx().set_tx(tx);
+
+        MegaHandler::<_, _, EthFrame<EthInterpreter>, H>::with_hooks(hooks).run(&mut evm)
+    }
+
+    #[test]
+    fn test_tx_gas_floors_raise_the_gas_charged() {
+        const FLOOR: u64 = 200_000;
+        let gas_used = |hooks| run_gas_floor(hooks).expect("tx should execute").gas_used();
+
+        let default = gas_used(TxGasFloors::default());
+        assert!(default < FLOOR, "the floor must bind");
+        assert_eq!(default, run_gas_floor(DefaultMegaHandlerHooks).unwrap().gas_used());
+
+        assert_eq!(gas_used(TxGasFloors::default().with_floor(MegaTxType::Legacy, FLOOR)), FLOOR);
+        assert_eq!(
+            gas_used(TxGasFloors::default().with_floor(MegaTxType::Eip7702, FLOOR)),
+            default,
+            "the floor of another type does not apply"
+        );
+        let inactive = TxGasFloors::default()
+            .with_floor(MegaTxType::Legacy, FLOOR)
+            .with_activation(MegaSpecId::REX6);
+        assert_eq!(gas_used(inactive), default, "the floors are not active before REX6");
+
+        let err =
+            run_gas_floor(TxGasFloors::default().with_floor(MegaTxType::Legacy, GAS_LIMIT + 1))
+                .expect_err("a floor above the gas limit must be rejected");
+        assert!(
+            matches!(
+                err,
+                EVMError::Transaction(MegaTransactionError::Base(OpTransactionError::Base(
+                    InvalidTransaction::GasFloorMoreThanGasLimit { gas_floor, gas_limit: GAS_LIMIT }
+                ))) if gas_floor == GAS_LIMIT + 1
+            ),
+            "got {err:?}"
+        );
+
+        // A mega system transaction is deposit-like and must never fail validation.
+        let system_tx = run_gas_floor_tx(
+            TxGasFloors::default().with_floor(MegaTxType::Legacy, GAS_LIMIT + 1),
+            MEGA_SYSTEM_ADDRESS,
+            ORACLE_CONTRACT_ADDRESS,
+        );
+        assert!(system_tx.is_ok(), "a system transaction has no floor: {system_tx:?}");
+        assert_eq!(
+            TxGasFloors::default()
+                .with_floor(MegaTxType::Deposit, FLOOR)
+                .floor(DEPOSIT_TRANSACTION_TYPE),
+            0
+        );
+    }
+
+    #[test]
+    fn test_custom_hooks_override_default_policy() {
+        let (default_result, _) = run_with(DefaultMegaHandlerHooks);
+        let (custom_result, hooks) = run_with(NoRescueHooks::default());
+
+        for result in [&default_result, &custom_result] {
+            assert!(
+                matches!(
+                    result,
+                    ExecutionResult::Halt {
+                        reason: MegaHaltReason::ComputeGasLimitExceeded { .. },
+                        ..
+                    }
+                ),
+                "unexpected result: {result:?}"
+            );
+        }
+        assert_eq!(hooks.finalized.get(), 1, "limits are finalized once per transaction");
+        assert!(default_result.gas_used() < GAS_LIMIT, "the default hooks refund rescued gas");
+        assert_eq!(custom_result.gas_used(), GAS_LIMIT, "the custom hooks refund nothing");
+    }
+}
@@ -3,11 +3,11 @@ use core::cell::RefCell;
 #[cfg(not(feature = "std"))]
 use alloc as std;
 use mega_system_contracts::access_control::IMegaAccessControl::VolatileDataAccessType;
-use std::{format, rc::Rc};
+use std::{format, rc::Rc, string::ToString};
 
 use crate::{
-    AdditionalLimit, ExternalEnvTypes, MegaContext, MegaSpecId, OracleEnv,
-    VolatileDataAccessTracker, ORACLE_CONTRACT_ADDRESS,
+    AdditionalLimit, ExternalEnvError, ExternalEnvKey, ExternalEnvTypes, MegaContext, MegaSpecId,
+    OracleEnv, SaltEnv, VolatileDataAccessTracker, ORACLE_CONTRACT_ADDRESS,
 };
 use alloy_evm::Database;
 use alloy_primitives::{Address, Bytes, Log, B256, U256};
@@ -17,7 +17,7 @@ use revm::{
     context_interface::{context::ContextError, journaled_state::AccountLoad},
     interpreter::{Host, SStoreResult, SelfDestructResult, StateLoad},
     primitives::{hash_map::Entry, StorageKey, KECCAK_EMPTY},
-    state::{Account, Bytecode, EvmStorageSlot},
+    state::{Account, Bytecode, EvmState, EvmStorageSlot},
     Journal,
 };
 
@@ -60,7 +60,13 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> Host for MegaContext<DB, ExtEnvs>
 
     fn block_hash(&mut self, number: u64) -> Option<B256> {
         self.mark_block_env_accessed(VolatileDataAccessType::BlockHash);
-        self.inner.block_hash(number)
+        let provided = self.block_hash_provider.as_ref().and_then(|p| p.block_hash(number));
+        let hash = match provided {
+            Some(hash) => hash,
+            None => self.inner.block_hash(number)?,
+        };
+        self.accessed_block_hashes.insert(number, hash);
+        Some(hash)
     }
 
     // Blob-related block environment methods - with tracking
@@ -164,6 +170,7 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> Host for MegaContext<DB, ExtEnvs>
             // if the oracle env provides a value, return it. Otherwise, fallback to the inner
             // context.
             if let Some(value) = self.oracle_env.borrow().get_oracle_storage(key) {
+                self.accessed_oracle_slots.entry(key).or_insert(value);
                 // Accessing oracle contract storage is forced to be cold access, since it always
                 // reads from the outside world (oracle_env).
                 return Some(StateLoad::new(value, true));
@@ -178,6 +185,7 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> Host for MegaContext<DB, ExtEnvs>
                 // whether the payload builder read from the oracle_env or not. So we force such
                 // sload always to be cold access to ensure consistent gas cost.
                 state_load.is_cold = true;
+                self.accessed_oracle_slots.entry(key).or_insert(state_load.data);
             }
             state_load
         })
@@ -260,6 +268,10 @@ pub trait HostExt: Host {
     /// Used by instruction handlers to pre-check whether an opcode targets the beneficiary.
     fn beneficiary_address(&self) -> Address;
 
+    /// Returns whether volatile data accesses by the code of `address` are exempt from compute
+    /// gas detention. See [`DetentionExemptions`](crate::DetentionExemptions).
+    fn is_detention_exempt(&self, address: Address) -> bool;
+
     /// Resolves the EIP-7702 delegate of `address` one hop on a best-effort basis, returning
     /// `address` itself when there is no delegate or when the resolve hits a DB error.
     ///
@@ -297,7 +309,11 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> HostExt for MegaContext<DB, ExtEnv
         let result = self.dynamic_storage_gas_cost.borrow_mut().sstore_set_gas(address, key);
         result
             .map_err(|e| {
-                *self.error() = Err(ContextError::Custom(format!("{e}")));
+                let bucket_id =
+                    <ExtEnvs::SaltEnv as SaltEnv>::bucket_id_for_slot_at(self.spec, address, key);
+                let key = ExternalEnvKey::StorageSlot { address, key, bucket_id };
+                *self.error() =
+                    Err(ContextError::Custom(ExternalEnvError::salt(key, e).to_string()));
             })
             .ok()
     }
@@ -311,7 +327,11 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> HostExt for MegaContext<DB, ExtEnv
         let result = self.dynamic_storage_gas_cost.borrow_mut().new_account_gas(address);
         result
             .map_err(|e| {
-                *self.error() = Err(ContextError::Custom(format!("{e}")));
+                let bucket_id =
+                    <ExtEnvs::SaltEnv as SaltEnv>::bucket_id_for_account_at(self.spec, address);
+                let key = ExternalEnvKey::Account { address, bucket_id };
+                *self.error() =
+                    Err(ContextError::Custom(ExternalEnvError::salt(key, e).to_string()));
             })
             .ok()
     }
@@ -325,7 +345,11 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> HostExt for MegaContext<DB, ExtEnv
         let result = self.dynamic_storage_gas_cost.borrow_mut().create_contract_gas(address);
         result
             .map_err(|e| {
-                *self.error() = Err(ContextError::Custom(format!("{e}")));
+                let bucket_id =
+                    <ExtEnvs::SaltEnv as SaltEnv>::bucket_id_for_account_at(self.spec, address);
+                let key = ExternalEnvKey::Account { address, bucket_id };
+                *self.error() =
+                    Err(ContextError::Custom(ExternalEnvError::salt(key, e).to_string()));
             })
             .ok()
     }
@@ -346,6 +370,11 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> HostExt for MegaContext<DB, ExtEnv
         self.inner.block.beneficiary
     }
 
+    #[inline]
+    fn is_detention_exempt(&self, address: Address) -> bool {
+        self.detention_exemptions.contains(&address)
+    }
+
     #[inline]
     fn best_effort_resolve_eip7702_delegate_address(&mut self, address: Address) -> Address {
         // Resolve through the journal directly so a DB error propagates as `Err` here (and is
@@ -483,23 +512,40 @@ fn inspect_account<DB: revm::Database>(
     load_code: bool,
 ) -> Result<&mut Account, <DB as revm::Database>::Error> {
     let transaction_id = journal.transaction_id;
-    match journal.inner.state.entry(address) {
+    inspect_account_in(
+        &mut journal.inner.state,
+        &mut journal.database,
+        transaction_id,
+        address,
+        load_code,
+    )
+}
+
+/// [`inspect_account`] over the journal's state and database borrowed separately, so the
+/// returned account does not keep the database borrowed.
+fn inspect_account_in<'a, DB: revm::Database>(
+    state: &'a mut EvmState,
+    database: &mut DB,
+    transaction_id: usize,
+    address: Address,
+    load_code: bool,
+) -> Result<&'a mut Account, <DB as revm::Database>::Error> {
+    match state.entry(address) {
         Entry::Occupied(entry) => {
             let account = entry.into_mut();
             if account.info.code_hash != KECCAK_EMPTY && account.info.code.is_none() {
                 // Load code if not loaded before
-                account.info.code = Some(journal.database.code_by_hash(account.info.code_hash)?);
+                account.info.code = Some(database.code_by_hash(account.info.code_hash)?);
             }
             Ok(account)
         }
         Entry::Vacant(entry) => {
-            let mut account = journal
-                .database
+            let mut account = database
                 .basic(address)?
                 .map(|info| info.into())
                 .unwrap_or_else(|| Account::new_not_existing(transaction_id));
             if load_code && account.info.code_hash != KECCAK_EMPTY && account.info.code.is_none() {
-                account.info.code = Some(journal.database.code_by_hash(account.info.code_hash)?);
+                account.info.code = Some(database.code_by_hash(account.info.code_hash)?);
             }
             // deliberately mark the account as cold since we are only inspecting it, not warming
             // it.
@@ -616,35 +662,28 @@ impl<DB: revm::Database> JournalInspectTr for Journal<DB> {
         // the delegate's flag instead would mistakenly short-circuit storage reads when the
         // delegate happens to be a freshly-CREATEd contract in the same tx, corrupting
         // SSTORE accounting (gas / kv_updates / data_size) on the delegator's slots.
-        // Fold two inspect_account calls into one hydrating load on REX4 path.
-        // inspect_account's occupied branch hydrates lazy code unconditionally,
-        // so the old second (always-occupied) pass hydrated the same code that
-        // load_code=true hydrates inline here — identical final account state,
-        // identical DB-call sequence and error position, one fewer state.entry lookup.
         // Newly-created accounts must short-circuit storage misses to ZERO before any DB call.
         // Querying here would otherwise trigger a witness lookup for a slot with no meaningful
         // pre-state value, which breaks stateless replay when CREATE lands on a pre-funded
         // address: its `Loaded` cache status bypasses revm's `State::storage` short-circuit and
         // exposes the call to the witness backend.
-        let is_newly_created;
-        let account = if is_rex4_enabled {
-            let account = inspect_account(self, address, true)?;
-            is_newly_created = account.is_created();
-            debug_assert!(account.info.code_hash == KECCAK_EMPTY || account.info.code.is_some());
-            account
-        } else {
-            // Non-REX4: is_created must be read on the original address (an EOA delegating
-            // via 7702 is never CREATEd), but the storage account follows delegation —
-            // genuinely two different accounts, so the two loads cannot be folded.
-            is_newly_created = inspect_account(self, address, false)?.is_created();
-            self.inspect_account_delegated(spec, address)?
-        };
-        // REX4/REX5 hot path: use entry() API for a single HashMap probe.
-        // The prologue guarantees the account is in inner.state; reload via get_mut
-        // to narrow the borrow from &mut self to &mut inner.state, keeping
-        // self.database reachable for the miss path.
         if is_rex4_enabled {
-            let account = self.inner.state.get_mut(&address).unwrap();
+            // REX4+ hot path: one account probe and one slot probe.
+            // A single hydrating load replaces the two `inspect_account` passes: the occupied
+            // branch hydrates lazy code unconditionally, so the second pass hydrated the same
+            // code that `load_code = true` hydrates inline — identical final account state and
+            // identical DB-call sequence and error position.
+            // Borrowing the state and the database separately keeps `self.database` reachable
+            // for the miss path while the account is held, so the account is never re-fetched.
+            let account = inspect_account_in(
+                &mut self.inner.state,
+                &mut self.database,
+                transaction_id,
+                address,
+                true,
+            )?;
+            let is_newly_created = account.is_created();
+            debug_assert!(account.info.code_hash == KECCAK_EMPTY || account.info.code.is_some());
             return match account.storage.entry(key) {
                 Entry::Occupied(entry) => Ok(entry.into_mut()),
                 Entry::Vacant(entry) => {
@@ -660,7 +699,14 @@ impl<DB: revm::Database> JournalInspectTr for Journal<DB> {
             };
         }
 
-        // Pre-REX4: original contains_key + reload pattern (genuinely two different accounts).
+        // Pre-REX4: is_created must be read on the original address (an EOA delegating via 7702
+        // is never CREATEd), but the storage account follows delegation — genuinely two
+        // different accounts, so the two loads cannot be folded.
+        let is_newly_created = inspect_account(self, address, false)?.is_created();
+        let account = self.inspect_account_delegated(spec, address)?;
+        // The contains_key + reload pattern is frozen on these specs: each reload re-walks the
+        // delegation chain, and the occupied branch of `inspect_account` hydrates lazy code
+        // along the way, which can change both the DB-call sequence and the account reached.
         if account.storage.contains_key(&key) {
             // Need to reload account to satisfy borrow checker.
             let account = self.inspect_account_delegated(spec, address)?;
@@ -110,6 +110,10 @@ use revm::{
 /// 5. **`volatile_data_ext`** — Applies gas detention on volatile data access (block env,
 ///    beneficiary, oracle) and pre-execution disable checks (Rex4+). Wraps `compute_gas_ext` or
 ///    `forward_gas_ext` handlers depending on the opcode.
+/// 6. **`memory_limit_ext`** (REX6+) — Checks the interpreter memory limit before any
+///    memory-expanding opcode runs. The outermost layer: wraps `volatile_data_ext` (CALL-family,
+///    EXTCODECOPY), `additional_limit_ext` (LOG0–LOG4), `forward_gas_ext` (CREATE, CREATE2) and
+///    `compute_gas_ext` (the remaining memory opcodes) handlers.
 ///
 /// ## Spec Progression and Opcode Overrides
 ///
@@ -144,16 +148,26 @@ use revm::{
 ///   - SELFDESTRUCT: `volatile_data_ext::selfdestruct_with_beneficiary_guard` →
 ///     `storage_gas_ext::selfdestruct` (new-account storage gas, beneficiary-volatile guard
 ///     outermost)
-/// - **REX6** (extends REX5): unifies the per-opcode gas-metering order. The table wiring is
-///   unchanged. Storage-affecting handlers (SSTORE, LOG, CALL-family, CREATE/CREATE2) all follow a
-///   canonical order: charge storage gas → run the raw opcode body → record compute gas exactly
-///   once via `record_storage_compute_gas!` after the body completes, excluding the storage gas.
-///   For SSTORE / LOG / CALL-family this is byte-equivalent to the pre-REX6 layering (nothing
-///   between `gas_before` and the storage charge debits EVM gas), so `storage_gas_ext::*` records
-///   compute inline on every spec — no `if REX6` branch is needed. SELFDESTRUCT keeps its
+/// - **REX6** (extends REX5):
+///   - MLOAD, MSTORE, MSTORE8, MCOPY, KECCAK256, CALLDATACOPY, CODECOPY, RETURNDATACOPY:
+///     `memory_limit_ext` → `compute_gas_ext`
+///   - EXTCODECOPY: `memory_limit_ext` → `volatile_data_ext`
+///   - LOG0–LOG4: `memory_limit_ext` → `additional_limit_ext` → `storage_gas_ext`
+///   - CREATE, CREATE2: `memory_limit_ext` → `forward_gas_ext` → `storage_gas_ext`
+///   - CALL, CALLCODE, DELEGATECALL, STATICCALL: `memory_limit_ext` → `volatile_data_ext` →
+///     `forward_gas_ext` → `storage_gas_ext`
+///   - RETURN, REVERT: `memory_limit_ext` → `compute_gas_ext`
+///
+///   REX6 also unifies the per-opcode gas-metering order inside the shared handlers, without
+///   further table changes. Storage-affecting handlers (SSTORE, LOG, CALL-family, CREATE/CREATE2)
+///   all follow a canonical order: charge storage gas → run the raw opcode body → record compute
+///   gas exactly once via `record_storage_compute_gas!` after the body completes, excluding the
+///   storage gas. For SSTORE / LOG / CALL-family this is byte-equivalent to the pre-REX6 layering
+///   (nothing between `gas_before` and the storage charge debits EVM gas), so `storage_gas_ext::*`
+///   records compute inline on every spec — no `if REX6` branch is needed. SELFDESTRUCT keeps its
 ///   delegation to `compute_gas_ext::selfdestruct`, whose trailing `record_compute_gas_all_dims`
 ///   check records the same single compute window while latching the pre-recorded data/KV/state
-///   usage. CREATE2 is the one real behavior change: REX6+ short-circuits to `create_rex6`, which
+///   usage. CREATE2 is the one real metering change: REX6+ short-circuits to `create_rex6`, which
 ///   folds the memory-expansion gas into the single post-body recording instead of recording it as
 ///   a separate eager entry as REX5 did.
 ///
@@ -213,6 +227,38 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> MegaInstructions<DB, ExtEnvs> {
         };
         Self { spec, inner: instruction_table }
     }
+
+    /// Registers the custom instruction `I` at `opcode`, replacing the instruction there, if any.
+    ///
+    /// The instruction is wrapped with [`compute_gas_ext::custom`], so the gas it spends is
+    /// recorded as compute gas and subject to the compute gas limit like any other opcode.
+    pub fn with_instruction<I: CustomInstruction>(mut self, opcode: u8) -> Self {
+        self.insert_instruction::<I>(opcode);
+        self
+    }
+
+    /// Registers the custom instruction `I` at `opcode`, replacing the instruction there, if any.
+    ///
+    /// See [`with_instruction`](Self::with_instruction).
+    pub fn insert_instruction<I: CustomInstruction>(&mut self, opcode: u8) {
+        self.inner.insert_instruction(
+            opcode,
+            compute_gas_ext::custom::<I, EthInterpreter, MegaContext<DB, ExtEnvs>>,
+        );
+    }
+}
+
+/// An instruction registered on [`MegaInstructions`] with
+/// [`MegaInstructions::with_instruction`], e.g. to experiment with new `MegaETH` opcodes.
+///
+/// Instructions are plain function pointers in revm, so a custom instruction is a type rather
+/// than a closure: the table entry is [`compute_gas_ext::custom`] monomorphized for it. It must not
+/// spawn a child frame, since the gas forwarded to one would be recorded as compute gas.
+pub trait CustomInstruction {
+    /// Executes the instruction, charging its gas to `context.interpreter.gas`.
+    fn execute<WIRE: InterpreterTypes, H: HostExt + ?Sized>(
+        context: InstructionContext<'_, H, WIRE>,
+    );
 }
 
 impl<DB: Database, ExtEnvs: ExternalEnvTypes> InstructionProvider
@@ -381,9 +427,14 @@ mod rex6 {
 
     /// Returns the instruction table for the `REX6` spec.
     ///
-    /// Changes from Rex5: the instruction *table* is unchanged (same handler functions as Rex5).
-    /// Every Rex6 behavior difference is expressed as internal `spec.is_enabled(MegaSpecId::REX6)`
-    /// dispatch inside the shared handlers, never as a swapped table entry:
+    /// Changes from Rex5:
+    /// - MLOAD, MSTORE, MSTORE8, MCOPY, KECCAK256, CALLDATACOPY, CODECOPY, RETURNDATACOPY,
+    ///   EXTCODECOPY, LOG0–LOG4, CREATE, CREATE2, CALL, CALLCODE, DELEGATECALL, STATICCALL, RETURN,
+    ///   REVERT: `memory_limit_ext` → the Rex5 handler (interpreter memory limit).
+    ///
+    /// Every other Rex6 behavior difference is expressed as internal
+    /// `spec.is_enabled(MegaSpecId::REX6)` dispatch inside the shared handlers, never as a
+    /// swapped table entry:
     /// - the storage-affecting handlers (SSTORE, LOG, CALL-family, CREATE/CREATE2) charge storage
     ///   gas, run their body, then record compute gas exactly once (via
     ///   [`record_storage_compute_gas!`]) with the storage gas excluded; SELFDESTRUCT keeps its
@@ -402,7 +453,34 @@ mod rex6 {
     where
         WIRE::Stack: StackInspectTr,
     {
-        rex5::instruction_table::<WIRE, H>()
+        use revm::bytecode::opcode::*;
+        let mut table = rex5::instruction_table::<WIRE, H>();
+
+        // Rex6: memory-expanding opcodes check the interpreter memory limit before expanding.
+        table[MLOAD as usize] = memory_limit_ext::mload;
+        table[MSTORE as usize] = memory_limit_ext::mstore;
+        table[MSTORE8 as usize] = memory_limit_ext::mstore8;
+        table[MCOPY as usize] = memory_limit_ext::mcopy;
+        table[KECCAK256 as usize] = memory_limit_ext::keccak256;
+        table[CALLDATACOPY as usize] = memory_limit_ext::calldatacopy;
+        table[CODECOPY as usize] = memory_limit_ext::codecopy;
+        table[RETURNDATACOPY as usize] = memory_limit_ext::returndatacopy;
+        table[EXTCODECOPY as usize] = memory_limit_ext::extcodecopy;
+        table[LOG0 as usize] = memory_limit_ext::log::<0, _, _>;
+        table[LOG1 as usize] = memory_limit_ext::log::<1, _, _>;
+        table[LOG2 as usize] = memory_limit_ext::log::<2, _, _>;
+        table[LOG3 as usize] = memory_limit_ext::log::<3, _, _>;
+        table[LOG4 as usize] = memory_limit_ext::log::<4, _, _>;
+        table[CREATE as usize] = memory_limit_ext::create;
+        table[CREATE2 as usize] = memory_limit_ext::create2;
+        table[CALL as usize] = memory_limit_ext::call;
+        table[CALLCODE as usize] = memory_limit_ext::call_code;
+        table[DELEGATECALL as usize] = memory_limit_ext::delegate_call;
+        table[STATICCALL as usize] = memory_limit_ext::static_call;
+        table[RETURN as usize] = memory_limit_ext::ret;
+        table[REVERT as usize] = memory_limit_ext::revert;
+
+        table
     }
 }
 
@@ -921,6 +999,54 @@ pub mod volatile_data_ext {
         };
     }
 
+    /// Saves the compute gas limit of the volatile data tracker if the executing contract is
+    /// [detention-exempt](crate::DetentionExemptions). Pair with
+    /// `restore_exempt_compute_gas_limit!` after the inner instruction, so an exempt contract's
+    /// accesses are recorded in the bitmap without detaining the transaction.
+    macro_rules! save_exempt_compute_gas_limit {
+        ($context:expr) => {
+            $context
+                .host
+                .is_detention_exempt($context.interpreter.input.target_address())
+                .then(|| $context.host.volatile_data_tracker().borrow().get_compute_gas_limit())
+        };
+    }
+
+    /// Restores the compute gas limit saved by `save_exempt_compute_gas_limit!`, if any.
+    macro_rules! restore_exempt_compute_gas_limit {
+        ($context:expr, $saved:expr) => {
+            if let Some(limit) = $saved {
+                $context.host.volatile_data_tracker().borrow_mut().restore_compute_gas_limit(limit);
+            }
+        };
+    }
+
+    /// Like `run_inner_instruction_or_abort!`, but without letting a
+    /// [detention-exempt](crate::DetentionExemptions) executing contract lower the compute gas
+    /// limit. The limit is restored before the abort check so it cannot leak past a failed
+    /// instruction.
+    macro_rules! run_inner_instruction_detention_exempt_or_abort {
+        ($inner_fn:path, $context:expr) => {
+            let saved = save_exempt_compute_gas_limit!($context);
+            {
+                let ctx = InstructionContext::<'_, H, WIRE> {
+                    interpreter: &mut *$context.interpreter,
+                    host: &mut *$context.host,
+                };
+                $inner_fn(ctx);
+            }
+            restore_exempt_compute_gas_limit!($context, saved);
+            if $context
+                .interpreter
+                .bytecode
+                .instruction_result()
+                .is_some_and(|result| result.is_error())
+            {
+                return;
+            }
+        };
+    }
+
     /// Macro to create opcode handlers for **unconditionally volatile** opcodes.
     ///
     /// These opcodes (TIMESTAMP, NUMBER, etc.) always access volatile data.
@@ -946,7 +1072,7 @@ pub mod volatile_data_ext {
                 return;
             }
 
-            run_inner_instruction_or_abort!($original_fn, context);
+            run_inner_instruction_detention_exempt_or_abort!($original_fn, context);
             apply_compute_gas_limit!(context);
         }
     };
@@ -990,7 +1116,7 @@ pub mod volatile_data_ext {
                 }
             }
 
-            run_inner_instruction_or_abort!($original_fn, context);
+            run_inner_instruction_detention_exempt_or_abort!($original_fn, context);
             apply_compute_gas_limit!(context);
         }
     };
@@ -1116,7 +1242,10 @@ pub mod volatile_data_ext {
             }
         }
 
-        run_inner_instruction_or_abort!(super::storage_gas_ext::selfdestruct, context);
+        run_inner_instruction_detention_exempt_or_abort!(
+            super::storage_gas_ext::selfdestruct,
+            context
+        );
         apply_compute_gas_limit!(context);
     }
 
@@ -1141,7 +1270,7 @@ pub mod volatile_data_ext {
             return;
         }
 
-        run_inner_instruction_or_abort!(compute_gas_ext::selfbalance, context);
+        run_inner_instruction_detention_exempt_or_abort!(compute_gas_ext::selfbalance, context);
         apply_compute_gas_limit!(context);
     }
 
@@ -1239,6 +1368,7 @@ pub mod volatile_data_ext {
 
             // Delegate to the existing forward_gas_ext handler via reborrow so that
             // `context` remains usable for `apply_compute_gas_limit!` afterward.
+            let saved = save_exempt_compute_gas_limit!(context);
             {
                 let ctx = InstructionContext::<'_, H, WIRE> {
                     interpreter: &mut *context.interpreter,
@@ -1246,6 +1376,7 @@ pub mod volatile_data_ext {
                 };
                 $inner_fn(ctx);
             }
+            restore_exempt_compute_gas_limit!(context, saved);
 
             // Propagate the detained compute gas limit if the CALL triggered beneficiary
             // access (via `host.load_account_delegated()` inside the CALL handler).
@@ -1265,6 +1396,155 @@ pub mod volatile_data_ext {
     wrap_call_volatile_check!(call_code, "CALLCODE", forward_gas_ext::call_code);
 }
 
+/// Extends memory-expanding opcodes with the interpreter memory limit (REX6+).
+///
+/// Each wrapper reads the memory ranges the opcode will touch from the stack and checks the
+/// resulting memory size against
+/// [`EvmTxRuntimeLimits::interpreter_memory_limit`](crate::EvmTxRuntimeLimits) before running
+/// the REX5 handler of the opcode, so an over-limit expansion is never allocated, regardless of
+/// the gas available. Exceeding the limit halts the transaction with
+/// [`MegaHaltReason::MemoryLimitExceeded`](crate::MegaHaltReason::MemoryLimitExceeded).
+///
+/// The wrappers are the outermost layer: no other wrapper-side work (volatile data checks,
+/// storage gas, compute gas) runs for an over-limit expansion.
+pub mod memory_limit_ext {
+    use super::*;
+
+    /// Returns the word-aligned memory size needed to access `len` bytes at `offset`, `0` for an
+    /// empty access (which never expands memory), or `None` if an operand is missing from the
+    /// stack. Operands beyond `u64` saturate, so they always exceed the limit.
+    #[inline]
+    fn access_end(offset: Option<U256>, len: Option<U256>) -> Option<u64> {
+        let (offset, len) = (offset?, len?);
+        if len.is_zero() {
+            return Some(0);
+        }
+        let end = offset.saturating_to::<u64>().saturating_add(len.saturating_to::<u64>());
+        Some(end.div_ceil(32).saturating_mul(32))
+    }
+
+    /// Checks the memory size `end` against the interpreter memory limit. Returns `false` if
+    /// the interpreter has been halted.
+    #[inline]
+    fn check_memory_limit<WIRE: InterpreterTypes, H: HostExt + ?Sized>(
+        context: &mut InstructionContext<'_, H, WIRE>,
+        end: Option<u64>,
+    ) -> bool {
+        let Some(end) = end else {
+            context.interpreter.halt(InstructionResult::StackUnderflow);
+            return false;
+        };
+        // Accesses within the current memory do not expand it.
+        if end <= context.interpreter.memory.size() as u64 {
+            return true;
+        }
+        let additional_limit = context.host.additional_limit();
+        let mut additional_limit = additional_limit.borrow_mut();
+        if !additional_limit.check_memory_expansion(end) {
+            context.interpreter.halt(additional_limit.exceeding_instruction_result());
+            return false;
+        }
+        true
+    }
+
+    /// Macro to wrap a memory-expanding opcode with the interpreter memory limit check.
+    ///
+    /// Two variants:
+    /// - default: the opcode accesses one or more `(offset, len)` memory ranges, given as the stack
+    ///   positions of their operands. The needed memory size is the largest range end.
+    /// - `@fixed`: the opcode accesses a fixed number of bytes at the offset on the given stack
+    ///   position (`MLOAD`, `MSTORE`, `MSTORE8`).
+    macro_rules! wrap_op_memory_limit {
+        ($fn_name:ident, $opcode_name:expr, $inner_fn:path, $(($offset:literal, $len:literal)),+) => {
+            #[doc = concat!("`", $opcode_name, "` opcode with the interpreter memory limit.")]
+            #[inline]
+            pub fn $fn_name<
+                WIRE: InterpreterTypes<Stack: StackInspectTr>,
+                H: HostExt + ContextTr + JournalInspectTr + ?Sized,
+            >(
+                mut context: InstructionContext<'_, H, WIRE>,
+            ) {
+                let stack = &context.interpreter.stack;
+                let end = [$(access_end(stack.inspect::<$offset>(), stack.inspect::<$len>())),+]
+                    .into_iter()
+                    .try_fold(0, |end, access| access.map(|access| access.max(end)));
+                if check_memory_limit(&mut context, end) {
+                    $inner_fn(context);
+                }
+            }
+        };
+        (@fixed $fn_name:ident, $opcode_name:expr, $inner_fn:path, $offset:literal, $size:literal) => {
+            #[doc = concat!("`", $opcode_name, "` opcode with the interpreter memory limit.")]
+            #[inline]
+            pub fn $fn_name<
+                WIRE: InterpreterTypes<Stack: StackInspectTr>,
+                H: HostExt + ContextTr + JournalInspectTr + ?Sized,
+            >(
+                mut context: InstructionContext<'_, H, WIRE>,
+            ) {
+                let offset = context.interpreter.stack.inspect::<$offset>();
+                let end = access_end(offset, Some(U256::from($size)));
+                if check_memory_limit(&mut context, end) {
+                    $inner_fn(context);
+                }
+            }
+        };
+    }
+
+    wrap_op_memory_limit!(@fixed mload, "MLOAD", compute_gas_ext::mload, 0, 32);
+    wrap_op_memory_limit!(@fixed mstore, "MSTORE", compute_gas_ext::mstore, 0, 32);
+    wrap_op_memory_limit!(@fixed mstore8, "MSTORE8", compute_gas_ext::mstore8, 0, 1);
+    // MCOPY: [dst, src, len].
+    wrap_op_memory_limit!(mcopy, "MCOPY", compute_gas_ext::mcopy, (0, 2), (1, 2));
+    wrap_op_memory_limit!(keccak256, "KECCAK256", compute_gas_ext::keccak256, (0, 1));
+    wrap_op_memory_limit!(calldatacopy, "CALLDATACOPY", compute_gas_ext::calldatacopy, (0, 2));
+    wrap_op_memory_limit!(codecopy, "CODECOPY", compute_gas_ext::codecopy, (0, 2));
+    wrap_op_memory_limit!(
+        returndatacopy,
+        "RETURNDATACOPY",
+        compute_gas_ext::returndatacopy,
+        (0, 2)
+    );
+    wrap_op_memory_limit!(extcodecopy, "EXTCODECOPY", volatile_data_ext::extcodecopy, (1, 3));
+    wrap_op_memory_limit!(ret, "RETURN", compute_gas_ext::ret, (0, 1));
+    wrap_op_memory_limit!(revert, "REVERT", compute_gas_ext::revert, (0, 1));
+    wrap_op_memory_limit!(create, "CREATE", forward_gas_ext::create, (1, 2));
+    wrap_op_memory_limit!(create2, "CREATE2", forward_gas_ext::create2, (1, 2));
+    // CALL / CALLCODE: [gas, to, value, args_offset, args_len, ret_offset, ret_len].
+    wrap_op_memory_limit!(call, "CALL", volatile_data_ext::call, (3, 4), (5, 6));
+    wrap_op_memory_limit!(call_code, "CALLCODE", volatile_data_ext::call_code, (3, 4), (5, 6));
+    // DELEGATECALL / STATICCALL: [gas, to, args_offset, args_len, ret_offset, ret_len].
+    wrap_op_memory_limit!(
+        delegate_call,
+        "DELEGATECALL",
+        volatile_data_ext::delegate_call,
+        (2, 3),
+        (4, 5)
+    );
+    wrap_op_memory_limit!(
+        static_call,
+        "STATICCALL",
+        volatile_data_ext::static_call,
+        (2, 3),
+        (4, 5)
+    );
+
+    /// `LOG` opcode with the interpreter memory limit.
+    pub fn log<
+        const N: usize,
+        WIRE: InterpreterTypes<Stack: StackInspectTr>,
+        H: HostExt + ContextTr + JournalInspectTr + ?Sized,
+    >(
+        mut context: InstructionContext<'_, H, WIRE>,
+    ) {
+        let stack = &context.interpreter.stack;
+        let end = access_end(stack.inspect::<0>(), stack.inspect::<1>());
+        if check_memory_limit(&mut context, end) {
+            additional_limit_ext::log::<N, WIRE, H>(context);
+        }
+    }
+}
+
 /// Extends opcodes with additional limit (kv update limit, data limit, etc.) enforcement.
 pub mod additional_limit_ext {
     use super::*;
@@ -2131,6 +2411,21 @@ pub mod compute_gas_ext {
         };
     }
 
+    /// A [`CustomInstruction`] with compute gas tracking.
+    #[inline]
+    pub fn custom<I: CustomInstruction, WIRE: InterpreterTypes, H: HostExt + ?Sized>(
+        context: InstructionContext<'_, H, WIRE>,
+    ) {
+        let gas_before = context.interpreter.gas.remaining();
+
+        // Call the custom instruction
+        run_inner_instruction_or_abort!(I::execute, context);
+
+        let gas_used = gas_before.saturating_sub(context.interpreter.gas.remaining());
+        let mut additional_limit = context.host.additional_limit().borrow_mut();
+        compute_gas!(context.interpreter, additional_limit, gas_used);
+    }
+
     wrap_op_compute_gas!(stop, "STOP", instructions::control::stop);
     wrap_op_compute_gas!(add, "ADD", instructions::arithmetic::add);
     wrap_op_compute_gas!(mul, "MUL", instructions::arithmetic::mul);
@@ -2307,20 +2602,147 @@ pub mod compute_gas_ext {
     }
 }
 
-/// Trait to inspect the stack elements.
+/// Trait to inspect the stack elements without popping them, e.g. from an inspector or a custom
+/// instruction that needs the operands of the instruction it wraps.
+///
+/// All methods are bounds-checked: a stack too short for the request yields `None`. The names
+/// avoid those of the inherent methods of revm's [`Stack`], which take precedence.
 pub trait StackInspectTr {
+    /// Returns the stack elements, from the bottom to the top.
+    fn as_slice(&self) -> &[U256];
+
     /// Inspect the N-th element of the stack. The top of the stack is the 0-th element.
     /// If the stack is too short, return None.
-    fn inspect<const N: usize>(&self) -> Option<U256>;
+    fn inspect<const N: usize>(&self) -> Option<U256> {
+        self.peek_at(N)
+    }
+
+    /// Returns the `n`-th element of the stack, the top being the 0-th.
+    fn peek_at(&self, n: usize) -> Option<U256> {
+        let data = self.as_slice();
+        data.len().checked_sub(n + 1).map(|index| data[index])
+    }
+
+    /// Returns the top `N` elements of the stack, the top first, i.e. in the order an instruction
+    /// pops its operands.
+    fn peek_n<const N: usize>(&self) -> Option<[U256; N]> {
+        let top = self.top_slice(N)?;
+        Some(core::array::from_fn(|i| top[N - 1 - i]))
+    }
+
+    /// Returns the top `n` elements of the stack as a slice, from the bottom to the top.
+    fn top_slice(&self, n: usize) -> Option<&[U256]> {
+        let data = self.as_slice();
+        data.len().checked_sub(n).map(|start| &data[start..])
+    }
 }
 
 impl StackInspectTr for Stack {
-    fn inspect<const N: usize>(&self) -> Option<U256> {
-        if N >= self.len() {
-            return None;
+    fn as_slice(&self) -> &[U256] {
+        self.data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{context_without_operator_fee, BytecodeBuilder, MemoryDatabase},
+        EvmTxRuntimeLimits, MegaEvm, MegaHaltReason, MegaTransaction,
+    };
+    use alloy_primitives::{address, Address};
+    use revm::{
+        bytecode::opcode::{PUSH0, SSTORE, STOP},
+        context::{result::ExecutionResult, TxEnv},
+        interpreter::interpreter_types::StackTr,
+    };
+
+    const CALLER: Address = address!("4000000000000000000000000000000000000001");
+    const CALLEE: Address = address!("5000000000000000000000000000000000000001");
+    /// An opcode unassigned in every spec.
+    const ANSWER: u8 = 0x0c;
+
+    /// Pushes 42 for 1,000 gas.
+    struct Answer;
+
+    impl CustomInstruction for Answer {
+        fn execute<WIRE: InterpreterTypes, H: HostExt + ?Sized>(
+            context: InstructionContext<'_, H, WIRE>,
+        ) {
+            if !context.interpreter.gas.record_cost(1_000) {
+                context.interpreter.halt(InstructionResult::OutOfGas);
+                return;
+            }
+            if !context.interpreter.stack.push(U256::from(42)) {
+                context.interpreter.halt(InstructionResult::StackOverflow);
+            }
+        }
+    }
+
+    fn run_answer(limits: EvmTxRuntimeLimits) -> (ExecutionResult<MegaHaltReason>, U256) {
+        let code = BytecodeBuilder::default().append_many([ANSWER, PUSH0, SSTORE, STOP]).build();
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000))
+            .account_code(CALLEE, code);
+        let context =
+            context_without_operator_fee(&mut db, MegaSpecId::REX4).with_tx_runtime_limits(limits);
+        let mut tx = MegaTransaction::new(TxEnv {
+            caller: CALLER,
+            gas_limit: 1_000_000,
+            kind: alloy_primitives::TxKind::Call(CALLEE),
+            ..Default::default()
+        });
+        tx.enveloped_tx = Some(Bytes::new());
+        let outcome = MegaEvm::new(context)
+            .with_instruction::<Answer>(ANSWER)
+            .execute_transaction(tx)
+            .expect("tx should execute");
+        let slot = outcome
+            .state
+            .get(&CALLEE)
+            .and_then(|account| account.storage.get(&U256::ZERO))
+            .map(|slot| slot.present_value)
+            .unwrap_or_default();
+        (outcome.result, slot)
+    }
+
+    #[test]
+    fn test_custom_instruction_is_compute_gas_metered() {
+        let (result, slot) = run_answer(EvmTxRuntimeLimits::from_spec(MegaSpecId::REX4));
+        assert!(result.is_success(), "{result:?}");
+        assert_eq!(slot, U256::from(42));
+
+        // The 21,000 intrinsic compute gas fits, but not the 1,000 gas of the custom instruction.
+        let limits =
+            EvmTxRuntimeLimits::from_spec(MegaSpecId::REX4).with_tx_compute_gas_limit(21_500);
+        let (result, slot) = run_answer(limits);
+        assert!(
+            matches!(
+                result,
+                ExecutionResult::Halt {
+                    reason: MegaHaltReason::ComputeGasLimitExceeded { .. },
+                    ..
+                }
+            ),
+            "{result:?}"
+        );
+        assert_eq!(slot, U256::ZERO);
+    }
+
+    #[test]
+    fn test_stack_inspection_is_bounds_checked() {
+        let mut stack = Stack::new();
+        for value in 1..=3_u64 {
+            assert!(stack.push(U256::from(value)));
         }
-        let index = self.len() - 1 - N;
-        // SAFETY: the index must be within the bounds of the stack
-        Some(unsafe { *self.data().get_unchecked(index) })
+
+        assert_eq!(stack.inspect::<0>(), Some(U256::from(3)));
+        assert_eq!(stack.peek_at(2), Some(U256::from(1)));
+        assert_eq!(stack.peek_at(3), None);
+        assert_eq!(stack.peek_n::<2>(), Some([U256::from(3), U256::from(2)]));
+        assert_eq!(stack.peek_n::<4>(), None);
+        assert_eq!(stack.top_slice(2), Some(&[U256::from(2), U256::from(3)] as &[U256]));
+        assert_eq!(stack.top_slice(0), Some(&[] as &[U256]));
+        assert_eq!(stack.top_slice(4), None);
     }
 }
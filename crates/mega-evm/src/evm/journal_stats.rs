@@ -0,0 +1,39 @@
+//! Journal checkpoint statistics of a transaction, for diagnosing deep reverts.
+
+use serde::{Deserialize, Serialize};
+
+/// Statistics of the journal checkpoints taken by a transaction.
+///
+/// Every frame that runs bytecode executes inside a journal checkpoint, which is committed when
+/// the frame succeeds and reverted, discarding the journal entries recorded since, when it reverts
+/// or halts. Pathological re-entrancy shows up as deep checkpoint nesting, and costly deep reverts
+/// as many reverted entries.
+///
+/// Only the checkpoints of frames that run bytecode are counted: calls to precompiles and system
+/// contract interceptors, and calls that fail before running any code, are not.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalStats {
+    /// The maximum number of checkpoints open at once, i.e. the deepest frame nesting.
+    pub max_depth: usize,
+    /// The number of checkpoints taken.
+    pub checkpoints: u64,
+    /// The number of checkpoints reverted.
+    pub reverted_checkpoints: u64,
+    /// The number of journal entries discarded by reverted checkpoints, including the entries of
+    /// committed inner frames discarded along with them.
+    pub reverted_entries: u64,
+}
+
+impl JournalStats {
+    /// Records that the checkpoint of a frame at `depth` (1 for the outermost frame) was
+    /// committed, or reverted discarding `reverted_entries` journal entries.
+    pub(crate) fn record_frame_end(&mut self, depth: usize, reverted_entries: Option<usize>) {
+        self.max_depth = self.max_depth.max(depth);
+        self.checkpoints += 1;
+        if let Some(entries) = reverted_entries {
+            self.reverted_checkpoints += 1;
+            self.reverted_entries += entries as u64;
+        }
+    }
+}
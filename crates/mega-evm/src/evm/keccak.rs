@@ -0,0 +1,57 @@
+//! Selection of the keccak256 implementation.
+//!
+//! The interpreter (`KECCAK256`, `CREATE2`), address derivation and state hashing all hash with
+//! [`alloy_primitives::keccak256`], whose implementation is chosen at compile time by the
+//! features of `alloy-primitives`. This crate forwards them:
+//!
+//! - default: the `sha3` crate, built with its assembly backend, which uses the ARMv8 SHA-3
+//!   instructions on `aarch64` targets compiled with `target-feature=+sha3`;
+//! - `asm-keccak`: the `keccak-asm` crate, the CRYPTOGAMS assembly implementations;
+//! - `tiny-keccak`: the portable `tiny-keccak` crate.
+//!
+//! `asm-keccak` takes precedence over `tiny-keccak` when both are enabled. All the
+//! implementations produce the same hashes; only their speed differs, see the `keccak` benchmark.
+
+use core::fmt::{self, Display, Formatter};
+
+/// A keccak256 implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeccakBackend {
+    /// The `sha3` crate, accelerated with the CPU SHA-3 extensions where available.
+    Sha3,
+    /// The `keccak-asm` crate.
+    Asm,
+    /// The `tiny-keccak` crate.
+    TinyKeccak,
+}
+
+impl KeccakBackend {
+    /// Returns the implementation selected by the features of this crate.
+    ///
+    /// Features are unified across the dependency graph, so another crate enabling a keccak
+    /// feature of `alloy-primitives` directly is not reflected here.
+    pub const fn selected() -> Self {
+        if cfg!(all(feature = "asm-keccak", not(miri))) {
+            Self::Asm
+        } else if cfg!(feature = "tiny-keccak") {
+            Self::TinyKeccak
+        } else {
+            Self::Sha3
+        }
+    }
+
+    /// Returns the name of the implementation.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Sha3 => "sha3",
+            Self::Asm => "asm-keccak",
+            Self::TinyKeccak => "tiny-keccak",
+        }
+    }
+}
+
+impl Display for KeccakBackend {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
@@ -16,6 +16,10 @@ pub struct EvmTxRuntimeLimits {
     pub block_env_access_compute_gas_limit: u64,
     /// Compute gas limit when accessing oracle data.
     pub oracle_access_compute_gas_limit: u64,
+    /// Maximum memory size (in bytes) of a single interpreter. Only enforced from `REX6`.
+    pub interpreter_memory_limit: u64,
+    /// Maximum number of frames created by a transaction. Only enforced from `REX6`.
+    pub tx_frame_limit: u64,
 }
 
 impl EvmTxRuntimeLimits {
@@ -41,6 +45,8 @@ impl EvmTxRuntimeLimits {
             tx_state_growth_limit: u64::MAX,
             block_env_access_compute_gas_limit: u64::MAX,
             oracle_access_compute_gas_limit: u64::MAX,
+            interpreter_memory_limit: u64::MAX,
+            tx_frame_limit: u64::MAX,
         }
     }
 
@@ -72,6 +78,7 @@ impl EvmTxRuntimeLimits {
             block_env_access_compute_gas_limit:
                 crate::constants::mini_rex::BLOCK_ENV_ACCESS_COMPUTE_GAS,
             oracle_access_compute_gas_limit: crate::constants::mini_rex::ORACLE_ACCESS_COMPUTE_GAS,
+            ..Self::equivalence()
         }
     }
 
@@ -100,9 +107,13 @@ impl EvmTxRuntimeLimits {
 
     /// Limits for the `REX6` spec.
     ///
-    /// Currently identical to Rex5 limits.
+    /// Adds the interpreter memory limit and the frame limit to the Rex5 limits.
     fn rex6() -> Self {
-        Self::rex5()
+        Self {
+            interpreter_memory_limit: crate::constants::rex6::INTERPRETER_MEMORY_LIMIT,
+            tx_frame_limit: crate::constants::rex6::TX_FRAME_LIMIT,
+            ..Self::rex5()
+        }
     }
 }
 
@@ -148,4 +159,16 @@ impl EvmTxRuntimeLimits {
         self.oracle_access_compute_gas_limit = oracle_access_compute_gas_limit;
         self
     }
+
+    /// Sets the maximum memory size of a single interpreter.
+    pub fn with_interpreter_memory_limit(mut self, interpreter_memory_limit: u64) -> Self {
+        self.interpreter_memory_limit = interpreter_memory_limit;
+        self
+    }
+
+    /// Sets the maximum number of frames created by a transaction.
+    pub fn with_tx_frame_limit(mut self, tx_frame_limit: u64) -> Self {
+        self.tx_frame_limit = tx_frame_limit;
+        self
+    }
 }
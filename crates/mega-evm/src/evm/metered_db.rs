@@ -0,0 +1,253 @@
+//! Database I/O metering, for correlating slow transactions with their state access patterns.
+
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use alloy_primitives::{Address, B256};
+use revm::{
+    database::DatabaseRef,
+    primitives::{HashMap, StorageKey, StorageValue},
+    state::{Account, AccountInfo, Bytecode},
+    Database, DatabaseCommit,
+};
+use serde::{Deserialize, Serialize};
+
+/// The number of bytes returned by an account lookup that found the account: its balance, nonce
+/// and code hash.
+const ACCOUNT_INFO_BYTES: u64 = 32 + 8 + 32;
+
+/// The lookups served by a [`MeteredDb`] and the bytes they returned.
+///
+/// Only the lookups that reach the metered database are counted: wrapped in a
+/// [`State`](revm::database::State), reads served from its cache never reach it, so the counts
+/// are the actual I/O against the backend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbIoStats {
+    /// The number of account lookups.
+    pub basic_reads: u64,
+    /// The number of storage slot lookups.
+    pub storage_reads: u64,
+    /// The number of bytecode lookups.
+    pub code_reads: u64,
+    /// The number of block hash lookups.
+    pub block_hash_reads: u64,
+    /// The bytes returned by all the lookups. An account found counts its balance, nonce and code
+    /// hash, a missing account nothing, and a bytecode its original length.
+    pub bytes_read: u64,
+}
+
+impl DbIoStats {
+    /// Returns the total number of lookups.
+    pub fn total_reads(&self) -> u64 {
+        self.basic_reads + self.storage_reads + self.code_reads + self.block_hash_reads
+    }
+}
+
+#[derive(Debug, Default)]
+struct DbIoCounters {
+    basic_reads: AtomicU64,
+    storage_reads: AtomicU64,
+    code_reads: AtomicU64,
+    block_hash_reads: AtomicU64,
+    bytes_read: AtomicU64,
+}
+
+/// A handle to the counters of a [`MeteredDb`], which stays readable while the database is owned
+/// by an EVM.
+///
+/// Set on a context with
+/// [`MegaContext::with_db_io_meter`](crate::MegaContext::with_db_io_meter), the counters are reset
+/// when each transaction starts and reported in
+/// [`MegaTransactionOutcome::db_io`](crate::MegaTransactionOutcome::db_io).
+#[derive(Debug, Clone, Default)]
+pub struct DbIoMeter {
+    counters: Arc<DbIoCounters>,
+}
+
+impl DbIoMeter {
+    /// Returns the lookups counted since the last reset.
+    pub fn stats(&self) -> DbIoStats {
+        let counters = &self.counters;
+        DbIoStats {
+            basic_reads: counters.basic_reads.load(Ordering::Relaxed),
+            storage_reads: counters.storage_reads.load(Ordering::Relaxed),
+            code_reads: counters.code_reads.load(Ordering::Relaxed),
+            block_hash_reads: counters.block_hash_reads.load(Ordering::Relaxed),
+            bytes_read: counters.bytes_read.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Resets the counters to zero.
+    pub fn reset(&self) {
+        let counters = &self.counters;
+        counters.basic_reads.store(0, Ordering::Relaxed);
+        counters.storage_reads.store(0, Ordering::Relaxed);
+        counters.code_reads.store(0, Ordering::Relaxed);
+        counters.block_hash_reads.store(0, Ordering::Relaxed);
+        counters.bytes_read.store(0, Ordering::Relaxed);
+    }
+
+    fn record(&self, counter: &AtomicU64, bytes: u64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+        self.counters.bytes_read.fetch_add(bytes, Ordering::Relaxed);
+    }
+}
+
+/// A database counting the lookups it serves and the bytes they return.
+///
+/// Failed lookups are not counted. Commits are forwarded to the wrapped database unmetered.
+#[derive(Debug, Clone)]
+pub struct MeteredDb<DB> {
+    inner: DB,
+    meter: DbIoMeter,
+}
+
+impl<DB> MeteredDb<DB> {
+    /// Wraps `inner` with fresh counters.
+    pub fn new(inner: DB) -> Self {
+        Self { inner, meter: DbIoMeter::default() }
+    }
+
+    /// Returns a handle to the counters.
+    pub fn meter(&self) -> DbIoMeter {
+        self.meter.clone()
+    }
+
+    /// Returns the wrapped database.
+    pub fn inner(&self) -> &DB {
+        &self.inner
+    }
+
+    /// Consumes the wrapper and returns the wrapped database.
+    pub fn into_inner(self) -> DB {
+        self.inner
+    }
+
+    fn record_basic(&self, info: &Option<AccountInfo>) {
+        let bytes = if info.is_some() { ACCOUNT_INFO_BYTES } else { 0 };
+        self.meter.record(&self.meter.counters.basic_reads, bytes);
+    }
+
+    fn record_code(&self, code: &Bytecode) {
+        self.meter.record(&self.meter.counters.code_reads, code.original_bytes().len() as u64);
+    }
+
+    fn record_storage(&self) {
+        self.meter.record(&self.meter.counters.storage_reads, 32);
+    }
+
+    fn record_block_hash(&self) {
+        self.meter.record(&self.meter.counters.block_hash_reads, 32);
+    }
+}
+
+impl<DB: Database> Database for MeteredDb<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.inner.basic(address)?;
+        self.record_basic(&info);
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let code = self.inner.code_by_hash(code_hash)?;
+        self.record_code(&code);
+        Ok(code)
+    }
+
+    fn storage(
+        &mut self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        let value = self.inner.storage(address, index)?;
+        self.record_storage();
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        let hash = self.inner.block_hash(number)?;
+        self.record_block_hash();
+        Ok(hash)
+    }
+}
+
+impl<DB: DatabaseRef> DatabaseRef for MeteredDb<DB> {
+    type Error = DB::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.inner.basic_ref(address)?;
+        self.record_basic(&info);
+        Ok(info)
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let code = self.inner.code_by_hash_ref(code_hash)?;
+        self.record_code(&code);
+        Ok(code)
+    }
+
+    fn storage_ref(
+        &self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        let value = self.inner.storage_ref(address, index)?;
+        self.record_storage();
+        Ok(value)
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        let hash = self.inner.block_hash_ref(number)?;
+        self.record_block_hash();
+        Ok(hash)
+    }
+}
+
+impl<DB: DatabaseCommit> DatabaseCommit for MeteredDb<DB> {
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        self.inner.commit(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, Bytes, U256};
+    use revm::database::{CacheDB, EmptyDB};
+
+    const ALICE: Address = address!("0000000000000000000000000000000000100001");
+
+    #[test]
+    fn test_metered_db_counts_lookups_and_bytes() {
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00, 0x00]));
+        let mut cache = CacheDB::new(EmptyDB::default());
+        cache.insert_account_info(
+            ALICE,
+            AccountInfo { code_hash: code.hash_slow(), code: Some(code), ..Default::default() },
+        );
+        let mut db = MeteredDb::new(cache);
+        let meter = db.meter();
+
+        let info = db.basic(ALICE).unwrap().unwrap();
+        db.basic(Address::ZERO).unwrap();
+        db.code_by_hash(info.code_hash).unwrap();
+        db.storage(ALICE, U256::from(1)).unwrap();
+        db.block_hash_ref(7).unwrap();
+
+        let stats = meter.stats();
+        assert_eq!(stats.basic_reads, 2);
+        assert_eq!(stats.storage_reads, 1);
+        assert_eq!(stats.code_reads, 1);
+        assert_eq!(stats.block_hash_reads, 1);
+        assert_eq!(stats.total_reads(), 5);
+        assert_eq!(stats.bytes_read, ACCOUNT_INFO_BYTES + 3 + 32 + 32);
+
+        meter.reset();
+        assert_eq!(meter.stats(), DbIoStats::default());
+    }
+}
@@ -25,15 +25,29 @@
 //! - **`REX4`**: Per-call-frame resource budgets, relative gas detention, storage gas stipend,
 //!   `MegaAccessControl` and `MegaLimitControl` system contracts
 
+mod block_hash;
+mod call_graph;
+mod chain;
+mod conflict;
 mod context;
+mod divergence;
+mod error;
 mod execution;
 mod factory;
+mod fee;
+mod hooks;
 mod host;
 mod instructions;
 mod interfaces;
+mod journal_stats;
+mod keccak;
 mod limit;
+mod metered_db;
+mod opcode_profile;
+mod overlay;
 mod precompiles;
 mod result;
+mod simulation;
 mod spec;
 mod state;
 
@@ -41,17 +55,31 @@ mod state;
 use alloc as std;
 use std::{collections::BTreeMap, vec::Vec};
 
-use alloy_primitives::{Address, B256};
+use alloy_primitives::{Address, B256, U256};
+pub use block_hash::*;
+pub use call_graph::*;
+pub use chain::*;
+pub use conflict::*;
 pub use context::*;
+pub use divergence::*;
+pub use error::*;
 pub use execution::*;
 pub use factory::*;
+pub use fee::*;
+pub use hooks::*;
 pub use host::*;
 pub use instructions::*;
 #[allow(unused_imports, unreachable_pub)]
 pub use interfaces::*;
+pub use journal_stats::*;
+pub use keccak::*;
 pub use limit::*;
+pub use metered_db::*;
+pub use opcode_profile::*;
+pub use overlay::*;
 pub use precompiles::*;
 pub use result::*;
+pub use simulation::*;
 pub use spec::*;
 pub use state::*;
 
@@ -204,6 +232,14 @@ impl<DB: Database, INSP, ExtEnvs: ExternalEnvTypes> MegaEvm<DB, INSP, ExtEnvs> {
         Self { inner, inspect: self.inspect }
     }
 
+    /// Registers the custom instruction `I` at `opcode`, replacing the instruction there, if any.
+    ///
+    /// See [`MegaInstructions::with_instruction`].
+    pub fn with_instruction<I: CustomInstruction>(mut self, opcode: u8) -> Self {
+        self.inner.instruction.insert_instruction::<I>(opcode);
+        self
+    }
+
     /// Adds or overrides dynamic precompiles in the EVM.
     ///
     /// # Parameters
@@ -325,19 +361,79 @@ where
         } else {
             ExecuteEvm::transact(self, tx)?
         };
+        let accessed_block_hashes = self.ctx_ref().accessed_block_hashes.clone();
+        let accessed_oracle_slots = self.ctx_ref().accessed_oracle_slots.clone();
+        let journal = self.ctx().take_exported_journal();
+        let volatile_data_accessed =
+            self.ctx_ref().volatile_data_tracker.borrow().get_volatile_data_accessed();
+        let detained_gas_refund = self.ctx_ref().detained_gas_refund;
+        let call_graph = self.ctx().take_call_graph();
+        let journal_stats = self.ctx().take_journal_stats();
+        let db_io = self.ctx_ref().db_io_stats();
+        let opcode_profile = self.ctx().take_opcode_profile();
         let additional_limit = self.ctx().additional_limit.borrow();
-        let LimitUsage { data_size, kv_updates, compute_gas, state_growth } =
+        let LimitUsage { data_size, da_data_size, kv_updates, compute_gas, state_growth, .. } =
             additional_limit.get_usage();
         Ok(MegaTransactionOutcome {
             result,
             state,
             data_size,
+            da_data_size,
             kv_updates,
             compute_gas_used: compute_gas,
             state_growth_used: state_growth,
+            accessed_block_hashes,
+            accessed_oracle_slots,
+            journal,
+            volatile_data_accessed,
+            detained_gas_refund,
+            call_graph,
+            journal_stats,
+            db_io,
+            opcode_profile,
         })
     }
 
+    /// Execute a transaction like [`MegaEvm::execute_transaction`] and also return its additional
+    /// limit usage.
+    ///
+    /// The usage and the volatile data accessed are snapshotted by the handler when the
+    /// transaction ends, so no `RefCell` of the context is borrowed after execution. Unlike
+    /// [`MegaEvm::execute_transaction`], this cannot panic if an inspector still holds a borrow of
+    /// the limit trackers.
+    pub fn execute_transaction_owned(
+        &mut self,
+        tx: MegaTransaction,
+    ) -> Result<(MegaTransactionOutcome, LimitUsage), EVMError<DB::Error, MegaTransactionError>>
+    {
+        let ResultAndState { result, state } = if self.inspect {
+            InspectEvm::inspect_tx(self, tx)?
+        } else {
+            ExecuteEvm::transact(self, tx)?
+        };
+        let ctx = self.ctx();
+        let usage = ctx.tx_limit_usage;
+        let outcome = MegaTransactionOutcome {
+            result,
+            state,
+            data_size: usage.data_size,
+            da_data_size: usage.da_data_size,
+            kv_updates: usage.kv_updates,
+            compute_gas_used: usage.compute_gas,
+            state_growth_used: usage.state_growth,
+            accessed_block_hashes: ctx.accessed_block_hashes.clone(),
+            accessed_oracle_slots: ctx.accessed_oracle_slots.clone(),
+            journal: ctx.take_exported_journal(),
+            volatile_data_accessed: ctx.tx_volatile_data_accessed,
+            detained_gas_refund: ctx.detained_gas_refund,
+            call_graph: ctx.take_call_graph(),
+            journal_stats: ctx.take_journal_stats(),
+            db_io: ctx.db_io_stats(),
+            opcode_profile: ctx.take_opcode_profile(),
+        };
+        Ok((outcome, usage))
+    }
+
     /// Inspect a transaction and return the outcome. The inspector used is the one set up already
     /// in the EVM. Use [`MegaEvm::with_inspector`] to set up a custom inspector.
     ///
@@ -357,16 +453,36 @@ where
         tx: MegaTransaction,
     ) -> Result<MegaTransactionOutcome, EVMError<DB::Error, MegaTransactionError>> {
         let ResultAndState { result, state } = InspectEvm::inspect_tx(self, tx)?;
+        let accessed_block_hashes = self.ctx_ref().accessed_block_hashes.clone();
+        let accessed_oracle_slots = self.ctx_ref().accessed_oracle_slots.clone();
+        let journal = self.ctx().take_exported_journal();
+        let volatile_data_accessed =
+            self.ctx_ref().volatile_data_tracker.borrow().get_volatile_data_accessed();
+        let detained_gas_refund = self.ctx_ref().detained_gas_refund;
+        let call_graph = self.ctx().take_call_graph();
+        let journal_stats = self.ctx().take_journal_stats();
+        let db_io = self.ctx_ref().db_io_stats();
+        let opcode_profile = self.ctx().take_opcode_profile();
         let additional_limit = self.ctx().additional_limit.borrow();
-        let LimitUsage { data_size, kv_updates, compute_gas, state_growth } =
+        let LimitUsage { data_size, da_data_size, kv_updates, compute_gas, state_growth, .. } =
             additional_limit.get_usage();
         Ok(MegaTransactionOutcome {
             result,
             state,
             data_size,
+            da_data_size,
             kv_updates,
             compute_gas_used: compute_gas,
             state_growth_used: state_growth,
+            accessed_block_hashes,
+            accessed_oracle_slots,
+            journal,
+            volatile_data_accessed,
+            detained_gas_refund,
+            call_graph,
+            journal_stats,
+            db_io,
+            opcode_profile,
         })
     }
 
@@ -378,45 +494,53 @@ where
     pub fn get_accessed_bucket_ids(&self) -> Vec<BucketId> {
         self.ctx_ref().dynamic_storage_gas_cost.borrow().get_bucket_ids()
     }
-}
 
-impl<DB: Database + BlockHashes, INSP, ExtEnvs: ExternalEnvTypes> MegaEvm<DB, INSP, ExtEnvs> {
-    /// Get the block hashes used during transaction execution.
+    /// Get the block hashes read by `BLOCKHASH` during the last executed transaction.
     ///
     /// # Returns
     ///
-    /// Returns the block hashes used during transaction execution.
+    /// Returns the block hashes read during the last executed transaction, keyed by block number.
     pub fn get_accessed_block_hashes(&self) -> BTreeMap<u64, B256> {
-        self.db_ref().get_accessed_block_hashes()
+        self.ctx_ref().accessed_block_hashes.clone()
+    }
+
+    /// Get the oracle contract storage slots read during the last executed transaction.
+    ///
+    /// # Returns
+    ///
+    /// Returns the slots read during the last executed transaction, with the first value
+    /// observed for each slot.
+    pub fn get_accessed_oracle_slots(&self) -> BTreeMap<U256, U256> {
+        self.ctx_ref().accessed_oracle_slots.clone()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{test_utils::MemoryDatabase, EmptyExternalEnv};
+    use crate::{
+        test_utils::{context_without_operator_fee, BytecodeBuilder, MemoryDatabase},
+        EmptyExternalEnv,
+    };
     use alloy_primitives::{address, Bytes, U256};
+    use core::cell::RefCell;
     use revm::{
+        bytecode::opcode::{BLOCKHASH, CALL, GAS, LOG0, POP, PUSH0, SSTORE},
         context::{
             result::{ExecResultAndState, ExecutionResult},
             ContextSetters, TxEnv,
         },
-        database::State,
         inspector::NoOpInspector,
         state::EvmState,
-        ExecuteCommitEvm, ExecuteEvm, InspectEvm, SystemCallEvm,
+        DatabaseRef, ExecuteCommitEvm, ExecuteEvm, InspectEvm, JournalEntry, SystemCallEvm,
     };
+    use std::rc::Rc;
 
     const CALLER: Address = address!("4000000000000000000000000000000000000001");
     const CALLEE: Address = address!("5000000000000000000000000000000000000001");
 
     fn configure_context<DB: Database>(db: DB) -> MegaContext<DB, EmptyExternalEnv> {
-        let mut context = MegaContext::new(db, MegaSpecId::REX4);
-        context.modify_chain(|chain| {
-            chain.operator_fee_scalar = Some(U256::ZERO);
-            chain.operator_fee_constant = Some(U256::ZERO);
-        });
-        context
+        context_without_operator_fee(db, MegaSpecId::REX4)
     }
 
     fn tx_env() -> TxEnv {
@@ -556,11 +680,7 @@ mod tests {
         let mut db = MemoryDatabase::default()
             .account_balance(CALLER, U256::from(1_000_000))
             .account_code(CALLEE, Bytes::new());
-        let mut context = MegaContext::new(&mut db, MegaSpecId::REX5);
-        context.modify_chain(|chain| {
-            chain.operator_fee_scalar = Some(U256::ZERO);
-            chain.operator_fee_constant = Some(U256::ZERO);
-        });
+        let mut context = context_without_operator_fee(&mut db, MegaSpecId::REX5);
         context.block.gas_limit = 100_000_000;
         let mut evm = MegaEvm::new(context);
 
@@ -577,13 +697,305 @@ mod tests {
     }
 
     #[test]
-    fn test_mega_evm_exposes_state_wrapper_block_hashes() {
-        let mut db = MemoryDatabase::default();
-        let mut state = State::builder().with_database(&mut db).build();
-        state.block_hashes.insert(7, B256::from([7_u8; 32]));
+    fn test_execute_transaction_reports_accessed_block_hashes() {
+        let code = BytecodeBuilder::default()
+            .push_number(7_u8)
+            .append(BLOCKHASH)
+            .append(POP)
+            .stop()
+            .build();
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000))
+            .account_code(CALLEE, code);
+        let mut context = configure_context(&mut db);
+        context.modify_block(|block| block.number = U256::from(10));
+        let mut evm = MegaEvm::new(context);
+
+        let outcome = evm.execute_transaction(mega_tx()).unwrap();
+        assert!(outcome.result.is_success());
+        assert_eq!(outcome.accessed_block_hashes.keys().copied().collect::<Vec<_>>(), [7]);
+        assert_eq!(evm.get_accessed_block_hashes(), outcome.accessed_block_hashes);
+    }
+
+    #[test]
+    fn test_block_hash_provider_serves_blockhash() {
+        let code = BytecodeBuilder::default()
+            .push_number(7_u8)
+            .append(BLOCKHASH)
+            .append(POP)
+            .push_number(8_u8)
+            .append(BLOCKHASH)
+            .append(POP)
+            .stop()
+            .build();
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000))
+            .account_code(CALLEE, code);
+        let provided = B256::repeat_byte(0x77);
+        let provider = Rc::new(RefCell::new(BlockHashRingBuffer::new()));
+        provider.borrow_mut().push(7, provided);
+        let mut context = configure_context(&mut db).with_block_hash_provider(provider);
+        context.modify_block(|block| block.number = U256::from(10));
+        let mut evm = MegaEvm::new(context);
+
+        let outcome = evm.execute_transaction(mega_tx()).unwrap();
+        assert!(outcome.result.is_success());
+        assert_eq!(outcome.accessed_block_hashes[&7], provided);
+        // Hashes the provider does not know are read from the database.
+        assert_eq!(
+            outcome.accessed_block_hashes[&8],
+            db.block_hash_ref(8).unwrap(),
+            "unknown hashes fall back to the database"
+        );
+    }
+
+    #[test]
+    fn test_execute_transaction_exports_journal() {
+        let code =
+            BytecodeBuilder::default().push_number(1_u8).append(PUSH0).append(SSTORE).build();
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000))
+            .account_code(CALLEE, code);
+        let outcome = MegaEvm::new(configure_context(&mut db)).execute_transaction(mega_tx());
+        assert!(outcome.unwrap().journal.is_none(), "journal export is disabled by default");
+
+        let mut evm = MegaEvm::new(configure_context(&mut db).with_journal_export(true));
+        let outcome = evm.execute_transaction(mega_tx()).unwrap();
+        let journal = outcome.journal.expect("journal export is enabled");
+        assert!(journal.contains(&JournalEntry::StorageChanged {
+            address: CALLEE,
+            key: U256::ZERO,
+            had_value: U256::ZERO,
+        }));
+        assert!(journal.contains(&JournalEntry::NonceChange { address: CALLER }));
+
+        let mut rejected = mega_tx();
+        rejected.base.value = U256::from(10_000_000);
+        assert!(evm.execute_transaction(rejected).is_err());
+        assert_eq!(evm.ctx_ref().exported_journal(), Some(&[][..]));
+    }
+
+    #[test]
+    fn test_execute_transaction_owned_matches_execute_transaction() {
+        let code =
+            BytecodeBuilder::default().push_number(1_u8).append(PUSH0).append(SSTORE).build();
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000))
+            .account_code(CALLEE, code);
+        let expected =
+            MegaEvm::new(configure_context(&mut db)).execute_transaction(mega_tx()).unwrap();
+
+        for inspect in [false, true] {
+            let mut evm = MegaEvm::new(configure_context(&mut db));
+            let (outcome, usage) = if inspect {
+                evm.with_inspector(NoOpInspector).execute_transaction_owned(mega_tx())
+            } else {
+                evm.execute_transaction_owned(mega_tx())
+            }
+            .unwrap();
+            assert_eq!(outcome.result, expected.result);
+            assert_eq!(
+                (usage.data_size, usage.kv_updates, usage.compute_gas, usage.state_growth),
+                (
+                    expected.data_size,
+                    expected.kv_updates,
+                    expected.compute_gas_used,
+                    expected.state_growth_used
+                )
+            );
+            assert!(usage.kv_updates > 0, "the SSTORE is counted");
+            assert_eq!(
+                (outcome.data_size, outcome.kv_updates, outcome.compute_gas_used),
+                (usage.data_size, usage.kv_updates, usage.compute_gas)
+            );
+        }
+    }
+
+    #[test]
+    fn test_execute_transaction_records_call_graph() {
+        const IDENTITY: Address = address!("0000000000000000000000000000000000000004");
+        let code = BytecodeBuilder::default()
+            .append_many([PUSH0, PUSH0, PUSH0, PUSH0, PUSH0])
+            .push_address(IDENTITY)
+            .append(GAS)
+            .append(CALL)
+            .append(POP)
+            .stop()
+            .build();
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000))
+            .account_code(CALLEE, code);
+        let outcome = MegaEvm::new(configure_context(&mut db)).execute_transaction(mega_tx());
+        assert!(
+            outcome.unwrap().call_graph.is_none(),
+            "call graph recording is disabled by default"
+        );
+
+        for inspect in [false, true] {
+            let mut evm = MegaEvm::new(configure_context(&mut db).with_call_graph_recording(true));
+            let outcome = if inspect {
+                evm.with_inspector(NoOpInspector).execute_transaction(mega_tx())
+            } else {
+                evm.execute_transaction(mega_tx())
+            };
+            let root = outcome.unwrap().call_graph.expect("call graph recording is enabled");
+            assert_eq!((root.kind, root.caller, root.callee), (CallKind::Call, CALLER, CALLEE));
+            assert!(root.is_success());
+            assert!(root.gas_limit < tx_env().gas_limit, "the intrinsic gas is not forwarded");
+            assert_eq!(root.calls.len(), 1);
+            let call = &root.calls[0];
+            assert_eq!((call.kind, call.caller, call.callee), (CallKind::Call, CALLEE, IDENTITY));
+            assert!(call.is_success() && call.calls.is_empty());
+            assert!(call.gas_used > 0 && call.gas_used < root.gas_used);
+        }
+    }
+
+    #[test]
+    fn test_execute_transaction_collects_journal_stats() {
+        const REVERTER: Address = address!("5000000000000000000000000000000000000002");
+        let code = BytecodeBuilder::default()
+            .sstore(U256::from(1), U256::from(1))
+            .append_many([PUSH0, PUSH0, PUSH0, PUSH0, PUSH0])
+            .push_address(REVERTER)
+            .append(GAS)
+            .append(CALL)
+            .append(POP)
+            .stop()
+            .build();
+        let reverter = BytecodeBuilder::default()
+            .sstore(U256::from(1), U256::from(1))
+            .sstore(U256::from(2), U256::from(2))
+            .revert()
+            .build();
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000))
+            .account_code(CALLEE, code)
+            .account_code(REVERTER, reverter);
+        let outcome = MegaEvm::new(configure_context(&mut db)).execute_transaction(mega_tx());
+        assert!(outcome.unwrap().journal_stats.is_none(), "journal stats are disabled by default");
+
+        // The EQUIVALENCE spec takes the fast path of `frame_run`.
+        for spec in [MegaSpecId::EQUIVALENCE, MegaSpecId::REX4] {
+            for inspect in [false, true] {
+                let context = context_without_operator_fee(&mut db, spec).with_journal_stats(true);
+                let mut evm = MegaEvm::new(context);
+                let outcome = if inspect {
+                    evm.with_inspector(NoOpInspector).execute_transaction(mega_tx())
+                } else {
+                    evm.execute_transaction(mega_tx())
+                };
+                let outcome = outcome.unwrap();
+                assert!(outcome.result.is_success());
+                let stats = outcome.journal_stats.expect("journal stats are enabled");
+                assert_eq!((stats.max_depth, stats.checkpoints), (2, 2), "{spec:?}");
+                assert_eq!(stats.reverted_checkpoints, 1, "{spec:?}");
+                // The two storage changes of the reverted frame, at least.
+                assert!(stats.reverted_entries >= 2, "{spec:?}: {stats:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_execute_transaction_reports_db_io() {
+        let code = BytecodeBuilder::default().sstore(U256::from(1), U256::from(1)).stop().build();
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000))
+            .account_code(CALLEE, code);
+        let outcome = MegaEvm::new(configure_context(&mut db)).execute_transaction(mega_tx());
+        assert!(outcome.unwrap().db_io.is_none(), "db io metering is disabled by default");
+
+        let mut run = |reads_before_tx: bool| {
+            let mut metered = MeteredDb::new(&mut db);
+            let meter = metered.meter();
+            if reads_before_tx {
+                metered.storage(CALLEE, U256::from(2)).unwrap();
+            }
+            let context = configure_context(metered).with_db_io_meter(Some(meter.clone()));
+            let outcome = MegaEvm::new(context).execute_transaction(mega_tx()).unwrap();
+            assert!(outcome.result.is_success());
+            let stats = outcome.db_io.expect("db io metering is enabled");
+            assert_eq!(stats, meter.stats());
+            stats
+        };
+        let stats = run(false);
+        assert!(stats.basic_reads >= 2, "{stats:?}");
+        assert!(stats.storage_reads >= 1, "{stats:?}");
+        // The reads before the transaction are not attributed to it.
+        assert_eq!(run(true), stats);
+    }
+
+    #[test]
+    fn test_execute_transaction_reports_no_opcode_profile_by_default() {
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000))
+            .account_code(CALLEE, BytecodeBuilder::default().stop().build());
+        let outcome = MegaEvm::new(configure_context(&mut db)).execute_transaction(mega_tx());
+        assert!(outcome.unwrap().opcode_profile.is_none(), "profiling is disabled by default");
+    }
+
+    #[cfg(feature = "opcode-profiling")]
+    #[test]
+    fn test_execute_transaction_profiles_opcodes() {
+        use revm::bytecode::opcode::{ADD, SLOAD, STOP};
+
+        let code = BytecodeBuilder::default()
+            .repeat(10, |b| b.push_number(1_u8).append_many([SLOAD, POP]))
+            .append_many([PUSH0, PUSH0, ADD, POP])
+            .stop()
+            .build();
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000))
+            .account_code(CALLEE, code);
+
+        // The EQUIVALENCE spec takes the fast path of `frame_run`.
+        for spec in [MegaSpecId::EQUIVALENCE, MegaSpecId::REX4] {
+            let context = context_without_operator_fee(&mut db, spec).with_opcode_profiling(true);
+            let mut evm = MegaEvm::new(context);
+            let outcome = evm.execute_transaction(mega_tx()).unwrap();
+            assert!(outcome.result.is_success());
+            let profile = outcome.opcode_profile.expect("opcode profiling is enabled");
+            assert_eq!(profile.timing(SLOAD).count, 10, "{spec:?}");
+            assert!(profile.timing(SLOAD).gas >= 10 * 100, "{spec:?}");
+            assert_eq!((profile.timing(ADD).count, profile.timing(STOP).count), (1, 1));
+            assert!(profile.histogram().iter().all(|(_, timing)| timing.count > 0));
+
+            // The next transaction starts from an empty profile.
+            let outcome = evm.execute_transaction(mega_tx()).unwrap();
+            let profile = outcome.opcode_profile.expect("opcode profiling stays enabled");
+            assert_eq!(profile.timing(SLOAD).count, 10, "{spec:?}");
+        }
+    }
+
+    #[test]
+    fn test_execution_hash_matches_only_identical_outcomes() {
+        let execute = |value: u64| {
+            let code = BytecodeBuilder::default()
+                .sstore(U256::ZERO, U256::from(value))
+                .mstore(0, [0xab; 32])
+                .push_number(32_u8)
+                .append_many([PUSH0, LOG0])
+                .stop()
+                .build();
+            let mut db = MemoryDatabase::default()
+                .account_balance(CALLER, U256::from(1_000_000))
+                .account_code(CALLEE, code);
+            MegaEvm::new(configure_context(&mut db)).execute_transaction(mega_tx()).unwrap()
+        };
+
+        let outcome = execute(1);
+        assert!(outcome.result.is_success());
+        assert_eq!(outcome.execution_hash(), execute(1).execution_hash());
+        assert_ne!(outcome.execution_hash(), execute(2).execution_hash());
+
+        // Diagnostic data does not contribute.
+        let mut with_diagnostics = outcome.clone();
+        with_diagnostics.accessed_block_hashes.insert(1, B256::ZERO);
+        assert_eq!(with_diagnostics.execution_hash(), outcome.execution_hash());
 
-        let evm = MegaEvm::new(configure_context(&mut state));
-        assert_eq!(evm.get_accessed_block_hashes().get(&7), Some(&B256::from([7_u8; 32])));
+        // Resource usage does.
+        let mut with_more_usage = outcome.clone();
+        with_more_usage.kv_updates += 1;
+        assert_ne!(with_more_usage.execution_hash(), outcome.execution_hash());
     }
 
     #[test]
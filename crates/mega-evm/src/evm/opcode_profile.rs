@@ -0,0 +1,96 @@
+//! Per-opcode wall-time profile of a transaction, for validating compute gas pricing.
+
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use core::time::Duration;
+use std::vec::Vec;
+
+use revm::bytecode::OpCode;
+
+/// The executions of one opcode in a transaction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpcodeTiming {
+    /// The number of times the opcode was executed.
+    pub count: u64,
+    /// The wall time spent executing the opcode.
+    pub total_time: Duration,
+    /// The gas charged by the opcode. For opcodes that spawn a frame (`CALL`, `CREATE`, ...) this
+    /// includes the gas forwarded to the child frame.
+    pub gas: u64,
+}
+
+impl OpcodeTiming {
+    /// Returns the mean wall time of one execution of the opcode.
+    pub fn mean_time(&self) -> Duration {
+        let nanos = self.total_time.as_nanos().checked_div(self.count as u128).unwrap_or_default();
+        Duration::from_nanos(nanos as u64)
+    }
+
+    /// Returns the wall time spent per unit of gas charged, in nanoseconds. Comparing this ratio
+    /// across opcodes shows which ones are over- or under-priced relative to their CPU cost.
+    pub fn ns_per_gas(&self) -> f64 {
+        self.total_time.as_nanos() as f64 / self.gas.max(1) as f64
+    }
+}
+
+/// The wall time spent per opcode by a transaction, collected when opcode profiling is enabled
+/// (see `MegaContext::with_opcode_profiling`, behind the `opcode-profiling` feature).
+///
+/// Every instruction is timed individually, so the timings include the overhead of reading the
+/// clock and are only meaningful relative to each other. Frames run under an inspector are not
+/// profiled, since the inspector hooks would dominate the timings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpcodeProfile {
+    timings: [OpcodeTiming; 256],
+}
+
+impl Default for OpcodeProfile {
+    fn default() -> Self {
+        Self { timings: [OpcodeTiming::default(); 256] }
+    }
+}
+
+impl OpcodeProfile {
+    /// Records one execution of `opcode` that took `elapsed` and charged `gas`.
+    #[cfg(feature = "opcode-profiling")]
+    #[inline]
+    pub(crate) fn record(&mut self, opcode: u8, elapsed: Duration, gas: u64) {
+        let timing = &mut self.timings[opcode as usize];
+        timing.count += 1;
+        timing.total_time += elapsed;
+        timing.gas = timing.gas.saturating_add(gas);
+    }
+
+    /// Returns the timing of `opcode`.
+    pub fn timing(&self, opcode: u8) -> &OpcodeTiming {
+        &self.timings[opcode as usize]
+    }
+
+    /// Returns the total wall time spent executing instructions.
+    pub fn total_time(&self) -> Duration {
+        self.timings.iter().map(|timing| timing.total_time).sum()
+    }
+
+    /// Returns the histogram of the opcodes executed, ordered by the time spent on them, most
+    /// expensive first.
+    pub fn histogram(&self) -> Vec<(OpCode, OpcodeTiming)> {
+        let mut histogram: Vec<_> = self
+            .timings
+            .iter()
+            .enumerate()
+            .filter(|(_, timing)| timing.count > 0)
+            .filter_map(|(opcode, timing)| Some((OpCode::new(opcode as u8)?, *timing)))
+            .collect();
+        histogram.sort_by(|(_, a), (_, b)| b.total_time.cmp(&a.total_time));
+        histogram
+    }
+
+    /// Adds the timings of `other` to this profile, e.g. to aggregate a block.
+    pub fn merge(&mut self, other: &Self) {
+        for (timing, other) in self.timings.iter_mut().zip(other.timings.iter()) {
+            timing.count += other.count;
+            timing.total_time += other.total_time;
+            timing.gas = timing.gas.saturating_add(other.gas);
+        }
+    }
+}
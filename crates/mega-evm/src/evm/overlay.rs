@@ -0,0 +1,328 @@
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::vec::Vec;
+
+use alloy_primitives::{Address, B256, U256};
+use revm::{
+    database::DatabaseRef,
+    primitives::{HashMap, StorageKey, StorageValue, KECCAK_EMPTY},
+    state::{Account, AccountInfo, Bytecode},
+    Database, DatabaseCommit,
+};
+
+/// The changes to a single account recorded in an overlay layer.
+#[derive(Debug, Default, Clone)]
+struct OverlayAccount {
+    /// The account info, or `None` if the account was destroyed.
+    info: Option<AccountInfo>,
+    /// Whether all storage below this layer was wiped (self-destruct or re-creation).
+    storage_cleared: bool,
+    /// Storage slots written in this layer.
+    storage: HashMap<StorageKey, StorageValue>,
+}
+
+/// The changes committed to one layer of an [`OverlayDb`].
+#[derive(Debug, Default, Clone)]
+struct OverlayLayer {
+    accounts: HashMap<Address, OverlayAccount>,
+    contracts: HashMap<B256, Bytecode>,
+}
+
+impl OverlayLayer {
+    /// Applies `other` on top of this layer.
+    fn apply(&mut self, other: Self) {
+        for (address, changes) in other.accounts {
+            let account = self.accounts.entry(address).or_default();
+            account.info = changes.info;
+            if changes.storage_cleared {
+                account.storage.clear();
+                account.storage_cleared = true;
+            }
+            account.storage.extend(changes.storage);
+        }
+        self.contracts.extend(other.contracts);
+    }
+}
+
+/// A database committing state changes into in-memory layers on top of a read-only base.
+///
+/// Reads go through the layers from the newest to the oldest before falling back to the base, so
+/// a simulation service can stack a pending block and pending transactions on top of a state
+/// provider without copying its state. Commits go to the newest layer.
+///
+/// Layers double as revert points: [`push_layer`](Self::push_layer) opens a new layer,
+/// [`pop_layer`](Self::pop_layer) discards it with all the changes committed since, and
+/// [`squash_layer`](Self::squash_layer) keeps its changes by folding it into the layer below.
+/// The bottom layer always exists and is only discarded by [`clear`](Self::clear).
+#[derive(Debug, Clone)]
+pub struct OverlayDb<Base> {
+    base: Base,
+    /// The layers from the oldest to the newest. Never empty.
+    layers: Vec<OverlayLayer>,
+}
+
+impl<Base> OverlayDb<Base> {
+    /// Creates a database with no changes on top of `base`.
+    pub fn new(base: Base) -> Self {
+        Self { base, layers: Vec::from([OverlayLayer::default()]) }
+    }
+
+    /// Returns the base database.
+    pub fn base(&self) -> &Base {
+        &self.base
+    }
+
+    /// Consumes the database, discarding its changes, and returns the base database.
+    pub fn into_base(self) -> Base {
+        self.base
+    }
+
+    /// Returns the number of layers pushed on top of the bottom layer.
+    pub fn depth(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    /// Opens a new layer for the changes committed from now on, and returns the new depth.
+    pub fn push_layer(&mut self) -> usize {
+        self.layers.push(OverlayLayer::default());
+        self.depth()
+    }
+
+    /// Discards the newest layer and the changes committed to it. Returns `false` if there is no
+    /// layer on top of the bottom layer.
+    pub fn pop_layer(&mut self) -> bool {
+        if self.depth() == 0 {
+            return false;
+        }
+        self.layers.pop();
+        true
+    }
+
+    /// Folds the newest layer into the one below, keeping its changes. Returns `false` if there
+    /// is no layer on top of the bottom layer.
+    pub fn squash_layer(&mut self) -> bool {
+        if self.depth() == 0 {
+            return false;
+        }
+        let top = self.layers.pop().expect("overlay has a layer above the bottom one");
+        self.layers.last_mut().expect("overlay has a bottom layer").apply(top);
+        true
+    }
+
+    /// Discards the layers above `depth`, reverting to the state when that depth was reached.
+    pub fn revert_to(&mut self, depth: usize) {
+        self.layers.truncate(depth + 1);
+    }
+
+    /// Discards every change, leaving only the base state.
+    pub fn clear(&mut self) {
+        self.layers.clear();
+        self.layers.push(OverlayLayer::default());
+    }
+
+    /// Returns whether no change has been committed on top of the base.
+    pub fn is_empty(&self) -> bool {
+        self.layers.iter().all(|layer| layer.accounts.is_empty() && layer.contracts.is_empty())
+    }
+
+    /// Returns the layers from the newest to the oldest.
+    fn layers(&self) -> impl Iterator<Item = &OverlayLayer> {
+        self.layers.iter().rev()
+    }
+
+    /// Returns the newest layer.
+    fn top_mut(&mut self) -> &mut OverlayLayer {
+        self.layers.last_mut().expect("overlay has a bottom layer")
+    }
+}
+
+impl<Base: DatabaseRef> DatabaseRef for OverlayDb<Base> {
+    type Error = Base::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        match self.layers().find_map(|layer| layer.accounts.get(&address)) {
+            Some(account) => Ok(account.info.clone()),
+            None => self.base.basic_ref(address),
+        }
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        match self.layers().find_map(|layer| layer.contracts.get(&code_hash)) {
+            Some(code) => Ok(code.clone()),
+            None => self.base.code_by_hash_ref(code_hash),
+        }
+    }
+
+    fn storage_ref(
+        &self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        for layer in self.layers() {
+            if let Some(account) = layer.accounts.get(&address) {
+                if let Some(value) = account.storage.get(&index) {
+                    return Ok(*value);
+                }
+                if account.storage_cleared {
+                    return Ok(U256::ZERO);
+                }
+            }
+        }
+        self.base.storage_ref(address, index)
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.base.block_hash_ref(number)
+    }
+}
+
+impl<Base: DatabaseRef> Database for OverlayDb<Base> {
+    type Error = Base::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.basic_ref(address)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.code_by_hash_ref(code_hash)
+    }
+
+    fn storage(
+        &mut self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        self.storage_ref(address, index)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.block_hash_ref(number)
+    }
+}
+
+impl<Base> DatabaseCommit for OverlayDb<Base> {
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        let top = self.top_mut();
+        for (address, mut account) in changes {
+            if !account.is_touched() {
+                continue;
+            }
+            let entry = top.accounts.entry(address).or_default();
+            if account.is_selfdestructed() {
+                *entry = OverlayAccount { info: None, storage_cleared: true, ..Default::default() };
+                continue;
+            }
+            if let Some(code) = account.info.code.take() {
+                if account.info.code_hash != KECCAK_EMPTY {
+                    top.contracts.insert(account.info.code_hash, code);
+                }
+            }
+            if account.is_created() {
+                entry.storage.clear();
+                entry.storage_cleared = true;
+            }
+            entry.info = Some(account.info);
+            entry
+                .storage
+                .extend(account.storage.into_iter().map(|(k, slot)| (k, slot.present_value())));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MemoryDatabase;
+    use alloy_primitives::address;
+    use revm::state::EvmStorageSlot;
+
+    const ALICE: Address = address!("0000000000000000000000000000000000100000");
+    const BOB: Address = address!("0000000000000000000000000000000000100001");
+
+    fn write(db: &mut OverlayDb<MemoryDatabase>, address: Address, balance: u64, value: u64) {
+        let mut account =
+            Account::from(AccountInfo { balance: U256::from(balance), ..Default::default() });
+        account.mark_touch();
+        account
+            .storage
+            .insert(U256::ZERO, EvmStorageSlot::new_changed(U256::ZERO, U256::from(value), 0));
+        db.commit(HashMap::from_iter([(address, account)]));
+    }
+
+    fn balance(db: &OverlayDb<MemoryDatabase>, address: Address) -> U256 {
+        db.basic_ref(address).unwrap().map(|info| info.balance).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_commits_shadow_the_base_without_modifying_it() {
+        let base = MemoryDatabase::default()
+            .account_balance(ALICE, U256::from(100))
+            .account_storage(ALICE, U256::ZERO, U256::from(1));
+        let mut db = OverlayDb::new(base);
+        assert!(db.is_empty());
+
+        write(&mut db, ALICE, 50, 2);
+        assert_eq!(balance(&db, ALICE), U256::from(50));
+        assert_eq!(db.storage_ref(ALICE, U256::ZERO).unwrap(), U256::from(2));
+        assert_eq!(db.base().basic_ref(ALICE).unwrap().unwrap().balance, U256::from(100));
+        assert_eq!(db.base().storage_ref(ALICE, U256::ZERO).unwrap(), U256::from(1));
+    }
+
+    #[test]
+    fn test_pop_layer_reverts_its_changes() {
+        let mut db = OverlayDb::new(MemoryDatabase::default());
+        write(&mut db, ALICE, 1, 1);
+
+        assert_eq!(db.push_layer(), 1);
+        write(&mut db, ALICE, 2, 2);
+        write(&mut db, BOB, 3, 3);
+        assert_eq!(balance(&db, ALICE), U256::from(2));
+
+        assert!(db.pop_layer());
+        assert_eq!(balance(&db, ALICE), U256::from(1));
+        assert_eq!(db.storage_ref(ALICE, U256::ZERO).unwrap(), U256::from(1));
+        assert!(db.basic_ref(BOB).unwrap().is_none());
+        assert!(!db.pop_layer(), "the bottom layer cannot be popped");
+    }
+
+    #[test]
+    fn test_squash_layer_keeps_its_changes() {
+        let mut db = OverlayDb::new(MemoryDatabase::default());
+        db.push_layer();
+        write(&mut db, ALICE, 1, 1);
+        db.push_layer();
+        write(&mut db, BOB, 2, 2);
+
+        assert!(db.squash_layer());
+        assert_eq!(db.depth(), 1);
+        db.revert_to(0);
+        assert!(db.is_empty());
+
+        db.push_layer();
+        write(&mut db, ALICE, 1, 1);
+        assert!(db.squash_layer());
+        assert_eq!(db.depth(), 0);
+        assert_eq!(balance(&db, ALICE), U256::from(1));
+
+        db.clear();
+        assert!(db.is_empty());
+        assert!(db.basic_ref(ALICE).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_selfdestruct_hides_base_storage() {
+        let base = MemoryDatabase::default().account_storage(ALICE, U256::ZERO, U256::from(7));
+        let mut db = OverlayDb::new(base);
+        db.push_layer();
+
+        let mut account = Account::from(AccountInfo::default());
+        account.mark_touch();
+        account.mark_selfdestruct();
+        db.commit(HashMap::from_iter([(ALICE, account)]));
+        assert!(db.basic_ref(ALICE).unwrap().is_none());
+        assert_eq!(db.storage_ref(ALICE, U256::ZERO).unwrap(), U256::ZERO);
+
+        db.pop_layer();
+        assert_eq!(db.storage_ref(ALICE, U256::ZERO).unwrap(), U256::from(7));
+    }
+}
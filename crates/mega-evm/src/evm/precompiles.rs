@@ -170,6 +170,14 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> PrecompileProvider<MegaContext<DB,
         is_static: bool,
         gas_limit: u64,
     ) -> Result<Option<Self::Output>, String> {
+        // EQUIVALENCE fast path: no compute gas or precompile usage is recorded before
+        // `MINI_REX`, so run the precompile as op-revm does.
+        if !context.spec.is_enabled(MegaSpecId::MINI_REX) {
+            return PrecompileProvider::<OpContext<DB>>::run(
+                self, context, address, inputs, is_static, gas_limit,
+            );
+        }
+
         // REX5+: cap forwarded gas at the current compute-gas remaining so a precompile
         // cannot spend more compute gas than the per-frame / TX-level budget permits.
         // Pre-REX5 keeps the original forwarding semantics for backward compatibility.
@@ -249,9 +257,21 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> PrecompileProvider<MegaContext<DB,
                     output.gas.limit()
                 };
                 context.additional_limit.borrow_mut().record_compute_gas(compute_gas);
-            } else if context.spec.is_enabled(MegaSpecId::MINI_REX) {
+            } else {
                 context.additional_limit.borrow_mut().record_compute_gas(output.gas.spent());
             }
+            // Stateful precompiles report the state they wrote. A failed call wrote nothing.
+            if output.result.is_ok() {
+                if let Some(reporter) = context.precompile_resource_reporter.clone() {
+                    let input = inputs.input.bytes(context);
+                    if let Some(usage) = reporter.resource_usage(address, &input, &output) {
+                        context
+                            .additional_limit
+                            .borrow_mut()
+                            .after_precompile_call(usage, &mut output);
+                    }
+                }
+            }
             output
         }))
     }
@@ -267,6 +287,35 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> PrecompileProvider<MegaContext<DB,
     }
 }
 
+/// The resources consumed by a stateful precompile call, beyond its compute gas.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrecompileResourceUsage {
+    /// The size in bytes of the data written.
+    pub data_size: u64,
+    /// The number of key-value updates.
+    pub kv_updates: u64,
+    /// The net number of accounts and storage slots created.
+    pub state_growth: u64,
+}
+
+/// Reports the resource usage of stateful precompile calls.
+///
+/// A precompile that writes state does so outside the interpreter, bypassing the `SSTORE` hooks of
+/// [`AdditionalLimit`](crate::AdditionalLimit). After each successful precompile call, the
+/// precompile provider asks the reporter configured on the [`MegaContext`] for the usage of the
+/// call and charges it to the call's frame, so the usage is discarded if a parent frame reverts
+/// and a call whose usage exceeds a limit fails as an `SSTORE` would.
+pub trait PrecompileResourceReporter: core::fmt::Debug {
+    /// Returns the resources consumed by the call to the precompile at `address` with `input`
+    /// that produced `result`, or `None` if the precompile does not write state.
+    fn resource_usage(
+        &self,
+        address: &Address,
+        input: &[u8],
+        result: &InterpreterResult,
+    ) -> Option<PrecompileResourceUsage>;
+}
+
 /// A builder function to build dynamic precompiles for a given [`MegaSpecId`].
 pub type DynPrecompilesBuilder =
     Arc<dyn Fn(MegaSpecId) -> HashMap<Address, DynPrecompile> + Send + Sync>;
@@ -279,7 +328,8 @@ mod tests {
 
     use super::{kzg_point_evaluation::GAS_COST, mini_rex, rex, MegaPrecompiles};
     use crate::{
-        test_utils::MemoryDatabase, AdditionalLimit, EvmTxRuntimeLimits, MegaContext, MegaSpecId,
+        test_utils::{context_without_operator_fee, MemoryDatabase},
+        AdditionalLimit, EvmTxRuntimeLimits, MegaContext, MegaSpecId,
     };
     use alloy_evm::precompiles::PrecompilesMap;
     use alloy_primitives::Bytes;
@@ -654,7 +704,7 @@ mod tests {
         let caller = address!("0000000000000000000000000000000000600000");
 
         let mut db = MemoryDatabase::default().account_balance(caller, U256::from(10_000_000));
-        let mut context = MegaContext::new(&mut db, MegaSpecId::REX5);
+        let mut context = context_without_operator_fee(&mut db, MegaSpecId::REX5);
         // Tight TX-level compute-gas budget that's smaller than KZG's GAS_COST.
         let tx_limits = EvmTxRuntimeLimits {
             tx_compute_gas_limit: GAS_COST - 1,
@@ -663,10 +713,6 @@ mod tests {
         context.additional_limit =
             Rc::new(RefCell::new(AdditionalLimit::new(MegaSpecId::REX5, tx_limits)));
         context.set_block(BlockEnv { gas_limit: 1_000_000_000, ..Default::default() });
-        context.modify_chain(|chain| {
-            chain.operator_fee_scalar = Some(U256::from(0));
-            chain.operator_fee_constant = Some(U256::from(0));
-        });
 
         // Build a valid KZG-input calldata so the precompile would proceed up to its
         // gas-cost check (which is the OOG trigger under the cap).
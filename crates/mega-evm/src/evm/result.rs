@@ -1,8 +1,13 @@
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::{collections::BTreeMap, vec::Vec};
+
 use alloy_evm::block::StateChangeSource;
 pub use alloy_evm::InvalidTxError;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, Bytes, Keccak256, B256, U256};
+use alloy_sol_types::SolError;
 pub use op_revm::{OpHaltReason, OpTransactionError};
-use revm::{context::result::ExecutionResult, state::EvmState};
+use revm::{context::result::ExecutionResult, state::EvmState, JournalEntry};
 pub use revm::{
     context::result::{EVMError, InvalidTransaction},
     context_interface::{
@@ -11,7 +16,9 @@ pub use revm::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::VolatileDataAccess;
+use crate::{
+    CallFrame, DbIoStats, DelegationTargetDenied, JournalStats, LimitKind, VolatileDataAccess,
+};
 
 /// The execution outcome of a transaction in `MegaETH`.
 ///
@@ -25,12 +32,155 @@ pub struct MegaTransactionOutcome {
     pub state: EvmState,
     /// The data size usage in bytes.
     pub data_size: u64,
+    /// The data size usage in bytes with the transaction's own bytes counted at their
+    /// flz-estimated DA size. See [`LimitUsage::da_data_size`](crate::LimitUsage::da_data_size).
+    pub da_data_size: u64,
     /// The number of KV updates.
     pub kv_updates: u64,
     /// The compute gas used.
     pub compute_gas_used: u64,
     /// The state growth used.
     pub state_growth_used: u64,
+    /// The block hashes read by `BLOCKHASH`, keyed by block number.
+    pub accessed_block_hashes: BTreeMap<u64, B256>,
+    /// The oracle contract storage slots read, with the first value observed for each slot.
+    pub accessed_oracle_slots: BTreeMap<U256, U256>,
+    /// The ordered journal entries of the transaction, if journal export is enabled (see
+    /// [`MegaContext::with_journal_export`](crate::MegaContext::with_journal_export)).
+    pub journal: Option<Vec<JournalEntry>>,
+    /// The volatile data accessed by the transaction, which subjects it to detention.
+    pub volatile_data_accessed: VolatileDataAccess,
+    /// The refund of the gas left when the detained compute gas limit halted the transaction, if
+    /// it did.
+    pub detained_gas_refund: Option<DetainedGasRefund>,
+    /// The call graph of the transaction, if call graph recording is enabled (see
+    /// [`MegaContext::with_call_graph_recording`](crate::MegaContext::with_call_graph_recording)).
+    pub call_graph: Option<CallFrame>,
+    /// The journal checkpoint statistics of the transaction, if journal stats collection is
+    /// enabled (see
+    /// [`MegaContext::with_journal_stats`](crate::MegaContext::with_journal_stats)).
+    pub journal_stats: Option<JournalStats>,
+    /// The database lookups caused by the transaction, if database I/O metering is enabled (see
+    /// [`MegaContext::with_db_io_meter`](crate::MegaContext::with_db_io_meter)).
+    pub db_io: Option<DbIoStats>,
+    /// The wall time spent per opcode by the transaction, if opcode profiling is enabled (see
+    /// `MegaContext::with_opcode_profiling`, behind the `opcode-profiling` feature). Always `None`
+    /// without the feature.
+    pub opcode_profile: Option<crate::OpcodeProfile>,
+}
+
+impl MegaTransactionOutcome {
+    /// Returns a canonical hash of the consensus-relevant parts of the outcome: the status and
+    /// output, the logs, the state diff and the resource usage.
+    ///
+    /// Replicas compare it with the hash the sequencer computed to check that re-executing the
+    /// transaction produced the same outcome, without exchanging the outcomes themselves. The
+    /// hash is independent of the iteration order of [`state`](Self::state): the diff covers the
+    /// touched accounts in address order and their changed slots in key order, as
+    /// [`DatabaseCommit`](revm::DatabaseCommit) would apply them. Diagnostic data (accessed block
+    /// hashes, journal, call graph, ...) is left out.
+    ///
+    /// Halts are identified by their [revert output](MegaHaltReason::to_revert_output), so two
+    /// outcomes halted for different [`MegaHaltReason::Base`] reasons with the same gas used and
+    /// state diff hash the same.
+    pub fn execution_hash(&self) -> B256 {
+        fn write_u64(hasher: &mut Keccak256, value: u64) {
+            hasher.update(value.to_be_bytes());
+        }
+        fn write_bytes(hasher: &mut Keccak256, bytes: &[u8]) {
+            write_u64(hasher, bytes.len() as u64);
+            hasher.update(bytes);
+        }
+
+        let mut hasher = Keccak256::new();
+        hasher.update(b"mega-evm/execution-hash/v1");
+
+        // Status and output.
+        match &self.result {
+            ExecutionResult::Success { output, .. } => {
+                hasher.update([0]);
+                write_bytes(&mut hasher, output.data());
+            }
+            ExecutionResult::Revert { output, .. } => {
+                hasher.update([1]);
+                write_bytes(&mut hasher, output);
+            }
+            ExecutionResult::Halt { reason, .. } => {
+                hasher.update([2]);
+                write_bytes(&mut hasher, &reason.to_revert_output().unwrap_or_default());
+            }
+        }
+
+        // Logs.
+        let logs = self.result.logs();
+        write_u64(&mut hasher, logs.len() as u64);
+        for log in logs {
+            hasher.update(log.address);
+            write_u64(&mut hasher, log.data.topics().len() as u64);
+            for topic in log.data.topics() {
+                hasher.update(topic);
+            }
+            write_bytes(&mut hasher, &log.data.data);
+        }
+
+        // State diff.
+        let mut accounts: Vec<_> =
+            self.state.iter().filter(|(_, account)| account.is_touched()).collect();
+        accounts.sort_unstable_by_key(|(address, _)| **address);
+        write_u64(&mut hasher, accounts.len() as u64);
+        for (address, account) in accounts {
+            hasher.update(address);
+            hasher.update([account.is_selfdestructed() as u8, account.is_created() as u8]);
+            hasher.update(account.info.balance.to_be_bytes::<32>());
+            write_u64(&mut hasher, account.info.nonce);
+            hasher.update(account.info.code_hash);
+            let mut slots: Vec<_> =
+                account.storage.iter().filter(|(_, slot)| slot.is_changed()).collect();
+            slots.sort_unstable_by_key(|(key, _)| **key);
+            write_u64(&mut hasher, slots.len() as u64);
+            for (key, slot) in slots {
+                hasher.update(key.to_be_bytes::<32>());
+                hasher.update(slot.present_value.to_be_bytes::<32>());
+            }
+        }
+
+        // Resource usage.
+        for usage in [
+            self.result.gas_used(),
+            self.data_size,
+            self.kv_updates,
+            self.compute_gas_used,
+            self.state_growth_used,
+        ] {
+            write_u64(&mut hasher, usage);
+        }
+
+        hasher.finalize()
+    }
+}
+
+/// The gas refunded at the end of a transaction halted by the detained compute gas limit.
+///
+/// Detention halts the transaction with [`MegaHaltReason::VolatileDataAccessOutOfGas`], and the
+/// gas left is refunded to the sender rather than consumed. The refund is settled after the last
+/// frame returns, so it appears in no call frame of a trace; tracers attach this event to the
+/// transaction instead.
+///
+/// It is not delivered through an inspector callback: alloy-evm's `EvmFactory` accepts any revm
+/// [`Inspector`](revm::Inspector), so [`MegaEvm`](crate::MegaEvm) cannot require an extension
+/// trait of its inspector. Tracers read it after the run from
+/// [`MegaTransactionOutcome::detained_gas_refund`] or
+/// [`MegaContext::detained_gas_refund`](crate::MegaContext::detained_gas_refund).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DetainedGasRefund {
+    /// The volatile data accessed by the transaction, which triggered the detention.
+    pub access_type: VolatileDataAccess,
+    /// The effective detained compute gas limit that was exceeded.
+    pub detained_limit: u64,
+    /// The compute gas used when the transaction halted.
+    pub compute_gas_used: u64,
+    /// The gas refunded to the sender.
+    pub refunded_gas: u64,
 }
 
 /// The execution outcome of system call in `MegaETH`.
@@ -44,16 +194,54 @@ pub struct MegaSystemCallOutcome {
 
 /// `MegaETH` transaction validation error type.
 ///
-/// TODO: This is currently a type alias due to constraints from `op_revm::OpHandler`.
-/// `OpHandler` requires `ERROR: From<OpTransactionError>`, but we cannot satisfy this
-/// for `EVMError<DBError, MegaTransactionError>` due to Rust's orphan rules.
-///
-/// To add custom transaction error variants, we need to:
-/// 1. Stop using `OpHandler` directly
-/// 2. Implement all handler methods manually without delegating to `OpHandler`
-/// 3. Then we can use a custom enum like: ``` pub enum MegaTransactionError {
-///    Base(OpTransactionError), CustomVariant, } ```
-pub type MegaTransactionError = OpTransactionError;
+/// It is a wrapper around [`OpTransactionError`], which internally wraps [`InvalidTransaction`],
+/// with the rejections specific to `MegaETH`. [`MegaHandler`](crate::MegaHandler) runs op-revm's
+/// handler with op-revm's own error and wraps its transaction errors in [`Self::Base`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MegaTransactionError {
+    /// Base [`OpTransactionError`]
+    #[error(transparent)]
+    Base(#[from] OpTransactionError),
+    /// An EIP-7702 authorization delegates to a target the
+    /// [`MegaHandlerHooks`](crate::MegaHandlerHooks) do not allow.
+    #[error(transparent)]
+    DelegationTargetDenied(#[from] DelegationTargetDenied),
+}
+
+impl From<InvalidTransaction> for MegaTransactionError {
+    fn from(err: InvalidTransaction) -> Self {
+        Self::Base(OpTransactionError::Base(err))
+    }
+}
+
+impl<DBError> From<MegaTransactionError> for EVMError<DBError, MegaTransactionError> {
+    fn from(err: MegaTransactionError) -> Self {
+        Self::Transaction(err)
+    }
+}
+
+impl TransactionErrorTr for MegaTransactionError {}
+
+impl InvalidTxError for MegaTransactionError {
+    fn is_nonce_too_low(&self) -> bool {
+        matches!(self, Self::Base(OpTransactionError::Base(InvalidTransaction::NonceTooLow { .. })))
+    }
+}
+
+alloy_sol_types::sol! {
+    /// ABI-encoded revert output of a transaction halted by a resource limit.
+    #[derive(Debug, PartialEq, Eq)]
+    error MegaLimitHalted(uint8 kind, uint64 limit, uint64 used);
+
+    /// ABI-encoded revert output of a transaction halted by the detained compute gas limit after
+    /// accessing volatile data.
+    #[derive(Debug, PartialEq, Eq)]
+    error MegaVolatileDataAccessOutOfGas(uint16 accessType, uint64 limit, uint64 used);
+
+    /// ABI-encoded revert output of a system transaction calling a non-whitelisted contract.
+    #[derive(Debug, PartialEq, Eq)]
+    error MegaSystemTxInvalidCallee(address callee);
+}
 
 /// `MegaETH` halt reason type, with additional MegaETH-specific halt reasons.
 ///
@@ -110,6 +298,104 @@ pub enum MegaHaltReason {
         /// The actual compute gas usage
         actual: u64,
     },
+    /// Interpreter memory limit exceeded (REX6+)
+    MemoryLimitExceeded {
+        /// The configured interpreter memory limit
+        limit: u64,
+        /// The memory size the halting opcode would have expanded to
+        actual: u64,
+    },
+    /// Transaction frame limit exceeded (REX6+)
+    FrameLimitExceeded {
+        /// The configured frame limit
+        limit: u64,
+        /// The number of frames created, including the one that exceeded the limit
+        actual: u64,
+    },
+}
+
+impl MegaHaltReason {
+    /// Returns the exceeded limit kind, the limit and the usage if this is a resource-limit halt.
+    pub const fn limit_exceeded(&self) -> Option<(LimitKind, u64, u64)> {
+        match *self {
+            Self::DataLimitExceeded { limit, actual } => Some((LimitKind::DataSize, limit, actual)),
+            Self::KVUpdateLimitExceeded { limit, actual } => {
+                Some((LimitKind::KVUpdate, limit, actual))
+            }
+            Self::ComputeGasLimitExceeded { limit, actual } => {
+                Some((LimitKind::ComputeGas, limit, actual))
+            }
+            Self::StateGrowthLimitExceeded { limit, actual } => {
+                Some((LimitKind::StateGrowth, limit, actual))
+            }
+            Self::MemoryLimitExceeded { limit, actual } => Some((LimitKind::Memory, limit, actual)),
+            Self::FrameLimitExceeded { limit, actual } => {
+                Some((LimitKind::FrameCount, limit, actual))
+            }
+            Self::Base(_) |
+            Self::SystemTxInvalidCallee { .. } |
+            Self::VolatileDataAccessOutOfGas { .. } => None,
+        }
+    }
+
+    /// Returns ABI-encoded revert data describing a `MegaETH`-specific halt, so RPC layers can
+    /// report why the transaction halted instead of a bare out-of-gas error.
+    ///
+    /// Resource-limit halts encode as `MegaLimitHalted(uint8 kind, uint64 limit, uint64 used)`
+    /// with the [`LimitKind`] discriminant, volatile data access halts as
+    /// `MegaVolatileDataAccessOutOfGas(uint16 accessType, uint64 limit, uint64 used)`, and invalid
+    /// system transaction callees as `MegaSystemTxInvalidCallee(address callee)`. Returns `None`
+    /// for the [`Base`](Self::Base) halt reasons. [`from_revert_output`](Self::from_revert_output)
+    /// decodes the output back.
+    pub fn to_revert_output(&self) -> Option<Bytes> {
+        if let Some((kind, limit, used)) = self.limit_exceeded() {
+            return Some(MegaLimitHalted { kind: kind.as_u8(), limit, used }.abi_encode().into());
+        }
+        let output = match *self {
+            Self::VolatileDataAccessOutOfGas { access_type, limit, actual } => {
+                MegaVolatileDataAccessOutOfGas {
+                    accessType: access_type.bits(),
+                    limit,
+                    used: actual,
+                }
+                .abi_encode()
+            }
+            Self::SystemTxInvalidCallee { callee } => {
+                MegaSystemTxInvalidCallee { callee }.abi_encode()
+            }
+            _ => return None,
+        };
+        Some(output.into())
+    }
+
+    /// Decodes revert data produced by [`to_revert_output`](Self::to_revert_output). Returns `None`
+    /// if `output` is not one of its errors.
+    pub fn from_revert_output(output: &[u8]) -> Option<Self> {
+        if let Ok(MegaLimitHalted { kind, limit, used: actual }) =
+            MegaLimitHalted::abi_decode(output)
+        {
+            return Some(match LimitKind::from_u8(kind)? {
+                LimitKind::DataSize => Self::DataLimitExceeded { limit, actual },
+                LimitKind::KVUpdate => Self::KVUpdateLimitExceeded { limit, actual },
+                LimitKind::ComputeGas => Self::ComputeGasLimitExceeded { limit, actual },
+                LimitKind::StateGrowth => Self::StateGrowthLimitExceeded { limit, actual },
+                LimitKind::Memory => Self::MemoryLimitExceeded { limit, actual },
+                LimitKind::FrameCount => Self::FrameLimitExceeded { limit, actual },
+            });
+        }
+        if let Ok(MegaVolatileDataAccessOutOfGas { accessType, limit, used }) =
+            MegaVolatileDataAccessOutOfGas::abi_decode(output)
+        {
+            return Some(Self::VolatileDataAccessOutOfGas {
+                access_type: VolatileDataAccess::from_bits_truncate(accessType),
+                limit,
+                actual: used,
+            });
+        }
+        MegaSystemTxInvalidCallee::abi_decode(output)
+            .ok()
+            .map(|MegaSystemTxInvalidCallee { callee }| Self::SystemTxInvalidCallee { callee })
+    }
 }
 
 impl From<EthHaltReason> for MegaHaltReason {
@@ -134,6 +420,8 @@ impl TryFrom<MegaHaltReason> for EthHaltReason {
             MegaHaltReason::KVUpdateLimitExceeded { .. } |
             MegaHaltReason::ComputeGasLimitExceeded { .. } |
             MegaHaltReason::StateGrowthLimitExceeded { .. } |
+            MegaHaltReason::MemoryLimitExceeded { .. } |
+            MegaHaltReason::FrameLimitExceeded { .. } |
             MegaHaltReason::SystemTxInvalidCallee { .. } |
             MegaHaltReason::VolatileDataAccessOutOfGas { .. } => Err(value),
         }
@@ -176,6 +464,50 @@ mod tests {
         assert!(EthHaltReason::try_from(mega).is_err());
     }
 
+    #[test]
+    fn test_revert_output_roundtrip() {
+        let variants = [
+            MegaHaltReason::DataLimitExceeded { limit: 1, actual: 2 },
+            MegaHaltReason::KVUpdateLimitExceeded { limit: 3, actual: 4 },
+            MegaHaltReason::ComputeGasLimitExceeded { limit: 5, actual: 6 },
+            MegaHaltReason::StateGrowthLimitExceeded { limit: 7, actual: 8 },
+            MegaHaltReason::MemoryLimitExceeded { limit: 9, actual: 10 },
+            MegaHaltReason::FrameLimitExceeded { limit: 11, actual: 12 },
+            MegaHaltReason::VolatileDataAccessOutOfGas {
+                access_type: VolatileDataAccess::ORACLE | VolatileDataAccess::BLOCK_NUMBER,
+                limit: 13,
+                actual: 14,
+            },
+            MegaHaltReason::SystemTxInvalidCallee { callee: Address::repeat_byte(0x63) },
+        ];
+        for variant in variants {
+            let output = variant.to_revert_output().expect("mega-specific halts have an output");
+            assert_eq!(MegaHaltReason::from_revert_output(&output), Some(variant));
+        }
+    }
+
+    #[test]
+    fn test_limit_halt_revert_output_encoding() {
+        let output =
+            MegaHaltReason::ComputeGasLimitExceeded { limit: 100, actual: 101 }.to_revert_output();
+        let decoded = MegaLimitHalted::abi_decode(&output.unwrap()).unwrap();
+        assert_eq!(
+            decoded,
+            MegaLimitHalted { kind: LimitKind::ComputeGas.as_u8(), limit: 100, used: 101 }
+        );
+    }
+
+    #[test]
+    fn test_base_halt_reasons_have_no_revert_output() {
+        let reason = MegaHaltReason::from(EthHaltReason::OutOfGas(OutOfGasError::Basic));
+        assert!(reason.to_revert_output().is_none());
+        assert!(reason.limit_exceeded().is_none());
+
+        let unknown_kind = MegaLimitHalted { kind: 0xff, limit: 1, used: 2 }.abi_encode();
+        assert!(MegaHaltReason::from_revert_output(&unknown_kind).is_none());
+        assert!(MegaHaltReason::from_revert_output(&[0xde, 0xad]).is_none());
+    }
+
     #[test]
     fn test_all_mega_specific_variants_fail_eth_conversion() {
         let variants: Vec<MegaHaltReason> = vec![
@@ -183,6 +515,8 @@ mod tests {
             MegaHaltReason::KVUpdateLimitExceeded { limit: 1, actual: 2 },
             MegaHaltReason::ComputeGasLimitExceeded { limit: 1, actual: 2 },
             MegaHaltReason::StateGrowthLimitExceeded { limit: 1, actual: 2 },
+            MegaHaltReason::MemoryLimitExceeded { limit: 1, actual: 2 },
+            MegaHaltReason::FrameLimitExceeded { limit: 1, actual: 2 },
             MegaHaltReason::SystemTxInvalidCallee { callee: Address::ZERO },
         ];
         for variant in variants {
@@ -0,0 +1,40 @@
+//! Options relaxing transaction validation for simulation.
+
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+
+/// Options relaxing the pre-execution validation of a transaction, for simulating flows the
+/// caller alone cannot pay for, such as paymaster or sponsored transactions.
+///
+/// Set with [`MegaContext::with_simulation_options`](crate::MegaContext::with_simulation_options).
+/// The default options validate as when executing blocks. Never set them when executing blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationOptions {
+    /// Skips the check that the fee payer can afford the maximum fee and the caller the value.
+    /// As with `CfgEnv::disable_balance_check`, the fee is then deducted only as far as the
+    /// balance covers it, and the caller's balance is raised to the transferred value if lower.
+    #[serde(default)]
+    pub disable_balance_check: bool,
+    /// The account paying the fees of the transaction instead of the caller.
+    ///
+    /// The maximum fee is checked against and deducted from this account, and the unused gas is
+    /// reimbursed to it. The caller still pays the transferred value and its nonce is still
+    /// checked and bumped. Deposit-like transactions pay no fee and are unaffected.
+    #[serde(default)]
+    pub fee_payer_override: Option<Address>,
+}
+
+impl SimulationOptions {
+    /// Sets whether the balance check is skipped.
+    pub fn with_disable_balance_check(mut self, disable_balance_check: bool) -> Self {
+        self.disable_balance_check = disable_balance_check;
+        self
+    }
+
+    /// Sets the account paying the fees instead of the caller.
+    pub fn with_fee_payer_override(mut self, fee_payer: Option<Address>) -> Self {
+        self.fee_payer_override = fee_payer;
+        self
+    }
+}
@@ -6,6 +6,8 @@ use core::{
 };
 pub use op_revm::OpSpecId;
 pub use revm::primitives::hardfork::{SpecId as EthSpecId, UnknownHardfork};
+
+use crate::constants::SpecConstants;
 use serde::{Deserialize, Serialize};
 
 /// `MegaETH` spec id, defining different versions of the `MegaETH` EVM.
@@ -108,6 +110,177 @@ impl MegaSpecId {
     pub const fn is_enabled(self, other: Self) -> bool {
         other as u8 <= self as u8
     }
+
+    /// Returns the limits and costs in effect under this spec.
+    pub fn constants(self) -> SpecConstants {
+        SpecConstants::for_spec(self)
+    }
+
+    /// Returns `true` if `feature` is active under this spec.
+    pub const fn has_feature(self, feature: SpecFeature) -> bool {
+        feature.is_active_in(self)
+    }
+
+    /// Returns the features active under this spec, in the order of [`SpecFeature::ALL`].
+    pub fn features(self) -> impl Iterator<Item = SpecFeature> {
+        SpecFeature::ALL.into_iter().filter(move |feature| feature.is_active_in(self))
+    }
+}
+
+/// A behavior of the `MegaETH` EVM that is active in a contiguous range of [`MegaSpecId`]s.
+///
+/// Lets code branch on the behavior it depends on instead of comparing specs, e.g.
+/// `spec.has_feature(SpecFeature::SelfdestructDisabled)` rather than
+/// `spec.is_enabled(MegaSpecId::MINI_REX) && !spec.is_enabled(MegaSpecId::REX2)`. The list covers
+/// the headline behavior of each hardfork; the upgrade pages under `docs/spec/upgrades` remain the
+/// normative description.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum SpecFeature {
+    /// Gas is split into compute gas and storage gas.
+    DualGasModel,
+    /// Per-transaction compute gas, data size and KV update limits.
+    ResourceLimits,
+    /// Accessing volatile data (block environment, beneficiary, oracle) lowers the compute gas
+    /// limit.
+    GasDetention,
+    /// CALL and CREATE forward at most 98/100 of the remaining gas.
+    CallGasForwardingCap,
+    /// LOG opcodes charge storage gas on top of the standard cost.
+    LogStorageGas,
+    /// SELFDESTRUCT halts with `InvalidFEOpcode`.
+    SelfdestructDisabled,
+    /// Contract code and initcode size limits raised above the Ethereum limits.
+    IncreasedContractSizeLimits,
+    /// The oracle and high-precision timestamp system contracts.
+    OracleContract,
+    /// Transactions are charged intrinsic storage gas.
+    IntrinsicStorageGas,
+    /// CALLCODE, DELEGATECALL and STATICCALL also forward at most 98/100 of the remaining gas.
+    ForwardingCapOnAllCalls,
+    /// Per-transaction state growth limit.
+    StateGrowthLimit,
+    /// The compute gas limit lowered by gas detention is reset between transactions.
+    ComputeGasLimitResetPerTx,
+    /// SELFDESTRUCT follows EIP-6780.
+    Eip6780Selfdestruct,
+    /// The `KeylessDeploy` system contract.
+    KeylessDeploy,
+    /// Oracle gas detention is triggered by SLOAD on the oracle contract and capped at 20M.
+    SloadOracleDetention,
+    /// The compute gas of keyless deployments is tracked.
+    KeylessDeployComputeGas,
+    /// Resource limits are budgeted per call frame.
+    PerFrameResourceBudgets,
+    /// The gas detention cap is relative to the usage at the volatile access.
+    RelativeGasDetention,
+    /// Value-transferring CALL and CALLCODE receive `STORAGE_CALL_STIPEND`.
+    StorageCallStipend,
+    /// The `MegaAccessControl` and `MegaLimitControl` system contracts.
+    AccessAndLimitControl,
+    /// The `SequencerRegistry` system contract.
+    SequencerRegistry,
+    /// The system address is read from the `SequencerRegistry` instead of being fixed.
+    DynamicSystemAddress,
+    /// Updates of the caller account are counted once in data size and KV updates.
+    CallerAccountUpdateDedup,
+    /// Authorities materialized by EIP-7702 authorizations count towards state growth.
+    Eip7702AuthorityStateGrowth,
+    /// A SELFDESTRUCT beneficiary created by the transfer is charged as a new account.
+    SelfdestructBeneficiaryMetering,
+    /// Every opcode records storage gas, runs, and then records compute gas once.
+    UnifiedGasMeteringOrder,
+    /// System-originated transactions are exempt from resource metering.
+    SystemTxMeteringExemption,
+    /// Interpreter memory and the number of frames per transaction are limited.
+    MemoryAndFrameLimits,
+    /// EIP-4844 blob transactions are rejected.
+    BlobTransactionRejection,
+}
+
+impl SpecFeature {
+    /// Every feature, ordered by the spec introducing it.
+    pub const ALL: [Self; 29] = [
+        Self::DualGasModel,
+        Self::ResourceLimits,
+        Self::GasDetention,
+        Self::CallGasForwardingCap,
+        Self::LogStorageGas,
+        Self::SelfdestructDisabled,
+        Self::IncreasedContractSizeLimits,
+        Self::OracleContract,
+        Self::IntrinsicStorageGas,
+        Self::ForwardingCapOnAllCalls,
+        Self::StateGrowthLimit,
+        Self::ComputeGasLimitResetPerTx,
+        Self::Eip6780Selfdestruct,
+        Self::KeylessDeploy,
+        Self::SloadOracleDetention,
+        Self::KeylessDeployComputeGas,
+        Self::PerFrameResourceBudgets,
+        Self::RelativeGasDetention,
+        Self::StorageCallStipend,
+        Self::AccessAndLimitControl,
+        Self::SequencerRegistry,
+        Self::DynamicSystemAddress,
+        Self::CallerAccountUpdateDedup,
+        Self::Eip7702AuthorityStateGrowth,
+        Self::SelfdestructBeneficiaryMetering,
+        Self::UnifiedGasMeteringOrder,
+        Self::SystemTxMeteringExemption,
+        Self::MemoryAndFrameLimits,
+        Self::BlobTransactionRejection,
+    ];
+
+    /// Returns the first spec in which the feature is active.
+    pub const fn introduced_in(self) -> MegaSpecId {
+        match self {
+            Self::DualGasModel |
+            Self::ResourceLimits |
+            Self::GasDetention |
+            Self::CallGasForwardingCap |
+            Self::LogStorageGas |
+            Self::SelfdestructDisabled |
+            Self::IncreasedContractSizeLimits |
+            Self::OracleContract => MegaSpecId::MINI_REX,
+            Self::IntrinsicStorageGas | Self::ForwardingCapOnAllCalls | Self::StateGrowthLimit => {
+                MegaSpecId::REX
+            }
+            Self::ComputeGasLimitResetPerTx => MegaSpecId::REX1,
+            Self::Eip6780Selfdestruct | Self::KeylessDeploy => MegaSpecId::REX2,
+            Self::SloadOracleDetention | Self::KeylessDeployComputeGas => MegaSpecId::REX3,
+            Self::PerFrameResourceBudgets |
+            Self::RelativeGasDetention |
+            Self::StorageCallStipend |
+            Self::AccessAndLimitControl => MegaSpecId::REX4,
+            Self::SequencerRegistry |
+            Self::DynamicSystemAddress |
+            Self::CallerAccountUpdateDedup |
+            Self::Eip7702AuthorityStateGrowth |
+            Self::SelfdestructBeneficiaryMetering => MegaSpecId::REX5,
+            Self::UnifiedGasMeteringOrder |
+            Self::SystemTxMeteringExemption |
+            Self::MemoryAndFrameLimits |
+            Self::BlobTransactionRejection => MegaSpecId::REX6,
+        }
+    }
+
+    /// Returns the first spec in which the feature is no longer active, if any.
+    pub const fn removed_in(self) -> Option<MegaSpecId> {
+        match self {
+            Self::SelfdestructDisabled => Some(MegaSpecId::REX2),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the feature is active under `spec`.
+    pub const fn is_active_in(self, spec: MegaSpecId) -> bool {
+        spec.is_enabled(self.introduced_in()) &&
+            match self.removed_in() {
+                Some(removed_in) => !spec.is_enabled(removed_in),
+                None => true,
+            }
+    }
 }
 
 impl From<MegaSpecId> for &'static str {
@@ -223,4 +396,73 @@ mod tests {
         assert!(!MegaSpecId::REX4.is_enabled(MegaSpecId::REX5));
         assert!(!MegaSpecId::REX5.is_enabled(MegaSpecId::REX6));
     }
+
+    /// Activation matrix of every feature, with one column per spec of `ALL_SPECS` (from
+    /// `EQUIVALENCE` to `REX6`): `x` if the feature is active, `.` if not.
+    const FEATURE_MATRIX: [(SpecFeature, &str); 29] = [
+        (SpecFeature::DualGasModel, ".xxxxxxxx"),
+        (SpecFeature::ResourceLimits, ".xxxxxxxx"),
+        (SpecFeature::GasDetention, ".xxxxxxxx"),
+        (SpecFeature::CallGasForwardingCap, ".xxxxxxxx"),
+        (SpecFeature::LogStorageGas, ".xxxxxxxx"),
+        (SpecFeature::SelfdestructDisabled, ".xxx....."),
+        (SpecFeature::IncreasedContractSizeLimits, ".xxxxxxxx"),
+        (SpecFeature::OracleContract, ".xxxxxxxx"),
+        (SpecFeature::IntrinsicStorageGas, "..xxxxxxx"),
+        (SpecFeature::ForwardingCapOnAllCalls, "..xxxxxxx"),
+        (SpecFeature::StateGrowthLimit, "..xxxxxxx"),
+        (SpecFeature::ComputeGasLimitResetPerTx, "...xxxxxx"),
+        (SpecFeature::Eip6780Selfdestruct, "....xxxxx"),
+        (SpecFeature::KeylessDeploy, "....xxxxx"),
+        (SpecFeature::SloadOracleDetention, ".....xxxx"),
+        (SpecFeature::KeylessDeployComputeGas, ".....xxxx"),
+        (SpecFeature::PerFrameResourceBudgets, "......xxx"),
+        (SpecFeature::RelativeGasDetention, "......xxx"),
+        (SpecFeature::StorageCallStipend, "......xxx"),
+        (SpecFeature::AccessAndLimitControl, "......xxx"),
+        (SpecFeature::SequencerRegistry, ".......xx"),
+        (SpecFeature::DynamicSystemAddress, ".......xx"),
+        (SpecFeature::CallerAccountUpdateDedup, ".......xx"),
+        (SpecFeature::Eip7702AuthorityStateGrowth, ".......xx"),
+        (SpecFeature::SelfdestructBeneficiaryMetering, ".......xx"),
+        (SpecFeature::UnifiedGasMeteringOrder, "........x"),
+        (SpecFeature::SystemTxMeteringExemption, "........x"),
+        (SpecFeature::MemoryAndFrameLimits, "........x"),
+        (SpecFeature::BlobTransactionRejection, "........x"),
+    ];
+
+    #[test]
+    fn test_feature_activation_matrix() {
+        assert_eq!(FEATURE_MATRIX.map(|(feature, _)| feature), SpecFeature::ALL);
+        for (feature, row) in FEATURE_MATRIX {
+            for ((spec, _), cell) in ALL_SPECS.into_iter().zip(row.chars()) {
+                assert_eq!(
+                    spec.has_feature(feature),
+                    cell == 'x',
+                    "{feature:?} activation under {spec:?}",
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_features_are_listed_once_in_introduction_order() {
+        for pair in SpecFeature::ALL.windows(2) {
+            assert!(pair[0].introduced_in() <= pair[1].introduced_in(), "{pair:?}");
+        }
+        let mut sorted = SpecFeature::ALL;
+        sorted.sort();
+        assert!(sorted.windows(2).all(|pair| pair[0] != pair[1]), "duplicate feature in ALL");
+    }
+
+    #[test]
+    fn test_spec_features_match_has_feature() {
+        assert_eq!(MegaSpecId::EQUIVALENCE.features().count(), 0);
+        for (spec, _) in ALL_SPECS {
+            let expected: Vec<_> =
+                SpecFeature::ALL.into_iter().filter(|feature| spec.has_feature(*feature)).collect();
+            assert_eq!(spec.features().collect::<Vec<_>>(), expected);
+        }
+        assert!(!MegaSpecId::REX6.features().any(|f| f == SpecFeature::SelfdestructDisabled));
+    }
 }
@@ -8,10 +8,13 @@ use revm::state::{Account, AccountStatus, EvmState, EvmStorage};
 use auto_impl::auto_impl;
 use revm::{database::State, Database};
 
-/// A helper trait to get the block hashes used during transaction execution.
+/// A helper trait to get the block hashes cached by a database.
+///
+/// The block hashes read during a transaction are recorded by the context regardless of the
+/// database, see [`MegaTransactionOutcome::accessed_block_hashes`](crate::MegaTransactionOutcome).
 #[auto_impl(&, &mut, Box, Rc, Arc)]
 pub trait BlockHashes {
-    /// Get the block hashes used during transaction execution.
+    /// Get the block hashes cached by the database.
     fn get_accessed_block_hashes(&self) -> BTreeMap<u64, B256>;
 }
 
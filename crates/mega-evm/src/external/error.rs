@@ -0,0 +1,109 @@
+//! Errors of external environments.
+
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::string::{String, ToString};
+
+use alloy_primitives::{Address, U256};
+
+use crate::BucketId;
+
+/// An external environment queried during execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExternalEnvKind {
+    /// The [`SaltEnv`](crate::SaltEnv).
+    Salt,
+    /// The [`OracleEnv`](crate::OracleEnv).
+    Oracle,
+}
+
+impl Display for ExternalEnvKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Salt => f.write_str("SALT"),
+            Self::Oracle => f.write_str("oracle"),
+        }
+    }
+}
+
+/// What an external environment was queried for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExternalEnvKey {
+    /// The capacity of the bucket of an account.
+    Account {
+        /// The account.
+        address: Address,
+        /// The bucket of the account.
+        bucket_id: BucketId,
+    },
+    /// The capacity of the bucket of a storage slot.
+    StorageSlot {
+        /// The account owning the slot.
+        address: Address,
+        /// The slot.
+        key: U256,
+        /// The bucket of the slot.
+        bucket_id: BucketId,
+    },
+    /// A storage slot of the oracle contract.
+    OracleSlot(U256),
+}
+
+impl Display for ExternalEnvKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Account { address, bucket_id } => {
+                write!(f, "account {address} (bucket {bucket_id})")
+            }
+            Self::StorageSlot { address, key, bucket_id } => {
+                write!(f, "slot {key:#x} of {address} (bucket {bucket_id})")
+            }
+            Self::OracleSlot(slot) => write!(f, "oracle slot {slot:#x}"),
+        }
+    }
+}
+
+/// A failed query of an external environment, fatal to the transaction that made it.
+///
+/// The EVM halts the frame with `FatalExternalError` and the transaction fails with
+/// `EVMError::Custom` carrying this error's message, which names the environment, the key
+/// queried and the error of the environment.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{env} env query for {key} failed: {error}")]
+pub struct ExternalEnvError {
+    /// The environment queried.
+    pub env: ExternalEnvKind,
+    /// The key queried.
+    pub key: ExternalEnvKey,
+    /// The error returned by the environment.
+    pub error: String,
+}
+
+impl ExternalEnvError {
+    /// Creates the error of a failed [`SaltEnv`](crate::SaltEnv) query for `key`.
+    pub fn salt(key: ExternalEnvKey, error: impl Display) -> Self {
+        Self { env: ExternalEnvKind::Salt, key, error: error.to_string() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn test_external_env_error_names_env_key_and_cause() {
+        let address = address!("0000000000000000000000000000000000100001");
+        let error = ExternalEnvError::salt(
+            ExternalEnvKey::StorageSlot { address, key: U256::from(0x10), bucket_id: 7 },
+            "bucket store unavailable",
+        );
+        assert_eq!(
+            error.to_string(),
+            "SALT env query for slot 0x10 of 0x0000000000000000000000000000000000100001 \
+             (bucket 7) failed: bucket store unavailable"
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use alloy_primitives::BlockNumber;
 use auto_impl::auto_impl;
 
-use crate::{ExternalEnvTypes, ExternalEnvs};
+use crate::{ExternalEnvRegistry, ExternalEnvTypes, ExternalEnvs};
 
 /// Factory for creating block-specific external environment instances.
 ///
@@ -21,6 +21,12 @@ use crate::{ExternalEnvTypes, ExternalEnvs};
 ///
 /// This factory is typically called once per block when initializing the EVM. The returned
 /// [`ExternalEnvs`] are then used throughout transaction execution within that block.
+///
+/// # Reorgs
+///
+/// A factory caching data across blocks, e.g. bucket capacities or oracle snapshots, must drop
+/// what was derived from reorged blocks when told so through
+/// [`invalidate_from`](Self::invalidate_from).
 #[auto_impl(&, Box, Arc)]
 pub trait ExternalEnvFactory {
     /// The concrete types for SALT and Oracle environments this factory produces.
@@ -38,4 +44,42 @@ pub trait ExternalEnvFactory {
     ///
     /// A container with SALT and Oracle environment instances configured for the given block.
     fn external_envs(&self, block: BlockNumber) -> ExternalEnvs<Self::EnvTypes>;
+
+    /// Creates the additional environments, beyond SALT and oracle, for executing EVM operations
+    /// at the specified block.
+    ///
+    /// The default implementation provides none. A factory can be extended with additional
+    /// environments without implementing this method by pairing it with them in a tuple, e.g.
+    /// `(factory, price_feed)`.
+    fn extra_envs(&self, block: BlockNumber) -> ExternalEnvRegistry {
+        let _ = block;
+        ExternalEnvRegistry::default()
+    }
+
+    /// Drops the cached data derived from the state of `block` and later blocks, after they are
+    /// reorged out of the canonical chain.
+    ///
+    /// Environments read the state of the parent of their block, so this invalidates what backs
+    /// the environments of the blocks after `block`: later calls to
+    /// [`external_envs`](Self::external_envs) and [`extra_envs`](Self::extra_envs) for these
+    /// blocks must read the new canonical chain. Data derived from earlier blocks may be kept.
+    ///
+    /// Implementations may rely on the following, checked by debug assertions in the factories of
+    /// this crate:
+    ///
+    /// - `block` is never the genesis block, which cannot be reorged.
+    ///
+    /// Environments already created are not affected, and must be dropped by the caller.
+    ///
+    /// The default implementation caches nothing, so there is nothing to drop.
+    fn invalidate_from(&self, block: BlockNumber) {
+        debug_assert_reorged_block(block);
+    }
+}
+
+/// Checks the guarantees [`ExternalEnvFactory::invalidate_from`] makes on the first reorged
+/// `block`.
+#[inline]
+pub(crate) fn debug_assert_reorged_block(block: BlockNumber) {
+    debug_assert!(block > 0, "the genesis block cannot be reorged");
 }
@@ -80,7 +80,7 @@ impl<SaltEnvImpl: SaltEnv> DynamicGasCost<SaltEnvImpl> {
         key: U256,
     ) -> Result<u64, SaltEnvImpl::Error> {
         // increase the gas cost according to the bucket capacity
-        let bucket_id = SaltEnvImpl::bucket_id_for_slot(address, key);
+        let bucket_id = SaltEnvImpl::bucket_id_for_slot_at(self.spec, address, key);
         let multiplier = self.load_bucket_cost_multiplier(bucket_id)?;
 
         Ok(self.sstore_set_gas_for_multiplier(multiplier))
@@ -90,7 +90,7 @@ impl<SaltEnvImpl: SaltEnv> DynamicGasCost<SaltEnvImpl> {
     /// [`NEWACCOUNT`](revm::interpreter::gas::NEWACCOUNT) gas cost in the original EVM.
     pub fn new_account_gas(&mut self, address: Address) -> Result<u64, SaltEnvImpl::Error> {
         // increase the gas cost according to the bucket capacity
-        let bucket_id = SaltEnvImpl::bucket_id_for_account(address);
+        let bucket_id = SaltEnvImpl::bucket_id_for_account_at(self.spec, address);
         let multiplier = self.load_bucket_cost_multiplier(bucket_id)?;
 
         Ok(self.new_account_gas_for_multiplier(multiplier))
@@ -100,7 +100,7 @@ impl<SaltEnvImpl: SaltEnv> DynamicGasCost<SaltEnvImpl> {
     /// [`CREATE`](revm::interpreter::gas::CREATE) gas cost in the original EVM.
     pub fn create_contract_gas(&mut self, address: Address) -> Result<u64, SaltEnvImpl::Error> {
         // increase the gas cost according to the bucket capacity
-        let bucket_id = SaltEnvImpl::bucket_id_for_account(address);
+        let bucket_id = SaltEnvImpl::bucket_id_for_account_at(self.spec, address);
         let multiplier = self.load_bucket_cost_multiplier(bucket_id)?;
 
         Ok(self.create_contract_gas_for_multiplier(multiplier))
@@ -3,6 +3,8 @@
 //! This module provides interfaces for accessing external data sources during EVM execution:
 //! - **SALT**: Bucket capacity information for dynamic gas pricing
 //! - **Oracle**: Storage from the `MegaETH` oracle contract
+//! - **Additional environments**: Any further kind of external data, registered by type in an
+//!   [`ExternalEnvRegistry`]
 //!
 //! # Architecture
 //!
@@ -10,6 +12,7 @@
 //! 1. [`ExternalEnvFactory`] creates block-specific environment instances
 //! 2. [`ExternalEnvs`] bundles SALT and Oracle implementations
 //! 3. Individual oracle methods (e.g., [`SaltEnv::get_bucket_capacity`]) provide data
+//! 4. [`ExternalEnvFactory::extra_envs`] supplies the additional environments of the block
 //!
 //! Block context is established at factory creation time, not per oracle call, ensuring
 //! consistent state snapshots throughout execution.
@@ -18,20 +21,28 @@ use alloy_primitives::BlockNumber;
 use auto_impl::auto_impl;
 use core::fmt::Debug;
 
+mod error;
 mod factory;
 mod gas;
 #[cfg(any(test, feature = "test-utils"))]
 mod hasher;
 mod oracle;
+mod registry;
+#[cfg(feature = "std")]
+mod resilient;
 mod salt;
 #[cfg(any(test, feature = "test-utils"))]
 mod test_utils;
 
+pub use error::*;
 pub use factory::*;
 pub use gas::*;
 #[cfg(any(test, feature = "test-utils"))]
 pub use hasher::*;
 pub use oracle::*;
+pub use registry::*;
+#[cfg(feature = "std")]
+pub use resilient::*;
 pub use salt::*;
 #[cfg(any(test, feature = "test-utils"))]
 pub use test_utils::*;
@@ -93,6 +104,11 @@ impl ExternalEnvFactory for EmptyExternalEnv {
     fn external_envs(&self, _block: BlockNumber) -> ExternalEnvs<Self::EnvTypes> {
         ExternalEnvs { salt_env: *self, oracle_env: *self }
     }
+
+    /// Nothing is cached: every block gets the same empty environments.
+    fn invalidate_from(&self, block: BlockNumber) {
+        factory::debug_assert_reorged_block(block);
+    }
 }
 
 #[cfg(test)]
@@ -111,4 +127,11 @@ mod tests {
         assert_eq!(<EmptyExternalEnv as SaltEnv>::bucket_id_for_account(Address::ZERO), 0);
         assert_eq!(<EmptyExternalEnv as SaltEnv>::bucket_id_for_slot(Address::ZERO, U256::ZERO), 0);
     }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the genesis block cannot be reorged")]
+    fn test_invalidating_genesis_is_rejected() {
+        EmptyExternalEnv.invalidate_from(0);
+    }
 }
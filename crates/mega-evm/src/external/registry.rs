@@ -0,0 +1,178 @@
+//! Registry of additional external environments.
+
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::{rc::Rc, vec::Vec};
+
+use core::{
+    any::{type_name, Any, TypeId},
+    fmt::Debug,
+};
+
+use alloy_primitives::BlockNumber;
+
+use crate::{ExternalEnvFactory, ExternalEnvs};
+
+/// Registry of external environments beyond SALT and oracle, keyed by type.
+///
+/// [`ExternalEnvTypes`](crate::ExternalEnvTypes) fixes the SALT and oracle environments. Further
+/// kinds of external data, such as a price feed or a DA fee source, are registered here instead,
+/// so adding a kind changes neither that trait nor its implementors. Code with access to the
+/// [`MegaContext`](crate::MegaContext) looks them up by type with
+/// [`MegaContext::extra_env`](crate::MegaContext::extra_env).
+#[derive(Clone, Default)]
+pub struct ExternalEnvRegistry {
+    /// The registered environments with their type id and type name, at most one per type.
+    envs: Vec<(TypeId, &'static str, Rc<dyn Any>)>,
+}
+
+impl Debug for ExternalEnvRegistry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.envs.iter().map(|(_, name, _)| name)).finish()
+    }
+}
+
+impl ExternalEnvRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `env`, replacing the environment of the same type, if any.
+    pub fn with<E: Any>(mut self, env: E) -> Self {
+        self.insert(env);
+        self
+    }
+
+    /// Registers `env`, replacing the environment of the same type, if any.
+    pub fn insert<E: Any>(&mut self, env: E) {
+        self.insert_entry((TypeId::of::<E>(), type_name::<E>(), Rc::new(env)));
+    }
+
+    /// Registers every environment of `other`, replacing the environments of the same types.
+    pub fn extend(&mut self, other: Self) {
+        other.envs.into_iter().for_each(|entry| self.insert_entry(entry));
+    }
+
+    fn insert_entry(&mut self, entry: (TypeId, &'static str, Rc<dyn Any>)) {
+        match self.envs.iter_mut().find(|(id, _, _)| *id == entry.0) {
+            Some(existing) => *existing = entry,
+            None => self.envs.push(entry),
+        }
+    }
+
+    /// Returns the environment of type `E`, if registered.
+    pub fn get<E: Any>(&self) -> Option<&E> {
+        self.envs
+            .iter()
+            .find(|(id, _, _)| *id == TypeId::of::<E>())
+            .and_then(|(_, _, env)| env.downcast_ref())
+    }
+
+    /// Returns whether an environment of type `E` is registered.
+    pub fn contains<E: Any>(&self) -> bool {
+        self.get::<E>().is_some()
+    }
+
+    /// Returns the number of registered environments.
+    pub fn len(&self) -> usize {
+        self.envs.len()
+    }
+
+    /// Returns whether no environment is registered.
+    pub fn is_empty(&self) -> bool {
+        self.envs.is_empty()
+    }
+}
+
+/// Implements [`ExternalEnvFactory`] for a tuple of a factory followed by additional environments.
+///
+/// The tuple produces the SALT and oracle environments of its factory, and registers the
+/// additional environments on top of the factory's own
+/// [`extra_envs`](ExternalEnvFactory::extra_envs). Existing factories are thus extended with new
+/// kinds of environments without being changed, e.g. `(TestExternalEnvs::new(), price_feed)`.
+macro_rules! impl_external_env_factory_tuple {
+    ($($env:ident $index:tt),+) => {
+        impl<F: ExternalEnvFactory, $($env: Any + Clone),+> ExternalEnvFactory for (F, $($env),+) {
+            type EnvTypes = F::EnvTypes;
+
+            fn external_envs(&self, block: BlockNumber) -> ExternalEnvs<Self::EnvTypes> {
+                self.0.external_envs(block)
+            }
+
+            fn extra_envs(&self, block: BlockNumber) -> ExternalEnvRegistry {
+                let mut registry = self.0.extra_envs(block);
+                $(registry.insert(self.$index.clone());)+
+                registry
+            }
+
+            fn invalidate_from(&self, block: BlockNumber) {
+                crate::external::factory::debug_assert_reorged_block(block);
+                self.0.invalidate_from(block)
+            }
+        }
+    };
+}
+
+impl_external_env_factory_tuple!(E1 1);
+impl_external_env_factory_tuple!(E1 1, E2 2);
+impl_external_env_factory_tuple!(E1 1, E2 2, E3 3);
+impl_external_env_factory_tuple!(E1 1, E2 2, E3 3, E4 4);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EmptyExternalEnv;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct PriceFeed(u64);
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct DaFee(u64);
+
+    #[test]
+    fn test_registry_keeps_one_env_per_type() {
+        let mut registry = ExternalEnvRegistry::new().with(PriceFeed(1));
+        assert_eq!(registry.get::<PriceFeed>(), Some(&PriceFeed(1)));
+        assert!(!registry.contains::<DaFee>());
+
+        registry.insert(PriceFeed(2));
+        registry.extend(ExternalEnvRegistry::new().with(DaFee(3)));
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.get::<PriceFeed>(), Some(&PriceFeed(2)));
+        assert_eq!(registry.get::<DaFee>(), Some(&DaFee(3)));
+    }
+
+    #[test]
+    fn test_tuple_factory_registers_extra_envs() {
+        assert!(EmptyExternalEnv.extra_envs(1).is_empty());
+
+        let factory = (EmptyExternalEnv, PriceFeed(7), DaFee(9));
+        let registry = factory.extra_envs(1);
+        assert_eq!(registry.get::<PriceFeed>(), Some(&PriceFeed(7)));
+        assert_eq!(registry.get::<DaFee>(), Some(&DaFee(9)));
+        let _: ExternalEnvs<EmptyExternalEnv> = factory.external_envs(1);
+    }
+
+    #[test]
+    fn test_tuple_factory_forwards_invalidation() {
+        #[derive(Debug, Default)]
+        struct InvalidationRecorder(core::cell::Cell<Option<BlockNumber>>);
+
+        impl ExternalEnvFactory for InvalidationRecorder {
+            type EnvTypes = EmptyExternalEnv;
+
+            fn external_envs(&self, block: BlockNumber) -> ExternalEnvs<Self::EnvTypes> {
+                EmptyExternalEnv.external_envs(block)
+            }
+
+            fn invalidate_from(&self, block: BlockNumber) {
+                self.0.set(Some(block));
+            }
+        }
+
+        let factory = (InvalidationRecorder::default(), PriceFeed(7));
+        factory.invalidate_from(12);
+        assert_eq!(factory.0 .0.get(), Some(12));
+    }
+}
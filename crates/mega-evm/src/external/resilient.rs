@@ -0,0 +1,284 @@
+//! Retry and fallback policy around external environment providers.
+
+use core::{
+    fmt::Display,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+use tracing::warn;
+
+use crate::{BucketId, MegaSpecId, OracleEnv, SaltEnv, SaltEnvMut, MIN_BUCKET_SIZE};
+
+/// The calls made through a [`ResilientExternalEnv`] and how they were served.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResilientEnvStats {
+    /// The number of calls made to the wrapped environment, retries excluded.
+    pub calls: u64,
+    /// The number of retries after a failed attempt.
+    pub retries: u64,
+    /// The number of calls that failed after exhausting the retries.
+    pub failures: u64,
+    /// The number of failed calls served with the fallback value instead.
+    pub fallbacks: u64,
+}
+
+#[derive(Debug, Default)]
+struct ResilientEnvCounters {
+    calls: AtomicU64,
+    retries: AtomicU64,
+    failures: AtomicU64,
+    fallbacks: AtomicU64,
+}
+
+/// A [`SaltEnv`] and [`OracleEnv`] retrying the failed queries of the wrapped environment, so a
+/// transient provider hiccup does not fail the transaction.
+///
+/// A failed [`get_bucket_capacity`](SaltEnv::get_bucket_capacity) is retried up to
+/// [`with_max_retries`](Self::with_max_retries) times, sleeping between attempts with an
+/// exponential backoff, until the call has taken [`with_timeout`](Self::with_timeout) in total.
+/// The timeout is checked between attempts: a single attempt is never interrupted. If all the
+/// attempts fail, the last error is returned, or with
+/// [`with_fallback_capacity`](Self::with_fallback_capacity) the fallback capacity.
+///
+/// A fallback capacity changes the storage gas charged, so it must only be configured where gas
+/// need not match the canonical chain, e.g. for simulation and gas estimation. Oracle queries are
+/// infallible and are forwarded as is. Clones share the [`stats`](Self::stats) counters.
+#[derive(Debug, Clone)]
+pub struct ResilientExternalEnv<T> {
+    inner: T,
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    timeout: Option<Duration>,
+    fallback_capacity: Option<u64>,
+    counters: Arc<ResilientEnvCounters>,
+}
+
+impl<T> ResilientExternalEnv<T> {
+    /// Wraps `inner`, retrying a failed query 3 times with a backoff from 10ms up to 100ms, with
+    /// no timeout and no fallback.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(100),
+            timeout: None,
+            fallback_capacity: None,
+            counters: Arc::default(),
+        }
+    }
+
+    /// Sets the number of retries after the first failed attempt.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the backoff before the first retry, doubled before each following retry up to
+    /// `max_backoff`.
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the total time after which a failing query is no longer retried.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the bucket capacity returned when all the attempts fail, raised to
+    /// [`MIN_BUCKET_SIZE`] if lower.
+    pub fn with_fallback_capacity(mut self, capacity: u64) -> Self {
+        self.fallback_capacity = Some(capacity.max(MIN_BUCKET_SIZE as u64));
+        self
+    }
+
+    /// Returns the wrapped environment.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns the calls made so far.
+    pub fn stats(&self) -> ResilientEnvStats {
+        let counters = &self.counters;
+        ResilientEnvStats {
+            calls: counters.calls.load(Ordering::Relaxed),
+            retries: counters.retries.load(Ordering::Relaxed),
+            failures: counters.failures.load(Ordering::Relaxed),
+            fallbacks: counters.fallbacks.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Calls `query` until it succeeds or the retries or the timeout are exhausted.
+    fn retry<R, E: Display>(&self, mut query: impl FnMut(&T) -> Result<R, E>) -> Result<R, E> {
+        let counters = &self.counters;
+        counters.calls.fetch_add(1, Ordering::Relaxed);
+        let started = Instant::now();
+        let mut backoff = self.initial_backoff;
+        let mut attempt = 0;
+        loop {
+            let error = match query(&self.inner) {
+                Ok(value) => return Ok(value),
+                Err(error) => error,
+            };
+            let timed_out = self.timeout.is_some_and(|timeout| started.elapsed() >= timeout);
+            if attempt == self.max_retries || timed_out {
+                counters.failures.fetch_add(1, Ordering::Relaxed);
+                return Err(error);
+            }
+            attempt += 1;
+            counters.retries.fetch_add(1, Ordering::Relaxed);
+            warn!(target: "mega_evm::external", %error, attempt, "retrying external env query");
+            std::thread::sleep(backoff);
+            backoff = (backoff * 2).min(self.max_backoff);
+        }
+    }
+}
+
+impl<T: SaltEnv> SaltEnv for ResilientExternalEnv<T> {
+    type Error = T::Error;
+
+    fn get_bucket_capacity(&self, bucket_id: BucketId) -> Result<u64, Self::Error> {
+        match self.retry(|inner| inner.get_bucket_capacity(bucket_id)) {
+            Err(error) => match self.fallback_capacity {
+                Some(capacity) => {
+                    self.counters.fallbacks.fetch_add(1, Ordering::Relaxed);
+                    warn!(
+                        target: "mega_evm::external",
+                        %error,
+                        bucket_id,
+                        capacity,
+                        "falling back to the default bucket capacity"
+                    );
+                    Ok(capacity)
+                }
+                None => Err(error),
+            },
+            capacity => capacity,
+        }
+    }
+
+    fn bucket_id_for_account(account: Address) -> BucketId {
+        T::bucket_id_for_account(account)
+    }
+
+    fn bucket_id_for_slot(address: Address, key: U256) -> BucketId {
+        T::bucket_id_for_slot(address, key)
+    }
+
+    fn bucket_id_for_account_at(spec: MegaSpecId, account: Address) -> BucketId {
+        T::bucket_id_for_account_at(spec, account)
+    }
+
+    fn bucket_id_for_slot_at(spec: MegaSpecId, address: Address, key: U256) -> BucketId {
+        T::bucket_id_for_slot_at(spec, address, key)
+    }
+}
+
+/// Bucket usage is written back without retries: the sequencer must see a failed commit.
+impl<T: SaltEnvMut> SaltEnvMut for ResilientExternalEnv<T> {
+    fn record_bucket_usage(&mut self, bucket_id: BucketId, growth: u64) -> Result<(), Self::Error> {
+        self.inner.record_bucket_usage(bucket_id, growth)
+    }
+
+    fn commit_bucket_usage(&mut self) -> Result<(), Self::Error> {
+        self.inner.commit_bucket_usage()
+    }
+
+    fn discard_bucket_usage(&mut self) {
+        self.inner.discard_bucket_usage()
+    }
+}
+
+impl<T: OracleEnv> OracleEnv for ResilientExternalEnv<T> {
+    fn get_oracle_storage(&self, slot: U256) -> Option<U256> {
+        self.counters.calls.fetch_add(1, Ordering::Relaxed);
+        self.inner.get_oracle_storage(slot)
+    }
+
+    fn on_hint(&self, from: Address, topic: B256, data: Bytes) {
+        self.inner.on_hint(from, topic, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    /// A SALT environment failing its first `failures` queries.
+    #[derive(Debug)]
+    struct FlakySaltEnv {
+        failures: Cell<u32>,
+    }
+
+    impl FlakySaltEnv {
+        fn new(failures: u32) -> ResilientExternalEnv<Self> {
+            ResilientExternalEnv::new(Self { failures: Cell::new(failures) })
+                .with_backoff(Duration::ZERO, Duration::ZERO)
+        }
+    }
+
+    impl SaltEnv for FlakySaltEnv {
+        type Error = &'static str;
+
+        fn get_bucket_capacity(&self, _bucket_id: BucketId) -> Result<u64, Self::Error> {
+            let failures = self.failures.get();
+            if failures == 0 {
+                return Ok(2 * MIN_BUCKET_SIZE as u64);
+            }
+            self.failures.set(failures - 1);
+            Err("provider unavailable")
+        }
+
+        fn bucket_id_for_account(_account: Address) -> BucketId {
+            0
+        }
+
+        fn bucket_id_for_slot(_address: Address, _key: U256) -> BucketId {
+            0
+        }
+    }
+
+    #[test]
+    fn test_transient_failures_are_retried() {
+        let env = FlakySaltEnv::new(2);
+        assert_eq!(env.get_bucket_capacity(0), Ok(2 * MIN_BUCKET_SIZE as u64));
+        assert_eq!(
+            env.stats(),
+            ResilientEnvStats { calls: 1, retries: 2, failures: 0, fallbacks: 0 }
+        );
+    }
+
+    #[test]
+    fn test_exhausted_retries_return_the_error_or_the_fallback() {
+        let env = FlakySaltEnv::new(10).with_max_retries(1);
+        assert_eq!(env.get_bucket_capacity(0), Err("provider unavailable"));
+        assert_eq!(
+            env.stats(),
+            ResilientEnvStats { calls: 1, retries: 1, failures: 1, fallbacks: 0 }
+        );
+
+        let env = FlakySaltEnv::new(10).with_max_retries(0).with_fallback_capacity(0);
+        assert_eq!(env.get_bucket_capacity(0), Ok(MIN_BUCKET_SIZE as u64));
+        assert_eq!(
+            env.stats(),
+            ResilientEnvStats { calls: 1, retries: 0, failures: 1, fallbacks: 1 }
+        );
+    }
+
+    #[test]
+    fn test_timeout_stops_retrying() {
+        let env = FlakySaltEnv::new(10).with_max_retries(u32::MAX).with_timeout(Duration::ZERO);
+        assert!(env.get_bucket_capacity(0).is_err());
+        assert_eq!(env.stats().retries, 0);
+    }
+}
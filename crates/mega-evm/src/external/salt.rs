@@ -10,7 +10,7 @@ use core::{
 use alloy_primitives::{Address, U256};
 use auto_impl::auto_impl;
 
-use crate::EmptyExternalEnv;
+use crate::{EmptyExternalEnv, MegaSpecId};
 
 /// SALT bucket identifier. Accounts and storage slots are mapped to buckets, which have
 /// dynamic capacities that affect gas costs.
@@ -87,6 +87,112 @@ pub trait SaltEnv: Debug + Unpin {
     /// * `address` - The contract address owning the storage
     /// * `key` - The storage slot key
     fn bucket_id_for_slot(address: Address, key: U256) -> BucketId;
+
+    /// Maps an account address to its bucket ID under the bucket topology of `spec`.
+    ///
+    /// The default implementation uses [`bucket_id_for_account`](SaltEnv::bucket_id_for_account)
+    /// for every spec. Implementations whose bucket topology changes at a hardfork override it,
+    /// typically with a [`BucketMapping`].
+    fn bucket_id_for_account_at(spec: MegaSpecId, account: Address) -> BucketId {
+        let _ = spec;
+        Self::bucket_id_for_account(account)
+    }
+
+    /// Maps a storage slot to its bucket ID under the bucket topology of `spec`.
+    ///
+    /// The default implementation uses [`bucket_id_for_slot`](SaltEnv::bucket_id_for_slot) for
+    /// every spec. See [`bucket_id_for_account_at`](SaltEnv::bucket_id_for_account_at).
+    fn bucket_id_for_slot_at(spec: MegaSpecId, address: Address, key: U256) -> BucketId {
+        let _ = spec;
+        Self::bucket_id_for_slot(address, key)
+    }
+}
+
+/// Write-back interface to the SALT bucket capacity model.
+///
+/// After executing a block, the sequencer reports the state growth observed in each bucket so the
+/// capacities used to price the following blocks reflect it. Usage is staged by
+/// [`record_bucket_usage`](SaltEnvMut::record_bucket_usage) and only takes effect on
+/// [`commit_bucket_usage`](SaltEnvMut::commit_bucket_usage), which the sequencer calls together
+/// with committing the block, so a discarded block never changes the capacities.
+#[auto_impl(Box)]
+pub trait SaltEnvMut: SaltEnv {
+    /// Stages `growth` new entries (accounts or storage slots) stored in the specified bucket.
+    fn record_bucket_usage(&mut self, bucket_id: BucketId, growth: u64) -> Result<(), Self::Error>;
+
+    /// Applies the usage staged since the last commit or discard to the bucket capacities, all at
+    /// once.
+    fn commit_bucket_usage(&mut self) -> Result<(), Self::Error>;
+
+    /// Drops the usage staged since the last commit or discard.
+    fn discard_bucket_usage(&mut self);
+}
+
+/// A SALT bucket topology: how accounts and storage slots are assigned to buckets.
+#[derive(Debug, Clone, Copy)]
+pub struct BucketTopology {
+    /// Maps an account address to its bucket ID.
+    pub bucket_id_for_account: fn(Address) -> BucketId,
+    /// Maps a storage slot, given the owning contract and the slot key, to its bucket ID.
+    pub bucket_id_for_slot: fn(Address, U256) -> BucketId,
+}
+
+impl BucketTopology {
+    /// Returns the topology of the bucket ID functions of `S`.
+    pub const fn of<S: SaltEnv>() -> Self {
+        Self {
+            bucket_id_for_account: S::bucket_id_for_account,
+            bucket_id_for_slot: S::bucket_id_for_slot,
+        }
+    }
+}
+
+/// A versioned SALT bucket topology, resolving accounts and storage slots to buckets according to
+/// the spec of the block being executed.
+///
+/// Each version applies from its activation spec until the next version activates, so blocks on
+/// either side of a hardfork that changes the topology are each priced against their own buckets.
+/// The mapping is `const`-constructible, so a [`SaltEnv`] can keep it in a `const` and resolve
+/// [`bucket_id_for_account_at`](SaltEnv::bucket_id_for_account_at) and
+/// [`bucket_id_for_slot_at`](SaltEnv::bucket_id_for_slot_at) through it.
+#[derive(Debug, Clone, Copy)]
+pub struct BucketMapping<const N: usize> {
+    /// The versions with their activation spec, ordered by activation.
+    versions: [(MegaSpecId, BucketTopology); N],
+}
+
+impl<const N: usize> BucketMapping<N> {
+    /// Creates a mapping from `versions`, ordered by activation spec. The first version also
+    /// applies to the specs before its activation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `versions` is empty.
+    pub const fn new(versions: [(MegaSpecId, BucketTopology); N]) -> Self {
+        assert!(N > 0, "a bucket mapping needs at least one version");
+        Self { versions }
+    }
+
+    /// Returns the topology in effect under `spec`.
+    pub fn topology(&self, spec: MegaSpecId) -> &BucketTopology {
+        let (_, topology) = self
+            .versions
+            .iter()
+            .rev()
+            .find(|(activation, _)| spec.is_enabled(*activation))
+            .unwrap_or(&self.versions[0]);
+        topology
+    }
+
+    /// Maps an account address to its bucket ID under `spec`.
+    pub fn bucket_id_for_account(&self, spec: MegaSpecId, account: Address) -> BucketId {
+        (self.topology(spec).bucket_id_for_account)(account)
+    }
+
+    /// Maps a storage slot to its bucket ID under `spec`.
+    pub fn bucket_id_for_slot(&self, spec: MegaSpecId, address: Address, key: U256) -> BucketId {
+        (self.topology(spec).bucket_id_for_slot)(address, key)
+    }
 }
 
 /// No-op implementation that returns minimum bucket size for all buckets.
@@ -109,3 +215,82 @@ impl SaltEnv for EmptyExternalEnv {
         0 as BucketId
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DynamicGasCost;
+
+    /// Bucket capacity of the buckets of the second topology.
+    const GROWN_CAPACITY: u64 = 4 * MIN_BUCKET_SIZE as u64;
+
+    /// A SALT backend whose topology moves every key from bucket 1 to a grown bucket 2 at REX6.
+    #[derive(Debug)]
+    struct MigratingSaltEnv;
+
+    const MAPPING: BucketMapping<2> = BucketMapping::new([
+        (
+            MegaSpecId::EQUIVALENCE,
+            BucketTopology { bucket_id_for_account: |_| 1, bucket_id_for_slot: |_, _| 1 },
+        ),
+        (
+            MegaSpecId::REX6,
+            BucketTopology { bucket_id_for_account: |_| 2, bucket_id_for_slot: |_, _| 2 },
+        ),
+    ]);
+
+    impl SaltEnv for MigratingSaltEnv {
+        type Error = Infallible;
+
+        fn get_bucket_capacity(&self, bucket_id: BucketId) -> Result<u64, Self::Error> {
+            Ok(if bucket_id == 2 { GROWN_CAPACITY } else { MIN_BUCKET_SIZE as u64 })
+        }
+
+        fn bucket_id_for_account(account: Address) -> BucketId {
+            MAPPING.bucket_id_for_account(MegaSpecId::default(), account)
+        }
+
+        fn bucket_id_for_slot(address: Address, key: U256) -> BucketId {
+            MAPPING.bucket_id_for_slot(MegaSpecId::default(), address, key)
+        }
+
+        fn bucket_id_for_account_at(spec: MegaSpecId, account: Address) -> BucketId {
+            MAPPING.bucket_id_for_account(spec, account)
+        }
+
+        fn bucket_id_for_slot_at(spec: MegaSpecId, address: Address, key: U256) -> BucketId {
+            MAPPING.bucket_id_for_slot(spec, address, key)
+        }
+    }
+
+    #[test]
+    fn test_bucket_mapping_resolves_version_by_spec() {
+        assert_eq!(MAPPING.bucket_id_for_account(MegaSpecId::EQUIVALENCE, Address::ZERO), 1);
+        assert_eq!(MAPPING.bucket_id_for_account(MegaSpecId::REX5, Address::ZERO), 1);
+        assert_eq!(MAPPING.bucket_id_for_slot(MegaSpecId::REX6, Address::ZERO, U256::ZERO), 2);
+
+        let single =
+            BucketMapping::new([(MegaSpecId::REX, BucketTopology::of::<EmptyExternalEnv>())]);
+        assert_eq!(single.bucket_id_for_account(MegaSpecId::MINI_REX, Address::ZERO), 0);
+    }
+
+    #[test]
+    fn test_storage_gas_follows_topology_across_boundary() {
+        let mut before = DynamicGasCost::new(MegaSpecId::REX5, MigratingSaltEnv, 0);
+        let mut after = DynamicGasCost::new(MegaSpecId::REX6, MigratingSaltEnv, 0);
+
+        assert_eq!(before.sstore_set_gas(Address::ZERO, U256::ZERO).unwrap(), 0);
+        assert_eq!(before.get_bucket_ids(), [1]);
+
+        let multiplier = GROWN_CAPACITY / MIN_BUCKET_SIZE as u64;
+        assert_eq!(
+            after.sstore_set_gas(Address::ZERO, U256::ZERO).unwrap(),
+            crate::constants::rex::SSTORE_SET_STORAGE_GAS_BASE * (multiplier - 1)
+        );
+        assert_eq!(
+            after.new_account_gas(Address::ZERO).unwrap(),
+            crate::constants::rex::NEW_ACCOUNT_STORAGE_GAS_BASE * (multiplier - 1)
+        );
+        assert_eq!(after.get_bucket_ids(), [2]);
+    }
+}
@@ -1,7 +1,8 @@
 //! In-memory external environment implementations.
 //!
 //! Provides [`TestExternalEnvs`], a configurable in-memory implementation of SALT and Oracle
-//! environments backed by `HashMap`s.
+//! environments backed by `HashMap`s, and [`SeededTestExternalEnv`], which derives them from a
+//! seed for fuzzing and property tests.
 //! Unlike [`EmptyExternalEnv`](crate::EmptyExternalEnv) which returns hardcoded defaults,
 //! this implementation allows setting specific bucket capacities and oracle storage values.
 //!
@@ -26,7 +27,10 @@ use std::{rc::Rc, vec::Vec};
 use alloy_primitives::{Address, BlockNumber, Bytes, B256, U256};
 use revm::primitives::HashMap;
 
-use crate::{BucketId, ExternalEnvFactory, ExternalEnvTypes, ExternalEnvs, OracleEnv, SaltEnv};
+use crate::{
+    BucketId, ExternalEnvFactory, ExternalEnvTypes, ExternalEnvs, OracleEnv, SaltEnv, SaltEnvMut,
+    MIN_BUCKET_SIZE,
+};
 
 /// Strategy trait for computing bucket IDs from raw key bytes.
 ///
@@ -109,6 +113,10 @@ pub struct TestExternalEnvs<Error = Infallible, Hasher = SimpleBucketHasher> {
     default_bucket_capacity: Rc<RefCell<Option<u64>>>,
     /// Recorded hints from `on_hint` calls. Used for testing the hint mechanism.
     recorded_hints: Rc<RefCell<Vec<RecordedHint>>>,
+    /// Committed bucket usage reported through [`SaltEnvMut`]. Maps bucket IDs to entry counts.
+    bucket_usage: Rc<RefCell<HashMap<BucketId, u64>>>,
+    /// Bucket usage staged through [`SaltEnvMut::record_bucket_usage`], not committed yet.
+    pending_bucket_usage: Rc<RefCell<HashMap<BucketId, u64>>>,
 }
 
 impl Default for TestExternalEnvs {
@@ -144,6 +152,8 @@ impl<Error: Unpin + Clone + Display + 'static, Hasher: BucketHasher>
             bucket_capacity: Rc::new(RefCell::new(HashMap::default())),
             default_bucket_capacity: Rc::new(RefCell::new(None)),
             recorded_hints: Rc::new(RefCell::new(Vec::new())),
+            bucket_usage: Rc::new(RefCell::new(HashMap::default())),
+            pending_bucket_usage: Rc::new(RefCell::new(HashMap::default())),
         }
     }
 
@@ -223,6 +233,12 @@ impl<Error: Unpin + Clone + Display + 'static, Hasher: BucketHasher>
         self.bucket_capacity.borrow().iter().map(|(&id, &cap)| (id, cap)).collect()
     }
 
+    /// Returns the committed usage of a bucket reported through [`SaltEnvMut`], in number of
+    /// entries.
+    pub fn bucket_usage(&self, bucket_id: BucketId) -> u64 {
+        self.bucket_usage.borrow().get(&bucket_id).copied().unwrap_or_default()
+    }
+
     /// Returns the configured oracle storage as `(slot, value)` pairs.
     ///
     /// Used to snapshot the effective external environment into a self-validating
@@ -240,6 +256,12 @@ impl<Error: Unpin + Clone + Display, Hasher: BucketHasher> ExternalEnvFactory
     fn external_envs(&self, _block: BlockNumber) -> ExternalEnvs<Self::EnvTypes> {
         ExternalEnvs { salt_env: self.clone(), oracle_env: self.clone() }
     }
+
+    /// The configured bucket capacities and oracle storage apply to every block, so reorgs leave
+    /// them as they are.
+    fn invalidate_from(&self, block: BlockNumber) {
+        crate::external::factory::debug_assert_reorged_block(block);
+    }
 }
 
 impl<Error: Unpin + Display, Hasher: BucketHasher> ExternalEnvTypes
@@ -278,6 +300,39 @@ impl<Error: Unpin + Display, Hasher: BucketHasher> SaltEnv for TestExternalEnvs<
     }
 }
 
+/// Reference capacity model: a bucket's capacity is the smallest power-of-two multiple of
+/// [`MIN_BUCKET_SIZE`] holding its committed usage, and never shrinks.
+impl<Error: Unpin + Display, Hasher: BucketHasher> SaltEnvMut for TestExternalEnvs<Error, Hasher> {
+    fn record_bucket_usage(&mut self, bucket_id: BucketId, growth: u64) -> Result<(), Self::Error> {
+        let mut pending = self.pending_bucket_usage.borrow_mut();
+        let staged = pending.entry(bucket_id).or_default();
+        *staged = staged.saturating_add(growth);
+        Ok(())
+    }
+
+    fn commit_bucket_usage(&mut self) -> Result<(), Self::Error> {
+        let pending = core::mem::take(&mut *self.pending_bucket_usage.borrow_mut());
+        for (bucket_id, growth) in pending {
+            let usage = {
+                let mut usage = self.bucket_usage.borrow_mut();
+                let usage = usage.entry(bucket_id).or_default();
+                *usage = usage.saturating_add(growth);
+                *usage
+            };
+            let required = usage.checked_next_power_of_two().unwrap_or(u64::MAX);
+            let capacity = self.get_bucket_capacity(bucket_id)?.max(required);
+            self.bucket_capacity
+                .borrow_mut()
+                .insert(bucket_id, capacity.max(MIN_BUCKET_SIZE as u64));
+        }
+        Ok(())
+    }
+
+    fn discard_bucket_usage(&mut self) {
+        self.pending_bucket_usage.borrow_mut().clear();
+    }
+}
+
 impl<Error: Unpin + Display, Hasher: BucketHasher> OracleEnv for TestExternalEnvs<Error, Hasher> {
     fn get_oracle_storage(&self, slot: U256) -> Option<U256> {
         self.oracle_storage.borrow().get(&slot).copied()
@@ -288,6 +343,106 @@ impl<Error: Unpin + Display, Hasher: BucketHasher> OracleEnv for TestExternalEnv
     }
 }
 
+/// Deterministic pseudo-random external environment for fuzzing and property tests.
+///
+/// Bucket capacities and oracle storage values are derived from a seed instead of configured, so
+/// executions exercise the dynamic storage gas and oracle paths rather than always hitting the
+/// minimum bucket capacity and empty oracle storage of [`TestExternalEnvs`]. The same seed always
+/// yields the same environment, on every block and platform.
+///
+/// - Every bucket has a capacity of `MIN_BUCKET_SIZE << k`, with `k` drawn from
+///   `0..=max_capacity_shift`.
+/// - Three in four oracle storage slots hold a pseudo-random value, the others are empty.
+#[derive(derive_more::Debug, Clone)]
+pub struct SeededTestExternalEnv<Hasher = SimpleBucketHasher> {
+    #[debug(ignore)]
+    _phantom: core::marker::PhantomData<Hasher>,
+    /// The seed every value is derived from.
+    seed: u64,
+    /// The largest power of two a bucket capacity exceeds [`MIN_BUCKET_SIZE`] by.
+    max_capacity_shift: u32,
+}
+
+impl<Hasher: BucketHasher> SeededTestExternalEnv<Hasher> {
+    /// Domain separator of the oracle storage values from the bucket capacities.
+    const ORACLE_DOMAIN: u64 = 0x6f7261636c65;
+
+    /// Creates an environment derived from `seed`, with bucket capacities up to 16 times the
+    /// minimum.
+    pub fn new(seed: u64) -> Self {
+        Self { _phantom: core::marker::PhantomData, seed, max_capacity_shift: 4 }
+    }
+
+    /// Sets the largest power of two a bucket capacity may exceed [`MIN_BUCKET_SIZE`] by.
+    pub fn with_max_capacity_shift(mut self, max_capacity_shift: u32) -> Self {
+        self.max_capacity_shift = max_capacity_shift;
+        self
+    }
+
+    /// Returns the seed of the environment.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+/// Mixes `value` into `state` with the `SplitMix64` finalizer.
+fn mix(state: u64, value: u64) -> u64 {
+    let mut z = state ^ value.wrapping_mul(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+impl<Hasher: BucketHasher> ExternalEnvFactory for SeededTestExternalEnv<Hasher> {
+    type EnvTypes = Self;
+
+    fn external_envs(&self, _block: BlockNumber) -> ExternalEnvs<Self::EnvTypes> {
+        ExternalEnvs { salt_env: self.clone(), oracle_env: self.clone() }
+    }
+
+    /// Bucket capacities are derived from the seed alone, so reorgs leave them as they are.
+    fn invalidate_from(&self, block: BlockNumber) {
+        crate::external::factory::debug_assert_reorged_block(block);
+    }
+}
+
+impl<Hasher: BucketHasher> ExternalEnvTypes for SeededTestExternalEnv<Hasher> {
+    type SaltEnv = Self;
+
+    type OracleEnv = Self;
+}
+
+impl<Hasher: BucketHasher> SaltEnv for SeededTestExternalEnv<Hasher> {
+    type Error = Infallible;
+
+    fn get_bucket_capacity(&self, bucket_id: BucketId) -> Result<u64, Self::Error> {
+        let shift = mix(self.seed, bucket_id as u64) % (self.max_capacity_shift as u64 + 1);
+        Ok((MIN_BUCKET_SIZE as u64).saturating_mul(1 << shift.min(63)))
+    }
+
+    fn bucket_id_for_account(account: Address) -> BucketId {
+        TestExternalEnvs::<Infallible, Hasher>::bucket_id_for_account(account)
+    }
+
+    fn bucket_id_for_slot(address: Address, key: U256) -> BucketId {
+        TestExternalEnvs::<Infallible, Hasher>::bucket_id_for_slot(address, key)
+    }
+}
+
+impl<Hasher: BucketHasher> OracleEnv for SeededTestExternalEnv<Hasher> {
+    fn get_oracle_storage(&self, slot: U256) -> Option<U256> {
+        let state = slot
+            .as_limbs()
+            .iter()
+            .fold(mix(self.seed, Self::ORACLE_DOMAIN), |state, limb| mix(state, *limb));
+        if state % 4 == 0 {
+            return None;
+        }
+        let limbs = [state, mix(state, 1), mix(state, 2), mix(state, 3)];
+        Some(U256::from_limbs(limbs))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -305,4 +460,53 @@ mod tests {
         assert_eq!(env.get_bucket_capacity(123), Ok(512));
         assert_eq!(env.get_bucket_capacity(999), Ok(2048));
     }
+
+    #[test]
+    fn test_bucket_usage_is_applied_on_commit_only() {
+        let mut env = TestExternalEnvs::<Infallible>::new().with_bucket_capacity(7, 1024);
+
+        env.record_bucket_usage(1, 200).unwrap();
+        env.record_bucket_usage(1, 100).unwrap();
+        env.record_bucket_usage(7, 10).unwrap();
+        assert_eq!(env.get_bucket_capacity(1), Ok(MIN_BUCKET_SIZE as u64));
+        env.commit_bucket_usage().unwrap();
+        assert_eq!(env.bucket_usage(1), 300);
+        assert_eq!(env.get_bucket_capacity(1), Ok(512));
+        assert_eq!(env.get_bucket_capacity(7), Ok(1024), "capacities never shrink");
+
+        env.record_bucket_usage(1, 1000).unwrap();
+        env.discard_bucket_usage();
+        env.commit_bucket_usage().unwrap();
+        assert_eq!(env.bucket_usage(1), 300);
+        assert_eq!(env.get_bucket_capacity(1), Ok(512));
+    }
+
+    #[test]
+    fn test_seeded_env_is_deterministic_and_varied() {
+        let env = SeededTestExternalEnv::<SimpleBucketHasher>::new(7);
+        let same = SeededTestExternalEnv::<SimpleBucketHasher>::new(7);
+        let other = SeededTestExternalEnv::<SimpleBucketHasher>::new(8);
+
+        let capacities: Vec<u64> = (0..64).map(|id| env.get_bucket_capacity(id).unwrap()).collect();
+        let same_capacities: Vec<u64> =
+            (0..64).map(|id| same.get_bucket_capacity(id).unwrap()).collect();
+        let other_capacities: Vec<u64> =
+            (0..64).map(|id| other.get_bucket_capacity(id).unwrap()).collect();
+        assert_eq!(capacities, same_capacities);
+        assert_ne!(capacities, other_capacities);
+        assert!(capacities.iter().all(|capacity| {
+            capacity.is_power_of_two() &&
+                (MIN_BUCKET_SIZE as u64..=16 * MIN_BUCKET_SIZE as u64).contains(capacity)
+        }));
+        assert!(capacities.iter().any(|capacity| *capacity > MIN_BUCKET_SIZE as u64));
+
+        let values: Vec<Option<U256>> =
+            (0..64u64).map(|slot| env.get_oracle_storage(U256::from(slot))).collect();
+        assert_eq!(values[5], same.get_oracle_storage(U256::from(5)));
+        assert!(values.iter().any(Option::is_none));
+        assert!(values.iter().any(|value| value.is_some_and(|value| !value.is_zero())));
+
+        let flat = env.with_max_capacity_shift(0);
+        assert!((0..64).all(|id| flat.get_bucket_capacity(id) == Ok(MIN_BUCKET_SIZE as u64)));
+    }
 }
@@ -13,6 +13,9 @@ pub mod constants;
 mod evm;
 mod external;
 mod limit;
+pub mod mempool;
+#[cfg(feature = "std")]
+pub mod parallel;
 pub mod sandbox;
 mod system;
 #[cfg(any(test, feature = "test-utils"))]
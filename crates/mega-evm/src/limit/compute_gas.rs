@@ -51,6 +51,15 @@ impl ComputeGasTracker {
         }
     }
 
+    /// Re-initializes the tracker for `spec` and `tx_limit` as [`new`](Self::new) would, but
+    /// keeps the frame stack's allocation.
+    pub(crate) fn reinit(&mut self, spec: MegaSpecId, tx_limit: u64) {
+        let mut recycled = Self::new(spec, tx_limit);
+        core::mem::swap(&mut recycled.frame_tracker, &mut self.frame_tracker);
+        recycled.frame_tracker.reinit(spec, tx_limit);
+        *self = recycled;
+    }
+
     /// Pushes a new frame onto the tracker.
     ///
     /// - **Rex4+ top-level**: budget = `tx_entry.remaining()` (accounts for intrinsic compute gas
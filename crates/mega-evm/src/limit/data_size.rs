@@ -59,11 +59,22 @@ pub const LOG_BASE_SIZE: u64 = 32;
 /// - Storage writes: 40 bytes (only when original ≠ new value, refunded when reset to original)
 /// - Account updates from calls/creates: 40 bytes each
 /// - Contract code: actual deployed bytecode size
+///
+/// ## DA-Estimated Usage
+///
+/// The transaction's own bytes (base data, calldata, access lists and authorizations) are
+/// counted raw. Alongside, the tracker keeps the flz-estimated compressed size of the enveloped
+/// transaction, i.e. what it costs in DA, and [`da_usage`](Self::da_usage) reports the usage with
+/// the raw bytes swapped for that estimate. Only the raw usage is enforced.
 #[derive(Debug, Clone)]
 pub(crate) struct DataSizeTracker {
     rex4_enabled: bool,
     rex5_enabled: bool,
     rex6_enabled: bool,
+    /// The raw size of the transaction's own bytes recorded in `before_tx_start`.
+    tx_raw_size: u64,
+    /// The flz-estimated size of the enveloped transaction, or `tx_raw_size` without one.
+    tx_da_size: u64,
     frame_tracker: FrameLimitTracker<CallFrameInfo>,
 }
 
@@ -73,10 +84,27 @@ impl DataSizeTracker {
             rex4_enabled: spec.is_enabled(MegaSpecId::REX4),
             rex5_enabled: spec.is_enabled(MegaSpecId::REX5),
             rex6_enabled: spec.is_enabled(MegaSpecId::REX6),
+            tx_raw_size: 0,
+            tx_da_size: 0,
             frame_tracker: FrameLimitTracker::new(spec, tx_limit),
         }
     }
 
+    /// Re-initializes the tracker for `spec` and `tx_limit` as [`new`](Self::new) would, but
+    /// keeps the frame stack's allocation.
+    pub(crate) fn reinit(&mut self, spec: MegaSpecId, tx_limit: u64) {
+        let mut recycled = Self::new(spec, tx_limit);
+        core::mem::swap(&mut recycled.frame_tracker, &mut self.frame_tracker);
+        recycled.frame_tracker.reinit(spec, tx_limit);
+        *self = recycled;
+    }
+
+    /// Returns the data size usage with the transaction's own bytes counted at their
+    /// flz-estimated compressed size instead of raw.
+    pub(crate) fn da_usage(&self) -> u64 {
+        self.tx_usage().saturating_sub(self.tx_raw_size).saturating_add(self.tx_da_size)
+    }
+
     /// Returns whether there is at least one active frame on the stack.
     pub(crate) fn has_active_frame(&self) -> bool {
         self.frame_tracker.has_active_frame()
@@ -117,6 +145,12 @@ impl DataSizeTracker {
         self.record_discardable(ACCOUNT_INFO_WRITE_SIZE);
     }
 
+    /// Records the data written by a stateful precompile call as discardable data in the current
+    /// frame.
+    pub(crate) fn record_precompile_writes(&mut self, size: u64) {
+        self.record_discardable(size);
+    }
+
     /// Records an account info write (40 bytes) as TX-level persistent (non-discardable) data.
     ///
     /// Used by the REX6 EIP-7702 authorization scan, which runs in `validate` before any frame
@@ -160,6 +194,8 @@ impl TxRuntimeLimit for DataSizeTracker {
     }
 
     fn reset(&mut self) {
+        self.tx_raw_size = 0;
+        self.tx_da_size = 0;
         self.frame_tracker.reset();
     }
 
@@ -213,6 +249,9 @@ impl TxRuntimeLimit for DataSizeTracker {
     /// - EIP-7702 authorizations (101 bytes each) + authority account updates (40 bytes each)
     /// - Caller account update (40 bytes)
     ///
+    /// The flz-estimated size of the enveloped transaction is kept aside for
+    /// [`da_usage`](Self::da_usage).
+    ///
     /// All recorded as pre-frame (non-discardable) since no frame exists yet.
     fn before_tx_start(&mut self, tx: &crate::MegaTransaction) {
         // TX intrinsic data (non-discardable, recorded before any frame is pushed)
@@ -224,6 +263,13 @@ impl TxRuntimeLimit for DataSizeTracker {
             .unwrap_or_default();
         size += tx.authorization_list_len() as u64 * AUTHORIZATION_SIZE;
         self.frame_tracker.add_tx_persistent(size);
+        self.tx_raw_size = size;
+        self.tx_da_size = match tx.enveloped_tx.as_deref() {
+            Some(enveloped) if !enveloped.is_empty() => {
+                op_alloy_flz::tx_estimated_size_fjord_bytes(enveloped)
+            }
+            _ => size,
+        };
 
         // EIP-7702 authority account updates (non-discardable).
         //
@@ -385,6 +431,7 @@ impl TxRuntimeLimit for DataSizeTracker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_primitives::Bytes;
 
     /// The originated data-size constants are *sums* of their salt key/value components,
     /// not products. Pin the values against independent literals so an arithmetic slip
@@ -403,4 +450,26 @@ mod tests {
         tracker.push_empty_frame();
         assert!(tracker.has_active_frame(), "a frame is on the stack");
     }
+
+    /// The DA-estimated usage swaps the transaction's raw bytes for the flz estimate of the
+    /// enveloped transaction, and equals the raw usage without one.
+    #[test]
+    fn test_da_usage_uses_flz_estimate_of_enveloped_tx() {
+        let mut tx = crate::MegaTransaction::default();
+        tx.base.data = Bytes::from(vec![0u8; 1000]);
+        tx.enveloped_tx = Some(Bytes::from(vec![0u8; 1000]));
+
+        let mut tracker = DataSizeTracker::new(MegaSpecId::REX4, u64::MAX);
+        tracker.before_tx_start(&tx);
+        let raw = BASE_TX_SIZE + 1000;
+        let da = op_alloy_flz::tx_estimated_size_fjord_bytes(&[0u8; 1000]);
+        assert!(da < raw, "zero bytes compress well");
+        assert_eq!(tracker.tx_usage(), raw + ACCOUNT_INFO_WRITE_SIZE);
+        assert_eq!(tracker.da_usage(), da + ACCOUNT_INFO_WRITE_SIZE);
+
+        tx.enveloped_tx = None;
+        tracker.reset();
+        tracker.before_tx_start(&tx);
+        assert_eq!(tracker.da_usage(), tracker.tx_usage());
+    }
 }
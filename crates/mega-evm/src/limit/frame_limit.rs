@@ -74,8 +74,8 @@ pub(crate) struct FrameLimitEntry<I> {
 
     // The three budget fields below MUST only be mutated through `FrameLimitTracker`'s
     // cache-aware helpers (`add_tx_persistent`, `add_frame_persistent`,
-    // `add_frame_discardable`, `add_frame_refund`) or through `pop_frame`'s explicit
-    // cache deltas. Writing them directly desyncs `cached_total_used` /
+    // `add_frame_discardable`, `add_tx_refund`, `add_frame_refund`) or through `pop_frame`'s
+    // explicit cache deltas. Writing them directly desyncs `cached_total_used` /
     // `cached_total_refund` and silently corrupts every subsequent `net_usage()` result.
     /// Persistent usage in this frame even if it is reverted.
     persistent_usage: u64,
@@ -124,6 +124,14 @@ impl<I> FrameLimitTracker<I> {
         }
     }
 
+    /// Re-initializes the tracker for `spec` and `tx_limit` as [`new`](Self::new) would, but
+    /// keeps the frame stack's allocation.
+    pub(crate) fn reinit(&mut self, spec: MegaSpecId, tx_limit: u64) {
+        let mut frame_stack = core::mem::take(&mut self.frame_stack);
+        frame_stack.clear();
+        *self = Self { frame_stack, ..Self::new(spec, tx_limit) };
+    }
+
     /// Returns the TX-level limit.
     pub(crate) fn tx_limit(&self) -> u64 {
         self.tx_entry.limit
@@ -259,7 +267,7 @@ impl<I> FrameLimitTracker<I> {
     ///
     /// O(1): served from `cached_total_used` / `cached_total_refund`, which are maintained
     /// incrementally by `add_tx_persistent`, `add_frame_persistent`, `add_frame_discardable`,
-    /// `add_frame_refund`, and `pop_frame`.
+    /// `add_tx_refund`, `add_frame_refund`, and `pop_frame`.
     #[inline]
     pub(crate) fn net_usage(&self) -> u64 {
         let net_usage = self.cached_total_used.saturating_sub(self.cached_total_refund);
@@ -300,6 +308,16 @@ impl<I> FrameLimitTracker<I> {
         self.cached_total_used += n;
     }
 
+    /// Adds `n` to `tx_entry.refund` and keeps the cache in sync.
+    ///
+    /// The TX-level counterpart of `add_frame_refund`, for pre-frame events that release
+    /// usage (e.g. clearing an EIP-7702 delegation).
+    #[inline]
+    pub(crate) fn add_tx_refund(&mut self, n: u64) {
+        self.tx_entry.refund += n;
+        self.cached_total_refund += n;
+    }
+
     /// Adds `n` to the current top frame's `persistent_usage` and keeps the cache in sync.
     /// Returns `false` (and does nothing) when the frame stack is empty — callers that
     /// need a tx-level fallback must check the return value.
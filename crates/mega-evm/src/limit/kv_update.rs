@@ -47,6 +47,15 @@ impl KVUpdateTracker {
         }
     }
 
+    /// Re-initializes the tracker for `spec` and `tx_limit` as [`new`](Self::new) would, but
+    /// keeps the frame stack's allocation.
+    pub(crate) fn reinit(&mut self, spec: MegaSpecId, tx_limit: u64) {
+        let mut recycled = Self::new(spec, tx_limit);
+        core::mem::swap(&mut recycled.frame_tracker, &mut self.frame_tracker);
+        recycled.frame_tracker.reinit(spec, tx_limit);
+        *self = recycled;
+    }
+
     /// Records a discardable KV update in the current frame.
     fn record_discardable(&mut self, n: u64) {
         self.frame_tracker.add_frame_discardable(n);
@@ -72,6 +81,12 @@ impl KVUpdateTracker {
         self.record_discardable(1);
     }
 
+    /// Records the KV updates of a stateful precompile call as discardable KV updates in the
+    /// current frame.
+    pub(crate) fn record_precompile_updates(&mut self, n: u64) {
+        self.record_discardable(n);
+    }
+
     /// Records a single account update as TX-level persistent (non-discardable) KV usage.
     ///
     /// Used by the REX6 EIP-7702 authorization scan, which runs in `validate` before any frame
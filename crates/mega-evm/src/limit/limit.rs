@@ -18,7 +18,7 @@ use super::{
 };
 use crate::{
     EvmTxRuntimeLimits, JournalInspectTr, MegaHaltReason, MegaSpecId, MegaTransaction,
-    VolatileDataAccess,
+    PrecompileResourceUsage, VolatileDataAccess,
 };
 
 use super::LimitCheck;
@@ -65,6 +65,11 @@ use super::LimitCheck;
 ///   new), and account updates from value transfers and creates
 /// - **State Growth**: Tracks net new accounts + net new storage slots
 ///
+/// REX6+ also enforces the interpreter memory limit, checked by the memory-expanding opcodes
+/// before memory is resized; it has no tracker since memory does not outlive its frame. REX6+
+/// further caps the number of frames a transaction creates, independent of call depth, so cheap
+/// sub-calls cannot inflate the per-frame overhead while staying under gas.
+///
 /// Additionally, this struct manages the `STORAGE_CALL_STIPEND` (Rex4+): extra gas granted to
 /// value-transferring `CALL`/`CALLCODE` for storage operations. REX5+ tracks the stipend as a
 /// separated internal allowance drained at the `storage_gas_ext` charging sites; REX4 retains
@@ -107,6 +112,13 @@ pub struct AdditionalLimit {
 
     /// A tracker for the `STORAGE_CALL_STIPEND` granted to value-transferring calls (REX4+).
     pub(crate) storage_call_stipend: storage_call_stipend::StorageCallStipendTracker,
+
+    /// The number of frames created in the current transaction, including the top-level frame.
+    pub(crate) frames_created: u64,
+
+    /// The enforced frame limit: [`EvmTxRuntimeLimits::tx_frame_limit`] from REX6, unbounded
+    /// before.
+    frame_limit: u64,
 }
 
 /// The usage of the additional limits.
@@ -114,12 +126,18 @@ pub struct AdditionalLimit {
 pub struct LimitUsage {
     /// The data size usage in bytes.
     pub data_size: u64,
+    /// The data size usage in bytes with the transaction's own bytes counted at their
+    /// flz-estimated compressed size, i.e. their DA size, instead of raw. Not enforced by the
+    /// EVM; a [`BlockLimits`](crate::BlockLimits) may account it against the block data limit.
+    pub da_data_size: u64,
     /// The number of KV updates.
     pub kv_updates: u64,
     /// The compute gas usage.
     pub compute_gas: u64,
     /// The state growth.
     pub state_growth: u64,
+    /// The number of frames created, including the top-level frame.
+    pub frames_created: u64,
 }
 
 impl AdditionalLimit {
@@ -134,6 +152,34 @@ impl AdditionalLimit {
             kv_update: kv_update::KVUpdateTracker::new(spec, limits.tx_kv_updates_limit),
             compute_gas: compute_gas::ComputeGasTracker::new(spec, limits.tx_compute_gas_limit),
             storage_call_stipend: storage_call_stipend::StorageCallStipendTracker::new(spec),
+            frames_created: 0,
+            frame_limit: Self::frame_limit_for(spec, limits),
+        }
+    }
+
+    /// Re-initializes the limits for `spec` and `limits` as [`new`](Self::new) would, but keeps
+    /// the allocations of the trackers' frame stacks.
+    ///
+    /// Used by [`TrackerPool`](super::TrackerPool) to hand out recycled instances.
+    pub(crate) fn reinit(&mut self, spec: MegaSpecId, limits: EvmTxRuntimeLimits) {
+        self.has_exceeded_limit = LimitCheck::WithinLimit;
+        self.rescued_gas = 0;
+        self.limits = limits;
+        self.state_growth.reinit(spec, limits.tx_state_growth_limit);
+        self.data_size.reinit(spec, limits.tx_data_size_limit);
+        self.kv_update.reinit(spec, limits.tx_kv_updates_limit);
+        self.compute_gas.reinit(spec, limits.tx_compute_gas_limit);
+        self.storage_call_stipend.reinit(spec);
+        self.frames_created = 0;
+        self.frame_limit = Self::frame_limit_for(spec, limits);
+    }
+
+    /// Returns the frame limit enforced for `spec`. Frames are only capped from REX6.
+    fn frame_limit_for(spec: MegaSpecId, limits: EvmTxRuntimeLimits) -> u64 {
+        if spec.is_enabled(MegaSpecId::REX6) {
+            limits.tx_frame_limit
+        } else {
+            u64::MAX
         }
     }
 }
@@ -175,6 +221,7 @@ impl AdditionalLimit {
         self.data_size.reset();
         self.kv_update.reset();
         self.storage_call_stipend.reset();
+        self.frames_created = 0;
     }
 
     /// Test-only setter for [`has_exceeded_limit`](Self::has_exceeded_limit). Bypasses every
@@ -215,9 +262,11 @@ impl AdditionalLimit {
     pub fn get_usage(&self) -> LimitUsage {
         LimitUsage {
             data_size: self.data_size.tx_usage(),
+            da_data_size: self.data_size.da_usage(),
             kv_updates: self.kv_update.tx_usage(),
             compute_gas: self.compute_gas.tx_usage(),
             state_growth: self.state_growth.tx_usage(),
+            frames_created: self.frames_created,
         }
     }
 
@@ -266,6 +315,12 @@ impl AdditionalLimit {
         self.storage_call_stipend.push_empty_frame();
     }
 
+    /// Returns the number of frames created in the current transaction.
+    #[inline]
+    pub fn frames_created(&self) -> u64 {
+        self.frames_created
+    }
+
     /// Returns the current effective compute gas limit (may be detained/lowered by volatile
     /// data access).
     #[inline]
@@ -463,6 +518,55 @@ impl AdditionalLimit {
         true
     }
 
+    /// Checks an expansion of the current interpreter's memory to `new_size` bytes against the
+    /// interpreter memory limit and returns `false` if the limit has been exceeded.
+    ///
+    /// Called by the REX6+ memory-expanding opcode wrappers before memory is resized, so an
+    /// over-limit expansion is never allocated. An exceed is latched as a TX-level
+    /// [`LimitKind::Memory`](super::LimitKind::Memory) exceed: memory is not a metered resource
+    /// with a per-frame budget, it bounds the sequencer's allocations. Like every other dimension,
+    /// it is not enforced against an `Exempt` transaction.
+    #[inline]
+    pub(crate) fn check_memory_expansion(&mut self, new_size: u64) -> bool {
+        if !self.has_exceeded_limit.within_limit() {
+            return !self.has_exceeded_limit.exceeded_limit();
+        }
+        if new_size > self.limits.interpreter_memory_limit {
+            self.has_exceeded_limit = LimitCheck::ExceedsLimit {
+                kind: super::LimitKind::Memory,
+                limit: self.limits.interpreter_memory_limit,
+                used: new_size,
+                frame_local: false,
+            };
+            return false;
+        }
+        true
+    }
+
+    /// Counts a new frame against the transaction's frame limit and returns `false` if a limit has
+    /// been exceeded.
+    ///
+    /// Crossing the frame limit (REX6+) is a TX-level exceed, wherever the frame is created: the
+    /// limit bounds the total number of frames of the transaction, not its call depth. Like every
+    /// other dimension, it is not enforced against an `Exempt` transaction.
+    #[inline]
+    pub(crate) fn record_frame_created(&mut self) -> bool {
+        self.frames_created += 1;
+        if !self.has_exceeded_limit.within_limit() {
+            return !self.has_exceeded_limit.exceeded_limit();
+        }
+        if self.frames_created > self.frame_limit {
+            self.has_exceeded_limit = LimitCheck::ExceedsLimit {
+                kind: super::LimitKind::FrameCount,
+                limit: self.frame_limit,
+                used: self.frames_created,
+                frame_local: false,
+            };
+            return false;
+        }
+        true
+    }
+
     /// Records the compute gas used and checks ALL four limit dimensions (the
     /// pre-optimization fan-out), returning `false` if any has been exceeded.
     ///
@@ -574,6 +678,16 @@ impl AdditionalLimit {
         self.check_limit();
     }
 
+    /// Records the net EIP-7702 delegation changes of a REX6 authorization list as TX-level state
+    /// growth: +1 for each pre-existing authority that gains a delegation and -1 for each that
+    /// loses one.
+    ///
+    /// Latches any TX-level overflow into `has_exceeded_limit` via `check_limit`.
+    pub(crate) fn on_rex6_eip7702_delegation_changes(&mut self, installs: u64, clears: u64) {
+        self.state_growth.record_delegation_changes(installs, clears);
+        self.check_limit();
+    }
+
     /// Hook called before a new execution frame is initialized. Returns `Some(FrameResult)` if the
     /// limit is exceeded and the frame should terminate early with the returned `FrameResult`.
     ///
@@ -595,6 +709,9 @@ impl AdditionalLimit {
         // to stack, and cap per-frame compute gas budget.
         self.storage_call_stipend.before_frame_init(frame_init, &mut self.compute_gas);
 
+        // REX6+: count the frame against the per-transaction frame limit.
+        self.record_frame_created();
+
         if self.check_limit().exceeded_limit() {
             return Ok(self.create_exceeded_limit_result(&frame_init.frame_input));
         }
@@ -818,6 +935,7 @@ impl AdditionalLimit {
         self.data_size.merge_persistent_usage(usage.data_size);
         self.kv_update.merge_persistent_usage(usage.kv_updates);
         self.state_growth.merge_persistent_usage(usage.state_growth);
+        self.frames_created = self.frames_created.saturating_add(usage.frames_created);
     }
 
     /// Hook called when an orginally zero storage slot is written non-zero value for the first time
@@ -868,6 +986,30 @@ impl AdditionalLimit {
         !self.check_limit().exceeded_limit()
     }
 
+    /// Hook called after a successful call to a stateful precompile, whose state writes bypass the
+    /// `SSTORE` hooks.
+    ///
+    /// Records `usage` as discardable usage of the precompile's frame and marks `result` as
+    /// exceeding the limit if the usage overflows a limit.
+    pub(crate) fn after_precompile_call(
+        &mut self,
+        usage: PrecompileResourceUsage,
+        result: &mut InterpreterResult,
+    ) {
+        self.data_size.record_precompile_writes(usage.data_size);
+        self.kv_update.record_precompile_updates(usage.kv_updates);
+        self.state_growth.record_growth(usage.state_growth);
+
+        if self.check_limit().exceeded_limit() {
+            let output = self.has_exceeded_limit.revert_data();
+            mark_interpreter_result_as_exceeding_limit(
+                result,
+                self.exceeding_instruction_result(),
+                output,
+            );
+        }
+    }
+
     /// Hook called when a log is written. Returns `false` if the limit has been exceeded.
     pub(crate) fn on_log(&mut self, num_topics: u64, data_size: u64) -> bool {
         self.state_growth.after_log(num_topics, data_size);
@@ -994,6 +1136,8 @@ mod metering_exemption_tests {
             tx_state_growth_limit: 1,
             block_env_access_compute_gas_limit: u64::MAX,
             oracle_access_compute_gas_limit: u64::MAX,
+            interpreter_memory_limit: u64::MAX,
+            tx_frame_limit: u64::MAX,
         }
     }
 
@@ -1063,6 +1207,8 @@ mod tests {
             tx_state_growth_limit: 1_000,
             block_env_access_compute_gas_limit: 1_000_000,
             oracle_access_compute_gas_limit: 1_000_000,
+            interpreter_memory_limit: 1_024,
+            tx_frame_limit: 2,
         }
     }
 
@@ -1133,6 +1279,57 @@ mod tests {
         assert_eq!(latched_kind(&limit), Some(LimitKind::DataSize));
     }
 
+    /// An over-limit memory expansion latches a TX-level (not frame-local) exceed that maps to
+    /// `MemoryLimitExceeded`, and an expansion up to the limit itself is allowed.
+    #[test]
+    fn test_check_memory_expansion_latches_tx_level_exceed() {
+        let mut limit = AdditionalLimit::new(MegaSpecId::REX6, test_limits());
+        limit.push_empty_frame();
+
+        assert!(limit.check_memory_expansion(1_024), "expansion up to the limit must pass");
+        assert_eq!(latched_kind(&limit), None);
+
+        assert!(!limit.check_memory_expansion(1_056), "expansion past the limit must fail");
+        assert_eq!(latched_kind(&limit), Some(LimitKind::Memory));
+        assert!(!limit.has_exceeded_limit.is_frame_local(), "memory exceed must be TX-level");
+        assert_eq!(limit.exceeding_instruction_result(), InstructionResult::OutOfGas);
+        assert_eq!(
+            limit.check_limit().maybe_halt_reason(),
+            Some(MegaHaltReason::MemoryLimitExceeded { limit: 1_024, actual: 1_056 })
+        );
+    }
+
+    #[test]
+    fn test_frame_limit_latches_tx_level_exceed() {
+        let mut limit = AdditionalLimit::new(MegaSpecId::REX6, test_limits());
+
+        assert!(limit.record_frame_created(), "frames up to the limit must pass");
+        assert!(limit.record_frame_created(), "frames up to the limit must pass");
+        assert_eq!(latched_kind(&limit), None);
+
+        assert!(!limit.record_frame_created(), "a frame past the limit must fail");
+        assert_eq!(latched_kind(&limit), Some(LimitKind::FrameCount));
+        assert!(!limit.has_exceeded_limit.is_frame_local(), "frame exceed must be TX-level");
+        assert_eq!(
+            limit.check_limit().maybe_halt_reason(),
+            Some(MegaHaltReason::FrameLimitExceeded { limit: 2, actual: 3 })
+        );
+
+        limit.reset();
+        assert_eq!(limit.frames_created(), 0);
+        assert!(limit.record_frame_created(), "reset must clear the frame count");
+    }
+
+    #[test]
+    fn test_frame_limit_not_enforced_before_rex6() {
+        let mut limit = AdditionalLimit::new(MegaSpecId::REX5, test_limits());
+        for _ in 0..10 {
+            assert!(limit.record_frame_created(), "pre-REX6 frames must not be capped");
+        }
+        assert_eq!(limit.frames_created(), 10);
+        assert_eq!(latched_kind(&limit), None);
+    }
+
     /// `intrinsic_check_for_tx` is a REX5-only preflight (EIP-7702 authority growth);
     /// its `debug_assert!(spec.is_enabled(REX5))` precondition must reject a pre-REX5
     /// spec. Calling it at REX4 must trip the assert.
@@ -7,12 +7,14 @@ mod frame_limit;
 mod kv_update;
 #[allow(clippy::module_inception)]
 mod limit;
+mod pool;
 mod state_growth;
 mod storage_call_stipend;
 
 pub use data_size::*;
 pub(crate) use frame_limit::{FrameLimitTracker, TxRuntimeLimit};
 pub use limit::*;
+pub use pool::*;
 
 use crate::MegaHaltReason;
 
@@ -33,6 +35,10 @@ pub enum LimitKind {
     ComputeGas,
     /// State growth limit (net new accounts and storage slots).
     StateGrowth,
+    /// Interpreter memory limit (bytes of memory of a single call frame, REX6+).
+    Memory,
+    /// Frame count limit (frames created by a transaction, REX6+).
+    FrameCount,
 }
 
 impl LimitKind {
@@ -43,6 +49,8 @@ impl LimitKind {
             Self::KVUpdate => 1,
             Self::ComputeGas => 2,
             Self::StateGrowth => 3,
+            Self::Memory => 4,
+            Self::FrameCount => 5,
         }
     }
 
@@ -53,6 +61,8 @@ impl LimitKind {
             1 => Some(Self::KVUpdate),
             2 => Some(Self::ComputeGas),
             3 => Some(Self::StateGrowth),
+            4 => Some(Self::Memory),
+            5 => Some(Self::FrameCount),
             _ => None,
         }
     }
@@ -151,6 +161,12 @@ impl LimitCheck {
             Self::ExceedsLimit { kind: LimitKind::StateGrowth, limit, used, .. } => {
                 Some(MegaHaltReason::StateGrowthLimitExceeded { limit: *limit, actual: *used })
             }
+            Self::ExceedsLimit { kind: LimitKind::Memory, limit, used, .. } => {
+                Some(MegaHaltReason::MemoryLimitExceeded { limit: *limit, actual: *used })
+            }
+            Self::ExceedsLimit { kind: LimitKind::FrameCount, limit, used, .. } => {
+                Some(MegaHaltReason::FrameLimitExceeded { limit: *limit, actual: *used })
+            }
             Self::WithinLimit | Self::Exempt => None,
         }
     }
@@ -196,6 +212,8 @@ mod tests {
             LimitKind::KVUpdate,
             LimitKind::ComputeGas,
             LimitKind::StateGrowth,
+            LimitKind::Memory,
+            LimitKind::FrameCount,
         ] {
             assert_eq!(
                 LimitKind::from_u8(kind.as_u8()),
@@ -203,6 +221,6 @@ mod tests {
                 "round-trip failed for {kind:?}"
             );
         }
-        assert_eq!(LimitKind::from_u8(4), None);
+        assert_eq!(LimitKind::from_u8(6), None);
     }
 }
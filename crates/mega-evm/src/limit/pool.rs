@@ -0,0 +1,101 @@
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::{rc::Rc, vec::Vec};
+
+use core::cell::RefCell;
+
+use crate::{AdditionalLimit, EvmTxRuntimeLimits, MegaSpecId};
+
+/// A pool of [`AdditionalLimit`] instances whose tracker frame stacks are reused across
+/// contexts.
+///
+/// A [`MegaContext`](crate::MegaContext) resets its trackers in place between transactions, so
+/// the frame stacks only grow once per context. Block builders, however, create a fresh context
+/// for every block, and each one starts with empty stacks that reallocate as call depth grows.
+/// Sharing a pool between contexts (see [`MegaContext::set_tracker_pool`]) lets every new context
+/// start from stacks that have already grown. [`MegaBlockExecutor`](crate::MegaBlockExecutor)
+/// returns the trackers to the pool when the block is finished.
+///
+/// The pool is a cheap, single-threaded handle: clones share the same idle instances.
+///
+/// [`MegaContext::set_tracker_pool`]: crate::MegaContext::set_tracker_pool
+#[derive(Debug, Clone, Default)]
+pub struct TrackerPool {
+    idle: Rc<RefCell<Vec<AdditionalLimit>>>,
+}
+
+impl TrackerPool {
+    /// Creates an empty pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of idle instances in the pool.
+    pub fn len(&self) -> usize {
+        self.idle.borrow().len()
+    }
+
+    /// Returns `true` if the pool holds no idle instances.
+    pub fn is_empty(&self) -> bool {
+        self.idle.borrow().is_empty()
+    }
+
+    /// Takes an idle instance re-initialized for `spec` and `limits`, or creates one if the pool
+    /// is empty.
+    pub(crate) fn acquire(&self, spec: MegaSpecId, limits: EvmTxRuntimeLimits) -> AdditionalLimit {
+        match self.idle.borrow_mut().pop() {
+            Some(mut limit) => {
+                limit.reinit(spec, limits);
+                limit
+            }
+            None => AdditionalLimit::new(spec, limits),
+        }
+    }
+
+    /// Returns an instance to the pool.
+    pub(crate) fn release(&self, limit: AdditionalLimit) {
+        self.idle.borrow_mut().push(limit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MegaContext;
+    use revm::database::EmptyDB;
+
+    #[test]
+    fn test_acquire_matches_fresh_instance() {
+        let pool = TrackerPool::new();
+        let mut used =
+            pool.acquire(MegaSpecId::REX4, EvmTxRuntimeLimits::from_spec(MegaSpecId::REX4));
+        used.push_empty_frame();
+        used.rescued_gas = 7;
+        pool.release(used);
+        assert_eq!(pool.len(), 1);
+
+        let limits = EvmTxRuntimeLimits::from_spec(MegaSpecId::REX6);
+        let recycled = pool.acquire(MegaSpecId::REX6, limits);
+        assert!(pool.is_empty());
+        assert_eq!(
+            format!("{recycled:?}"),
+            format!("{:?}", AdditionalLimit::new(MegaSpecId::REX6, limits))
+        );
+    }
+
+    #[test]
+    fn test_contexts_reuse_released_trackers() {
+        let pool = TrackerPool::new();
+        let mut ctx =
+            MegaContext::new(EmptyDB::default(), MegaSpecId::REX4).with_tracker_pool(pool.clone());
+        assert!(pool.is_empty());
+        ctx.release_trackers();
+        assert_eq!(pool.len(), 1);
+
+        let next = MegaContext::new(EmptyDB::default(), MegaSpecId::REX5)
+            .with_tracker_pool(pool.clone())
+            .with_tx_runtime_limits(EvmTxRuntimeLimits::no_limits());
+        assert!(pool.is_empty());
+        assert_eq!(next.additional_limit.borrow().limits, EvmTxRuntimeLimits::no_limits());
+    }
+}
@@ -12,6 +12,8 @@
 //!   unconditional under pre-REX6) or via `CALL` with value transfer to an empty account
 //! - **+1** for each valid EIP-7702 authorization that creates a previously non-existent authority
 //!   account (Rex5+)
+//! - **+1** for each EIP-7702 delegation installed on an existing account that had none, and **-1**
+//!   for each delegation cleared from one (Rex6+, net over the authorization list)
 //! - **+1** for writing a storage slot from zero to non-zero for the first time
 //! - **-1** for clearing a storage slot back to zero (only when the slot was empty at transaction
 //!   start)
@@ -94,6 +96,8 @@ use crate::{FrameLimitTracker, JournalInspectTr, MegaSpecId, TxRuntimeLimit};
 ///   unconditional under pre-REX6) or via `CALL` with value transfer to an empty account (EIP-161)
 /// - **+1** for each valid EIP-7702 authorization that creates a previously non-existent authority
 ///   account (Rex5+)
+/// - **+1** for each EIP-7702 delegation installed on an existing account that had none, and **-1**
+///   for each delegation cleared from one (Rex6+, net over the authorization list)
 /// - **+1** for writing a storage slot from zero to non-zero for the first time
 /// - **-1** for clearing a storage slot back to zero (only when the slot was empty at transaction
 ///   start)
@@ -110,6 +114,15 @@ impl StateGrowthTracker {
         Self { spec, frame_tracker: FrameLimitTracker::new(spec, tx_limit) }
     }
 
+    /// Re-initializes the tracker for `spec` and `tx_limit` as [`new`](Self::new) would, but
+    /// keeps the frame stack's allocation.
+    pub(crate) fn reinit(&mut self, spec: MegaSpecId, tx_limit: u64) {
+        let mut recycled = Self::new(spec, tx_limit);
+        core::mem::swap(&mut recycled.frame_tracker, &mut self.frame_tracker);
+        recycled.frame_tracker.reinit(spec, tx_limit);
+        *self = recycled;
+    }
+
     /// Pushes a new frame onto the tracker.
     ///
     /// For Rex4+, delegates to `FrameLimitTracker::push_frame()` which uses
@@ -161,6 +174,16 @@ impl StateGrowthTracker {
         self.frame_tracker.add_tx_persistent(amount);
     }
 
+    /// Records EIP-7702 delegations installed on (`installs`) and cleared from (`clears`)
+    /// pre-existing authority accounts by pre-execution auth processing (Rex6+).
+    ///
+    /// Like [`Self::record_authority_creations`], this is TX-level usage that frame reverts do
+    /// not discard.
+    pub(crate) fn record_delegation_changes(&mut self, installs: u64, clears: u64) {
+        self.frame_tracker.add_tx_persistent(installs);
+        self.frame_tracker.add_tx_refund(clears);
+    }
+
     /// Returns the remaining state growth budget for the current call frame, capped by
     /// the TX-level remaining.
     pub(crate) fn current_call_remaining(&self) -> u64 {
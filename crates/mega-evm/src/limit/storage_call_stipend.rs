@@ -65,6 +65,14 @@ impl StorageCallStipendTracker {
         }
     }
 
+    /// Re-initializes the tracker for `spec` as [`new`](Self::new) would, but keeps the stack's
+    /// allocation.
+    pub(crate) fn reinit(&mut self, spec: MegaSpecId) {
+        let mut stack = core::mem::take(&mut self.stack);
+        stack.clear();
+        *self = Self { stack, ..Self::new(spec) };
+    }
+
     /// Returns the stipend amount for the given spec. Zero for pre-REX4.
     fn stipend_for_spec(spec: MegaSpecId) -> u64 {
         if spec.is_enabled(MegaSpecId::REX4) {
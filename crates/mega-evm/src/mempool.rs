@@ -0,0 +1,403 @@
+//! Stateless pre-checks for admitting transactions into the mempool.
+//!
+//! [`admission_check`] rejects a transaction that the EVM would certainly reject as invalid under
+//! the given spec, with the same reason: [`InvalidTransaction`] for the checks revm performs in
+//! `validate`, and [`MegaTxLimitExceededError`] for the transaction-level limits the block executor
+//! enforces in [`BlockLimiter::pre_execution_check`](crate::BlockLimiter::pre_execution_check).
+//!
+//! The checks only look at the transaction itself. Checks that depend on the state, such as the
+//! nonce, the balance, or the storage gas of a created or newly funded account, are left to
+//! execution, so an admitted transaction may still be rejected when it is included.
+//!
+//! [`intrinsic_gas`] exposes the intrinsic gas `admission_check` requires, by component, e.g. for
+//! gas estimators.
+
+use alloy_consensus::Transaction;
+use alloy_eips::Encodable2718;
+use op_revm::transaction::deposit::DEPOSIT_TRANSACTION_TYPE;
+use revm::{
+    context::result::InvalidTransaction,
+    interpreter::gas::{calculate_initial_tx_gas, get_tokens_in_calldata, STANDARD_TOKEN_COST},
+    primitives::hardfork::SpecId,
+};
+
+use crate::{
+    BlockLimits, MegaChainConfig, MegaErrorKind, MegaSpecId, MegaTransactionExt,
+    MegaTxLimitExceededError,
+};
+
+/// The chain parameters a transaction is admitted against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdmissionConfig {
+    /// The chain configuration, providing the chain id.
+    pub chain: MegaChainConfig,
+    /// The block limits, providing the transaction-level gas, size and DA size limits.
+    pub limits: BlockLimits,
+    /// The minimum fee per gas a transaction must be willing to pay, usually the base fee of the
+    /// pending block.
+    pub base_fee: u64,
+}
+
+impl AdmissionConfig {
+    /// Creates an admission configuration with no base fee requirement.
+    pub fn new(chain: MegaChainConfig, limits: BlockLimits) -> Self {
+        Self { chain, limits, base_fee: 0 }
+    }
+
+    /// Sets the minimum fee per gas.
+    pub fn with_base_fee(mut self, base_fee: u64) -> Self {
+        self.base_fee = base_fee;
+        self
+    }
+}
+
+/// The reason a transaction is not admitted into the mempool.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AdmissionError {
+    /// Deposit transactions are derived from L1 and cannot be submitted to the mempool.
+    #[error("deposit transactions cannot be submitted to the mempool")]
+    Deposit,
+    /// The transaction is invalid under the spec.
+    #[error(transparent)]
+    Invalid(#[from] InvalidTransaction),
+    /// The transaction exceeds a transaction-level limit.
+    #[error(transparent)]
+    LimitExceeded(#[from] MegaTxLimitExceededError),
+}
+
+impl MegaErrorKind for AdmissionError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Deposit => false,
+            Self::Invalid(err) => err.is_retryable(),
+            Self::LimitExceeded(err) => err.is_retryable(),
+        }
+    }
+
+    fn is_client_error(&self) -> bool {
+        true
+    }
+}
+
+/// Checks whether `tx` may be admitted into the mempool under `spec`.
+///
+/// In order, the checks are:
+/// 1. the transaction is not a deposit and its type is supported (no EIP-4844, EIP-7702 only from
+///    Prague on, with a non-empty authorization list);
+/// 2. the chain id matches the chain's;
+/// 3. the gas limit, the EIP-2718 encoded size and the estimated DA size are within the
+///    transaction-level limits;
+/// 4. the initcode of a contract creation is within the spec's initcode size limit;
+/// 5. the priority fee does not exceed the max fee, which covers the base fee;
+/// 6. the gas limit covers the intrinsic gas and the EIP-7623 floor, including the calldata and
+///    intrinsic storage gas of `MINI_REX` and `REX`.
+pub fn admission_check<T>(
+    tx: &T,
+    config: &AdmissionConfig,
+    spec: MegaSpecId,
+) -> Result<(), AdmissionError>
+where
+    T: Transaction + Encodable2718 + MegaTransactionExt,
+{
+    let eth_spec = spec.into_eth_spec();
+    let constants = spec.constants();
+
+    if tx.ty() == DEPOSIT_TRANSACTION_TYPE {
+        return Err(AdmissionError::Deposit);
+    }
+    if tx.is_eip4844() {
+        return Err(InvalidTransaction::Eip4844NotSupported.into());
+    }
+    if tx.is_eip7702() {
+        if !eth_spec.is_enabled_in(SpecId::PRAGUE) {
+            return Err(InvalidTransaction::Eip7702NotSupported.into());
+        }
+        if tx.authorization_list().is_none_or(|list| list.is_empty()) {
+            return Err(InvalidTransaction::EmptyAuthorizationList.into());
+        }
+    }
+
+    if tx.chain_id().is_some_and(|chain_id| chain_id != config.chain.chain_id) {
+        return Err(InvalidTransaction::InvalidChainId.into());
+    }
+
+    let limits = &config.limits;
+    if tx.gas_limit() > limits.tx_gas_limit {
+        return Err(MegaTxLimitExceededError::TransactionGasLimit {
+            tx_gas_limit: tx.gas_limit(),
+            limit: limits.tx_gas_limit,
+        }
+        .into());
+    }
+    let tx_size = tx.tx_size();
+    if tx_size > limits.tx_encode_size_limit {
+        return Err(MegaTxLimitExceededError::TransactionEncodeSizeLimit {
+            tx_size,
+            limit: limits.tx_encode_size_limit,
+        }
+        .into());
+    }
+    let da_size = tx.estimated_da_size();
+    if da_size > limits.tx_da_size_limit {
+        return Err(MegaTxLimitExceededError::DataAvailabilitySizeLimit {
+            da_size,
+            limit: limits.tx_da_size_limit,
+        }
+        .into());
+    }
+
+    if tx.is_create() && tx.input().len() > constants.max_initcode_size {
+        return Err(InvalidTransaction::CreateInitCodeSizeLimit.into());
+    }
+
+    if tx.max_priority_fee_per_gas().is_some_and(|priority_fee| priority_fee > tx.max_fee_per_gas())
+    {
+        return Err(InvalidTransaction::PriorityFeeGreaterThanMaxFee.into());
+    }
+    if tx.max_fee_per_gas() < config.base_fee as u128 {
+        return Err(InvalidTransaction::GasPriceLessThanBasefee.into());
+    }
+
+    let gas = intrinsic_gas(tx, spec);
+    let gas_limit = tx.gas_limit();
+    let initial_gas = gas.initial_gas();
+    if initial_gas > gas_limit {
+        return Err(
+            InvalidTransaction::CallGasCostMoreThanGasLimit { gas_limit, initial_gas }.into()
+        );
+    }
+    if gas.floor > gas_limit {
+        return Err(InvalidTransaction::GasFloorMoreThanGasLimit {
+            gas_limit,
+            gas_floor: gas.floor,
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// The intrinsic gas of a transaction: the gas charged before its first frame runs, by component.
+///
+/// The state-independent part of what the EVM charges in `validate`, so the mempool and gas
+/// estimators reuse the exact validation math. The storage gas of a created or newly funded
+/// account depends on the state and is not included.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IntrinsicGas {
+    /// The base cost of a transaction, plus the creation cost and the EIP-3860 initcode cost of a
+    /// contract creation.
+    pub base: u64,
+    /// The calldata cost, including the calldata storage gas from `MINI_REX`.
+    pub calldata: u64,
+    /// The EIP-2930 access list cost.
+    pub access_list: u64,
+    /// The EIP-7702 authorization list cost.
+    pub authorization: u64,
+    /// The intrinsic storage gas charged to every transaction from `REX`.
+    pub storage: u64,
+    /// The EIP-7623 floor, including the calldata floor storage gas from `MINI_REX`. Zero before
+    /// Prague, where no floor applies.
+    pub floor: u64,
+}
+
+impl IntrinsicGas {
+    /// Returns the initial gas the gas limit must cover: the sum of every component but the floor.
+    pub fn initial_gas(&self) -> u64 {
+        self.base + self.calldata + self.access_list + self.authorization + self.storage
+    }
+
+    /// Returns the minimum gas limit of the transaction: the initial gas or the floor, whichever
+    /// is larger.
+    pub fn min_gas_limit(&self) -> u64 {
+        self.initial_gas().max(self.floor)
+    }
+}
+
+/// Returns the intrinsic gas of `tx` under `spec`.
+pub fn intrinsic_gas<T: Transaction>(tx: &T, spec: MegaSpecId) -> IntrinsicGas {
+    let eth_spec = spec.into_eth_spec();
+    let spec_constants = spec.constants();
+    let (access_list_accounts, access_list_storages) =
+        tx.access_list().map_or((0, 0), |access_list| {
+            let storages = access_list.iter().map(|item| item.storage_keys.len() as u64).sum();
+            (access_list.len() as u64, storages)
+        });
+    let authorizations = tx.authorization_count().unwrap_or_default();
+    let initial_gas = |input: &[u8], is_create, accounts, storages, authorizations| {
+        calculate_initial_tx_gas(eth_spec, input, is_create, accounts, storages, authorizations)
+    };
+
+    // revm only computes the total, so the list costs are isolated by difference.
+    let gas = initial_gas(
+        tx.input(),
+        tx.is_create(),
+        access_list_accounts,
+        access_list_storages,
+        authorizations,
+    );
+    let empty = initial_gas(&[], false, 0, 0, 0).initial_gas;
+    let access_list =
+        initial_gas(&[], false, access_list_accounts, access_list_storages, 0).initial_gas - empty;
+    let authorization = initial_gas(&[], false, 0, 0, authorizations).initial_gas - empty;
+    let tokens_in_calldata = get_tokens_in_calldata(tx.input(), true);
+    let standard_calldata = tokens_in_calldata * STANDARD_TOKEN_COST;
+
+    let floor = if eth_spec.is_enabled_in(SpecId::PRAGUE) {
+        gas.floor_gas +
+            spec_constants.calldata_standard_token_storage_floor_gas * tokens_in_calldata
+    } else {
+        0
+    };
+    IntrinsicGas {
+        base: gas.initial_gas - standard_calldata - access_list - authorization,
+        calldata: standard_calldata +
+            spec_constants.calldata_standard_token_storage_gas * tokens_in_calldata,
+        access_list,
+        authorization,
+        storage: spec_constants.tx_intrinsic_storage_gas,
+        floor,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{constants, MegaTxEnvelope};
+    use alloy_consensus::{Signed, TxEip1559, TxLegacy};
+    use alloy_eips::eip2930::{AccessList, AccessListItem};
+    use alloy_primitives::{address, Address, Bytes, Signature, TxKind, B256, U256};
+
+    const CALLEE: Address = address!("0000000000000000000000000000000000100001");
+
+    fn legacy(gas_limit: u64, input: Bytes) -> MegaTxEnvelope {
+        let tx = TxLegacy {
+            chain_id: Some(1),
+            nonce: 0,
+            gas_price: 1,
+            gas_limit,
+            to: TxKind::Call(CALLEE),
+            value: U256::ZERO,
+            input,
+        };
+        MegaTxEnvelope::Legacy(Signed::new_unchecked(
+            tx,
+            Signature::test_signature(),
+            Default::default(),
+        ))
+    }
+
+    fn config() -> AdmissionConfig {
+        AdmissionConfig::new(MegaChainConfig::default(), BlockLimits::no_limits())
+    }
+
+    #[test]
+    fn test_intrinsic_storage_gas_is_required_from_rex() {
+        let tx = legacy(21_000, Bytes::new());
+        assert!(admission_check(&tx, &config(), MegaSpecId::MINI_REX).is_ok());
+        assert!(matches!(
+            admission_check(&tx, &config(), MegaSpecId::REX),
+            Err(AdmissionError::Invalid(InvalidTransaction::CallGasCostMoreThanGasLimit {
+                gas_limit: 21_000,
+                initial_gas,
+            })) if initial_gas == 21_000 + constants::rex::TX_INTRINSIC_STORAGE_GAS
+        ));
+
+        let tx = legacy(21_000 + constants::rex::TX_INTRINSIC_STORAGE_GAS, Bytes::new());
+        assert!(admission_check(&tx, &config(), MegaSpecId::REX).is_ok());
+    }
+
+    #[test]
+    fn test_intrinsic_gas_breaks_down_the_validation_math() {
+        let tx = TxEip1559 {
+            chain_id: 1,
+            gas_limit: 1_000_000,
+            to: TxKind::Call(CALLEE),
+            input: Bytes::from_static(&[1; 100]),
+            access_list: AccessList(Vec::from([AccessListItem {
+                address: CALLEE,
+                storage_keys: Vec::from([B256::ZERO, B256::repeat_byte(1)]),
+            }])),
+            ..Default::default()
+        };
+        let tx = MegaTxEnvelope::Eip1559(Signed::new_unchecked(
+            tx,
+            Signature::test_signature(),
+            Default::default(),
+        ));
+        let tokens = 4 * 100;
+
+        let gas = intrinsic_gas(&tx, MegaSpecId::REX);
+        assert_eq!(
+            gas,
+            IntrinsicGas {
+                base: 21_000,
+                calldata: tokens * (4 + constants::mini_rex::CALLDATA_STANDARD_TOKEN_STORAGE_GAS),
+                access_list: 2_400 + 2 * 1_900,
+                authorization: 0,
+                storage: constants::rex::TX_INTRINSIC_STORAGE_GAS,
+                floor: 21_000 +
+                    tokens *
+                        (10 + constants::mini_rex::CALLDATA_STANDARD_TOKEN_STORAGE_FLOOR_GAS),
+            }
+        );
+        assert_eq!(gas.min_gas_limit(), gas.initial_gas().max(gas.floor));
+
+        let equivalence = intrinsic_gas(&tx, MegaSpecId::EQUIVALENCE);
+        assert_eq!(equivalence.calldata, tokens * 4);
+        assert_eq!(equivalence.storage, 0);
+        assert_eq!(equivalence.floor, 21_000 + tokens * 10);
+    }
+
+    #[test]
+    fn test_limits_and_fees_are_checked() {
+        let tx = legacy(1_000_000, Bytes::from_static(&[1; 100]));
+        let spec = MegaSpecId::REX;
+
+        let limits = BlockLimits::no_limits().with_tx_gas_limit(100_000);
+        assert!(matches!(
+            admission_check(&tx, &AdmissionConfig::new(MegaChainConfig::default(), limits), spec),
+            Err(AdmissionError::LimitExceeded(
+                MegaTxLimitExceededError::TransactionGasLimit { .. }
+            ))
+        ));
+
+        let limits = BlockLimits::no_limits().with_tx_encode_size_limit(100);
+        assert!(matches!(
+            admission_check(&tx, &AdmissionConfig::new(MegaChainConfig::default(), limits), spec),
+            Err(AdmissionError::LimitExceeded(
+                MegaTxLimitExceededError::TransactionEncodeSizeLimit { .. }
+            ))
+        ));
+
+        assert!(matches!(
+            admission_check(&tx, &config().with_base_fee(2), spec),
+            Err(AdmissionError::Invalid(InvalidTransaction::GasPriceLessThanBasefee))
+        ));
+        assert!(matches!(
+            admission_check(
+                &tx,
+                &AdmissionConfig { chain: MegaChainConfig::new(2), ..config() },
+                spec
+            ),
+            Err(AdmissionError::Invalid(InvalidTransaction::InvalidChainId))
+        ));
+
+        let tx = TxEip1559 {
+            chain_id: 1,
+            gas_limit: 1_000_000,
+            max_fee_per_gas: 1,
+            max_priority_fee_per_gas: 2,
+            to: TxKind::Call(CALLEE),
+            ..Default::default()
+        };
+        let tx = MegaTxEnvelope::Eip1559(Signed::new_unchecked(
+            tx,
+            Signature::test_signature(),
+            Default::default(),
+        ));
+        assert!(matches!(
+            admission_check(&tx, &config(), spec),
+            Err(AdmissionError::Invalid(InvalidTransaction::PriorityFeeGreaterThanMaxFee))
+        ));
+    }
+}
@@ -0,0 +1,338 @@
+//! Experimental optimistic parallel execution of the transactions of a block.
+//!
+//! [`execute_transactions`] executes a batch of transactions with a [`MegaBlockExecutor`] in the
+//! manner of Block-STM, producing the same receipts and state as executing them one by one with
+//! [`MegaBlockExecutor::run_transaction`] and [`MegaBlockExecutor::commit_execution_outcome`]:
+//!
+//! 1. **Speculation.** Worker threads execute every transaction against the executor's state as of
+//!    the start of the batch. Each worker owns a [`MegaEvm`] built by a caller-supplied function
+//!    over a read-only view of that state.
+//! 2. **Validation.** In block order, a speculative outcome is valid if none of the accounts and
+//!    storage slots the transaction accessed was written by a transaction committed earlier in the
+//!    batch. Valid outcomes are committed as they are.
+//! 3. **Re-execution.** Transactions with an invalid outcome, or whose speculative execution
+//!    failed, are executed again by the block executor on the current state.
+//!
+//! Every transaction credits the fee vaults, and the block beneficiary unless its reward is
+//! deferred (see [`MegaContext::defer_beneficiary_reward`]). These credits would make any two
+//! transactions conflict, so they are not treated as accesses: a worker executes each transaction
+//! a second time with rewards disabled to obtain the accounts and slots it genuinely accessed, and
+//! the credits are committed as increments on the current balances. A deferred beneficiary reward
+//! is added to the executor's ledger.
+//!
+//! # Limitations
+//!
+//! - The state has a single version: speculation always reads the state at the start of the batch,
+//!   so a chain of dependent transactions is executed sequentially once the first one conflicts.
+//! - The SALT buckets accessed by speculatively committed transactions are not recorded in
+//!   [`MegaBlockExecutor::get_accessed_bucket_ids`].
+//! - The worker EVMs must be configured with the same environment and external environments as the
+//!   block executor's EVM. This is not checked.
+//!
+//! [`recover_senders_parallel`] recovers the senders of a block's transactions on worker threads,
+//! so the ECDSA recovery is not paid one transaction at a time in the execution loop. It uses
+//! scoped `std` threads, like [`execute_transactions`], over contiguous chunks rather than rayon,
+//! so the crate takes no thread-pool dependency. Each recovery goes through
+//! [`SignerRecoverable::recover_signer`] and hence alloy's secp256k1 backend: k256 has no batch
+//! recovery API, so the amortization comes from the threads alone.
+
+use std::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    vec::Vec,
+};
+
+use alloy_consensus::{
+    crypto::RecoveryError, transaction::SignerRecoverable, Transaction, TxReceipt,
+};
+use alloy_eips::Encodable2718;
+use alloy_evm::{block::BlockExecutionError, Database, Evm as _, IntoTxEnv, RecoveredTx};
+use alloy_op_evm::block::receipt_builder::OpReceiptBuilder;
+use alloy_primitives::{map::HashSet, Address, U256};
+use op_revm::constants::{BASE_FEE_RECIPIENT, L1_FEE_RECIPIENT, OPERATOR_FEE_RECIPIENT};
+use revm::{
+    context::ContextTr,
+    database::{State, WrapDatabaseRef},
+    handler::EvmTr,
+    inspector::NoOpInspector,
+    state::EvmState,
+    Database as _, DatabaseRef, Inspector,
+};
+
+use crate::{
+    ExternalEnvTypes, MegaBlockExecutor, MegaContext, MegaEvm, MegaHardforks, MegaSpecId,
+    MegaTransaction, MegaTransactionExt, MegaTransactionOutcome, StateLocation,
+};
+
+/// Summary of a call to [`execute_transactions`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParallelExecutionStats {
+    /// Number of transactions whose speculative outcome was committed.
+    pub speculative: usize,
+    /// Number of transactions re-executed by the block executor.
+    pub re_executed: usize,
+}
+
+/// Executes `transactions` in order with `executor`, speculatively executing them in parallel on
+/// `num_workers` threads first. See the [module documentation](self) for how it works.
+///
+/// `make_evm` builds the EVM of a worker over a read-only view of the executor's state. The
+/// resulting receipts and state are the same as executing the transactions one by one with
+/// [`MegaBlockExecutor::run_transaction`] and [`MegaBlockExecutor::commit_execution_outcome`].
+/// Like a caller propagating their errors, this stops at the first transaction that fails; the
+/// transactions before it stay committed.
+pub fn execute_transactions<'db, DB, C, R, INSP, ExtEnvs, WorkerExtEnvs, Tx, F>(
+    executor: &mut MegaBlockExecutor<C, MegaEvm<&'db mut State<DB>, INSP, ExtEnvs>, R>,
+    transactions: &[Tx],
+    num_workers: NonZeroUsize,
+    make_evm: F,
+) -> Result<ParallelExecutionStats, BlockExecutionError>
+where
+    DB: Database + DatabaseRef<Error: Send + Sync + 'static> + Sync + 'db,
+    C: MegaHardforks,
+    ExtEnvs: ExternalEnvTypes,
+    INSP: Inspector<MegaContext<&'db mut State<DB>, ExtEnvs>>,
+    R: OpReceiptBuilder<
+        Transaction: Transaction + Encodable2718 + MegaTransactionExt,
+        Receipt: TxReceipt,
+    >,
+    WorkerExtEnvs: ExternalEnvTypes,
+    Tx: IntoTxEnv<MegaTransaction>
+        + RecoveredTx<R::Transaction>
+        + MegaTransactionExt
+        + Encodable2718
+        + Copy
+        + Sync,
+    F: for<'a> Fn(
+            WrapDatabaseRef<&'a State<DB>>,
+        ) -> MegaEvm<WrapDatabaseRef<&'a State<DB>>, NoOpInspector, WorkerExtEnvs>
+        + Sync,
+{
+    let system_address = executor.evm.ctx_ref().system_address();
+    let deferring = executor.evm.ctx_ref().deferred_beneficiary_reward().is_some();
+    let beneficiary = executor.evm.block().beneficiary;
+    let mut fee_recipients =
+        Vec::from([L1_FEE_RECIPIENT, BASE_FEE_RECIPIENT, OPERATOR_FEE_RECIPIENT]);
+    if !deferring && !fee_recipients.contains(&beneficiary) {
+        fee_recipients.push(beneficiary);
+    }
+
+    let state: &State<DB> = executor.evm.db();
+    let next = AtomicUsize::new(0);
+    let speculations = thread::scope(|scope| {
+        let workers: Vec<_> = (0..num_workers.get())
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut evm = make_evm(WrapDatabaseRef(state));
+                    evm.ctx().set_system_address(system_address);
+                    if deferring {
+                        evm.ctx().defer_beneficiary_reward();
+                    }
+                    let mut speculations = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(tx) = transactions.get(index) else { break };
+                        let speculation =
+                            speculate(&mut evm, (*tx).into_tx_env(), &fee_recipients, beneficiary);
+                        speculations.push((index, speculation));
+                    }
+                    speculations
+                })
+            })
+            .collect();
+
+        let mut speculations: Vec<Option<Speculation>> =
+            (0..transactions.len()).map(|_| None).collect();
+        for worker in workers {
+            for (index, speculation) in worker.join().expect("parallel execution worker panicked") {
+                speculations[index] = speculation;
+            }
+        }
+        speculations
+    });
+
+    let mut stats = ParallelExecutionStats::default();
+    let mut written = HashSet::default();
+    for (tx, speculation) in transactions.iter().zip(speculations) {
+        let pending_reward = executor
+            .evm
+            .ctx_ref()
+            .deferred_beneficiary_reward()
+            .is_some_and(|reward| !reward.is_zero());
+        match speculation.filter(|speculation| speculation.is_valid(&written, pending_reward)) {
+            Some(Speculation { outcome, fee_credits, beneficiary_reward, .. }) => {
+                record_writes(&mut written, &outcome.state);
+                written.extend(
+                    fee_credits.iter().map(|(address, _)| StateLocation::Account(*address)),
+                );
+                executor.commit_speculative_outcome(
+                    *tx,
+                    outcome,
+                    &fee_credits,
+                    beneficiary_reward,
+                )?;
+                stats.speculative += 1;
+            }
+            None => {
+                let outcome = executor.run_transaction(*tx)?;
+                record_writes(&mut written, &outcome.inner.state);
+                // The executor credits the pending reward before re-executing a transaction that
+                // accessed the beneficiary.
+                if pending_reward &&
+                    executor
+                        .evm
+                        .ctx_ref()
+                        .volatile_data_tracker
+                        .borrow()
+                        .has_accessed_beneficiary_balance()
+                {
+                    written.insert(StateLocation::Account(beneficiary));
+                }
+                executor.commit_execution_outcome(outcome)?;
+                stats.re_executed += 1;
+            }
+        }
+    }
+    Ok(stats)
+}
+
+/// The minimum number of transactions a worker of [`recover_senders_parallel`] is given, below
+/// which spawning it costs more than the recoveries it takes over.
+const MIN_RECOVERIES_PER_WORKER: usize = 8;
+
+/// The error of [`recover_senders_parallel`].
+#[derive(Debug, thiserror::Error)]
+#[error("failed to recover the sender of transaction {index}: {source}")]
+pub struct SenderRecoveryError {
+    /// The index of the first transaction whose sender could not be recovered.
+    pub index: usize,
+    /// The recovery error.
+    #[source]
+    pub source: RecoveryError,
+}
+
+/// Recovers the senders of `transactions`, in order, splitting them across as many threads as
+/// the machine provides.
+///
+/// The result is the same as recovering each sender with
+/// [`SignerRecoverable::recover_signer`], deposits included, whose sender is not signed. Fails
+/// with the first transaction, in order, whose signature is invalid.
+pub fn recover_senders_parallel<T: SignerRecoverable + Sync>(
+    transactions: &[T],
+) -> Result<Vec<Address>, SenderRecoveryError> {
+    let available = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let num_workers = available.min(transactions.len() / MIN_RECOVERIES_PER_WORKER).max(1);
+    let recover = |offset: usize, chunk: &[T]| {
+        chunk
+            .iter()
+            .enumerate()
+            .map(|(index, tx)| {
+                tx.recover_signer()
+                    .map_err(|source| SenderRecoveryError { index: offset + index, source })
+            })
+            .collect::<Result<Vec<_>, _>>()
+    };
+    if num_workers == 1 {
+        return recover(0, transactions);
+    }
+
+    let chunk_size = transactions.len().div_ceil(num_workers);
+    thread::scope(|scope| {
+        let workers: Vec<_> = transactions
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(chunk_index, chunk)| {
+                scope.spawn(move || recover(chunk_index * chunk_size, chunk))
+            })
+            .collect();
+        let mut senders = Vec::with_capacity(transactions.len());
+        for worker in workers {
+            senders.extend(worker.join().expect("sender recovery worker panicked")?);
+        }
+        Ok(senders)
+    })
+}
+
+/// Outcome of executing a transaction against the state at the start of the batch.
+#[derive(Debug)]
+struct Speculation {
+    /// The outcome, without the fee recipients credited by the transaction.
+    outcome: MegaTransactionOutcome,
+    /// The fee recipients credited by the transaction, with their credits.
+    fee_credits: Vec<(Address, U256)>,
+    /// The deferred beneficiary reward.
+    beneficiary_reward: U256,
+    /// The accounts and slots the outcome depends on.
+    reads: Vec<StateLocation>,
+    /// Whether the transaction accessed the beneficiary.
+    accesses_beneficiary: bool,
+    /// Whether the deferred reward was accounted as materializing the beneficiary, which the
+    /// executor only does when no reward is pending.
+    materializes_beneficiary: bool,
+}
+
+impl Speculation {
+    /// Returns whether the outcome is the one the transaction produces after the writes in
+    /// `written` and with a pending deferred beneficiary reward if `pending_reward` is set.
+    fn is_valid(&self, written: &HashSet<StateLocation>, pending_reward: bool) -> bool {
+        !self.reads.iter().any(|location| written.contains(location)) &&
+            !(pending_reward && (self.accesses_beneficiary || self.materializes_beneficiary))
+    }
+}
+
+/// Executes `tx` against the state at the start of the batch. Returns `None` if the execution
+/// failed.
+fn speculate<DB: Database, ExtEnvs: ExternalEnvTypes>(
+    evm: &mut MegaEvm<DB, NoOpInspector, ExtEnvs>,
+    tx: MegaTransaction,
+    fee_recipients: &[Address],
+    beneficiary: Address,
+) -> Option<Speculation> {
+    // Without rewards, the state holds exactly the accounts and slots the transaction accessed.
+    evm.ctx().disable_beneficiary = true;
+    let accessed = evm.execute_transaction(tx.clone());
+    evm.ctx().disable_beneficiary = false;
+    let accessed = accessed.ok()?;
+
+    let mut outcome = evm.execute_transaction(tx).ok()?;
+    let beneficiary_reward = evm.ctx().take_deferred_beneficiary_reward();
+
+    let rex6 = evm.ctx().mega_spec().is_enabled(MegaSpecId::REX6);
+    let mut reads: Vec<StateLocation> = StateLocation::accessed(&accessed.state).collect();
+    let mut fee_credits = Vec::new();
+    for &address in fee_recipients {
+        if accessed.state.contains_key(&address) {
+            continue;
+        }
+        let Some(account) = outcome.state.remove(&address) else { continue };
+        let before = evm.ctx().db_mut().basic(address).ok()?.unwrap_or_default();
+        fee_credits.push((address, account.info.balance.saturating_sub(before.balance)));
+        // From REX6, a credit materializing the recipient is accounted as state growth.
+        if rex6 && before.is_empty() {
+            reads.push(StateLocation::Account(address));
+        }
+    }
+
+    // From REX6, a deferred reward is accounted depending on whether the beneficiary is empty.
+    let mut materializes_beneficiary = false;
+    if rex6 && !beneficiary_reward.is_zero() {
+        reads.push(StateLocation::Account(beneficiary));
+        materializes_beneficiary =
+            evm.ctx().db_mut().basic(beneficiary).ok()?.unwrap_or_default().is_empty();
+    }
+
+    Some(Speculation {
+        outcome,
+        fee_credits,
+        beneficiary_reward,
+        reads,
+        accesses_beneficiary: accessed.state.contains_key(&beneficiary),
+        materializes_beneficiary,
+    })
+}
+
+/// Adds the accounts and slots written in `state` to `written`.
+fn record_writes(written: &mut HashSet<StateLocation>, state: &EvmState) {
+    written.extend(StateLocation::written(state));
+}
@@ -1093,7 +1093,8 @@ fn sandbox_intrinsic_overflow_error(
 /// Derives the sandbox's TX runtime limits from the parent's remaining budgets.
 ///
 /// Tightens the four resource dimensions (compute gas, data size, KV updates, state growth)
-/// to the parent's frame-local remaining capacity. The base is the parent's active
+/// to the parent's frame-local remaining capacity, and the frame limit to the frames the parent
+/// transaction has left. The base is the parent's active
 /// `EvmTxRuntimeLimits` (not spec defaults), so any custom detention caps
 /// (`block_env_access_compute_gas_limit`, `oracle_access_compute_gas_limit`) are
 /// preserved.
@@ -1115,6 +1116,7 @@ fn sandbox_runtime_limits<DB: AlloyDatabase, ExtEnvs: ExternalEnvTypes>(
         .with_tx_data_size_limit(parent_limit.current_call_remaining_data_size())
         .with_tx_kv_updates_limit(parent_limit.current_call_remaining_kv_updates())
         .with_tx_state_growth_limit(parent_limit.current_call_remaining_state_growth())
+        .with_tx_frame_limit(limits.tx_frame_limit.saturating_sub(parent_limit.frames_created()))
 }
 
 /// Reads an account nonce from the journal cache first, then falls back to the backing database.
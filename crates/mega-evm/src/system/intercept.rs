@@ -17,9 +17,9 @@ use revm::{
 
 use crate::{
     sandbox::execute_keyless_deploy_call, ExternalEnvTypes, IKeylessDeploy, IMegaAccessControl,
-    IMegaLimitControl, IOracle, MegaContext, MegaSpecId, OracleEnv, ACCESS_CONTROL_ADDRESS,
-    DISABLED_BY_PARENT_REVERT_DATA, KEYLESS_DEPLOY_ADDRESS, LIMIT_CONTROL_ADDRESS,
-    ORACLE_CONTRACT_ADDRESS,
+    IMegaLimitBudget, IMegaLimitControl, IOracle, MegaContext, MegaSpecId, OracleEnv,
+    ACCESS_CONTROL_ADDRESS, DISABLED_BY_PARENT_REVERT_DATA, KEYLESS_DEPLOY_ADDRESS,
+    LIMIT_CONTROL_ADDRESS, ORACLE_CONTRACT_ADDRESS,
 };
 
 /// The result of a system contract call interception attempt.
@@ -457,12 +457,17 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> SystemContractInterceptor<DB, ExtE
 ///
 /// Handles:
 /// - `remainingComputeGas()`: returns remaining compute gas of the current call.
+/// - `remainingBudgets()` (Rex6+): returns the remaining budget of every resource limit of the
+///   current call, see [`IMegaLimitBudget`].
 #[derive(Debug)]
 pub struct LimitControlInterceptor;
 
 impl LimitControlInterceptor {
     /// The minimum spec required for this interceptor to be active.
     pub const ACTIVATION_SPEC: MegaSpecId = MegaSpecId::REX4;
+
+    /// The minimum spec required for `remainingBudgets()` to be intercepted.
+    pub const BUDGETS_ACTIVATION_SPEC: MegaSpecId = MegaSpecId::REX6;
 }
 
 impl LimitControlInterceptor {
@@ -484,6 +489,31 @@ impl LimitControlInterceptor {
             call_inputs.return_memory_offset.clone(),
         )))
     }
+
+    fn handle_remaining_budgets<DB: Database, ExtEnvs: ExternalEnvTypes>(
+        ctx: &MegaContext<DB, ExtEnvs>,
+        call_inputs: &CallInputs,
+    ) -> InterceptResult {
+        if let Some(result) = reject_non_zero_transfer(call_inputs) {
+            return Some(result);
+        }
+        let limit = ctx.additional_limit.borrow();
+        let budgets = IMegaLimitBudget::remainingBudgetsReturn {
+            computeGas: limit.current_call_remaining_compute_gas(),
+            dataSize: limit.current_call_remaining_data_size(),
+            kvUpdates: limit.current_call_remaining_kv_updates(),
+            stateGrowth: limit.current_call_remaining_state_growth(),
+        };
+        let output = IMegaLimitBudget::remainingBudgetsCall::abi_encode_returns(&budgets);
+        Some(FrameResult::Call(CallOutcome::new(
+            InterpreterResult::new(
+                InstructionResult::Return,
+                Bytes::from(output),
+                Gas::new(call_inputs.gas_limit),
+            ),
+            call_inputs.return_memory_offset.clone(),
+        )))
+    }
 }
 
 impl<DB: Database, ExtEnvs: ExternalEnvTypes> SystemContractInterceptor<DB, ExtEnvs>
@@ -502,6 +532,11 @@ impl<DB: Database, ExtEnvs: ExternalEnvTypes> SystemContractInterceptor<DB, ExtE
         if selector == IMegaLimitControl::remainingComputeGasCall::SELECTOR {
             return Self::handle_remaining_compute_gas(ctx, call_inputs);
         }
+        if selector == IMegaLimitBudget::remainingBudgetsCall::SELECTOR &&
+            ctx.spec.is_enabled(Self::BUDGETS_ACTIVATION_SPEC)
+        {
+            return Self::handle_remaining_budgets(ctx, call_inputs);
+        }
         // Unknown selector — not intercepted.
         None
     }
@@ -1,7 +1,8 @@
 //! The `MegaLimitControl` system contract for the `MegaETH` EVM.
 //!
-//! This contract currently provides a read-only query to return remaining compute gas
-//! of the current call.
+//! This contract currently provides read-only queries to return remaining compute gas
+//! of the current call and, from Rex6, the remaining budget of every resource limit
+//! ([`IMegaLimitBudget`]).
 //! The runtime result is produced by EVM interception, not by executing on-chain bytecode.
 
 use alloy_evm::Database;
@@ -19,7 +20,7 @@ pub use mega_system_contracts::limit_control::V1_0_0_CODE as LIMIT_CONTROL_CODE;
 /// The code hash of the `MegaLimitControl` contract (version 1.0.0).
 pub use mega_system_contracts::limit_control::V1_0_0_CODE_HASH as LIMIT_CONTROL_CODE_HASH;
 
-pub use mega_system_contracts::limit_control::IMegaLimitControl;
+pub use mega_system_contracts::limit_control::{IMegaLimitBudget, IMegaLimitControl};
 
 /// Ensures the `MegaLimitControl` contract is deployed in the designated address and returns
 /// the state changes.
@@ -2,10 +2,23 @@
 //! `../../../system-contracts/contracts/Oracle.sol`.
 
 use alloy_evm::Database;
-use alloy_primitives::{address, b256, bytes, Address, Bytes, B256};
-use revm::{database::State, state::EvmState};
-
-use crate::{MegaHardforks, SystemContractSpec};
+use alloy_primitives::{address, b256, bytes, Address, Bytes, B256, U256};
+use alloy_sol_types::SolCall;
+use revm::{
+    context::{ContextTr, JournalTr},
+    database::State,
+    handler::FrameResult,
+    interpreter::{
+        CallInputs, CallOutcome, CallScheme, Gas, Host, InstructionResult, InterpreterResult,
+    },
+    primitives::CALL_STACK_LIMIT,
+    state::EvmState,
+};
+
+use crate::{
+    inspect_account_code_hash, ExternalEnvTypes, MegaContext, MegaHardforks, MegaSpecId,
+    SystemContractSpec,
+};
 
 /// The address of the oracle system contract.
 pub const ORACLE_CONTRACT_ADDRESS: Address = address!("0x6342000000000000000000000000000000000001");
@@ -125,13 +138,152 @@ pub(crate) fn high_precision_timestamp_oracle_spec(
     })
 }
 
+/// Gas spent by the oracle bytecode to serve a `getSlot(uint256)` call, keyed by the code hash of
+/// the deployed oracle version: `(code hash, gas up to and including the SLOAD, total gas)`. Both
+/// include the cold `SLOAD` of the requested slot.
+///
+/// Every oracle version needs a row, measured by running its bytecode; the unit tests below check
+/// both.
+const ORACLE_GET_SLOT_GAS: [(B256, u64, u64); 3] = [
+    (ORACLE_CONTRACT_CODE_HASH, 2298, 2367),
+    (ORACLE_CONTRACT_CODE_HASH_REX2, 2343, 2412),
+    (ORACLE_CONTRACT_CODE_HASH_REX5, 2321, 2390),
+];
+
+/// Memory (in bytes) expanded by the oracle bytecode while serving a `getSlot(uint256)` call.
+const ORACLE_GET_SLOT_MEMORY_SIZE: u64 = 160;
+
+/// Serves a `getSlot(uint256)` call of the oracle contract without running its bytecode, if the
+/// oracle fast path is enabled (see [`MegaContext::with_oracle_fast_path`]).
+///
+/// Called from `frame_init` after the callee frame has been pushed on the limit trackers. The
+/// slot is read through [`Host::sload`], so oracle access marking, `accessed_oracle_slots`, and
+/// the [`OracleEnv`](crate::OracleEnv) lookup behave as for the `SLOAD` of the bytecode, and the
+/// call spends the gas the bytecode of the deployed oracle version would.
+///
+/// Returns `Ok(None)` whenever the outcome of the bytecode cannot be reproduced exactly (unknown
+/// oracle code, a gas or limit shortfall that would halt the bytecode, disabled volatile data
+/// access, ...); the caller then runs the bytecode as usual.
+pub(crate) fn oracle_get_slot_fast_path<DB: Database, ExtEnvs: ExternalEnvTypes>(
+    ctx: &mut MegaContext<DB, ExtEnvs>,
+    call_inputs: &CallInputs,
+    depth: usize,
+) -> Result<Option<FrameResult>, DB::Error> {
+    if !ctx.oracle_fast_path ||
+        !ctx.spec.is_enabled(MegaSpecId::REX3) ||
+        call_inputs.target_address != ORACLE_CONTRACT_ADDRESS ||
+        call_inputs.bytecode_address != ORACLE_CONTRACT_ADDRESS ||
+        !matches!(call_inputs.scheme, CallScheme::Call | CallScheme::StaticCall) ||
+        call_inputs.transfer_value().is_some_and(|value| !value.is_zero()) ||
+        depth > CALL_STACK_LIMIT as usize
+    {
+        return Ok(None);
+    }
+
+    let input = call_inputs.input.bytes(ctx);
+    if input.len() != 4 + 32 {
+        return Ok(None);
+    }
+    let Ok(call) = IOracle::getSlotCall::abi_decode(&input) else {
+        return Ok(None);
+    };
+
+    let code_hash = inspect_account_code_hash(ctx.journal_mut(), ORACLE_CONTRACT_ADDRESS)?;
+    let Some(&(_, sload_gas, gas_cost)) =
+        ORACLE_GET_SLOT_GAS.iter().find(|(hash, _, _)| *hash == code_hash)
+    else {
+        return Ok(None);
+    };
+    if call_inputs.gas_limit < gas_cost {
+        return Ok(None);
+    }
+
+    // The bytecode marks the oracle access at its `SLOAD`, lowering the compute gas limit to
+    // the tracker's limit (relative to the usage at that point from Rex4, absolute before). Only
+    // serve the call if the rest of the bytecode run would fit under that limit.
+    {
+        let additional_limit = ctx.additional_limit.borrow();
+        if additional_limit.limits.interpreter_memory_limit < ORACLE_GET_SLOT_MEMORY_SIZE ||
+            additional_limit.current_call_remaining_compute_gas() < gas_cost
+        {
+            return Ok(None);
+        }
+        let tracker = ctx.volatile_data_tracker.borrow();
+        if tracker.volatile_access_disabled(depth + 1) {
+            return Ok(None);
+        }
+        let existing_limit = tracker.get_compute_gas_limit();
+        let detained_limit = if ctx.caller() != ctx.system_address {
+            let oracle_limit = tracker.oracle_access_limit();
+            Some(existing_limit.map_or(oracle_limit, |limit| limit.min(oracle_limit)))
+        } else {
+            existing_limit
+        };
+        if let Some(limit) = detained_limit {
+            let needed = if ctx.spec.is_enabled(MegaSpecId::REX4) {
+                gas_cost - sload_gas
+            } else {
+                additional_limit.get_usage().compute_gas.saturating_add(gas_cost)
+            };
+            if needed > limit {
+                return Ok(None);
+            }
+        }
+    }
+
+    // Touch the oracle account like the value transfer of a regular CALL frame does. STATICCALL
+    // cannot modify state, so the account is left untouched.
+    if call_inputs.scheme == CallScheme::Call &&
+        ctx.journal_mut()
+            .transfer(call_inputs.caller, ORACLE_CONTRACT_ADDRESS, U256::ZERO)?
+            .is_some()
+    {
+        return Ok(None);
+    }
+
+    let mut gas = Gas::new(call_inputs.gas_limit);
+    let _ = gas.record_cost(gas_cost);
+    let Some(value) = ctx.sload(ORACLE_CONTRACT_ADDRESS, call.slot) else {
+        // The database error is stored in the context and surfaces at the end of the transaction.
+        return Ok(Some(FrameResult::Call(CallOutcome::new(
+            InterpreterResult::new(InstructionResult::FatalExternalError, Bytes::new(), gas),
+            call_inputs.return_memory_offset.clone(),
+        ))));
+    };
+
+    // Record the compute gas around the detention point exactly as the bytecode would, since
+    // from Rex4 the lowered limit is relative to the usage at the `SLOAD`.
+    let compute_gas_limit = ctx.volatile_data_tracker.borrow().get_compute_gas_limit();
+    let mut additional_limit = ctx.additional_limit.borrow_mut();
+    additional_limit.record_compute_gas(sload_gas);
+    if let Some(limit) = compute_gas_limit {
+        additional_limit.set_compute_gas_limit(limit);
+    }
+    additional_limit.record_compute_gas(gas_cost - sload_gas);
+
+    Ok(Some(FrameResult::Call(CallOutcome::new(
+        InterpreterResult::new(
+            InstructionResult::Return,
+            IOracle::getSlotCall::abi_encode_returns(&B256::from(value.data)).into(),
+            gas,
+        ),
+        call_inputs.return_memory_offset.clone(),
+    ))))
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{MegaHardfork, MegaHardforkConfig};
+    use crate::{
+        test_utils::{context_without_operator_fee, GasInspector, MemoryDatabase},
+        MegaEvm, MegaHardfork, MegaHardforkConfig, MegaTransaction,
+    };
 
     use super::*;
-    use alloy_primitives::keccak256;
+    use alloy_hardforks::ForkCondition;
+    use alloy_primitives::{keccak256, TxKind};
     use revm::{
+        bytecode::opcode::SLOAD,
+        context::TxEnv,
         database::InMemoryDB,
         state::{AccountInfo, Bytecode},
     };
@@ -603,4 +755,80 @@ mod tests {
             "existing account must not be marked as created on code update"
         );
     }
+
+    /// Every oracle version a hardfork deploys, and the latest artifact, has a row in
+    /// `ORACLE_GET_SLOT_GAS`.
+    #[test]
+    fn test_oracle_get_slot_gas_covers_every_oracle_version() {
+        let deployed = [
+            MegaHardforkConfig::default().with(MegaHardfork::MiniRex, ForkCondition::Timestamp(0)),
+            MegaHardforkConfig::default().with_all_activated().without(MegaHardfork::Rex5),
+            MegaHardforkConfig::default().with_all_activated(),
+        ]
+        .map(|hardforks| oracle_spec(&hardforks, 0).expect("the oracle is deployed").code_hash);
+        for code_hash in
+            deployed.into_iter().chain([mega_system_contracts::oracle::LATEST_CODE_HASH])
+        {
+            assert!(
+                ORACLE_GET_SLOT_GAS.iter().any(|(hash, _, _)| *hash == code_hash),
+                "no getSlot gas for oracle code hash {code_hash}"
+            );
+        }
+    }
+
+    /// Runs `getSlot` through the oracle bytecode `code` under `spec` and returns the gas the
+    /// oracle frame spends up to and including the `SLOAD`, and in total.
+    fn measure_get_slot_gas(spec: MegaSpecId, code: Bytes) -> (u64, u64) {
+        let caller = address!("0000000000000000000000000000000000600000");
+        let mut db = MemoryDatabase::default()
+            .account_balance(caller, U256::from(10_000_000))
+            .account_code(ORACLE_CONTRACT_ADDRESS, code);
+        let mut inspector = GasInspector::new();
+        let mut evm = MegaEvm::new(context_without_operator_fee(&mut db, spec))
+            .with_inspector(&mut inspector);
+        let tx = TxEnv {
+            caller,
+            kind: TxKind::Call(ORACLE_CONTRACT_ADDRESS),
+            data: IOracle::getSlotCall { slot: U256::from(7) }.abi_encode().into(),
+            gas_limit: 1_000_000,
+            ..Default::default()
+        };
+        let mut tx = MegaTransaction::new(tx);
+        tx.enveloped_tx = Some(Bytes::new());
+        let outcome = evm.execute_transaction(tx).expect("transact ok");
+        assert!(outcome.result.is_success(), "{spec:?}: {:?}", outcome.result);
+        drop(evm);
+
+        let records = inspector.records();
+        let frame_gas = records.first().expect("the oracle bytecode ran").gas_before;
+        let sload =
+            records.iter().find(|r| r.opcode.get() == SLOAD).expect("getSlot loads the slot");
+        let last = records.last().expect("the oracle bytecode ran");
+        (frame_gas - sload.gas_after, frame_gas - last.gas_after)
+    }
+
+    /// Every `ORACLE_GET_SLOT_GAS` row matches the gas its oracle bytecode spends on each spec
+    /// the fast path serves.
+    #[test]
+    fn test_oracle_get_slot_gas_matches_bytecode() {
+        let codes = [
+            ORACLE_CONTRACT_CODE,
+            ORACLE_CONTRACT_CODE_REX2,
+            ORACLE_CONTRACT_CODE_REX5,
+            mega_system_contracts::oracle::LATEST_CODE,
+        ];
+        for (code_hash, sload_gas, gas_cost) in ORACLE_GET_SLOT_GAS {
+            let code = codes
+                .iter()
+                .find(|code| keccak256(code) == code_hash)
+                .unwrap_or_else(|| panic!("no oracle code for code hash {code_hash}"));
+            for spec in [MegaSpecId::REX3, MegaSpecId::REX4, MegaSpecId::REX5, MegaSpecId::REX6] {
+                assert_eq!(
+                    measure_get_slot_gas(spec, code.clone()),
+                    (sload_gas, gas_cost),
+                    "getSlot gas of oracle {code_hash} under {spec:?}"
+                );
+            }
+        }
+    }
 }
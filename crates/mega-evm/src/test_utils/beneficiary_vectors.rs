@@ -0,0 +1,255 @@
+//! Conformance vectors for beneficiary access: `BALANCE` and the `EXTCODE*` opcodes applied to
+//! the block beneficiary at various call depths, under every `MegaETH` spec.
+//!
+//! Reading the beneficiary detains the compute gas of the transaction (see
+//! [`BLOCK_ENV_ACCESS_COMPUTE_GAS`](crate::constants::mini_rex::BLOCK_ENV_ACCESS_COMPUTE_GAS)).
+//! Each vector holds everything another client implementation needs to replay it — the state, the
+//! transaction, the beneficiary and the limits — and the outcome this implementation produces.
+//! The vectors are exported as JSON to [`BENEFICIARY_VECTORS_FILE`] with the
+//! `export-beneficiary-vectors` binary (`cargo run -p mega-fixtures --bin
+//! export-beneficiary-vectors`) and checked by the `golden` test target.
+
+use std::{collections::BTreeMap, fs, io, path::Path, vec::Vec};
+
+use alloy_primitives::{address, Address, Bytes, U256};
+use revm::{
+    bytecode::opcode::{OpCode, BALANCE, EXTCODECOPY, EXTCODEHASH, EXTCODESIZE, POP, PUSH0},
+    context::BlockEnv,
+    handler::EvmTr,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::mini_rex::BLOCK_ENV_ACCESS_COMPUTE_GAS,
+    test_utils::{
+        context_without_operator_fee, AllocAccount, BytecodeBuilder, MegaTxBuilder, MemoryDatabase,
+    },
+    EvmTxRuntimeLimits, MegaEvm, MegaSpecId, VolatileDataAccess,
+};
+
+/// The file holding the exported vectors.
+pub const BENEFICIARY_VECTORS_FILE: &str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/vectors/beneficiary_access.json");
+
+/// The specs every vector is generated under.
+pub const BENEFICIARY_VECTOR_SPECS: [MegaSpecId; 8] = [
+    MegaSpecId::MINI_REX,
+    MegaSpecId::REX,
+    MegaSpecId::REX1,
+    MegaSpecId::REX2,
+    MegaSpecId::REX3,
+    MegaSpecId::REX4,
+    MegaSpecId::REX5,
+    MegaSpecId::REX6,
+];
+
+/// The opcodes reading the account they are applied to.
+pub const BENEFICIARY_ACCESS_OPCODES: [u8; 4] = [BALANCE, EXTCODESIZE, EXTCODEHASH, EXTCODECOPY];
+
+/// The call depths the opcodes run at, 0 being the callee of the transaction.
+pub const BENEFICIARY_VECTOR_DEPTHS: [usize; 3] = [0, 1, 2];
+
+const CALLER: Address = address!("0000000000000000000000000000000000b00000");
+const BENEFICIARY: Address = address!("0000000000000000000000000000000000b0beef");
+/// An account that is not the beneficiary, read by the control vectors.
+const BYSTANDER: Address = address!("0000000000000000000000000000000000b0cafe");
+const TX_GAS_LIMIT: u64 = 10_000_000;
+const TX_COMPUTE_GAS_LIMIT: u64 = 200_000_000;
+
+/// A beneficiary access conformance vector.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BeneficiaryAccessVector {
+    /// The name of the vector, unique in the set.
+    pub name: String,
+    /// The spec the transaction runs under.
+    pub spec: MegaSpecId,
+    /// The mnemonic of the opcode under test.
+    pub opcode: String,
+    /// The call depth the opcode runs at, 0 being the callee of the transaction.
+    pub depth: usize,
+    /// The account the opcode is applied to.
+    pub target: Address,
+    /// The beneficiary of the block.
+    pub beneficiary: Address,
+    /// The compute gas limit of the transaction.
+    pub tx_compute_gas_limit: u64,
+    /// The compute gas allowed once the beneficiary is read: the whole transaction's before
+    /// `REX4`, the rest of the transaction's from `REX4`.
+    pub block_env_access_compute_gas_limit: u64,
+    /// The state the transaction runs on.
+    pub pre: BTreeMap<Address, AllocAccount>,
+    /// The sender of the transaction.
+    pub caller: Address,
+    /// The callee of the transaction.
+    pub to: Address,
+    /// The gas limit of the transaction.
+    pub gas_limit: u64,
+    /// The outcome of the transaction.
+    pub expected: BeneficiaryAccessOutcome,
+}
+
+/// The outcome of a [`BeneficiaryAccessVector`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BeneficiaryAccessOutcome {
+    /// Whether the transaction succeeded.
+    pub success: bool,
+    /// The gas used.
+    pub gas_used: u64,
+    /// The compute gas used.
+    pub compute_gas_used: u64,
+    /// Whether the beneficiary access was recorded as volatile data access.
+    pub beneficiary_accessed: bool,
+    /// The compute gas limit of the transaction after detention, equal to
+    /// `tx_compute_gas_limit` if nothing was detained.
+    pub detained_compute_gas_limit: u64,
+}
+
+/// Returns the address of the contract at `depth` of the call chain.
+fn chain_contract(depth: usize) -> Address {
+    Address::left_padding_from(&[0xb0, 0x01, depth as u8])
+}
+
+/// Generates the vector set: every opcode at every depth under every spec, applied to the
+/// beneficiary and, as a control, to another account.
+pub fn beneficiary_access_vectors() -> Vec<BeneficiaryAccessVector> {
+    let account_code = Bytes::from_static(&[PUSH0, POP, 0x00]);
+    let mut vectors = Vec::new();
+    for spec in BENEFICIARY_VECTOR_SPECS {
+        for opcode in BENEFICIARY_ACCESS_OPCODES {
+            let mnemonic = OpCode::new(opcode).expect("a valid opcode").as_str();
+            for depth in BENEFICIARY_VECTOR_DEPTHS {
+                for (target, kind) in [(BENEFICIARY, "beneficiary"), (BYSTANDER, "bystander")] {
+                    let reader = match opcode {
+                        EXTCODECOPY => BytecodeBuilder::default()
+                            .push_number(32_u8)
+                            .append_many([PUSH0, PUSH0])
+                            .push_address(target),
+                        _ => BytecodeBuilder::default().push_address(target),
+                    };
+                    let mut db = MemoryDatabase::default()
+                        .account_code(chain_contract(depth), reader.append(opcode).stop().build())
+                        .account_code(BENEFICIARY, account_code.clone())
+                        .account_balance(BENEFICIARY, U256::from(1_000_000))
+                        .account_code(BYSTANDER, account_code.clone())
+                        .account_balance(BYSTANDER, U256::from(1_000_000));
+                    for caller_depth in 0..depth {
+                        let code = BytecodeBuilder::default()
+                            .call_contract(chain_contract(caller_depth + 1), [])
+                            .append(POP)
+                            .stop()
+                            .build();
+                        db = db.account_code(chain_contract(caller_depth), code);
+                    }
+
+                    let mut vector = BeneficiaryAccessVector {
+                        name: format!("{mnemonic}_{kind}_depth{depth}_{spec}"),
+                        spec,
+                        opcode: mnemonic.to_string(),
+                        depth,
+                        target,
+                        beneficiary: BENEFICIARY,
+                        tx_compute_gas_limit: TX_COMPUTE_GAS_LIMIT,
+                        block_env_access_compute_gas_limit: BLOCK_ENV_ACCESS_COMPUTE_GAS,
+                        pre: db.to_alloc(),
+                        caller: CALLER,
+                        to: chain_contract(0),
+                        gas_limit: TX_GAS_LIMIT,
+                        expected: BeneficiaryAccessOutcome::default(),
+                    };
+                    vector.expected = run_beneficiary_access_vector(&vector);
+                    vectors.push(vector);
+                }
+            }
+        }
+    }
+    vectors
+}
+
+/// Executes the transaction of `vector` and returns its outcome.
+pub fn run_beneficiary_access_vector(vector: &BeneficiaryAccessVector) -> BeneficiaryAccessOutcome {
+    let mut db = MemoryDatabase::from_alloc(vector.pre.clone());
+    let context = context_without_operator_fee(&mut db, vector.spec)
+        .with_block(BlockEnv { beneficiary: vector.beneficiary, ..Default::default() })
+        .with_tx_runtime_limits(
+            EvmTxRuntimeLimits::no_limits()
+                .with_tx_compute_gas_limit(vector.tx_compute_gas_limit)
+                .with_block_env_access_compute_gas_limit(vector.block_env_access_compute_gas_limit),
+        );
+    let tx = MegaTxBuilder::new()
+        .caller(vector.caller)
+        .call(vector.to)
+        .gas_limit(vector.gas_limit)
+        .build();
+    let mut evm = MegaEvm::new(context);
+    let outcome =
+        evm.execute_transaction(tx).expect("beneficiary access vectors are valid transactions");
+    let detained_compute_gas_limit =
+        evm.ctx_ref().additional_limit.borrow().detained_compute_gas_limit();
+    BeneficiaryAccessOutcome {
+        success: outcome.result.is_success(),
+        gas_used: outcome.result.gas_used(),
+        compute_gas_used: outcome.compute_gas_used,
+        beneficiary_accessed: outcome
+            .volatile_data_accessed
+            .contains(VolatileDataAccess::BENEFICIARY_BALANCE),
+        detained_compute_gas_limit,
+    }
+}
+
+/// Replays `vector` and checks the outcome against the expected one and the documented
+/// detention: reading the beneficiary caps the compute gas at
+/// `block_env_access_compute_gas_limit` before `REX4`, and at the compute gas used so far plus
+/// `block_env_access_compute_gas_limit` from `REX4`; reading another account detains nothing.
+pub fn check_beneficiary_access_vector(vector: &BeneficiaryAccessVector) -> Result<(), String> {
+    let actual = run_beneficiary_access_vector(vector);
+    if actual != vector.expected {
+        return Err(format!("{}: expected {:?}, got {actual:?}", vector.name, vector.expected));
+    }
+    if !actual.success {
+        return Err(format!("{}: the transaction failed", vector.name));
+    }
+
+    let cap = vector.block_env_access_compute_gas_limit;
+    let detained = actual.detained_compute_gas_limit;
+    let documented = if vector.target != vector.beneficiary {
+        detained == vector.tx_compute_gas_limit
+    } else if !actual.beneficiary_accessed {
+        false
+    } else if vector.spec.is_enabled(MegaSpecId::REX4) {
+        (cap..=cap.saturating_add(actual.compute_gas_used)).contains(&detained)
+    } else {
+        detained == cap.min(vector.tx_compute_gas_limit)
+    };
+    if !documented {
+        return Err(format!(
+            "{}: {} on {} detained the compute gas limit to {detained} under {}",
+            vector.name, vector.opcode, vector.target, vector.spec
+        ));
+    }
+    Ok(())
+}
+
+/// Renders `vectors` as a pretty-printed JSON array.
+pub fn beneficiary_access_vectors_json(vectors: &[BeneficiaryAccessVector]) -> String {
+    serde_json::to_string_pretty(vectors).expect("vector serialization cannot fail")
+}
+
+/// Loads the vectors exported to `path`.
+pub fn load_beneficiary_access_vectors(
+    path: impl AsRef<Path>,
+) -> io::Result<Vec<BeneficiaryAccessVector>> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// Generates the vectors and exports them to `path`, returning the number of vectors written.
+pub fn export_beneficiary_access_vectors(path: impl AsRef<Path>) -> io::Result<usize> {
+    let path = path.as_ref();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let vectors = beneficiary_access_vectors();
+    fs::write(path, beneficiary_access_vectors_json(&vectors))?;
+    Ok(vectors.len())
+}
@@ -0,0 +1,267 @@
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::vec::Vec;
+
+use alloy_consensus::{transaction::Recovered, Header};
+use alloy_evm::{
+    block::{BlockExecutionError, BlockExecutor},
+    EvmEnv,
+};
+use alloy_op_evm::block::{receipt_builder::OpReceiptBuilder, OpAlloyReceiptBuilder};
+use alloy_primitives::{Address, Bytes, B256, U256};
+use revm::{
+    context::{BlockEnv, CfgEnv},
+    database::State,
+};
+
+use crate::{
+    test_utils::MemoryDatabase, BlockLimits, MegaBlockExecutionCtx, MegaBlockExecutorFactory,
+    MegaEvmFactory, MegaHardforkConfig, MegaHardforks, MegaTxEnvelope, TestExternalEnvs,
+};
+
+/// Receipt type produced by [`TestChain`].
+pub type TestChainReceipt = <OpAlloyReceiptBuilder as OpReceiptBuilder>::Receipt;
+
+/// A block mined by [`TestChain::mine_block`].
+#[derive(Debug, Clone)]
+pub struct MinedBlock {
+    /// The block header. Roots other than the parent hash are not computed.
+    pub header: Header,
+    /// One receipt per executed transaction, in order.
+    pub receipts: Vec<TestChainReceipt>,
+}
+
+/// A deterministic in-memory chain that executes whole blocks through
+/// [`MegaBlockExecutorFactory`].
+///
+/// Each [`mine_block`](Self::mine_block) call builds the next block on top of the current head:
+/// the spec is resolved from the configured hardforks at the new block's timestamp, pre-execution
+/// changes are applied, the transactions are executed, and the post-execution changes are
+/// committed to the chain's state. State, block hashes and the head header carry over between
+/// blocks, so tests can observe multi-block behavior such as per-block limit resets and hardfork
+/// boundaries.
+#[derive(Debug)]
+pub struct TestChain {
+    state: State<MemoryDatabase>,
+    hardforks: MegaHardforkConfig,
+    external_envs: TestExternalEnvs,
+    block_limits: BlockLimits,
+    chain_id: u64,
+    gas_limit: u64,
+    block_time: u64,
+    beneficiary: Address,
+    head: Header,
+}
+
+impl TestChain {
+    /// Creates a chain over `db` whose genesis header has number 0 and timestamp 0.
+    pub fn new(db: MemoryDatabase, hardforks: MegaHardforkConfig) -> Self {
+        Self {
+            state: State::builder().with_database(db).build(),
+            hardforks,
+            external_envs: TestExternalEnvs::new(),
+            block_limits: BlockLimits::no_limits(),
+            chain_id: 1,
+            gas_limit: 30_000_000,
+            block_time: 1,
+            beneficiary: Address::ZERO,
+            head: Header { gas_limit: 30_000_000, ..Default::default() },
+        }
+    }
+
+    /// Sets the external environments (SALT bucket capacities, oracle storage).
+    pub fn with_external_envs(mut self, external_envs: TestExternalEnvs) -> Self {
+        self.external_envs = external_envs;
+        self
+    }
+
+    /// Sets the block-level limits applied to every mined block.
+    pub fn with_block_limits(mut self, block_limits: BlockLimits) -> Self {
+        self.block_limits = block_limits;
+        self
+    }
+
+    /// Sets the chain id.
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// Sets the gas limit of mined blocks.
+    pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = gas_limit;
+        self
+    }
+
+    /// Sets the timestamp increment between consecutive blocks.
+    pub fn with_block_time(mut self, block_time: u64) -> Self {
+        self.block_time = block_time;
+        self
+    }
+
+    /// Sets the timestamp of the genesis header.
+    pub fn with_genesis_timestamp(mut self, timestamp: u64) -> Self {
+        self.head.timestamp = timestamp;
+        self
+    }
+
+    /// Sets the beneficiary of mined blocks.
+    pub fn with_beneficiary(mut self, beneficiary: Address) -> Self {
+        self.beneficiary = beneficiary;
+        self
+    }
+
+    /// Returns the header of the latest block.
+    pub fn head(&self) -> &Header {
+        &self.head
+    }
+
+    /// Returns the chain state.
+    pub fn state(&self) -> &State<MemoryDatabase> {
+        &self.state
+    }
+
+    /// Returns the chain state mutably, e.g. to seed accounts between blocks.
+    pub fn state_mut(&mut self) -> &mut State<MemoryDatabase> {
+        &mut self.state
+    }
+
+    /// Executes `txs` as the next block and advances the head to it.
+    ///
+    /// On error the head is not advanced, but state changes made by transactions executed
+    /// before the failure are kept.
+    pub fn mine_block(
+        &mut self,
+        txs: &[Recovered<MegaTxEnvelope>],
+    ) -> Result<MinedBlock, BlockExecutionError> {
+        let number = self.head.number + 1;
+        let timestamp = self.head.timestamp + self.block_time;
+        let parent_hash = self.head.hash_slow();
+        self.state.block_hashes.insert(self.head.number, parent_hash);
+
+        let mut cfg_env = CfgEnv::default();
+        cfg_env.chain_id = self.chain_id;
+        cfg_env.spec = self.hardforks.spec_id(timestamp);
+        let block_env = BlockEnv {
+            number: U256::from(number),
+            timestamp: U256::from(timestamp),
+            gas_limit: self.gas_limit,
+            beneficiary: self.beneficiary,
+            ..Default::default()
+        };
+        let block_ctx = MegaBlockExecutionCtx::new(
+            parent_hash,
+            Some(B256::ZERO),
+            Bytes::new(),
+            self.block_limits,
+        );
+
+        let factory = MegaBlockExecutorFactory::new(
+            self.hardforks.clone(),
+            MegaEvmFactory::new().with_external_env_factory(self.external_envs.clone()),
+            OpAlloyReceiptBuilder::default(),
+        );
+        let mut executor = factory.create_executor(
+            &mut self.state,
+            block_ctx,
+            EvmEnv::new(cfg_env, block_env.clone()),
+        );
+        executor.apply_pre_execution_changes()?;
+        for tx in txs {
+            executor.execute_transaction(tx)?;
+        }
+        let (_, result) = executor.finish()?;
+
+        let header = Header {
+            parent_hash,
+            number,
+            timestamp,
+            gas_limit: self.gas_limit,
+            gas_used: result.gas_used,
+            beneficiary: self.beneficiary,
+            base_fee_per_gas: Some(block_env.basefee),
+            parent_beacon_block_root: Some(B256::ZERO),
+            ..Default::default()
+        };
+        self.head = header.clone();
+        Ok(MinedBlock { header, receipts: result.receipts })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MegaHardfork, MegaSpecId};
+    use alloy_consensus::{Signed, TxLegacy, TxReceipt};
+    use alloy_hardforks::ForkCondition;
+    use alloy_primitives::{address, Signature, TxKind};
+    use revm::Database;
+
+    const CALLER: Address = address!("0000000000000000000000000000000000100000");
+    const CALLEE: Address = address!("0000000000000000000000000000000000100001");
+
+    fn transfer(nonce: u64) -> Recovered<MegaTxEnvelope> {
+        let tx = TxLegacy {
+            chain_id: Some(1),
+            nonce,
+            gas_price: 0,
+            gas_limit: 100_000,
+            to: TxKind::Call(CALLEE),
+            value: U256::from(1),
+            input: Bytes::new(),
+        };
+        let signed = Signed::new_unchecked(tx, Signature::test_signature(), Default::default());
+        Recovered::new_unchecked(MegaTxEnvelope::Legacy(signed), CALLER)
+    }
+
+    fn chain() -> TestChain {
+        let db = MemoryDatabase::default().account_balance(CALLER, U256::from(1_000_000));
+        let hardforks =
+            MegaHardforkConfig::default().with(MegaHardfork::Rex4, ForkCondition::Timestamp(0));
+        TestChain::new(db, hardforks)
+    }
+
+    #[test]
+    fn test_mine_blocks_links_headers_and_carries_state() {
+        let mut chain = chain();
+        let genesis_hash = chain.head().hash_slow();
+
+        let block1 = chain.mine_block(&[transfer(0), transfer(1)]).unwrap();
+        assert_eq!(block1.header.number, 1);
+        assert_eq!(block1.header.parent_hash, genesis_hash);
+        assert_eq!(block1.receipts.len(), 2);
+        assert!(block1.receipts.iter().all(|r| r.status()));
+        assert_eq!(block1.header.gas_used, block1.receipts[1].cumulative_gas_used());
+
+        let block2 = chain.mine_block(&[transfer(2)]).unwrap();
+        assert_eq!(block2.header.number, 2);
+        assert_eq!(block2.header.parent_hash, block1.header.hash_slow());
+        assert_eq!(block2.header.timestamp, block1.header.timestamp + 1);
+
+        let callee = chain.state_mut().basic(CALLEE).unwrap().unwrap();
+        assert_eq!(callee.balance, U256::from(3));
+        assert_eq!(chain.state_mut().block_hash(1).unwrap(), block1.header.hash_slow());
+    }
+
+    #[test]
+    fn test_mine_block_resolves_spec_per_timestamp() {
+        let db = MemoryDatabase::default();
+        let hardforks = MegaHardforkConfig::default()
+            .with(MegaHardfork::Rex3, ForkCondition::Timestamp(0))
+            .with(MegaHardfork::Rex4, ForkCondition::Timestamp(20));
+        let mut chain = TestChain::new(db, hardforks).with_block_time(10);
+        assert_eq!(chain.hardforks.spec_id(10), MegaSpecId::REX3);
+        chain.mine_block(&[]).unwrap();
+        assert_eq!(chain.hardforks.spec_id(chain.head().timestamp + 10), MegaSpecId::REX4);
+        let block = chain.mine_block(&[]).unwrap();
+        assert_eq!(block.header.timestamp, 20);
+    }
+
+    #[test]
+    fn test_failed_block_does_not_advance_head() {
+        let mut chain = chain();
+        // Nonce 5 is invalid for a fresh sender.
+        assert!(chain.mine_block(&[transfer(5)]).is_err());
+        assert_eq!(chain.head().number, 0);
+    }
+}
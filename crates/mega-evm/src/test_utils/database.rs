@@ -1,12 +1,29 @@
 use core::convert::Infallible;
+use std::{collections::BTreeMap, path::Path};
 
-use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_primitives::{Address, Bytes, B256, U256, U64};
 use delegate::delegate;
 use revm::{
     database::{AccountState, CacheDB, DBErrorMarker, EmptyDB},
     primitives::{HashMap, StorageKey, StorageValue},
     state::{Account, AccountInfo, Bytecode},
 };
+use serde::{Deserialize, Serialize};
+
+/// An account of an alloc, the pre- and post-state JSON format of `t8n` tools and state tests:
+/// a map from address to account. Missing fields default to zero or empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AllocAccount {
+    /// The balance.
+    pub balance: U256,
+    /// The code.
+    pub code: Bytes,
+    /// The nonce.
+    pub nonce: U64,
+    /// The non-zero storage slots, ordered by slot.
+    pub storage: BTreeMap<StorageKey, StorageValue>,
+}
 
 /// A memory database for testing purposes.
 #[derive(Debug, Default, Clone, derive_more::Deref, derive_more::DerefMut)]
@@ -98,6 +115,99 @@ impl MemoryDatabase {
         self.set_account_storage(address, storage_key, value);
         self
     }
+
+    /// Installs an EIP-7702 delegation designator (`0xef0100 || target`) as the code of
+    /// `authority`, as a type-4 transaction authorizing `target` would have.
+    pub fn set_account_delegation(&mut self, authority: Address, target: Address) {
+        let bytecode = Bytecode::new_eip7702(target);
+        let account_info = self.db.load_account(authority).unwrap();
+        account_info.info.code_hash = bytecode.hash_slow();
+        account_info.info.code = Some(bytecode);
+        account_info.account_state = AccountState::None;
+    }
+
+    /// Installs an EIP-7702 delegation designator as the code of `authority`.
+    pub fn account_delegation(mut self, authority: Address, target: Address) -> Self {
+        self.set_account_delegation(authority, target);
+        self
+    }
+
+    /// Creates a `MemoryDatabase` holding the accounts of an alloc.
+    pub fn from_alloc(alloc: BTreeMap<Address, AllocAccount>) -> Self {
+        let mut db = Self::default();
+        for (address, account) in alloc {
+            let code = Bytecode::new_raw_checked(account.code.clone())
+                .unwrap_or_else(|_| Bytecode::new_legacy(account.code));
+            db.db.insert_account_info(
+                address,
+                AccountInfo {
+                    balance: account.balance,
+                    nonce: account.nonce.to(),
+                    code_hash: code.hash_slow(),
+                    code: Some(code),
+                },
+            );
+            for (slot, value) in account.storage {
+                db.set_account_storage(address, slot, value);
+            }
+        }
+        db
+    }
+
+    /// Dumps the accounts of the database as an alloc. Accounts known not to exist are skipped,
+    /// as are zero storage slots.
+    pub fn to_alloc(&self) -> BTreeMap<Address, AllocAccount> {
+        let contracts = &self.db.cache.contracts;
+        self.db
+            .cache
+            .accounts
+            .iter()
+            .filter(|(_, account)| account.account_state != AccountState::NotExisting)
+            .map(|(address, account)| {
+                let code = account
+                    .info
+                    .code
+                    .as_ref()
+                    .or_else(|| contracts.get(&account.info.code_hash))
+                    .map(Bytecode::original_bytes)
+                    .unwrap_or_default();
+                let storage = account
+                    .storage
+                    .iter()
+                    .filter(|(_, value)| !value.is_zero())
+                    .map(|(slot, value)| (*slot, *value))
+                    .collect();
+                let account = AllocAccount {
+                    balance: account.info.balance,
+                    code,
+                    nonce: U64::from(account.info.nonce),
+                    storage,
+                };
+                (*address, account)
+            })
+            .collect()
+    }
+
+    /// Creates a `MemoryDatabase` from an alloc JSON document.
+    pub fn from_alloc_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json).map(Self::from_alloc)
+    }
+
+    /// Dumps the accounts of the database as a pretty-printed alloc JSON document, ordered by
+    /// address so dumps are byte-reproducible.
+    pub fn to_alloc_json(&self) -> String {
+        serde_json::to_string_pretty(&self.to_alloc()).expect("alloc serialization cannot fail")
+    }
+
+    /// Loads a `MemoryDatabase` from an alloc JSON file.
+    pub fn load_alloc_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self::from_alloc_json(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Dumps the accounts of the database to an alloc JSON file.
+    pub fn dump_alloc_file(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_alloc_json())
+    }
 }
 
 impl revm::Database for MemoryDatabase {
@@ -113,6 +223,19 @@ impl revm::Database for MemoryDatabase {
     }
 }
 
+impl revm::DatabaseRef for MemoryDatabase {
+    type Error = Infallible;
+
+    delegate! {
+        to self.db {
+            fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error>;
+            fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error>;
+            fn storage_ref(&self, address: Address, index: StorageKey) -> Result<StorageValue, Self::Error>;
+            fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error>;
+        }
+    }
+}
+
 impl revm::DatabaseCommit for MemoryDatabase {
     delegate! {
         to self.db {
@@ -193,3 +316,88 @@ impl revm::DatabaseCommit for ErrorInjectingDatabase {
         self.inner.commit(changes);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, bytes};
+    use revm::{Database, DatabaseRef};
+
+    const ALLOC: &str = r#"{
+        "0x1000000000000000000000000000000000000001": {
+            "balance": "0xde0b6b3a7640000",
+            "code": "0x",
+            "nonce": "0x1",
+            "storage": {}
+        },
+        "0x2000000000000000000000000000000000000002": {
+            "balance": "0x0",
+            "code": "0x600160005500",
+            "nonce": "0x0",
+            "storage": { "0x01": "0x2a" }
+        },
+        "0x3000000000000000000000000000000000000003": { "balance": "0x5" }
+    }"#;
+
+    #[test]
+    fn test_load_alloc_json() {
+        let mut db = MemoryDatabase::from_alloc_json(ALLOC).unwrap();
+        let eoa = db.basic(address!("1000000000000000000000000000000000000001")).unwrap().unwrap();
+        assert_eq!((eoa.balance, eoa.nonce), (U256::from(10).pow(U256::from(18)), 1));
+
+        let contract = address!("2000000000000000000000000000000000000002");
+        let info = db.basic_ref(contract).unwrap().unwrap();
+        let code = db.code_by_hash(info.code_hash).unwrap();
+        assert_eq!(code.original_bytes(), bytes!("600160005500"));
+        assert_eq!(db.storage(contract, U256::from(1)).unwrap(), U256::from(42));
+
+        let partial = db.basic(address!("3000000000000000000000000000000000000003")).unwrap();
+        assert_eq!(partial.unwrap().balance, U256::from(5));
+    }
+
+    #[test]
+    fn test_account_delegation() {
+        let authority = address!("1000000000000000000000000000000000000001");
+        let target = address!("2000000000000000000000000000000000000002");
+        let mut db = MemoryDatabase::default().account_delegation(authority, target);
+        let code = db.basic(authority).unwrap().unwrap().code.unwrap();
+        assert_eq!(code.eip7702_address(), Some(target));
+
+        let alloc = db.to_alloc();
+        let reloaded = MemoryDatabase::from_alloc(alloc);
+        let code = reloaded.basic_ref(authority).unwrap().unwrap().code.unwrap();
+        assert!(code.is_eip7702(), "alloc code is reloaded as a delegation designator");
+    }
+
+    #[test]
+    fn test_alloc_roundtrip() {
+        let db = MemoryDatabase::default()
+            .account_balance(address!("1000000000000000000000000000000000000001"), U256::from(7))
+            .account_nonce(address!("1000000000000000000000000000000000000001"), 3)
+            .account_code(address!("2000000000000000000000000000000000000002"), bytes!("00"))
+            .account_storage(
+                address!("2000000000000000000000000000000000000002"),
+                U256::from(1),
+                U256::from(2),
+            )
+            .account_storage(
+                address!("2000000000000000000000000000000000000002"),
+                U256::from(3),
+                U256::ZERO,
+            );
+        let alloc = db.to_alloc();
+        let contract = &alloc[&address!("2000000000000000000000000000000000000002")];
+        assert_eq!(contract.storage.len(), 1, "zero slots are not dumped");
+
+        let json = db.to_alloc_json();
+        let reloaded = MemoryDatabase::from_alloc_json(&json).unwrap();
+        assert_eq!(reloaded.to_alloc(), alloc);
+        assert_eq!(reloaded.to_alloc_json(), json);
+
+        let file = std::env::temp_dir().join(format!("mega-evm-alloc-{}.json", std::process::id()));
+        db.dump_alloc_file(&file).unwrap();
+        let loaded = MemoryDatabase::load_alloc_file(&file).unwrap();
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(loaded.to_alloc(), alloc);
+    }
+}
@@ -9,9 +9,18 @@ use revm::{
 };
 
 use crate::{
-    MegaContext, MegaEvm, MegaHaltReason, MegaSpecId, MegaTransaction, MegaTransactionError,
+    EmptyExternalEnv, MegaChainConfig, MegaContext, MegaEvm, MegaHaltReason, MegaSpecId,
+    MegaTransaction, MegaTransactionError,
 };
 
+/// Creates a context for `spec` that charges no operator fee.
+pub fn context_without_operator_fee<DB: alloy_evm::Database>(
+    db: DB,
+    spec: MegaSpecId,
+) -> MegaContext<DB, EmptyExternalEnv> {
+    MegaContext::new(db, spec).with_chain_config(MegaChainConfig::default().without_operator_fee())
+}
+
 /// Executes a transaction on the EVM.
 pub fn transact<DB>(
     spec: MegaSpecId,
@@ -25,12 +34,7 @@ where
     DB: Database + Debug,
     DB::Error: Send + Sync + Debug + 'static,
 {
-    let mut context = MegaContext::new(db, spec);
-    context.modify_chain(|chain| {
-        chain.operator_fee_scalar = Some(U256::from(0));
-        chain.operator_fee_constant = Some(U256::from(0));
-    });
-    let mut evm = MegaEvm::new(context);
+    let mut evm = MegaEvm::new(context_without_operator_fee(db, spec));
     let tx = TxEnv {
         caller,
         kind: callee.map_or(TxKind::Create, TxKind::Call),
@@ -0,0 +1,300 @@
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::{rc::Rc, vec::Vec};
+
+use core::convert::Infallible;
+
+use alloy_primitives::{Address, B256, U256};
+use revm::{
+    database::DatabaseRef,
+    primitives::{HashMap, HashSet, StorageKey, StorageValue, KECCAK_EMPTY},
+    state::{Account, AccountInfo, Bytecode},
+    DatabaseCommit,
+};
+
+use crate::test_utils::MemoryDatabase;
+
+/// The changes to a single account recorded in a [`ForkLayer`].
+#[derive(Debug, Default, Clone)]
+struct ForkAccount {
+    /// The account info, or `None` if the account was destroyed.
+    info: Option<AccountInfo>,
+    /// Whether all storage below this layer was wiped (self-destruct or re-creation).
+    storage_cleared: bool,
+    /// Storage slots written in this layer.
+    storage: HashMap<StorageKey, StorageValue>,
+}
+
+/// A set of state changes.
+#[derive(Debug, Default, Clone)]
+struct ForkChanges {
+    accounts: HashMap<Address, ForkAccount>,
+    contracts: HashMap<B256, Bytecode>,
+}
+
+impl ForkChanges {
+    fn is_empty(&self) -> bool {
+        self.accounts.is_empty() && self.contracts.is_empty()
+    }
+
+    /// Applies `other` on top of these changes.
+    fn apply(&mut self, other: &Self) {
+        for (address, changes) in &other.accounts {
+            let account = self.accounts.entry(*address).or_default();
+            account.info = changes.info.clone();
+            if changes.storage_cleared {
+                account.storage.clear();
+                account.storage_cleared = true;
+            }
+            account.storage.extend(changes.storage.iter().map(|(k, v)| (*k, *v)));
+        }
+        self.contracts.extend(other.contracts.iter().map(|(k, v)| (*k, v.clone())));
+    }
+}
+
+/// A frozen set of changes shared by every fork taken after it.
+#[derive(Debug)]
+struct ForkLayer {
+    parent: Option<Rc<ForkLayer>>,
+    changes: ForkChanges,
+}
+
+/// An in-memory database with cheap copy-on-write forks.
+///
+/// The pre-state is a [`MemoryDatabase`] shared by all forks. Committed changes go to a private
+/// overlay; [`fork`](Self::fork) freezes the overlay into a layer shared by the original and the
+/// fork, so forking never copies state. Use [`merge`](Self::merge) to bring a fork's changes back.
+///
+/// This lets tests explore several execution branches from the same pre-state (e.g. a
+/// transaction that exceeds a limit versus one that does not) without rebuilding it.
+#[derive(Debug, Clone)]
+pub struct ForkableDb {
+    base: Rc<MemoryDatabase>,
+    head: Option<Rc<ForkLayer>>,
+    overlay: ForkChanges,
+}
+
+impl From<MemoryDatabase> for ForkableDb {
+    fn from(base: MemoryDatabase) -> Self {
+        Self::new(base)
+    }
+}
+
+impl ForkableDb {
+    /// Creates a database over the given pre-state.
+    pub fn new(base: MemoryDatabase) -> Self {
+        Self { base: Rc::new(base), head: None, overlay: ForkChanges::default() }
+    }
+
+    /// Returns a fork sharing the current state. Changes committed to either database afterwards
+    /// are not visible to the other.
+    pub fn fork(&mut self) -> Self {
+        if !self.overlay.is_empty() {
+            let changes = core::mem::take(&mut self.overlay);
+            self.head = Some(Rc::new(ForkLayer { parent: self.head.take(), changes }));
+        }
+        Self { base: self.base.clone(), head: self.head.clone(), overlay: ForkChanges::default() }
+    }
+
+    /// Applies the changes `fork` made since it was forked from this database's history.
+    ///
+    /// Where both databases changed the same account or slot, the fork's value wins.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fork` does not share this database's pre-state.
+    pub fn merge(&mut self, fork: Self) {
+        assert!(
+            Rc::ptr_eq(&self.base, &fork.base),
+            "cannot merge a database with a different base"
+        );
+        let ancestors: HashSet<*const ForkLayer> = self.layers().map(Rc::as_ptr).collect();
+        let mut diverged: Vec<&Rc<ForkLayer>> =
+            fork.layers().take_while(|layer| !ancestors.contains(&Rc::as_ptr(layer))).collect();
+        diverged.reverse();
+        for layer in diverged {
+            self.overlay.apply(&layer.changes);
+        }
+        self.overlay.apply(&fork.overlay);
+    }
+
+    /// Returns the frozen layers from the newest to the oldest.
+    fn layers(&self) -> impl Iterator<Item = &Rc<ForkLayer>> {
+        core::iter::successors(self.head.as_ref(), |layer| layer.parent.as_ref())
+    }
+
+    /// Returns the change sets from the newest to the oldest.
+    fn changes(&self) -> impl Iterator<Item = &ForkChanges> {
+        core::iter::once(&self.overlay).chain(self.layers().map(|layer| &layer.changes))
+    }
+}
+
+impl DatabaseRef for ForkableDb {
+    type Error = Infallible;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        match self.changes().find_map(|changes| changes.accounts.get(&address)) {
+            Some(account) => Ok(account.info.clone()),
+            None => self.base.basic_ref(address),
+        }
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        match self.changes().find_map(|changes| changes.contracts.get(&code_hash)) {
+            Some(code) => Ok(code.clone()),
+            None => self.base.code_by_hash_ref(code_hash),
+        }
+    }
+
+    fn storage_ref(
+        &self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        for changes in self.changes() {
+            if let Some(account) = changes.accounts.get(&address) {
+                if let Some(value) = account.storage.get(&index) {
+                    return Ok(*value);
+                }
+                if account.storage_cleared {
+                    return Ok(U256::ZERO);
+                }
+            }
+        }
+        self.base.storage_ref(address, index)
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.base.block_hash_ref(number)
+    }
+}
+
+impl revm::Database for ForkableDb {
+    type Error = Infallible;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.basic_ref(address)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.code_by_hash_ref(code_hash)
+    }
+
+    fn storage(
+        &mut self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        self.storage_ref(address, index)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.block_hash_ref(number)
+    }
+}
+
+impl DatabaseCommit for ForkableDb {
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        for (address, mut account) in changes {
+            if !account.is_touched() {
+                continue;
+            }
+            let entry = self.overlay.accounts.entry(address).or_default();
+            if account.is_selfdestructed() {
+                *entry = ForkAccount { info: None, storage_cleared: true, ..Default::default() };
+                continue;
+            }
+            if let Some(code) = account.info.code.take() {
+                if account.info.code_hash != KECCAK_EMPTY {
+                    self.overlay.contracts.insert(account.info.code_hash, code);
+                }
+            }
+            if account.is_created() {
+                entry.storage.clear();
+                entry.storage_cleared = true;
+            }
+            entry.info = Some(account.info);
+            entry
+                .storage
+                .extend(account.storage.into_iter().map(|(k, slot)| (k, slot.present_value())));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use revm::{state::EvmStorageSlot, Database};
+
+    const ALICE: Address = address!("0000000000000000000000000000000000100000");
+    const BOB: Address = address!("0000000000000000000000000000000000100001");
+
+    fn write(db: &mut ForkableDb, address: Address, balance: u64, slot: u64, value: u64) {
+        let mut account =
+            Account::from(AccountInfo { balance: U256::from(balance), ..Default::default() });
+        account.mark_touch();
+        account.storage.insert(
+            U256::from(slot),
+            EvmStorageSlot::new_changed(U256::ZERO, U256::from(value), 0),
+        );
+        db.commit(HashMap::from_iter([(address, account)]));
+    }
+
+    fn balance(db: &mut ForkableDb, address: Address) -> U256 {
+        db.basic(address).unwrap().map(|info| info.balance).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_forks_are_isolated() {
+        let base = MemoryDatabase::default().account_balance(ALICE, U256::from(100));
+        let mut db = ForkableDb::new(base);
+        write(&mut db, BOB, 1, 0, 10);
+
+        let mut fork = db.fork();
+        write(&mut fork, ALICE, 50, 0, 20);
+        write(&mut db, BOB, 2, 0, 30);
+
+        assert_eq!(balance(&mut db, ALICE), U256::from(100));
+        assert_eq!(balance(&mut fork, ALICE), U256::from(50));
+        assert_eq!(balance(&mut fork, BOB), U256::from(1));
+        assert_eq!(fork.storage(BOB, U256::ZERO).unwrap(), U256::from(10));
+        assert_eq!(db.storage(BOB, U256::ZERO).unwrap(), U256::from(30));
+        assert_eq!(db.storage(ALICE, U256::ZERO).unwrap(), U256::ZERO);
+    }
+
+    #[test]
+    fn test_merge_applies_fork_changes() {
+        let mut db = ForkableDb::new(MemoryDatabase::default());
+        write(&mut db, ALICE, 1, 0, 1);
+
+        let mut fork = db.fork();
+        write(&mut fork, ALICE, 2, 1, 2);
+        let mut nested = fork.fork();
+        write(&mut nested, BOB, 3, 0, 3);
+        fork.merge(nested);
+        db.merge(fork);
+
+        assert_eq!(balance(&mut db, ALICE), U256::from(2));
+        assert_eq!(balance(&mut db, BOB), U256::from(3));
+        assert_eq!(db.storage(ALICE, U256::ZERO).unwrap(), U256::from(1));
+        assert_eq!(db.storage(ALICE, U256::from(1)).unwrap(), U256::from(2));
+        assert_eq!(db.storage(BOB, U256::ZERO).unwrap(), U256::from(3));
+    }
+
+    #[test]
+    fn test_selfdestruct_clears_storage_in_fork_only() {
+        let base = MemoryDatabase::default().account_storage(ALICE, U256::ZERO, U256::from(7));
+        let mut db = ForkableDb::new(base);
+        let mut fork = db.fork();
+
+        let mut account = Account::from(AccountInfo::default());
+        account.mark_touch();
+        account.mark_selfdestruct();
+        fork.commit(HashMap::from_iter([(ALICE, account)]));
+
+        assert!(fork.basic(ALICE).unwrap().is_none());
+        assert_eq!(fork.storage(ALICE, U256::ZERO).unwrap(), U256::ZERO);
+        assert_eq!(db.storage(ALICE, U256::ZERO).unwrap(), U256::from(7));
+    }
+}
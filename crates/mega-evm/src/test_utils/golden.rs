@@ -0,0 +1,297 @@
+//! Golden execution traces: the opcode stream and the gas of every step of a corpus of
+//! representative contracts, executed under every spec.
+//!
+//! The traces are stored as text files under [`GOLDEN_TRACES_DIR`] and checked by the `golden`
+//! test target, so a refactor that changes which opcodes run or what they cost fails loudly. An
+//! intended change is accepted by regenerating the traces with the `bless-golden-traces` binary
+//! (`cargo run -p mega-fixtures --bin bless-golden-traces`) and reviewing the diff.
+
+use std::{
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+    vec::Vec,
+};
+
+use alloy_primitives::{address, Address, U256};
+use revm::{
+    bytecode::opcode::{
+        ADD, CALL, CREATE, GAS, KECCAK256, LOG1, MLOAD, MSTORE8, MUL, POP, PUSH0, RETURN, SLOAD,
+        SSTORE, SWAP1,
+    },
+    context::result::ExecutionResult,
+};
+
+use crate::{
+    test_utils::{
+        context_without_operator_fee, BytecodeBuilder, GasInspector, MegaTxBuilder, MemoryDatabase,
+    },
+    MegaEvm, MegaSpecId, MegaTransaction,
+};
+
+/// The directory holding the golden traces, one `<case>.<spec>.trace` file per case and spec.
+pub const GOLDEN_TRACES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/traces");
+
+/// The specs every case of the corpus is traced under.
+pub const GOLDEN_SPECS: [MegaSpecId; 9] = [
+    MegaSpecId::EQUIVALENCE,
+    MegaSpecId::MINI_REX,
+    MegaSpecId::REX,
+    MegaSpecId::REX1,
+    MegaSpecId::REX2,
+    MegaSpecId::REX3,
+    MegaSpecId::REX4,
+    MegaSpecId::REX5,
+    MegaSpecId::REX6,
+];
+
+const CALLER: Address = address!("0000000000000000000000000000000000a00000");
+const CONTRACT: Address = address!("0000000000000000000000000000000000a00001");
+const MIDDLE: Address = address!("0000000000000000000000000000000000a00002");
+const REVERTER: Address = address!("0000000000000000000000000000000000a00003");
+const FRESH: Address = address!("0000000000000000000000000000000000a00004");
+
+/// A case of the golden trace corpus: a transaction and the state it runs on.
+#[derive(Debug)]
+pub struct GoldenCase {
+    /// The name of the case, used in the names of its trace files.
+    pub name: &'static str,
+    /// The state the transaction runs on.
+    pub db: MemoryDatabase,
+    /// The transaction.
+    pub tx: MegaTransaction,
+}
+
+impl GoldenCase {
+    fn call(name: &'static str, db: MemoryDatabase) -> Self {
+        let tx = MegaTxBuilder::new().caller(CALLER).call(CONTRACT).gas_limit(10_000_000).build();
+        Self { name, db, tx }
+    }
+}
+
+/// Returns the corpus of cases, each exercising a different area of gas accounting.
+pub fn golden_corpus() -> Vec<GoldenCase> {
+    let arithmetic = BytecodeBuilder::default()
+        .append(PUSH0)
+        .repeat(16, |b| {
+            b.append(SWAP1)
+                .push_number(7_u8)
+                .append(MUL)
+                .push_number(3_u8)
+                .append_many([ADD, SWAP1])
+        })
+        .append(POP)
+        .stop()
+        .build();
+
+    let storage = BytecodeBuilder::default()
+        .sstore(U256::from(1), U256::from(1))
+        .sstore(U256::from(2), U256::from(2))
+        .sstore(U256::from(1), U256::from(3))
+        .sstore(U256::from(2), U256::ZERO)
+        .sstore(U256::from(3), U256::ZERO)
+        .push_number(1_u8)
+        .append_many([SLOAD, POP])
+        .stop()
+        .build();
+
+    let memory = BytecodeBuilder::default()
+        .mstore(0, [0xab; 64])
+        .push_number(64_u8)
+        .append_many([PUSH0, KECCAK256, POP])
+        .push_number(0x2000_u16)
+        .append_many([MLOAD, POP])
+        .stop()
+        .build();
+
+    // CONTRACT calls MIDDLE, which calls REVERTER, then sends value to a fresh account.
+    let outer = BytecodeBuilder::default()
+        .call_contract(MIDDLE, [0x01, 0x02, 0x03, 0x04])
+        .append(POP)
+        .append_many([PUSH0, PUSH0, PUSH0, PUSH0])
+        .push_number(1_u8)
+        .push_address(FRESH)
+        .append_many([GAS, CALL, POP])
+        .stop()
+        .build();
+    let middle = BytecodeBuilder::default()
+        .call_contract(REVERTER, [])
+        .append(PUSH0)
+        .append(SSTORE)
+        .stop()
+        .build();
+    let reverter = BytecodeBuilder::default().sstore(U256::from(1), U256::from(1)).revert().build();
+
+    // Init code returning a single STOP byte as runtime code.
+    let init_code = [PUSH0, PUSH0, MSTORE8, 0x60, 0x01, PUSH0, RETURN];
+    let create = BytecodeBuilder::default()
+        .mstore(0, init_code)
+        .push_number(init_code.len() as u8)
+        .append_many([PUSH0, PUSH0, CREATE, POP])
+        .stop()
+        .build();
+
+    let logs = BytecodeBuilder::default()
+        .mstore(0, [0xcd; 32])
+        .push_number(0x42_u8)
+        .push_number(32_u8)
+        .append_many([PUSH0, LOG1])
+        .stop()
+        .build();
+
+    vec![
+        GoldenCase::call(
+            "arithmetic",
+            MemoryDatabase::default().account_code(CONTRACT, arithmetic),
+        ),
+        GoldenCase::call(
+            "storage",
+            MemoryDatabase::default().account_code(CONTRACT, storage).account_storage(
+                CONTRACT,
+                U256::from(3),
+                U256::from(5),
+            ),
+        ),
+        GoldenCase::call("memory", MemoryDatabase::default().account_code(CONTRACT, memory)),
+        GoldenCase::call(
+            "nested_calls",
+            MemoryDatabase::default()
+                .account_code(CONTRACT, outer)
+                .account_balance(CONTRACT, U256::from(1))
+                .account_code(MIDDLE, middle)
+                .account_code(REVERTER, reverter),
+        ),
+        GoldenCase::call("create", MemoryDatabase::default().account_code(CONTRACT, create)),
+        GoldenCase::call("logs", MemoryDatabase::default().account_code(CONTRACT, logs)),
+    ]
+}
+
+/// Executes `case` under `spec` and renders its trace: a header with the outcome, then one line
+/// per step with the call depth, the opcode, the gas left before the step and its cost.
+pub fn golden_trace(case: &GoldenCase, spec: MegaSpecId) -> String {
+    let mut db = case.db.clone();
+    let context = context_without_operator_fee(&mut db, spec);
+    let mut inspector = GasInspector::new();
+    let outcome = MegaEvm::new(context)
+        .with_inspector(&mut inspector)
+        .execute_transaction(case.tx.clone())
+        .expect("golden cases are valid transactions");
+    let status = match &outcome.result {
+        ExecutionResult::Success { .. } => "success",
+        ExecutionResult::Revert { .. } => "revert",
+        ExecutionResult::Halt { .. } => "halt",
+    };
+
+    let mut trace = String::new();
+    writeln!(trace, "# case: {}", case.name).unwrap();
+    writeln!(trace, "# spec: {spec}").unwrap();
+    writeln!(trace, "# result: {status}, gas used {}", outcome.result.gas_used()).unwrap();
+    for step in inspector.records() {
+        writeln!(
+            trace,
+            "{} {} gas={} cost={}",
+            step.depth,
+            step.opcode.as_str(),
+            step.gas_before,
+            step.gas_cost()
+        )
+        .unwrap();
+    }
+    trace
+}
+
+/// Returns the path of the golden trace of the case named `case` under `spec`.
+pub fn golden_trace_path(dir: &Path, case: &str, spec: MegaSpecId) -> PathBuf {
+    dir.join(format!("{case}.{spec}.trace"))
+}
+
+/// A golden trace that does not match the current execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoldenMismatch {
+    /// The name of the case.
+    pub case: &'static str,
+    /// The spec the case was traced under.
+    pub spec: MegaSpecId,
+    /// The first line that differs (1-based).
+    pub line: usize,
+    /// The line of the golden trace, or `None` if it ends earlier.
+    pub expected: Option<String>,
+    /// The line of the current trace, or `None` if it ends earlier.
+    pub actual: Option<String>,
+}
+
+impl core::fmt::Display for GoldenMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let line = |line: &Option<String>| line.clone().unwrap_or_else(|| "<end>".to_string());
+        write!(
+            f,
+            "{} under {}: line {} changed from `{}` to `{}`",
+            self.case,
+            self.spec,
+            self.line,
+            line(&self.expected),
+            line(&self.actual)
+        )
+    }
+}
+
+/// The outcome of checking the corpus against the golden traces.
+#[derive(Debug, Default)]
+pub struct GoldenReport {
+    /// The traces that changed.
+    pub mismatches: Vec<GoldenMismatch>,
+    /// The golden trace files that do not exist yet.
+    pub missing: Vec<PathBuf>,
+}
+
+/// Traces every case of the corpus under every spec and compares the traces with the golden
+/// traces in `dir`.
+pub fn check_golden_traces(dir: &Path) -> io::Result<GoldenReport> {
+    let mut report = GoldenReport::default();
+    for case in golden_corpus() {
+        for spec in GOLDEN_SPECS {
+            let path = golden_trace_path(dir, case.name, spec);
+            let expected = match fs::read_to_string(&path) {
+                Ok(expected) => expected,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    report.missing.push(path);
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+            let actual = golden_trace(&case, spec);
+            let (mut expected_lines, mut actual_lines) = (expected.lines(), actual.lines());
+            for line in 1.. {
+                let (expected, actual) = (expected_lines.next(), actual_lines.next());
+                if expected.is_none() && actual.is_none() {
+                    break;
+                }
+                if expected != actual {
+                    report.mismatches.push(GoldenMismatch {
+                        case: case.name,
+                        spec,
+                        line,
+                        expected: expected.map(str::to_string),
+                        actual: actual.map(str::to_string),
+                    });
+                    break;
+                }
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Regenerates the golden traces of every case under every spec in `dir`, returning the number
+/// of traces written.
+pub fn bless_golden_traces(dir: &Path) -> io::Result<usize> {
+    fs::create_dir_all(dir)?;
+    let mut written = 0;
+    for case in golden_corpus() {
+        for spec in GOLDEN_SPECS {
+            fs::write(golden_trace_path(dir, case.name, spec), golden_trace(&case, spec))?;
+            written += 1;
+        }
+    }
+    Ok(written)
+}
@@ -0,0 +1,462 @@
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::vec::Vec;
+
+use revm::{
+    bytecode::{opcode, OpCode},
+    context::{ContextTr, JournalTr, Transaction},
+    interpreter::{
+        interpreter_types::Jumps, CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter,
+    },
+    Database, Inspector,
+};
+
+use crate::{ExternalEnvTypes, MegaContext, MegaTransactionOutcome};
+
+/// A single gas mutation observed by [`InvariantInspector`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GasMutation {
+    /// An opcode executed in the current frame.
+    Opcode {
+        /// The opcode executed.
+        opcode: OpCode,
+        /// Call depth of the frame executing the opcode.
+        depth: usize,
+        /// EVM gas charged by the opcode, including gas forwarded to a child frame.
+        evm_gas: u64,
+        /// Compute gas recorded by the opcode.
+        compute_gas: u64,
+        /// Change of the frame's refund counter.
+        refund_delta: i64,
+    },
+    /// A call or create frame was entered.
+    FrameEnter {
+        /// Call depth of the new frame.
+        depth: usize,
+        /// Gas limit of the new frame.
+        gas_limit: u64,
+    },
+    /// A call or create frame returned.
+    FrameExit {
+        /// Call depth of the returning frame.
+        depth: usize,
+        /// Gas left unspent by the frame.
+        remaining: u64,
+        /// Refund counter of the frame.
+        refunded: i64,
+        /// Whether the frame succeeded, so its refunds propagate to the parent.
+        success: bool,
+    },
+}
+
+/// A gas accounting invariant violated by a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// An opcode recorded more compute gas than the EVM gas it charged.
+    OpcodeComputeExceedsEvmGas {
+        /// The offending opcode.
+        opcode: OpCode,
+        /// Call depth of the frame executing the opcode.
+        depth: usize,
+        /// EVM gas charged by the opcode.
+        evm_gas: u64,
+        /// Compute gas recorded by the opcode.
+        compute_gas: u64,
+    },
+    /// A frame returned more gas than it was given.
+    FrameRemainingExceedsLimit {
+        /// Call depth of the frame.
+        depth: usize,
+        /// Gas limit of the frame.
+        gas_limit: u64,
+        /// Gas left unspent by the frame.
+        remaining: u64,
+    },
+    /// The reported `gas_used` exceeds the transaction gas limit.
+    GasUsedExceedsLimit {
+        /// Reported gas used.
+        gas_used: u64,
+        /// Transaction gas limit.
+        gas_limit: u64,
+    },
+    /// The reported `gas_used` is lower than the executed gas minus the largest allowed refund.
+    GasUsedBelowRefundedGas {
+        /// Reported gas used.
+        gas_used: u64,
+        /// Intrinsic gas plus the gas spent by the top-level frame.
+        executed_gas: u64,
+        /// Largest refund the transaction could have received.
+        max_refund: u64,
+    },
+    /// The reported compute gas exceeds the gas the transaction consumed.
+    ComputeGasExceedsExecutedGas {
+        /// Reported compute gas used.
+        compute_gas_used: u64,
+        /// Intrinsic gas plus the gas spent by the top-level frame.
+        executed_gas: u64,
+    },
+    /// The reported compute gas is lower than the compute gas observed during execution.
+    ComputeGasBelowRecorded {
+        /// Reported compute gas used.
+        compute_gas_used: u64,
+        /// Intrinsic compute gas plus the compute gas recorded by opcodes.
+        recorded: u64,
+    },
+    /// No top-level frame was observed, so the transaction cannot be reconciled.
+    MissingTopLevelFrame,
+}
+
+/// Top-level frame accounting captured for the tx-end reconciliation.
+#[derive(Debug, Clone, Default)]
+struct TopLevelFrame {
+    tx_gas_limit: u64,
+    gas_limit: u64,
+    intrinsic_compute_gas: u64,
+    exit: Option<(u64, i64, bool)>,
+}
+
+/// Snapshot taken in `step` and completed in `step_end`.
+#[derive(Debug, Clone)]
+struct PendingStep {
+    opcode: OpCode,
+    depth: usize,
+    remaining: u64,
+    refunded: i64,
+    compute_gas: u64,
+}
+
+/// An inspector that records every gas mutation of a transaction and reconciles them with the
+/// reported outcome.
+///
+/// For each opcode it records the EVM gas charged, the compute gas recorded by the limit
+/// subsystem, and the refund delta; for each frame it records the gas limit on entry and the
+/// remaining gas and refunds on exit. Storage gas is not metered separately by the EVM, so it is
+/// derived as the EVM gas an opcode charged beyond its compute gas (see
+/// [`storage_gas`](Self::storage_gas)).
+///
+/// After the transaction, [`check`](Self::check) verifies that:
+/// - no opcode records more compute gas than the EVM gas it charged;
+/// - no frame returns more gas than it was given;
+/// - `gas_used` lies between the executed gas minus the largest allowed refund (EIP-3529 caps
+///   refunds at a fifth of the executed gas) and the transaction gas limit;
+/// - the reported compute gas covers the intrinsic and per-opcode compute gas, and never exceeds
+///   the executed gas.
+///
+/// Unit tests can call [`assert_invariants`](Self::assert_invariants); fuzz harnesses can collect
+/// the violations returned by [`check`](Self::check). Call [`clear`](Self::clear) between
+/// transactions when reusing the inspector.
+#[derive(Debug, Default)]
+pub struct InvariantInspector {
+    mutations: Vec<GasMutation>,
+    violations: Vec<InvariantViolation>,
+    frame_limits: Vec<u64>,
+    pending: Option<PendingStep>,
+    top_level: Option<TopLevelFrame>,
+}
+
+impl InvariantInspector {
+    /// Creates an empty inspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clears all recorded state so the inspector can observe another transaction.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Returns the gas mutations recorded so far, in execution order.
+    pub fn mutations(&self) -> &[GasMutation] {
+        &self.mutations
+    }
+
+    /// Returns the compute gas recorded by opcodes.
+    pub fn opcode_compute_gas(&self) -> u64 {
+        self.opcode_totals().0
+    }
+
+    /// Returns the storage gas charged by opcodes, i.e. the EVM gas charged beyond compute gas by
+    /// opcodes that do not spawn a frame.
+    pub fn storage_gas(&self) -> u64 {
+        self.opcode_totals().1
+    }
+
+    /// Returns the refunds accrued by opcodes, before frame reverts discard any of them.
+    pub fn opcode_refunds(&self) -> i64 {
+        self.mutations
+            .iter()
+            .map(|m| match m {
+                GasMutation::Opcode { refund_delta, .. } => *refund_delta,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    fn opcode_totals(&self) -> (u64, u64) {
+        self.mutations.iter().fold((0, 0), |(compute, storage), m| match m {
+            GasMutation::Opcode { opcode, evm_gas, compute_gas, .. } => {
+                let storage_gas = if spawns_frame(opcode.get()) {
+                    0
+                } else {
+                    evm_gas.saturating_sub(*compute_gas)
+                };
+                (compute + compute_gas, storage + storage_gas)
+            }
+            _ => (compute, storage),
+        })
+    }
+
+    /// Reconciles the recorded gas mutations with the transaction outcome.
+    ///
+    /// Returns every violated invariant, including per-opcode and per-frame violations detected
+    /// during execution.
+    pub fn check(&self, outcome: &MegaTransactionOutcome) -> Result<(), Vec<InvariantViolation>> {
+        let mut violations = self.violations.clone();
+        match self.top_level.as_ref().and_then(|top| top.exit.map(|exit| (top, exit))) {
+            Some((top, (remaining, refunded, success))) => {
+                let gas_used = outcome.result.gas_used();
+                let intrinsic_gas = top.tx_gas_limit.saturating_sub(top.gas_limit);
+                let executed_gas = intrinsic_gas + top.gas_limit.saturating_sub(remaining);
+                let refund = if success { refunded.max(0) as u64 } else { 0 };
+                let max_refund = refund.min(executed_gas / 5);
+
+                if gas_used > top.tx_gas_limit {
+                    violations.push(InvariantViolation::GasUsedExceedsLimit {
+                        gas_used,
+                        gas_limit: top.tx_gas_limit,
+                    });
+                }
+                if gas_used < executed_gas - max_refund {
+                    violations.push(InvariantViolation::GasUsedBelowRefundedGas {
+                        gas_used,
+                        executed_gas,
+                        max_refund,
+                    });
+                }
+                if outcome.compute_gas_used > executed_gas {
+                    violations.push(InvariantViolation::ComputeGasExceedsExecutedGas {
+                        compute_gas_used: outcome.compute_gas_used,
+                        executed_gas,
+                    });
+                }
+                let recorded = top.intrinsic_compute_gas + self.opcode_compute_gas();
+                if outcome.compute_gas_used < recorded {
+                    violations.push(InvariantViolation::ComputeGasBelowRecorded {
+                        compute_gas_used: outcome.compute_gas_used,
+                        recorded,
+                    });
+                }
+            }
+            None => violations.push(InvariantViolation::MissingTopLevelFrame),
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Panics with the list of violations if [`check`](Self::check) fails.
+    #[track_caller]
+    pub fn assert_invariants(&self, outcome: &MegaTransactionOutcome) {
+        if let Err(violations) = self.check(outcome) {
+            panic!("gas accounting invariants violated: {violations:#?}");
+        }
+    }
+
+    fn frame_enter(&mut self, depth: usize, gas_limit: u64, tx_gas_limit: u64, compute_gas: u64) {
+        if self.top_level.is_none() {
+            self.top_level = Some(TopLevelFrame {
+                tx_gas_limit,
+                gas_limit,
+                intrinsic_compute_gas: compute_gas,
+                exit: None,
+            });
+        }
+        self.frame_limits.push(gas_limit);
+        self.mutations.push(GasMutation::FrameEnter { depth, gas_limit });
+    }
+
+    fn frame_exit(&mut self, depth: usize, remaining: u64, refunded: i64, success: bool) {
+        let gas_limit = self.frame_limits.pop().unwrap_or(u64::MAX);
+        if remaining > gas_limit {
+            self.violations.push(InvariantViolation::FrameRemainingExceedsLimit {
+                depth,
+                gas_limit,
+                remaining,
+            });
+        }
+        if self.frame_limits.is_empty() {
+            if let Some(top) = self.top_level.as_mut() {
+                top.exit = Some((remaining, refunded, success));
+            }
+        }
+        self.mutations.push(GasMutation::FrameExit { depth, remaining, refunded, success });
+    }
+}
+
+/// Returns whether `opcode` spawns a child frame, so its EVM gas includes forwarded gas.
+const fn spawns_frame(opcode: u8) -> bool {
+    matches!(
+        opcode,
+        opcode::CALL |
+            opcode::CALLCODE |
+            opcode::DELEGATECALL |
+            opcode::STATICCALL |
+            opcode::CREATE |
+            opcode::CREATE2
+    )
+}
+
+fn compute_gas_usage<DB: Database, ExtEnvs: ExternalEnvTypes>(
+    context: &MegaContext<DB, ExtEnvs>,
+) -> u64 {
+    context.additional_limit.borrow().get_usage().compute_gas
+}
+
+impl<DB: Database, ExtEnvs: ExternalEnvTypes> Inspector<MegaContext<DB, ExtEnvs>>
+    for InvariantInspector
+{
+    fn step(&mut self, interp: &mut Interpreter, context: &mut MegaContext<DB, ExtEnvs>) {
+        let Some(opcode) = OpCode::new(interp.bytecode.opcode()) else { return };
+        self.pending = Some(PendingStep {
+            opcode,
+            depth: context.journal().depth(),
+            remaining: interp.gas.remaining(),
+            refunded: interp.gas.refunded(),
+            compute_gas: compute_gas_usage(context),
+        });
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut MegaContext<DB, ExtEnvs>) {
+        let Some(step) = self.pending.take() else { return };
+        let evm_gas = step.remaining.saturating_sub(interp.gas.remaining());
+        let compute_gas = compute_gas_usage(context).saturating_sub(step.compute_gas);
+        if compute_gas > evm_gas {
+            self.violations.push(InvariantViolation::OpcodeComputeExceedsEvmGas {
+                opcode: step.opcode,
+                depth: step.depth,
+                evm_gas,
+                compute_gas,
+            });
+        }
+        self.mutations.push(GasMutation::Opcode {
+            opcode: step.opcode,
+            depth: step.depth,
+            evm_gas,
+            compute_gas,
+            refund_delta: interp.gas.refunded() - step.refunded,
+        });
+    }
+
+    fn call(
+        &mut self,
+        context: &mut MegaContext<DB, ExtEnvs>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        let depth = context.journal().depth();
+        let tx_gas_limit = context.tx().gas_limit();
+        self.frame_enter(depth, inputs.gas_limit, tx_gas_limit, compute_gas_usage(context));
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        context: &mut MegaContext<DB, ExtEnvs>,
+        _inputs: &CallInputs,
+        outcome: &mut CallOutcome,
+    ) {
+        let gas = outcome.gas();
+        let success = outcome.result.result.is_ok();
+        self.frame_exit(context.journal().depth(), gas.remaining(), gas.refunded(), success);
+    }
+
+    fn create(
+        &mut self,
+        context: &mut MegaContext<DB, ExtEnvs>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        let depth = context.journal().depth();
+        let tx_gas_limit = context.tx().gas_limit();
+        self.frame_enter(depth, inputs.gas_limit, tx_gas_limit, compute_gas_usage(context));
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut MegaContext<DB, ExtEnvs>,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        let gas = outcome.gas();
+        let success = outcome.result.result.is_ok();
+        self.frame_exit(context.journal().depth(), gas.remaining(), gas.refunded(), success);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{
+            context_without_operator_fee, BytecodeBuilder, MegaTxBuilder, MemoryDatabase,
+        },
+        MegaEvm, MegaSpecId,
+    };
+    use alloy_primitives::{address, Address, U256};
+
+    const CALLER: Address = address!("0000000000000000000000000000000000100000");
+    const CALLEE: Address = address!("0000000000000000000000000000000000100001");
+
+    fn execute(
+        spec: MegaSpecId,
+        code: BytecodeBuilder,
+    ) -> (InvariantInspector, MegaTransactionOutcome) {
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000))
+            .account_code(CALLEE, code.build());
+        let context = context_without_operator_fee(&mut db, spec);
+        let mut evm = MegaEvm::new(context).with_inspector(InvariantInspector::new());
+        let tx = MegaTxBuilder::new().caller(CALLER).call(CALLEE).gas_limit(10_000_000).build();
+        let outcome = evm.execute_transaction(tx).unwrap();
+        (core::mem::take(&mut evm.inspector), outcome)
+    }
+
+    #[test]
+    fn test_sstore_reconciles_compute_and_storage_gas() {
+        let code = BytecodeBuilder::default().sstore(U256::ZERO, U256::from(1)).stop();
+        let (inspector, outcome) = execute(MegaSpecId::MINI_REX, code);
+        inspector.assert_invariants(&outcome);
+        assert!(inspector.storage_gas() > 0, "a fresh SSTORE charges storage gas");
+        assert!(inspector.opcode_compute_gas() > 0);
+        assert!(matches!(inspector.mutations()[0], GasMutation::FrameEnter { depth: 0, .. }));
+        assert!(matches!(inspector.mutations().last(), Some(GasMutation::FrameExit { .. })));
+    }
+
+    #[test]
+    fn test_refund_is_recorded_and_reconciled() {
+        let code = BytecodeBuilder::default()
+            .sstore(U256::ZERO, U256::from(1))
+            .sstore(U256::ZERO, U256::ZERO)
+            .stop();
+        let (inspector, outcome) = execute(MegaSpecId::EQUIVALENCE, code);
+        inspector.assert_invariants(&outcome);
+        assert!(inspector.opcode_refunds() > 0);
+    }
+
+    #[test]
+    fn test_check_reports_inconsistent_outcome() {
+        let code = BytecodeBuilder::default().stop();
+        let (inspector, mut outcome) = execute(MegaSpecId::REX4, code);
+        outcome.compute_gas_used = u64::MAX;
+        let violations = inspector.check(&outcome).unwrap_err();
+        assert!(violations
+            .iter()
+            .any(|v| matches!(v, InvariantViolation::ComputeGasExceedsExecutedGas { .. })));
+
+        assert_eq!(
+            InvariantInspector::new().check(&outcome).unwrap_err(),
+            vec![InvariantViolation::MissingTopLevelFrame]
+        );
+    }
+}
@@ -1,13 +1,25 @@
 //! Test utilities for the `MegaETH` EVM.
 
+mod beneficiary_vectors;
 mod bytes;
+mod chain;
 mod database;
 mod evm;
+mod forkable;
+mod golden;
 mod inspectors;
+mod invariant;
 mod opcode_gen;
+mod tx;
 
+pub use beneficiary_vectors::*;
 pub use bytes::*;
+pub use chain::*;
 pub use database::*;
 pub use evm::*;
+pub use forkable::*;
+pub use golden::*;
 pub use inspectors::*;
+pub use invariant::*;
 pub use opcode_gen::*;
+pub use tx::*;
@@ -2,29 +2,52 @@
 
 #[cfg(not(feature = "std"))]
 use alloc as std;
-use std::vec::Vec;
+use std::{collections::BTreeMap, format, string::String, vec::Vec};
 
 use alloy_primitives::{Address, Bytes, U256};
 use revm::bytecode::opcode::{
-    DUP1, EQ, INVALID, JUMPDEST, JUMPI, MSTORE, PUSH0, RETURN, REVERT, SSTORE, STOP,
+    CALL, DUP1, EQ, GAS, INVALID, ISZERO, JUMP, JUMPDEST, JUMPI, MSTORE, POP, PUSH0, PUSH2, RETURN,
+    REVERT, SSTORE, STOP, SUB, SWAP1,
 };
 
 use crate::test_utils::right_pad_bytes;
 
 /// A builder for assembling EVM bytecode.
+///
+/// Jump destinations are named with [`label`](Self::label) and targeted with
+/// [`jump`](Self::jump) and [`jumpi`](Self::jumpi), before or after the label is placed. The
+/// targets are resolved when the bytecode is built.
 #[derive(Debug, Default)]
 pub struct BytecodeBuilder {
     code: Vec<u8>,
+    /// The offset of the `JUMPDEST` of each label.
+    labels: BTreeMap<String, usize>,
+    /// The offset of the `PUSH2` immediate of each jump, with the label it targets.
+    jumps: Vec<(usize, String)>,
+    /// The number of labels generated by [`repeat`](Self::repeat).
+    generated_labels: usize,
 }
 
 impl BytecodeBuilder {
     /// Build the bytecode.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a jump targets a label that was never placed.
     pub fn build(self) -> Bytes {
-        self.code.into()
+        self.build_vec().into()
     }
 
     /// Build the bytecode as a vector.
-    pub fn build_vec(self) -> Vec<u8> {
+    ///
+    /// # Panics
+    ///
+    /// Panics if a jump targets a label that was never placed.
+    pub fn build_vec(mut self) -> Vec<u8> {
+        for (offset, label) in &self.jumps {
+            let target = self.labels.get(label).unwrap_or_else(|| panic!("unknown label {label}"));
+            self.code[*offset..*offset + 2].copy_from_slice(&(*target as u16).to_be_bytes());
+        }
         self.code
     }
 
@@ -156,6 +179,80 @@ impl BytecodeBuilder {
         self = self.append(STOP);
         self
     }
+
+    /// Append a JUMPDEST opcode and name its offset `label`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the label is already placed or the code is too large for a `PUSH2` target.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        let label = label.into();
+        assert!(self.code.len() <= u16::MAX as usize, "jump target out of PUSH2 range");
+        let previous = self.labels.insert(label.clone(), self.code.len());
+        assert!(previous.is_none(), "duplicate label {label}");
+        self.append(JUMPDEST)
+    }
+
+    /// Append a PUSH2 of the offset of `label`, which may be placed later.
+    fn push_label(mut self, label: impl Into<String>) -> Self {
+        self.code.push(PUSH2);
+        self.jumps.push((self.code.len(), label.into()));
+        self.code.extend([0, 0]);
+        self
+    }
+
+    /// Append an unconditional jump to `label`.
+    pub fn jump(self, label: impl Into<String>) -> Self {
+        self.push_label(label).append(JUMP)
+    }
+
+    /// Append a jump to `label` taken if the value on top of the stack, which is consumed, is
+    /// non-zero.
+    pub fn jumpi(self, label: impl Into<String>) -> Self {
+        self.push_label(label).append(JUMPI)
+    }
+
+    /// Append a loop running the code appended by `body` `times` times.
+    ///
+    /// The loop keeps its remaining iteration count on top of the stack while `body` runs, so the
+    /// body sees the values below it one position deeper and must leave the stack as it found it.
+    pub fn repeat(mut self, times: u64, body: impl FnOnce(Self) -> Self) -> Self {
+        let id = self.generated_labels;
+        self.generated_labels += 1;
+        let (start, end) = (format!("__repeat{id}_start"), format!("__repeat{id}_end"));
+        self = self
+            .push_number(times)
+            .label(start.clone())
+            .append_many([DUP1, ISZERO])
+            .jumpi(end.clone());
+        self = body(self);
+        self.push_number(1_u8).append_many([SWAP1, SUB]).jump(start).label(end).append(POP)
+    }
+
+    /// Append a CALL of `address` with `args` as calldata, forwarding all the gas and no value.
+    ///
+    /// The arguments are stored in memory from offset 0, the return data is not copied to memory,
+    /// and the success flag of the call is left on the stack.
+    pub fn call_contract(mut self, address: Address, args: impl AsRef<[u8]>) -> Self {
+        let args_len = args.as_ref().len();
+        if args_len > 0 {
+            self = self.mstore(0x0, args);
+        }
+        self.append_many([PUSH0, PUSH0])
+            .push_number(args_len as u64)
+            .append_many([PUSH0, PUSH0])
+            .push_address(address)
+            .append_many([GAS, CALL])
+    }
+
+    /// Append an assembly snippet that consumes the two values on top of the stack and calls the
+    /// INVALID opcode if they are not equal.
+    pub fn assert_eq(mut self) -> Self {
+        let id = self.generated_labels;
+        self.generated_labels += 1;
+        let equal = format!("__assert_eq{id}");
+        self.append(EQ).jumpi(equal.clone()).append(INVALID).label(equal)
+    }
 }
 
 #[cfg(test)]
@@ -169,11 +266,12 @@ mod tests {
     };
 
     use crate::{
-        test_utils::MemoryDatabase, MegaContext, MegaEvm, MegaHaltReason, MegaSpecId,
-        MegaTransaction, MegaTransactionError,
+        test_utils::{context_without_operator_fee, MemoryDatabase},
+        MegaEvm, MegaHaltReason, MegaSpecId, MegaTransaction, MegaTransactionError,
     };
 
     use super::*;
+    use revm::bytecode::opcode::{ADD, SWAP2};
 
     fn execute_bytecode(
         bytecode: Bytes,
@@ -181,11 +279,7 @@ mod tests {
         let contract = address!("0000000000000000000000000000000000100001");
         let mut db = MemoryDatabase::default();
         db.set_account_code(contract, bytecode);
-        let mut context = MegaContext::new(&mut db, MegaSpecId::MINI_REX);
-        context.modify_chain(|chain| {
-            chain.operator_fee_scalar = Some(U256::from(0));
-            chain.operator_fee_constant = Some(U256::from(0));
-        });
+        let context = context_without_operator_fee(&mut db, MegaSpecId::MINI_REX);
         let mut evm = MegaEvm::new(context);
         let tx = TxEnvBuilder::default().call(contract).gas_limit(1_000_000_000).build_fill();
         let mut tx = MegaTransaction::new(tx);
@@ -202,6 +296,55 @@ mod tests {
         assert!(result.unwrap().result.is_success(), "Transaction should succeed");
     }
 
+    #[test]
+    fn test_repeat_runs_body_the_given_times() {
+        for (times, expected, success) in [(5, 15, true), (0, 0, true), (5, 12, false)] {
+            let bytecode = BytecodeBuilder::default()
+                .append(PUSH0)
+                .repeat(times, |b| b.append(SWAP1).push_number(3_u8).append(ADD).append(SWAP1))
+                .push_number(expected as u64)
+                .assert_eq()
+                .stop()
+                .build();
+            let result = execute_bytecode(bytecode).unwrap().result;
+            assert_eq!(result.is_success(), success, "{times} times, expected {expected}");
+        }
+    }
+
+    #[test]
+    fn test_nested_repeat_and_forward_jump() {
+        let bytecode = BytecodeBuilder::default()
+            .append(PUSH0)
+            .repeat(3, |b| {
+                b.repeat(4, |b| b.append(SWAP2).push_number(1_u8).append(ADD).append(SWAP2))
+            })
+            .push_number(12_u64)
+            .assert_eq()
+            .jump("end")
+            .append(INVALID)
+            .label("end")
+            .stop()
+            .build();
+        assert!(execute_bytecode(bytecode).unwrap().result.is_success());
+    }
+
+    #[test]
+    fn test_call_contract_leaves_success_flag() {
+        let bytecode = BytecodeBuilder::default()
+            .call_contract(address!("0000000000000000000000000000000000100002"), [0xab; 40])
+            .push_number(1_u8)
+            .assert_eq()
+            .stop()
+            .build();
+        assert!(execute_bytecode(bytecode).unwrap().result.is_success());
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown label missing")]
+    fn test_build_panics_on_unknown_label() {
+        BytecodeBuilder::default().jump("missing").build();
+    }
+
     #[test]
     fn test_assert_stack_value_failure() {
         let mut builder = BytecodeBuilder::default().push_number(0x2333u64);
@@ -0,0 +1,311 @@
+#[cfg(not(feature = "std"))]
+use alloc as std;
+use std::vec::Vec;
+
+use alloy_eips::eip7702::{
+    Authorization, RecoveredAuthority, RecoveredAuthorization, SignedAuthorization,
+};
+use alloy_primitives::{Address, Bytes, Signature, B256, U256};
+use k256::ecdsa::SigningKey;
+use op_revm::transaction::deposit::DepositTransactionParts;
+use revm::context::tx::TxEnvBuilder;
+
+use crate::{MegaTransaction, MEGA_SYSTEM_ADDRESS};
+
+/// Default gas limit of transactions built by [`MegaTxBuilder`].
+pub const DEFAULT_TEST_TX_GAS_LIMIT: u64 = 100_000_000;
+
+/// Returns the address of the account controlled by the test secret key `secret_key`.
+///
+/// Any non-zero value below the secp256k1 curve order, e.g. `B256::with_last_byte(1)`, is a valid
+/// secret key.
+///
+/// # Panics
+///
+/// Panics if `secret_key` is not a valid secret key.
+pub fn test_key_address(secret_key: B256) -> Address {
+    Address::from_private_key(&signing_key(secret_key))
+}
+
+/// Signs an EIP-7702 authorization with the test secret key `secret_key`, so that its authority
+/// recovers to [`test_key_address`] of that key.
+///
+/// # Panics
+///
+/// Panics if `secret_key` is not a valid secret key.
+pub fn sign_authorization(secret_key: B256, authorization: Authorization) -> SignedAuthorization {
+    let (signature, recovery_id) = signing_key(secret_key)
+        .sign_prehash_recoverable(authorization.signature_hash().as_slice())
+        .expect("signing a 32-byte prehash cannot fail");
+    authorization
+        .into_signed(Signature::from_signature_and_parity(signature, recovery_id.is_y_odd()))
+}
+
+fn signing_key(secret_key: B256) -> SigningKey {
+    SigningKey::from_slice(secret_key.as_slice()).expect("valid secret key")
+}
+
+/// A fluent builder for [`MegaTransaction`]s with test-friendly defaults.
+///
+/// Defaults to a zero-fee call with a [`DEFAULT_TEST_TX_GAS_LIMIT`] gas limit.
+/// The transaction type is derived from the fields that are set, and the
+/// enveloped bytes default to empty so no L1 data fee is charged.
+#[derive(Debug)]
+pub struct MegaTxBuilder {
+    base: TxEnvBuilder,
+    chain_id: u64,
+    authorizations: Vec<RecoveredAuthorization>,
+    deposit: DepositTransactionParts,
+    enveloped_tx: Bytes,
+}
+
+impl Default for MegaTxBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MegaTxBuilder {
+    /// Creates a builder with the default settings.
+    pub fn new() -> Self {
+        Self {
+            base: TxEnvBuilder::new().gas_limit(DEFAULT_TEST_TX_GAS_LIMIT).gas_price(0),
+            chain_id: 1,
+            authorizations: Vec::new(),
+            deposit: DepositTransactionParts::default(),
+            enveloped_tx: Bytes::new(),
+        }
+    }
+
+    /// Creates a builder for a `MegaETH` system transaction: a legacy call from
+    /// [`MEGA_SYSTEM_ADDRESS`] to `to`, which must be on the system-transaction whitelist to be
+    /// treated as one.
+    pub fn system_tx(to: Address) -> Self {
+        Self::new().caller(MEGA_SYSTEM_ADDRESS).call(to)
+    }
+
+    /// Sets the sender.
+    pub fn caller(mut self, caller: Address) -> Self {
+        self.base = self.base.caller(caller);
+        self
+    }
+
+    /// Makes the transaction a call to `to`.
+    pub fn call(mut self, to: Address) -> Self {
+        self.base = self.base.call(to);
+        self
+    }
+
+    /// Makes the transaction a contract creation.
+    pub fn create(mut self) -> Self {
+        self.base = self.base.create();
+        self
+    }
+
+    /// Sets the calldata (or initcode for a creation).
+    pub fn data(mut self, data: impl Into<Bytes>) -> Self {
+        self.base = self.base.data(data.into());
+        self
+    }
+
+    /// Sets the transferred value.
+    pub fn value(mut self, value: U256) -> Self {
+        self.base = self.base.value(value);
+        self
+    }
+
+    /// Sets the gas limit.
+    pub fn gas_limit(mut self, gas_limit: u64) -> Self {
+        self.base = self.base.gas_limit(gas_limit);
+        self
+    }
+
+    /// Sets the gas price (the max fee per gas for EIP-1559 style transactions).
+    pub fn gas_price(mut self, gas_price: u128) -> Self {
+        self.base = self.base.gas_price(gas_price);
+        self
+    }
+
+    /// Sets the priority fee, making the transaction EIP-1559 style.
+    pub fn gas_priority_fee(mut self, fee: u128) -> Self {
+        self.base = self.base.gas_priority_fee(Some(fee));
+        self
+    }
+
+    /// Sets the sender nonce.
+    pub fn nonce(mut self, nonce: u64) -> Self {
+        self.base = self.base.nonce(nonce);
+        self
+    }
+
+    /// Sets the chain id of the transaction and of authorizations added afterwards.
+    pub fn chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self.base = self.base.chain_id(Some(chain_id));
+        self
+    }
+
+    /// Adds an EIP-7702 authorization by `authority` delegating to `delegate`, signed for the
+    /// builder's chain id.
+    ///
+    /// The authority is taken as already recovered, so no signature is needed.
+    pub fn authorization(self, authority: Address, delegate: Address, nonce: u64) -> Self {
+        let chain_id = U256::from(self.chain_id);
+        self.authorization_with(
+            Authorization { chain_id, address: delegate, nonce },
+            RecoveredAuthority::Valid(authority),
+        )
+    }
+
+    /// Adds an EIP-7702 authorization delegating to `delegate`, signed with the test secret key
+    /// `secret_key` for the builder's chain id.
+    ///
+    /// The authority is recovered from the signature, as a node would.
+    pub fn signed_authorization(mut self, secret_key: B256, delegate: Address, nonce: u64) -> Self {
+        let chain_id = U256::from(self.chain_id);
+        let authorization = Authorization { chain_id, address: delegate, nonce };
+        self.authorizations.push(sign_authorization(secret_key, authorization).into_recovered());
+        self
+    }
+
+    /// Adds an EIP-7702 authorization with an explicit authority recovery result, e.g.
+    /// [`RecoveredAuthority::Invalid`] to model an unrecoverable signature.
+    pub fn authorization_with(
+        mut self,
+        authorization: Authorization,
+        authority: RecoveredAuthority,
+    ) -> Self {
+        self.authorizations.push(RecoveredAuthorization::new_unchecked(authorization, authority));
+        self
+    }
+
+    /// Makes the transaction an OP deposit with the given source hash and minted value.
+    ///
+    /// A zero `source_hash` is replaced by a non-zero placeholder, since only a non-zero source
+    /// hash marks a transaction as a deposit.
+    pub fn deposit(mut self, source_hash: B256, mint: u128) -> Self {
+        self.deposit.source_hash =
+            if source_hash.is_zero() { B256::with_last_byte(1) } else { source_hash };
+        self.deposit.mint = Some(mint);
+        self
+    }
+
+    /// Marks a deposit as an OP system transaction.
+    pub fn system_deposit(mut self) -> Self {
+        self.deposit.is_system_transaction = true;
+        self
+    }
+
+    /// Sets the enveloped (RLP-encoded) transaction bytes used for the L1 data fee.
+    pub fn enveloped_tx(mut self, enveloped_tx: impl Into<Bytes>) -> Self {
+        self.enveloped_tx = enveloped_tx.into();
+        self
+    }
+
+    /// Builds the transaction, deriving its type from the fields that are set.
+    pub fn build(self) -> MegaTransaction {
+        let mut base = self.base;
+        if !self.authorizations.is_empty() {
+            base = base.authorization_list_recovered(self.authorizations);
+        }
+        let mut tx = MegaTransaction::new(base.build_fill());
+        tx.deposit = self.deposit;
+        tx.enveloped_tx = Some(self.enveloped_tx);
+        tx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        is_deposit_like_transaction, is_mega_system_transaction_with,
+        test_utils::{context_without_operator_fee, MemoryDatabase},
+        MegaEvm, MegaSpecId, ORACLE_CONTRACT_ADDRESS,
+    };
+    use alloy_primitives::address;
+    use op_revm::transaction::deposit::DEPOSIT_TRANSACTION_TYPE;
+    use revm::{context::Transaction, ExecuteEvm};
+
+    const CALLER: Address = address!("0000000000000000000000000000000000100000");
+    const CALLEE: Address = address!("0000000000000000000000000000000000100001");
+
+    #[test]
+    fn test_default_tx_is_free_legacy_call() {
+        let tx = MegaTxBuilder::new().caller(CALLER).call(CALLEE).build();
+        assert_eq!(tx.tx_type(), 0);
+        assert_eq!(tx.caller(), CALLER);
+        assert_eq!(tx.base.gas_limit, DEFAULT_TEST_TX_GAS_LIMIT);
+        assert_eq!(tx.base.gas_price, 0);
+        assert_eq!(tx.enveloped_tx, Some(Bytes::new()));
+    }
+
+    #[test]
+    fn test_authorization_makes_eip7702_tx() {
+        let delegate = address!("0000000000000000000000000000000000100002");
+        let tx = MegaTxBuilder::new()
+            .caller(CALLER)
+            .call(CALLEE)
+            .chain_id(6342)
+            .authorization(CALLER, delegate, 0)
+            .build();
+        assert_eq!(tx.tx_type(), 4);
+        assert_eq!(tx.authorization_list_len(), 1);
+        let auth = tx.base.authorization_list[0].as_ref().right().unwrap().inner();
+        assert_eq!(auth.chain_id, U256::from(6342));
+        assert_eq!(auth.address, delegate);
+    }
+
+    #[test]
+    fn test_signed_authorization_recovers_key_address() {
+        let key = B256::with_last_byte(1);
+        let delegate = address!("0000000000000000000000000000000000100002");
+        // The well-known address of secret key 1.
+        assert_eq!(test_key_address(key), address!("7e5f4552091a69125d5dfcb7b8c2659029395bdf"));
+
+        let authorization =
+            Authorization { chain_id: U256::from(6342), address: delegate, nonce: 3 };
+        let signed = sign_authorization(key, authorization.clone());
+        assert_eq!(signed.recover_authority().unwrap(), test_key_address(key));
+        assert_eq!(signed.strip_signature(), authorization);
+
+        let tx = MegaTxBuilder::new()
+            .caller(CALLER)
+            .call(CALLEE)
+            .chain_id(6342)
+            .signed_authorization(key, delegate, 3)
+            .build();
+        let auth = tx.base.authorization_list[0].as_ref().right().unwrap();
+        assert_eq!(auth.authority(), Some(test_key_address(key)));
+    }
+
+    #[test]
+    fn test_signed_authorization_installs_delegation() {
+        let key = B256::with_last_byte(2);
+        let authority = test_key_address(key);
+        let delegate = address!("0000000000000000000000000000000000100002");
+        let mut db = MemoryDatabase::default();
+        let context = context_without_operator_fee(&mut db, MegaSpecId::REX4);
+        let tx = MegaTxBuilder::new()
+            .caller(CALLER)
+            .call(CALLEE)
+            .signed_authorization(key, delegate, 0)
+            .build();
+        let result = MegaEvm::new(context).transact(tx).unwrap();
+        assert!(result.result.is_success());
+        let code = result.state[&authority].info.code.clone().unwrap();
+        assert_eq!(code.eip7702_address(), Some(delegate));
+    }
+
+    #[test]
+    fn test_deposit_and_system_tx() {
+        let deposit =
+            MegaTxBuilder::new().caller(CALLER).call(CALLEE).deposit(B256::ZERO, 7).build();
+        assert_eq!(deposit.tx_type(), DEPOSIT_TRANSACTION_TYPE);
+        assert_eq!(deposit.deposit.mint, Some(7));
+
+        let system = MegaTxBuilder::system_tx(ORACLE_CONTRACT_ADDRESS).build();
+        assert!(is_mega_system_transaction_with(&system, MEGA_SYSTEM_ADDRESS));
+        assert!(is_deposit_like_transaction(&system, MEGA_SYSTEM_ADDRESS));
+    }
+}
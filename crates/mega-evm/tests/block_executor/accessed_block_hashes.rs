@@ -1,7 +1,7 @@
 //! Tests for block hash access tracking on `MegaBlockExecutor`.
 //!
-//! `get_accessed_block_hashes` exposes the executor database's block hash record,
-//! which accumulates across every transaction executed so far.
+//! `get_accessed_block_hashes` exposes the executor's block hash record, which
+//! accumulates across every transaction executed so far.
 //! `clear_accessed_block_hashes` resets it so callers can attribute BLOCKHASH
 //! reads to a single transaction (the replay fixture dump relies on this).
 
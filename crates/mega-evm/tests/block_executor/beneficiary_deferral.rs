@@ -0,0 +1,165 @@
+//! Tests for deferring the beneficiary reward to the end of the block.
+//!
+//! With deferral enabled, no transaction writes the block beneficiary; the accumulated reward is
+//! credited once in the post-execution changes. The final block state must match the one produced
+//! by crediting the beneficiary after every transaction.
+
+use std::convert::Infallible;
+
+use alloy_consensus::{transaction::Recovered, Signed, TxLegacy};
+use alloy_evm::{block::BlockExecutor, EvmEnv, EvmFactory};
+use alloy_op_evm::block::receipt_builder::OpAlloyReceiptBuilder;
+use alloy_primitives::{address, Address, Bytes, Signature, TxKind, B256, U256};
+use mega_evm::{
+    test_utils::{BytecodeBuilder, MemoryDatabase},
+    BlockLimits, MegaBlockExecutionCtx, MegaBlockExecutor, MegaEvmFactory, MegaHardfork,
+    MegaHardforkConfig, MegaSpecId, MegaTxEnvelope, TestExternalEnvs,
+};
+use op_revm::constants::{BASE_FEE_RECIPIENT, L1_FEE_RECIPIENT, OPERATOR_FEE_RECIPIENT};
+use revm::{
+    bytecode::opcode::{BALANCE, COINBASE, PUSH0, SSTORE},
+    context::BlockEnv,
+    database::State,
+    handler::EvmTr,
+    Database as _,
+};
+
+const CALLER: Address = address!("2000000000000000000000000000000000000002");
+const CONTRACT: Address = address!("1000000000000000000000000000000000000001");
+const BENEFICIARY: Address = address!("3000000000000000000000000000000000000003");
+/// Stores the beneficiary's balance in slot 0.
+const READER: Address = address!("1000000000000000000000000000000000000002");
+const BASE_FEE: u64 = 10;
+
+fn create_transaction(nonce: u64, to: Address) -> Recovered<MegaTxEnvelope> {
+    let tx_legacy = TxLegacy {
+        chain_id: Some(8453),
+        nonce,
+        gas_price: 1_000_000,
+        gas_limit: 1_000_000,
+        to: TxKind::Call(to),
+        value: U256::ZERO,
+        input: Bytes::new(),
+    };
+    let signed = Signed::new_unchecked(tx_legacy, Signature::test_signature(), Default::default());
+    Recovered::new_unchecked(MegaTxEnvelope::Legacy(signed), CALLER)
+}
+
+/// Final state observed by the tests.
+#[derive(Debug, PartialEq, Eq)]
+struct BlockOutcome {
+    /// Balances of the caller, the beneficiary and the fee vaults.
+    balances: Vec<U256>,
+    /// Slot 0 of [`READER`].
+    observed_beneficiary_balance: U256,
+    /// Gas used by each transaction.
+    gas_used: Vec<u64>,
+}
+
+/// Executes a block with one transaction to each of `targets`.
+fn execute_block(targets: &[Address], defer: bool) -> BlockOutcome {
+    let mut db = MemoryDatabase::default();
+    // Sets a storage slot on the first transaction and rewrites it on the next ones.
+    db.set_account_code(
+        CONTRACT,
+        BytecodeBuilder::default().push_number(1_u8).append(PUSH0).append(SSTORE).stop().build(),
+    );
+    db.set_account_code(
+        READER,
+        BytecodeBuilder::default()
+            .append(COINBASE)
+            .append(BALANCE)
+            .append(PUSH0)
+            .append(SSTORE)
+            .stop()
+            .build(),
+    );
+    db.set_account_balance(CALLER, U256::from(1_000_000_000_000_000_000u128));
+
+    let mut state = State::builder().with_database(&mut db).build();
+    let evm_factory =
+        MegaEvmFactory::new().with_external_env_factory(TestExternalEnvs::<Infallible>::new());
+    let mut cfg_env = revm::context::CfgEnv::default();
+    cfg_env.spec = MegaSpecId::REX4;
+    let block_env = BlockEnv {
+        number: U256::from(1000),
+        beneficiary: BENEFICIARY,
+        timestamp: U256::from(1_800_000_000),
+        gas_limit: 30_000_000,
+        basefee: BASE_FEE,
+        ..Default::default()
+    };
+    let mut evm = evm_factory.create_evm(&mut state, EvmEnv::new(cfg_env, block_env));
+    if defer {
+        evm.ctx_mut().defer_beneficiary_reward();
+    }
+
+    let block_ctx =
+        MegaBlockExecutionCtx::new(B256::ZERO, None, Bytes::new(), BlockLimits::no_limits());
+    let chain_spec = MegaHardforkConfig::default()
+        .with_all_activated()
+        .without(MegaHardfork::Rex5)
+        .without(MegaHardfork::Rex6);
+    let mut executor =
+        MegaBlockExecutor::new(evm, block_ctx, chain_spec, OpAlloyReceiptBuilder::default());
+    executor.apply_pre_execution_changes().expect("pre-execution changes should succeed");
+
+    let mut gas_used = Vec::new();
+    for (nonce, target) in targets.iter().enumerate() {
+        let tx = create_transaction(nonce as u64, *target);
+        let outcome = executor.run_transaction(&tx).expect("transaction should execute");
+        assert!(outcome.result.is_success(), "unexpected result: {:?}", outcome.result);
+        if *target == CONTRACT {
+            assert_eq!(
+                outcome.state.contains_key(&BENEFICIARY),
+                !defer,
+                "only an undeferred reward writes the beneficiary per transaction"
+            );
+        }
+        gas_used.push(executor.commit_execution_outcome(outcome).expect("commit should succeed"));
+    }
+    let (evm, _) = executor.finish().expect("finish should succeed");
+    assert_eq!(evm.ctx_ref().deferred_beneficiary_reward(), defer.then_some(U256::ZERO));
+    drop(evm);
+
+    let balances =
+        [CALLER, BENEFICIARY, BASE_FEE_RECIPIENT, L1_FEE_RECIPIENT, OPERATOR_FEE_RECIPIENT]
+            .into_iter()
+            .map(|address| {
+                state.basic(address).expect("account should load").unwrap_or_default().balance
+            })
+            .collect();
+    let observed_beneficiary_balance =
+        state.storage(READER, U256::ZERO).expect("storage should load");
+    BlockOutcome { balances, observed_beneficiary_balance, gas_used }
+}
+
+#[test]
+fn test_deferred_beneficiary_reward_matches_per_transaction_reward() {
+    let targets = [CONTRACT; 3];
+    let outcome = execute_block(&targets, false);
+    assert_eq!(execute_block(&targets, true), outcome);
+
+    let tip = U256::from(1_000_000 - BASE_FEE);
+    let expected_reward: U256 = outcome.gas_used.iter().map(|gas| U256::from(*gas) * tip).sum();
+    assert_eq!(outcome.balances[1], expected_reward, "beneficiary receives the priority fees");
+}
+
+#[test]
+fn test_beneficiary_access_sees_deferred_reward() {
+    let targets = [CONTRACT, CONTRACT, READER, CONTRACT];
+    let outcome = execute_block(&targets, false);
+    assert_eq!(execute_block(&targets, true), outcome);
+
+    let tip = U256::from(1_000_000 - BASE_FEE);
+    let earlier_reward =
+        outcome.gas_used[..2].iter().map(|gas| U256::from(*gas) * tip).sum::<U256>();
+    assert_eq!(outcome.observed_beneficiary_balance, earlier_reward);
+}
+
+#[test]
+fn test_deferred_beneficiary_reward_with_empty_block() {
+    let outcome = execute_block(&[], false);
+    assert_eq!(execute_block(&[], true), outcome);
+    assert_eq!(outcome.balances[1], U256::ZERO);
+}
@@ -0,0 +1,162 @@
+//! Tests for the block-level summary of volatile data accesses on `MegaBlockExecutor`.
+//!
+//! `get_block_env_access_summary` aggregates the accesses of the committed transactions only;
+//! a transaction whose outcome is discarded leaves the summary unchanged. The summary also
+//! commits to the detention exemption set the block was executed with.
+
+use std::convert::Infallible;
+
+use alloy_consensus::{transaction::Recovered, Signed, TxLegacy};
+use alloy_evm::{
+    block::{BlockExecutor, CommitChanges},
+    EvmEnv, EvmFactory,
+};
+use alloy_hardforks::ForkCondition;
+use alloy_op_evm::block::receipt_builder::OpAlloyReceiptBuilder;
+use alloy_primitives::{address, Address, Bytes, Signature, TxKind, B256, U256};
+use mega_evm::{
+    test_utils::{BytecodeBuilder, MemoryDatabase},
+    BlockEnvAccessSummary, BlockLimits, DetentionExemptions, MegaBlockExecutionCtx,
+    MegaBlockExecutor, MegaEvmFactory, MegaHardfork, MegaHardforkConfig, MegaSpecId,
+    MegaTxEnvelope, TestExternalEnvs, VolatileDataAccess,
+};
+use revm::{
+    bytecode::opcode::{COINBASE, NUMBER, POP, TIMESTAMP},
+    context::BlockEnv,
+    database::State,
+};
+
+const CALLER: Address = address!("2000000000000000000000000000000000000002");
+const CLOCK_CONTRACT: Address = address!("1000000000000000000000000000000000000001");
+const COINBASE_CONTRACT: Address = address!("1000000000000000000000000000000000000002");
+const PLAIN_CONTRACT: Address = address!("1000000000000000000000000000000000000003");
+
+fn create_transaction(nonce: u64, to: Address) -> Recovered<MegaTxEnvelope> {
+    let tx_legacy = TxLegacy {
+        chain_id: Some(8453),
+        nonce,
+        gas_price: 1_000_000,
+        gas_limit: 1_000_000,
+        to: TxKind::Call(to),
+        value: U256::ZERO,
+        input: Bytes::new(),
+    };
+    let signed = Signed::new_unchecked(tx_legacy, Signature::test_signature(), Default::default());
+    Recovered::new_unchecked(MegaTxEnvelope::Legacy(signed), CALLER)
+}
+
+#[test]
+fn test_block_env_access_summary_counts_committed_transactions() {
+    let mut db = MemoryDatabase::default();
+    db.set_account_code(
+        CLOCK_CONTRACT,
+        BytecodeBuilder::default()
+            .append(TIMESTAMP)
+            .append(POP)
+            .append(NUMBER)
+            .append(POP)
+            .stop()
+            .build(),
+    );
+    db.set_account_code(
+        COINBASE_CONTRACT,
+        BytecodeBuilder::default().append(COINBASE).append(POP).stop().build(),
+    );
+    db.set_account_code(PLAIN_CONTRACT, BytecodeBuilder::default().stop().build());
+    db.set_account_balance(CALLER, U256::from(1_000_000_000_000_000u64));
+
+    let mut state = State::builder().with_database(&mut db).build();
+    let evm_factory =
+        MegaEvmFactory::new().with_external_env_factory(TestExternalEnvs::<Infallible>::new());
+    let mut cfg_env = revm::context::CfgEnv::default();
+    cfg_env.spec = MegaSpecId::MINI_REX;
+    let block_env = BlockEnv {
+        number: U256::from(1000),
+        timestamp: U256::from(1_800_000_000),
+        gas_limit: 30_000_000,
+        ..Default::default()
+    };
+    let evm = evm_factory.create_evm(&mut state, EvmEnv::new(cfg_env, block_env));
+    let block_ctx =
+        MegaBlockExecutionCtx::new(B256::ZERO, None, Bytes::new(), BlockLimits::no_limits());
+    let chain_spec =
+        MegaHardforkConfig::default().with(MegaHardfork::MiniRex, ForkCondition::Timestamp(0));
+    let mut executor =
+        MegaBlockExecutor::new(evm, block_ctx, chain_spec, OpAlloyReceiptBuilder::default());
+    assert_eq!(executor.get_block_env_access_summary(), BlockEnvAccessSummary::default());
+
+    for (nonce, to) in [(0, CLOCK_CONTRACT), (1, PLAIN_CONTRACT), (2, CLOCK_CONTRACT)] {
+        executor.execute_transaction(&create_transaction(nonce, to)).expect("tx should succeed");
+    }
+    // A discarded outcome is not counted.
+    let committed = executor
+        .execute_transaction_with_commit_condition(
+            &create_transaction(3, COINBASE_CONTRACT),
+            |_| CommitChanges::No,
+        )
+        .expect("tx should execute");
+    assert_eq!(committed, None);
+
+    let summary = executor.get_block_env_access_summary();
+    assert_eq!(summary.txs, 3);
+    assert_eq!(summary.volatile_txs, 2);
+    assert_eq!(summary.timestamp_txs, 2);
+    assert_eq!(summary.number_txs, 2);
+    assert_eq!(summary.coinbase_txs, 0);
+    assert_eq!(summary.accessed, VolatileDataAccess::TIMESTAMP | VolatileDataAccess::BLOCK_NUMBER);
+    assert_eq!(summary.max_detained_gas, 0);
+
+    executor
+        .execute_transaction(&create_transaction(3, COINBASE_CONTRACT))
+        .expect("tx should succeed");
+    let summary = executor.get_block_env_access_summary();
+    assert_eq!((summary.txs, summary.volatile_txs, summary.coinbase_txs), (4, 3, 1));
+}
+
+/// Executes a block calling [`CLOCK_CONTRACT`] with `exemptions` and returns its summary.
+fn clock_block_summary(exemptions: DetentionExemptions) -> BlockEnvAccessSummary {
+    let mut db = MemoryDatabase::default();
+    db.set_account_code(
+        CLOCK_CONTRACT,
+        BytecodeBuilder::default().append(TIMESTAMP).append(POP).stop().build(),
+    );
+    db.set_account_balance(CALLER, U256::from(1_000_000_000_000_000u64));
+
+    let mut state = State::builder().with_database(&mut db).build();
+    let evm_factory = MegaEvmFactory::new()
+        .with_external_env_factory(TestExternalEnvs::<Infallible>::new())
+        .with_detention_exemptions(exemptions);
+    let mut cfg_env = revm::context::CfgEnv::default();
+    cfg_env.spec = MegaSpecId::MINI_REX;
+    let block_env = BlockEnv {
+        number: U256::from(1000),
+        timestamp: U256::from(1_800_000_000),
+        gas_limit: 30_000_000,
+        ..Default::default()
+    };
+    let evm = evm_factory.create_evm(&mut state, EvmEnv::new(cfg_env, block_env));
+    let block_ctx =
+        MegaBlockExecutionCtx::new(B256::ZERO, None, Bytes::new(), BlockLimits::no_limits());
+    let chain_spec =
+        MegaHardforkConfig::default().with(MegaHardfork::MiniRex, ForkCondition::Timestamp(0));
+    let mut executor =
+        MegaBlockExecutor::new(evm, block_ctx, chain_spec, OpAlloyReceiptBuilder::default());
+    executor
+        .execute_transaction(&create_transaction(0, CLOCK_CONTRACT))
+        .expect("tx should succeed");
+    executor.get_block_env_access_summary()
+}
+
+#[test]
+fn test_block_env_access_summary_commits_to_detention_exemptions() {
+    let exemptions = DetentionExemptions::new([CLOCK_CONTRACT]);
+    let exempt = clock_block_summary(exemptions.clone());
+    assert_eq!(exempt.detention_exemptions_hash, exemptions.hash());
+    assert_eq!(clock_block_summary(exemptions), exempt, "the same set must replay identically");
+
+    // The exemption does not hide the access, so only the hash tells the replays apart.
+    let replay = clock_block_summary(DetentionExemptions::default());
+    assert_eq!(replay.detention_exemptions_hash, DetentionExemptions::EMPTY_HASH);
+    assert_eq!(replay.accessed, exempt.accessed);
+    assert_ne!(replay, exempt, "a replay with another set must be detected");
+}
@@ -0,0 +1,92 @@
+//! Tests for how the block executor accounts transaction data sizes against the block
+//! transactions data limit (`BlockLimits::data_size_accounting`).
+
+use std::{
+    convert::Infallible,
+    sync::{Arc, Mutex},
+};
+
+use alloy_consensus::{transaction::Recovered, Signed, TxLegacy};
+use alloy_evm::{block::BlockExecutor, EvmEnv, EvmFactory};
+use alloy_op_evm::block::receipt_builder::OpAlloyReceiptBuilder;
+use alloy_primitives::{address, Address, Bytes, Signature, TxKind, B256, U256};
+use mega_evm::{
+    test_utils::MemoryDatabase, BlockLimits, DataSizeAccounting, MegaBlockExecutionCtx,
+    MegaBlockExecutor, MegaEvmFactory, MegaHardforkConfig, MegaSpecId, MegaTxEnvelope,
+    StreamedReceipt, TestExternalEnvs,
+};
+use op_alloy_consensus::OpReceiptEnvelope;
+use revm::{context::BlockEnv, database::State};
+
+const ALICE: Address = address!("2000000000000000000000000000000000000002");
+const RECIPIENT: Address = address!("1000000000000000000000000000000000000001");
+
+/// A transaction whose calldata compresses well, so its flz-estimated size is far below its raw
+/// size.
+fn create_transaction() -> Recovered<MegaTxEnvelope> {
+    let tx_legacy = TxLegacy {
+        chain_id: Some(8453),
+        nonce: 0,
+        gas_price: 0,
+        gas_limit: 1_000_000,
+        to: TxKind::Call(RECIPIENT),
+        value: U256::ZERO,
+        input: Bytes::from(vec![0u8; 2_000]),
+    };
+    let signed = Signed::new_unchecked(tx_legacy, Signature::test_signature(), Default::default());
+    Recovered::new_unchecked(MegaTxEnvelope::Legacy(signed), ALICE)
+}
+
+/// Executes the transaction in a block with the given `accounting` and returns the data size
+/// streamed for the transaction and the data size used by the block.
+fn execute(accounting: DataSizeAccounting) -> (u64, u64) {
+    let mut db = MemoryDatabase::default();
+    db.set_account_balance(ALICE, U256::from(1_000_000_000_000_000_000u128));
+
+    let mut state = State::builder().with_database(&mut db).build();
+    let evm_factory =
+        MegaEvmFactory::new().with_external_env_factory(TestExternalEnvs::<Infallible>::new());
+    let mut cfg_env = revm::context::CfgEnv::default();
+    cfg_env.spec = MegaSpecId::REX4;
+    let block_env = BlockEnv {
+        number: U256::from(1000),
+        timestamp: U256::from(1_800_000_000),
+        gas_limit: 30_000_000,
+        ..Default::default()
+    };
+    let evm = evm_factory.create_evm(&mut state, EvmEnv::new(cfg_env, block_env));
+    let block_ctx = MegaBlockExecutionCtx::new(
+        B256::ZERO,
+        None,
+        Bytes::new(),
+        BlockLimits::no_limits().with_data_size_accounting(accounting),
+    );
+    let chain_spec = MegaHardforkConfig::default().with_all_activated();
+
+    let streamed = Arc::new(Mutex::new(None));
+    let sink = streamed.clone();
+    let mut executor =
+        MegaBlockExecutor::new(evm, block_ctx, chain_spec, OpAlloyReceiptBuilder::default())
+            .with_receipt_hook(move |receipt: StreamedReceipt<'_, OpReceiptEnvelope>| {
+                *sink.lock().unwrap() =
+                    Some((receipt.usage.data_size, receipt.block_usage.block_data_used));
+            });
+    executor.apply_pre_execution_changes().expect("pre-execution changes should succeed");
+    executor.execute_transaction(&create_transaction()).expect("transaction should succeed");
+
+    let usage = streamed.lock().unwrap().take();
+    usage.expect("the receipt is streamed")
+}
+
+/// The block accounts the raw data size by default and the DA-estimated one under
+/// `DataSizeAccounting::DaEstimated`, and the streamed usage follows the same accounting.
+#[test]
+fn test_block_data_usage_follows_data_size_accounting() {
+    let (raw_tx, raw_block) = execute(DataSizeAccounting::Raw);
+    assert_eq!(raw_tx, raw_block);
+    assert!(raw_tx > 2_000, "the raw data size counts every calldata byte");
+
+    let (da_tx, da_block) = execute(DataSizeAccounting::DaEstimated);
+    assert_eq!(da_tx, da_block);
+    assert!(da_tx < raw_tx, "zero calldata compresses, so its DA estimate is below its raw size");
+}
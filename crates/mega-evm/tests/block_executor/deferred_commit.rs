@@ -0,0 +1,124 @@
+//! Tests for recording the state mutations of a block for a deferred state commitment.
+
+use std::convert::Infallible;
+
+use alloy_consensus::{transaction::Recovered, Signed, TxLegacy};
+use alloy_evm::{
+    block::{BlockExecutor, CommitChanges, StateChangeSource},
+    EvmEnv, EvmFactory,
+};
+use alloy_op_evm::block::receipt_builder::OpAlloyReceiptBuilder;
+use alloy_primitives::{address, Address, Bytes, Signature, TxKind, B256, U256};
+use mega_evm::{
+    test_utils::MemoryDatabase, BlockLimits, MegaBlockExecutionCtx, MegaBlockExecutor,
+    MegaEvmFactory, MegaHardfork, MegaHardforkConfig, MegaSpecId, MegaTxEnvelope, TestExternalEnvs,
+};
+use revm::{context::BlockEnv, database::State, Database as _};
+
+const ALICE: Address = address!("2000000000000000000000000000000000000002");
+const RECIPIENT: Address = address!("1000000000000000000000000000000000000001");
+
+fn create_transaction(nonce: u64) -> Recovered<MegaTxEnvelope> {
+    let tx_legacy = TxLegacy {
+        chain_id: Some(8453),
+        nonce,
+        gas_price: 0,
+        gas_limit: 100_000,
+        to: TxKind::Call(RECIPIENT),
+        value: U256::from(1),
+        input: Bytes::new(),
+    };
+    let signed = Signed::new_unchecked(tx_legacy, Signature::test_signature(), Default::default());
+    Recovered::new_unchecked(MegaTxEnvelope::Legacy(signed), ALICE)
+}
+
+#[test]
+fn test_deferred_commit_reproduces_the_block_state() {
+    let genesis =
+        MemoryDatabase::default().account_balance(ALICE, U256::from(1_000_000_000_000_000u64));
+
+    let mut db = genesis.clone();
+    let mut state = State::builder().with_database(&mut db).build();
+    let evm_factory =
+        MegaEvmFactory::new().with_external_env_factory(TestExternalEnvs::<Infallible>::new());
+    let mut cfg_env = revm::context::CfgEnv::default();
+    cfg_env.spec = MegaSpecId::REX4;
+    let block_env = BlockEnv {
+        number: U256::from(1000),
+        timestamp: U256::from(1_800_000_000),
+        gas_limit: 30_000_000,
+        ..Default::default()
+    };
+    let evm = evm_factory.create_evm(&mut state, EvmEnv::new(cfg_env, block_env));
+    let block_ctx =
+        MegaBlockExecutionCtx::new(B256::ZERO, None, Bytes::new(), BlockLimits::no_limits());
+    let chain_spec = MegaHardforkConfig::default()
+        .with_all_activated()
+        .without(MegaHardfork::Rex5)
+        .without(MegaHardfork::Rex6);
+    let mut executor =
+        MegaBlockExecutor::new(evm, block_ctx, chain_spec, OpAlloyReceiptBuilder::default())
+            .with_state_mutation_log();
+    executor.apply_pre_execution_changes().expect("pre-execution changes should succeed");
+    let pre_block = executor.commit_later().expect("mutations are recorded");
+
+    executor
+        .execute_transaction_with_commit_condition(&create_transaction(0), |_| CommitChanges::Yes)
+        .unwrap();
+    // A discarded outcome is never committed, so it is not recorded either.
+    executor
+        .execute_transaction_with_commit_condition(&create_transaction(1), |_| CommitChanges::No)
+        .unwrap();
+    executor
+        .execute_transaction_with_commit_condition(&create_transaction(1), |_| CommitChanges::Yes)
+        .unwrap();
+    let (_, _, block) = executor.finish_with_deferred_commit().expect("finish should succeed");
+    let block = block.expect("mutations are recorded");
+
+    let recipient_sources: Vec<_> = block
+        .mutations()
+        .iter()
+        .filter(|mutation| mutation.address == RECIPIENT)
+        .map(|mutation| mutation.source)
+        .collect();
+    assert_eq!(
+        recipient_sources,
+        [StateChangeSource::Transaction(0), StateChangeSource::Transaction(1)]
+    );
+
+    // Applying the mutations to the parent state later yields the state of the block.
+    let mut deferred = genesis;
+    pre_block.commit(&mut deferred);
+    block.commit(&mut deferred);
+    for address in [ALICE, RECIPIENT] {
+        assert_eq!(deferred.basic(address).unwrap(), state.basic(address).unwrap());
+    }
+    assert_eq!(deferred.basic(RECIPIENT).unwrap().unwrap().balance, U256::from(2));
+    assert_eq!(deferred.basic(ALICE).unwrap().unwrap().nonce, 2);
+}
+
+#[test]
+fn test_commit_later_without_mutation_log_returns_none() {
+    let mut db = MemoryDatabase::default();
+    let mut state = State::builder().with_database(&mut db).build();
+    let evm_factory =
+        MegaEvmFactory::new().with_external_env_factory(TestExternalEnvs::<Infallible>::new());
+    let mut cfg_env = revm::context::CfgEnv::default();
+    cfg_env.spec = MegaSpecId::REX4;
+    let block_env = BlockEnv {
+        number: U256::from(1000),
+        timestamp: U256::from(1_800_000_000),
+        gas_limit: 30_000_000,
+        ..Default::default()
+    };
+    let evm = evm_factory.create_evm(&mut state, EvmEnv::new(cfg_env, block_env));
+    let block_ctx =
+        MegaBlockExecutionCtx::new(B256::ZERO, None, Bytes::new(), BlockLimits::no_limits());
+    let chain_spec = MegaHardforkConfig::default()
+        .with_all_activated()
+        .without(MegaHardfork::Rex5)
+        .without(MegaHardfork::Rex6);
+    let mut executor =
+        MegaBlockExecutor::new(evm, block_ctx, chain_spec, OpAlloyReceiptBuilder::default());
+    assert!(executor.commit_later().is_none());
+}
@@ -1,8 +1,17 @@
 //! Tests for block executor functionality.
 
 mod accessed_block_hashes;
+mod beneficiary_deferral;
+mod block_env_access_summary;
 mod block_limits;
+mod data_size_accounting;
+mod deferred_commit;
 mod deposit_da_exemption;
 mod inspector;
+mod mini_blocks;
+mod parallel;
+mod prefetch;
+mod receipt_stream;
 mod sequencer_registry;
 mod trait_factory_runtime_limits;
+mod tx_policy;
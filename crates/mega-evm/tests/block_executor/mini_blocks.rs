@@ -0,0 +1,120 @@
+//! Tests for executing a block as mini-blocks.
+//!
+//! The mini-blocks of a block share its env, state and receipts, but each is admitted against its
+//! own limits: a transaction that does not fit in the current mini-block is offered again after
+//! sealing it.
+
+use std::convert::Infallible;
+
+use alloy_consensus::{transaction::Recovered, Signed, TxLegacy, TxReceipt};
+use alloy_evm::{block::BlockExecutor, EvmEnv, EvmFactory};
+use alloy_op_evm::block::receipt_builder::OpAlloyReceiptBuilder;
+use alloy_primitives::{address, Address, Bytes, Signature, TxKind, B256, U256};
+use mega_evm::{
+    test_utils::MemoryDatabase, BlockLimits, MegaBlockExecutionCtx, MegaBlockExecutor,
+    MegaEvmFactory, MegaHardfork, MegaHardforkConfig, MegaSpecId, MegaTxEnvelope, TestExternalEnvs,
+};
+use revm::{context::BlockEnv, database::State};
+
+const ALICE: Address = address!("2000000000000000000000000000000000000002");
+const RECIPIENT: Address = address!("1000000000000000000000000000000000000001");
+
+/// The gas limit of every transaction. A mini-block of [`MINI_BLOCK_GAS_LIMIT`] admits a first
+/// transfer, but not a second one on top of the 21,000 gas used by the first.
+const TX_GAS_LIMIT: u64 = 100_000;
+const MINI_BLOCK_GAS_LIMIT: u64 = 120_000;
+
+fn create_transaction(nonce: u64) -> Recovered<MegaTxEnvelope> {
+    let tx_legacy = TxLegacy {
+        chain_id: Some(8453),
+        nonce,
+        gas_price: 0,
+        gas_limit: TX_GAS_LIMIT,
+        to: TxKind::Call(RECIPIENT),
+        value: U256::from(1),
+        input: Bytes::new(),
+    };
+    let signed = Signed::new_unchecked(tx_legacy, Signature::test_signature(), Default::default());
+    Recovered::new_unchecked(MegaTxEnvelope::Legacy(signed), ALICE)
+}
+
+#[test]
+fn test_mini_blocks_have_separate_limits_and_receipt_ranges() {
+    let mut db = MemoryDatabase::default();
+    db.set_account_balance(ALICE, U256::from(1_000_000_000_000_000_000u128));
+
+    let mut state = State::builder().with_database(&mut db).build();
+    let evm_factory =
+        MegaEvmFactory::new().with_external_env_factory(TestExternalEnvs::<Infallible>::new());
+    let mut cfg_env = revm::context::CfgEnv::default();
+    cfg_env.spec = MegaSpecId::REX4;
+    let block_env = BlockEnv {
+        number: U256::from(1000),
+        timestamp: U256::from(1_800_000_000),
+        gas_limit: 30_000_000,
+        ..Default::default()
+    };
+    let evm = evm_factory.create_evm(&mut state, EvmEnv::new(cfg_env, block_env));
+    let block_ctx =
+        MegaBlockExecutionCtx::new(B256::ZERO, None, Bytes::new(), BlockLimits::no_limits());
+    let chain_spec = MegaHardforkConfig::default()
+        .with_all_activated()
+        .without(MegaHardfork::Rex5)
+        .without(MegaHardfork::Rex6);
+    let mut executor =
+        MegaBlockExecutor::new(evm, block_ctx, chain_spec, OpAlloyReceiptBuilder::default())
+            .with_mini_blocks(BlockLimits::no_limits().with_block_gas_limit(MINI_BLOCK_GAS_LIMIT));
+    executor.apply_pre_execution_changes().expect("pre-execution changes should succeed");
+
+    for nonce in 0..3 {
+        let tx = create_transaction(nonce);
+        let outcome = match executor.run_transaction(&tx) {
+            Ok(outcome) => outcome,
+            Err(_) => {
+                // The current mini-block is full: seal it and offer the transaction again.
+                executor.seal_mini_block().expect("mini-blocks are enabled");
+                executor.run_transaction(&tx).expect("a fresh mini-block should admit the tx")
+            }
+        };
+        executor.commit_transaction_outcome(outcome).expect("commit should succeed");
+    }
+    executor.seal_mini_block().expect("mini-blocks are enabled");
+
+    let mini_blocks = executor.mini_blocks().expect("mini-blocks are enabled").sealed().to_vec();
+    let ranges: Vec<_> = mini_blocks.iter().map(|mini| mini.receipts.clone()).collect();
+    assert_eq!(ranges, [0..1, 1..2, 2..3]);
+    assert!(mini_blocks.iter().all(|mini| mini.usage.block_gas_used == 21_000));
+
+    // The block limiter accumulates across mini-blocks, and so do the receipts.
+    assert_eq!(executor.block_limiter.block_gas_used, 63_000);
+    let (_, result) = executor.finish().expect("finish should succeed");
+    let cumulative: Vec<_> = result.receipts.iter().map(|r| r.cumulative_gas_used()).collect();
+    assert_eq!(cumulative, [21_000, 42_000, 63_000]);
+}
+
+#[test]
+fn test_seal_mini_block_without_mini_blocks_returns_none() {
+    let mut db = MemoryDatabase::default();
+    let mut state = State::builder().with_database(&mut db).build();
+    let evm_factory =
+        MegaEvmFactory::new().with_external_env_factory(TestExternalEnvs::<Infallible>::new());
+    let mut cfg_env = revm::context::CfgEnv::default();
+    cfg_env.spec = MegaSpecId::REX4;
+    let block_env = BlockEnv {
+        number: U256::from(1000),
+        timestamp: U256::from(1_800_000_000),
+        gas_limit: 30_000_000,
+        ..Default::default()
+    };
+    let evm = evm_factory.create_evm(&mut state, EvmEnv::new(cfg_env, block_env));
+    let block_ctx =
+        MegaBlockExecutionCtx::new(B256::ZERO, None, Bytes::new(), BlockLimits::no_limits());
+    let chain_spec = MegaHardforkConfig::default()
+        .with_all_activated()
+        .without(MegaHardfork::Rex5)
+        .without(MegaHardfork::Rex6);
+    let mut executor =
+        MegaBlockExecutor::new(evm, block_ctx, chain_spec, OpAlloyReceiptBuilder::default());
+    assert!(executor.mini_blocks().is_none());
+    assert!(executor.seal_mini_block().is_none());
+}
@@ -0,0 +1,266 @@
+//! Tests for the experimental optimistic parallel executor.
+//!
+//! Executing a block through [`mega_evm::parallel::execute_transactions`] must produce the same
+//! receipts and state as executing its transactions one by one with the block executor, whether
+//! the speculative outcomes are committed or the transactions re-executed.
+//! [`mega_evm::parallel::recover_senders_parallel`] must recover the same senders as recovering
+//! them one by one.
+
+use std::{convert::Infallible, num::NonZeroUsize};
+
+use alloy_consensus::{
+    transaction::{Recovered, SignerRecoverable},
+    Signed, TxLegacy,
+};
+use alloy_evm::{block::BlockExecutor, EvmEnv, EvmFactory};
+use alloy_op_evm::block::receipt_builder::OpAlloyReceiptBuilder;
+use alloy_primitives::{address, Address, Bytes, Signature, TxKind, B256, U256};
+use mega_evm::{
+    parallel::{execute_transactions, recover_senders_parallel, ParallelExecutionStats},
+    test_utils::{BytecodeBuilder, MemoryDatabase},
+    BlockLimits, MegaBlockExecutionCtx, MegaBlockExecutor, MegaEvmFactory, MegaHardfork,
+    MegaHardforkConfig, MegaSpecId, MegaTxEnvelope, TestExternalEnvs,
+};
+use op_alloy_consensus::OpReceipt;
+use op_revm::constants::{BASE_FEE_RECIPIENT, L1_FEE_RECIPIENT, OPERATOR_FEE_RECIPIENT};
+use revm::{
+    bytecode::opcode::{ADD, BALANCE, COINBASE, PUSH0, SLOAD, SSTORE},
+    context::BlockEnv,
+    database::State,
+    handler::EvmTr,
+    Database as _,
+};
+
+const CALLERS: [Address; 5] = [
+    address!("2000000000000000000000000000000000000001"),
+    address!("2000000000000000000000000000000000000002"),
+    address!("2000000000000000000000000000000000000003"),
+    address!("2000000000000000000000000000000000000004"),
+    address!("2000000000000000000000000000000000000005"),
+];
+/// Sets slot 0 to 1.
+const SETTERS: [Address; 2] = [
+    address!("1000000000000000000000000000000000000001"),
+    address!("1000000000000000000000000000000000000002"),
+];
+/// Increments slot 0.
+const COUNTER: Address = address!("1000000000000000000000000000000000000003");
+/// Stores the beneficiary's balance in slot 0.
+const READER: Address = address!("1000000000000000000000000000000000000004");
+const BENEFICIARY: Address = address!("3000000000000000000000000000000000000003");
+
+fn create_transaction(caller: Address, nonce: u64, to: Address) -> Recovered<MegaTxEnvelope> {
+    let tx_legacy = TxLegacy {
+        chain_id: Some(8453),
+        nonce,
+        gas_price: 1_000_000,
+        gas_limit: 1_000_000,
+        to: TxKind::Call(to),
+        value: U256::ZERO,
+        input: Bytes::new(),
+    };
+    let signed = Signed::new_unchecked(tx_legacy, Signature::test_signature(), Default::default());
+    Recovered::new_unchecked(MegaTxEnvelope::Legacy(signed), caller)
+}
+
+/// Final state observed by the tests.
+#[derive(Debug, PartialEq, Eq)]
+struct BlockOutcome {
+    receipts: Vec<OpReceipt>,
+    gas_used: u64,
+    /// Balances of the callers, the beneficiary and the fee vaults.
+    balances: Vec<U256>,
+    /// Slot 0 of the contracts.
+    storage: Vec<U256>,
+}
+
+/// Executes the block, in parallel if `num_workers` is set.
+fn execute_block(
+    transactions: &[Recovered<MegaTxEnvelope>],
+    defer: bool,
+    num_workers: Option<NonZeroUsize>,
+) -> (BlockOutcome, Option<ParallelExecutionStats>) {
+    let mut db = MemoryDatabase::default();
+    for setter in SETTERS {
+        db.set_account_code(
+            setter,
+            BytecodeBuilder::default()
+                .push_number(1_u8)
+                .append(PUSH0)
+                .append(SSTORE)
+                .stop()
+                .build(),
+        );
+    }
+    db.set_account_code(
+        COUNTER,
+        BytecodeBuilder::default()
+            .append(PUSH0)
+            .append(SLOAD)
+            .push_number(1_u8)
+            .append(ADD)
+            .append(PUSH0)
+            .append(SSTORE)
+            .stop()
+            .build(),
+    );
+    db.set_account_code(
+        READER,
+        BytecodeBuilder::default()
+            .append(COINBASE)
+            .append(BALANCE)
+            .append(PUSH0)
+            .append(SSTORE)
+            .stop()
+            .build(),
+    );
+    for caller in CALLERS {
+        db.set_account_balance(caller, U256::from(1_000_000_000_000_000_000u128));
+    }
+
+    let mut state = State::builder().with_database(db).build();
+    let mut cfg_env = revm::context::CfgEnv::default();
+    cfg_env.spec = MegaSpecId::REX4;
+    let block_env = BlockEnv {
+        number: U256::from(1000),
+        beneficiary: BENEFICIARY,
+        timestamp: U256::from(1_800_000_000),
+        gas_limit: 30_000_000,
+        basefee: 10,
+        ..Default::default()
+    };
+    let evm_env = EvmEnv::new(cfg_env, block_env);
+    let mut evm = MegaEvmFactory::new()
+        .with_external_env_factory(TestExternalEnvs::<Infallible>::new())
+        .create_evm(&mut state, evm_env.clone());
+    if defer {
+        evm.ctx_mut().defer_beneficiary_reward();
+    }
+
+    let block_ctx =
+        MegaBlockExecutionCtx::new(B256::ZERO, None, Bytes::new(), BlockLimits::no_limits());
+    let chain_spec = MegaHardforkConfig::default()
+        .with_all_activated()
+        .without(MegaHardfork::Rex5)
+        .without(MegaHardfork::Rex6);
+    let mut executor =
+        MegaBlockExecutor::new(evm, block_ctx, chain_spec, OpAlloyReceiptBuilder::default());
+    executor.apply_pre_execution_changes().expect("pre-execution changes should succeed");
+
+    let stats = match num_workers {
+        Some(num_workers) => Some(
+            execute_transactions(
+                &mut executor,
+                &transactions.iter().collect::<Vec<_>>(),
+                num_workers,
+                |db| {
+                    MegaEvmFactory::new()
+                        .with_external_env_factory(TestExternalEnvs::<Infallible>::new())
+                        .create_evm(db, evm_env.clone())
+                },
+            )
+            .expect("parallel execution should succeed"),
+        ),
+        None => {
+            for tx in transactions {
+                let outcome = executor.run_transaction(tx).expect("transaction should execute");
+                executor.commit_execution_outcome(outcome).expect("commit should succeed");
+            }
+            None
+        }
+    };
+    let (evm, result) = executor.finish().expect("finish should succeed");
+    drop(evm);
+
+    let balances = CALLERS
+        .into_iter()
+        .chain([BENEFICIARY, BASE_FEE_RECIPIENT, L1_FEE_RECIPIENT, OPERATOR_FEE_RECIPIENT])
+        .map(|address| {
+            state.basic(address).expect("account should load").unwrap_or_default().balance
+        })
+        .collect();
+    let storage = SETTERS
+        .into_iter()
+        .chain([COUNTER, READER])
+        .map(|address| state.storage(address, U256::ZERO).expect("storage should load"))
+        .collect();
+    let outcome =
+        BlockOutcome { receipts: result.receipts, gas_used: result.gas_used, balances, storage };
+    (outcome, stats)
+}
+
+/// A block mixing independent transactions with ones conflicting with an earlier transaction.
+fn mixed_block() -> Vec<Recovered<MegaTxEnvelope>> {
+    Vec::from([
+        create_transaction(CALLERS[0], 0, SETTERS[0]),
+        create_transaction(CALLERS[1], 0, SETTERS[1]),
+        create_transaction(CALLERS[2], 0, COUNTER),
+        // Reads the counter written by the previous transaction.
+        create_transaction(CALLERS[3], 0, COUNTER),
+        // Reads the caller of the first transaction.
+        create_transaction(CALLERS[0], 1, SETTERS[1]),
+        // Reads the beneficiary credited by the earlier transactions.
+        create_transaction(CALLERS[4], 0, READER),
+    ])
+}
+
+#[test]
+fn test_parallel_execution_matches_sequential_execution() {
+    let transactions = mixed_block();
+    for defer in [false, true] {
+        let (expected, _) = execute_block(&transactions, defer, None);
+        for num_workers in [1, 2, 4] {
+            let (outcome, stats) =
+                execute_block(&transactions, defer, NonZeroUsize::new(num_workers));
+            assert_eq!(outcome, expected, "defer: {defer}, workers: {num_workers}");
+            assert_eq!(
+                stats,
+                Some(ParallelExecutionStats { speculative: 3, re_executed: 3 }),
+                "defer: {defer}, workers: {num_workers}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_parallel_execution_commits_independent_transactions() {
+    let transactions = [
+        create_transaction(CALLERS[0], 0, SETTERS[0]),
+        create_transaction(CALLERS[1], 0, SETTERS[1]),
+    ];
+    let (expected, _) = execute_block(&transactions, true, None);
+    let (outcome, stats) = execute_block(&transactions, true, NonZeroUsize::new(2));
+    assert_eq!(outcome, expected);
+    assert_eq!(stats, Some(ParallelExecutionStats { speculative: 2, re_executed: 0 }));
+    assert_eq!(outcome.storage[..2], [U256::from(1); 2]);
+}
+
+#[test]
+fn test_parallel_execution_of_empty_block() {
+    let (expected, _) = execute_block(&[], false, None);
+    let (outcome, stats) = execute_block(&[], false, NonZeroUsize::new(2));
+    assert_eq!(outcome, expected);
+    assert_eq!(stats, Some(ParallelExecutionStats::default()));
+}
+
+#[test]
+fn test_recover_senders_parallel_matches_sequential_recovery() {
+    let transactions: Vec<MegaTxEnvelope> =
+        (0..100).map(|nonce| create_transaction(CALLERS[0], nonce, COUNTER).into_inner()).collect();
+    let expected: Vec<Address> = transactions
+        .iter()
+        .map(|tx| tx.recover_signer().expect("the signature is valid"))
+        .collect();
+    assert_eq!(recover_senders_parallel(&transactions).unwrap(), expected);
+    assert!(recover_senders_parallel::<MegaTxEnvelope>(&[]).unwrap().is_empty());
+
+    // The first invalid signature, in order, is reported.
+    let mut invalid = transactions;
+    for index in [70, 40] {
+        let MegaTxEnvelope::Legacy(signed) = &invalid[index] else { unreachable!() };
+        let unsigned = signed.tx().clone();
+        let signature = Signature::new(U256::ZERO, U256::ZERO, false);
+        invalid[index] = MegaTxEnvelope::Legacy(Signed::new_unhashed(unsigned, signature));
+    }
+    assert_eq!(recover_senders_parallel(&invalid).unwrap_err().index, 40);
+}
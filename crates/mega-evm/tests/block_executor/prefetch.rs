@@ -0,0 +1,106 @@
+//! Tests for prefetching the state a transaction reads before the block executor executes it.
+
+use std::{convert::Infallible, sync::Arc};
+
+use alloy_consensus::{transaction::Recovered, Signed, TxLegacy};
+use alloy_evm::{
+    block::{BlockExecutor, CommitChanges},
+    EvmEnv, EvmFactory,
+};
+use alloy_op_evm::block::receipt_builder::OpAlloyReceiptBuilder;
+use alloy_primitives::{address, keccak256, Address, Bytes, Signature, TxKind, B256, U256};
+use alloy_sol_types::SolCall;
+use mega_evm::{
+    test_utils::MemoryDatabase, BlockLimits, CalldataPrefetcher, MegaBlockExecutionCtx,
+    MegaBlockExecutor, MegaEvmFactory, MegaHardfork, MegaHardforkConfig, MegaSpecId,
+    MegaTxEnvelope, TestExternalEnvs,
+};
+use revm::{context::BlockEnv, database::State};
+
+alloy_sol_types::sol! {
+    function transfer(address to, uint256 amount) external returns (bool);
+}
+
+const ALICE: Address = address!("2000000000000000000000000000000000000002");
+const BOB: Address = address!("2000000000000000000000000000000000000003");
+/// A token without code, so the transfer itself reads none of its storage.
+const TOKEN: Address = address!("1000000000000000000000000000000000000001");
+
+/// The slot of `owner`'s balance in an `OpenZeppelin` ERC-20.
+fn balance_slot(owner: Address) -> U256 {
+    let mut preimage = [0u8; 64];
+    preimage[12..32].copy_from_slice(owner.as_slice());
+    U256::from_be_bytes(keccak256(preimage).0)
+}
+
+fn create_transfer() -> Recovered<MegaTxEnvelope> {
+    let tx_legacy = TxLegacy {
+        chain_id: Some(8453),
+        nonce: 0,
+        gas_price: 0,
+        gas_limit: 100_000,
+        to: TxKind::Call(TOKEN),
+        value: U256::ZERO,
+        input: transferCall { to: BOB, amount: U256::from(1) }.abi_encode().into(),
+    };
+    let signed = Signed::new_unchecked(tx_legacy, Signature::test_signature(), Default::default());
+    Recovered::new_unchecked(MegaTxEnvelope::Legacy(signed), ALICE)
+}
+
+/// Executes the transfer and returns its gas used and the token slots in the state cache after
+/// the block.
+fn execute_transfer(prefetch: bool) -> (u64, Vec<(U256, U256)>) {
+    let mut db = MemoryDatabase::default()
+        .account_balance(ALICE, U256::from(1_000_000_000_000_000_000u128))
+        .account_storage(TOKEN, balance_slot(BOB), U256::from(7));
+
+    let mut state = State::builder().with_database(&mut db).build();
+    let evm_factory =
+        MegaEvmFactory::new().with_external_env_factory(TestExternalEnvs::<Infallible>::new());
+    let mut cfg_env = revm::context::CfgEnv::default();
+    cfg_env.spec = MegaSpecId::REX4;
+    let block_env = BlockEnv {
+        number: U256::from(1000),
+        timestamp: U256::from(1_800_000_000),
+        gas_limit: 30_000_000,
+        ..Default::default()
+    };
+    let evm = evm_factory.create_evm(&mut state, EvmEnv::new(cfg_env, block_env));
+    let block_ctx =
+        MegaBlockExecutionCtx::new(B256::ZERO, None, Bytes::new(), BlockLimits::no_limits());
+    let chain_spec = MegaHardforkConfig::default()
+        .with_all_activated()
+        .without(MegaHardfork::Rex5)
+        .without(MegaHardfork::Rex6);
+    let mut executor =
+        MegaBlockExecutor::new(evm, block_ctx, chain_spec, OpAlloyReceiptBuilder::default());
+    if prefetch {
+        executor = executor.with_prefetcher(Arc::new(CalldataPrefetcher::default()));
+    }
+    executor.apply_pre_execution_changes().expect("pre-execution changes should succeed");
+    let gas_used = executor
+        .execute_transaction_with_commit_condition(&create_transfer(), |_| CommitChanges::Yes)
+        .unwrap()
+        .expect("the transfer is committed");
+    executor.finish().expect("finish should succeed");
+
+    let token = state.cache.accounts.get(&TOKEN).and_then(|account| account.account.as_ref());
+    let mut slots: Vec<_> = token
+        .map(|account| account.storage.iter().map(|(slot, value)| (*slot, *value)).collect())
+        .unwrap_or_default();
+    slots.sort_unstable();
+    (gas_used, slots)
+}
+
+#[test]
+fn test_calldata_prefetcher_loads_predicted_slots_before_execution() {
+    let (gas_used, slots) = execute_transfer(false);
+    assert!(slots.is_empty(), "the token has no code, so execution reads none of its slots");
+
+    let (prefetched_gas_used, prefetched_slots) = execute_transfer(true);
+    // Prefetching does not change the outcome.
+    assert_eq!(prefetched_gas_used, gas_used);
+    let mut expected = vec![(balance_slot(ALICE), U256::ZERO), (balance_slot(BOB), U256::from(7))];
+    expected.sort_unstable();
+    assert_eq!(prefetched_slots, expected);
+}
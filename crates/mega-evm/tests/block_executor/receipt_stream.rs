@@ -0,0 +1,110 @@
+//! Tests for streaming receipts from the block executor as transactions are committed.
+
+use std::{
+    convert::Infallible,
+    sync::{Arc, Mutex},
+};
+
+use alloy_consensus::{transaction::Recovered, Signed, TxLegacy, TxReceipt};
+use alloy_evm::{
+    block::{BlockExecutor, CommitChanges},
+    EvmEnv, EvmFactory,
+};
+use alloy_op_evm::block::receipt_builder::OpAlloyReceiptBuilder;
+use alloy_primitives::{address, Address, Bytes, Signature, TxKind, B256, U256};
+use mega_evm::{
+    test_utils::MemoryDatabase, BlockLimits, MegaBlockExecutionCtx, MegaBlockExecutor,
+    MegaEvmFactory, MegaHardfork, MegaHardforkConfig, MegaSpecId, MegaTxEnvelope, StreamedReceipt,
+    TestExternalEnvs,
+};
+use op_alloy_consensus::OpReceiptEnvelope;
+use revm::{context::BlockEnv, database::State};
+
+const ALICE: Address = address!("2000000000000000000000000000000000000002");
+const RECIPIENT: Address = address!("1000000000000000000000000000000000000001");
+
+fn create_transaction(nonce: u64) -> Recovered<MegaTxEnvelope> {
+    let tx_legacy = TxLegacy {
+        chain_id: Some(8453),
+        nonce,
+        gas_price: 0,
+        gas_limit: 100_000,
+        to: TxKind::Call(RECIPIENT),
+        value: U256::from(1),
+        input: Bytes::new(),
+    };
+    let signed = Signed::new_unchecked(tx_legacy, Signature::test_signature(), Default::default());
+    Recovered::new_unchecked(MegaTxEnvelope::Legacy(signed), ALICE)
+}
+
+/// What the test keeps of a streamed receipt: the transaction index and hash, the cumulative gas
+/// of the receipt, the gas used by the transaction and by the block so far, and the mini-block.
+type Streamed = (usize, B256, u64, u64, u64, Option<u64>);
+
+#[test]
+fn test_receipts_are_streamed_as_transactions_commit() {
+    let mut db = MemoryDatabase::default();
+    db.set_account_balance(ALICE, U256::from(1_000_000_000_000_000_000u128));
+
+    let mut state = State::builder().with_database(&mut db).build();
+    let evm_factory =
+        MegaEvmFactory::new().with_external_env_factory(TestExternalEnvs::<Infallible>::new());
+    let mut cfg_env = revm::context::CfgEnv::default();
+    cfg_env.spec = MegaSpecId::REX4;
+    let block_env = BlockEnv {
+        number: U256::from(1000),
+        timestamp: U256::from(1_800_000_000),
+        gas_limit: 30_000_000,
+        ..Default::default()
+    };
+    let evm = evm_factory.create_evm(&mut state, EvmEnv::new(cfg_env, block_env));
+    let block_ctx =
+        MegaBlockExecutionCtx::new(B256::ZERO, None, Bytes::new(), BlockLimits::no_limits());
+    let chain_spec = MegaHardforkConfig::default()
+        .with_all_activated()
+        .without(MegaHardfork::Rex5)
+        .without(MegaHardfork::Rex6);
+
+    let streamed = Arc::new(Mutex::new(Vec::<Streamed>::new()));
+    let sink = streamed.clone();
+    let mut executor =
+        MegaBlockExecutor::new(evm, block_ctx, chain_spec, OpAlloyReceiptBuilder::default())
+            .with_mini_blocks(BlockLimits::no_limits())
+            .with_receipt_hook(move |receipt: StreamedReceipt<'_, OpReceiptEnvelope>| {
+                sink.lock().unwrap().push((
+                    receipt.index,
+                    receipt.tx_hash,
+                    receipt.receipt.cumulative_gas_used(),
+                    receipt.usage.gas_used,
+                    receipt.block_usage.block_gas_used,
+                    receipt.mini_block.map(|(index, _)| index),
+                ));
+            });
+    executor.apply_pre_execution_changes().expect("pre-execution changes should succeed");
+
+    let txs = [create_transaction(0), create_transaction(1), create_transaction(2)];
+
+    executor.execute_transaction_with_commit_condition(&txs[0], |_| CommitChanges::Yes).unwrap();
+    // Each receipt is available before the block is finished.
+    assert_eq!(streamed.lock().unwrap().len(), 1);
+
+    // A transaction whose outcome is discarded is not streamed.
+    executor.execute_transaction_with_commit_condition(&txs[1], |_| CommitChanges::No).unwrap();
+    assert_eq!(streamed.lock().unwrap().len(), 1);
+
+    executor.seal_mini_block().expect("mini-blocks are enabled");
+    executor.execute_transaction_with_commit_condition(&txs[1], |_| CommitChanges::Yes).unwrap();
+    executor.execute_transaction_with_commit_condition(&txs[2], |_| CommitChanges::Yes).unwrap();
+
+    let (_, result) = executor.finish().expect("finish should succeed");
+    assert_eq!(result.receipts.len(), 3);
+    let hashes: Vec<_> = txs.iter().map(|tx| tx.tx_hash()).collect();
+    assert_eq!(
+        *streamed.lock().unwrap(),
+        [
+            (0, hashes[0], 21_000, 21_000, 21_000, Some(0)),
+            (1, hashes[1], 42_000, 21_000, 42_000, Some(1)),
+            (2, hashes[2], 63_000, 21_000, 63_000, Some(1)),
+        ]
+    );
+}
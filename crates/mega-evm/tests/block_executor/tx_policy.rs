@@ -0,0 +1,116 @@
+//! Tests for the transaction policy consulted by the block executor.
+//!
+//! A transaction rejected by the policy is skipped and recorded, and the rest of the block executes
+//! as if it had not been offered.
+
+use std::{convert::Infallible, sync::Arc};
+
+use alloy_consensus::{transaction::Recovered, Signed, TxLegacy};
+use alloy_evm::{
+    block::{BlockExecutor, CommitChanges},
+    EvmEnv, EvmFactory,
+};
+use alloy_op_evm::block::receipt_builder::OpAlloyReceiptBuilder;
+use alloy_primitives::{address, Address, Bytes, Signature, TxKind, B256, U256};
+use mega_evm::{
+    test_utils::MemoryDatabase, AddressPolicy, BlockLimits, MegaBlockExecutionCtx,
+    MegaBlockExecutor, MegaEvmFactory, MegaHardfork, MegaHardforkConfig, MegaSpecId,
+    MegaTxEnvelope, TestExternalEnvs, TxPolicy, TxPolicyInput, TxPolicyViolation,
+};
+use revm::{context::BlockEnv, database::State, Database as _};
+
+const ALICE: Address = address!("2000000000000000000000000000000000000002");
+const BOB: Address = address!("2000000000000000000000000000000000000003");
+const RECIPIENT: Address = address!("1000000000000000000000000000000000000001");
+const SANCTIONED: Address = address!("1000000000000000000000000000000000000002");
+
+fn create_transaction(sender: Address, nonce: u64, to: Address) -> Recovered<MegaTxEnvelope> {
+    let tx_legacy = TxLegacy {
+        chain_id: Some(8453),
+        nonce,
+        gas_price: 0,
+        gas_limit: 1_000_000,
+        to: TxKind::Call(to),
+        value: U256::from(1),
+        input: Bytes::new(),
+    };
+    let signed = Signed::new_unchecked(tx_legacy, Signature::test_signature(), Default::default());
+    Recovered::new_unchecked(MegaTxEnvelope::Legacy(signed), sender)
+}
+
+#[test]
+fn test_rejected_transactions_are_skipped_and_recorded() {
+    let mut db = MemoryDatabase::default();
+    db.set_account_balance(ALICE, U256::from(1_000_000_000_000_000_000u128));
+    db.set_account_balance(BOB, U256::from(1_000_000_000_000_000_000u128));
+
+    let mut state = State::builder().with_database(&mut db).build();
+    let evm_factory =
+        MegaEvmFactory::new().with_external_env_factory(TestExternalEnvs::<Infallible>::new());
+    let mut cfg_env = revm::context::CfgEnv::default();
+    cfg_env.spec = MegaSpecId::REX4;
+    let block_env = BlockEnv {
+        number: U256::from(1000),
+        timestamp: U256::from(1_800_000_000),
+        gas_limit: 30_000_000,
+        ..Default::default()
+    };
+    let evm = evm_factory.create_evm(&mut state, EvmEnv::new(cfg_env, block_env));
+    let block_ctx =
+        MegaBlockExecutionCtx::new(B256::ZERO, None, Bytes::new(), BlockLimits::no_limits());
+    let chain_spec = MegaHardforkConfig::default()
+        .with_all_activated()
+        .without(MegaHardfork::Rex5)
+        .without(MegaHardfork::Rex6);
+    let mut executor =
+        MegaBlockExecutor::new(evm, block_ctx, chain_spec, OpAlloyReceiptBuilder::default())
+            .with_tx_policy(Arc::new(AddressPolicy::Deny(Vec::from([BOB, SANCTIONED]))));
+    executor.apply_pre_execution_changes().expect("pre-execution changes should succeed");
+
+    let txs = [
+        create_transaction(ALICE, 0, RECIPIENT),
+        create_transaction(BOB, 0, RECIPIENT),
+        create_transaction(ALICE, 1, SANCTIONED),
+        create_transaction(ALICE, 1, RECIPIENT),
+    ];
+    let committed: Vec<bool> = txs
+        .iter()
+        .map(|tx| {
+            executor
+                .execute_transaction_with_commit_condition(tx, |_| CommitChanges::Yes)
+                .expect("a rejected transaction must not fail the block")
+                .is_some()
+        })
+        .collect();
+    assert_eq!(committed, [true, false, false, true]);
+
+    let (evm, result, rejected) = executor.finish_with_rejections().expect("finish should succeed");
+    drop(evm);
+    assert_eq!(result.receipts.len(), 2);
+    let rejected: Vec<_> = rejected.into_iter().map(|r| (r.sender, r.violation)).collect();
+    assert_eq!(
+        rejected,
+        [
+            (BOB, TxPolicyViolation::SenderDenied(BOB)),
+            (ALICE, TxPolicyViolation::CalleeDenied(SANCTIONED)),
+        ]
+    );
+
+    let mut balance = |address| state.basic(address).unwrap().unwrap_or_default().balance;
+    assert_eq!(balance(RECIPIENT), U256::from(2));
+    assert_eq!(balance(SANCTIONED), U256::ZERO);
+}
+
+#[test]
+fn test_allow_policy_only_admits_listed_senders() {
+    let policy = AddressPolicy::Allow(Vec::from([ALICE]));
+    let input = |sender| TxPolicyInput {
+        hash: B256::ZERO,
+        sender,
+        to: Some(SANCTIONED),
+        value: U256::ZERO,
+        input: Bytes::new(),
+    };
+    assert_eq!(policy.check(&input(ALICE)), Ok(()));
+    assert_eq!(policy.check(&input(BOB)), Err(TxPolicyViolation::SenderDenied(BOB)));
+}
@@ -0,0 +1,80 @@
+//! Tests for the `EQUIVALENCE` fast path, which bypasses the `MegaETH` frame hooks.
+
+use alloy_primitives::{address, Address, Bytes, U256};
+use mega_evm::{
+    test_utils::{context_without_operator_fee, BytecodeBuilder, MemoryDatabase},
+    LimitUsage, MegaEvm, MegaSpecId, MegaTransaction, PrecompileResourceReporter,
+    PrecompileResourceUsage,
+};
+use revm::{
+    bytecode::opcode::*,
+    context::{tx::TxEnvBuilder, TxEnv},
+    handler::EvmTr,
+    interpreter::InterpreterResult,
+};
+
+const CALLER: Address = address!("0000000000000000000000000000000000100000");
+const CALLEE: Address = address!("0000000000000000000000000000000000100001");
+const IDENTITY: Address = address!("0000000000000000000000000000000000000004");
+
+/// Reports usage for every precompile call, which the fast path must never ask for.
+#[derive(Debug)]
+struct ReportEverything;
+
+impl PrecompileResourceReporter for ReportEverything {
+    fn resource_usage(
+        &self,
+        _address: &Address,
+        _input: &[u8],
+        _result: &InterpreterResult,
+    ) -> Option<PrecompileResourceUsage> {
+        Some(PrecompileResourceUsage { data_size: 1, kv_updates: 1, state_growth: 1 })
+    }
+}
+
+/// A contract that writes a fresh storage slot and calls the identity precompile.
+fn writer_code() -> Bytes {
+    BytecodeBuilder::default()
+        .sstore(U256::ZERO, U256::from(1))
+        .append_many([PUSH0, PUSH0, PUSH0, PUSH0, PUSH0])
+        .push_address(IDENTITY)
+        .append(GAS)
+        .append(CALL)
+        .append(POP)
+        .stop()
+        .build()
+}
+
+fn transact(spec: MegaSpecId, tx: TxEnv) -> (bool, LimitUsage) {
+    let mut db = MemoryDatabase::default()
+        .account_balance(CALLER, U256::from(10).pow(U256::from(18)))
+        .account_code(CALLEE, writer_code());
+    let context = context_without_operator_fee(&mut db, spec)
+        .with_precompile_resource_reporter(ReportEverything);
+    let mut evm = MegaEvm::new(context);
+    let mut tx = MegaTransaction::new(tx);
+    tx.enveloped_tx = Some(Bytes::new());
+    let r = alloy_evm::Evm::transact_raw(&mut evm, tx).unwrap();
+    let usage = evm.ctx_ref().additional_limit.borrow().get_usage();
+    (r.result.is_success(), usage)
+}
+
+#[test]
+fn test_equivalence_skips_limiter_hooks() {
+    let tx = TxEnvBuilder::default().caller(CALLER).call(CALLEE).gas_limit(10_000_000).build_fill();
+
+    let (success, usage) = transact(MegaSpecId::EQUIVALENCE, tx.clone());
+    assert!(success);
+    let LimitUsage { compute_gas, data_size, kv_updates, state_growth, .. } = usage;
+    assert_eq!(
+        (compute_gas, data_size, kv_updates, state_growth),
+        (0, 0, 0, 0),
+        "EQUIVALENCE must not record any resource usage"
+    );
+
+    // The same transaction is metered once the `MegaETH` hooks apply.
+    let (success, usage) = transact(MegaSpecId::MINI_REX, tx);
+    assert!(success);
+    assert!(usage.compute_gas > 0);
+    assert_eq!(usage.state_growth, 2, "the written slot and the reported precompile usage");
+}
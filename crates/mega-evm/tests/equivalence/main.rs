@@ -1,3 +1,4 @@
 //! Tests for Equivalence spec (baseline EVM behavior).
 
 mod evm_state;
+mod fast_path;
@@ -0,0 +1,49 @@
+//! Beneficiary access conformance vectors.
+//!
+//! The generated vectors must follow the documented detention, and the vectors exported to
+//! `tests/golden/vectors` must still match the current execution. After an intended change,
+//! re-export them with `cargo run -p mega-fixtures --bin export-beneficiary-vectors` and review
+//! the diff.
+
+use std::{collections::BTreeSet, path::Path};
+
+use mega_evm::test_utils::{
+    beneficiary_access_vectors, beneficiary_access_vectors_json, check_beneficiary_access_vector,
+    load_beneficiary_access_vectors, BENEFICIARY_VECTORS_FILE,
+};
+
+#[test]
+fn test_generated_vectors_follow_documented_detention() {
+    let vectors = beneficiary_access_vectors();
+    let names: BTreeSet<_> = vectors.iter().map(|vector| vector.name.as_str()).collect();
+    assert_eq!(names.len(), vectors.len(), "vector names are unique");
+
+    let failures: Vec<_> =
+        vectors.iter().filter_map(|vector| check_beneficiary_access_vector(vector).err()).collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn test_exported_vectors_are_unchanged() {
+    let path = Path::new(BENEFICIARY_VECTORS_FILE);
+    if !path.exists() {
+        eprintln!("no vectors at {}, export them to check them", path.display());
+        return;
+    }
+    let exported = load_beneficiary_access_vectors(path).unwrap();
+    let failures: Vec<_> = exported
+        .iter()
+        .filter_map(|vector| check_beneficiary_access_vector(vector).err())
+        .collect();
+    assert!(
+        failures.is_empty(),
+        "beneficiary access vectors changed; if intended, run \
+         `cargo run -p mega-fixtures --bin export-beneficiary-vectors`:\n{}",
+        failures.join("\n")
+    );
+    assert_eq!(
+        beneficiary_access_vectors_json(&exported),
+        beneficiary_access_vectors_json(&beneficiary_access_vectors()),
+        "the exported vector set is outdated"
+    );
+}
@@ -0,0 +1,35 @@
+//! Golden execution trace regression tests.
+//!
+//! Every case of `mega_evm::test_utils::golden_corpus` is traced under every spec and compared
+//! with its golden trace under `tests/golden/traces`. After an intended change, regenerate the
+//! traces with `cargo run -p mega-fixtures --bin bless-golden-traces` and review the diff.
+
+mod beneficiary_vectors;
+
+use std::path::Path;
+
+use mega_evm::test_utils::{check_golden_traces, golden_corpus, golden_trace, GOLDEN_TRACES_DIR};
+
+#[test]
+fn test_golden_traces_are_unchanged() {
+    let report = check_golden_traces(Path::new(GOLDEN_TRACES_DIR)).unwrap();
+    // A new case may land before its traces are blessed.
+    for path in &report.missing {
+        eprintln!("no golden trace at {}, bless it to check it", path.display());
+    }
+    assert!(
+        report.mismatches.is_empty(),
+        "golden traces changed; if intended, run \
+         `cargo run -p mega-fixtures --bin bless-golden-traces`:\n{}",
+        report.mismatches.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n")
+    );
+}
+
+#[test]
+fn test_golden_traces_are_deterministic() {
+    for case in golden_corpus() {
+        let trace = golden_trace(&case, mega_evm::MegaSpecId::REX4);
+        assert_eq!(trace, golden_trace(&case, mega_evm::MegaSpecId::REX4), "{}", case.name);
+        assert!(trace.lines().count() > 3, "{} runs bytecode", case.name);
+    }
+}
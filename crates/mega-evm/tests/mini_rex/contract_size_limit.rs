@@ -36,9 +36,9 @@ fn initcode_size_limit_test_case(spec: MegaSpecId, initcode_size: usize, success
     } else {
         assert!(matches!(
             result,
-            Err(EVMError::Transaction(MegaTransactionError::Base(
+            Err(EVMError::Transaction(MegaTransactionError::Base(OpTransactionError::Base(
                 InvalidTransaction::CreateInitCodeSizeLimit
-            )))
+            ))))
         ));
     }
 }
@@ -311,3 +311,38 @@ fn test_minirex_create_minirex_max_contract_size_plus_one() {
         false,
     );
 }
+
+/// Test that verifies an EOF (EIP-7692) container cannot be deployed under any spec.
+///
+/// EOF is not supported and there is no `eof-experimental` feature: the pinned revm does not
+/// implement EOF, so a constructor returning an EOF container header is rejected by EIP-3541
+/// before the contract size limit or data size accounting applies. Revisit once revm ships EOF.
+#[test]
+fn test_eof_container_deployment_rejected() {
+    // Returns the EOF magic and version `0xEF0001` as the runtime code.
+    let init_code = BytecodeBuilder::default()
+        .mstore(0, [0xEF, 0x00, 0x01])
+        .push_number(3_u8)
+        .push_number(0_u8)
+        .append(RETURN)
+        .build();
+    for spec in [MegaSpecId::EQUIVALENCE, MegaSpecId::MINI_REX, MegaSpecId::REX, MegaSpecId::REX6] {
+        let mut db = MemoryDatabase::default();
+        let result = deploy_contract(&mut db, init_code.clone(), spec);
+        assert!(
+            matches!(
+                result,
+                Ok(ResultAndState {
+                    result: ExecutionResult::Halt {
+                        reason: MegaHaltReason::Base(OpHaltReason::Base(
+                            EthHaltReason::CreateContractStartingWithEF
+                        )),
+                        ..
+                    },
+                    ..
+                })
+            ),
+            "EOF container must be rejected under {spec:?}: {result:?}"
+        );
+    }
+}
@@ -10,5 +10,6 @@ mod disallow_selfdestruct;
 mod gas;
 mod mega_system_transaction;
 mod oracle;
+mod precompile_resource_usage;
 mod state_growth_limit;
 mod tx_data_and_kv_update_limit;
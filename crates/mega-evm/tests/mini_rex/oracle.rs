@@ -439,7 +439,7 @@ fn test_oracle_storage_sload_uses_oracle_env() {
     let external_envs = TestExternalEnvs::<std::convert::Infallible>::new()
         .with_oracle_storage(test_slot, oracle_value);
 
-    let (result, _evm, oracle_accessed) =
+    let (result, evm, oracle_accessed) =
         execute_transaction(MegaSpecId::MINI_REX, &mut db, &external_envs, NoOpInspector, CALLEE);
 
     // Verify the transaction succeeded
@@ -447,6 +447,9 @@ fn test_oracle_storage_sload_uses_oracle_env() {
 
     // Verify oracle was accessed
     assert!(oracle_accessed, "Oracle should have been accessed");
+
+    // Verify the read is reported with the value served by the oracle env
+    assert_eq!(evm.get_accessed_oracle_slots(), [(test_slot, oracle_value)].into());
 }
 
 /// Test that SLOAD on oracle contract falls back to database when OracleEnv returns None.
@@ -498,7 +501,7 @@ fn test_oracle_storage_sload_fallback_to_database() {
     // Create external envs WITHOUT setting oracle storage (so it returns None)
     let external_envs = TestExternalEnvs::<std::convert::Infallible>::new();
 
-    let (result, _evm, oracle_accessed) =
+    let (result, evm, oracle_accessed) =
         execute_transaction(MegaSpecId::MINI_REX, &mut db, &external_envs, NoOpInspector, CALLEE);
 
     // Verify the transaction succeeded
@@ -506,6 +509,9 @@ fn test_oracle_storage_sload_fallback_to_database() {
 
     // Verify oracle was accessed
     assert!(oracle_accessed, "Oracle should have been accessed");
+
+    // Verify the read is reported with the value loaded from the database
+    assert_eq!(evm.get_accessed_oracle_slots(), [(test_slot, db_value)].into());
 }
 
 /// Test that SLOAD works correctly when transaction directly calls the oracle contract.
@@ -0,0 +1,129 @@
+//! Tests for the resource usage reported by stateful precompiles.
+//!
+//! These tests stand in for a stateful precompile with a reporter that charges every successful
+//! call to the identity precompile, and verify that the reported usage is enforced like the usage
+//! of an `SSTORE`.
+
+use std::convert::Infallible;
+
+use alloy_primitives::{address, Address, Bytes, U256};
+use mega_evm::{
+    test_utils::{context_without_operator_fee, BytecodeBuilder, MemoryDatabase},
+    EvmTxRuntimeLimits, LimitUsage, MegaEvm, MegaHaltReason, MegaSpecId, MegaTransaction,
+    MegaTransactionError, PrecompileResourceReporter, PrecompileResourceUsage,
+};
+use revm::{
+    bytecode::opcode::*,
+    context::{
+        result::{EVMError, ResultAndState},
+        tx::TxEnvBuilder,
+        TxEnv,
+    },
+    handler::EvmTr,
+    interpreter::InterpreterResult,
+};
+
+// Test addresses
+const CALLER: Address = address!("0000000000000000000000000000000000100000");
+const CALLEE: Address = address!("0000000000000000000000000000000000100001");
+const IDENTITY: Address = address!("0000000000000000000000000000000000000004");
+
+/// Reports one written storage slot for every call to the identity precompile.
+#[derive(Debug)]
+struct IdentityWritesSlot;
+
+impl PrecompileResourceReporter for IdentityWritesSlot {
+    fn resource_usage(
+        &self,
+        address: &Address,
+        _input: &[u8],
+        _result: &InterpreterResult,
+    ) -> Option<PrecompileResourceUsage> {
+        (*address == IDENTITY).then_some(PrecompileResourceUsage {
+            data_size: 64,
+            kv_updates: 1,
+            state_growth: 1,
+        })
+    }
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn transact(
+    db: &mut MemoryDatabase,
+    limits: EvmTxRuntimeLimits,
+    tx: TxEnv,
+) -> Result<(ResultAndState<MegaHaltReason>, LimitUsage), EVMError<Infallible, MegaTransactionError>>
+{
+    let context = context_without_operator_fee(db, MegaSpecId::MINI_REX)
+        .with_tx_runtime_limits(limits)
+        .with_precompile_resource_reporter(IdentityWritesSlot);
+    let mut evm = MegaEvm::new(context);
+    let mut tx = MegaTransaction::new(tx);
+    tx.enveloped_tx = Some(Bytes::new());
+    let r = alloy_evm::Evm::transact_raw(&mut evm, tx)?;
+    let usage = evm.ctx_ref().additional_limit.borrow().get_usage();
+    Ok((r, usage))
+}
+
+/// Bytecode that calls the identity precompile, then runs `end`.
+fn call_identity(end: u8) -> Bytes {
+    BytecodeBuilder::default()
+        .append_many([PUSH0, PUSH0, PUSH0, PUSH0, PUSH0])
+        .push_address(IDENTITY)
+        .append(GAS)
+        .append(CALL)
+        .append(POP)
+        .append(PUSH0)
+        .append(PUSH0)
+        .append(end)
+        .build()
+}
+
+fn db_with_callee(code: Bytes) -> MemoryDatabase {
+    MemoryDatabase::default()
+        .account_balance(CALLER, U256::from(1_000_000))
+        .account_code(CALLEE, code)
+}
+
+fn tx() -> TxEnv {
+    TxEnvBuilder::default().caller(CALLER).call(CALLEE).gas_limit(1_000_000).build_fill()
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[test]
+fn test_precompile_usage_is_recorded() {
+    let mut db = db_with_callee(call_identity(RETURN));
+    let (without_call, base) =
+        transact(&mut db_with_callee(Bytes::new()), EvmTxRuntimeLimits::no_limits(), tx()).unwrap();
+    let (result, usage) = transact(&mut db, EvmTxRuntimeLimits::no_limits(), tx()).unwrap();
+
+    assert!(without_call.result.is_success());
+    assert!(result.result.is_success(), "tx should succeed: {result:?}");
+    assert_eq!(usage.state_growth, 1, "the reported slot is state growth");
+    assert_eq!(usage.kv_updates - base.kv_updates, 1, "the reported KV update is recorded");
+    assert_eq!(usage.data_size - base.data_size, 64, "the reported data size is recorded");
+}
+
+#[test]
+fn test_precompile_usage_discarded_on_revert() {
+    let mut db = db_with_callee(call_identity(REVERT));
+    let (result, usage) = transact(&mut db, EvmTxRuntimeLimits::no_limits(), tx()).unwrap();
+
+    assert!(!result.result.is_success(), "the callee reverts");
+    assert_eq!(usage.state_growth, 0, "the reverted frame's precompile usage is discarded");
+}
+
+#[test]
+fn test_precompile_usage_enforces_state_growth_limit() {
+    let mut db = db_with_callee(call_identity(RETURN));
+    let limits = EvmTxRuntimeLimits::no_limits().with_tx_state_growth_limit(0);
+    let (result, _) = transact(&mut db, limits, tx()).unwrap();
+
+    assert!(!result.result.is_success(), "the reported usage exceeds the limit: {result:?}");
+}
@@ -12,19 +12,19 @@
 //! * `access/volatile.rs:89` — `VolatileDataAccess::as_u8 -> 0` and `-> 1`. Killed by asserting the
 //!   exact bit-position discriminant of several distinct variants (so neither constant matches all
 //!   of them).
-//! * `access/tracker.rs:131` — `has_accessed_beneficiary_balance -> true`. Killed by asserting it
+//! * `access/tracker.rs:136` — `has_accessed_beneficiary_balance -> true`. Killed by asserting it
 //!   is `false` on a fresh tracker.
 //! * `access/tracker.rs:104` — `get_volatile_data_info -> Some(Default::default())`. Killed by
 //!   asserting it is `None` on a fresh (no-access) tracker; the mutant returns `Some(empty)`.
-//! * `access/tracker.rs:180` — `disable_access` depth guard (`>=` vs `<`, and the match-guard
+//! * `access/tracker.rs:185` — `disable_access` depth guard (`>=` vs `<`, and the match-guard
 //!   replaced with `true` / `false`). Killed by re-disabling at a deeper / shallower depth and
 //!   observing whether the shallower depth is retained.
-//! * `evm/mod.rs:107` — `Debug for MegaEvm::fmt -> Ok(())`. Killed by asserting the formatted
+//! * `evm/mod.rs:135` — `Debug for MegaEvm::fmt -> Ok(())`. Killed by asserting the formatted
 //!   output contains the struct name and field; the mutant writes nothing.
-//! * `evm/mod.rs:251` — `block_env_mut -> Box::leak(Box::new(Default::default()))`. Killed by
+//! * `evm/mod.rs:287` — `block_env_mut -> Box::leak(Box::new(Default::default()))`. Killed by
 //!   mutating a field through `block_env_mut` and reading it back via `block_env_ref`; the mutant
 //!   writes to a throwaway leaked env so the read shows the original value.
-//! * `evm/mod.rs:379` — `get_accessed_bucket_ids -> vec![]` and `vec![Default::default()]`. Killed
+//! * `evm/mod.rs:495` — `get_accessed_bucket_ids -> vec![]` and `vec![Default::default()]`. Killed
 //!   by executing an `SSTORE`-set transaction under `TestExternalEnvs` (non-zero bucket IDs) and
 //!   asserting the returned IDs are non-empty and equal the predicted (non-zero) bucket ID.
 
@@ -249,14 +249,14 @@ fn test_get_accessed_bucket_ids_reports_touched_non_zero_bucket() {
 }
 
 // ============================================================================
-// evm/context.rs:521 — MegaContext::disable_beneficiary
+// evm/context.rs:1024 — MegaContext::disable_beneficiary
 // ============================================================================
 
 /// Runs a simple value-transfer transaction whose entire gas price is a priority fee (block
 /// `basefee` is 0), and returns the block beneficiary's (coinbase's) post-transaction balance.
 ///
 /// When `disable` is true, `MegaContext::disable_beneficiary()` is called before execution; the
-/// consumer at `evm/execution.rs:689` then skips `reward_beneficiary`, so the coinbase is NOT
+/// consumer at `evm/execution.rs:1558` then skips `reward_beneficiary`, so the coinbase is NOT
 /// credited the priority fee. Operator fees are zeroed so the only thing crediting the beneficiary
 /// is the priority-fee reward.
 fn beneficiary_balance_after_transfer(disable: bool) -> U256 {
@@ -320,7 +320,7 @@ fn test_disable_beneficiary_suppresses_coinbase_reward() {
 }
 
 // ============================================================================
-// evm/execution.rs:167 — MegaHandler::before_execution intrinsic-gas boundary
+// evm/execution.rs:197 — MegaHandler::before_execution intrinsic-gas boundary
 // ============================================================================
 
 /// Executes a plain call (no calldata, no access list, no value) to an existing code account that
@@ -328,7 +328,7 @@ fn test_disable_beneficiary_suppresses_coinbase_reward() {
 ///
 /// Under REX the intrinsic gas of such a call is exactly `21000 + TX_INTRINSIC_STORAGE_GAS`
 /// (no calldata words, no new-account creation since the callee already has code and the value is
-/// zero). `MegaHandler::before_execution` (`evm/execution.rs:167`) halts the tx out-of-gas when
+/// zero). `MegaHandler::before_execution` (`evm/execution.rs:197`) halts the tx out-of-gas when
 /// `gas_limit < initial_gas`. Operator fees and the basefee are zeroed so the only thing that can
 /// make the tx fail at the boundary is that intrinsic-gas guard.
 fn intrinsic_boundary_result(
@@ -365,7 +365,7 @@ fn intrinsic_boundary_result(
 }
 
 /// `before_execution` must allow a tx whose `gas_limit` equals its (fully Mega-adjusted) intrinsic
-/// gas, and reject one below it. This pins the `<` boundary at `evm/execution.rs:167`.
+/// gas, and reject one below it. This pins the `<` boundary at `evm/execution.rs:197`.
 ///
 /// Under REX the intrinsic gas of the bare call above is `21000 + TX_INTRINSIC_STORAGE_GAS`
 /// (= 60000). With `gas_limit == initial_gas` the real `gas_limit < initial_gas` check is `false`,
@@ -405,7 +405,7 @@ fn test_before_execution_allows_exact_intrinsic_gas() {
 }
 
 // ============================================================================
-// evm/context.rs:563 — MegaContext::on_new_block refreshes the dynamic gas cache
+// evm/context.rs:1092 — MegaContext::on_new_block refreshes the dynamic gas cache
 // ============================================================================
 
 /// `on_new_block()` (invoked by `with_block`) must refresh the dynamic storage gas-cost
@@ -307,17 +307,17 @@ fn test_hardfork_schedule_testnet_arm_returns_testnet_schedule() {
 //
 // `BlockLimiter` (`pub`, re-exported via `block::*`) is constructed directly with
 // `BlockLimiter::new(BlockLimits)` and its `pub` usage counters are written directly,
-// mirroring the inline `#[cfg(test)]` pattern at `limit.rs:1027+`. We start from
+// mirroring the inline `#[cfg(test)]` pattern at `limit.rs:1105+`. We start from
 // `BlockLimits::no_limits()` (every limit == `u64::MAX`) and tighten exactly the one
 // dimension under test, so the other clauses/checks cannot interfere.
 //
 // Covered survivors:
-// * `pre_execution_check` boundary `>` checks (778/789/802/839): an at-limit value must return `Ok`
+// * `pre_execution_check` boundary `>` checks (860/871/884/921): an at-limit value must return `Ok`
 //   (kills both `>`→`==` and `>`→`>=`, which error at equality); a strictly over-limit value must
 //   return the expected `Err`.
-// * `post_execution_update` (947) `==`→`!=` on the deposit-type check: a non-deposit tx must
+// * `post_execution_update` (1030) `==`→`!=` on the deposit-type check: a non-deposit tx must
 //   advance `block_da_size_used`; a deposit tx must not. The `!=` mutant inverts both.
-// * `is_block_limit_reached` (1011-1019): one per-dimension test with only that dimension at `used
+// * `is_block_limit_reached` (1095-1101): one per-dimension test with only that dimension at `used
 //   == limit` (others strictly below) asserting `true` kills that clause's `>=`→`<` and the
 //   adjacent `||`→`&&` (only one true clause ⇒ `&&` collapses to false); an all-below test
 //   asserting `false` kills the whole-fn `->true`, and the per-dimension trues kill the whole-fn
@@ -353,9 +353,19 @@ fn outcome_for(
             },
             state: EvmState::default(),
             data_size: 0,
+            da_data_size: 0,
             kv_updates: 0,
             compute_gas_used: 0,
             state_growth_used: 0,
+            accessed_block_hashes: Default::default(),
+            accessed_oracle_slots: Default::default(),
+            journal: None,
+            volatile_data_accessed: Default::default(),
+            detained_gas_refund: None,
+            call_graph: None,
+            journal_stats: None,
+            db_io: None,
+            opcode_profile: None,
         },
     }
 }
@@ -464,7 +474,7 @@ fn test_pre_execution_check_block_da_size_boundary() {
 }
 
 // ---------------------------------------------------------------------------
-// post_execution_update — deposit-type branch (947 `==`→`!=`)
+// post_execution_update — deposit-type branch (1030 `==`→`!=`)
 // ---------------------------------------------------------------------------
 
 /// 947 (`outcome.tx.tx().ty() == DEPOSIT_TRANSACTION_TYPE`). A non-deposit (legacy) tx must
@@ -484,7 +494,7 @@ fn test_post_execution_update_advances_da_for_non_deposit() {
 }
 
 // ---------------------------------------------------------------------------
-// is_block_limit_reached — per-dimension boundary + all-clause OR (1011-1019)
+// is_block_limit_reached — per-dimension boundary + all-clause OR (1095-1101)
 // ---------------------------------------------------------------------------
 
 /// All counters strictly below their limits ⇒ `false`. Kills the whole-fn `->true` mutant.
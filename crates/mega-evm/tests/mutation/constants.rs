@@ -16,7 +16,7 @@ use mega_evm::constants::{mini_rex, rex};
 
 /// Pins `mini_rex::ADDITIONAL_INITCODE_SIZE` (`24 * 1024`).
 ///
-/// Kills `constants.rs:25:52 replace * with /` and `:25:52 replace * with +`.
+/// Kills `constants.rs:32:52 replace * with /` and `:32:52 replace * with +`.
 #[test]
 fn test_mini_rex_additional_initcode_size() {
     assert_eq!(mini_rex::ADDITIONAL_INITCODE_SIZE, 24 * 1024);
@@ -26,7 +26,7 @@ fn test_mini_rex_additional_initcode_size() {
 /// Pins `mini_rex::MAX_INITCODE_SIZE`
 /// (`MAX_CONTRACT_SIZE + ADDITIONAL_INITCODE_SIZE`).
 ///
-/// Kills `constants.rs:27:60 replace + with -`.
+/// Kills `constants.rs:34:60 replace + with -`.
 #[test]
 fn test_mini_rex_max_initcode_size() {
     // 512 * 1024 + 24 * 1024 = 524_288 + 24_576
@@ -39,8 +39,8 @@ fn test_mini_rex_max_initcode_size() {
 
 /// Pins `mini_rex::BLOCK_DATA_LIMIT` (`12 * 1024 * 1024 + 512 * 1024`, 12.5 MB).
 ///
-/// Kills `constants.rs:59:62 replace * with +`, `:59:56 replace + with -`,
-/// `:59:62 replace * with /`, and `:59:56 replace + with *`.
+/// Kills `constants.rs:66:62 replace * with +`, `:66:56 replace + with -`,
+/// `:66:62 replace * with /`, and `:66:56 replace + with *`.
 #[test]
 fn test_mini_rex_block_data_limit() {
     assert_eq!(mini_rex::BLOCK_DATA_LIMIT, 12 * 1024 * 1024 + 512 * 1024);
@@ -49,7 +49,7 @@ fn test_mini_rex_block_data_limit() {
 
 /// Pins `mini_rex::TX_DATA_LIMIT` (`BLOCK_DATA_LIMIT * 25 / 100`, 25% of block).
 ///
-/// Kills `constants.rs:62:58 replace / with *` and `:62:53 replace * with +`.
+/// Kills `constants.rs:69:58 replace / with *` and `:69:53 replace * with +`.
 #[test]
 fn test_mini_rex_tx_data_limit() {
     assert_eq!(mini_rex::TX_DATA_LIMIT, mini_rex::BLOCK_DATA_LIMIT * 25 / 100);
@@ -59,7 +59,7 @@ fn test_mini_rex_tx_data_limit() {
 /// Pins `mini_rex::TX_KV_UPDATE_LIMIT`
 /// (`BLOCK_KV_UPDATE_LIMIT * 25 / 100`, 25% of block).
 ///
-/// Kills `constants.rs:68:68 replace / with *` and `:68:63 replace * with +`.
+/// Kills `constants.rs:75:68 replace / with *` and `:75:63 replace * with +`.
 #[test]
 fn test_mini_rex_tx_kv_update_limit() {
     assert_eq!(mini_rex::TX_KV_UPDATE_LIMIT, mini_rex::BLOCK_KV_UPDATE_LIMIT * 25 / 100);
@@ -69,7 +69,7 @@ fn test_mini_rex_tx_kv_update_limit() {
 /// Pins `rex::TX_DATA_LIMIT` (`12 * 1024 * 1024 + 512 * 1024`, same as the
 /// block data limit).
 ///
-/// Kills the full operator-swap cluster on line 188: `+:53 with *`/`with -`,
+/// Kills the full operator-swap cluster on line 206: `+:53 with *`/`with -`,
 /// `*:46 with +`/`with /`, `*:59 with +`/`with /`, and `*:39 with /`/`with +`.
 #[test]
 fn test_rex_tx_data_limit() {
@@ -6,13 +6,13 @@
 //! tests re-assert the same invariants through the crate's public / `test-utils`-gated API.
 //!
 //! Covered survivors:
-//! * `block/executor.rs:70` — `Debug::fmt` replaced with `Ok(Default::default())`. The real impl
+//! * `block/executor.rs:92` — `Debug::fmt` replaced with `Ok(Default::default())`. The real impl
 //!   writes the struct name; the mutant writes nothing. We pin the formatted output.
-//! * `block/executor.rs:297` — `post_execution_changes -> Ok(vec![])`. The real body *always*
+//! * `block/executor.rs:434` — `post_execution_changes -> Ok(vec![])`. The real body *always*
 //!   pushes exactly one `PostBlock(BalanceIncrements)` outcome (because
 //!   `eips::transact_balance_increments` unconditionally returns `Some(state)`), so a fresh
 //!   executor's `post_execution_changes()` returns a length-1 vec. The mutant returns length 0.
-//! * `block/executor.rs:496` — `get_accessed_bucket_ids -> vec![]` and `->
+//! * `block/executor.rs:987` — `get_accessed_bucket_ids -> vec![]` and `->
 //!   vec![Default::default()]` (i.e. `vec![0]`). After a transaction that performs an `SSTORE`
 //!   (zero→non-zero), the dynamic storage gas tracker records the slot's bucket id. With
 //!   `TestExternalEnvs`'s default `SimpleBucketHasher`, that id lies in `[NUM_META_BUCKETS,
@@ -44,7 +44,7 @@
 //! can construct — and no non-test caller anywhere in `mega-evm` invokes them. So no public-API
 //! input distinguishes the mutants.
 //!
-//! `external/mod.rs:94` (`EmptyExternalEnv::external_envs` →
+//! `external/mod.rs:105` (`EmptyExternalEnv::external_envs` →
 //! `ExternalEnvs::from(Default::default())`) is provably equivalent and is recorded in
 //! `mutants/suppressions.toml` instead of being killed here: `EmptyExternalEnv` is a unit struct,
 //! so both the real body (`ExternalEnvs { salt_env: EmptyExternalEnv, oracle_env: EmptyExternalEnv
@@ -121,7 +121,7 @@ macro_rules! build_executor {
 }
 
 // ============================================================================
-// block/executor.rs:70 — Debug::fmt
+// block/executor.rs:92 — Debug::fmt
 // ============================================================================
 
 /// `Debug` for `MegaBlockExecutor` must write the struct name. The mutant returns `Ok(())`
@@ -141,7 +141,7 @@ fn test_block_executor_debug_writes_struct_name() {
 }
 
 // ============================================================================
-// block/executor.rs:297 — post_execution_changes
+// block/executor.rs:434 — post_execution_changes
 // ============================================================================
 
 /// `post_execution_changes` always produces exactly one `PostBlock(BalanceIncrements)` outcome,
@@ -172,7 +172,7 @@ fn test_post_execution_changes_yields_balance_increment_outcome() {
 }
 
 // ============================================================================
-// block/executor.rs:496 — get_accessed_bucket_ids
+// block/executor.rs:987 — get_accessed_bucket_ids
 // ============================================================================
 
 /// A contract that writes a non-zero value to a previously-zero storage slot, then stops.
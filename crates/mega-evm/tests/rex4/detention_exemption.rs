@@ -0,0 +1,120 @@
+//! Tests for the detention exemption of trusted contracts.
+//!
+//! A detention-exempt contract reading the block environment still has the access recorded, but
+//! the transaction's compute gas limit is not lowered. Contracts it calls are detained as usual.
+
+use std::convert::Infallible;
+
+use alloy_primitives::{address, Address, Bytes, U256};
+use mega_evm::{
+    test_utils::{context_without_operator_fee, BytecodeBuilder, MemoryDatabase},
+    DetentionExemptions, EvmTxRuntimeLimits, MegaEvm, MegaHaltReason, MegaSpecId, MegaTransaction,
+    MegaTransactionError, VolatileDataAccess,
+};
+use revm::{
+    bytecode::opcode::*,
+    context::{
+        result::{EVMError, ExecutionResult, ResultAndState},
+        tx::TxEnvBuilder,
+    },
+    handler::EvmTr,
+};
+
+// ============================================================================
+// TEST ADDRESSES
+// ============================================================================
+
+const CALLER: Address = address!("0000000000000000000000000000000000310000");
+const TRUSTED: Address = address!("0000000000000000000000000000000000310001");
+const READER: Address = address!("0000000000000000000000000000000000310002");
+
+/// A small block env detention cap, so that the burn after the access exceeds it.
+const BLOCK_ENV_CAP: u64 = 10_000;
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// Appends about 50k gas of PUSH0/POP pairs, well beyond [`BLOCK_ENV_CAP`].
+fn append_burn_gas(mut builder: BytecodeBuilder) -> BytecodeBuilder {
+    for _ in 0..10_000 {
+        builder = builder.append(PUSH0).append(POP);
+    }
+    builder
+}
+
+/// Reads `TIMESTAMP`, then burns more compute gas than the detention cap allows.
+fn timestamp_then_burn() -> Bytes {
+    append_burn_gas(BytecodeBuilder::default().append(TIMESTAMP).append(POP)).stop().build()
+}
+
+/// Calls `target`, then stops.
+fn call(target: Address) -> Bytes {
+    BytecodeBuilder::default()
+        .append_many([PUSH0, PUSH0, PUSH0, PUSH0, PUSH0])
+        .push_address(target)
+        .append(GAS)
+        .append(CALL)
+        .append(POP)
+        .stop()
+        .build()
+}
+
+fn transact(
+    db: &mut MemoryDatabase,
+    callee: Address,
+    exemptions: DetentionExemptions,
+) -> Result<
+    (ResultAndState<MegaHaltReason>, VolatileDataAccess),
+    EVMError<Infallible, MegaTransactionError>,
+> {
+    let context = context_without_operator_fee(db, MegaSpecId::REX4)
+        .with_tx_runtime_limits(
+            EvmTxRuntimeLimits::no_limits().with_block_env_access_compute_gas_limit(BLOCK_ENV_CAP),
+        )
+        .with_detention_exemptions(exemptions);
+    let mut evm = MegaEvm::new(context);
+    let tx = TxEnvBuilder::default().caller(CALLER).call(callee).gas_limit(1_000_000).build_fill();
+    let mut tx = MegaTransaction::new(tx);
+    tx.enveloped_tx = Some(Bytes::new());
+    let r = alloy_evm::Evm::transact_raw(&mut evm, tx)?;
+    let accessed = evm.ctx_ref().volatile_data_tracker.borrow().get_volatile_data_accessed();
+    Ok((r, accessed))
+}
+
+fn is_volatile_data_access_oog(result: &ExecutionResult<MegaHaltReason>) -> bool {
+    matches!(
+        result,
+        &ExecutionResult::Halt { reason: MegaHaltReason::VolatileDataAccessOutOfGas { .. }, .. }
+    )
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[test]
+fn test_exempt_contract_is_not_detained() {
+    let mut db = MemoryDatabase::default()
+        .account_balance(CALLER, U256::from(1_000_000))
+        .account_code(TRUSTED, timestamp_then_burn());
+
+    let (result, _) = transact(&mut db, TRUSTED, DetentionExemptions::default()).unwrap();
+    assert!(is_volatile_data_access_oog(&result.result), "unexpected result: {result:?}");
+
+    let (result, accessed) =
+        transact(&mut db, TRUSTED, DetentionExemptions::new([TRUSTED])).unwrap();
+    assert!(result.result.is_success(), "the exempt contract is not detained: {result:?}");
+    assert!(accessed.contains(VolatileDataAccess::TIMESTAMP), "the access is still recorded");
+}
+
+#[test]
+fn test_contract_called_by_exempt_contract_is_detained() {
+    let mut db = MemoryDatabase::default()
+        .account_balance(CALLER, U256::from(1_000_000))
+        .account_code(TRUSTED, call(READER))
+        .account_code(READER, timestamp_then_burn());
+
+    let (result, _) = transact(&mut db, TRUSTED, DetentionExemptions::new([TRUSTED])).unwrap();
+    assert!(is_volatile_data_access_oog(&result.result), "unexpected result: {result:?}");
+}
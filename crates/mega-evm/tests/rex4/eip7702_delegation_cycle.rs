@@ -42,8 +42,6 @@ use revm::{
         tx::TxEnvBuilder,
         TxEnv,
     },
-    database::AccountState,
-    state::Bytecode,
 };
 
 const CALLER: Address = address!("0000000000000000000000000000000000500000");
@@ -71,19 +69,6 @@ fn transact(
     alloy_evm::Evm::transact_raw(&mut evm, tx)
 }
 
-/// Sets EIP-7702 delegation bytecode on an account, making it delegate to `delegate_to`.
-///
-/// This produces the same `0xef0100 || address` designator that revm's
-/// `apply_eip7702_auth_list` writes during Type 4 transaction processing.
-fn set_eip7702_delegation(db: &mut MemoryDatabase, address: Address, delegate_to: Address) {
-    let bytecode = Bytecode::new_eip7702(delegate_to);
-    let code_hash = bytecode.hash_slow();
-    let account = db.load_account(address).unwrap();
-    account.info.code = Some(bytecode);
-    account.info.code_hash = code_hash;
-    account.account_state = AccountState::None;
-}
-
 /// Builds a contract that CALLs `target` with zero value and empty calldata.
 fn build_call_contract(target: Address) -> Bytes {
     BytecodeBuilder::default()
@@ -155,7 +140,7 @@ fn test_self_delegation_via_call() {
     db.set_account_code(PARENT, build_call_contract(SELF_DELEGATING));
     db.set_account_balance(PARENT, U256::from(1_000_000_000u64));
 
-    set_eip7702_delegation(&mut db, SELF_DELEGATING, SELF_DELEGATING);
+    db.set_account_delegation(SELF_DELEGATING, SELF_DELEGATING);
     db.set_account_balance(SELF_DELEGATING, U256::from(1_000_000u64));
 
     let tx =
@@ -176,8 +161,8 @@ fn test_two_address_cycle_via_call() {
     db.set_account_code(PARENT, build_call_contract(CYCLE_A));
     db.set_account_balance(PARENT, U256::from(1_000_000_000u64));
 
-    set_eip7702_delegation(&mut db, CYCLE_A, CYCLE_B);
-    set_eip7702_delegation(&mut db, CYCLE_B, CYCLE_A);
+    db.set_account_delegation(CYCLE_A, CYCLE_B);
+    db.set_account_delegation(CYCLE_B, CYCLE_A);
     db.set_account_balance(CYCLE_A, U256::from(1_000_000u64));
     db.set_account_balance(CYCLE_B, U256::from(1_000_000u64));
 
@@ -197,7 +182,7 @@ fn test_self_delegation_via_staticcall() {
     db.set_account_code(PARENT, build_staticcall_contract(SELF_DELEGATING));
     db.set_account_balance(PARENT, U256::from(1_000_000_000u64));
 
-    set_eip7702_delegation(&mut db, SELF_DELEGATING, SELF_DELEGATING);
+    db.set_account_delegation(SELF_DELEGATING, SELF_DELEGATING);
     db.set_account_balance(SELF_DELEGATING, U256::from(1_000_000u64));
 
     let tx =
@@ -216,8 +201,8 @@ fn test_two_address_cycle_via_delegatecall() {
     db.set_account_code(PARENT, build_delegatecall_contract(CYCLE_A));
     db.set_account_balance(PARENT, U256::from(1_000_000_000u64));
 
-    set_eip7702_delegation(&mut db, CYCLE_A, CYCLE_B);
-    set_eip7702_delegation(&mut db, CYCLE_B, CYCLE_A);
+    db.set_account_delegation(CYCLE_A, CYCLE_B);
+    db.set_account_delegation(CYCLE_B, CYCLE_A);
     db.set_account_balance(CYCLE_A, U256::from(1_000_000u64));
     db.set_account_balance(CYCLE_B, U256::from(1_000_000u64));
 
@@ -237,7 +222,7 @@ fn test_self_delegation_via_callcode() {
     db.set_account_code(PARENT, build_callcode_contract(SELF_DELEGATING));
     db.set_account_balance(PARENT, U256::from(1_000_000_000u64));
 
-    set_eip7702_delegation(&mut db, SELF_DELEGATING, SELF_DELEGATING);
+    db.set_account_delegation(SELF_DELEGATING, SELF_DELEGATING);
     db.set_account_balance(SELF_DELEGATING, U256::from(1_000_000u64));
 
     let tx =
@@ -257,7 +242,7 @@ fn test_direct_tx_to_self_delegating() {
     let mut db = MemoryDatabase::default();
     db.set_account_balance(CALLER, U256::from(1_000_000_000u64));
 
-    set_eip7702_delegation(&mut db, SELF_DELEGATING, SELF_DELEGATING);
+    db.set_account_delegation(SELF_DELEGATING, SELF_DELEGATING);
     db.set_account_balance(SELF_DELEGATING, U256::from(1_000_000u64));
 
     let tx = TxEnvBuilder::default()
@@ -322,7 +307,7 @@ fn test_self_delegation_via_create() {
     db.set_account_balance(CALLER, U256::from(1_000_000_000u64));
 
     // CYCLE_A delegates to CYCLE_B, CYCLE_B has CREATE code
-    set_eip7702_delegation(&mut db, CYCLE_A, CYCLE_B);
+    db.set_account_delegation(CYCLE_A, CYCLE_B);
     db.set_account_balance(CYCLE_A, U256::from(1_000_000_000u64));
     db.set_account_code(CYCLE_B, build_create_contract());
     db.set_account_balance(CYCLE_B, U256::from(1_000_000_000u64));
@@ -350,10 +335,10 @@ fn test_delegation_cycle_via_create2() {
 
     // CYCLE_A delegates to CYCLE_B (which has CREATE2 code).
     // CYCLE_B also delegates back to CYCLE_A to form the cycle.
-    set_eip7702_delegation(&mut db, CYCLE_A, CYCLE_B);
+    db.set_account_delegation(CYCLE_A, CYCLE_B);
     db.set_account_balance(CYCLE_A, U256::from(1_000_000_000u64));
     db.set_account_code(CYCLE_B, build_create2_contract());
-    set_eip7702_delegation(&mut db, CYCLE_B, CYCLE_A);
+    db.set_account_delegation(CYCLE_B, CYCLE_A);
     db.set_account_balance(CYCLE_B, U256::from(1_000_000_000u64));
 
     db.set_account_code(PARENT, build_call_contract(CYCLE_A));
@@ -438,7 +423,7 @@ fn run_inspect_storage_pollution_scenario(spec: MegaSpecId) -> ResultAndState<Me
     db.set_account_balance(DELEGATE, U256::from(1_000_000u64));
 
     // DELEGATOR: delegates to DELEGATE, storage[1] = 42
-    set_eip7702_delegation(&mut db, DELEGATOR, DELEGATE);
+    db.set_account_delegation(DELEGATOR, DELEGATE);
     db.set_account_storage(DELEGATOR, U256::from(1), U256::from(42));
     db.set_account_balance(DELEGATOR, U256::from(1_000_000u64));
 
@@ -20,9 +20,9 @@ use std::convert::Infallible;
 use alloy_primitives::{address, Address, Bytes, U256};
 use alloy_sol_types::SolError;
 use mega_evm::{
-    test_utils::{BytecodeBuilder, MemoryDatabase},
-    EvmTxRuntimeLimits, MegaContext, MegaEvm, MegaHaltReason, MegaLimitExceeded, MegaSpecId,
-    MegaTransaction, MegaTransactionError,
+    test_utils::{context_without_operator_fee, BytecodeBuilder, GasInspector, MemoryDatabase},
+    EvmTxRuntimeLimits, MegaEvm, MegaHaltReason, MegaLimitExceeded, MegaSpecId, MegaTransaction,
+    MegaTransactionError, VolatileDataAccess,
 };
 use revm::{
     bytecode::opcode::*,
@@ -67,15 +67,11 @@ fn transact_with_spec(
     tx: TxEnv,
 ) -> Result<(ResultAndState<MegaHaltReason>, u64, u64), EVMError<Infallible, MegaTransactionError>>
 {
-    let mut context = MegaContext::new(db, spec).with_tx_runtime_limits(
+    let context = context_without_operator_fee(db, spec).with_tx_runtime_limits(
         EvmTxRuntimeLimits::no_limits()
             .with_tx_compute_gas_limit(compute_gas_limit)
             .with_block_env_access_compute_gas_limit(block_env_access_limit),
     );
-    context.modify_chain(|chain| {
-        chain.operator_fee_scalar = Some(U256::from(0));
-        chain.operator_fee_constant = Some(U256::from(0));
-    });
     let mut evm = MegaEvm::new(context);
     let mut tx = MegaTransaction::new(tx);
     tx.enveloped_tx = Some(Bytes::new());
@@ -293,3 +289,51 @@ fn test_pre_rex4_absolute_cap_preserved() {
         "Pre-REX4 detained limit should be the absolute cap (20M)"
     );
 }
+
+/// The gas refunded when detention halts a transaction is reported on the outcome, since it is
+/// settled after the last frame and invisible to inspectors. A traced run reports the same refund,
+/// which the tracer attaches to the transaction.
+#[test]
+fn test_detained_gas_refund_is_reported() {
+    let execute = |code: Bytes, inspector: Option<&mut GasInspector>| {
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(1_000_000))
+            .account_code(CALLEE, code);
+        let context = context_without_operator_fee(&mut db, MegaSpecId::REX4)
+            .with_tx_runtime_limits(
+                EvmTxRuntimeLimits::no_limits().with_block_env_access_compute_gas_limit(10_000),
+            );
+        let mut tx = MegaTransaction::new(default_tx());
+        tx.enveloped_tx = Some(Bytes::new());
+        match inspector {
+            Some(inspector) => {
+                let mut evm = MegaEvm::new(context).with_inspector(inspector);
+                let outcome = evm.execute_transaction(tx).unwrap();
+                assert_eq!(evm.ctx_ref().detained_gas_refund(), outcome.detained_gas_refund);
+                outcome
+            }
+            None => MegaEvm::new(context).execute_transaction(tx).unwrap(),
+        }
+    };
+
+    let builder = BytecodeBuilder::default().append(TIMESTAMP).append(POP);
+    let code = append_burn_gas(builder, 50_000).stop().build();
+    let outcome = execute(code.clone(), None);
+    assert!(is_volatile_data_access_oog(&outcome.result), "unexpected result: {outcome:?}");
+    let refund = outcome.detained_gas_refund.expect("detention halted the transaction");
+    assert!(refund.access_type.contains(VolatileDataAccess::TIMESTAMP));
+    assert!(refund.compute_gas_used > refund.detained_limit);
+    assert!(refund.refunded_gas > 0);
+    assert!(outcome.result.gas_used() < default_tx().gas_limit, "the refund is settled");
+
+    let mut tracer = GasInspector::new();
+    let traced = execute(code, Some(&mut tracer));
+    assert!(!tracer.records().is_empty(), "the tracer observed the execution");
+    assert_eq!(traced.detained_gas_refund, Some(refund));
+    assert_eq!(traced.result.gas_used(), outcome.result.gas_used());
+
+    let outcome =
+        execute(BytecodeBuilder::default().append(TIMESTAMP).append(POP).stop().build(), None);
+    assert!(outcome.result.is_success());
+    assert_eq!(outcome.detained_gas_refund, None);
+}
@@ -4,6 +4,7 @@ mod access_control;
 mod beneficiary_detention;
 mod create_safety;
 mod deployment;
+mod detention_exemption;
 mod eip7702_delegation_cycle;
 mod frame_limits;
 mod frame_state_growth;
@@ -28,8 +28,6 @@ use mega_evm::{
 use revm::{
     bytecode::opcode::{CALL, CALLCODE, STOP},
     context::{result::ResultAndState, TxEnv},
-    database::AccountState,
-    state::Bytecode,
 };
 
 const CALLER: Address = address!("2000000000000000000000000000000000000001");
@@ -40,17 +38,6 @@ const EMPTY_TARGET: Address = address!("3000000000000000000000000000000000000001
 /// holds the actual CALLCODE-emitting runtime bytecode.
 const DELEGATE: Address = address!("4000000000000000000000000000000000000001");
 
-/// Writes an EIP-7702 designator (`0xef0100 || delegate_to`) into `address`,
-/// mirroring what revm's `apply_eip7702_auth_list` does for Type 4 transactions.
-fn set_eip7702_delegation(db: &mut MemoryDatabase, address: Address, delegate_to: Address) {
-    let bytecode = Bytecode::new_eip7702(delegate_to);
-    let code_hash = bytecode.hash_slow();
-    let account = db.load_account(address).unwrap();
-    account.info.code = Some(bytecode);
-    account.info.code_hash = code_hash;
-    account.account_state = AccountState::None;
-}
-
 /// Builds bytecode that performs `CALLCODE(gas=GAS, target, value=1, args=[], ret=[])`
 /// followed by `STOP`. The CALL stipend covers gas inside the (empty-code) callee.
 fn callcode_bytecode(target: Address) -> Bytes {
@@ -187,7 +174,7 @@ fn test_rex5_callcode_from_eip7702_authority_no_storage_gas() {
             .account_balance(CALLER, U256::from(1_000_000_000_000u64))
             .account_balance(CALLEE, U256::from(1_000_000_000u64))
             .account_code(DELEGATE, bytecode.clone());
-        set_eip7702_delegation(&mut db, CALLEE, DELEGATE);
+        db.set_account_delegation(CALLEE, DELEGATE);
 
         let authority_bucket = TestExternalEnvs::<Infallible>::bucket_id_for_account(CALLEE);
         let target_bucket = TestExternalEnvs::<Infallible>::bucket_id_for_account(EMPTY_TARGET);
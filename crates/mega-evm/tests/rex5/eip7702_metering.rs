@@ -24,9 +24,7 @@ use mega_evm::{
 use revm::{
     bytecode::opcode::*,
     context::{result::ResultAndState, tx::TxEnvBuilder, TxEnv},
-    database::AccountState,
     handler::EvmTr,
-    state::Bytecode,
 };
 
 // ============================================================================
@@ -62,19 +60,6 @@ fn transact(
     (r, usage)
 }
 
-/// Sets EIP-7702 delegation bytecode on an account, making it delegate to `delegate_to`.
-///
-/// This produces the same `0xef0100 || address` designator that revm's
-/// `apply_eip7702_auth_list` writes during Type 4 transaction processing.
-fn set_eip7702_delegation(db: &mut MemoryDatabase, address: Address, delegate_to: Address) {
-    let bytecode = Bytecode::new_eip7702(delegate_to);
-    let code_hash = bytecode.hash_slow();
-    let account = db.load_account(address).unwrap();
-    account.info.code = Some(bytecode);
-    account.info.code_hash = code_hash;
-    account.account_state = AccountState::None;
-}
-
 /// Builds a contract that CALLs `target` with 1 wei value and empty calldata.
 ///
 /// The value transfer is necessary to trigger the new-account storage gas logic in
@@ -119,7 +104,7 @@ fn test_call_with_value_to_eip7702_authority_rex5_vs_rex4() {
         db.set_account_code(PARENT, build_call_with_value_contract(AUTHORITY));
         // Set up authority: delegates to EMPTY_DELEGATE (which does not exist).
         // Give authority some balance so it is a "real" account in the DB.
-        set_eip7702_delegation(&mut db, AUTHORITY, EMPTY_DELEGATE);
+        db.set_account_delegation(AUTHORITY, EMPTY_DELEGATE);
         db.set_account_balance(AUTHORITY, U256::from(1_000u64));
         db
     };
@@ -171,7 +156,7 @@ fn test_rex5_call_to_eip7702_authority_no_state_growth() {
         .account_balance(CALLER, U256::from(1_000_000_000u64))
         .account_balance(PARENT, U256::from(1_000_000_000u64));
     db.set_account_code(PARENT, build_call_with_value_contract(AUTHORITY));
-    set_eip7702_delegation(&mut db, AUTHORITY, EMPTY_DELEGATE);
+    db.set_account_delegation(AUTHORITY, EMPTY_DELEGATE);
     db.set_account_balance(AUTHORITY, U256::from(1_000u64));
 
     let tx =
@@ -248,7 +233,7 @@ fn test_rex5_create_inside_delegated_frame_uses_authority_nonce() {
         let mut db =
             MemoryDatabase::default().account_balance(CALLER, U256::from(1_000_000_000u64));
         db.set_account_code(delegate_addr, delegate_code.clone());
-        set_eip7702_delegation(&mut db, AUTHORITY, delegate_addr);
+        db.set_account_delegation(AUTHORITY, delegate_addr);
         db.set_account_balance(AUTHORITY, U256::from(1_000_000u64));
         db.set_account_nonce(AUTHORITY, authority_nonce);
 
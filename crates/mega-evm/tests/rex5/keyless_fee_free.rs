@@ -28,9 +28,7 @@ use revm::{
         result::{HaltReason, OutOfGasError},
         TxEnv,
     },
-    database::AccountState,
     inspector::NoOpInspector,
-    state::Bytecode,
     Database as _,
 };
 
@@ -690,20 +688,6 @@ fn test_rex5_signer_has_code_check_respects_disable_eip3607() {
     assert_eq!(account_info(&mut db2, clean_signer).nonce, 1, "clean signer nonce must bump");
 }
 
-/// Installs an EIP-7702 delegation designator (`0xef0100 || delegate_to`) at `address`,
-/// matching what revm's `apply_eip7702_auth_list` writes during Type-4 tx processing.
-/// `MemoryDatabase::set_account_code` only writes `Bytecode::new_legacy(..)`, which would
-/// not satisfy `Bytecode::is_eip7702()` even if the bytes match the EIP-7702 magic, so
-/// the test wires the proper variant directly.
-fn set_eip7702_delegation_at(db: &mut MemoryDatabase, address: Address, delegate_to: Address) {
-    let bytecode = Bytecode::new_eip7702(delegate_to);
-    let code_hash = bytecode.hash_slow();
-    let account = db.load_account(address).unwrap();
-    account.info.code = Some(bytecode);
-    account.info.code_hash = code_hash;
-    account.account_state = AccountState::None;
-}
-
 // ============================================================================
 // 10. EIP-7702 delegated signer is allowed through the EIP-3607 pre-check
 // ============================================================================
@@ -722,7 +706,7 @@ fn test_rex5_signer_with_eip7702_delegation_passes_eip3607_check() {
     let mut db = MemoryDatabase::default();
     db.set_account_balance(RELAYER, U256::from(1_000_000_000u64));
     let delegate_to = address!("dddddddddddddddddddddddddddddddddddd0001");
-    set_eip7702_delegation_at(&mut db, signer, delegate_to);
+    db.set_account_delegation(signer, delegate_to);
 
     let external_envs = TestExternalEnvs::<std::convert::Infallible>::new();
     let result = run_keyless_outer(
@@ -19,6 +19,7 @@ mod keyless_empty_code_logs;
 mod keyless_fee_free;
 mod keyless_gas_cap_postcap_recheck;
 mod keyless_replay_barrier;
+mod oracle_fast_path;
 mod oracle_hint_metering;
 mod pre_block_system_calls;
 mod precompile_compute_gas;
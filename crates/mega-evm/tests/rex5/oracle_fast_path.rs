@@ -0,0 +1,205 @@
+//! Tests for the oracle `getSlot` fast path (`MegaContext::with_oracle_fast_path`).
+//!
+//! The fast path serves `getSlot(uint256)` from the `OracleEnv` without running the oracle
+//! bytecode. It must be indistinguishable from the bytecode path: same gas, same return data,
+//! same compute gas usage and detention, same accessed oracle slots. Calls it cannot reproduce
+//! exactly fall back to the bytecode.
+
+use std::collections::BTreeMap;
+
+use alloy_primitives::{address, Address, Bytes, B256, U256};
+use alloy_sol_types::SolCall;
+use mega_evm::{
+    test_utils::{context_without_operator_fee, BytecodeBuilder, GasInspector, MemoryDatabase},
+    IOracle, MegaEvm, MegaHaltReason, MegaSpecId, MegaTransaction, TestExternalEnvs,
+    ORACLE_CONTRACT_ADDRESS, ORACLE_CONTRACT_CODE, ORACLE_CONTRACT_CODE_REX2,
+    ORACLE_CONTRACT_CODE_REX5,
+};
+use revm::{
+    bytecode::opcode::*,
+    context::{result::ExecutionResult, tx::TxEnvBuilder},
+    handler::EvmTr,
+    inspector::NoOpInspector,
+};
+
+const CALLER: Address = address!("0000000000000000000000000000000000600000");
+const CALLER_CONTRACT: Address = address!("0000000000000000000000000000000000600001");
+
+/// Oracle slot populated in the `OracleEnv`.
+const ENV_SLOT: U256 = U256::from_limbs([7, 0, 0, 0]);
+const ENV_VALUE: U256 = U256::from_limbs([0xdead, 0xbeef, 0, 0]);
+/// Oracle slot only present in the database.
+const DB_SLOT: U256 = U256::from_limbs([9, 0, 0, 0]);
+const DB_VALUE: U256 = U256::from_limbs([0x1234, 0, 0, 0]);
+
+/// Builds bytecode that STATICCALLs `getSlot(slot)` on the oracle `calls` times with `forward_gas`
+/// and returns the success flag and the 32-byte result of the last call.
+fn build_get_slot_bytecode(slot: U256, calls: usize, forward_gas: u64) -> Bytes {
+    let calldata = IOracle::getSlotCall { slot }.abi_encode();
+    let mut builder = BytecodeBuilder::default().mstore(0x40, &calldata);
+    for _ in 0..calls {
+        builder = builder
+            .push_number(32u64) // retSize
+            .push_number(0x20u64) // retOffset
+            .push_number(calldata.len() as u64) // argsSize
+            .push_number(0x40u64) // argsOffset
+            .push_address(ORACLE_CONTRACT_ADDRESS)
+            .push_number(forward_gas)
+            .append(STATICCALL)
+            .push_number(0u64)
+            .append(MSTORE);
+    }
+    builder.push_number(64u64).push_number(0u64).append(RETURN).build()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Outcome {
+    result: ExecutionResult<MegaHaltReason>,
+    compute_gas_used: u64,
+    compute_gas_limit: u64,
+    accessed_oracle_slots: BTreeMap<U256, U256>,
+}
+
+fn run(spec: MegaSpecId, oracle_code: Bytes, caller_code: Bytes, fast_path: bool) -> Outcome {
+    let external_envs = TestExternalEnvs::<std::convert::Infallible>::new()
+        .with_oracle_storage(ENV_SLOT, ENV_VALUE);
+    let mut db = MemoryDatabase::default()
+        .account_balance(CALLER, U256::from(10_000_000))
+        .account_code(CALLER_CONTRACT, caller_code)
+        .account_code(ORACLE_CONTRACT_ADDRESS, oracle_code);
+    db.set_account_storage(ORACLE_CONTRACT_ADDRESS, DB_SLOT, DB_VALUE);
+    let context = context_without_operator_fee(&mut db, spec)
+        .with_external_envs((&external_envs).into())
+        .with_oracle_fast_path(fast_path);
+
+    let tx = TxEnvBuilder::default()
+        .caller(CALLER)
+        .call(CALLER_CONTRACT)
+        .gas_limit(100_000_000)
+        .build_fill();
+    let mut evm = MegaEvm::new(context).with_inspector(NoOpInspector);
+    let mut tx = MegaTransaction::new(tx);
+    tx.enveloped_tx = Some(Bytes::new());
+    let envelope = alloy_evm::Evm::transact_raw(&mut evm, tx).expect("transact ok");
+    let additional_limit = evm.ctx_ref().additional_limit.borrow();
+    Outcome {
+        result: envelope.result,
+        compute_gas_used: additional_limit.get_usage().compute_gas,
+        compute_gas_limit: additional_limit.compute_gas_limit(),
+        accessed_oracle_slots: evm.ctx_ref().accessed_oracle_slots().clone(),
+    }
+}
+
+/// Asserts that the fast path and the bytecode path produce the same outcome and returns it.
+fn assert_parity(spec: MegaSpecId, oracle_code: Bytes, caller_code: Bytes) -> Outcome {
+    let bytecode = run(spec, oracle_code.clone(), caller_code.clone(), false);
+    let fast_path = run(spec, oracle_code, caller_code, true);
+    assert_eq!(fast_path, bytecode, "fast path must match the bytecode path under {spec:?}");
+    fast_path
+}
+
+fn output_word(outcome: &Outcome, index: usize) -> B256 {
+    let output = outcome.result.output().expect("call output");
+    B256::from_slice(&output[index * 32..(index + 1) * 32])
+}
+
+#[test]
+fn test_fast_path_matches_bytecode_for_every_oracle_version() {
+    for (spec, oracle_code) in [
+        (MegaSpecId::REX3, ORACLE_CONTRACT_CODE),
+        (MegaSpecId::REX3, ORACLE_CONTRACT_CODE_REX2),
+        (MegaSpecId::REX4, ORACLE_CONTRACT_CODE_REX2),
+        (MegaSpecId::REX5, ORACLE_CONTRACT_CODE_REX5),
+        (MegaSpecId::REX6, ORACLE_CONTRACT_CODE_REX5),
+    ] {
+        let outcome =
+            assert_parity(spec, oracle_code, build_get_slot_bytecode(ENV_SLOT, 1, 100_000));
+        assert!(outcome.result.is_success(), "{spec:?}: {:?}", outcome.result);
+        assert_eq!(output_word(&outcome, 0), B256::with_last_byte(1));
+        assert_eq!(output_word(&outcome, 1), B256::from(ENV_VALUE));
+        assert_eq!(outcome.accessed_oracle_slots, BTreeMap::from([(ENV_SLOT, ENV_VALUE)]));
+    }
+}
+
+#[test]
+fn test_fast_path_reads_database_slot_not_in_oracle_env() {
+    let outcome = assert_parity(
+        MegaSpecId::REX5,
+        ORACLE_CONTRACT_CODE_REX5,
+        build_get_slot_bytecode(DB_SLOT, 1, 100_000),
+    );
+    assert!(outcome.result.is_success());
+    assert_eq!(output_word(&outcome, 1), B256::from(DB_VALUE));
+    assert_eq!(outcome.accessed_oracle_slots, BTreeMap::from([(DB_SLOT, DB_VALUE)]));
+}
+
+#[test]
+fn test_fast_path_repeated_calls_match_bytecode() {
+    let outcome = assert_parity(
+        MegaSpecId::REX5,
+        ORACLE_CONTRACT_CODE_REX5,
+        build_get_slot_bytecode(ENV_SLOT, 10, 100_000),
+    );
+    assert!(outcome.result.is_success());
+    assert_eq!(output_word(&outcome, 1), B256::from(ENV_VALUE));
+}
+
+/// A call without enough gas for the whole bytecode run falls back and fails the same way.
+#[test]
+fn test_fast_path_insufficient_gas_falls_back() {
+    let outcome = assert_parity(
+        MegaSpecId::REX5,
+        ORACLE_CONTRACT_CODE_REX5,
+        build_get_slot_bytecode(ENV_SLOT, 1, 2_000),
+    );
+    assert!(outcome.result.is_success());
+    assert_eq!(output_word(&outcome, 0), B256::ZERO, "the inner call must fail");
+}
+
+/// An unknown oracle code is never served by the fast path.
+#[test]
+fn test_fast_path_unknown_oracle_code_falls_back() {
+    let oracle_code = BytecodeBuilder::default()
+        .push_number(0x2au64)
+        .push_number(0u64)
+        .append(MSTORE)
+        .push_number(32u64)
+        .push_number(0u64)
+        .append(RETURN)
+        .build();
+    let outcome =
+        assert_parity(MegaSpecId::REX5, oracle_code, build_get_slot_bytecode(ENV_SLOT, 1, 100_000));
+    assert_eq!(output_word(&outcome, 1), B256::with_last_byte(0x2a));
+    assert!(outcome.accessed_oracle_slots.is_empty());
+}
+
+/// Served calls do not run the oracle bytecode, so no oracle step reaches the inspector.
+#[test]
+fn test_fast_path_skips_oracle_bytecode() {
+    let sloads = |fast_path: bool| {
+        let external_envs = TestExternalEnvs::<std::convert::Infallible>::new()
+            .with_oracle_storage(ENV_SLOT, ENV_VALUE);
+        let mut db = MemoryDatabase::default()
+            .account_balance(CALLER, U256::from(10_000_000))
+            .account_code(CALLER_CONTRACT, build_get_slot_bytecode(ENV_SLOT, 1, 100_000))
+            .account_code(ORACLE_CONTRACT_ADDRESS, ORACLE_CONTRACT_CODE_REX5);
+        let context = context_without_operator_fee(&mut db, MegaSpecId::REX5)
+            .with_external_envs((&external_envs).into())
+            .with_oracle_fast_path(fast_path);
+        let mut inspector = GasInspector::new();
+        let mut evm = MegaEvm::new(context).with_inspector(&mut inspector);
+        let tx = TxEnvBuilder::default()
+            .caller(CALLER)
+            .call(CALLER_CONTRACT)
+            .gas_limit(100_000_000)
+            .build_fill();
+        let mut tx = MegaTransaction::new(tx);
+        tx.enveloped_tx = Some(Bytes::new());
+        let result = alloy_evm::Evm::transact_raw(&mut evm, tx).expect("transact ok").result;
+        assert!(result.is_success());
+        drop(evm);
+        inspector.records().iter().filter(|r| r.opcode.get() == SLOAD).count()
+    };
+    assert_eq!(sloads(false), 1);
+    assert_eq!(sloads(true), 0);
+}
@@ -41,7 +41,6 @@ use revm::{
         tx::TxEnvBuilder,
         BlockEnv, TxEnv,
     },
-    database::AccountState,
     handler::EvmTr,
     state::Bytecode,
 };
@@ -120,17 +119,6 @@ fn transact_with_beneficiary(
     Ok((result, usage, detained, beneficiary_marked))
 }
 
-/// Installs `0xef0100 || delegate_to` at `address`, mirroring what revm's
-/// `apply_eip7702_auth_list` does for Type 4 transactions.
-fn set_eip7702_delegation(db: &mut MemoryDatabase, address: Address, delegate_to: Address) {
-    let bytecode = Bytecode::new_eip7702(delegate_to);
-    let code_hash = bytecode.hash_slow();
-    let account = db.load_account(address).unwrap();
-    account.info.code = Some(bytecode);
-    account.info.code_hash = code_hash;
-    account.account_state = AccountState::None;
-}
-
 /// Builds bytecode that calls `disableVolatileDataAccess()` on the
 /// `MegaAccessControl` system contract.
 fn call_disable_volatile_data_access(builder: BytecodeBuilder) -> BytecodeBuilder {
@@ -295,7 +283,7 @@ fn test_rex6_call_to_eip7702_delegator_to_beneficiary_disabled_reverts() {
         .account_balance(CALLER, U256::from(1_000_000_000u64))
         .account_code(MIDDLE, middle_code)
         .account_code(BENEFICIARY, beneficiary_code);
-    set_eip7702_delegation(&mut db, DELEGATOR_TO_BENEFICIARY, BENEFICIARY);
+    db.set_account_delegation(DELEGATOR_TO_BENEFICIARY, BENEFICIARY);
 
     let tx =
         TxEnvBuilder::default().caller(CALLER).call(MIDDLE).gas_limit(100_000_000).build_fill();
@@ -335,7 +323,7 @@ fn test_rex5_call_to_eip7702_delegator_to_beneficiary_disabled_does_not_revert()
         .account_balance(CALLER, U256::from(1_000_000_000u64))
         .account_code(MIDDLE, middle_code)
         .account_code(BENEFICIARY, beneficiary_code);
-    set_eip7702_delegation(&mut db, DELEGATOR_TO_BENEFICIARY, BENEFICIARY);
+    db.set_account_delegation(DELEGATOR_TO_BENEFICIARY, BENEFICIARY);
 
     let tx =
         TxEnvBuilder::default().caller(CALLER).call(MIDDLE).gas_limit(100_000_000).build_fill();
@@ -376,7 +364,7 @@ fn test_rex6_call_to_eip7702_delegator_to_beneficiary_enabled_marks_beneficiary(
             .account_balance(CALLER, U256::from(1_000_000_000u64))
             .account_code(MIDDLE, middle_code.clone())
             .account_code(BENEFICIARY, beneficiary_code.clone());
-        set_eip7702_delegation(&mut db, DELEGATOR_TO_BENEFICIARY, BENEFICIARY);
+        db.set_account_delegation(DELEGATOR_TO_BENEFICIARY, BENEFICIARY);
         db
     };
     let tx =
@@ -870,7 +858,7 @@ fn test_rex6_call_to_beneficiary_that_delegates_elsewhere_still_reverts() {
         .account_balance(CALLER, U256::from(1_000_000_000u64))
         .account_code(MIDDLE, middle_code);
     // The beneficiary itself delegates to a non-beneficiary address.
-    set_eip7702_delegation(&mut db, BENEFICIARY, EMPTY_NON_BENEFICIARY);
+    db.set_account_delegation(BENEFICIARY, EMPTY_NON_BENEFICIARY);
 
     let (result, _, _, _) =
         transact_with_beneficiary(MegaSpecId::REX6, &mut db, tx_to_middle()).unwrap();
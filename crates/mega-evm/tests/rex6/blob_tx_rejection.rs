@@ -0,0 +1,59 @@
+//! REX6 rejection of EIP-4844 blob transactions.
+//!
+//! `MegaETH` blocks carry no blobs, so REX6 rejects a type-3 transaction during validation with
+//! `InvalidTransaction::Eip4844NotSupported` instead of inheriting the op-revm blob validation and
+//! charging a blob fee no fee vault receives.
+
+use std::convert::Infallible;
+
+use alloy_primitives::{b256, Bytes, TxKind, U256};
+use mega_evm::{
+    test_utils::MemoryDatabase, EVMError, MegaContext, MegaEvm, MegaSpecId, MegaTransaction,
+    MegaTransactionError, OpTransactionError,
+};
+use revm::{
+    context::{result::InvalidTransaction, transaction::TransactionType, ContextTr, TxEnv},
+    ExecuteEvm,
+};
+
+use crate::common::{CALLER, CONTRACT};
+
+/// Runs a funded call to [`CONTRACT`] carrying one blob under REX6, typed as `tx_type`.
+fn transact(tx_type: TransactionType) -> Result<(), EVMError<Infallible, MegaTransactionError>> {
+    let db = MemoryDatabase::default().account_balance(CALLER, U256::from(10).pow(U256::from(18)));
+    let context = MegaContext::new(db, MegaSpecId::REX6);
+    let mut tx = MegaTransaction::new(TxEnv {
+        tx_type: tx_type as u8,
+        caller: CALLER,
+        chain_id: Some(context.cfg().chain_id),
+        kind: TxKind::Call(CONTRACT),
+        gas_limit: 1_000_000,
+        gas_priority_fee: Some(0),
+        blob_hashes: Vec::from([b256!(
+            "0100000000000000000000000000000000000000000000000000000000000001"
+        )]),
+        max_fee_per_blob_gas: 1,
+        ..Default::default()
+    });
+    tx.enveloped_tx = Some(Bytes::new());
+    MegaEvm::new(context).transact(tx).map(|_| ())
+}
+
+#[test]
+fn test_rex6_rejects_blob_transactions() {
+    let err = transact(TransactionType::Eip4844).expect_err("blob tx must be rejected");
+    assert!(
+        matches!(
+            err,
+            EVMError::Transaction(MegaTransactionError::Base(OpTransactionError::Base(
+                InvalidTransaction::Eip4844NotSupported
+            )))
+        ),
+        "expected Eip4844NotSupported, got {err:?}",
+    );
+}
+
+#[test]
+fn test_rex6_accepts_the_same_transaction_as_eip1559() {
+    transact(TransactionType::Eip1559).expect("the EIP-1559 variant should be valid");
+}
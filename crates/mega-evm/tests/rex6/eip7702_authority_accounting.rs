@@ -4,7 +4,9 @@
 //! - net-new authorities are charged dynamic SALT account-creation gas, so a type-4 tx that creates
 //!   an authority in a heavy SALT bucket consumes more gas than it did pre-REX6;
 //! - DataSize/KV are charged only for *applied* authorities (passed the chain-id/nonce/code gates),
-//!   not every recoverable one, so a skipped authorization no longer inflates resource usage.
+//!   not every recoverable one, so a skipped authorization no longer inflates resource usage;
+//! - installing a delegation on an existing account is state growth and clearing one is a refund,
+//!   netted over the authorization list.
 //!
 //! Pre-REX6 keeps the old split (ungated `before_tx_start` DataSize/KV + pre-execution
 //! state-growth scan, no authority SALT gas), frozen for replay parity — the REX5 arms pin it.
@@ -16,7 +18,8 @@ use alloy_primitives::{address, Address, Bytes, U256};
 use mega_evm::{
     constants, test_utils::MemoryDatabase, BucketHasher, EVMError, EvmTxRuntimeLimits, LimitUsage,
     MegaContext, MegaEvm, MegaHaltReason, MegaSpecId, MegaTransaction, MegaTransactionError,
-    SimpleBucketHasher, TestExternalEnvs, ACCOUNT_INFO_WRITE_SIZE, MIN_BUCKET_SIZE,
+    OpTransactionError, SimpleBucketHasher, TestExternalEnvs, ACCOUNT_INFO_WRITE_SIZE,
+    MIN_BUCKET_SIZE,
 };
 use revm::{
     context::{
@@ -151,6 +154,14 @@ fn tx_with_auths(auths: Vec<RecoveredAuthorization>) -> TxEnv {
         .build_fill()
 }
 
+/// An authorization for `authority` delegating to the zero address, which clears its delegation.
+fn auth_clear(authority: Address, chain_id: u64, nonce: u64) -> RecoveredAuthorization {
+    RecoveredAuthorization::new_unchecked(
+        Authorization { chain_id: U256::from(chain_id), address: Address::ZERO, nonce },
+        RecoveredAuthority::Valid(authority),
+    )
+}
+
 fn funded_db() -> MemoryDatabase {
     MemoryDatabase::default()
         .account_balance(CALLER, U256::from(1_000_000_000_000_000_000u64))
@@ -258,9 +269,9 @@ fn test_rex6_authority_salt_gas_enforced_against_gas_limit() {
     assert!(
         matches!(
             err,
-            EVMError::Transaction(MegaTransactionError::Base(
+            EVMError::Transaction(MegaTransactionError::Base(OpTransactionError::Base(
                 InvalidTransaction::CallGasCostMoreThanGasLimit { .. }
-            ))
+            )))
         ),
         "expected CallGasCostMoreThanGasLimit from the unaffordable SALT gas, got {err:?}",
     );
@@ -371,17 +382,19 @@ fn test_rex6_duplicate_authority_applies_twice_grows_once() {
     assert_eq!(authority_nonce(&res_skip2), 1, "only the first authorization applied: nonce once");
 }
 
-/// An applied authority that already exists is charged DataSize/KV but is not state growth.
+/// An applied authority that already exists and is already delegated is charged DataSize/KV but is
+/// not state growth.
 ///
-/// Delegating an account that already exists writes it (data +40, KV +1) but creates no net-new
-/// state entry. Against a net-new authority — same single applied write — the existing one differs
-/// only in the state-growth dimension (0 vs 1).
+/// Re-delegating such an account writes it (data +40, KV +1) but creates no net-new state entry.
+/// Against a net-new authority — same single applied write — the existing one differs only in the
+/// state-growth dimension (0 vs 1).
 #[test]
 fn test_rex6_existing_authority_charged_but_not_state_growth() {
     let envs = no_heavy_buckets();
 
-    // AUTHORITY_A is pre-funded, so it already exists; AUTHORITY_B does not (net-new).
+    // AUTHORITY_A is pre-funded and delegated, so it already exists; AUTHORITY_B does not.
     let mut db_existing = funded_db().account_balance(AUTHORITY_A, U256::from(1u64));
+    db_existing.set_account_delegation(AUTHORITY_A, CALLEE);
     let (res_existing, u_existing) = transact(
         MegaSpecId::REX6,
         &mut db_existing,
@@ -410,10 +423,73 @@ fn test_rex6_existing_authority_charged_but_not_state_growth() {
         "an applied authority charges one KV update either way"
     );
     // Only the net-new authority grows state.
-    assert_eq!(u_existing.state_growth, 0, "delegating an existing account is not state growth");
+    assert_eq!(u_existing.state_growth, 0, "re-delegating an existing account is not state growth");
     assert_eq!(u_new.state_growth, 1, "delegating a net-new account is state growth");
 }
 
+/// Installing a delegation on an existing, undelegated account is state growth under REX6 only.
+#[test]
+fn test_rex6_delegation_install_is_state_growth() {
+    let envs = no_heavy_buckets();
+    let db = || funded_db().account_balance(AUTHORITY_A, U256::from(1u64));
+
+    let (res_rex5, u_rex5) =
+        transact(MegaSpecId::REX5, &mut db(), &envs, tx_with_auths(vec![auth(AUTHORITY_A, 1, 0)]));
+    let (res_rex6, u_rex6) =
+        transact(MegaSpecId::REX6, &mut db(), &envs, tx_with_auths(vec![auth(AUTHORITY_A, 1, 0)]));
+    assert!(res_rex5.result.is_success(), "REX5 tx should succeed: {res_rex5:?}");
+    assert!(res_rex6.result.is_success(), "REX6 tx should succeed: {res_rex6:?}");
+
+    assert_eq!(u_rex5.state_growth, 0, "REX5 does not count delegation installs");
+    assert_eq!(u_rex6.state_growth, 1, "REX6 counts the installed delegation");
+}
+
+/// Clearing an existing delegation releases one unit of state growth, offsetting other growth in
+/// the same transaction.
+#[test]
+fn test_rex6_delegation_clear_refunds_state_growth() {
+    let envs = no_heavy_buckets();
+
+    // AUTHORITY_A is delegated and cleared; AUTHORITY_B is net-new (+1).
+    let mut db = funded_db().account_balance(AUTHORITY_A, U256::from(1u64));
+    db.set_account_delegation(AUTHORITY_A, CALLEE);
+    let (res, usage) = transact(
+        MegaSpecId::REX6,
+        &mut db,
+        &envs,
+        tx_with_auths(vec![auth_clear(AUTHORITY_A, 1, 0), auth(AUTHORITY_B, 1, 0)]),
+    );
+    assert!(res.result.is_success(), "tx should succeed: {res:?}");
+    assert_eq!(usage.state_growth, 0, "the cleared delegation offsets the new authority");
+
+    let authority = res.state.get(&AUTHORITY_A).expect("authority update should be preserved");
+    assert!(authority.info.code.as_ref().is_none_or(|c| c.is_empty()), "delegation is cleared");
+}
+
+/// Setting and then clearing a delegation on the same account within one transaction nets to no
+/// state growth, for both an existing and a net-new authority.
+#[test]
+fn test_rex6_delegation_set_then_clear_nets_zero() {
+    let envs = no_heavy_buckets();
+    let auths = || vec![auth(AUTHORITY_A, 1, 0), auth_clear(AUTHORITY_A, 1, 1)];
+
+    let mut db_existing = funded_db().account_balance(AUTHORITY_A, U256::from(1u64));
+    let (res_existing, u_existing) =
+        transact(MegaSpecId::REX6, &mut db_existing, &envs, tx_with_auths(auths()));
+    assert!(res_existing.result.is_success(), "tx should succeed: {res_existing:?}");
+    assert_eq!(u_existing.state_growth, 0, "set-then-clear on an existing account nets to zero");
+
+    // The net-new authority is still materialized (nonce 2), which is its only growth.
+    let (res_new, u_new) =
+        transact(MegaSpecId::REX6, &mut funded_db(), &envs, tx_with_auths(auths()));
+    assert!(res_new.result.is_success(), "tx should succeed: {res_new:?}");
+    assert_eq!(u_new.state_growth, 1, "only the authority account creation is counted");
+
+    let authority = res_existing.state.get(&AUTHORITY_A).expect("authority should be touched");
+    assert_eq!(authority.info.nonce, 2, "both authorizations applied");
+    assert!(authority.info.code.as_ref().is_none_or(|c| c.is_empty()), "delegation is cleared");
+}
+
 /// A net-new authority that overflows the state-growth limit halts the REX6 tx AND is never
 /// applied. The halt is a HALT (not `Err`), so `apply_eip7702_auth_list`'s pre-frame `mark_touch`
 /// would otherwise persist; the guard skips applying the list. Pins: halt fires, and `AUTHORITY_A`
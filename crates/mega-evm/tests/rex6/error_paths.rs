@@ -99,6 +99,10 @@ fn assert_injected_salt_custom_error(
                 msg.contains("injected salt error"),
                 "error should carry the injected SALT failure, got: {msg}"
             );
+            assert!(
+                msg.starts_with("SALT env query for "),
+                "error should name the failing env and key, got: {msg}"
+            );
         }
         Err(other) => panic!("expected EVMError::Custom, got: {other:?}"),
         Ok(result) => panic!("expected a fatal DB-error halt, got success: {:?}", result.result),
@@ -0,0 +1,80 @@
+//! REX6 per-transaction frame limit.
+//!
+//! From REX6 every frame a transaction creates, including the top-level frame, counts against
+//! `EvmTxRuntimeLimits::tx_frame_limit`, independent of call depth. Creating one frame past the
+//! limit is a TX-level exceed: the transaction halts with `MegaHaltReason::FrameLimitExceeded`.
+//! REX5 and earlier do not cap the number of frames.
+
+use alloy_primitives::{address, Address, Bytes, U256};
+use mega_evm::{
+    test_utils::{BytecodeBuilder, MemoryDatabase},
+    EvmTxRuntimeLimits, MegaHaltReason, MegaSpecId,
+};
+use revm::{
+    bytecode::opcode::{CALL, GAS, POP, PUSH0},
+    context::result::ExecutionResult,
+};
+
+use crate::common::{transact, CALLER, CONTRACT};
+
+/// Callee of the sub-calls; its code only stops.
+const CHILD: Address = address!("0000000000000000000000000000000000200003");
+
+/// Calls [`CHILD`] `calls` times in sequence, each at depth 1.
+fn sequential_calls(calls: usize) -> Bytes {
+    let mut builder = BytecodeBuilder::default();
+    for _ in 0..calls {
+        builder = builder
+            .append_many([PUSH0, PUSH0, PUSH0, PUSH0, PUSH0])
+            .push_address(CHILD)
+            .append(GAS)
+            .append(CALL)
+            .append(POP);
+    }
+    builder.stop().build()
+}
+
+fn db_with(code: Bytes) -> MemoryDatabase {
+    MemoryDatabase::default()
+        .account_balance(CALLER, U256::from(1_000_000_000_000_000_000u128))
+        .account_code(CONTRACT, code)
+        .account_code(CHILD, BytecodeBuilder::default().stop().build())
+}
+
+fn limits(spec: MegaSpecId) -> EvmTxRuntimeLimits {
+    EvmTxRuntimeLimits::from_spec(spec).with_tx_frame_limit(3)
+}
+
+/// The top-level frame plus two sub-calls fit a limit of three frames.
+#[test]
+fn test_rex6_frames_up_to_limit_succeed() {
+    let outcome =
+        transact(MegaSpecId::REX6, db_with(sequential_calls(2)), limits(MegaSpecId::REX6));
+    assert!(outcome.is_success(), "three frames must fit the limit: {:?}", outcome.result);
+}
+
+/// A fourth frame halts the whole transaction, even though no frame is deeper than depth 1.
+#[test]
+fn test_rex6_frame_past_limit_halts_tx() {
+    let outcome =
+        transact(MegaSpecId::REX6, db_with(sequential_calls(3)), limits(MegaSpecId::REX6));
+    assert!(
+        matches!(
+            outcome.result,
+            ExecutionResult::Halt {
+                reason: MegaHaltReason::FrameLimitExceeded { limit: 3, actual: 4 },
+                ..
+            }
+        ),
+        "the fourth frame must halt the transaction: {:?}",
+        outcome.result,
+    );
+}
+
+/// REX5 ignores the frame limit, even when one is configured.
+#[test]
+fn test_rex5_frame_limit_not_enforced() {
+    let outcome =
+        transact(MegaSpecId::REX5, db_with(sequential_calls(3)), limits(MegaSpecId::REX5));
+    assert!(outcome.is_success(), "REX5 must not cap frames: {:?}", outcome.result);
+}
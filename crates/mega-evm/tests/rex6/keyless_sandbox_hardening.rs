@@ -28,12 +28,13 @@ use mega_evm::{
     revm::context::result::ExecutionResult,
     sandbox::{calculate_keyless_deploy_address, decode_error_result, KeylessDeployError},
     test_utils::{BytecodeBuilder, MemoryDatabase},
-    EvmTxRuntimeLimits, IKeylessDeploy, MegaContext, MegaEvm, MegaHaltReason, MegaSpecId,
-    MegaTransaction, TestExternalEnvs, KEYLESS_DEPLOY_ADDRESS,
+    EvmTxRuntimeLimits, IKeylessDeploy, LimitUsage, MegaContext, MegaEvm, MegaHaltReason,
+    MegaSpecId, MegaTransaction, TestExternalEnvs, KEYLESS_DEPLOY_ADDRESS,
 };
 use revm::{
-    bytecode::opcode::{DELEGATECALL, MSTORE8, POP, RETURN, SELFDESTRUCT},
+    bytecode::opcode::{CALL, DELEGATECALL, GAS, MSTORE8, POP, PUSH0, RETURN, SELFDESTRUCT},
     context::TxEnv,
+    handler::EvmTr,
     inspector::NoOpInspector,
 };
 
@@ -46,6 +47,8 @@ const LARGE_GAS_LIMIT_OVERRIDE: u64 = 10_000_000_000;
 /// invokes `SELFDESTRUCT(0)` so a DELEGATECALL from a constructor
 /// destroys the constructor's own (being-created) account.
 const SELFDESTRUCT_HELPER: Address = address!("00000000000000000000000000000000000abcde");
+/// Callee of the constructor calls in the frame limit test; its code only stops.
+const CHILD: Address = address!("00000000000000000000000000000000000abcdf");
 
 // ============================================================================
 // HELPERS
@@ -97,6 +100,29 @@ fn run_keyless_outer(
     tx_compute_gas_limit: Option<u64>,
     outer_gas_limit_override: Option<u64>,
 ) -> ExecutionResult<MegaHaltReason> {
+    let tx_runtime_limits = tx_compute_gas_limit
+        .map(|limit| EvmTxRuntimeLimits::no_limits().with_tx_compute_gas_limit(limit));
+    run_keyless_outer_with_limits(
+        spec,
+        db,
+        keyless_tx_bytes,
+        gas_limit_override,
+        tx_runtime_limits,
+        outer_gas_limit_override,
+    )
+    .0
+}
+
+/// Like [`run_keyless_outer`], but under the given runtime limits, and also returns the parent
+/// transaction's limit usage, sandbox usage merged in.
+fn run_keyless_outer_with_limits(
+    spec: MegaSpecId,
+    db: &mut MemoryDatabase,
+    keyless_tx_bytes: Bytes,
+    gas_limit_override: u64,
+    tx_runtime_limits: Option<EvmTxRuntimeLimits>,
+    outer_gas_limit_override: Option<u64>,
+) -> (ExecutionResult<MegaHaltReason>, LimitUsage) {
     let call_data = IKeylessDeploy::keylessDeployCall {
         keylessDeploymentTransaction: keyless_tx_bytes,
         gasLimitOverride: U256::from(gas_limit_override),
@@ -110,10 +136,8 @@ fn run_keyless_outer(
         chain.operator_fee_constant = Some(U256::ZERO);
     });
 
-    if let Some(limit) = tx_compute_gas_limit {
-        context = context.with_tx_runtime_limits(
-            EvmTxRuntimeLimits::no_limits().with_tx_compute_gas_limit(limit),
-        );
+    if let Some(limits) = tx_runtime_limits {
+        context = context.with_tx_runtime_limits(limits);
     }
 
     let tx = TxEnv {
@@ -130,8 +154,10 @@ fn run_keyless_outer(
     tx.enveloped_tx = Some(Bytes::new());
 
     let mut evm = MegaEvm::new(context).with_inspector(NoOpInspector);
-    alloy_evm::Evm::transact_commit(&mut evm, tx)
-        .expect("outer keyless call should not fail at the EVM-error level")
+    let result = alloy_evm::Evm::transact_commit(&mut evm, tx)
+        .expect("outer keyless call should not fail at the EVM-error level");
+    let usage = evm.ctx_ref().additional_limit.borrow().get_usage();
+    (result, usage)
 }
 
 /// Extract `IKeylessDeploy::keylessDeployReturn` payload from a Success-style outer result.
@@ -449,3 +475,58 @@ fn test_rex6_keyless_constructor_returning_oversized_bytecode_is_rejected() {
         "oversized-bytecode constructor must surface as ExecutionHalted; got {decoded_err:?}",
     );
 }
+
+// ============================================================================
+// Sandbox frames count against the parent's frame limit
+// ============================================================================
+
+/// REX6: the frames a keyless deploy creates in the sandbox count against the parent
+/// transaction's frame limit. The sandbox only gets the frames the parent has left, and its
+/// frame count is merged back into the parent's usage like the other dimensions.
+#[test]
+fn test_rex6_keyless_sandbox_frames_count_against_parent_frame_limit() {
+    // The constructor calls `CHILD` twice and deploys a single `STOP`: with the create frame,
+    // the sandbox creates three frames.
+    let mut builder = BytecodeBuilder::default();
+    for _ in 0..2 {
+        builder = builder
+            .append_many([PUSH0, PUSH0, PUSH0, PUSH0, PUSH0])
+            .push_address(CHILD)
+            .append(GAS)
+            .append(CALL)
+            .append(POP);
+    }
+    let init_code = builder.push_number(1_u8).push_number(0_u8).append(RETURN).build();
+    let (keyless_tx_bytes, signer) = build_keyless_tx_with_init_code(init_code);
+
+    let run = |tx_frame_limit: u64| {
+        let mut db = MemoryDatabase::default();
+        db.set_account_balance(RELAYER, U256::from(1_000_000_000u64));
+        db.set_account_code(CHILD, BytecodeBuilder::default().stop().build());
+        let (result, usage) = run_keyless_outer_with_limits(
+            MegaSpecId::REX6,
+            &mut db,
+            keyless_tx_bytes.clone(),
+            LARGE_GAS_LIMIT_OVERRIDE,
+            Some(EvmTxRuntimeLimits::no_limits().with_tx_frame_limit(tx_frame_limit)),
+            None,
+        );
+        (decode_keyless_return(&result), usage)
+    };
+
+    let (decoded, usage) = run(3);
+    assert_eq!(
+        decoded.deployedAddress,
+        calculate_keyless_deploy_address(signer),
+        "three sandbox frames must fit a limit of three",
+    );
+    assert_eq!(usage.frames_created, 3, "the sandbox frames must be merged into the parent");
+
+    let (decoded, _) = run(2);
+    assert_eq!(decoded.deployedAddress, Address::ZERO, "a third frame must exceed the limit");
+    let decoded_err = decode_error_result(&decoded.errorData);
+    assert!(
+        matches!(decoded_err, Some(KeylessDeployError::ExecutionHalted { .. })),
+        "the sandbox must halt on the frame limit; got {decoded_err:?}",
+    );
+}
@@ -0,0 +1,157 @@
+//! Tests for the `remainingBudgets()` query served at the `MegaLimitControl` address.
+//!
+//! From Rex6 the interceptor returns the remaining budget of every resource limit for the
+//! current call; earlier specs fall through to the on-chain fallback, which reverts with
+//! `NotIntercepted()`.
+
+use alloy_primitives::{address, Address, Bytes, U256};
+use alloy_sol_types::{SolCall, SolError};
+use mega_evm::{
+    test_utils::{context_without_operator_fee, BytecodeBuilder, MemoryDatabase},
+    EvmTxRuntimeLimits, IMegaLimitBudget, MegaEvm, MegaHaltReason, MegaSpecId, MegaTransaction,
+    LIMIT_CONTROL_ADDRESS, LIMIT_CONTROL_CODE,
+};
+use revm::{
+    bytecode::opcode::{POP, RETURN, STATICCALL},
+    context::{
+        result::{ExecutionResult, ResultAndState},
+        tx::TxEnvBuilder,
+        TxEnv,
+    },
+};
+
+const CALLER: Address = address!("0000000000000000000000000000000000300000");
+const CONTRACT: Address = address!("0000000000000000000000000000000000300001");
+
+const REMAINING_BUDGETS_SELECTOR: [u8; 4] = IMegaLimitBudget::remainingBudgetsCall::SELECTOR;
+
+const DATA_SIZE_LIMIT: u64 = 1_000_000;
+const KV_UPDATES_LIMIT: u64 = 1_000;
+const STATE_GROWTH_LIMIT: u64 = 1_000;
+
+fn transact(
+    spec: MegaSpecId,
+    db: &mut MemoryDatabase,
+    tx: TxEnv,
+) -> ResultAndState<MegaHaltReason> {
+    let limits = EvmTxRuntimeLimits::no_limits()
+        .with_tx_data_size_limit(DATA_SIZE_LIMIT)
+        .with_tx_kv_updates_limit(KV_UPDATES_LIMIT)
+        .with_tx_state_growth_limit(STATE_GROWTH_LIMIT);
+    let context = context_without_operator_fee(db, spec).with_tx_runtime_limits(limits);
+    let mut evm = MegaEvm::new(context);
+    let mut tx = MegaTransaction::new(tx);
+    tx.enveloped_tx = Some(Bytes::new());
+    alloy_evm::Evm::transact_raw(&mut evm, tx).expect("tx should not surface EVMError")
+}
+
+fn query_tx(to: Address, value: U256) -> TxEnv {
+    TxEnvBuilder::default()
+        .caller(CALLER)
+        .call(to)
+        .value(value)
+        .gas_limit(100_000_000)
+        .data(Bytes::copy_from_slice(&REMAINING_BUDGETS_SELECTOR))
+        .build_fill()
+}
+
+fn db() -> MemoryDatabase {
+    MemoryDatabase::default()
+        .account_balance(CALLER, U256::from(1_000_000))
+        .account_code(LIMIT_CONTROL_ADDRESS, LIMIT_CONTROL_CODE)
+}
+
+fn decode_budgets(
+    result: &ResultAndState<MegaHaltReason>,
+) -> IMegaLimitBudget::remainingBudgetsReturn {
+    let output = match &result.result {
+        ExecutionResult::Success { output, .. } => output.data().clone(),
+        _ => panic!("expected success output, got: {:?}", result.result),
+    };
+    IMegaLimitBudget::remainingBudgetsCall::abi_decode_returns(&output)
+        .expect("should decode remainingBudgets output")
+}
+
+fn revert_output(result: &ResultAndState<MegaHaltReason>) -> Bytes {
+    match &result.result {
+        ExecutionResult::Revert { output, .. } => output.clone(),
+        _ => panic!("expected revert, got: {:?}", result.result),
+    }
+}
+
+/// Builds a contract that writes `slots` fresh storage slots, then STATICCALLs
+/// `remainingBudgets()` and returns its ABI result.
+fn query_after_sstores(slots: u64) -> Bytes {
+    let mut builder = BytecodeBuilder::default();
+    for slot in 0..slots {
+        builder = builder.sstore(U256::from(slot), U256::from(1));
+    }
+    builder
+        .mstore(0x0, REMAINING_BUDGETS_SELECTOR)
+        .push_number(128_u64) // retSize
+        .push_number(0x20_u64) // retOffset
+        .push_number(4_u64) // argsSize
+        .push_number(0_u64) // argsOffset
+        .push_address(LIMIT_CONTROL_ADDRESS)
+        .push_number(100_000_u64) // gas
+        .append(STATICCALL)
+        .append(POP)
+        .push_number(128_u64)
+        .push_number(0x20_u64)
+        .append(RETURN)
+        .build()
+}
+
+fn budgets_after_sstores(slots: u64) -> IMegaLimitBudget::remainingBudgetsReturn {
+    let mut db = db().account_code(CONTRACT, query_after_sstores(slots));
+    let tx =
+        TxEnvBuilder::default().caller(CALLER).call(CONTRACT).gas_limit(100_000_000).build_fill();
+    decode_budgets(&transact(MegaSpecId::REX6, &mut db, tx))
+}
+
+/// A direct query returns every budget below its limit, net of the intrinsic usage.
+#[test]
+fn test_direct_tx_remaining_budgets() {
+    let result = transact(MegaSpecId::REX6, &mut db(), query_tx(LIMIT_CONTROL_ADDRESS, U256::ZERO));
+    let budgets = decode_budgets(&result);
+
+    assert!(budgets.computeGas > 0);
+    assert!(budgets.dataSize > 0 && budgets.dataSize < DATA_SIZE_LIMIT);
+    assert!(budgets.kvUpdates > 0 && budgets.kvUpdates < KV_UPDATES_LIMIT);
+    assert!(budgets.stateGrowth > 0 && budgets.stateGrowth <= STATE_GROWTH_LIMIT);
+}
+
+/// Storage writes made before the query are reflected in the returned budgets.
+#[test]
+fn test_remaining_budgets_decrease_after_storage_writes() {
+    let before = budgets_after_sstores(0);
+    let after = budgets_after_sstores(2);
+
+    assert!(after.computeGas < before.computeGas);
+    assert!(after.dataSize < before.dataSize);
+    assert_eq!(before.kvUpdates - after.kvUpdates, 2);
+    assert_eq!(before.stateGrowth - after.stateGrowth, 2);
+}
+
+/// The query rejects value-bearing calls like every other limit-control method.
+#[test]
+fn test_remaining_budgets_with_value_reverts() {
+    let result =
+        transact(MegaSpecId::REX6, &mut db(), query_tx(LIMIT_CONTROL_ADDRESS, U256::from(1)));
+    assert_eq!(
+        revert_output(&result).as_ref(),
+        &IMegaLimitBudget::NonZeroTransfer::SELECTOR,
+        "value-bearing query should revert with NonZeroTransfer()"
+    );
+}
+
+/// Before Rex6 the selector is unknown to the interceptor and reaches the on-chain fallback.
+#[test]
+fn test_pre_rex6_remaining_budgets_not_intercepted() {
+    let result = transact(MegaSpecId::REX5, &mut db(), query_tx(LIMIT_CONTROL_ADDRESS, U256::ZERO));
+    assert_eq!(
+        revert_output(&result).as_ref(),
+        &IMegaLimitBudget::NotIntercepted::SELECTOR,
+        "pre-Rex6 query should revert with NotIntercepted()"
+    );
+}
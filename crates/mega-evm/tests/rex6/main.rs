@@ -5,16 +5,22 @@
 //!   authorities (not every recoverable one).
 
 mod beneficiary_detention;
+mod blob_tx_rejection;
 mod common;
 mod create2_metering_order;
 mod create_frame_accounting;
 mod eip7702_authority_accounting;
 mod error_paths;
 mod fee_reward_accounting;
+mod frame_limit;
 mod frame_local_accounting;
 mod keyless_sandbox_hardening;
+mod limit_budget;
+mod memory_limit;
 mod metering_order_parity;
+mod nonce_gap_tolerance;
 mod oracle_hint_volatile_access;
 mod self_transfer_account_dedup;
 mod sequencer_registry_rotation;
+mod simulation_options;
 mod system_tx_metering_exemption;
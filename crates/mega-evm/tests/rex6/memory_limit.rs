@@ -0,0 +1,127 @@
+//! REX6 interpreter memory limit.
+//!
+//! From REX6 the memory-expanding opcodes check the new memory size against
+//! `EvmTxRuntimeLimits::interpreter_memory_limit` before expanding. Crossing it is a TX-level
+//! exceed: the transaction halts with `MegaHaltReason::MemoryLimitExceeded`, whichever frame
+//! expanded, and the remaining gas is rescued like for the other TX-level limits. REX5 and earlier
+//! only bound memory through gas.
+
+use alloy_primitives::{address, Address, Bytes, U256};
+use mega_evm::{
+    test_utils::{BytecodeBuilder, MemoryDatabase},
+    EvmTxRuntimeLimits, MegaHaltReason, MegaSpecId,
+};
+use revm::{
+    bytecode::opcode::{CALL, GAS, MSTORE, MSTORE8, PUSH0, RETURN},
+    context::result::ExecutionResult,
+};
+
+use crate::common::{transact, transact_default, CALLER, CONTRACT};
+
+/// Callee whose code expands memory in the nested-frame case.
+const CHILD: Address = address!("0000000000000000000000000000000000200003");
+
+/// Stores a zero byte at `offset`, expanding memory to `offset + 1` rounded up to a word.
+fn mstore8_at(offset: u64) -> Bytes {
+    BytecodeBuilder::default().append(PUSH0).push_number(offset).append(MSTORE8).stop().build()
+}
+
+/// Calls [`CHILD`] with all remaining gas and no memory arguments.
+fn call_child() -> Bytes {
+    BytecodeBuilder::default()
+        .append_many([PUSH0, PUSH0, PUSH0, PUSH0, PUSH0])
+        .push_address(CHILD)
+        .append(GAS)
+        .append(CALL)
+        .stop()
+        .build()
+}
+
+fn db_with(code: Bytes) -> MemoryDatabase {
+    MemoryDatabase::default()
+        .account_balance(CALLER, U256::from(1_000_000_000_000_000_000u128))
+        .account_code(CONTRACT, code)
+}
+
+fn assert_memory_limit_halt(result: &ExecutionResult<MegaHaltReason>, limit: u64, actual: u64) {
+    assert!(
+        matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: MegaHaltReason::MemoryLimitExceeded { limit: l, actual: a },
+                ..
+            } if *l == limit && *a == actual
+        ),
+        "expected MemoryLimitExceeded {{ limit: {limit}, actual: {actual} }}: {result:?}",
+    );
+}
+
+/// Expanding to exactly the limit succeeds; one more word halts with the expanded size.
+#[test]
+fn test_rex6_memory_limit_boundary() {
+    let limits =
+        EvmTxRuntimeLimits::from_spec(MegaSpecId::REX6).with_interpreter_memory_limit(1_024);
+
+    let ok = transact(MegaSpecId::REX6, db_with(mstore8_at(1_023)), limits);
+    assert!(ok.is_success(), "expansion to the limit must succeed: {:?}", ok.result);
+
+    let exceeded = transact(MegaSpecId::REX6, db_with(mstore8_at(1_024)), limits);
+    assert_memory_limit_halt(&exceeded.result, 1_024, 1_056);
+}
+
+/// The default 64 MiB limit is checked before the expansion is charged, so a transaction that
+/// could not afford the expansion still reports the memory limit rather than running out of gas.
+#[test]
+fn test_rex6_default_memory_limit_halts_before_gas_charge() {
+    let code = BytecodeBuilder::default()
+        .append(PUSH0)
+        .push_number(64u64 * 1024 * 1024)
+        .append(MSTORE)
+        .stop()
+        .build();
+
+    let rex6 = transact_default(MegaSpecId::REX6, db_with(code.clone()));
+    assert_memory_limit_halt(&rex6.result, 64 * 1024 * 1024, 64 * 1024 * 1024 + 32);
+
+    let rex5 = transact_default(MegaSpecId::REX5, db_with(code));
+    assert!(
+        !matches!(
+            rex5.result,
+            ExecutionResult::Halt { reason: MegaHaltReason::MemoryLimitExceeded { .. }, .. }
+        ),
+        "REX5 has no interpreter memory limit: {:?}",
+        rex5.result,
+    );
+    assert!(!rex5.is_success(), "REX5 cannot pay for a 64 MiB expansion");
+}
+
+/// An exceed in a nested frame halts the whole transaction instead of failing only the callee.
+#[test]
+fn test_rex6_memory_limit_in_child_frame_halts_tx() {
+    let limits =
+        EvmTxRuntimeLimits::from_spec(MegaSpecId::REX6).with_interpreter_memory_limit(1_024);
+    let db = db_with(call_child()).account_code(CHILD, mstore8_at(4_096));
+
+    let outcome = transact(MegaSpecId::REX6, db, limits);
+    assert_memory_limit_halt(&outcome.result, 1_024, 4_128);
+}
+
+/// Zero-length accesses never expand memory, whatever their offset.
+#[test]
+fn test_rex6_zero_length_access_ignores_offset() {
+    let limits =
+        EvmTxRuntimeLimits::from_spec(MegaSpecId::REX6).with_interpreter_memory_limit(1_024);
+    // RETURN(offset = 2^64, len = 0)
+    let code = BytecodeBuilder::default()
+        .append(PUSH0)
+        .push_number(u128::from(u64::MAX) + 1)
+        .append(RETURN)
+        .build();
+
+    let outcome = transact(MegaSpecId::REX6, db_with(code), limits);
+    assert!(
+        outcome.is_success(),
+        "zero-length RETURN must not expand memory: {:?}",
+        outcome.result
+    );
+}
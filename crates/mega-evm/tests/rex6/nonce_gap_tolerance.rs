@@ -0,0 +1,162 @@
+//! Nonce gap tolerance (`MegaContext::with_nonce_gap_tolerance`) for simulating future
+//! transactions.
+//!
+//! With the flag set, a transaction nonce above the caller's nonce is accepted and the caller's
+//! nonce is raised to it once the transaction passes validation. A nonce below the caller's nonce
+//! is still rejected, and without the flag the strict nonce equality applies.
+
+use std::convert::Infallible;
+
+use alloy_primitives::{Bytes, TxKind, U256};
+use mega_evm::{
+    test_utils::{BytecodeBuilder, MemoryDatabase},
+    EVMError, EmptyExternalEnv, MegaContext, MegaEvm, MegaHaltReason, MegaSpecId, MegaTransaction,
+    MegaTransactionError, OpTransactionError,
+};
+use revm::{
+    context::{
+        result::{ExecutionResult, InvalidTransaction, Output, ResultAndState},
+        TxEnv,
+    },
+    inspector::NoOpInspector,
+    ExecuteEvm,
+};
+
+use crate::common::{CALLER, CONTRACT};
+
+const STATE_NONCE: u64 = 3;
+
+fn evm(nonce_gap_tolerance: bool) -> MegaEvm<MemoryDatabase, NoOpInspector, EmptyExternalEnv> {
+    let db = MemoryDatabase::default()
+        .account_balance(CALLER, U256::from(10).pow(U256::from(18)))
+        .account_nonce(CALLER, STATE_NONCE)
+        .account_code(CONTRACT, BytecodeBuilder::default().stop().build());
+    MegaEvm::new(
+        MegaContext::new(db, MegaSpecId::REX6).with_nonce_gap_tolerance(nonce_gap_tolerance),
+    )
+}
+
+fn tx(kind: TxKind, tx_nonce: u64) -> MegaTransaction {
+    let mut tx = MegaTransaction::new(TxEnv {
+        caller: CALLER,
+        kind,
+        nonce: tx_nonce,
+        gas_limit: 10_000_000,
+        ..Default::default()
+    });
+    tx.enveloped_tx = Some(Bytes::new());
+    tx
+}
+
+fn transact(
+    kind: TxKind,
+    tx_nonce: u64,
+    nonce_gap_tolerance: bool,
+) -> Result<ResultAndState<MegaHaltReason>, EVMError<Infallible, MegaTransactionError>> {
+    evm(nonce_gap_tolerance).transact(tx(kind, tx_nonce))
+}
+
+fn caller_nonce_after(result: &ResultAndState<MegaHaltReason>) -> u64 {
+    result.state.get(&CALLER).expect("caller is in the state").info.nonce
+}
+
+#[test]
+fn test_nonce_gap_rejected_by_default() {
+    let err = transact(TxKind::Call(CONTRACT), STATE_NONCE + 2, false)
+        .expect_err("a nonce gap must be rejected without the flag");
+    assert!(
+        matches!(
+            err,
+            EVMError::Transaction(MegaTransactionError::Base(OpTransactionError::Base(
+                InvalidTransaction::NonceTooHigh { tx: 5, state: 3 }
+            )))
+        ),
+        "expected NonceTooHigh, got {err:?}",
+    );
+}
+
+#[test]
+fn test_nonce_gap_accepted_with_tolerance() {
+    let result = transact(TxKind::Call(CONTRACT), STATE_NONCE + 2, true)
+        .expect("a nonce gap must be accepted with the flag");
+    assert!(result.result.is_success());
+    assert_eq!(caller_nonce_after(&result), STATE_NONCE + 3, "the bump follows the tx nonce");
+}
+
+#[test]
+fn test_exact_nonce_unchanged_with_tolerance() {
+    let result = transact(TxKind::Call(CONTRACT), STATE_NONCE, true).expect("exact nonce");
+    assert!(result.result.is_success());
+    assert_eq!(caller_nonce_after(&result), STATE_NONCE + 1);
+}
+
+#[test]
+fn test_nonce_too_low_rejected_with_tolerance() {
+    let err = transact(TxKind::Call(CONTRACT), STATE_NONCE - 1, true)
+        .expect_err("a past nonce must still be rejected");
+    assert!(
+        matches!(
+            err,
+            EVMError::Transaction(MegaTransactionError::Base(OpTransactionError::Base(
+                InvalidTransaction::NonceTooLow { tx: 2, state: 3 }
+            )))
+        ),
+        "expected NonceTooLow, got {err:?}",
+    );
+}
+
+#[test]
+fn test_nonce_gap_create_address_follows_tx_nonce() {
+    let tx_nonce = STATE_NONCE + 4;
+    let result = transact(TxKind::Create, tx_nonce, true).expect("create with a nonce gap");
+    let ExecutionResult::Success { output: Output::Create(_, Some(created)), .. } = &result.result
+    else {
+        panic!("expected a successful creation, got {:?}", result.result);
+    };
+    assert_eq!(*created, CALLER.create(tx_nonce));
+    assert_eq!(caller_nonce_after(&result), tx_nonce + 1);
+}
+
+#[test]
+fn test_rejected_nonce_gap_leaves_caller_nonce_unchanged() {
+    let mut evm = evm(true);
+    // The fee exceeds the caller's balance, so the transaction is rejected after validation
+    // closed the gap.
+    let mut unaffordable = tx(TxKind::Call(CONTRACT), STATE_NONCE + 2);
+    unaffordable.base.gas_price = 1_000_000_000_000;
+    let err = evm.transact(unaffordable).expect_err("the fee is not affordable");
+    assert!(
+        matches!(
+            err,
+            EVMError::Transaction(MegaTransactionError::Base(OpTransactionError::Base(
+                InvalidTransaction::LackOfFundForMaxFee { .. }
+            )))
+        ),
+        "expected LackOfFundForMaxFee, got {err:?}",
+    );
+
+    // The raised nonce was discarded with the transaction.
+    let result = evm.transact(tx(TxKind::Call(CONTRACT), STATE_NONCE)).expect("exact nonce");
+    assert!(result.result.is_success());
+    assert_eq!(caller_nonce_after(&result), STATE_NONCE + 1);
+}
+
+#[test]
+fn test_large_nonce_gap_closed_at_once() {
+    let tx_nonce = u64::MAX - 1;
+    let result = transact(TxKind::Call(CONTRACT), tx_nonce, true).expect("a large nonce gap");
+    assert!(result.result.is_success());
+    assert_eq!(caller_nonce_after(&result), u64::MAX);
+}
+
+#[test]
+fn test_rejected_large_nonce_gap_leaves_caller_nonce_unchanged() {
+    let mut evm = evm(true);
+    let mut unaffordable = tx(TxKind::Call(CONTRACT), u64::MAX - 1);
+    unaffordable.base.gas_price = 1_000_000_000_000;
+    evm.transact(unaffordable).expect_err("the fee is not affordable");
+
+    let result = evm.transact(tx(TxKind::Call(CONTRACT), STATE_NONCE)).expect("exact nonce");
+    assert!(result.result.is_success());
+    assert_eq!(caller_nonce_after(&result), STATE_NONCE + 1);
+}
@@ -0,0 +1,139 @@
+//! Simulation options (`MegaContext::with_simulation_options`) for simulating sponsored
+//! transactions.
+//!
+//! With a fee payer override, the fees are checked against and charged to the fee payer, the
+//! unused gas is reimbursed to it, and the caller only pays the transferred value. Disabling the
+//! balance check accepts a caller that cannot afford the transaction at all.
+
+use std::convert::Infallible;
+
+use alloy_primitives::{address, Address, Bytes, TxKind, U256};
+use mega_evm::{
+    test_utils::{context_without_operator_fee, BytecodeBuilder, MemoryDatabase},
+    EVMError, MegaEvm, MegaHaltReason, MegaSpecId, MegaTransaction, MegaTransactionError,
+    OpTransactionError, SimulationOptions,
+};
+use revm::{
+    context::{
+        result::{InvalidTransaction, ResultAndState},
+        TxEnv,
+    },
+    ExecuteEvm,
+};
+
+use crate::common::{CALLER, CONTRACT};
+
+/// Account sponsoring the fees of the caller.
+const SPONSOR: Address = address!("0000000000000000000000000000000000200003");
+const GAS_PRICE: u128 = 1_000;
+const GAS_LIMIT: u64 = 1_000_000;
+const VALUE: u64 = 1_234;
+
+fn transact(
+    caller_balance: u64,
+    sponsor_balance: U256,
+    options: SimulationOptions,
+) -> Result<ResultAndState<MegaHaltReason>, EVMError<Infallible, MegaTransactionError>> {
+    let db = MemoryDatabase::default()
+        .account_balance(CALLER, U256::from(caller_balance))
+        .account_balance(SPONSOR, sponsor_balance)
+        .account_code(CONTRACT, BytecodeBuilder::default().stop().build());
+    let context =
+        context_without_operator_fee(db, MegaSpecId::REX6).with_simulation_options(options);
+    let mut tx = MegaTransaction::new(TxEnv {
+        caller: CALLER,
+        kind: TxKind::Call(CONTRACT),
+        value: U256::from(VALUE),
+        gas_price: GAS_PRICE,
+        gas_limit: GAS_LIMIT,
+        ..Default::default()
+    });
+    tx.enveloped_tx = Some(Bytes::new());
+    MegaEvm::new(context).transact(tx)
+}
+
+fn balance_after(result: &ResultAndState<MegaHaltReason>, address: Address) -> U256 {
+    result.state.get(&address).expect("account is in the state").info.balance
+}
+
+fn sponsored() -> SimulationOptions {
+    SimulationOptions::default().with_fee_payer_override(Some(SPONSOR))
+}
+
+fn assert_lack_of_funds(err: EVMError<Infallible, MegaTransactionError>) {
+    assert!(
+        matches!(
+            err,
+            EVMError::Transaction(MegaTransactionError::Base(OpTransactionError::Base(
+                InvalidTransaction::LackOfFundForMaxFee { .. }
+            )))
+        ),
+        "expected LackOfFundForMaxFee, got {err:?}",
+    );
+}
+
+#[test]
+fn test_unfunded_caller_rejected_by_default() {
+    let err = transact(VALUE, U256::MAX, SimulationOptions::default())
+        .expect_err("the caller cannot afford the fees");
+    assert_lack_of_funds(err);
+}
+
+#[test]
+fn test_fee_payer_pays_fees_and_caller_pays_value() {
+    let sponsor_balance = U256::from(10).pow(U256::from(18));
+    let result = transact(VALUE, sponsor_balance, sponsored()).expect("sponsored transaction");
+    assert!(result.result.is_success());
+
+    let fee = U256::from(result.result.gas_used() as u128 * GAS_PRICE);
+    assert!(!fee.is_zero());
+    assert_eq!(balance_after(&result, SPONSOR), sponsor_balance - fee, "the sponsor pays the fee");
+    assert_eq!(balance_after(&result, CALLER), U256::ZERO, "the caller pays the value only");
+    assert_eq!(balance_after(&result, CONTRACT), U256::from(VALUE));
+    assert_eq!(result.state.get(&CALLER).expect("caller is in the state").info.nonce, 1);
+}
+
+#[test]
+fn test_fee_payer_matches_self_paid_fee() {
+    let funded = U256::from(10).pow(U256::from(18));
+    let self_paid = transact(10u64.pow(18), U256::ZERO, SimulationOptions::default())
+        .expect("self-paid transaction");
+    let sponsored = transact(VALUE, funded, sponsored()).expect("sponsored transaction");
+    assert_eq!(sponsored.result.gas_used(), self_paid.result.gas_used());
+    assert_eq!(
+        funded - balance_after(&sponsored, SPONSOR),
+        U256::from(10u64.pow(18)) - U256::from(VALUE) - balance_after(&self_paid, CALLER),
+    );
+}
+
+#[test]
+fn test_fee_payer_must_afford_max_fee() {
+    let max_fee = U256::from(GAS_LIMIT as u128 * GAS_PRICE);
+    let err = transact(VALUE, max_fee - U256::from(1), sponsored())
+        .expect_err("the sponsor cannot afford the maximum fee");
+    assert_lack_of_funds(err);
+}
+
+#[test]
+fn test_caller_must_afford_value_with_fee_payer() {
+    let err = transact(VALUE - 1, U256::MAX, sponsored())
+        .expect_err("the caller cannot afford the value");
+    assert_lack_of_funds(err);
+}
+
+#[test]
+fn test_disable_balance_check_accepts_unfunded_caller() {
+    let options = SimulationOptions::default().with_disable_balance_check(true);
+    let result = transact(0, U256::ZERO, options).expect("balance check disabled");
+    assert!(result.result.is_success());
+    assert_eq!(balance_after(&result, CONTRACT), U256::from(VALUE));
+}
+
+#[test]
+fn test_disable_balance_check_with_unfunded_fee_payer() {
+    let options = sponsored().with_disable_balance_check(true);
+    let result = transact(0, U256::ZERO, options).expect("balance check disabled");
+    assert!(result.result.is_success());
+    assert_eq!(balance_after(&result, CONTRACT), U256::from(VALUE));
+    assert_eq!(balance_after(&result, CALLER), U256::ZERO);
+}
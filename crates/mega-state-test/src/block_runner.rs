@@ -0,0 +1,211 @@
+//! Execution of [`BlockFixture`]s through `MegaBlockExecutorFactory`.
+//!
+//! Where the state-test runner executes single transactions with `MegaEvm`, this runner executes
+//! whole blocks the way an OP-stack node does: the spec and block limits are resolved from the
+//! hardforks at the block timestamp, pre-execution changes are applied, the transactions are
+//! decoded and executed, and the post-execution changes are committed. The state root, receipts
+//! root and gas used of every block are then checked against the fixture.
+
+use crate::{
+    roots::{receipts_root, state_root},
+    types::{pre_state_cache, BlockFixture, ExpectedBlock, FixtureBlock},
+};
+use mega_evm::{
+    alloy_consensus::transaction::{Recovered, SignerRecoverable},
+    alloy_eips::Decodable2718,
+    alloy_evm::{
+        block::{BlockExecutionError, BlockExecutor},
+        EvmEnv,
+    },
+    alloy_hardforks::ForkCondition,
+    alloy_op_evm::block::OpAlloyReceiptBuilder,
+    revm::{
+        context::{BlockEnv, CfgEnv},
+        database::{EmptyDB, State},
+        primitives::{Bytes, B256, U256},
+    },
+    AHashBucketHasher, BlockLimits, MegaBlockExecutionCtx, MegaBlockExecutorFactory,
+    MegaEvmFactory, MegaHardforkConfig, MegaHardforks, MegaTxEnvelope, TestExternalEnvs,
+};
+use std::convert::Infallible;
+use thiserror::Error;
+
+/// The block executor factory blocks are executed with.
+type FixtureExecutorFactory = MegaBlockExecutorFactory<
+    MegaHardforkConfig,
+    MegaEvmFactory<TestExternalEnvs<Infallible, AHashBucketHasher>>,
+    OpAlloyReceiptBuilder,
+>;
+
+/// Error of a [`BlockFixture`] run, naming the block it occurred in.
+#[derive(Debug, Error)]
+#[error("block {block}: {kind}")]
+pub struct BlockFixtureError {
+    /// The number of the block.
+    pub block: u64,
+    /// The error.
+    pub kind: BlockFixtureErrorKind,
+}
+
+/// Specific kind of [`BlockFixtureError`].
+#[derive(Debug, Error)]
+#[allow(missing_docs)]
+pub enum BlockFixtureErrorKind {
+    #[error("invalid fixture: {0}")]
+    InvalidFixture(String),
+    #[error("transaction {index} cannot be decoded: {reason}")]
+    InvalidTransaction { index: usize, reason: String },
+    #[error("block execution failed: {0}")]
+    Execution(String),
+    #[error("state root mismatch: got {got}, expected {expected}")]
+    StateRootMismatch { got: B256, expected: B256 },
+    #[error("receipts root mismatch: got {got}, expected {expected}")]
+    ReceiptsRootMismatch { got: B256, expected: B256 },
+    #[error("gas used mismatch: got {got}, expected {expected}")]
+    GasUsedMismatch { got: u64, expected: u64 },
+}
+
+/// Executes the blocks of `fixture` and checks the outcome of each against its expectation.
+///
+/// Stops at the first block that fails to execute or does not match.
+pub fn execute_block_fixture(fixture: &BlockFixture) -> Result<(), BlockFixtureError> {
+    run_blocks(fixture, |block, got| {
+        let expected = &block.expected;
+        let kind = if got.state_root != expected.state_root {
+            BlockFixtureErrorKind::StateRootMismatch {
+                got: got.state_root,
+                expected: expected.state_root,
+            }
+        } else if got.receipts_root != expected.receipts_root {
+            BlockFixtureErrorKind::ReceiptsRootMismatch {
+                got: got.receipts_root,
+                expected: expected.receipts_root,
+            }
+        } else if got.gas_used != expected.gas_used {
+            BlockFixtureErrorKind::GasUsedMismatch {
+                got: got.gas_used,
+                expected: expected.gas_used,
+            }
+        } else {
+            return Ok(());
+        };
+        Err(kind)
+    })
+}
+
+/// Executes the blocks of `fixture` and overwrites the expectation of each with its outcome.
+pub fn fill_block_fixture(fixture: &mut BlockFixture) -> Result<(), BlockFixtureError> {
+    let mut outcomes = Vec::with_capacity(fixture.blocks.len());
+    run_blocks(fixture, |_, got| {
+        outcomes.push(got);
+        Ok(())
+    })?;
+    for (block, outcome) in fixture.blocks.iter_mut().zip(outcomes) {
+        block.expected = outcome;
+    }
+    Ok(())
+}
+
+/// Executes the blocks of `fixture` in order, passing the outcome of each to `check`.
+fn run_blocks(
+    fixture: &BlockFixture,
+    mut check: impl FnMut(&FixtureBlock, ExpectedBlock) -> Result<(), BlockFixtureErrorKind>,
+) -> Result<(), BlockFixtureError> {
+    let hardforks =
+        fixture.hardforks.iter().fold(MegaHardforkConfig::new(), |config, (fork, ts)| {
+            config.with(*fork, ForkCondition::Timestamp(*ts))
+        });
+    let mega_env = fixture.mega_env.clone().unwrap_or_default();
+    let first_block = fixture.blocks.first().map_or(0, |block| block.number);
+    mega_env.validate().map_err(|reason| BlockFixtureError {
+        block: first_block,
+        kind: BlockFixtureErrorKind::InvalidFixture(reason),
+    })?;
+    let factory = MegaBlockExecutorFactory::new(
+        hardforks.clone(),
+        MegaEvmFactory::new()
+            .with_external_env_factory(mega_env.to_external_envs::<AHashBucketHasher>()),
+        OpAlloyReceiptBuilder::default(),
+    );
+
+    let mut cache = pre_state_cache(&fixture.pre);
+    cache.set_state_clear_flag(true);
+    let mut state = State::builder().with_cached_prestate(cache).with_bundle_update().build();
+    for block in &fixture.blocks {
+        let outcome = execute_block(&factory, &hardforks, fixture.chain_id, &mut state, block);
+        outcome
+            .and_then(|got| check(block, got))
+            .map_err(|kind| BlockFixtureError { block: block.number, kind })?;
+    }
+    Ok(())
+}
+
+/// Executes `block` on top of `state` and returns its outcome.
+fn execute_block(
+    factory: &FixtureExecutorFactory,
+    hardforks: &MegaHardforkConfig,
+    chain_id: u64,
+    state: &mut State<EmptyDB>,
+    block: &FixtureBlock,
+) -> Result<ExpectedBlock, BlockFixtureErrorKind> {
+    let attributes = &block.attributes;
+    if attributes.withdrawals.as_ref().is_some_and(|withdrawals| !withdrawals.is_empty()) {
+        return Err(BlockFixtureErrorKind::InvalidFixture(
+            "withdrawals are not supported on an OP-stack chain".to_string(),
+        ));
+    }
+    let transactions = attributes
+        .transactions
+        .iter()
+        .enumerate()
+        .map(|(index, raw)| {
+            decode_transaction(raw)
+                .map_err(|reason| BlockFixtureErrorKind::InvalidTransaction { index, reason })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    state.block_hashes.insert(block.number.saturating_sub(1), block.parent_hash);
+    let mut cfg_env = CfgEnv::default();
+    cfg_env.chain_id = chain_id;
+    cfg_env.spec = hardforks.spec_id(attributes.timestamp);
+    let block_env = BlockEnv {
+        number: U256::from(block.number),
+        timestamp: U256::from(attributes.timestamp),
+        gas_limit: attributes.gas_limit,
+        beneficiary: attributes.suggested_fee_recipient,
+        basefee: block.base_fee_per_gas,
+        prevrandao: Some(attributes.prev_randao),
+        ..Default::default()
+    };
+    let block_limits =
+        hardforks.hardfork(attributes.timestamp).map_or_else(BlockLimits::no_limits, |hardfork| {
+            BlockLimits::from_hardfork_and_block_gas_limit(hardfork, attributes.gas_limit)
+        });
+    let block_ctx = MegaBlockExecutionCtx::new(
+        block.parent_hash,
+        attributes.parent_beacon_block_root,
+        Bytes::new(),
+        block_limits,
+    );
+
+    let to_kind = |err: BlockExecutionError| BlockFixtureErrorKind::Execution(err.to_string());
+    let mut executor = factory.create_executor(state, block_ctx, EvmEnv::new(cfg_env, block_env));
+    executor.apply_pre_execution_changes().map_err(to_kind)?;
+    for tx in &transactions {
+        executor.execute_transaction(tx).map_err(to_kind)?;
+    }
+    let (_, result) = executor.finish().map_err(to_kind)?;
+
+    Ok(ExpectedBlock {
+        state_root: state_root(state.cache.trie_account()),
+        receipts_root: receipts_root(&result.receipts),
+        gas_used: result.gas_used,
+    })
+}
+
+/// Decodes an EIP-2718 encoded transaction and recovers its sender.
+fn decode_transaction(raw: &Bytes) -> Result<Recovered<MegaTxEnvelope>, String> {
+    let tx = MegaTxEnvelope::decode_2718(&mut raw.as_ref()).map_err(|err| err.to_string())?;
+    let signer = tx.recover_signer().map_err(|err| err.to_string())?;
+    Ok(Recovered::new_unchecked(tx, signer))
+}
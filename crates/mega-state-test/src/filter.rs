@@ -0,0 +1,195 @@
+//! Test selection for the state-test runner: name/fork/index filters and sharding.
+
+use crate::types::{SpecName, TxPartIndices};
+use regex::Regex;
+use std::{path::PathBuf, str::FromStr};
+
+/// Selects which units, `post` specs and `post` entries of a suite are executed.
+///
+/// Every criterion is optional; an empty filter (the [`Default`]) runs everything.
+#[derive(Clone, Debug, Default)]
+pub struct TestFilter {
+    /// Only run units whose name matches this pattern (unanchored search).
+    pub name: Option<Regex>,
+    /// Only run `post` entries recorded under this spec.
+    pub fork: Option<SpecName>,
+    /// Only run the `post` entry with these `(data, gas, value)` indices.
+    pub index: Option<TxPartIndices>,
+}
+
+impl TestFilter {
+    /// Returns `true` if no criterion is set.
+    pub const fn is_empty(&self) -> bool {
+        self.name.is_none() && self.fork.is_none() && self.index.is_none()
+    }
+
+    /// Returns whether the unit called `name` is selected.
+    pub fn matches_name(&self, name: &str) -> bool {
+        self.name.as_ref().is_none_or(|re| re.is_match(name))
+    }
+
+    /// Returns whether `post` entries under `spec` are selected.
+    pub fn matches_fork(&self, spec: &SpecName) -> bool {
+        self.fork.as_ref().is_none_or(|fork| fork == spec)
+    }
+
+    /// Returns whether the `post` entry with `indexes` is selected.
+    pub fn matches_index(&self, indexes: &TxPartIndices) -> bool {
+        self.index.as_ref().is_none_or(|index| index == indexes)
+    }
+}
+
+/// Compiles a `--filter` unit-name pattern.
+///
+/// # Errors
+///
+/// Returns the regex compile error, rendered for display.
+pub fn parse_name_pattern(s: &str) -> Result<Regex, String> {
+    Regex::new(s).map_err(|e| format!("invalid filter pattern {s:?}: {e}"))
+}
+
+/// Parses a fork name as it appears as a key of a fixture's `post` map (e.g. `Cancun`, `Rex5`).
+///
+/// # Errors
+///
+/// Returns an error for a name that does not map to a known [`SpecName`].
+pub fn parse_fork(s: &str) -> Result<SpecName, String> {
+    match serde_json::from_value(serde_json::Value::String(s.to_string())) {
+        Ok(SpecName::Unknown) | Err(_) => Err(format!("unknown fork {s:?}")),
+        Ok(spec) => Ok(spec),
+    }
+}
+
+/// Parses `d:g:v` into the `(data, gas, value)` indices of a `post` entry.
+///
+/// # Errors
+///
+/// Returns an error unless the input is exactly three `:`-separated unsigned integers.
+pub fn parse_indices(s: &str) -> Result<TxPartIndices, String> {
+    let invalid = || format!("invalid index {s:?}; expected <data>:<gas>:<value>, e.g. 0:0:0");
+    let mut parts = s.split(':').map(|p| p.parse::<usize>().map_err(|_| invalid()));
+    let (Some(data), Some(gas), Some(value), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid());
+    };
+    Ok(TxPartIndices { data: data?, gas: gas?, value: value? })
+}
+
+/// A `N/M` slice of the test files, used to split a suite across CI machines.
+///
+/// `index` is 1-based: `1/4` through `4/4` together cover every file exactly once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Shard {
+    /// 1-based shard index, in `1..=count`.
+    pub index: usize,
+    /// Total number of shards.
+    pub count: usize,
+}
+
+impl Shard {
+    /// Returns the files belonging to this shard.
+    ///
+    /// Files are sorted first so every machine derives the same partition,
+    /// independent of directory traversal order.
+    pub fn select(&self, mut files: Vec<PathBuf>) -> Vec<PathBuf> {
+        files.sort();
+        files
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| i % self.count == self.index - 1)
+            .map(|(_, file)| file)
+            .collect()
+    }
+}
+
+impl FromStr for Shard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid shard {s:?}; expected N/M with 1 <= N <= M");
+        let (index, count) = s.split_once('/').ok_or_else(invalid)?;
+        let index = index.parse::<usize>().map_err(|_| invalid())?;
+        let count = count.parse::<usize>().map_err(|_| invalid())?;
+        if index == 0 || index > count {
+            return Err(invalid());
+        }
+        Ok(Self { index, count })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_filter_matches_everything() {
+        let filter = TestFilter::default();
+        assert!(filter.is_empty());
+        assert!(filter.matches_name("anything"));
+        assert!(filter.matches_fork(&SpecName::Cancun));
+        assert!(filter.matches_index(&TxPartIndices { data: 3, gas: 1, value: 2 }));
+    }
+
+    #[test]
+    fn test_filter_criteria() {
+        let filter = TestFilter {
+            name: Some(Regex::new("sstore_").unwrap()),
+            fork: Some(SpecName::Rex5),
+            index: Some(TxPartIndices { data: 1, gas: 0, value: 0 }),
+        };
+        assert!(!filter.is_empty());
+        assert!(filter.matches_name("test_sstore_gas"));
+        assert!(!filter.matches_name("test_sload_gas"));
+        assert!(filter.matches_fork(&SpecName::Rex5));
+        assert!(!filter.matches_fork(&SpecName::Rex4));
+        assert!(filter.matches_index(&TxPartIndices { data: 1, gas: 0, value: 0 }));
+        assert!(!filter.matches_index(&TxPartIndices { data: 0, gas: 0, value: 0 }));
+    }
+
+    #[test]
+    fn test_parse_name_pattern() {
+        assert!(parse_name_pattern("^sstore_.*$").unwrap().is_match("sstore_cold"));
+        assert!(parse_name_pattern("(unclosed").is_err());
+    }
+
+    #[test]
+    fn test_parse_fork() {
+        assert_eq!(parse_fork("Cancun"), Ok(SpecName::Cancun));
+        assert_eq!(parse_fork("Rex5"), Ok(SpecName::Rex5));
+        assert!(parse_fork("FutureFork9000").is_err());
+        assert!(parse_fork("rex5").is_err());
+    }
+
+    #[test]
+    fn test_parse_indices() {
+        assert_eq!(parse_indices("1:2:3"), Ok(TxPartIndices { data: 1, gas: 2, value: 3 }));
+        for bad in ["", "1:2", "1:2:3:4", "a:0:0", "-1:0:0", "1::3"] {
+            assert!(parse_indices(bad).is_err(), "{bad:?} must be rejected");
+        }
+    }
+
+    #[test]
+    fn test_parse_shard() {
+        assert_eq!("2/4".parse::<Shard>(), Ok(Shard { index: 2, count: 4 }));
+        for bad in ["0/4", "5/4", "1/0", "1", "a/b", "1/2/3"] {
+            assert!(bad.parse::<Shard>().is_err(), "{bad:?} must be rejected");
+        }
+    }
+
+    #[test]
+    fn test_shards_partition_files() {
+        let files: Vec<PathBuf> =
+            ["c.json", "a.json", "e.json", "b.json", "d.json"].iter().map(PathBuf::from).collect();
+        let mut seen = Vec::new();
+        for index in 1..=3 {
+            let shard = Shard { index, count: 3 }.select(files.clone());
+            assert!(!shard.is_empty());
+            seen.extend(shard);
+        }
+        seen.sort();
+        let mut expected = files;
+        expected.sort();
+        assert_eq!(seen, expected, "shards must cover every file exactly once");
+    }
+}
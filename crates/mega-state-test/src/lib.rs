@@ -3,8 +3,16 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod block_runner;
+
+pub mod filter;
+
 pub mod types;
 
+pub mod report;
+
+pub mod roots;
+
 pub mod runner;
 
 pub mod utils;
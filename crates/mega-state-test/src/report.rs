@@ -0,0 +1,229 @@
+//! Machine-readable run reports: a JSON summary and `JUnit` XML.
+//!
+//! A report has one case per test file, the unit the runner schedules and times.
+
+use crate::runner::TestError;
+use serde_json::json;
+use std::{fmt::Write as _, path::Path, time::Duration};
+
+/// Outcome of a single test file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CaseStatus {
+    /// Every selected case in the file passed.
+    Passed,
+    /// The file is on the runner's skip list and was not executed.
+    Skipped,
+    /// The file failed.
+    Failed {
+        /// Name of the failing unit.
+        unit: String,
+        /// Rendered error, including the got/expected diff where the error carries one.
+        message: String,
+    },
+}
+
+/// Report entry for one test file.
+#[derive(Clone, Debug)]
+pub struct CaseReport {
+    /// Path of the test file.
+    pub path: String,
+    /// Outcome of the file.
+    pub status: CaseStatus,
+    /// Wall-clock time spent executing the file.
+    pub duration: Duration,
+}
+
+/// Collected outcomes of a runner invocation.
+#[derive(Clone, Debug, Default)]
+pub struct RunReport {
+    /// Per-file outcomes, in completion order.
+    pub cases: Vec<CaseReport>,
+}
+
+impl RunReport {
+    /// Record the outcome of executing `path`.
+    pub fn record(&mut self, path: &Path, result: Result<(), &TestError>, duration: Duration) {
+        let status = match result {
+            Ok(()) => CaseStatus::Passed,
+            Err(e) => CaseStatus::Failed { unit: e.name.clone(), message: e.kind.to_string() },
+        };
+        self.cases.push(CaseReport { path: path.display().to_string(), status, duration });
+    }
+
+    /// Record that `path` was skipped.
+    pub fn record_skipped(&mut self, path: &Path) {
+        self.cases.push(CaseReport {
+            path: path.display().to_string(),
+            status: CaseStatus::Skipped,
+            duration: Duration::ZERO,
+        });
+    }
+
+    /// Append the cases of another report.
+    pub fn extend(&mut self, other: Self) {
+        self.cases.extend(other.cases);
+    }
+
+    /// Returns the `(passed, failed, skipped)` counts.
+    pub fn counts(&self) -> (usize, usize, usize) {
+        self.cases.iter().fold((0, 0, 0), |(p, f, s), case| match case.status {
+            CaseStatus::Passed => (p + 1, f, s),
+            CaseStatus::Failed { .. } => (p, f + 1, s),
+            CaseStatus::Skipped => (p, f, s + 1),
+        })
+    }
+
+    /// Total time spent executing the recorded files.
+    pub fn duration(&self) -> Duration {
+        self.cases.iter().map(|case| case.duration).sum()
+    }
+
+    /// Render the report as a JSON summary.
+    pub fn to_json(&self) -> serde_json::Value {
+        let (passed, failed, skipped) = self.counts();
+        let tests = self
+            .cases
+            .iter()
+            .map(|case| {
+                let mut entry = json!({
+                    "path": case.path,
+                    "status": match case.status {
+                        CaseStatus::Passed => "pass",
+                        CaseStatus::Failed { .. } => "fail",
+                        CaseStatus::Skipped => "skip",
+                    },
+                    "durationSecs": case.duration.as_secs_f64(),
+                });
+                if let CaseStatus::Failed { unit, message } = &case.status {
+                    entry["failure"] = json!({ "unit": unit, "message": message });
+                }
+                entry
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "total": self.cases.len(),
+            "passed": passed,
+            "failed": failed,
+            "skipped": skipped,
+            "durationSecs": self.duration().as_secs_f64(),
+            "tests": tests,
+        })
+    }
+
+    /// Render the report as a `JUnit` XML document with a single `testsuite`.
+    pub fn to_junit_xml(&self) -> String {
+        let (_, failed, skipped) = self.counts();
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"state-test\" tests=\"{}\" failures=\"{failed}\" skipped=\"{skipped}\" time=\"{:.6}\">",
+            self.cases.len(),
+            self.duration().as_secs_f64(),
+        );
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"state-test\" tests=\"{}\" failures=\"{failed}\" skipped=\"{skipped}\" time=\"{:.6}\">",
+            self.cases.len(),
+            self.duration().as_secs_f64(),
+        );
+        for case in &self.cases {
+            let _ = write!(
+                xml,
+                "    <testcase classname=\"state-test\" name=\"{}\" time=\"{:.6}\"",
+                xml_escape(&case.path),
+                case.duration.as_secs_f64(),
+            );
+            match &case.status {
+                CaseStatus::Passed => xml.push_str("/>\n"),
+                CaseStatus::Skipped => xml.push_str(">\n      <skipped/>\n    </testcase>\n"),
+                CaseStatus::Failed { unit, message } => {
+                    let _ = write!(
+                        xml,
+                        ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>\n",
+                        xml_escape(unit),
+                        xml_escape(message),
+                    );
+                }
+            }
+        }
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+}
+
+/// Escape the five XML special characters for use in text and attribute values.
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::TestErrorKind;
+
+    fn sample() -> RunReport {
+        let mut report = RunReport::default();
+        report.record(Path::new("a.json"), Ok(()), Duration::from_millis(1500));
+        let err = TestError {
+            name: "unit<1>".to_string(),
+            path: "b.json".to_string(),
+            kind: TestErrorKind::GasUsedMismatch { got: 1, expected: 2 },
+        };
+        report.record(Path::new("b.json"), Err(&err), Duration::from_millis(500));
+        report.record_skipped(Path::new("c.json"));
+        report
+    }
+
+    #[test]
+    fn test_report_counts_and_duration() {
+        let report = sample();
+        assert_eq!(report.counts(), (1, 1, 1));
+        assert_eq!(report.duration(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_report_json_summary() {
+        let summary = sample().to_json();
+        assert_eq!(summary["total"], 3);
+        assert_eq!(summary["passed"], 1);
+        assert_eq!(summary["failed"], 1);
+        assert_eq!(summary["skipped"], 1);
+        assert_eq!(summary["tests"][1]["status"], "fail");
+        assert_eq!(summary["tests"][1]["failure"]["unit"], "unit<1>");
+        assert_eq!(
+            summary["tests"][1]["failure"]["message"],
+            "gas used mismatch: got 1, expected 2"
+        );
+        assert!(summary["tests"][0].get("failure").is_none());
+    }
+
+    #[test]
+    fn test_report_junit_xml() {
+        let xml = sample().to_junit_xml();
+        assert!(xml.contains("tests=\"3\" failures=\"1\" skipped=\"1\""));
+        assert!(
+            xml.contains("<testcase classname=\"state-test\" name=\"a.json\" time=\"1.500000\"/>")
+        );
+        assert!(xml.contains("<failure message=\"unit&lt;1&gt;\">gas used mismatch"));
+        assert!(xml.contains("<skipped/>"));
+    }
+
+    #[test]
+    fn test_xml_escape() {
+        assert_eq!(
+            xml_escape(r#"<a href="x">&'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&apos;&lt;/a&gt;"
+        );
+    }
+}
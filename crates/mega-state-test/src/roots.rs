@@ -0,0 +1,190 @@
+//! Merkle Patricia trie roots of post-states, transactions and receipts.
+//!
+//! The state-test runner, `mega-t8n` and tests derive their roots through these helpers so that
+//! they agree with each other and with the roots a node puts in a block header. The state root can
+//! be computed from a plain post-state, keyed by address and storage slot, or from a hashed
+//! post-state whose keys are already the keccak hashes used as trie paths.
+
+use alloy_rlp::{RlpEncodable, RlpMaxEncodedLen};
+use hash_db::Hasher;
+use mega_evm::{
+    alloy_eips::Encodable2718,
+    revm::{
+        database::PlainAccount,
+        primitives::{keccak256, Address, B256, U256},
+        state::AccountInfo,
+    },
+};
+use plain_hasher::PlainHasher;
+use triehash::{ordered_trie_root, sec_trie_root, trie_root};
+
+/// Computes the state root of a plain post-state.
+pub fn state_root<'a>(accounts: impl IntoIterator<Item = (Address, &'a PlainAccount)>) -> B256 {
+    sec_trie_root::<KeccakHasher, _, _, _>(accounts.into_iter().map(|(address, account)| {
+        let storage_root = storage_root(account.storage.iter().map(|(k, v)| (*k, *v)));
+        (address, alloy_rlp::encode_fixed_size(&TrieAccount::new(&account.info, storage_root)))
+    }))
+}
+
+/// Computes the state root of a hashed post-state: accounts are keyed by the keccak hash of their
+/// address, and storage slots by the keccak hash of their big-endian index.
+pub fn hashed_state_root<'a, I, S>(accounts: I) -> B256
+where
+    I: IntoIterator<Item = (B256, &'a AccountInfo, S)>,
+    S: IntoIterator<Item = (B256, U256)>,
+{
+    trie_root::<KeccakHasher, _, _, _>(accounts.into_iter().map(
+        |(hashed_address, info, storage)| {
+            let storage_root = hashed_storage_root(storage);
+            (hashed_address, alloy_rlp::encode_fixed_size(&TrieAccount::new(info, storage_root)))
+        },
+    ))
+}
+
+/// Computes the storage root of an account from its slots. Zero values are not part of the trie.
+pub fn storage_root(storage: impl IntoIterator<Item = (U256, U256)>) -> B256 {
+    sec_trie_root::<KeccakHasher, _, _, _>(
+        storage
+            .into_iter()
+            .filter(|(_, value)| !value.is_zero())
+            .map(|(slot, value)| (slot.to_be_bytes::<32>(), alloy_rlp::encode_fixed_size(&value))),
+    )
+}
+
+/// Computes the storage root of an account from its slots keyed by their hash. Zero values are
+/// not part of the trie.
+pub fn hashed_storage_root(storage: impl IntoIterator<Item = (B256, U256)>) -> B256 {
+    trie_root::<KeccakHasher, _, _, _>(
+        storage
+            .into_iter()
+            .filter(|(_, value)| !value.is_zero())
+            .map(|(hashed_slot, value)| (hashed_slot, alloy_rlp::encode_fixed_size(&value))),
+    )
+}
+
+/// Computes the transactions root of a block from its transactions, in block order.
+pub fn tx_root<T: Encodable2718>(transactions: &[T]) -> B256 {
+    ordered_root(transactions.iter().map(|tx| tx.encoded_2718()))
+}
+
+/// Computes the receipts root of a block from its receipts, in block order.
+pub fn receipts_root<R: Encodable2718>(receipts: &[R]) -> B256 {
+    ordered_root(receipts.iter().map(|receipt| receipt.encoded_2718()))
+}
+
+/// Computes the root of a trie mapping the RLP-encoded index of each item to the item.
+pub fn ordered_root<I>(items: I) -> B256
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    ordered_trie_root::<KeccakHasher, _>(items)
+}
+
+#[derive(RlpEncodable, RlpMaxEncodedLen)]
+struct TrieAccount {
+    nonce: u64,
+    balance: U256,
+    root_hash: B256,
+    code_hash: B256,
+}
+
+impl TrieAccount {
+    fn new(info: &AccountInfo, storage_root: B256) -> Self {
+        Self {
+            nonce: info.nonce,
+            balance: info.balance,
+            root_hash: storage_root,
+            code_hash: info.code_hash,
+        }
+    }
+}
+
+/// The keccak [`Hasher`] of Ethereum tries.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeccakHasher;
+
+impl Hasher for KeccakHasher {
+    type Out = B256;
+    type StdHasher = PlainHasher;
+    const LENGTH: usize = 32;
+
+    #[inline]
+    fn hash(x: &[u8]) -> Self::Out {
+        keccak256(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mega_evm::{
+        alloy_consensus::{proofs::calculate_transaction_root, Signed, TxLegacy},
+        revm::primitives::{address, b256, Bytes, HashMap, Signature, TxKind},
+        MegaTxEnvelope,
+    };
+
+    const EMPTY_ROOT: B256 =
+        b256!("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421");
+
+    fn account(balance: u64, storage: &[(u64, u64)]) -> PlainAccount {
+        PlainAccount {
+            info: AccountInfo { balance: U256::from(balance), nonce: 1, ..Default::default() },
+            storage: storage.iter().map(|(k, v)| (U256::from(*k), U256::from(*v))).collect(),
+        }
+    }
+
+    #[test]
+    fn test_empty_roots() {
+        assert_eq!(state_root([]), EMPTY_ROOT);
+        assert_eq!(storage_root([]), EMPTY_ROOT);
+        assert_eq!(tx_root::<MegaTxEnvelope>(&[]), EMPTY_ROOT);
+        assert_eq!(ordered_root::<[&[u8]; 0]>([]), EMPTY_ROOT);
+    }
+
+    #[test]
+    fn test_zero_storage_values_are_not_in_the_trie() {
+        assert_eq!(storage_root([(U256::from(1), U256::ZERO)]), EMPTY_ROOT);
+        assert_eq!(
+            storage_root([(U256::from(1), U256::from(2)), (U256::from(3), U256::ZERO)]),
+            storage_root([(U256::from(1), U256::from(2))])
+        );
+    }
+
+    #[test]
+    fn test_hashed_state_root_matches_plain_state_root() {
+        let accounts: HashMap<Address, PlainAccount> = HashMap::from_iter([
+            (address!("1000000000000000000000000000000000000001"), account(1, &[])),
+            (address!("1000000000000000000000000000000000000002"), account(2, &[(0, 1), (7, 0)])),
+        ]);
+        let plain = state_root(accounts.iter().map(|(address, account)| (*address, account)));
+        let hashed = hashed_state_root(accounts.iter().map(|(address, account)| {
+            let storage = account
+                .storage
+                .iter()
+                .map(|(slot, value)| (keccak256(slot.to_be_bytes::<32>()), *value));
+            (keccak256(address), &account.info, storage)
+        }));
+        assert_ne!(plain, EMPTY_ROOT);
+        assert_eq!(plain, hashed);
+    }
+
+    #[test]
+    fn test_tx_root_matches_alloy() {
+        let transactions: Vec<MegaTxEnvelope> = (0..130)
+            .map(|nonce| {
+                let tx = TxLegacy {
+                    chain_id: Some(6342),
+                    nonce,
+                    gas_price: 1,
+                    gas_limit: 21_000,
+                    to: TxKind::Call(Address::ZERO),
+                    value: U256::from(nonce),
+                    input: Bytes::new(),
+                };
+                MegaTxEnvelope::Legacy(Signed::new_unhashed(tx, Signature::test_signature()))
+            })
+            .collect();
+        assert_eq!(tx_root(&transactions), calculate_transaction_root(&transactions));
+    }
+}
@@ -1,14 +1,16 @@
 #![allow(missing_docs)]
 
 use crate::{
+    filter::TestFilter,
+    report::RunReport,
     types::{
-        tx_env_at, Env, SpecName, Test, TestError as TxBuildError, TestSuite, TestUnit,
+        tx_env_at, Env, MegaExpect, SpecName, Test, TestError as TxBuildError, TestSuite, TestUnit,
         TxPartIndices,
     },
     utils::{compute_test_roots, TestValidationResult},
 };
 use alloy_primitives::address;
-use indicatif::{ProgressBar, ProgressDrawTarget};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
 use mega_evm::{
     revm::{
         context::{block::BlockEnv, cfg::CfgEnv, tx::TxEnv},
@@ -23,8 +25,8 @@ use mega_evm::{
         primitives::{hardfork::SpecId, Bytes, B256},
         ExecuteCommitEvm,
     },
-    AHashBucketHasher, MegaContext, MegaEvm, MegaHaltReason, MegaSpecId, MegaTransaction,
-    MegaTransactionError,
+    AHashBucketHasher, LimitUsage, MegaContext, MegaEvm, MegaHaltReason, MegaSpecId,
+    MegaTransaction, MegaTransactionError,
 };
 use serde_json::json;
 use std::{
@@ -62,6 +64,10 @@ pub enum TestErrorKind {
     GasUsedMismatch { got: u64, expected: u64 },
     #[error("status mismatch: got {got:?}, expected {expected:?}")]
     StatusMismatch { got: String, expected: String },
+    #[error("{field} mismatch: got {got}, expected {expected}")]
+    MegaUsageMismatch { field: &'static str, got: u64, expected: u64 },
+    #[error("halt reason mismatch: got {got:?}, expected {expected:?}")]
+    HaltReasonMismatch { got: Option<String>, expected: String },
     /// The raw key is kept for programmatic use but deliberately redacted from
     /// the `Display` output: fixture `secretKey` values flow through error
     /// messages into logs and CI output.
@@ -315,6 +321,50 @@ fn validate_mega_expectations(
     Ok(())
 }
 
+/// Validate the `megaExpect` section (additional-limit usage and halt reason)
+/// when present.
+///
+/// The resource counters are only tracked from `MINI_REX` on; setting them in a
+/// fixture that runs under an earlier spec is a fixture error rather than a
+/// comparison against counters that were never recorded.
+fn validate_mega_limit_expectations(
+    test: &Test,
+    spec: MegaSpecId,
+    actual_result: &ExecutionResult<MegaHaltReason>,
+    usage: &LimitUsage,
+) -> Result<(), TestErrorKind> {
+    let Some(expect) = &test.mega_expect else {
+        return Ok(());
+    };
+    if expect.has_resource_expectations() {
+        if !spec.is_enabled(MegaSpecId::MINI_REX) {
+            return Err(TestErrorKind::FixtureError(format!(
+                "megaExpect resource counters require MINI_REX or later, got {spec:?}"
+            )));
+        }
+        for (field, expected, got) in [
+            ("data size", expect.data_size, usage.data_size),
+            ("KV updates", expect.kv_updates, usage.kv_updates),
+            ("compute gas", expect.compute_gas, usage.compute_gas),
+            ("state growth", expect.state_growth, usage.state_growth),
+        ] {
+            if let Some(expected) = expected {
+                if got != expected {
+                    return Err(TestErrorKind::MegaUsageMismatch { field, got, expected });
+                }
+            }
+        }
+    }
+    if let Some(expected) = &expect.halt_reason {
+        let got = halt_reason(actual_result);
+        if !got.as_deref().is_some_and(|got| MegaExpect::halt_reason_matches(expected, got)) {
+            return Err(TestErrorKind::HaltReasonMismatch { got, expected: expected.clone() });
+        }
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 fn check_evm_execution(
     test: &Test,
     expected_output: Option<&Bytes>,
@@ -323,6 +373,7 @@ fn check_evm_execution(
         ExecutionResult<MegaHaltReason>,
         EVMError<Infallible, MegaTransactionError>,
     >,
+    usage: &LimitUsage,
     db: &State<EmptyDB>,
     spec: MegaSpecId,
     print_json_outcome: bool,
@@ -364,6 +415,11 @@ fn check_evm_execution(
         validate_mega_expectations(test, result).inspect_err(|e| {
             print_json(Some(e));
         })?;
+
+        // MegaETH additional-limit usage and halt reason (`megaExpect`).
+        validate_mega_limit_expectations(test, spec, result, usage).inspect_err(|e| {
+            print_json(Some(e));
+        })?;
     }
 
     // Validate logs root
@@ -429,6 +485,19 @@ pub fn execute_test_suite(
     elapsed: &Arc<Mutex<Duration>>,
     trace: bool,
     print_json_outcome: bool,
+) -> Result<(), TestError> {
+    execute_test_suite_filtered(path, elapsed, trace, print_json_outcome, &TestFilter::default())
+}
+
+/// Execute the units, specs and `post` entries of a test suite selected by `filter`.
+///
+/// See [`execute_test_suite`]; entries rejected by `filter` are skipped silently.
+pub fn execute_test_suite_filtered(
+    path: &Path,
+    elapsed: &Arc<Mutex<Duration>>,
+    trace: bool,
+    print_json_outcome: bool,
+    filter: &TestFilter,
 ) -> Result<(), TestError> {
     if skip_test(path) {
         return Ok(());
@@ -447,6 +516,10 @@ pub fn execute_test_suite(
     })?;
 
     for (name, unit) in suite.0 {
+        if !filter.matches_name(&name) {
+            continue;
+        }
+
         // Prepare initial state
         let cache_state = unit.state();
 
@@ -461,7 +534,7 @@ pub fn execute_test_suite(
         // Post and execution
         for (spec_name, tests) in &unit.post {
             // Skip Constantinople spec
-            if *spec_name == SpecName::Constantinople {
+            if *spec_name == SpecName::Constantinople || !filter.matches_fork(spec_name) {
                 continue;
             }
 
@@ -476,6 +549,10 @@ pub fn execute_test_suite(
             let block = unit.block_env(&cfg);
 
             for (index, test) in tests.iter().enumerate() {
+                if !filter.matches_index(&test.indexes) {
+                    continue;
+                }
+
                 // Setup transaction environment
                 let tx = match test.tx_env(&unit) {
                     Ok(tx) => tx,
@@ -596,17 +673,19 @@ fn execute_single_test<'a>(ctx: TestExecutionContext<'a>) -> Result<(), TestErro
 
     // Execute
     let timer = Instant::now();
-    let (db, exec_result) = if ctx.trace {
+    let (db, exec_result, usage) = if ctx.trace {
         let mut evm = MegaEvm::new(evm_context)
             .with_inspector(TracerEip3155::buffered(stderr()).without_summary());
         let res = evm.inspect_tx_commit(tx);
+        let usage = evm.ctx.additional_limit.borrow().get_usage();
         let db = evm.into_inner().ctx.into_inner().journaled_state.database;
-        (db, res)
+        (db, res, usage)
     } else {
         let mut evm = MegaEvm::new(evm_context);
         let res = evm.transact_commit(tx);
+        let usage = evm.ctx.additional_limit.borrow().get_usage();
         let db = evm.into_inner().ctx.into_inner().journaled_state.database;
-        (db, res)
+        (db, res, usage)
     };
     *ctx.elapsed.lock().unwrap() += timer.elapsed();
 
@@ -619,6 +698,7 @@ fn execute_single_test<'a>(ctx: TestExecutionContext<'a>) -> Result<(), TestErro
         ctx.unit.out.as_ref(),
         ctx.name,
         &exec_result,
+        &usage,
         db,
         ctx.cfg.spec(),
         ctx.print_json_outcome,
@@ -659,6 +739,7 @@ pub struct ExecutedUnit {
 fn run_unit_once(
     unit: &TestUnit,
     spec: &SpecName,
+    indexes: TxPartIndices,
     compute_roots: bool,
 ) -> Result<(Duration, ExecutionResult<MegaHaltReason>, Option<TestValidationResult>), TestErrorKind>
 {
@@ -668,7 +749,7 @@ fn run_unit_once(
     configure_max_blobs(&mut cfg);
 
     let block = unit.block_env(&cfg);
-    let tx = tx_env_at(unit, TxPartIndices { data: 0, gas: 0, value: 0 })?;
+    let tx = tx_env_at(unit, indexes)?;
 
     let mut cache = unit.state();
     cache.set_state_clear_flag(cfg.spec.into_eth_spec().is_enabled_in(SpecId::SPURIOUS_DRAGON));
@@ -706,7 +787,17 @@ pub fn execute_unit_collect(
     unit: &TestUnit,
     spec: &SpecName,
 ) -> Result<ExecutedUnit, TestErrorKind> {
-    let (_elapsed, result, validation) = run_unit_once(unit, spec, true)?;
+    execute_unit_collect_at(unit, spec, TxPartIndices { data: 0, gas: 0, value: 0 })
+}
+
+/// Like [`execute_unit_collect`], for the transaction built from the given
+/// `(data, gas, value)` part indices.
+pub fn execute_unit_collect_at(
+    unit: &TestUnit,
+    spec: &SpecName,
+    indexes: TxPartIndices,
+) -> Result<ExecutedUnit, TestErrorKind> {
+    let (_elapsed, result, validation) = run_unit_once(unit, spec, indexes, true)?;
     let validation = validation.expect("roots requested");
     Ok(ExecutedUnit {
         state_root: validation.state_root,
@@ -727,7 +818,8 @@ pub fn time_unit_execution(
     unit: &TestUnit,
     spec: &SpecName,
 ) -> Result<(Duration, u64, String), TestErrorKind> {
-    let (elapsed, result, _validation) = run_unit_once(unit, spec, false)?;
+    let (elapsed, result, _validation) =
+        run_unit_once(unit, spec, TxPartIndices { data: 0, gas: 0, value: 0 }, false)?;
     Ok((elapsed, result.gas_used(), execution_status(&result).to_string()))
 }
 
@@ -859,18 +951,22 @@ pub fn bench_test_suite(
 /// Compute and write the `post` expectation for every unit in a fixture file,
 /// in place — the offline analog of `--dump-fixture`'s post-fill step, for a
 /// fixture that has no `post` yet (a hand-built or `prestateTracer`-snapshot
-/// case). It re-uses the same `execute_unit_collect` + [`Test::for_dump`] the
+/// case). It re-uses the same `execute_unit_collect_at` + [`Test::for_dump`] the
 /// dump path uses, so the result is a self-validating fixture that
 /// [`execute_test_suite`] checks like any other. Returns the number of units
 /// filled.
 ///
-/// `spec_override` selects the spec to execute/record under; when `None`, the
-/// unit's single existing `post` spec is used (so a fixture with an empty `post`
-/// must pass a spec).
+/// Like retesteth's filler, every combination of the transaction's `data`,
+/// `gasLimit` and `value` variants gets its own `post` entry. The unit-level
+/// `out` is only recorded when every combination returns the same output.
+///
+/// `spec_override` selects the spec to execute/record under; when `None`, every
+/// spec already keyed in the unit's `post` is refilled (so a fixture with an
+/// empty `post` must pass a spec).
 ///
-/// Filling replaces the unit's entire `post` map (single spec, single index
-/// `{0,0,0}`) with circularly-derived expectations, so a unit that already has a
-/// non-empty `post` is refused unless `force` is set.
+/// Filling replaces the unit's entire `post` map with circularly-derived
+/// expectations, so a unit that already has a non-empty `post` is refused
+/// unless `force` is set.
 pub fn fill_test_suite(
     path: &Path,
     spec_override: Option<SpecName>,
@@ -907,45 +1003,76 @@ pub fn fill_test_suite(
                 "unit {name} already has a post expectation; pass --force to overwrite"
             )));
         }
-        let spec = match spec_override {
-            Some(s) => s,
-            None => {
-                let mut specs = unit.post.keys();
-                match (specs.next(), specs.next()) {
-                    (Some(s), None) => *s,
-                    _ => {
-                        return Err(fixture_err(format!(
-                            "unit {name} has no single post spec; pass --bench-spec to fill"
-                        )))
-                    }
-                }
+        let specs: Vec<SpecName> = match spec_override {
+            Some(s) => vec![s],
+            None if unit.post.is_empty() => {
+                return Err(fixture_err(format!(
+                    "unit {name} has no post spec; pass --bench-spec to fill"
+                )))
             }
+            None => unit.post.keys().copied().collect(),
         };
-        // Reject an unmapped spec at selection time, so the error names the
-        // unit instead of surfacing from deep inside execution.
-        if spec == SpecName::Unknown {
-            return Err(fixture_err(format!(
-                "unit {name} selects an unknown spec; pass a valid --bench-spec"
-            )));
+        for spec in &specs {
+            // Reject an unmapped spec at selection time, so the error names the
+            // unit instead of surfacing from deep inside execution.
+            if *spec == SpecName::Unknown {
+                return Err(fixture_err(format!(
+                    "unit {name} selects an unknown spec; pass a valid --bench-spec"
+                )));
+            }
+            // Validation skips Constantinople (mirroring upstream revme), so a post
+            // recorded under it would never be checked.
+            if *spec == SpecName::Constantinople {
+                return Err(fixture_err(format!(
+                    "unit {name}: validation skips Constantinople; a post filled under it \
+                     would never be checked"
+                )));
+            }
         }
-        // Validation skips Constantinople (mirroring upstream revme), so a post
-        // recorded under it would never be checked.
-        if spec == SpecName::Constantinople {
+
+        let tx = &unit.transaction;
+        let combinations: Vec<TxPartIndices> = (0..tx.data.len())
+            .flat_map(|data| {
+                (0..tx.gas_limit.len()).flat_map(move |gas| {
+                    (0..tx.value.len()).map(move |value| TxPartIndices { data, gas, value })
+                })
+            })
+            .collect();
+        if combinations.is_empty() {
             return Err(fixture_err(format!(
-                "unit {name}: validation skips Constantinople; a post filled under it \
-                 would never be checked"
+                "unit {name} has an empty data, gasLimit or value list; nothing to fill"
             )));
         }
-        let executed = execute_unit_collect(&unit, &spec)
-            .map_err(|e| fixture_err(format!("execute {name}: {e}")))?;
-        unit.out = executed.output.clone();
-        let test = Test::for_dump(
-            executed.state_root,
-            executed.logs_root,
-            executed.gas_used,
-            executed.status,
-        );
-        unit.post = std::collections::BTreeMap::from([(spec, vec![test])]);
+
+        let mut post = std::collections::BTreeMap::new();
+        let mut outputs = Vec::with_capacity(specs.len() * combinations.len());
+        for spec in specs {
+            let mut tests = Vec::with_capacity(combinations.len());
+            for &indexes in &combinations {
+                let executed = execute_unit_collect_at(&unit, &spec, indexes).map_err(|e| {
+                    fixture_err(format!(
+                        "execute {name} at {}:{}:{}: {e}",
+                        indexes.data, indexes.gas, indexes.value
+                    ))
+                })?;
+                outputs.push(executed.output.clone());
+                let mut test = Test::for_dump(
+                    executed.state_root,
+                    executed.logs_root,
+                    executed.gas_used,
+                    executed.status,
+                );
+                test.indexes = indexes;
+                tests.push(test);
+            }
+            post.insert(spec, tests);
+        }
+        unit.out = if outputs.windows(2).all(|w| w[0] == w[1]) {
+            outputs.into_iter().next().flatten()
+        } else {
+            None
+        };
+        unit.post = post;
         filled.insert(name, unit);
     }
 
@@ -1012,22 +1139,49 @@ fn debug_failed_test<'a>(ctx: DebugContext<'a>) {
     );
 }
 
+/// Options for [`run_with_report`].
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Force single-threaded execution.
+    pub single_thread: bool,
+    /// Enable EVM execution tracing (implies `print_outcome`).
+    pub trace: bool,
+    /// Print test outcomes in JSON format (implies `single_thread`).
+    pub print_outcome: bool,
+    /// Continue running tests even if some fail.
+    pub keep_going: bool,
+    /// Number of worker threads; `None` uses the available parallelism.
+    ///
+    /// Each worker holds one parsed suite in memory at a time, so this also
+    /// bounds the runner's peak memory.
+    pub jobs: Option<usize>,
+    /// Selects the cases to execute.
+    pub filter: TestFilter,
+}
+
 #[derive(Clone, Copy)]
 struct TestRunnerConfig {
     single_thread: bool,
     trace: bool,
     print_outcome: bool,
     keep_going: bool,
+    jobs: Option<usize>,
 }
 
 impl TestRunnerConfig {
-    fn new(single_thread: bool, trace: bool, print_outcome: bool, keep_going: bool) -> Self {
+    fn new(options: &RunOptions) -> Self {
         // Trace implies print_outcome
-        let print_outcome = print_outcome || trace;
+        let print_outcome = options.print_outcome || options.trace;
         // print_outcome or trace implies single_thread
-        let single_thread = single_thread || print_outcome;
+        let single_thread = options.single_thread || print_outcome;
 
-        Self { single_thread, trace, print_outcome, keep_going }
+        Self {
+            single_thread,
+            trace: options.trace,
+            print_outcome,
+            keep_going: options.keep_going,
+            jobs: options.jobs,
+        }
     }
 }
 
@@ -1037,19 +1191,28 @@ struct TestRunnerState {
     console_bar: Arc<ProgressBar>,
     queue: Arc<Mutex<(usize, Vec<PathBuf>)>>,
     elapsed: Arc<Mutex<Duration>>,
+    filter: Arc<TestFilter>,
+    report: Arc<Mutex<RunReport>>,
 }
 
 impl TestRunnerState {
-    fn new(test_files: Vec<PathBuf>) -> Self {
+    fn new(test_files: Vec<PathBuf>, filter: TestFilter) -> Self {
         let n_files = test_files.len();
+        let console_bar =
+            ProgressBar::with_draw_target(Some(n_files as u64), ProgressDrawTarget::stdout())
+                .with_style(
+                    ProgressStyle::with_template(
+                        "{wide_bar} {pos}/{len} [{elapsed_precise}, ETA {eta}] {msg}",
+                    )
+                    .expect("valid progress template"),
+                );
         Self {
             n_errors: Arc::new(AtomicUsize::new(0)),
-            console_bar: Arc::new(ProgressBar::with_draw_target(
-                Some(n_files as u64),
-                ProgressDrawTarget::stdout(),
-            )),
+            console_bar: Arc::new(console_bar),
             queue: Arc::new(Mutex::new((0usize, test_files))),
             elapsed: Arc::new(Mutex::new(Duration::ZERO)),
+            filter: Arc::new(filter),
+            report: Arc::new(Mutex::new(RunReport::default())),
         }
     }
 
@@ -1072,13 +1235,31 @@ fn run_test_worker(state: TestRunnerState, config: TestRunnerConfig) -> Result<(
             return Ok(());
         };
 
-        let result =
-            execute_test_suite(&test_path, &state.elapsed, config.trace, config.print_outcome);
+        if skip_test(&test_path) {
+            state.report.lock().unwrap().record_skipped(&test_path);
+            state.console_bar.inc(1);
+            continue;
+        }
+
+        let start = Instant::now();
+        let result = execute_test_suite_filtered(
+            &test_path,
+            &state.elapsed,
+            config.trace,
+            config.print_outcome,
+            &state.filter,
+        );
+        state.report.lock().unwrap().record(
+            &test_path,
+            result.as_ref().map(|_| ()),
+            start.elapsed(),
+        );
 
         state.console_bar.inc(1);
 
         if let Err(err) = result {
-            state.n_errors.fetch_add(1, Ordering::SeqCst);
+            let n_errors = state.n_errors.fetch_add(1, Ordering::SeqCst) + 1;
+            state.console_bar.set_message(format!("{n_errors} failed"));
             if !config.keep_going {
                 return Err(err);
             }
@@ -1086,11 +1267,15 @@ fn run_test_worker(state: TestRunnerState, config: TestRunnerConfig) -> Result<(
     }
 }
 
-fn determine_thread_count(single_thread: bool, n_files: usize) -> usize {
-    match (single_thread, std::thread::available_parallelism()) {
-        (true, _) | (false, Err(_)) => 1,
-        (false, Ok(n)) => n.get().min(n_files),
+fn determine_thread_count(single_thread: bool, jobs: Option<usize>, n_files: usize) -> usize {
+    if single_thread {
+        return 1;
     }
+    let jobs = match jobs {
+        Some(jobs) => jobs,
+        None => std::thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    jobs.min(n_files).max(1)
 }
 
 /// Run all test files in parallel or single-threaded mode
@@ -1108,10 +1293,37 @@ pub fn run(
     print_outcome: bool,
     keep_going: bool,
 ) -> Result<(), TestError> {
-    let config = TestRunnerConfig::new(single_thread, trace, print_outcome, keep_going);
+    run_filtered(test_files, single_thread, trace, print_outcome, keep_going, TestFilter::default())
+}
+
+/// Run the test files like [`run`], executing only the cases selected by `filter`.
+pub fn run_filtered(
+    test_files: Vec<PathBuf>,
+    single_thread: bool,
+    trace: bool,
+    print_outcome: bool,
+    keep_going: bool,
+    filter: TestFilter,
+) -> Result<(), TestError> {
+    let options =
+        RunOptions { single_thread, trace, print_outcome, keep_going, jobs: None, filter };
+    run_with_report(test_files, options, &mut RunReport::default())
+}
+
+/// Run the test files with the given options, appending the per-file outcomes to `report`.
+///
+/// Workers pull files from a shared queue, so a slow file never holds up the
+/// others. `report` is filled even when the run fails, so callers can still
+/// write it out.
+pub fn run_with_report(
+    test_files: Vec<PathBuf>,
+    options: RunOptions,
+    report: &mut RunReport,
+) -> Result<(), TestError> {
+    let config = TestRunnerConfig::new(&options);
     let n_files = test_files.len();
-    let state = TestRunnerState::new(test_files);
-    let num_threads = determine_thread_count(config.single_thread, n_files);
+    let state = TestRunnerState::new(test_files, options.filter);
+    let num_threads = determine_thread_count(config.single_thread, config.jobs, n_files);
 
     // Spawn worker threads
     let mut handles = Vec::with_capacity(num_threads);
@@ -1141,6 +1353,7 @@ pub fn run(
     }
 
     state.console_bar.finish();
+    report.extend(std::mem::take(&mut *state.report.lock().unwrap()));
 
     // Print summary
     println!(
@@ -1185,6 +1398,15 @@ mod tests {
     use mega_evm::revm::context::result::{Output, SuccessReason};
     use serde_json::json;
 
+    #[test]
+    fn test_determine_thread_count() {
+        assert_eq!(determine_thread_count(true, Some(8), 100), 1);
+        assert_eq!(determine_thread_count(false, Some(8), 100), 8);
+        assert_eq!(determine_thread_count(false, Some(8), 3), 3);
+        assert_eq!(determine_thread_count(false, Some(0), 3), 1);
+        assert!(determine_thread_count(false, None, 2) <= 2);
+    }
+
     fn success(gas_used: u64) -> ExecutionResult<MegaHaltReason> {
         ExecutionResult::Success {
             reason: SuccessReason::Stop,
@@ -1270,6 +1492,85 @@ mod tests {
         }
     }
 
+    fn test_with_mega_expect(expect: serde_json::Value) -> Test {
+        let value = json!({
+            "indexes": { "data": 0, "gas": 0, "value": 0 },
+            "hash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "logs": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "megaExpect": expect,
+        });
+        serde_json::from_value(value).expect("valid Test json")
+    }
+
+    const USAGE: LimitUsage = LimitUsage {
+        data_size: 110,
+        da_data_size: 110,
+        kv_updates: 1,
+        compute_gas: 21_000,
+        state_growth: 0,
+        frames_created: 1,
+    };
+
+    #[test]
+    fn test_mega_limit_expectations_pass_when_matching() {
+        let test = test_with_mega_expect(json!({
+            "dataSize": 110,
+            "kvUpdates": 1,
+            "computeGas": 21_000,
+            "stateGrowth": 0,
+        }));
+        assert!(validate_mega_limit_expectations(&test, MegaSpecId::REX, &success(21_000), &USAGE)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_mega_limit_expectations_usage_mismatch() {
+        let test = test_with_mega_expect(json!({ "kvUpdates": 2 }));
+        let err =
+            validate_mega_limit_expectations(&test, MegaSpecId::MINI_REX, &success(21_000), &USAGE)
+                .unwrap_err();
+        match err {
+            TestErrorKind::MegaUsageMismatch { field, got, expected } => {
+                assert_eq!(field, "KV updates");
+                assert_eq!(got, 1);
+                assert_eq!(expected, 2);
+            }
+            other => panic!("expected MegaUsageMismatch, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_mega_limit_expectations_reject_pre_mini_rex_counters() {
+        // EQUIVALENCE does not track the counters; comparing against zero would
+        // make the expectation vacuous.
+        let test = test_with_mega_expect(json!({ "computeGas": 0 }));
+        let err = validate_mega_limit_expectations(
+            &test,
+            MegaSpecId::EQUIVALENCE,
+            &success(21_000),
+            &LimitUsage::default(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, TestErrorKind::FixtureError(_)), "got {err:?}");
+    }
+
+    #[test]
+    fn test_mega_limit_expectations_halt_reason() {
+        let test = test_with_mega_expect(json!({ "haltReason": "DataLimitExceeded" }));
+        assert!(validate_mega_limit_expectations(&test, MegaSpecId::REX, &halt(), &USAGE).is_ok());
+        // A non-halting execution cannot satisfy a halt expectation.
+        let err =
+            validate_mega_limit_expectations(&test, MegaSpecId::REX, &success(21_000), &USAGE)
+                .unwrap_err();
+        match err {
+            TestErrorKind::HaltReasonMismatch { got, expected } => {
+                assert_eq!(got, None);
+                assert_eq!(expected, "DataLimitExceeded");
+            }
+            other => panic!("expected HaltReasonMismatch, got {other:?}"),
+        }
+    }
+
     fn success_with_output(output: &[u8]) -> ExecutionResult<MegaHaltReason> {
         ExecutionResult::Success {
             reason: SuccessReason::Stop,
@@ -1,6 +1,10 @@
 use std::collections::BTreeMap;
 
-use mega_evm::revm::primitives::{Bytes, StorageKey, StorageValue, U256};
+use mega_evm::revm::{
+    database::CacheState,
+    primitives::{keccak256, Address, Bytes, StorageKey, StorageValue, U256},
+    state::Bytecode,
+};
 use serde::{Deserialize, Serialize};
 
 /// Account information
@@ -18,3 +22,27 @@ pub struct AccountInfo {
     /// are byte-reproducible.
     pub storage: BTreeMap<StorageKey, StorageValue>,
 }
+
+/// Builds a [`CacheState`] holding the accounts of a fixture pre-state.
+///
+/// The state clear flag is left unset; runners set it for the spec they execute under.
+pub fn pre_state_cache(pre: &BTreeMap<Address, AccountInfo>) -> CacheState {
+    let mut cache_state = CacheState::new(false);
+    for (address, info) in pre {
+        let code_hash = keccak256(&info.code);
+        let bytecode = Bytecode::new_raw_checked(info.code.clone())
+            .unwrap_or_else(|_| Bytecode::new_legacy(info.code.clone()));
+        let acc_info = mega_evm::revm::state::AccountInfo {
+            balance: info.balance,
+            code_hash,
+            code: Some(bytecode),
+            nonce: info.nonce,
+        };
+        cache_state.insert_account_with_storage(
+            *address,
+            acc_info,
+            info.storage.iter().map(|(k, v)| (*k, *v)).collect(),
+        );
+    }
+    cache_state
+}
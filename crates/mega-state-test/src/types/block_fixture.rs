@@ -0,0 +1,96 @@
+use alloy_eips::eip4895::Withdrawal;
+use mega_evm::{
+    revm::primitives::{Address, Bytes, B256},
+    MegaHardfork,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::{AccountInfo, MegaEnv};
+
+/// A block fixture in the style of op-node / op-program derivation fixtures: a pre-state and a
+/// sequence of blocks, each built from the payload attributes the rollup node derived for it, with
+/// the expected outcome of executing it.
+///
+/// The blocks are executed in order through `MegaBlockExecutorFactory`, each on top of the state
+/// left by the previous one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BlockFixture {
+    /// The chain id.
+    #[serde(with = "alloy_serde::quantity")]
+    pub chain_id: u64,
+    /// Activation timestamp of each `MegaETH` hardfork. Hardforks not listed are not active.
+    /// Ethereum and Optimism hardforks up to Isthmus are always active.
+    pub hardforks: BTreeMap<MegaHardfork, u64>,
+    /// The state before the first block, ordered by address so serialized fixtures are
+    /// byte-reproducible.
+    pub pre: BTreeMap<Address, AccountInfo>,
+    /// `MegaETH` external-environment inputs (SALT bucket capacities, oracle storage). Absent
+    /// for the empty external environment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mega_env: Option<MegaEnv>,
+    /// The blocks to execute, in order.
+    pub blocks: Vec<FixtureBlock>,
+}
+
+/// A block of a [`BlockFixture`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FixtureBlock {
+    /// The block number.
+    #[serde(with = "alloy_serde::quantity")]
+    pub number: u64,
+    /// The hash of the parent block, served to `BLOCKHASH` and the EIP-2935 history contract.
+    pub parent_hash: B256,
+    /// The base fee, which the rollup node derives from the parent header rather than passing
+    /// it in the payload attributes.
+    #[serde(default, with = "alloy_serde::quantity")]
+    pub base_fee_per_gas: u64,
+    /// The payload attributes of the block.
+    pub attributes: PayloadAttributes,
+    /// The expected outcome of executing the block.
+    pub expected: ExpectedBlock,
+}
+
+/// The payload attributes of a block, with the field names of `OpPayloadAttributes` in the
+/// engine API. Fields that do not affect execution, such as `eip1559Params`, are ignored.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadAttributes {
+    /// The block timestamp.
+    #[serde(with = "alloy_serde::quantity")]
+    pub timestamp: u64,
+    /// The `PREVRANDAO` value.
+    pub prev_randao: B256,
+    /// The block beneficiary.
+    pub suggested_fee_recipient: Address,
+    /// The withdrawals, which must be empty on an OP-stack chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    /// The parent beacon block root, required once Cancun is active.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_beacon_block_root: Option<B256>,
+    /// The EIP-2718 encoded transactions of the block, deposits first.
+    #[serde(default)]
+    pub transactions: Vec<Bytes>,
+    /// Whether the block excludes transactions from the transaction pool.
+    #[serde(default)]
+    pub no_tx_pool: bool,
+    /// The block gas limit.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_limit: u64,
+}
+
+/// The expected outcome of executing a [`FixtureBlock`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ExpectedBlock {
+    /// The state root after the block.
+    pub state_root: B256,
+    /// The receipts root of the block.
+    pub receipts_root: B256,
+    /// The gas used by the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+}
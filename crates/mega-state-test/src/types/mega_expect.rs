@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+
+/// `MegaETH` per-transaction resource expectations (`megaExpect`).
+///
+/// Not part of the standard EEST schema: lets a fixture pin the `MegaETH`
+/// additional-limit counters (data size, KV updates, compute gas, state growth)
+/// and the halt reason of the transaction it encodes. Every field is optional;
+/// only the fields that are present are checked.
+///
+/// The resource counters are only tracked from `MINI_REX` on, so the runner
+/// rejects a fixture that sets them under an earlier spec instead of silently
+/// comparing against zero.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MegaExpect {
+    /// Expected data size usage in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_size: Option<u64>,
+    /// Expected number of KV updates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kv_updates: Option<u64>,
+    /// Expected compute gas usage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compute_gas: Option<u64>,
+    /// Expected state growth (net new accounts and storage slots).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_growth: Option<u64>,
+    /// Expected halt reason.
+    ///
+    /// Matches either the full `Debug` form of the `MegaHaltReason` (e.g.
+    /// `DataLimitExceeded { limit: 10, actual: 12 }`) or only its variant name
+    /// (e.g. `DataLimitExceeded`). A present value requires the transaction to
+    /// halt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub halt_reason: Option<String>,
+}
+
+impl MegaExpect {
+    /// Returns `true` if any of the additional-limit resource counters is set.
+    pub const fn has_resource_expectations(&self) -> bool {
+        self.data_size.is_some() ||
+            self.kv_updates.is_some() ||
+            self.compute_gas.is_some() ||
+            self.state_growth.is_some()
+    }
+
+    /// Returns whether the `got` halt reason (`Debug` form) satisfies `expected`.
+    ///
+    /// `expected` matches either the whole `Debug` string or the variant name
+    /// preceding the first field list.
+    pub fn halt_reason_matches(expected: &str, got: &str) -> bool {
+        let variant = got.split([' ', '{', '(']).next().unwrap_or_default();
+        got == expected || variant == expected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mega_expect_camel_case_roundtrip() {
+        let expect = MegaExpect {
+            data_size: Some(110),
+            kv_updates: Some(1),
+            compute_gas: Some(21_000),
+            state_growth: Some(0),
+            halt_reason: Some("DataLimitExceeded".to_string()),
+        };
+        let value = serde_json::to_value(&expect).expect("serialize");
+        for key in ["dataSize", "kvUpdates", "computeGas", "stateGrowth", "haltReason"] {
+            assert!(value.get(key).is_some(), "expected camelCase {key}");
+        }
+        let back: MegaExpect = serde_json::from_value(value).expect("deserialize");
+        assert_eq!(expect, back);
+    }
+
+    #[test]
+    fn test_mega_expect_rejects_unknown_fields() {
+        // A typo must not silently disable a check.
+        assert!(serde_json::from_str::<MegaExpect>(r#"{"dataSise": 1}"#).is_err());
+    }
+
+    #[test]
+    fn test_mega_expect_resource_expectations() {
+        assert!(!MegaExpect::default().has_resource_expectations());
+        let halt_only = MegaExpect { halt_reason: Some("x".to_string()), ..Default::default() };
+        assert!(!halt_only.has_resource_expectations());
+        let kv = MegaExpect { kv_updates: Some(0), ..Default::default() };
+        assert!(kv.has_resource_expectations());
+    }
+
+    #[test]
+    fn test_halt_reason_matches_full_or_variant() {
+        let got = "DataLimitExceeded { limit: 10, actual: 12 }";
+        assert!(MegaExpect::halt_reason_matches(got, got));
+        assert!(MegaExpect::halt_reason_matches("DataLimitExceeded", got));
+        assert!(!MegaExpect::halt_reason_matches("KVUpdateLimitExceeded", got));
+        assert!(!MegaExpect::halt_reason_matches("DataLimit", got));
+        assert!(MegaExpect::halt_reason_matches("Base", "Base(Base(OutOfGas(Basic)))"));
+    }
+}
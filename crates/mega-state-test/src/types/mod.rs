@@ -8,10 +8,12 @@
 //! test cases, and transaction data used in Ethereum state tests.
 
 mod account_info;
+mod block_fixture;
 mod deserializer;
 mod env;
 mod error;
 mod mega_env;
+mod mega_expect;
 mod spec;
 mod test;
 mod test_authorization;
@@ -20,10 +22,12 @@ mod test_unit;
 mod transaction;
 
 pub use account_info::*;
+pub use block_fixture::*;
 pub use deserializer::*;
 pub use env::*;
 pub use error::*;
 pub use mega_env::*;
+pub use mega_expect::*;
 pub use spec::*;
 pub use test::*;
 pub use test_authorization::*;
@@ -7,7 +7,7 @@ use mega_evm::{
 };
 use serde::{Deserialize, Serialize};
 
-use super::{error::TestError, transaction::TxPartIndices, AccountInfo, TestUnit};
+use super::{error::TestError, transaction::TxPartIndices, AccountInfo, MegaExpect, TestUnit};
 use crate::utils::recover_address;
 
 /// State test indexed state result deserialization.
@@ -56,6 +56,12 @@ pub struct Test {
     /// this value. Absent for pure-Ethereum tests.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mega_status: Option<String>,
+
+    /// `MegaETH`: expected additional-limit usage and halt reason. When
+    /// present, the runner checks each set field against the transaction's
+    /// actual usage. Absent for pure-Ethereum tests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mega_expect: Option<MegaExpect>,
 }
 
 impl Test {
@@ -76,6 +82,7 @@ impl Test {
             txbytes: None,
             mega_gas_used: Some(mega_gas_used),
             mega_status: Some(mega_status),
+            mega_expect: None,
         }
     }
 
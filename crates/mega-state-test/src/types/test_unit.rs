@@ -2,16 +2,14 @@ use mega_evm::{revm::primitives::eip4844, MegaSpecId};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
-use super::{AccountInfo, Env, MegaEnv, SpecName, Test, TransactionParts};
+use super::{pre_state_cache, AccountInfo, Env, MegaEnv, SpecName, Test, TransactionParts};
 use mega_evm::revm::{
     context::{block::BlockEnv, cfg::CfgEnv},
     context_interface::block::calc_excess_blob_gas,
     database::CacheState,
     primitives::{
-        eip4844::TARGET_BLOB_GAS_PER_BLOCK_CANCUN, hardfork::SpecId, keccak256, Address, Bytes,
-        B256,
+        eip4844::TARGET_BLOB_GAS_PER_BLOCK_CANCUN, hardfork::SpecId, Address, Bytes, B256,
     },
-    state::Bytecode,
 };
 
 /// Single test unit struct
@@ -87,24 +85,7 @@ impl TestUnit {
     ///
     /// A [`CacheState`] object containing the pre-state accounts and storages.
     pub fn state(&self) -> CacheState {
-        let mut cache_state = CacheState::new(false);
-        for (address, info) in &self.pre {
-            let code_hash = keccak256(&info.code);
-            let bytecode = Bytecode::new_raw_checked(info.code.clone())
-                .unwrap_or_else(|_| Bytecode::new_legacy(info.code.clone()));
-            let acc_info = mega_evm::revm::state::AccountInfo {
-                balance: info.balance,
-                code_hash,
-                code: Some(bytecode),
-                nonce: info.nonce,
-            };
-            cache_state.insert_account_with_storage(
-                *address,
-                acc_info,
-                info.storage.iter().map(|(k, v)| (*k, *v)).collect(),
-            );
-        }
-        cache_state
+        pre_state_cache(&self.pre)
     }
 
     /// Create a block environment from the test unit.
@@ -0,0 +1,146 @@
+//! End-to-end tests for the block fixture runner.
+//!
+//! A fixture of two blocks, built from payload attributes carrying a deposit and a signed
+//! transfer, is filled with [`fill_block_fixture`] and must then validate with
+//! [`execute_block_fixture`] after a JSON round-trip. Tampering with any expectation must be
+//! reported against the right block.
+
+use mega_evm::{
+    alloy_consensus::{transaction::SignerRecoverable, Sealed, Signed, TxLegacy},
+    alloy_eips::Encodable2718,
+    op_alloy_consensus::TxDeposit,
+    revm::primitives::{address, Address, Bytes, Signature, TxKind, B256, U256},
+    MegaTxEnvelope,
+};
+use serde_json::json;
+use state_test::{
+    block_runner::{execute_block_fixture, fill_block_fixture, BlockFixtureErrorKind},
+    types::BlockFixture,
+};
+
+const DEPOSITOR: Address = address!("1000000000000000000000000000000000000001");
+const RECIPIENT: Address = address!("2000000000000000000000000000000000000002");
+const FEE_RECIPIENT: Address = address!("3000000000000000000000000000000000000003");
+
+fn deposit(nonce: u64) -> Bytes {
+    let tx = TxDeposit {
+        source_hash: B256::with_last_byte(nonce as u8),
+        from: DEPOSITOR,
+        to: TxKind::Call(RECIPIENT),
+        mint: 0,
+        value: U256::from(1_000),
+        gas_limit: 1_000_000,
+        is_system_transaction: false,
+        input: Bytes::new(),
+    };
+    MegaTxEnvelope::Deposit(Sealed::new(tx)).encoded_2718().into()
+}
+
+fn transfer(nonce: u64) -> MegaTxEnvelope {
+    let tx = TxLegacy {
+        chain_id: Some(6342),
+        nonce,
+        gas_price: 1_000,
+        gas_limit: 1_000_000,
+        to: TxKind::Call(RECIPIENT),
+        value: U256::from(7),
+        input: Bytes::new(),
+    };
+    MegaTxEnvelope::Legacy(Signed::new_unhashed(tx, Signature::test_signature()))
+}
+
+fn fixture() -> BlockFixture {
+    let sender = transfer(0).recover_signer().expect("recoverable signature");
+    let funded =
+        json!({ "balance": "0xde0b6b3a7640000", "code": "0x", "nonce": "0x0", "storage": {} });
+    let block = |number: u64, transactions: Vec<Bytes>| {
+        json!({
+            "number": format!("{number:#x}"),
+            "parentHash": B256::with_last_byte(number as u8),
+            "baseFeePerGas": "0x3e8",
+            "attributes": {
+                "timestamp": format!("{:#x}", number * 2),
+                "prevRandao": B256::with_last_byte(0xaa),
+                "suggestedFeeRecipient": FEE_RECIPIENT,
+                "withdrawals": [],
+                "parentBeaconBlockRoot": B256::ZERO,
+                "transactions": transactions,
+                "noTxPool": false,
+                "gasLimit": "0x1c9c380",
+                "eip1559Params": "0x0000000000000000"
+            },
+            "expected": { "stateRoot": B256::ZERO, "receiptsRoot": B256::ZERO, "gasUsed": "0x0" }
+        })
+    };
+    serde_json::from_value(json!({
+        "chainId": "0x18c6",
+        "hardforks": { "MiniRex": 0, "Rex": 0, "Rex1": 0, "Rex2": 0, "Rex3": 0, "Rex4": 0 },
+        "pre": { DEPOSITOR.to_string(): funded, sender.to_string(): funded },
+        "blocks": [
+            block(1, vec![deposit(1), transfer(0).encoded_2718().into()]),
+            block(2, vec![deposit(2), transfer(1).encoded_2718().into()]),
+        ],
+    }))
+    .expect("valid fixture")
+}
+
+fn filled_fixture() -> BlockFixture {
+    let mut fixture = fixture();
+    fill_block_fixture(&mut fixture).expect("fixture executes");
+    fixture
+}
+
+#[test]
+fn test_filled_fixture_validates_after_roundtrip() {
+    let fixture = filled_fixture();
+    for block in &fixture.blocks {
+        assert_ne!(block.expected.state_root, B256::ZERO);
+        assert_ne!(block.expected.receipts_root, B256::ZERO);
+        assert!(block.expected.gas_used > 0);
+    }
+    assert_ne!(fixture.blocks[0].expected.state_root, fixture.blocks[1].expected.state_root);
+
+    let json = serde_json::to_string(&fixture).unwrap();
+    let reloaded: BlockFixture = serde_json::from_str(&json).unwrap();
+    assert_eq!(reloaded, fixture);
+    execute_block_fixture(&reloaded).expect("filled fixture validates");
+}
+
+#[test]
+fn test_mismatches_are_reported_against_their_block() {
+    let filled = filled_fixture();
+
+    let mut fixture = filled.clone();
+    fixture.blocks[1].expected.state_root = B256::repeat_byte(1);
+    let err = execute_block_fixture(&fixture).expect_err("state root mismatch");
+    assert_eq!(err.block, 2);
+    assert!(matches!(err.kind, BlockFixtureErrorKind::StateRootMismatch { .. }), "{err}");
+
+    let mut fixture = filled.clone();
+    fixture.blocks[0].expected.receipts_root = B256::repeat_byte(1);
+    let err = execute_block_fixture(&fixture).expect_err("receipts root mismatch");
+    assert_eq!(err.block, 1);
+    assert!(matches!(err.kind, BlockFixtureErrorKind::ReceiptsRootMismatch { .. }), "{err}");
+
+    let mut fixture = filled;
+    fixture.blocks[0].expected.gas_used += 1;
+    let err = execute_block_fixture(&fixture).expect_err("gas used mismatch");
+    assert!(matches!(err.kind, BlockFixtureErrorKind::GasUsedMismatch { .. }), "{err}");
+}
+
+#[test]
+fn test_invalid_blocks_are_rejected() {
+    let mut fixture = fixture();
+    fixture.blocks[0].attributes.transactions.push(Bytes::from_static(&[0x7f, 0x01]));
+    let err = execute_block_fixture(&fixture).expect_err("undecodable transaction");
+    assert!(
+        matches!(err.kind, BlockFixtureErrorKind::InvalidTransaction { index: 2, .. }),
+        "{err}"
+    );
+
+    let mut fixture = self::fixture();
+    fixture.blocks[1].attributes.transactions[1] = transfer(5).encoded_2718().into();
+    let err = execute_block_fixture(&fixture).expect_err("nonce too high");
+    assert_eq!(err.block, 2);
+    assert!(matches!(err.kind, BlockFixtureErrorKind::Execution(_)), "{err}");
+}
@@ -122,6 +122,36 @@ fn test_fill_computes_post_for_empty_fixture() {
     execute_test_suite(&path, &elapsed, false, false).expect("filled fixture self-validates");
 }
 
+#[test]
+fn test_fill_covers_every_index_combination_and_existing_spec() {
+    // Two data variants x one gas limit x two values: four post entries per spec.
+    let unit = sample_unit_json()
+        .replace(r#""data": ["0x"]"#, r#""data": ["0x", "0x01"]"#)
+        .replace(r#""value": ["0x3e8"]"#, r#""value": ["0x3e8", "0x0"]"#)
+        .replace(r#""post": {}"#, r#""post": { "Rex4": [], "Rex5": [] }"#);
+    let suite_json = format!("{{\"fill_matrix\": {unit}}}");
+    let dir = std::env::temp_dir().join("mega_evme_dump_roundtrip");
+    std::fs::create_dir_all(&dir).expect("mkdir");
+    let path = dir.join("fill_matrix.json");
+    std::fs::write(&path, &suite_json).expect("write fixture");
+
+    // No override: every spec keyed in the (empty) post is refilled.
+    fill_test_suite(&path, None, false).expect("fill");
+    let suite: TestSuite =
+        serde_json::from_str(&std::fs::read_to_string(&path).expect("read")).expect("parse");
+    let filled = &suite.0["fill_matrix"];
+    assert_eq!(filled.post.keys().copied().collect::<Vec<_>>(), [SpecName::Rex4, SpecName::Rex5]);
+    for tests in filled.post.values() {
+        let mut indexes: Vec<_> =
+            tests.iter().map(|t| (t.indexes.data, t.indexes.gas, t.indexes.value)).collect();
+        indexes.sort_unstable();
+        assert_eq!(indexes, [(0, 0, 0), (0, 0, 1), (1, 0, 0), (1, 0, 1)]);
+    }
+
+    let elapsed = Arc::new(Mutex::new(Duration::ZERO));
+    execute_test_suite(&path, &elapsed, false, false).expect("filled fixture self-validates");
+}
+
 #[test]
 fn test_fill_refuses_existing_post_without_force() {
     let (json, _) = dump_fixture_json();
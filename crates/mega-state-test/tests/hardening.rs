@@ -10,12 +10,15 @@ use std::{
     time::Duration,
 };
 
+use regex::Regex;
 use state_test::{
+    filter::TestFilter,
+    report::{CaseStatus, RunReport},
     runner::{
-        bench_test_suite, execute_test_suite, execute_unit_collect, fill_test_suite, run,
-        TestError, TestErrorKind,
+        bench_test_suite, execute_test_suite, execute_test_suite_filtered, execute_unit_collect,
+        fill_test_suite, run, run_with_report, RunOptions, TestError, TestErrorKind,
     },
-    types::{SpecName, TestUnit},
+    types::{SpecName, TestUnit, TxPartIndices},
 };
 
 /// Minimal valid unit JSON: a funded sender sends a legacy transaction to a
@@ -245,6 +248,37 @@ fn run_returns_ok_when_all_pass() {
     run(vec![path], true, false, false, false).expect("passing suite returns Ok");
 }
 
+// The run report records every executed file, including failures under
+// `keep_going`, so `--report`/`--summary` can be written after a failing run.
+#[test]
+fn run_with_report_records_pass_and_fail() {
+    let passing = write_suite("report_pass.json", &unit_json("0x"));
+    fill_test_suite(&passing, Some(SpecName::Rex5), false).expect("fill");
+    let mut unit = unit_json("0x");
+    unit["post"] = serde_json::json!({ "Rex5": [dummy_post_entry()] });
+    let failing = write_suite("report_fail.json", &unit);
+
+    let mut report = RunReport::default();
+    let options = RunOptions {
+        single_thread: true,
+        print_outcome: true,
+        keep_going: true,
+        ..Default::default()
+    };
+    let err = run_with_report(vec![passing.clone(), failing.clone()], options, &mut report)
+        .expect_err("failing file must fail the run");
+    assert!(matches!(err.kind, TestErrorKind::TestsFailed { failed: 1, total: 2 }));
+    assert_eq!(report.counts(), (1, 1, 0));
+    let failed = report.cases.iter().find(|c| c.path == failing.display().to_string()).unwrap();
+    match &failed.status {
+        CaseStatus::Failed { unit, message } => {
+            assert_eq!(unit, "hardening_test");
+            assert!(message.contains("mismatch"), "{message}");
+        }
+        other => panic!("expected a failure, got {other:?}"),
+    }
+}
+
 /// Recipient code `PUSH1 0x0f; BLOCKHASH; PUSH1 0x00; SSTORE`: stores the hash
 /// of block 15 (current block is 0x10 = 16) into storage slot 0, making the
 /// post-state root depend on the BLOCKHASH answer.
@@ -346,3 +380,35 @@ fn fill_preserves_unknown_unit_fields() {
         "unknown unit fields must survive a fill rewrite"
     );
 }
+
+// Cases rejected by a `TestFilter` are never executed: a defect in a filtered-out
+// `post` entry, spec or unit cannot fail the suite.
+#[test]
+fn filtered_out_cases_are_not_executed() {
+    let mut unit = unit_json("0x");
+    let mut entry = dummy_post_entry();
+    entry["indexes"]["data"] = serde_json::json!(5);
+    unit["post"] = serde_json::json!({ "Rex5": [entry] });
+    let path = write_suite("filtered_out_oob_index.json", &unit);
+    let elapsed = Arc::new(Mutex::new(Duration::ZERO));
+    let run_with =
+        |filter: &TestFilter| execute_test_suite_filtered(&path, &elapsed, false, true, filter);
+
+    expect_fixture_error(run_with(&TestFilter::default()), "out of bounds");
+    run_with(&TestFilter {
+        index: Some(TxPartIndices { data: 0, gas: 0, value: 0 }),
+        ..Default::default()
+    })
+    .expect("entry filtered out by index");
+    run_with(&TestFilter { fork: Some(SpecName::Rex4), ..Default::default() })
+        .expect("entry filtered out by fork");
+    run_with(&TestFilter { name: Some(Regex::new("^other_").unwrap()), ..Default::default() })
+        .expect("unit filtered out by name");
+    expect_fixture_error(
+        run_with(&TestFilter {
+            name: Some(Regex::new("hardening").unwrap()),
+            ..Default::default()
+        }),
+        "out of bounds",
+    );
+}
@@ -5,11 +5,13 @@
 
 use clap::Parser;
 use state_test::{
+    filter::{parse_fork, parse_indices, parse_name_pattern, Shard, TestFilter},
+    report::RunReport,
     runner::{
-        bench_test_suite, fill_test_suite, find_all_json_tests, run, TestError, TestErrorKind,
-        UnitBench,
+        bench_test_suite, fill_test_suite, find_all_json_tests, run_with_report, RunOptions,
+        TestError, TestErrorKind, UnitBench,
     },
-    types::SpecName,
+    types::{SpecName, TxPartIndices},
 };
 use std::{path::PathBuf, str::FromStr};
 
@@ -27,8 +29,11 @@ pub struct Cmd {
     #[arg(required = true, num_args = 1..)]
     paths: Vec<PathBuf>,
     /// Run tests in a single thread
-    #[arg(short = 's', long)]
+    #[arg(short = 's', long, conflicts_with = "jobs")]
     single_thread: bool,
+    /// Number of worker threads (default: the available parallelism)
+    #[arg(short = 'j', long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: Option<u16>,
     /// Output results in JSON format
     ///
     /// It will stop second run of evm on failure.
@@ -64,13 +69,41 @@ pub struct Cmd {
     ///
     /// The offline analog of `--dump-fixture`'s post-fill: makes a fixture that
     /// has no `post` (a hand-built or prestate-snapshot case) self-validating.
-    /// Use `--bench-spec` to choose the spec when the fixture has no `post` yet.
+    /// Every `data`/`gasLimit`/`value` combination gets its own `post` entry.
+    /// Use `--bench-spec` to choose the spec; without it, every spec already keyed
+    /// in the fixture's `post` is filled.
     /// Refuses fixtures that already have a `post` unless `--force` is set.
     #[arg(long, conflicts_with_all = ["bench", "bench_runs", "bench_warmup"])]
     fill: bool,
     /// Overwrite an existing non-empty `post` when filling with `--fill`.
     #[arg(long, requires = "fill")]
     force: bool,
+    /// Only run test units whose name matches this regex.
+    #[arg(long, value_name = "REGEX")]
+    filter: Option<String>,
+    /// Only run `post` entries under this fork, spelled as in the fixture (e.g. `Cancun`, `Rex5`).
+    #[arg(long, value_name = "NAME", value_parser = parse_fork)]
+    fork: Option<SpecName>,
+    /// Only run the `post` entry with these `data:gas:value` indices (e.g. `0:1:0`).
+    #[arg(long, value_name = "D:G:V", value_parser = parse_indices)]
+    index: Option<TxPartIndices>,
+    /// Only run the N-th of M equal slices of the test files (1-based, e.g. `2/4`).
+    ///
+    /// Files are sorted before slicing, so every machine of a CI matrix derives
+    /// the same partition.
+    #[arg(long, value_name = "N/M")]
+    shard: Option<Shard>,
+    /// Write a `JUnit` XML report of the run to this path.
+    ///
+    /// Written even when tests fail, with one test case per fixture file.
+    #[arg(long, value_name = "PATH")]
+    report: Option<PathBuf>,
+    /// Write a JSON summary of the run to this path.
+    ///
+    /// Holds pass/fail/skip counts, the duration of every fixture file and the
+    /// error (with its got/expected diff) of every failure.
+    #[arg(long, value_name = "PATH")]
+    summary: Option<PathBuf>,
 }
 
 impl Cmd {
@@ -82,6 +115,15 @@ impl Cmd {
         if self.bench {
             return self.run_bench();
         }
+        let mut report = RunReport::default();
+        let result = self.run_validate(&mut report);
+        self.write_reports(&report)?;
+        result
+    }
+
+    /// Validate every fixture under the given paths, recording outcomes in `report`.
+    fn run_validate(&self, report: &mut RunReport) -> Result<(), TestError> {
+        let filter = self.test_filter()?;
         for path in &self.paths {
             if !path.exists() {
                 return Err(TestError {
@@ -102,11 +144,64 @@ impl Cmd {
                 });
             }
 
-            run(test_files, self.single_thread, self.json, self.json_outcome, self.keep_going)?
+            let test_files = match self.shard {
+                Some(shard) => shard.select(test_files),
+                None => test_files,
+            };
+            if test_files.is_empty() {
+                println!("No test files in this shard, skipping.");
+                continue;
+            }
+
+            let options = RunOptions {
+                single_thread: self.single_thread,
+                trace: self.json,
+                print_outcome: self.json_outcome,
+                keep_going: self.keep_going,
+                jobs: self.jobs.map(usize::from),
+                filter: filter.clone(),
+            };
+            run_with_report(test_files, options, report)?
+        }
+        Ok(())
+    }
+
+    /// Write the `--report` and `--summary` files, if requested.
+    fn write_reports(&self, report: &RunReport) -> Result<(), TestError> {
+        let write = |path: &PathBuf, contents: String| {
+            std::fs::write(path, contents).map_err(|e| TestError {
+                name: "report".to_string(),
+                path: path.display().to_string(),
+                kind: TestErrorKind::FixtureError(format!("write: {e}")),
+            })
+        };
+        if let Some(path) = &self.report {
+            write(path, report.to_junit_xml())?;
+        }
+        if let Some(path) = &self.summary {
+            let summary =
+                serde_json::to_string_pretty(&report.to_json()).expect("serialize summary");
+            write(path, summary)?;
         }
         Ok(())
     }
 
+    /// Build the [`TestFilter`] from `--filter`, `--fork` and `--index`.
+    fn test_filter(&self) -> Result<TestFilter, TestError> {
+        let name = self
+            .filter
+            .as_deref()
+            .map(|pattern| {
+                parse_name_pattern(pattern).map_err(|msg| TestError {
+                    name: "filter".to_string(),
+                    path: pattern.to_string(),
+                    kind: TestErrorKind::FixtureError(msg),
+                })
+            })
+            .transpose()?;
+        Ok(TestFilter { name, fork: self.fork, index: self.index })
+    }
+
     /// Parse `--bench-spec` into a [`SpecName`], if given.
     fn resolve_spec(&self) -> Result<Option<SpecName>, TestError> {
         self.bench_spec
@@ -273,4 +368,44 @@ mod tests {
             "error should be actionable: {err}"
         );
     }
+
+    #[test]
+    fn test_filter_flags_parse() {
+        let cmd = Cmd::parse_from([
+            "state-test",
+            "fixtures",
+            "--filter",
+            "^sstore_",
+            "--fork",
+            "Rex5",
+            "--index",
+            "1:0:2",
+            "--shard",
+            "2/3",
+        ]);
+        let filter = cmd.test_filter().expect("valid filter");
+        assert!(filter.matches_name("sstore_cold"));
+        assert!(!filter.matches_name("sload_cold"));
+        assert_eq!(filter.fork, Some(SpecName::Rex5));
+        assert_eq!(filter.index, Some(TxPartIndices { data: 1, gas: 0, value: 2 }));
+        assert_eq!(cmd.shard, Some(Shard { index: 2, count: 3 }));
+    }
+
+    #[test]
+    fn test_jobs_flag() {
+        let cmd = Cmd::parse_from(["state-test", "fixtures", "-j", "4"]);
+        assert_eq!(cmd.jobs, Some(4));
+        assert!(Cmd::try_parse_from(["state-test", "fixtures", "--jobs", "0"]).is_err());
+        assert!(Cmd::try_parse_from(["state-test", "fixtures", "-j", "2", "-s"]).is_err());
+    }
+
+    #[test]
+    fn test_filter_flags_reject_invalid_values() {
+        for args in [["--fork", "FutureFork9000"], ["--index", "1:2"], ["--shard", "4/3"]] {
+            let argv = ["state-test", "fixtures", args[0], args[1]];
+            assert!(Cmd::try_parse_from(argv).is_err(), "{args:?} must be rejected");
+        }
+        let cmd = Cmd::parse_from(["state-test", "fixtures", "--filter", "(unclosed"]);
+        assert!(cmd.test_filter().is_err());
+    }
 }
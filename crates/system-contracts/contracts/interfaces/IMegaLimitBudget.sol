@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+/// @title IMegaLimitBudget
+/// @notice Interface for querying the remaining resource budgets of the current call.
+/// @dev Served at the MegaLimitControl address; the call is intercepted by MegaETH EVM.
+/// @dev Not intercepted (the on-chain fallback reverts with NotIntercepted) before Rex6.
+interface IMegaLimitBudget {
+    /// @notice The call was not intercepted by the EVM (called on unsupported network).
+    error NotIntercepted();
+    /// @notice The call carries non-zero transferred ETH for a view/control method.
+    error NonZeroTransfer();
+
+    /// @notice Returns the remaining budget of every resource limit for the current call.
+    /// @dev Each value is the caller's per-frame remaining budget, capped by the transaction-level remaining.
+    /// @return computeGas The remaining compute gas, same as IMegaLimitControl.remainingComputeGas.
+    /// @return dataSize The remaining data size, in bytes.
+    /// @return kvUpdates The remaining number of KV updates.
+    /// @return stateGrowth The remaining number of net new accounts and storage slots.
+    function remainingBudgets()
+        external
+        view
+        returns (uint64 computeGas, uint64 dataSize, uint64 kvUpdates, uint64 stateGrowth);
+}
@@ -61,6 +61,8 @@ pub mod limit_control {
     include!("generated/limit_control_artifacts.rs");
 
     alloy_sol_types::sol!("contracts/interfaces/IMegaLimitControl.sol");
+
+    alloy_sol_types::sol!("contracts/interfaces/IMegaLimitBudget.sol");
 }
 
 /// `SequencerRegistry` contract bytecode and interface.